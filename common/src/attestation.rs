@@ -0,0 +1,207 @@
+//! Creating in-toto attestations
+//!
+//! An attestation is an in-toto statement, wrapped in a signed DSSE envelope. See:
+//! <https://github.com/in-toto/attestation/tree/main/spec/v1> and
+//! <https://github.com/secure-systems-lab/dsse>.
+
+use crate::{signing::Signing, validate::ValidationOptions};
+use anyhow::Context;
+use base64::{prelude::BASE64_STANDARD, Engine};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use time::OffsetDateTime;
+
+/// The type of an in-toto statement
+pub const STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+
+/// The payload type of an in-toto statement, in a DSSE envelope
+pub const PAYLOAD_TYPE: &str = "application/vnd.in-toto+json";
+
+/// An in-toto statement
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Statement<P> {
+    #[serde(rename = "_type")]
+    pub r#type: &'static str,
+    pub subject: Vec<Subject>,
+    pub predicate_type: String,
+    pub predicate: P,
+}
+
+impl<P> Statement<P> {
+    pub fn new(subject: Vec<Subject>, predicate_type: impl Into<String>, predicate: P) -> Self {
+        Self {
+            r#type: STATEMENT_TYPE,
+            subject,
+            predicate_type: predicate_type.into(),
+            predicate,
+        }
+    }
+}
+
+/// The subject of a statement
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Subject {
+    pub name: String,
+    pub digest: BTreeMap<String, String>,
+}
+
+impl Subject {
+    /// Create a subject, using the SHA-256 digest of the data
+    pub fn sha256(name: impl Into<String>, data: &[u8]) -> Self {
+        let digest = format!("{:x}", Sha256::digest(data));
+        Self {
+            name: name.into(),
+            digest: BTreeMap::from([("sha256".to_string(), digest)]),
+        }
+    }
+}
+
+/// A DSSE envelope
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Envelope {
+    pub payload_type: String,
+    /// The base64 encoded payload
+    pub payload: String,
+    pub signatures: Vec<Signature>,
+}
+
+/// A signature of a DSSE envelope
+#[derive(Clone, Debug, Serialize)]
+pub struct Signature {
+    pub keyid: String,
+    /// The base64 encoded (binary OpenPGP) signature
+    pub sig: String,
+}
+
+impl Envelope {
+    /// Serialize and sign a statement
+    pub fn sign<P: Serialize>(statement: &Statement<P>, signing: &Signing) -> anyhow::Result<Self> {
+        let payload = serde_json::to_vec(statement)?;
+        let (fingerprint, sig) = signing.sign_detached_binary(&pae(PAYLOAD_TYPE, &payload))?;
+
+        Ok(Self {
+            payload_type: PAYLOAD_TYPE.to_string(),
+            payload: BASE64_STANDARD.encode(&payload),
+            signatures: vec![Signature {
+                keyid: fingerprint.to_hex(),
+                sig: BASE64_STANDARD.encode(sig),
+            }],
+        })
+    }
+}
+
+/// The DSSE pre-authentication encoding, which is the data actually being signed
+pub fn pae(payload_type: &str, payload: &[u8]) -> Vec<u8> {
+    let mut result = format!(
+        "DSSEv1 {} {payload_type} {} ",
+        payload_type.len(),
+        payload.len()
+    )
+    .into_bytes();
+    result.extend_from_slice(payload);
+    result
+}
+
+/// Create a manifest of all files in a directory, in the format of `sha256sum`.
+///
+/// Files are listed by their path relative to the directory, in a stable order. Hidden files and
+/// directories, as well as the excluded files, are not part of the manifest. Excluded files which
+/// don't exist are ignored.
+pub fn manifest(base: &Path, exclude: &[PathBuf]) -> anyhow::Result<String> {
+    let base = base
+        .canonicalize()
+        .with_context(|| format!("Resolving: {}", base.display()))?;
+    let exclude = exclude
+        .iter()
+        .filter_map(|path| path.canonicalize().ok())
+        .collect::<Vec<_>>();
+
+    let mut files = BTreeMap::new();
+    collect(&base, &base, &exclude, &mut files)?;
+
+    Ok(files
+        .into_iter()
+        .map(|(path, digest)| format!("{digest}  {path}\n"))
+        .collect())
+}
+
+fn collect(
+    base: &Path,
+    dir: &Path,
+    exclude: &[PathBuf],
+    files: &mut BTreeMap<String, String>,
+) -> anyhow::Result<()> {
+    for entry in std::fs::read_dir(dir).with_context(|| format!("Reading: {}", dir.display()))? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if entry.file_name().to_string_lossy().starts_with('.') || exclude.contains(&path) {
+            continue;
+        }
+
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect(base, &path, exclude, files)?;
+        } else if file_type.is_file() {
+            let data =
+                std::fs::read(&path).with_context(|| format!("Reading: {}", path.display()))?;
+            let name = path
+                .strip_prefix(base)?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.insert(name, format!("{:x}", Sha256::digest(&data)));
+        }
+    }
+
+    Ok(())
+}
+
+/// The predicate type of a mirror attestation
+pub const MIRROR_PREDICATE_TYPE: &str = "https://github.com/ctron/csaf-walker/mirror/v1";
+
+/// The predicate of a mirror attestation, describing how a mirror was created
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorPredicate {
+    /// The source the mirror was created from
+    pub source: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_on: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub finished_on: OffsetDateTime,
+    pub validation_policy: ValidationPolicy,
+    pub tool: Tool,
+}
+
+/// The validation policy applied to the mirrored documents
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationPolicy {
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub policy_date: Option<OffsetDateTime>,
+    pub strict_tls: bool,
+    pub lenient_digests: bool,
+}
+
+impl From<&ValidationOptions> for ValidationPolicy {
+    fn from(value: &ValidationOptions) -> Self {
+        Self {
+            policy_date: value.validation_date.map(OffsetDateTime::from),
+            strict_tls: value.strict_tls,
+            lenient_digests: value.lenient_digests,
+        }
+    }
+}
+
+/// The tool creating the mirror
+#[derive(Clone, Debug, Serialize)]
+pub struct Tool {
+    pub name: String,
+    pub version: String,
+}
@@ -0,0 +1,84 @@
+//! Limiting the memory used by documents in flight
+
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::sync::Notify;
+
+/// A budget of bytes, shared by all documents which have been retrieved, but not yet processed.
+///
+/// Before retrieving a document, a task waits until the budget is not exhausted. Once retrieved,
+/// the size of the document is reserved until the document was processed. As the size of a
+/// document is only known after retrieving it, the budget may be exceeded by the documents
+/// retrieved concurrently.
+#[derive(Debug)]
+pub struct MemoryBudget {
+    limit: usize,
+    used: Mutex<usize>,
+    notify: Notify,
+}
+
+impl MemoryBudget {
+    /// Create a new budget, with a limit in bytes.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            used: Mutex::new(0),
+            notify: Notify::new(),
+        }
+    }
+
+    /// The limit, in bytes
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// The number of bytes currently reserved
+    pub fn used(&self) -> usize {
+        *self.used.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Wait until the budget is not exhausted.
+    pub async fn ready(&self) {
+        loop {
+            // register before checking, so that we don't miss a release
+            let notified = self.notify.notified();
+
+            let used = self.used();
+            if used < self.limit {
+                break;
+            }
+
+            log::debug!("Memory budget exhausted ({used} of {} bytes)", self.limit);
+            notified.await;
+        }
+    }
+
+    /// Reserve the size of a document, until the returned reservation is dropped.
+    pub fn reserve(self: &Arc<Self>, bytes: usize) -> Reservation {
+        let mut used = self.used.lock().unwrap_or_else(PoisonError::into_inner);
+        *used = used.saturating_add(bytes);
+
+        Reservation {
+            budget: self.clone(),
+            bytes,
+        }
+    }
+}
+
+/// A reservation of bytes, returned to the budget when dropped
+#[derive(Debug)]
+pub struct Reservation {
+    budget: Arc<MemoryBudget>,
+    bytes: usize,
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        let mut used = self
+            .budget
+            .used
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        *used = used.saturating_sub(self.bytes);
+        self.budget.notify.notify_waiters();
+    }
+}
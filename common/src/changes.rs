@@ -1,7 +1,14 @@
 //! Changes based on the `changes.csv` file.
 
-use crate::fetcher::{self, Fetcher};
-use time::OffsetDateTime;
+use crate::{
+    fetcher::{self, Fetcher},
+    store::write_replace_sync,
+};
+use anyhow::Context;
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::Path;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
 use url::{ParseError, Url};
 
 #[derive(Debug, thiserror::Error)]
@@ -38,6 +45,11 @@ impl ChangeSource {
 
         log::info!("Found 'changes.csv', processing data");
 
+        Self::parse(&changes)
+    }
+
+    /// Parse the content of a `changes.csv` file.
+    pub fn parse(changes: &str) -> Result<Self, Error> {
         let reader = csv::ReaderBuilder::new()
             .delimiter(b',')
             .has_headers(false)
@@ -52,3 +64,112 @@ impl ChangeSource {
         Ok(Self { entries })
     }
 }
+
+/// A local `changes.csv` file, and its companion `index.txt`, maintained while storing documents.
+#[derive(Clone, Debug, Default)]
+pub struct ChangesFile {
+    entries: BTreeMap<String, OffsetDateTime>,
+    dirty: bool,
+}
+
+impl ChangesFile {
+    /// Load an existing `changes.csv` from the directory, starting empty if there is none.
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        let path = dir.join("changes.csv");
+        let file = match std::fs::File::open(&path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(err).with_context(|| format!("Failed to open: {}", path.display()))
+            }
+        };
+
+        let entries = csv::ReaderBuilder::new()
+            .delimiter(b',')
+            .has_headers(false)
+            .from_reader(file)
+            .into_deserialize::<ChangeEntry>()
+            .map(|entry| entry.map(|entry| (entry.file, entry.timestamp)))
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("Failed to parse: {}", path.display()))?;
+
+        Ok(Self {
+            entries,
+            dirty: false,
+        })
+    }
+
+    /// Add or update an entry.
+    pub fn insert(&mut self, file: impl Into<String>, timestamp: OffsetDateTime) {
+        self.entries.insert(file.into(), timestamp);
+        self.dirty = true;
+    }
+
+    /// Remove an entry, returning if it was present.
+    pub fn remove(&mut self, file: &str) -> bool {
+        let removed = self.entries.remove(file).is_some();
+        self.dirty |= removed;
+        removed
+    }
+
+    /// Replace the prefix of all entries starting with it, returning the number of changed entries.
+    pub fn rename_prefix(&mut self, from: &str, to: &str) -> usize {
+        let renamed = self
+            .entries
+            .keys()
+            .filter(|file| file.starts_with(from))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for file in &renamed {
+            if let Some(timestamp) = self.entries.remove(file) {
+                self.entries
+                    .insert(format!("{to}{}", &file[from.len()..]), timestamp);
+            }
+        }
+
+        self.dirty |= !renamed.is_empty();
+        renamed.len()
+    }
+
+    /// Write `changes.csv` and `index.txt` to the directory, if there were changes.
+    ///
+    /// Entries in `changes.csv` are ordered by timestamp, latest first. The files are replaced
+    /// atomically, so that readers never see a partially written file.
+    pub fn write(&mut self, dir: &Path) -> anyhow::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let mut entries = self.entries.iter().collect::<Vec<_>>();
+        entries.sort_by(|(a_file, a_ts), (b_file, b_ts)| {
+            b_ts.cmp(a_ts).then_with(|| a_file.cmp(b_file))
+        });
+
+        let mut writer = csv::WriterBuilder::new()
+            .has_headers(false)
+            .quote_style(csv::QuoteStyle::Always)
+            .from_writer(vec![]);
+        for (file, timestamp) in &entries {
+            writer.write_record([file.as_str(), timestamp.format(&Rfc3339)?.as_str()])?;
+        }
+        let changes = writer.into_inner()?;
+
+        let index = self.entries.keys().fold(String::new(), |mut index, file| {
+            index.push_str(file);
+            index.push('\n');
+            index
+        });
+
+        replace(&dir.join("changes.csv"), &changes)?;
+        replace(&dir.join("index.txt"), index.as_bytes())?;
+
+        self.dirty = false;
+
+        Ok(())
+    }
+}
+
+fn replace(path: &Path, data: &[u8]) -> anyhow::Result<()> {
+    write_replace_sync(path, data).with_context(|| format!("Failed to write: {}", path.display()))
+}
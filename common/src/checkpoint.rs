@@ -0,0 +1,192 @@
+//! Resuming an interrupted walk within a distribution
+//!
+//! For each distribution (identified by a key, like its URL), the number of entries which have
+//! been processed from the start of its index is recorded, together with the URL of the last of
+//! those entries. When walking again, those entries are skipped, as long as the index still
+//! has the same entry at that position. Otherwise, the distribution is processed from the start.
+
+use crate::store::write_replace_sync;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, ErrorKind};
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+
+/// Number of recorded entries after which the checkpoint gets stored
+const DEFAULT_INTERVAL: usize = 100;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointState {
+    /// The position, by distribution
+    #[serde(default)]
+    pub distributions: BTreeMap<String, Position>,
+}
+
+/// The position in the index of a distribution
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+    /// Number of entries, from the start of the index, which have been processed
+    pub processed: usize,
+    /// The URL of the last processed entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    state: CheckpointState,
+    /// Processed entries after the position, waiting for the entries before them
+    pending: BTreeMap<String, BTreeMap<usize, String>>,
+    /// Number of recorded entries since storing the state
+    unstored: usize,
+}
+
+/// A checkpoint, stored in a file
+#[derive(Debug)]
+pub struct Checkpoint {
+    file: PathBuf,
+    interval: usize,
+    inner: Mutex<Inner>,
+}
+
+impl Checkpoint {
+    /// Load the checkpoint from a file, starting fresh if the file doesn't exist.
+    pub fn load(file: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let file = file.into();
+
+        let state = match File::open(&file) {
+            Ok(reader) => serde_json::from_reader(BufReader::new(reader))?,
+            Err(err) if err.kind() == ErrorKind::NotFound => CheckpointState::default(),
+            Err(err) => return Err(err.into()),
+        };
+
+        for (key, position) in &state.distributions {
+            log::info!(
+                "Checkpoint - {key}: processed {} entries",
+                position.processed
+            );
+        }
+
+        Ok(Self {
+            file,
+            interval: DEFAULT_INTERVAL,
+            inner: Mutex::new(Inner {
+                state,
+                ..Default::default()
+            }),
+        })
+    }
+
+    /// Set the number of recorded entries after which the checkpoint gets stored.
+    pub fn with_interval(mut self, interval: usize) -> Self {
+        self.interval = interval.max(1);
+        self
+    }
+
+    /// Start processing the index of a distribution, returning the number of entries to skip.
+    ///
+    /// If the entry at the recorded position doesn't match, the index changed, and the
+    /// distribution will be processed from the start.
+    pub fn resume<'a>(&self, key: &str, urls: impl IntoIterator<Item = &'a str>) -> usize {
+        let mut inner = self.lock();
+        inner.pending.remove(key);
+
+        let Some(position) = inner
+            .state
+            .distributions
+            .get(key)
+            .filter(|position| position.processed > 0)
+        else {
+            return 0;
+        };
+
+        if urls.into_iter().nth(position.processed - 1) == position.last.as_deref() {
+            log::info!(
+                "Resuming {key}: skipping {} processed entries",
+                position.processed
+            );
+            return position.processed;
+        }
+
+        log::warn!("Index changed since the checkpoint, processing from the start: {key}");
+        inner.state.distributions.remove(key);
+        0
+    }
+
+    /// Record that the entry at position `n` of the index of a distribution has been processed.
+    ///
+    /// Entries may be recorded out of order. The position only moves past entries once all
+    /// entries before them have been processed.
+    pub fn record(&self, key: &str, n: usize, url: &str) {
+        let mut inner = self.lock();
+
+        let Inner {
+            state,
+            pending,
+            unstored,
+        } = &mut *inner;
+
+        let position = state.distributions.entry(key.to_string()).or_default();
+        let pending = pending.entry(key.to_string()).or_default();
+        pending.insert(n, url.to_string());
+
+        while let Some(url) = pending.remove(&position.processed) {
+            position.processed += 1;
+            position.last = Some(url);
+        }
+
+        *unstored += 1;
+        if *unstored >= self.interval {
+            *unstored = 0;
+            if let Err(err) = self.write(state) {
+                log::warn!("Failed to store checkpoint: {err}");
+            }
+        }
+    }
+
+    /// Store the current state of the checkpoint.
+    pub fn store(&self) -> anyhow::Result<()> {
+        let mut inner = self.lock();
+        inner.unstored = 0;
+        self.write(&inner.state)
+    }
+
+    /// Remove the checkpoint, as the walk was completed.
+    pub fn clear(&self) -> anyhow::Result<()> {
+        let mut inner = self.lock();
+        *inner = Inner::default();
+
+        match std::fs::remove_file(&self.file) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Finish a walk, clearing the checkpoint if the walk was complete, storing it otherwise.
+    pub fn finish(&self, complete: bool) {
+        let result = match complete {
+            true => self.clear(),
+            false => self.store(),
+        };
+        if let Err(err) = result {
+            log::warn!(
+                "Failed to update checkpoint ({}): {err}",
+                self.file.display()
+            );
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self, state: &CheckpointState) -> anyhow::Result<()> {
+        let data = serde_json::to_vec(state)?;
+
+        write_replace_sync(&self.file, &data)?;
+
+        Ok(())
+    }
+}
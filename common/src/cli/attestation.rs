@@ -0,0 +1,57 @@
+use crate::attestation::{manifest, Envelope, Statement, Subject};
+use crate::cli::signing::append;
+use crate::signing::Signing;
+use anyhow::Context;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, clap::Parser)]
+#[command(next_help_heading = "Attestation")]
+pub struct AttestationArguments {
+    /// Write a signed in-toto attestation of the mirror to this file. A manifest of the mirrored files (`.manifest`), which is the subject of the attestation, is stored next to it.
+    #[arg(long, env = "CSAF_WALKER_ATTESTATION", requires = "attestation_key")]
+    pub attestation: Option<PathBuf>,
+
+    /// OpenPGP keyring with the (unencrypted) secret key to sign the attestation with.
+    #[arg(long, env = "CSAF_WALKER_ATTESTATION_KEY", requires = "attestation")]
+    pub attestation_key: Option<PathBuf>,
+}
+
+impl AttestationArguments {
+    /// Create the attestation of a directory, if requested.
+    pub fn apply<P: Serialize>(
+        &self,
+        base: &Path,
+        predicate_type: &str,
+        predicate: impl FnOnce() -> P,
+    ) -> anyhow::Result<()> {
+        let (Some(path), Some(key)) = (&self.attestation, &self.attestation_key) else {
+            return Ok(());
+        };
+
+        let signing = Signing::from_files([key])?;
+
+        let manifest_path = append(path, "manifest");
+        let manifest = manifest(base, &[path.clone(), manifest_path.clone()])?;
+        let name = manifest_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let statement = Statement::new(
+            vec![Subject::sha256(name, manifest.as_bytes())],
+            predicate_type,
+            predicate(),
+        );
+        let envelope = Envelope::sign(&statement, &signing)?;
+
+        std::fs::write(&manifest_path, manifest)
+            .with_context(|| format!("Failed to write: {}", manifest_path.display()))?;
+        std::fs::write(path, serde_json::to_vec_pretty(&envelope)?)
+            .with_context(|| format!("Failed to write: {}", path.display()))?;
+
+        log::info!("Created attestation: {}", path.display());
+
+        Ok(())
+    }
+}
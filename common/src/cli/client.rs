@@ -1,15 +1,112 @@
-use crate::fetcher::{Fetcher, FetcherOptions};
+use crate::{
+    fetcher::{CircuitBreaker, DnsOptions, Fetcher, FetcherOptions, IpPreference, RedirectPolicy},
+    run::run_id,
+};
+use std::net::IpAddr;
 
-#[derive(Debug, clap::Parser)]
+#[derive(Clone, Debug, clap::Parser)]
 #[command(next_help_heading = "Client")]
 pub struct ClientArguments {
     /// Per-request HTTP timeout, in humantime duration format.
-    #[arg(short, long, default_value = "5s")]
+    #[arg(short, long, env = "CSAF_WALKER_TIMEOUT", default_value = "5s")]
     pub timeout: humantime::Duration,
 
     /// Per-request retries count
-    #[arg(short, long, default_value = "5")]
+    #[arg(short, long, env = "CSAF_WALKER_RETRIES", default_value = "5")]
     pub retries: usize,
+
+    /// Time to wait before retrying a failed request, in humantime duration format.
+    #[arg(long, env = "CSAF_WALKER_RETRY_DELAY", default_value = "0s")]
+    pub retry_delay: humantime::Duration,
+
+    /// Maximum number of HTTP redirects to follow
+    #[arg(long, env = "CSAF_WALKER_MAX_REDIRECTS", default_value = "10")]
+    pub max_redirects: usize,
+
+    /// Only follow redirects to the same host
+    #[arg(
+        long,
+        env = "CSAF_WALKER_SAME_HOST_REDIRECTS",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub same_host_redirects: bool,
+
+    /// Deny redirects from HTTPS to HTTP
+    #[arg(
+        long,
+        env = "CSAF_WALKER_DENY_REDIRECT_DOWNGRADE",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub deny_redirect_downgrade: bool,
+
+    /// Minimum TLS version to accept (`1.2` or `1.3`)
+    #[arg(long, env = "CSAF_WALKER_MIN_TLS_VERSION", value_parser = parse_tls_version)]
+    pub min_tls_version: Option<reqwest::tls::Version>,
+
+    /// Only allow HTTPS requests
+    #[arg(
+        long,
+        env = "CSAF_WALKER_HTTPS_ONLY",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub https_only: bool,
+
+    /// DNS servers to use, instead of the system resolver
+    #[arg(long, env = "CSAF_WALKER_DNS_SERVER")]
+    pub dns_server: Vec<IpAddr>,
+
+    /// Resolve a host to a static address, in the form of `HOST=IP`
+    #[arg(long, env = "CSAF_WALKER_RESOLVE", value_parser = parse_resolve)]
+    pub resolve: Vec<(String, IpAddr)>,
+
+    /// Preference of the IP address family
+    #[arg(long, env = "CSAF_WALKER_IP_PREFERENCE", value_enum, default_value_t = IpPreference::Any)]
+    pub ip_preference: IpPreference,
+
+    /// Respect the `robots.txt` rules and crawl delays of hosts
+    #[arg(
+        long,
+        env = "CSAF_WALKER_RESPECT_ROBOTS_TXT",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub respect_robots_txt: bool,
+
+    /// Skip requests to a host after that many consecutive failures (timeouts, connection
+    /// errors, 5xx or 429 responses), instead of waiting for each of them to fail.
+    #[arg(long, env = "CSAF_WALKER_CIRCUIT_BREAKER_THRESHOLD")]
+    pub circuit_breaker_threshold: Option<usize>,
+
+    /// Time after which a single request is sent to probe a host again, in humantime duration format.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_CIRCUIT_BREAKER_COOLDOWN",
+        default_value = "1m"
+    )]
+    pub circuit_breaker_cooldown: humantime::Duration,
+
+    /// The user agent to send, instead of the default one. The correlation ID of the run is sent as the `X-Correlation-ID` header in any case.
+    #[arg(long, env = "CSAF_WALKER_USER_AGENT")]
+    pub user_agent: Option<String>,
+}
+
+fn parse_resolve(value: &str) -> Result<(String, IpAddr), String> {
+    let (host, addr) = value
+        .split_once('=')
+        .ok_or_else(|| "Expected the format of 'HOST=IP'".to_string())?;
+    let addr = addr
+        .parse()
+        .map_err(|err| format!("Invalid IP address '{addr}': {err}"))?;
+    Ok((host.to_string(), addr))
+}
+
+fn parse_tls_version(value: &str) -> Result<reqwest::tls::Version, String> {
+    match value {
+        "1.0" => Ok(reqwest::tls::Version::TLS_1_0),
+        "1.1" => Ok(reqwest::tls::Version::TLS_1_1),
+        "1.2" => Ok(reqwest::tls::Version::TLS_1_2),
+        "1.3" => Ok(reqwest::tls::Version::TLS_1_3),
+        _ => Err(format!("Unsupported TLS version: {value}")),
+    }
 }
 
 impl From<ClientArguments> for FetcherOptions {
@@ -17,6 +114,27 @@ impl From<ClientArguments> for FetcherOptions {
         FetcherOptions {
             timeout: value.timeout.into(),
             retries: value.retries,
+            retry_delay: value.retry_delay.into(),
+            clock: Default::default(),
+            redirects: RedirectPolicy::new()
+                .max_hops(value.max_redirects)
+                .same_host_only(value.same_host_redirects)
+                .deny_downgrade(value.deny_redirect_downgrade),
+            min_tls_version: value.min_tls_version,
+            https_only: value.https_only,
+            dns: DnsOptions::new()
+                .nameservers(value.dns_server)
+                .extend_overrides(value.resolve)
+                .preference(value.ip_preference),
+            robots: value.respect_robots_txt,
+            circuit_breaker: value.circuit_breaker_threshold.map(|threshold| {
+                CircuitBreaker::new()
+                    .threshold(threshold)
+                    .cooldown(value.circuit_breaker_cooldown)
+            }),
+            adaptive: None,
+            user_agent: value.user_agent,
+            correlation_id: Some(run_id().clone()),
         }
     }
 }
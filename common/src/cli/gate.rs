@@ -0,0 +1,151 @@
+/// The level of findings which fails a command
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum FailOn {
+    /// Fail on errors
+    Errors,
+    /// Fail on errors and warnings
+    Warnings,
+    /// Never fail because of findings
+    #[default]
+    None,
+}
+
+#[derive(Debug, clap::Parser)]
+#[command(next_help_heading = "Failing")]
+pub struct GateArguments {
+    /// Fail the command (with a non-zero exit code) if findings of this level, or a more severe one, were found.
+    #[arg(long, env = "CSAF_WALKER_FAIL_ON", value_enum, default_value_t = FailOn::None)]
+    pub fail_on: FailOn,
+
+    /// Fail the command if more than this number of errors were found. Defaults to zero when failing on errors.
+    #[arg(long, env = "CSAF_WALKER_MAX_ERRORS")]
+    pub max_errors: Option<usize>,
+
+    /// Fail the command if more than this number of warnings were found. Defaults to zero when failing on warnings.
+    #[arg(long, env = "CSAF_WALKER_MAX_WARNINGS")]
+    pub max_warnings: Option<usize>,
+}
+
+impl GateArguments {
+    /// Use these limits, unless they were set explicitly.
+    pub fn with_limits(mut self, max_errors: Option<usize>, max_warnings: Option<usize>) -> Self {
+        self.max_errors = self.max_errors.or(max_errors);
+        self.max_warnings = self.max_warnings.or(max_warnings);
+        self
+    }
+
+    /// The maximum number of errors, if limited
+    pub fn error_limit(&self) -> Option<usize> {
+        self.max_errors.or(match self.fail_on {
+            FailOn::Errors | FailOn::Warnings => Some(0),
+            FailOn::None => None,
+        })
+    }
+
+    /// The maximum number of warnings, if limited
+    pub fn warning_limit(&self) -> Option<usize> {
+        self.max_warnings.or(match self.fail_on {
+            FailOn::Warnings => Some(0),
+            FailOn::Errors | FailOn::None => None,
+        })
+    }
+
+    /// Ensure no limit on warnings was requested, for commands which don't report warnings.
+    pub fn errors_only(&self) -> anyhow::Result<()> {
+        if self.fail_on == FailOn::Warnings {
+            anyhow::bail!(
+                "This command doesn't report warnings, failing on warnings is not supported"
+            );
+        }
+
+        if self.max_warnings.is_some() {
+            anyhow::bail!("This command doesn't report warnings, limiting them is not supported");
+        }
+
+        Ok(())
+    }
+
+    /// Check the number of findings, failing if a limit was exceeded.
+    pub fn check(&self, errors: usize, warnings: usize) -> anyhow::Result<()> {
+        if let Some(limit) = self.error_limit().filter(|limit| errors > *limit) {
+            anyhow::bail!("Found {errors} error(s), exceeding the limit of {limit}");
+        }
+
+        if let Some(limit) = self.warning_limit().filter(|limit| warnings > *limit) {
+            anyhow::bail!("Found {warnings} warning(s), exceeding the limit of {limit}");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn gate(
+        fail_on: FailOn,
+        max_errors: Option<usize>,
+        max_warnings: Option<usize>,
+    ) -> GateArguments {
+        GateArguments {
+            fail_on,
+            max_errors,
+            max_warnings,
+        }
+    }
+
+    #[test]
+    fn test_default_limits() {
+        let none = gate(FailOn::None, None, None);
+        assert_eq!(none.error_limit(), None);
+        assert_eq!(none.warning_limit(), None);
+        assert!(none.check(10, 10).is_ok());
+
+        let errors = gate(FailOn::Errors, None, None);
+        assert_eq!(errors.error_limit(), Some(0));
+        assert_eq!(errors.warning_limit(), None);
+        assert!(errors.check(0, 10).is_ok());
+        assert!(errors.check(1, 0).is_err());
+
+        let warnings = gate(FailOn::Warnings, None, None);
+        assert_eq!(warnings.error_limit(), Some(0));
+        assert_eq!(warnings.warning_limit(), Some(0));
+        assert!(warnings.check(0, 0).is_ok());
+        assert!(warnings.check(0, 1).is_err());
+        assert!(warnings.check(1, 0).is_err());
+    }
+
+    #[test]
+    fn test_explicit_limits() {
+        let errors = gate(FailOn::Errors, Some(2), None);
+        assert_eq!(errors.error_limit(), Some(2));
+        assert!(errors.check(2, 0).is_ok());
+        assert!(errors.check(3, 0).is_err());
+
+        let warnings = gate(FailOn::Warnings, None, Some(5));
+        assert_eq!(warnings.error_limit(), Some(0));
+        assert_eq!(warnings.warning_limit(), Some(5));
+        assert!(warnings.check(0, 5).is_ok());
+        assert!(warnings.check(0, 6).is_err());
+
+        // limits apply without a level too
+        let none = gate(FailOn::None, Some(1), Some(1));
+        assert!(none.check(1, 1).is_ok());
+        assert!(none.check(2, 0).is_err());
+        assert!(none.check(0, 2).is_err());
+
+        // defaults only fill in what's not set explicitly
+        let limited = gate(FailOn::Errors, Some(1), None).with_limits(Some(3), Some(4));
+        assert_eq!(limited.error_limit(), Some(1));
+        assert_eq!(limited.warning_limit(), Some(4));
+    }
+
+    #[test]
+    fn test_errors_only() {
+        assert!(gate(FailOn::None, None, None).errors_only().is_ok());
+        assert!(gate(FailOn::Errors, Some(1), None).errors_only().is_ok());
+        assert!(gate(FailOn::Warnings, None, None).errors_only().is_err());
+        assert!(gate(FailOn::Errors, None, Some(1)).errors_only().is_err());
+    }
+}
@@ -0,0 +1,43 @@
+use crate::lock::{Lock, LockOptions};
+use std::path::Path;
+
+#[derive(Debug, clap::Parser)]
+#[command(next_help_heading = "Locking")]
+pub struct LockArguments {
+    /// Maximum time to wait for a concurrent run to release its lock, in humantime duration format. By default, waits forever.
+    #[arg(long, env = "CSAF_WALKER_WAIT_FOR_LOCK", conflicts_with = "no_wait")]
+    pub wait_for_lock: Option<humantime::Duration>,
+
+    /// Fail immediately if a concurrent run holds the lock.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_NO_WAIT",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub no_wait: bool,
+}
+
+impl From<&LockArguments> for LockOptions {
+    fn from(value: &LockArguments) -> Self {
+        LockOptions::new()
+            .wait(!value.no_wait)
+            .timeout(value.wait_for_lock.map(Into::into))
+    }
+}
+
+impl LockArguments {
+    /// Acquire the lock of a directory (creating it, if necessary), and then the locks of the
+    /// files, in order.
+    pub async fn lock<'a>(
+        &self,
+        dir: &Path,
+        files: impl IntoIterator<Item = &'a Path>,
+    ) -> anyhow::Result<Vec<Lock>> {
+        let options = LockOptions::from(self);
+        let mut locks = vec![Lock::acquire_dir(dir, &options).await?];
+        for file in files {
+            locks.push(Lock::acquire(file, &options).await?);
+        }
+        Ok(locks)
+    }
+}
@@ -1,4 +1,7 @@
-use crate::progress::Progress;
+use crate::{
+    progress::Progress,
+    run::{run_id, set_run_id, CorrelationId},
+};
 use env_logger::Builder;
 use indicatif::MultiProgress;
 use indicatif_log_bridge::LogWrapper;
@@ -26,10 +29,19 @@ pub struct Logging {
     /// Provide a RUST_LOG filter, conflicts with --verbose and --quiet
     #[arg(long, global = true, conflicts_with_all(["verbose", "quiet"]), env("RUST_LOG"))]
     pub log: Option<String>,
+
+    /// The correlation ID of the run, sent with each request and added to logs and reports. Defaults to a random UUID.
+    #[arg(long, global = true, env = "CSAF_WALKER_CORRELATION_ID")]
+    pub correlation_id: Option<CorrelationId>,
 }
 
 impl Logging {
     pub fn init(self, app_modules: &[&'static str]) -> Progress {
+        if let Some(correlation_id) = self.correlation_id {
+            // nothing used the ID so far, so this can't fail
+            let _ = set_run_id(correlation_id);
+        }
+
         // init logging
 
         let mut builder = Builder::new();
@@ -41,9 +53,21 @@ impl Logging {
             None => {
                 // remove timestamps
 
-                if !self.log_timestamps {
-                    builder.format(|buf, record| writeln!(buf, "{}", record.args()));
-                }
+                match self.log_timestamps {
+                    // add the correlation ID, as timestamped logs are likely to be collected
+                    true => builder.format(|buf, record| {
+                        writeln!(
+                            buf,
+                            "[{} {:<5} {} {}] {}",
+                            buf.timestamp(),
+                            record.level(),
+                            record.target(),
+                            run_id(),
+                            record.args()
+                        )
+                    }),
+                    false => builder.format(|buf, record| writeln!(buf, "{}", record.args())),
+                };
 
                 // for app modules
                 let app_modules = |builder: &mut Builder, level| {
@@ -86,7 +110,7 @@ impl Logging {
 
         // init the progress meter
 
-        match self.quiet | self.no_progress {
+        let progress = match self.quiet | self.no_progress {
             true => {
                 builder.init();
                 Progress::default()
@@ -102,6 +126,10 @@ impl Logging {
 
                 multi.into()
             }
-        }
+        };
+
+        log::info!("Correlation ID: {}", run_id());
+
+        progress
     }
 }
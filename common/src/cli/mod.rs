@@ -1,7 +1,16 @@
 //! Command line helpers
 pub mod client;
+pub mod gate;
+pub mod lock;
+pub mod notify;
+pub mod parse;
+pub mod report;
 pub mod runner;
 
+#[cfg(feature = "openpgp")]
+pub mod attestation;
+#[cfg(feature = "openpgp")]
+pub mod signing;
 #[cfg(feature = "openpgp")]
 pub mod validation;
 
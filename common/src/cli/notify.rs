@@ -0,0 +1,17 @@
+use crate::notify::SummaryNotifier;
+use url::Url;
+
+#[derive(Debug, clap::Parser)]
+#[command(next_help_heading = "Notification")]
+pub struct NotifyArguments {
+    /// Send a single summary of the run (as JSON, with a `text` field) to this webhook URL.
+    #[arg(long, env = "CSAF_WALKER_NOTIFY_WEBHOOK")]
+    pub notify_webhook: Option<Url>,
+}
+
+impl NotifyArguments {
+    /// The notifier, if configured
+    pub fn notifier(&self) -> Option<SummaryNotifier> {
+        self.notify_webhook.clone().map(SummaryNotifier::new)
+    }
+}
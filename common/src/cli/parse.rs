@@ -0,0 +1,100 @@
+//! Parsing the command line
+//!
+//! Lists can be provided through environment variables as comma separated values. Splitting
+//! values at commas must not apply to the command line, as URLs and paths may contain commas.
+//! Clap doesn't make that difference, so the command line is parsed twice: once to find the
+//! lists taken from environment variables, and once more, splitting only those.
+
+use clap::{parser::ValueSource, ArgAction, ArgMatches, Command, Parser};
+use std::ffi::OsString;
+
+/// Parse the command line, like [`Parser::parse`], exiting on errors.
+pub fn parse<T: Parser>() -> T {
+    try_parse_from(std::env::args_os()).unwrap_or_else(|err| err.exit())
+}
+
+/// Parse the arguments, like [`Parser::try_parse_from`].
+///
+/// Values of lists taken from an environment variable are split at commas, values provided as
+/// arguments are not.
+pub fn try_parse_from<T, I, A>(args: I) -> Result<T, clap::Error>
+where
+    T: Parser,
+    I: IntoIterator<Item = A>,
+    A: Into<OsString>,
+{
+    let args = args.into_iter().map(Into::into).collect::<Vec<_>>();
+
+    let mut command = T::command();
+    let matches = command.clone().try_get_matches_from(&args)?;
+    split_env_lists(&mut command, &matches);
+
+    let mut matches = command.try_get_matches_from(args)?;
+    T::from_arg_matches_mut(&mut matches)
+}
+
+/// Split the values of lists at commas, if they were taken from an environment variable.
+fn split_env_lists(command: &mut Command, matches: &ArgMatches) {
+    let lists = command
+        .get_arguments()
+        .filter(|arg| arg.get_env().is_some() && matches!(arg.get_action(), ArgAction::Append))
+        .filter(|arg| matches.value_source(arg.get_id().as_str()) == Some(ValueSource::EnvVariable))
+        .map(|arg| arg.get_id().clone())
+        .collect::<Vec<_>>();
+
+    for id in lists {
+        *command = std::mem::take(command).mut_arg(id, |arg| arg.value_delimiter(','));
+    }
+
+    if let Some((name, matches)) = matches.subcommand() {
+        if let Some(subcommand) = command.find_subcommand_mut(name) {
+            split_env_lists(subcommand, matches);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, clap::Parser)]
+    struct Cli {
+        #[arg(long, env = "CSAF_WALKER_TEST_PARSE_KEY")]
+        key: Vec<String>,
+
+        #[command(subcommand)]
+        command: Option<Sub>,
+    }
+
+    #[derive(Debug, clap::Subcommand)]
+    enum Sub {
+        Run {
+            #[arg(long, env = "CSAF_WALKER_TEST_PARSE_PATH")]
+            path: Vec<String>,
+        },
+    }
+
+    #[test]
+    fn test_comma_in_argument() {
+        std::env::set_var("CSAF_WALKER_TEST_PARSE_KEY", "a,b");
+
+        let cli: Cli =
+            try_parse_from(["test", "--key", "https://example.com/key,1"]).expect("must parse");
+        assert_eq!(cli.key, vec!["https://example.com/key,1"]);
+
+        let cli: Cli = try_parse_from(["test"]).expect("must parse");
+        assert_eq!(cli.key, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_subcommand() {
+        std::env::set_var("CSAF_WALKER_TEST_PARSE_PATH", "a,b");
+
+        let cli: Cli = try_parse_from(["test", "run"]).expect("must parse");
+        assert!(matches!(cli.command, Some(Sub::Run { path }) if path == ["a", "b"]));
+
+        let cli: Cli = try_parse_from(["test", "run", "--path", "some,file", "--path", "other"])
+            .expect("must parse");
+        assert!(matches!(cli.command, Some(Sub::Run { path }) if path == ["some,file", "other"]));
+    }
+}
@@ -0,0 +1,43 @@
+use crate::report::{Bootstrap, ReportOptions, Theme};
+use anyhow::Context;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, clap::Parser)]
+#[command(next_help_heading = "Report styling")]
+pub struct ReportStyleArguments {
+    /// The theme of the HTML report. The compact theme is embedded into the report, not requiring any external resources.
+    #[arg(long, env = "CSAF_WALKER_REPORT_THEME", value_enum, default_value_t = Theme::Bootstrap)]
+    pub report_theme: Theme,
+
+    /// A local Bootstrap CSS file, embedded into the report instead of referencing the CDN.
+    #[arg(long, env = "CSAF_WALKER_BOOTSTRAP_CSS")]
+    pub bootstrap_css: Option<PathBuf>,
+
+    /// A local Bootstrap JS bundle, embedded into the report instead of referencing the CDN.
+    #[arg(long, env = "CSAF_WALKER_BOOTSTRAP_JS", requires = "bootstrap_css")]
+    pub bootstrap_js: Option<PathBuf>,
+}
+
+impl ReportStyleArguments {
+    /// Create the report options, reading the resources to embed.
+    pub fn report_options(&self) -> anyhow::Result<ReportOptions> {
+        let bootstrap = match &self.bootstrap_css {
+            Some(css) => Bootstrap::Inline {
+                css: read(css)?,
+                js: self.bootstrap_js.as_deref().map(read).transpose()?,
+            },
+            None => Bootstrap::Default,
+        };
+
+        Ok(ReportOptions {
+            bootstrap,
+            theme: self.report_theme,
+            clock: Default::default(),
+        })
+    }
+}
+
+fn read(path: &Path) -> anyhow::Result<String> {
+    std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read report resource: {}", path.display()))
+}
@@ -1,7 +1,93 @@
+use crate::budget::MemoryBudget;
+use crate::checkpoint::Checkpoint;
+use crate::clock::Clock;
+use crate::concurrency::{AdaptiveConcurrency, AdaptiveOptions};
+use crate::offload::Offload;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 #[derive(Debug, clap::Parser)]
 #[command(next_help_heading = "Runner")]
 pub struct RunnerArguments {
     /// Number of workers, too many parallel requests might make you violate request rates. NOTE: A number of zero will spawn an unlimited amount of workers.
-    #[arg(short, long, default_value = "1")]
+    #[arg(short, long, env = "CSAF_WALKER_WORKERS", default_value = "1")]
     pub workers: usize,
+
+    /// Stop processing new documents after this duration, in humantime duration format. Documents already being processed will be completed.
+    #[arg(long, env = "CSAF_WALKER_MAX_DURATION")]
+    pub max_duration: Option<humantime::Duration>,
+
+    /// Adapt the number of parallel workers to the latency and error responses of the provider, using the number of workers as the maximum.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_ADAPTIVE_CONCURRENCY",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub adaptive_concurrency: bool,
+
+    /// Limit the memory used by documents which have been retrieved, but not yet processed, in MiB. Retrieving new documents waits while the budget is exhausted.
+    #[arg(long, env = "CSAF_WALKER_MEMORY_BUDGET")]
+    pub memory_budget: Option<usize>,
+
+    /// Number of threads for CPU intensive work, like calculating digests and verifying signatures. Defaults to the number of available CPUs.
+    #[arg(long, env = "CSAF_WALKER_CPU_THREADS")]
+    pub cpu_threads: Option<usize>,
+
+    /// A file recording the progress within each distribution. An interrupted walk skips the documents which have already been processed when run again. The file is removed once a walk completed.
+    #[arg(long, env = "CSAF_WALKER_CHECKPOINT_FILE")]
+    pub checkpoint_file: Option<PathBuf>,
+
+    /// Run as if the current time was this timestamp (RFC 3339), advancing from there. This is used for the since-state and the date of reports, e.g. for reproducible runs, or for simulating a sync in the past.
+    #[arg(long, env = "CSAF_WALKER_NOW")]
+    pub now: Option<humantime::Timestamp>,
+}
+
+impl RunnerArguments {
+    /// The deadline of the walk, starting now.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.max_duration
+            .map(|max_duration| Instant::now() + Duration::from(max_duration))
+    }
+
+    /// Create the clock of the run, starting now.
+    pub fn clock(&self) -> Clock {
+        match self.now.as_deref() {
+            Some(now) => Clock::starting_at(*now),
+            None => Clock::system(),
+        }
+    }
+
+    /// Create a new adaptive concurrency controller, if requested.
+    pub fn adaptive(&self) -> Option<Arc<AdaptiveConcurrency>> {
+        let max = match self.workers {
+            0 => usize::MAX,
+            n => n,
+        };
+
+        self.adaptive_concurrency
+            .then(|| Arc::new(AdaptiveConcurrency::new(AdaptiveOptions::new().max(max))))
+    }
+
+    /// Create a new memory budget, if requested.
+    pub fn memory_budget(&self) -> Option<Arc<MemoryBudget>> {
+        self.memory_budget
+            .map(|mib| Arc::new(MemoryBudget::new(mib.saturating_mul(1024 * 1024))))
+    }
+
+    /// Load the checkpoint, if requested.
+    pub fn checkpoint(&self) -> anyhow::Result<Option<Arc<Checkpoint>>> {
+        self.checkpoint_file
+            .as_ref()
+            .map(|file| Checkpoint::load(file).map(Arc::new))
+            .transpose()
+    }
+
+    /// Create a new instance for offloading CPU intensive work from the async runtime.
+    pub fn offload(&self) -> Offload {
+        match self.cpu_threads {
+            Some(threads) => Offload::new(threads),
+            None => Offload::default(),
+        }
+    }
 }
@@ -0,0 +1,61 @@
+use crate::signing::Signing;
+use crate::timestamp::request_timestamp;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+#[derive(Debug, clap::Parser)]
+#[command(next_help_heading = "Signing")]
+pub struct SigningArguments {
+    /// Sign the generated files, using an (unencrypted) secret key of this keyring file. Detached, armored signatures (`.asc`) are stored next to the files.
+    #[arg(long, env = "CSAF_WALKER_SIGN_WITH")]
+    pub sign_with: Option<PathBuf>,
+
+    /// Obtain RFC 3161 timestamps for the generated files (or their signatures, when signing) from this time stamping authority. The responses (`.tsr`) are stored next to the files.
+    #[arg(long, env = "CSAF_WALKER_TIMESTAMP_AUTHORITY")]
+    pub timestamp_authority: Option<Url>,
+}
+
+impl SigningArguments {
+    /// Sign and timestamp files, as requested.
+    pub async fn apply(&self, files: &[PathBuf]) -> anyhow::Result<()> {
+        let signing = self
+            .sign_with
+            .as_ref()
+            .map(|path| Signing::from_files([path]))
+            .transpose()?;
+
+        if signing.is_none() && self.timestamp_authority.is_none() {
+            return Ok(());
+        }
+
+        let client = reqwest::Client::new();
+
+        for file in files {
+            let mut path = file.clone();
+            let mut data = tokio::fs::read(&path).await?;
+
+            if let Some(signing) = &signing {
+                data = signing.sign_detached(&data)?;
+                path = append(&path, "asc");
+                tokio::fs::write(&path, &data).await?;
+                log::info!("Signed: {}", file.display());
+            }
+
+            if let Some(authority) = &self.timestamp_authority {
+                let response = request_timestamp(&client, authority, &data).await?;
+                tokio::fs::write(append(&path, "tsr"), response).await?;
+                log::info!("Timestamped: {}", path.display());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Append an extension to the full file name
+pub(crate) fn append(path: &Path, extension: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(extension);
+    path.into()
+}
@@ -1,35 +1,113 @@
-use crate::validate::ValidationOptions;
+use crate::validate::{
+    policy::ValidationPolicy, v3_signatures_date, ValidationMode, ValidationOptions,
+};
 use flexible_time::timestamp::StartTimestamp;
+use std::path::PathBuf;
 use std::time::SystemTime;
-use time::{Date, Month, UtcOffset};
 
 #[derive(Debug, clap::Parser)]
 #[command(next_help_heading = "Validation")]
 pub struct ValidationArguments {
+    /// A validation policy (YAML) file, covering trust requirements, allowed TLP labels, required checks, thresholds, and allowed client errors. Conflicts with the other validation arguments.
+    #[arg(long, env = "CSAF_WALKER_VALIDATION_POLICY")]
+    pub validation_policy: Option<PathBuf>,
+
     /// OpenPGP policy date.
-    #[arg(long)]
+    #[arg(
+        long,
+        env = "CSAF_WALKER_POLICY_DATE",
+        conflicts_with = "validation_policy"
+    )]
     policy_date: Option<StartTimestamp>,
 
     /// Enable OpenPGP v3 signatures. Conflicts with 'policy_date'.
-    #[arg(short = '3', long = "v3-signatures", conflicts_with = "policy_date")]
+    #[arg(
+        short = '3',
+        long = "v3-signatures",
+        env = "CSAF_WALKER_V3_SIGNATURES",
+        conflicts_with_all = ["policy_date", "validation_policy"],
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
     v3_signatures: bool,
+
+    /// Report documents which were not retrieved using HTTPS with a valid certificate chain.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_STRICT_TLS",
+        conflicts_with = "validation_policy",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    strict_tls: bool,
+
+    /// Only warn about malformed digest files, instead of failing the validation.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_LENIENT_DIGESTS",
+        conflicts_with = "validation_policy",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    lenient_digests: bool,
+
+    /// Warn about provider keys expiring within this duration, in humantime duration format.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_KEY_EXPIRY_WARNING",
+        default_value = "30d",
+        conflicts_with = "validation_policy"
+    )]
+    key_expiry_warning: humantime::Duration,
+
+    /// Require valid signatures of this number of different provider keys, for documents carrying multiple signatures.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_REQUIRED_SIGNERS",
+        default_value_t = 1,
+        conflicts_with = "validation_policy"
+    )]
+    required_signers: usize,
+
+    /// How to handle documents failing the digest or signature checks: reject them, or pass them on, recording the failed checks.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_VALIDATION_MODE",
+        value_enum,
+        default_value_t = ValidationMode::Strict
+    )]
+    validation_mode: ValidationMode,
 }
 
-impl From<ValidationArguments> for ValidationOptions {
-    fn from(value: ValidationArguments) -> Self {
+impl ValidationArguments {
+    /// Load the validation policy, if one was provided.
+    pub fn policy(&self) -> anyhow::Result<Option<ValidationPolicy>> {
+        self.validation_policy
+            .as_ref()
+            .map(ValidationPolicy::load)
+            .transpose()
+    }
+}
+
+impl TryFrom<ValidationArguments> for ValidationOptions {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ValidationArguments) -> Result<Self, Self::Error> {
+        if let Some(policy) = value.policy()? {
+            return Ok(Self::try_from(&policy)?.mode(value.validation_mode));
+        }
+
         let validation_date: Option<SystemTime> = match (value.policy_date, value.v3_signatures) {
-            (_, true) => Some(SystemTime::from(
-                Date::from_calendar_date(2007, Month::January, 1)
-                    .expect("policy date is known to parse")
-                    .midnight()
-                    .assume_offset(UtcOffset::UTC),
-            )),
+            (_, true) => Some(v3_signatures_date()),
             (Some(date), _) => Some(date.into()),
             _ => None,
         };
 
         log::debug!("Policy date: {validation_date:?}");
 
-        Self { validation_date }
+        Ok(Self::new()
+            .validation_date(validation_date)
+            .strict_tls(value.strict_tls)
+            .lenient_digests(value.lenient_digests)
+            .key_expiry_warning(Some(value.key_expiry_warning.into()))
+            .required_signers(value.required_signers)
+            .mode(value.validation_mode))
     }
 }
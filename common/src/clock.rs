@@ -0,0 +1,115 @@
+//! A source of the current time
+//!
+//! Using the system clock directly makes the outcome of a run depend on when it was executed.
+//! Passing a [`Clock`] instead allows running against a different point in time, e.g. for
+//! reproducible tests, or for simulating a sync at some point in the past.
+
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime};
+use time::OffsetDateTime;
+
+/// A source of the current time, defaulting to the system clock
+#[derive(Clone, Debug, Default)]
+pub struct Clock(Inner);
+
+#[derive(Clone, Debug, Default)]
+enum Inner {
+    #[default]
+    System,
+    Shifted {
+        start: SystemTime,
+        origin: Instant,
+    },
+    Manual(Arc<Mutex<SystemTime>>),
+}
+
+impl Clock {
+    /// The system clock
+    pub fn system() -> Self {
+        Self(Inner::System)
+    }
+
+    /// A clock starting at the provided time, advancing at the pace of the system clock.
+    pub fn starting_at(start: SystemTime) -> Self {
+        Self(Inner::Shifted {
+            start,
+            origin: Instant::now(),
+        })
+    }
+
+    /// A clock standing still at the provided time, until it gets advanced.
+    ///
+    /// Clones of the clock share the same time, so advancing one advances all of them.
+    pub fn manual(time: SystemTime) -> Self {
+        Self(Inner::Manual(Arc::new(Mutex::new(time))))
+    }
+
+    /// The current time
+    pub fn now(&self) -> SystemTime {
+        match &self.0 {
+            Inner::System => SystemTime::now(),
+            Inner::Shifted { start, origin } => *start + origin.elapsed(),
+            Inner::Manual(time) => *time.lock().unwrap_or_else(PoisonError::into_inner),
+        }
+    }
+
+    /// The current time, in UTC
+    pub fn now_utc(&self) -> OffsetDateTime {
+        self.now().into()
+    }
+
+    /// Advance a manual clock. Other clocks are not affected.
+    pub fn advance(&self, duration: Duration) {
+        if let Inner::Manual(time) = &self.0 {
+            *time.lock().unwrap_or_else(PoisonError::into_inner) += duration;
+        }
+    }
+
+    /// Wait for the duration to pass.
+    ///
+    /// A manual clock is advanced instead, returning immediately.
+    pub async fn sleep(&self, duration: Duration) {
+        match &self.0 {
+            Inner::Manual(_) => self.advance(duration),
+            _ => tokio::time::sleep(duration).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_util::FutureExt;
+
+    #[test]
+    fn test_manual() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let clock = Clock::manual(start);
+        let other = clock.clone();
+
+        assert_eq!(clock.now(), start);
+
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(other.now(), start + Duration::from_secs(60));
+
+        // doesn't wait for an hour
+        assert!(other
+            .sleep(Duration::from_secs(3600))
+            .now_or_never()
+            .is_some());
+        assert_eq!(clock.now(), start + Duration::from_secs(3660));
+    }
+
+    #[test]
+    fn test_starting_at() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let clock = Clock::starting_at(start);
+
+        assert!(clock.now() >= start);
+        assert!(clock.now() < SystemTime::now());
+
+        // not affected
+        clock.advance(Duration::from_secs(3600));
+        assert!(clock.now() < start + Duration::from_secs(3600));
+    }
+}
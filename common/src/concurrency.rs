@@ -0,0 +1,161 @@
+//! Adaptive concurrency, using an AIMD (additive increase, multiplicative decrease) controller
+
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// Options for the [`AdaptiveConcurrency`] controller
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdaptiveOptions {
+    /// The minimum number of concurrent tasks
+    pub min: usize,
+    /// The maximum number of concurrent tasks
+    pub max: usize,
+    /// Requests taking longer are considered a sign of an overloaded server
+    pub latency_threshold: Duration,
+}
+
+impl Default for AdaptiveOptions {
+    fn default() -> Self {
+        Self {
+            min: 1,
+            max: usize::MAX,
+            latency_threshold: Duration::from_secs(2),
+        }
+    }
+}
+
+impl AdaptiveOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn min(mut self, min: usize) -> Self {
+        self.min = min;
+        self
+    }
+
+    pub fn max(mut self, max: usize) -> Self {
+        self.max = max;
+        self
+    }
+
+    pub fn latency_threshold(mut self, latency_threshold: impl Into<Duration>) -> Self {
+        self.latency_threshold = latency_threshold.into();
+        self
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    limit: f64,
+    in_flight: usize,
+    last_decrease: Option<Instant>,
+}
+
+/// Controlling the number of concurrent tasks, based on the outcome of requests.
+///
+/// The limit grows by one for each "window" of fast and successful requests, and is halved when
+/// requests are slow, or the server signals being overloaded (`429`, `5xx`, timeouts).
+#[derive(Debug)]
+pub struct AdaptiveConcurrency {
+    options: AdaptiveOptions,
+    state: Mutex<State>,
+    notify: Notify,
+}
+
+impl AdaptiveConcurrency {
+    pub fn new(options: AdaptiveOptions) -> Self {
+        let min = options.min.max(1);
+        let options = AdaptiveOptions {
+            min,
+            max: options.max.max(min),
+            ..options
+        };
+
+        Self {
+            state: Mutex::new(State {
+                limit: min as f64,
+                in_flight: 0,
+                last_decrease: None,
+            }),
+            options,
+            notify: Notify::new(),
+        }
+    }
+
+    /// The current limit of concurrent tasks
+    pub fn limit(&self) -> usize {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        Self::current(&state)
+    }
+
+    fn current(state: &State) -> usize {
+        state.limit as usize
+    }
+
+    /// Wait until another task may run.
+    pub async fn acquire(self: Arc<Self>) -> Permit {
+        loop {
+            // register before checking, so that we don't miss a release
+            let notified = self.notify.notified();
+
+            {
+                let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+                if state.in_flight < Self::current(&state) {
+                    state.in_flight += 1;
+                    break;
+                }
+            }
+
+            notified.await;
+        }
+
+        Permit { controller: self }
+    }
+
+    /// Record the outcome of a request.
+    pub fn record(&self, latency: Duration, overloaded: bool) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if overloaded || latency > self.options.latency_threshold {
+            // only decrease once per period, as concurrent requests will report the same problem
+            if state
+                .last_decrease
+                .is_some_and(|last| last.elapsed() < self.options.latency_threshold)
+            {
+                return;
+            }
+
+            state.limit = (state.limit / 2.0).max(self.options.min as f64);
+            state.last_decrease = Some(Instant::now());
+            log::info!("Reducing concurrency to: {}", Self::current(&state));
+        } else {
+            let before = Self::current(&state);
+            state.limit = (state.limit + 1.0 / state.limit).min(self.options.max as f64);
+            if Self::current(&state) > before {
+                log::debug!("Increasing concurrency to: {}", Self::current(&state));
+                self.notify.notify_waiters();
+            }
+        }
+    }
+}
+
+/// A permit to run a task, returned to the controller when dropped
+#[derive(Debug)]
+pub struct Permit {
+    controller: Arc<AdaptiveConcurrency>,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut state = self
+            .controller
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        state.in_flight = state.in_flight.saturating_sub(1);
+        self.controller.notify.notify_waiters();
+    }
+}
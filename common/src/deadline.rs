@@ -0,0 +1,110 @@
+//! Stopping a walk at a deadline
+
+use std::fmt::{Display, Formatter};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime};
+
+/// How far a walk got, before it was stopped by its deadline
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Completion {
+    /// Number of processed documents
+    pub processed: usize,
+    /// Number of discovered documents, which were not processed
+    pub remaining: usize,
+    /// A "since" timestamp, which picks up all remaining documents when walking again.
+    ///
+    /// This is only available if all documents have been discovered before the deadline passed.
+    pub resume_since: Option<SystemTime>,
+}
+
+impl Display for Completion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "processed {}, remaining {}",
+            self.processed, self.remaining
+        )?;
+        if self.resume_since.is_none() {
+            write!(f, " (discovery incomplete)")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    processed: usize,
+    remaining: usize,
+    earliest: Option<SystemTime>,
+    incomplete: bool,
+}
+
+/// Tracking documents of a walk against a deadline
+#[derive(Debug)]
+pub struct Deadline {
+    deadline: Instant,
+    state: Mutex<State>,
+}
+
+impl Deadline {
+    pub fn new(deadline: Instant) -> Self {
+        Self {
+            deadline,
+            state: Default::default(),
+        }
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        Instant::now() >= self.deadline
+    }
+
+    /// Check if a document may still be processed. If not, it is recorded as remaining.
+    pub fn admit(&self, modified: SystemTime) -> bool {
+        let exceeded = self.is_exceeded();
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if exceeded {
+            state.remaining += 1;
+            state.earliest = Some(state.earliest.map_or(modified, |e| e.min(modified)));
+        } else {
+            state.processed += 1;
+        }
+
+        !exceeded
+    }
+
+    /// Record that discovery was stopped, so that not all remaining documents are known.
+    pub fn stop_discovery(&self) {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .incomplete = true;
+    }
+
+    /// Get the completion, if the deadline stopped the walk.
+    pub fn into_completion(self) -> Option<Completion> {
+        let state = self
+            .state
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if state.remaining == 0 && !state.incomplete {
+            return None;
+        }
+
+        let resume_since = match state.incomplete {
+            true => None,
+            // a document is considered unchanged if it was modified at the "since" timestamp,
+            // so we need to start a bit earlier
+            false => state
+                .earliest
+                .and_then(|earliest| earliest.checked_sub(Duration::from_secs(1))),
+        };
+
+        Some(Completion {
+            processed: state.processed,
+            remaining: state.remaining,
+            resume_since,
+        })
+    }
+}
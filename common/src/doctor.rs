@@ -0,0 +1,284 @@
+//! Diagnosing the runtime environment
+//!
+//! Each check results in a [`Diagnosis`], including a hint on how to fix a problem.
+
+use crate::{
+    fetcher::Fetcher,
+    utils::space::{available_space, dir_size, format_bytes},
+};
+use std::error::Error as _;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::time::Duration;
+use time::OffsetDateTime;
+use url::Url;
+
+/// The maximum difference between the local and the server clock, before it's considered wrong
+pub const MAX_CLOCK_SKEW: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Status {
+    Ok,
+    Warning,
+    Failed,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ok => f.write_str("ok"),
+            Self::Warning => f.write_str("warning"),
+            Self::Failed => f.write_str("failed"),
+        }
+    }
+}
+
+/// The outcome of a single check
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnosis {
+    /// The name of the check
+    pub check: &'static str,
+    pub status: Status,
+    pub message: String,
+    /// How to fix the problem, if there is one
+    pub hint: Option<String>,
+}
+
+impl Diagnosis {
+    pub fn ok(check: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            check,
+            status: Status::Ok,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    pub fn warning(
+        check: &'static str,
+        message: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            check,
+            status: Status::Warning,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    pub fn failed(
+        check: &'static str,
+        message: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            check,
+            status: Status::Failed,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Check if extended attributes can be stored in the directory.
+pub fn check_xattrs(dir: &Path) -> Diagnosis {
+    const CHECK: &str = "xattr";
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    {
+        let result = (|| -> anyhow::Result<bool> {
+            std::fs::create_dir_all(dir)?;
+            let file = dir.join(format!(".csaf-walker-doctor-{}", std::process::id()));
+            std::fs::write(&file, b"")?;
+            let result = xattr::set(&file, crate::store::ATTR_ETAG, b"doctor")
+                .and_then(|()| xattr::get(&file, crate::store::ATTR_ETAG));
+            let _ = std::fs::remove_file(&file);
+            Ok(result?.as_deref() == Some(b"doctor".as_slice()))
+        })();
+
+        match result {
+            Ok(true) => Diagnosis::ok(CHECK, format!("Supported by: {}", dir.display())),
+            Ok(false) => Diagnosis::warning(
+                CHECK,
+                format!("Attributes are not retained by: {}", dir.display()),
+                "Use a filesystem supporting user extended attributes, or use --no-xattrs",
+            ),
+            Err(err) => Diagnosis::warning(
+                CHECK,
+                format!("Not supported by {}: {err}", dir.display()),
+                "Use a filesystem supporting user extended attributes (e.g. mount with 'user_xattr'), or use --no-xattrs",
+            ),
+        }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    Diagnosis::ok(
+        CHECK,
+        format!("Not used on this platform: {}", dir.display()),
+    )
+}
+
+/// Check the connection to the URL, including TLS, and the local clock against the server's.
+pub async fn check_connectivity(fetcher: &Fetcher, url: Url) -> Vec<Diagnosis> {
+    let metadata = match fetcher.head(url.clone()).await {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            // the connection itself might be fine, but the server might not support HEAD requests
+            if let Some(status) = err.status() {
+                return vec![Diagnosis::warning(
+                    "connectivity",
+                    format!("{url} responded with: {status}"),
+                    "Use a different probe URL",
+                )];
+            }
+
+            return vec![match is_tls_error(&err) {
+                true => Diagnosis::failed(
+                    "tls",
+                    format!("Failed to establish a trusted connection to {url}: {err:#}"),
+                    "Install the CA certificates of the system (e.g. the 'ca-certificates' package), or point SSL_CERT_FILE to a bundle including the CA of the server",
+                ),
+                false => Diagnosis::failed(
+                    "connectivity",
+                    format!("Failed to connect to {url}: {err:#}"),
+                    "Check the network, DNS, and proxy settings (HTTPS_PROXY), or use --dns-server",
+                ),
+            }];
+        }
+    };
+
+    let mut result = vec![Diagnosis::ok("connectivity", format!("Connected to {url}"))];
+
+    if url.scheme() == "https" {
+        result.push(Diagnosis::ok("tls", "Server certificate is trusted"));
+    }
+
+    result.push(match metadata.server_date {
+        Some(server_date) => check_clock(OffsetDateTime::now_utc(), server_date),
+        None => Diagnosis::warning(
+            "clock",
+            format!("{url} didn't report its date"),
+            "Use a different probe URL to check the clock",
+        ),
+    });
+
+    result
+}
+
+/// Compare the local clock to the date of a server.
+fn check_clock(now: OffsetDateTime, server_date: OffsetDateTime) -> Diagnosis {
+    const CHECK: &str = "clock";
+
+    let skew = (now - server_date).unsigned_abs();
+    if skew > MAX_CLOCK_SKEW {
+        Diagnosis::failed(
+            CHECK,
+            format!(
+                "Local clock differs from the server by {}",
+                humantime::format_duration(Duration::from_secs(skew.as_secs()))
+            ),
+            "Synchronize the system clock (e.g. using NTP), signatures and differential syncs depend on it",
+        )
+    } else {
+        Diagnosis::ok(CHECK, "Local clock matches the server")
+    }
+}
+
+fn is_tls_error(err: &crate::fetcher::Error) -> bool {
+    let mut source = err.source();
+    while let Some(err) = source {
+        let message = err.to_string().to_lowercase();
+        if message.contains("certificate") || message.contains("tls") || message.contains("ssl") {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
+
+/// Check the available space of the filesystem against the expected size of a mirror.
+///
+/// Without an explicit estimate, the size of the existing mirror is used.
+pub fn check_disk_space(dir: &Path, estimated: Option<u64>) -> Diagnosis {
+    const CHECK: &str = "disk space";
+
+    let available = match available_space(dir) {
+        Ok(available) => available,
+        Err(err) => {
+            return Diagnosis::warning(
+                CHECK,
+                format!(
+                    "Unable to check available space of {}: {err}",
+                    dir.display()
+                ),
+                "Ensure the output directory is accessible",
+            )
+        }
+    };
+
+    let estimated = match estimated {
+        Some(estimated) => estimated,
+        None => match dir_size(dir) {
+            Ok(size) => size,
+            Err(err) => {
+                return Diagnosis::warning(
+                    CHECK,
+                    format!("Unable to determine the size of {}: {err}", dir.display()),
+                    "Provide the expected size of the mirror",
+                )
+            }
+        },
+    };
+
+    if available < estimated {
+        Diagnosis::failed(
+            CHECK,
+            format!(
+                "{} available, but the mirror is estimated to need {}",
+                format_bytes(available),
+                format_bytes(estimated)
+            ),
+            "Free up space, use a larger filesystem, or limit the mirror using a retention policy",
+        )
+    } else {
+        Diagnosis::ok(
+            CHECK,
+            format!(
+                "{} available, the mirror is estimated to need {}",
+                format_bytes(available),
+                format_bytes(estimated)
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_clock() {
+        let now = OffsetDateTime::now_utc();
+
+        assert_eq!(
+            check_clock(now, now - Duration::from_secs(30)).status,
+            Status::Ok
+        );
+        assert_eq!(
+            check_clock(now, now + Duration::from_secs(3600)).status,
+            Status::Failed
+        );
+    }
+
+    #[test]
+    fn test_disk_space() {
+        let dir = std::env::temp_dir();
+
+        assert_eq!(check_disk_space(&dir, Some(0)).status, Status::Ok);
+        assert_eq!(
+            check_disk_space(&dir, Some(u64::MAX)).status,
+            Status::Failed
+        );
+    }
+}
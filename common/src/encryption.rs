@@ -0,0 +1,243 @@
+//! Encrypting documents at rest, using OpenPGP
+
+use anyhow::{bail, Context};
+use sequoia_openpgp::{
+    cert::CertParser,
+    crypto::SessionKey,
+    packet::{PKESK, SKESK},
+    parse::{
+        stream::{DecryptionHelper, DecryptorBuilder, MessageStructure, VerificationHelper},
+        Parse,
+    },
+    policy::StandardPolicy,
+    serialize::stream::{Encryptor, LiteralWriter, Message},
+    types::SymmetricAlgorithm,
+    Cert, Fingerprint, KeyHandle,
+};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Load all certificates from a keyring file
+pub(crate) fn load_certs(path: &Path) -> anyhow::Result<Vec<Cert>> {
+    let certs = CertParser::from_file(path)
+        .with_context(|| format!("Failed to open keyring: {}", path.display()))?
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Failed to parse keyring: {}", path.display()))?;
+
+    if certs.is_empty() {
+        bail!("Keyring contains no certificates: {}", path.display());
+    }
+
+    Ok(certs)
+}
+
+/// Parse all certificates from armored data, e.g. from an environment variable
+fn parse_certs(data: &str) -> anyhow::Result<Vec<Cert>> {
+    let certs = CertParser::from_bytes(data.as_bytes())?.collect::<Result<Vec<_>, _>>()?;
+
+    if certs.is_empty() {
+        bail!("No certificates found");
+    }
+
+    Ok(certs)
+}
+
+/// Encrypting documents for a set of recipients
+#[derive(Clone, Debug)]
+pub struct Encryption {
+    recipients: Arc<Vec<Cert>>,
+}
+
+impl PartialEq for Encryption {
+    fn eq(&self, other: &Self) -> bool {
+        fingerprints(&self.recipients) == fingerprints(&other.recipients)
+    }
+}
+
+impl Eq for Encryption {}
+
+impl Encryption {
+    pub fn new(recipients: Vec<Cert>) -> Self {
+        Self {
+            recipients: Arc::new(recipients),
+        }
+    }
+
+    /// Load recipients from keyring files
+    pub fn from_files<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> anyhow::Result<Self> {
+        let mut recipients = vec![];
+        for path in paths {
+            recipients.extend(load_certs(path.as_ref())?);
+        }
+        Ok(Self::new(recipients))
+    }
+
+    /// Load recipients from armored certificates, stored in an environment variable
+    pub fn from_env(name: &str) -> anyhow::Result<Option<Self>> {
+        match std::env::var(name) {
+            Ok(data) => Ok(Some(Self::new(parse_certs(&data).with_context(|| {
+                format!("Failed to parse recipients from '{name}'")
+            })?))),
+            Err(std::env::VarError::NotPresent) => Ok(None),
+            Err(err) => Err(err).with_context(|| format!("Failed to read '{name}'")),
+        }
+    }
+
+    /// Encrypt data for all recipients
+    // `Encryptor` is deprecated in newer versions, in favor of `Encryptor2`, which is not
+    // available in all versions we support.
+    #[allow(deprecated)]
+    pub fn encrypt(&self, data: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let policy = StandardPolicy::new();
+
+        let recipients = self
+            .recipients
+            .iter()
+            .flat_map(|cert| {
+                cert.keys()
+                    .with_policy(&policy, None)
+                    .supported()
+                    .alive()
+                    .revoked(false)
+                    .for_storage_encryption()
+            })
+            .collect::<Vec<_>>();
+
+        if recipients.is_empty() {
+            bail!("No valid encryption key found for any recipient");
+        }
+
+        let mut sink = Vec::with_capacity(data.len());
+        let message = Message::new(&mut sink);
+        let message = Encryptor::for_recipients(message, recipients).build()?;
+        let mut message = LiteralWriter::new(message).build()?;
+        message.write_all(data)?;
+        message.finalize()?;
+
+        Ok(sink)
+    }
+}
+
+/// Decrypting documents using a set of secret keys
+#[derive(Clone, Debug)]
+pub struct Decryption {
+    keys: Arc<Vec<Cert>>,
+}
+
+impl PartialEq for Decryption {
+    fn eq(&self, other: &Self) -> bool {
+        fingerprints(&self.keys) == fingerprints(&other.keys)
+    }
+}
+
+impl Eq for Decryption {}
+
+impl Decryption {
+    pub fn new(keys: Vec<Cert>) -> Self {
+        Self {
+            keys: Arc::new(keys),
+        }
+    }
+
+    /// Load (unencrypted) secret keys from keyring files
+    pub fn from_files<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> anyhow::Result<Self> {
+        let mut keys = vec![];
+        for path in paths {
+            keys.extend(load_certs(path.as_ref())?);
+        }
+        Ok(Self::new(keys))
+    }
+
+    /// Load (unencrypted) secret keys from armored data, stored in an environment variable
+    pub fn from_env(name: &str) -> anyhow::Result<Option<Self>> {
+        match std::env::var(name) {
+            Ok(data) => Ok(Some(Self::new(parse_certs(&data).with_context(|| {
+                format!("Failed to parse secret keys from '{name}'")
+            })?))),
+            Err(std::env::VarError::NotPresent) => Ok(None),
+            Err(err) => Err(err).with_context(|| format!("Failed to read '{name}'")),
+        }
+    }
+
+    /// Decrypt data, encrypted for one of the keys
+    pub fn decrypt(&self, data: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let policy = StandardPolicy::new();
+
+        let helper = Helper {
+            keys: &self.keys,
+            policy: &policy,
+        };
+
+        let mut decryptor =
+            DecryptorBuilder::from_bytes(data)?.with_policy(&policy, None, helper)?;
+
+        let mut result = Vec::with_capacity(data.len());
+        decryptor.read_to_end(&mut result)?;
+
+        Ok(result)
+    }
+}
+
+fn fingerprints(certs: &[Cert]) -> Vec<Fingerprint> {
+    certs.iter().map(Cert::fingerprint).collect()
+}
+
+struct Helper<'a> {
+    keys: &'a [Cert],
+    policy: &'a StandardPolicy<'a>,
+}
+
+impl VerificationHelper for Helper<'_> {
+    fn get_certs(&mut self, _ids: &[KeyHandle]) -> sequoia_openpgp::Result<Vec<Cert>> {
+        Ok(vec![])
+    }
+
+    fn check(&mut self, _structure: MessageStructure) -> sequoia_openpgp::Result<()> {
+        // we only decrypt, signatures are handled separately
+        Ok(())
+    }
+}
+
+impl DecryptionHelper for Helper<'_> {
+    fn decrypt<D>(
+        &mut self,
+        pkesks: &[PKESK],
+        _skesks: &[SKESK],
+        sym_algo: Option<SymmetricAlgorithm>,
+        mut decrypt: D,
+    ) -> sequoia_openpgp::Result<Option<Fingerprint>>
+    where
+        D: FnMut(SymmetricAlgorithm, &SessionKey) -> bool,
+    {
+        for cert in self.keys {
+            for key in cert
+                .keys()
+                .with_policy(self.policy, None)
+                .supported()
+                .for_storage_encryption()
+                .secret()
+            {
+                let mut pair = match key.key().clone().into_keypair() {
+                    Ok(pair) => pair,
+                    Err(err) => {
+                        log::debug!("Unable to use key {}: {err}", key.fingerprint());
+                        continue;
+                    }
+                };
+
+                for pkesk in pkesks {
+                    if pkesk
+                        .decrypt(&mut pair, sym_algo)
+                        .map(|(algo, session_key)| decrypt(algo, &session_key))
+                        .unwrap_or(false)
+                    {
+                        return Ok(Some(cert.fingerprint()));
+                    }
+                }
+            }
+        }
+
+        bail!("No key available to decrypt the document")
+    }
+}
@@ -0,0 +1,248 @@
+use super::Error;
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+use tokio::time::Instant;
+use url::{Origin, Url};
+
+/// Options for the circuit breaker of the [`super::Fetcher`]
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitBreaker {
+    /// Number of consecutive failures to a host, before requests to it are rejected
+    pub threshold: usize,
+    /// Time to wait, before a single request is let through again to probe the host
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self {
+            threshold: 10,
+            cooldown: Duration::from_secs(60),
+        }
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn cooldown(mut self, cooldown: impl Into<Duration>) -> Self {
+        self.cooldown = cooldown.into();
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+struct HostState {
+    failures: usize,
+    open_until: Option<Instant>,
+    probing: bool,
+}
+
+/// Tracking consecutive failures per origin
+#[derive(Debug)]
+pub struct Circuits {
+    options: CircuitBreaker,
+    hosts: Mutex<HashMap<Origin, HostState>>,
+}
+
+impl Circuits {
+    pub fn new(options: CircuitBreaker) -> Self {
+        Self {
+            options,
+            hosts: Default::default(),
+        }
+    }
+
+    /// Check if a request to the URL may be sent.
+    ///
+    /// Once the cooldown of an open circuit has passed, a single request is let through to probe
+    /// the host ("half-open"). The outcome of the request must be recorded using the returned
+    /// [`Permit`]. A permit dropped without an outcome (e.g. as the request was cancelled) lets
+    /// the next request probe the host.
+    pub fn check(&self, url: &Url) -> Result<Permit<'_>, Error> {
+        let origin = url.origin();
+        let mut hosts = self.hosts.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let probing = match hosts.get_mut(&origin) {
+            None => false,
+            Some(state) => match state.open_until {
+                None => false,
+                Some(until) if Instant::now() < until || state.probing => {
+                    log::debug!("Skipping request, circuit is open: {url}");
+                    return Err(Error::CircuitOpen(origin.ascii_serialization()));
+                }
+                Some(_) => {
+                    log::info!("Probing host: {}", origin.ascii_serialization());
+                    state.probing = true;
+                    true
+                }
+            },
+        };
+
+        Ok(Permit {
+            circuits: self,
+            origin,
+            probing,
+        })
+    }
+
+    /// Record the outcome of a request.
+    fn record<T>(&self, origin: &Origin, result: &Result<T, Error>) {
+        let mut hosts = self.hosts.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if !result.as_ref().err().is_some_and(is_host_failure) {
+            if let Some(state) = hosts.remove(origin) {
+                if state.open_until.is_some() {
+                    log::info!("Closing circuit: {}", origin.ascii_serialization());
+                }
+            }
+            return;
+        }
+
+        let state = hosts.entry(origin.clone()).or_default();
+        state.failures += 1;
+
+        if state.probing || state.failures >= self.options.threshold {
+            log::warn!(
+                "Opening circuit, after {} consecutive failures: {}",
+                state.failures,
+                origin.ascii_serialization()
+            );
+            state.open_until = Some(Instant::now() + self.options.cooldown);
+            state.probing = false;
+        }
+    }
+
+    /// Give up probing a host, without an outcome.
+    fn cancel_probe(&self, origin: &Origin) {
+        let mut hosts = self.hosts.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(state) = hosts.get_mut(origin) {
+            state.probing = false;
+        }
+    }
+}
+
+/// The permission to send a request, see [`Circuits::check`]
+#[derive(Debug)]
+pub struct Permit<'a> {
+    circuits: &'a Circuits,
+    origin: Origin,
+    probing: bool,
+}
+
+impl Permit<'_> {
+    /// Record the outcome of the request.
+    pub fn record<T>(mut self, result: &Result<T, Error>) {
+        self.circuits.record(&self.origin, result);
+        self.probing = false;
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if self.probing {
+            self.circuits.cancel_probe(&self.origin);
+        }
+    }
+}
+
+/// Check if the error indicates a problem with the host, rather than with the resource.
+pub(crate) fn is_host_failure(err: &Error) -> bool {
+    match err {
+        Error::Request(err) => {
+            err.is_timeout()
+                || err.is_connect()
+                || err.status().is_some_and(|status| {
+                    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+                })
+        }
+        Error::Disallowed(_) | Error::CircuitOpen(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn failure() -> Result<(), Error> {
+        let response = http::Response::builder()
+            .status(StatusCode::SERVICE_UNAVAILABLE)
+            .body("")
+            .unwrap();
+        reqwest::Response::from(response).error_for_status()?;
+        Ok(())
+    }
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn test_open() {
+        let circuits = Circuits::new(
+            CircuitBreaker::new()
+                .threshold(2)
+                .cooldown(Duration::from_secs(60)),
+        );
+        let a = url("https://a.example.com/1.json");
+
+        circuits.check(&a).unwrap().record(&failure());
+        // a success resets the count
+        circuits.check(&a).unwrap().record(&Ok(()));
+        circuits.check(&a).unwrap().record(&failure());
+        assert!(circuits.check(&a).is_ok());
+
+        circuits.check(&a).unwrap().record(&failure());
+        assert!(circuits.check(&a).unwrap_err().is_circuit_open());
+
+        // other hosts are not affected
+        assert!(circuits.check(&url("https://b.example.com/1.json")).is_ok());
+    }
+
+    #[test]
+    fn test_half_open() {
+        // without a cooldown, the next request after opening the circuit probes the host
+        let circuits = Circuits::new(CircuitBreaker::new().threshold(1).cooldown(Duration::ZERO));
+        let a = url("https://a.example.com/1.json");
+
+        circuits.check(&a).unwrap().record(&failure());
+
+        // only a single request probes the host
+        let probe = circuits.check(&a).unwrap();
+        assert!(circuits.check(&a).is_err());
+
+        // a failed probe re-opens the circuit
+        probe.record(&failure());
+        let probe = circuits.check(&a).unwrap();
+        assert!(circuits.check(&a).is_err());
+
+        // a successful probe closes it
+        probe.record(&Ok(()));
+        let first = circuits.check(&a).unwrap();
+        let second = circuits.check(&a).unwrap();
+        assert!(!first.probing && !second.probing);
+    }
+
+    #[test]
+    fn test_cancelled_probe() {
+        let circuits = Circuits::new(CircuitBreaker::new().threshold(1).cooldown(Duration::ZERO));
+        let a = url("https://a.example.com/1.json");
+
+        circuits.check(&a).unwrap().record(&failure());
+
+        // dropping the probe, e.g. when the request was cancelled, lets the next one probe
+        drop(circuits.check(&a).unwrap());
+        let probe = circuits.check(&a).unwrap();
+        assert!(probe.probing);
+    }
+}
@@ -0,0 +1,145 @@
+use hickory_resolver::{
+    config::{NameServerConfigGroup, ResolverConfig, ResolverOpts},
+    TokioAsyncResolver,
+};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+
+/// Preference of the IP address family when connecting to a host
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum IpPreference {
+    /// Use the addresses in the order of the resolver
+    #[default]
+    Any,
+    /// Try IPv4 addresses first
+    Ipv4First,
+    /// Try IPv6 addresses first
+    Ipv6First,
+    /// Only use IPv4 addresses
+    Ipv4Only,
+    /// Only use IPv6 addresses
+    Ipv6Only,
+}
+
+impl IpPreference {
+    /// Filter and order the addresses according to the preference.
+    pub fn apply(&self, mut addrs: Vec<IpAddr>) -> Vec<IpAddr> {
+        match self {
+            Self::Any => {}
+            Self::Ipv4First => addrs.sort_by_key(|addr| !addr.is_ipv4()),
+            Self::Ipv6First => addrs.sort_by_key(|addr| !addr.is_ipv6()),
+            Self::Ipv4Only => addrs.retain(IpAddr::is_ipv4),
+            Self::Ipv6Only => addrs.retain(IpAddr::is_ipv6),
+        }
+        addrs
+    }
+}
+
+/// Options for resolving host names
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DnsOptions {
+    /// Name servers to use instead of the system resolver
+    pub nameservers: Vec<IpAddr>,
+    /// Static host name to address mappings, bypassing the resolver
+    pub overrides: HashMap<String, Vec<IpAddr>>,
+    /// The preferred IP address family
+    pub preference: IpPreference,
+}
+
+impl DnsOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn nameservers<I>(mut self, nameservers: I) -> Self
+    where
+        I: IntoIterator<Item = IpAddr>,
+    {
+        self.nameservers = Vec::from_iter(nameservers);
+        self
+    }
+
+    pub fn add_nameserver(mut self, nameserver: IpAddr) -> Self {
+        self.nameservers.push(nameserver);
+        self
+    }
+
+    pub fn add_override(mut self, host: impl Into<String>, addr: IpAddr) -> Self {
+        self.overrides.entry(host.into()).or_default().push(addr);
+        self
+    }
+
+    pub fn extend_overrides<I, S>(mut self, overrides: I) -> Self
+    where
+        I: IntoIterator<Item = (S, IpAddr)>,
+        S: Into<String>,
+    {
+        for (host, addr) in overrides {
+            self = self.add_override(host, addr);
+        }
+        self
+    }
+
+    pub fn preference(mut self, preference: IpPreference) -> Self {
+        self.preference = preference;
+        self
+    }
+
+    /// Check if a custom resolver is required
+    pub(crate) fn needs_resolver(&self) -> bool {
+        !self.nameservers.is_empty() || self.preference != IpPreference::Any
+    }
+}
+
+/// A resolver, applying the DNS options
+pub(crate) struct Resolver {
+    resolver: Option<TokioAsyncResolver>,
+    preference: IpPreference,
+}
+
+impl Resolver {
+    pub fn new(options: &DnsOptions) -> Self {
+        let resolver = (!options.nameservers.is_empty()).then(|| {
+            let config = ResolverConfig::from_parts(
+                None,
+                vec![],
+                NameServerConfigGroup::from_ips_clear(&options.nameservers, 53, true),
+            );
+            TokioAsyncResolver::tokio(config, ResolverOpts::default())
+        });
+
+        Self {
+            resolver,
+            preference: options.preference,
+        }
+    }
+}
+
+impl Resolve for Resolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolver = self.resolver.clone();
+        let preference = self.preference;
+
+        Box::pin(async move {
+            let addrs: Vec<IpAddr> = match resolver {
+                Some(resolver) => resolver.lookup_ip(name.as_str()).await?.iter().collect(),
+                None => tokio::net::lookup_host((name.as_str(), 0))
+                    .await?
+                    .map(|addr| addr.ip())
+                    .collect(),
+            };
+
+            // the port will be replaced with the actual port of the request
+            let addrs: Addrs = Box::new(
+                preference
+                    .apply(addrs)
+                    .into_iter()
+                    .map(|ip| SocketAddr::new(ip, 0)),
+            );
+            Ok::<_, Box<dyn std::error::Error + Send + Sync>>(addrs)
+        })
+    }
+}
@@ -1,14 +1,32 @@
 //! Fetching remote resources
 
+mod circuit;
 mod data;
+mod dns;
+mod robots;
 
+pub use circuit::CircuitBreaker;
 pub use data::*;
+pub use dns::{DnsOptions, IpPreference};
+pub use robots::{RobotsRules, USER_AGENT_TOKEN};
 
-use reqwest::{Client, ClientBuilder, IntoUrl, Method, Response};
+use crate::{
+    clock::Clock,
+    concurrency::AdaptiveConcurrency,
+    retrieve::{RequestTiming, RetrievalMetadata},
+    run::{CorrelationId, CORRELATION_HEADER, USER_AGENT},
+    stage,
+};
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    redirect, Client, ClientBuilder, IntoUrl, Method, Response,
+};
 use std::fmt::Debug;
 use std::future::Future;
 use std::marker::PhantomData;
-use std::time::Duration;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use url::Url;
 
 /// Fetch data using HTTP.
@@ -19,6 +37,11 @@ use url::Url;
 pub struct Fetcher {
     client: Client,
     retries: usize,
+    retry_delay: Duration,
+    clock: Clock,
+    robots: Option<Arc<robots::Robots>>,
+    circuits: Option<Arc<circuit::Circuits>>,
+    adaptive: Option<Arc<AdaptiveConcurrency>>,
 }
 
 /// Error when retrieving
@@ -26,6 +49,25 @@ pub struct Fetcher {
 pub enum Error {
     #[error("Request error: {0}")]
     Request(#[from] reqwest::Error),
+    #[error("Disallowed by robots.txt: {0}")]
+    Disallowed(Url),
+    #[error("Circuit open, skipping requests to: {0}")]
+    CircuitOpen(String),
+}
+
+impl Error {
+    /// Check if the request was skipped, as the host failed too often
+    pub fn is_circuit_open(&self) -> bool {
+        matches!(self, Self::CircuitOpen(_))
+    }
+
+    /// Get the HTTP status code, if the request failed with an error response
+    pub fn status(&self) -> Option<reqwest::StatusCode> {
+        match self {
+            Self::Request(err) => err.status(),
+            _ => None,
+        }
+    }
 }
 
 /// Options for the [`Fetcher`]
@@ -34,6 +76,104 @@ pub enum Error {
 pub struct FetcherOptions {
     pub timeout: Duration,
     pub retries: usize,
+    /// The time to wait before retrying a failed request
+    pub retry_delay: Duration,
+    /// The clock used for waiting between retries
+    pub clock: Clock,
+    pub redirects: RedirectPolicy,
+    /// The minimum TLS version to accept
+    pub min_tls_version: Option<reqwest::tls::Version>,
+    /// Only allow HTTPS requests
+    pub https_only: bool,
+    /// Options for resolving host names
+    pub dns: DnsOptions,
+    /// Respect the `robots.txt` rules and crawl delays of hosts
+    pub robots: bool,
+    /// Skip requests to hosts which failed too often in a row
+    pub circuit_breaker: Option<CircuitBreaker>,
+    /// Report the latency and outcome of requests to an adaptive concurrency controller
+    pub adaptive: Option<Arc<AdaptiveConcurrency>>,
+    /// The user agent to send, instead of the default one
+    pub user_agent: Option<String>,
+    /// The correlation ID to send with each request
+    pub correlation_id: Option<CorrelationId>,
+}
+
+/// Policy for following HTTP redirects
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RedirectPolicy {
+    /// Maximum number of redirects to follow, `0` denies all redirects
+    pub max_hops: usize,
+    /// Only follow redirects to the same host as the original request
+    pub same_host_only: bool,
+    /// Deny redirects from `https` to `http`
+    pub deny_downgrade: bool,
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> Self {
+        Self {
+            max_hops: 10,
+            same_host_only: false,
+            deny_downgrade: false,
+        }
+    }
+}
+
+impl RedirectPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_hops(mut self, max_hops: usize) -> Self {
+        self.max_hops = max_hops;
+        self
+    }
+
+    pub fn same_host_only(mut self, same_host_only: bool) -> Self {
+        self.same_host_only = same_host_only;
+        self
+    }
+
+    pub fn deny_downgrade(mut self, deny_downgrade: bool) -> Self {
+        self.deny_downgrade = deny_downgrade;
+        self
+    }
+
+    /// Check if a redirect may be followed, returning the reason if it must not.
+    pub fn check(&self, previous: &[Url], next: &Url) -> Result<(), String> {
+        if previous.len() > self.max_hops {
+            return Err(format!("Too many redirects (max: {})", self.max_hops));
+        }
+
+        if self.same_host_only {
+            if let Some(first) = previous.first() {
+                if first.host_str() != next.host_str() {
+                    return Err(format!("Redirect to a different host denied: {next}"));
+                }
+            }
+        }
+
+        if self.deny_downgrade {
+            if let Some(last) = previous.last() {
+                if last.scheme() == "https" && next.scheme() != "https" {
+                    return Err(format!("Redirect to an insecure location denied: {next}"));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn into_policy(self) -> redirect::Policy {
+        redirect::Policy::custom(move |attempt| {
+            match self.check(attempt.previous(), attempt.url()) {
+                Ok(()) => attempt.follow(),
+                Err(err) => attempt.error(err),
+            }
+        })
+    }
 }
 
 impl FetcherOptions {
@@ -53,6 +193,73 @@ impl FetcherOptions {
         self.retries = retries;
         self
     }
+
+    /// Set the time to wait before retrying a failed request.
+    pub fn retry_delay(mut self, retry_delay: impl Into<Duration>) -> Self {
+        self.retry_delay = retry_delay.into();
+        self
+    }
+
+    /// Set the clock, used for waiting between retries.
+    pub fn clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Set the redirect policy.
+    pub fn redirects(mut self, redirects: RedirectPolicy) -> Self {
+        self.redirects = redirects;
+        self
+    }
+
+    /// Set the minimum TLS version.
+    pub fn min_tls_version(
+        mut self,
+        min_tls_version: impl Into<Option<reqwest::tls::Version>>,
+    ) -> Self {
+        self.min_tls_version = min_tls_version.into();
+        self
+    }
+
+    /// Only allow HTTPS requests.
+    pub fn https_only(mut self, https_only: bool) -> Self {
+        self.https_only = https_only;
+        self
+    }
+
+    /// Set the DNS options.
+    pub fn dns(mut self, dns: DnsOptions) -> Self {
+        self.dns = dns;
+        self
+    }
+
+    /// Respect the `robots.txt` rules and crawl delays of hosts.
+    pub fn robots(mut self, robots: bool) -> Self {
+        self.robots = robots;
+        self
+    }
+
+    pub fn circuit_breaker(mut self, circuit_breaker: impl Into<Option<CircuitBreaker>>) -> Self {
+        self.circuit_breaker = circuit_breaker.into();
+        self
+    }
+
+    pub fn adaptive(mut self, adaptive: impl Into<Option<Arc<AdaptiveConcurrency>>>) -> Self {
+        self.adaptive = adaptive.into();
+        self
+    }
+
+    /// Set the user agent, instead of the default one.
+    pub fn user_agent(mut self, user_agent: impl Into<Option<String>>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Set the correlation ID, sent with each request.
+    pub fn correlation_id(mut self, correlation_id: impl Into<Option<CorrelationId>>) -> Self {
+        self.correlation_id = correlation_id.into();
+        self
+    }
 }
 
 impl Default for FetcherOptions {
@@ -60,6 +267,17 @@ impl Default for FetcherOptions {
         Self {
             timeout: Duration::from_secs(30),
             retries: 5,
+            retry_delay: Duration::ZERO,
+            clock: Default::default(),
+            redirects: Default::default(),
+            min_tls_version: None,
+            https_only: false,
+            dns: Default::default(),
+            robots: false,
+            circuit_breaker: None,
+            adaptive: None,
+            user_agent: None,
+            correlation_id: None,
         }
     }
 }
@@ -73,7 +291,43 @@ impl From<Client> for Fetcher {
 impl Fetcher {
     /// Create a new downloader from options
     pub async fn new(options: FetcherOptions) -> anyhow::Result<Self> {
-        let client = ClientBuilder::new().timeout(options.timeout);
+        let mut headers = HeaderMap::new();
+        if let Some(correlation_id) = &options.correlation_id {
+            headers.insert(
+                CORRELATION_HEADER,
+                HeaderValue::from_str(correlation_id.as_str())?,
+            );
+        }
+
+        // TLS info is required for recording the peer certificate in the retrieval metadata
+        let client = ClientBuilder::new()
+            .user_agent(options.user_agent.as_deref().unwrap_or(USER_AGENT))
+            .default_headers(headers)
+            .timeout(options.timeout)
+            .tls_info(true)
+            .redirect(options.redirects.clone().into_policy())
+            .https_only(options.https_only);
+
+        let mut client = match options.min_tls_version {
+            Some(version) => client.min_tls_version(version),
+            None => client,
+        };
+
+        if options.dns.needs_resolver() {
+            client = client.dns_resolver(Arc::new(dns::Resolver::new(&options.dns)));
+        }
+
+        for (host, addrs) in &options.dns.overrides {
+            // the port will be replaced with the actual port of the request
+            let addrs = options
+                .dns
+                .preference
+                .apply(addrs.clone())
+                .into_iter()
+                .map(|ip| SocketAddr::new(ip, 0))
+                .collect::<Vec<_>>();
+            client = client.resolve_to_addrs(host, &addrs);
+        }
 
         Ok(Self::with_client(client.build()?, options))
     }
@@ -83,6 +337,13 @@ impl Fetcher {
         Self {
             client,
             retries: options.retries,
+            retry_delay: options.retry_delay,
+            clock: options.clock,
+            robots: options.robots.then(Default::default),
+            circuits: options
+                .circuit_breaker
+                .map(|options| Arc::new(circuit::Circuits::new(options))),
+            adaptive: options.adaptive,
         }
     }
 
@@ -96,7 +357,7 @@ impl Fetcher {
 
     /// fetch data, using a GET request.
     pub async fn fetch<D: Data>(&self, url: impl IntoUrl) -> Result<D, Error> {
-        log::debug!("Fetching: {}", url.as_str());
+        tracing::debug!(target: stage::FETCH, stage = "fetch", url = url.as_str(), "Fetching");
         self.fetch_processed(url, TypedProcessor::<D>::new()).await
     }
 
@@ -109,16 +370,47 @@ impl Fetcher {
         // if the URL building fails, there is no need to re-try, abort now.
         let url = url.into_url()?;
 
+        if let Some(robots) = &self.robots {
+            robots.check(&self.client, &url).await?;
+        }
+
         let mut retries = self.retries;
 
         loop {
-            match self.fetch_once(url.clone(), &processor).await {
+            let permit = match &self.circuits {
+                Some(circuits) => Some(circuits.check(&url)?),
+                None => None,
+            };
+
+            let start = Instant::now();
+            let result = self.fetch_once(url.clone(), &processor).await;
+            if let Some(permit) = permit {
+                permit.record(&result);
+            }
+            if let Some(adaptive) = &self.adaptive {
+                adaptive.record(
+                    start.elapsed(),
+                    result.as_ref().err().is_some_and(circuit::is_host_failure),
+                );
+            }
+
+            match result {
                 Ok(result) => break Ok(result),
                 Err(err) => {
-                    log::info!("Failed to retrieve (retries: {retries}): {err}");
+                    tracing::info!(
+                        target: stage::FETCH,
+                        stage = "fetch",
+                        url = %url,
+                        outcome = "failed",
+                        retries,
+                        error = %err,
+                        "Failed to retrieve"
+                    );
                     if retries > 0 {
-                        // TODO: consider adding a back-off delay
                         retries -= 1;
+                        if !self.retry_delay.is_zero() {
+                            self.clock.sleep(self.retry_delay).await;
+                        }
                     } else {
                         break Err(err);
                     }
@@ -127,12 +419,57 @@ impl Fetcher {
         }
     }
 
+    /// fetch the metadata of a resource, using a HEAD request.
+    ///
+    /// This is not re-tried, as the outcome is only used for optimizing a later GET request.
+    pub async fn head(&self, url: impl IntoUrl) -> Result<RetrievalMetadata, Error> {
+        let url = url.into_url()?;
+
+        if let Some(robots) = &self.robots {
+            robots.check(&self.client, &url).await?;
+        }
+
+        let permit = match &self.circuits {
+            Some(circuits) => Some(circuits.check(&url)?),
+            None => None,
+        };
+
+        tracing::debug!(target: stage::FETCH, stage = "fetch", url = %url, "Checking");
+
+        let result = self.head_once(url).await;
+        if let Some(permit) = permit {
+            permit.record(&result);
+        }
+
+        result
+    }
+
+    async fn head_once(&self, url: Url) -> Result<RetrievalMetadata, Error> {
+        let response = Self::send_timed(self.new_request(Method::HEAD, url).await?)
+            .await?
+            .error_for_status()?;
+
+        Ok(RetrievalMetadata::from_response(&response))
+    }
+
+    /// Send a request, attaching its [`RequestTiming`] to the response.
+    async fn send_timed(request: reqwest::RequestBuilder) -> Result<Response, reqwest::Error> {
+        let started = Instant::now();
+        let mut response = request.send().await?;
+        response.extensions_mut().insert(RequestTiming {
+            started,
+            first_byte: started.elapsed(),
+        });
+        Ok(response)
+    }
+
     async fn fetch_once<D: DataProcessor>(
         &self,
         url: Url,
         processor: &D,
     ) -> Result<D::Type, Error> {
-        let response = self.new_request(Method::GET, url).await?.send().await?;
+        let request = processor.prepare(self.new_request(Method::GET, url).await?);
+        let response = Self::send_timed(request).await?;
 
         Ok(processor.process(response).await?)
     }
@@ -141,6 +478,15 @@ impl Fetcher {
 /// Processing data returned by a request.
 pub trait DataProcessor {
     type Type: Sized;
+
+    /// Prepare the request of an attempt, before it gets sent.
+    ///
+    /// This allows a processor to continue an attempt which failed before, e.g. by requesting
+    /// only the remaining part of a body. The default is to send the request unmodified.
+    fn prepare(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        request
+    }
+
     fn process(
         &self,
         response: reqwest::Response,
@@ -0,0 +1,305 @@
+use super::Error;
+use reqwest::{Client, StatusCode};
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError};
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use tokio::time::Instant;
+use url::{Origin, Url};
+
+/// The token used for matching `User-agent` groups
+pub const USER_AGENT_TOKEN: &str = "csaf-walker";
+
+/// The rules of a `robots.txt` file, applying to us
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RobotsRules {
+    /// Path patterns, with the flag if they are allowed
+    pub rules: Vec<(String, bool)>,
+    /// The delay between two requests
+    pub crawl_delay: Option<Duration>,
+}
+
+impl RobotsRules {
+    /// Parse a `robots.txt` file, using the group for our user agent token, or the default group.
+    pub fn parse(content: &str) -> Self {
+        #[derive(Default)]
+        struct Group {
+            agents: Vec<String>,
+            rules: RobotsRules,
+        }
+
+        let mut groups: Vec<Group> = vec![];
+        let mut in_agents = false;
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_ascii_lowercase();
+            let value = value.trim();
+
+            if key == "user-agent" {
+                if !in_agents {
+                    groups.push(Group::default());
+                    in_agents = true;
+                }
+                if let Some(group) = groups.last_mut() {
+                    group.agents.push(value.to_ascii_lowercase());
+                }
+                continue;
+            }
+
+            in_agents = false;
+            let Some(group) = groups.last_mut() else {
+                continue;
+            };
+
+            match key.as_str() {
+                // an empty disallow rule allows everything
+                "disallow" if !value.is_empty() => group.rules.rules.push((value.into(), false)),
+                "allow" if !value.is_empty() => group.rules.rules.push((value.into(), true)),
+                "crawl-delay" => {
+                    group.rules.crawl_delay = value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|delay| delay.is_finite() && *delay >= 0.0)
+                        .map(Duration::from_secs_f64)
+                }
+                _ => {}
+            }
+        }
+
+        let find = |agent: &str| {
+            groups
+                .iter()
+                .find(|group| group.agents.iter().any(|a| a == agent))
+        };
+
+        find(USER_AGENT_TOKEN)
+            .or_else(|| find("*"))
+            .map(|group| group.rules.clone())
+            .unwrap_or_default()
+    }
+
+    /// Check if the path (including the query) is allowed.
+    ///
+    /// The longest matching rule wins, in case of a tie, allowing wins.
+    pub fn is_allowed(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|(pattern, _)| matches(pattern, path))
+            .max_by_key(|(pattern, allowed)| (pattern.len(), *allowed))
+            .map(|(_, allowed)| *allowed)
+            .unwrap_or(true)
+    }
+}
+
+/// Match a `robots.txt` path pattern, supporting `*` wildcards and the `$` end anchor.
+fn matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+
+    let mut parts = pattern.split('*');
+    let Some(first) = parts.next() else {
+        return true;
+    };
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts = parts.collect::<Vec<_>>();
+    for (n, part) in parts.iter().enumerate() {
+        if anchored && n == parts.len() - 1 {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+
+    !anchored || rest.is_empty()
+}
+
+#[derive(Debug)]
+struct HostState {
+    /// The rules, loaded by the first request to the origin
+    rules: OnceCell<RobotsRules>,
+    /// The earliest time of the next request
+    next: std::sync::Mutex<Instant>,
+}
+
+impl HostState {
+    fn new() -> Self {
+        Self {
+            rules: OnceCell::new(),
+            next: std::sync::Mutex::new(Instant::now()),
+        }
+    }
+}
+
+/// Tracking `robots.txt` rules and crawl delays per origin
+#[derive(Debug, Default)]
+pub struct Robots {
+    hosts: std::sync::Mutex<HashMap<Origin, Arc<HostState>>>,
+}
+
+impl Robots {
+    /// Check if the URL may be requested, waiting for the crawl delay if necessary.
+    pub async fn check(&self, client: &Client, url: &Url) -> Result<(), Error> {
+        if !matches!(url.scheme(), "http" | "https") {
+            return Ok(());
+        }
+
+        // only hold the lock of all origins for looking up the state of one
+        let state = self
+            .hosts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(url.origin())
+            .or_insert_with(|| Arc::new(HostState::new()))
+            .clone();
+
+        // concurrent requests to the same origin wait for the rules to be loaded once
+        let rules = state.rules.get_or_init(|| Self::load(client, url)).await;
+
+        let path = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        };
+        if !rules.is_allowed(&path) {
+            return Err(Error::Disallowed(url.clone()));
+        }
+
+        let wait = {
+            let mut next = state.next.lock().unwrap_or_else(PoisonError::into_inner);
+            let now = Instant::now();
+            let start = (*next).max(now);
+            *next = start + rules.crawl_delay.unwrap_or_default();
+            start - now
+        };
+
+        if !wait.is_zero() {
+            log::debug!("Waiting {wait:?} for crawl delay: {url}");
+            tokio::time::sleep(wait).await;
+        }
+
+        Ok(())
+    }
+
+    /// Load the rules for the origin of the URL. Failing to do so allows everything.
+    async fn load(client: &Client, url: &Url) -> RobotsRules {
+        let Ok(robots) = url.join("/robots.txt") else {
+            return Default::default();
+        };
+
+        log::debug!("Fetching: {robots}");
+
+        let response = match client.get(robots.clone()).send().await {
+            Ok(response) => response,
+            Err(err) => {
+                log::info!("Failed to retrieve {robots}, allowing all: {err}");
+                return Default::default();
+            }
+        };
+
+        match response.status() {
+            StatusCode::OK => match response.text().await {
+                Ok(content) => RobotsRules::parse(&content),
+                Err(err) => {
+                    log::info!("Failed to read {robots}, allowing all: {err}");
+                    Default::default()
+                }
+            },
+            status => {
+                log::debug!("No robots.txt ({status}), allowing all: {robots}");
+                Default::default()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_groups() {
+        let content = r#"
+# the default group
+User-agent: *
+Disallow: /
+
+User-agent: other
+User-agent: CSAF-Walker
+Disallow: /private/ # not for us
+Crawl-delay: 1.5
+
+User-agent: csaf-walker
+Disallow: /ignored/
+"#;
+
+        let rules = RobotsRules::parse(content);
+        // the first group naming our token wins, agents are matched case-insensitive
+        assert_eq!(rules.rules, vec![("/private/".to_string(), false)]);
+        assert_eq!(rules.crawl_delay, Some(Duration::from_millis(1500)));
+
+        // falling back to the default group
+        let rules =
+            RobotsRules::parse("User-agent: other\nDisallow: /a\n\nUser-agent: *\nDisallow: /b");
+        assert_eq!(rules.rules, vec![("/b".to_string(), false)]);
+
+        // no matching group, or an empty disallow rule, allow everything
+        assert_eq!(
+            RobotsRules::parse("User-agent: other\nDisallow: /"),
+            RobotsRules::default()
+        );
+        assert!(RobotsRules::parse("User-agent: *\nDisallow:").is_allowed("/"));
+
+        // rules before the first group are ignored, as are invalid delays
+        let rules = RobotsRules::parse("Disallow: /\nUser-agent: *\nCrawl-delay: -1");
+        assert_eq!(rules, RobotsRules::default());
+    }
+
+    #[test]
+    fn test_precedence() {
+        let rules = RobotsRules::parse(
+            "User-agent: *\nDisallow: /advisories/\nAllow: /advisories/public/\nAllow: /a\nDisallow: /a",
+        );
+
+        // the longest matching rule wins
+        assert!(!rules.is_allowed("/advisories/2024/a.json"));
+        assert!(rules.is_allowed("/advisories/public/a.json"));
+        // with rules of the same length, allowing wins
+        assert!(rules.is_allowed("/a"));
+        // without a matching rule, everything is allowed
+        assert!(rules.is_allowed("/other"));
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("/", "/any"));
+        assert!(matches("/a", "/a/b"));
+        assert!(!matches("/a", "/b/a"));
+
+        // wildcards
+        assert!(matches("/*.json", "/a/b.json"));
+        assert!(matches("/a/*/c", "/a/b/c/d"));
+        assert!(!matches("/a/*/c", "/a/b/d"));
+        assert!(matches("*", "/any"));
+
+        // end anchor
+        assert!(matches("/*.json$", "/a.json"));
+        assert!(!matches("/*.json$", "/a.json.asc"));
+        assert!(matches("/a$", "/a"));
+        assert!(!matches("/a$", "/a/"));
+
+        // the query is part of the path
+        let rules = RobotsRules::parse("User-agent: *\nDisallow: /*?download=");
+        assert!(!rules.is_allowed("/a.json?download=1"));
+        assert!(rules.is_allowed("/a.json"));
+    }
+}
@@ -0,0 +1,203 @@
+//! Generators and properties for testing the parsers of remote input
+//!
+//! The digest files, `changes.csv` files, and HTTP headers are provided by remote servers, and
+//! must be treated as untrusted input. The strategies generate valid input, together with the
+//! expected result of parsing it. The `check_*` functions verify properties which must hold for
+//! any input, and are used by the fuzz targets in the `fuzz` directory.
+
+use crate::{
+    changes::{ChangeEntry, ChangeSource},
+    retrieve::{DigestFile, DigestFormat, RetrievalMetadata, RetrievalTimings},
+    utils::hex::Hex,
+};
+use proptest::prelude::*;
+use reqwest::header::{self, HeaderMap, HeaderName, HeaderValue};
+use time::{
+    format_description::well_known::{Rfc2822, Rfc3339},
+    OffsetDateTime,
+};
+use url::Url;
+
+/// A timestamp (in UTC, with second precision), together with its RFC 3339 representation.
+pub fn timestamp() -> impl Strategy<Value = (OffsetDateTime, String)> {
+    // up to the end of the year 2099
+    (0i64..4_102_444_800).prop_filter_map("timestamp must be formattable", |secs| {
+        let timestamp = OffsetDateTime::from_unix_timestamp(secs).ok()?;
+        let formatted = timestamp.format(&Rfc3339).ok()?;
+        Some((timestamp, formatted))
+    })
+}
+
+/// A hex encoded digest, lower-cased.
+pub fn hex_digest() -> impl Strategy<Value = String> {
+    prop::collection::vec(any::<u8>(), 1..=64).prop_map(|bytes| Hex(&bytes).to_lower())
+}
+
+/// A file name, as it can be used in all digest file formats.
+pub fn file_name() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9_][a-zA-Z0-9_.-]{0,40}"
+}
+
+/// The content of a digest file, in any of the supported formats, and the expected result.
+pub fn digest_file() -> impl Strategy<Value = (String, DigestFile)> {
+    (
+        hex_digest(),
+        any::<bool>(),
+        file_name(),
+        prop_oneof![
+            Just(DigestFormat::Bare),
+            Just(DigestFormat::Gnu),
+            Just(DigestFormat::Bsd)
+        ],
+        "[ \t\n]{0,3}",
+    )
+        .prop_map(|(digest, upper, file, format, padding)| {
+            let written = match upper {
+                true => digest.to_uppercase(),
+                false => digest.clone(),
+            };
+
+            let (content, file) = match format {
+                DigestFormat::Bare => (written, None),
+                DigestFormat::Gnu => (format!("{written}  {file}"), Some(file)),
+                DigestFormat::Bsd => (format!("SHA256 ({file}) = {written}"), Some(file)),
+            };
+
+            (
+                format!("{padding}{content}\n"),
+                DigestFile {
+                    digest,
+                    file,
+                    format,
+                },
+            )
+        })
+}
+
+/// The content of a `changes.csv` file, and the expected entries.
+pub fn changes_csv() -> impl Strategy<Value = (String, Vec<ChangeEntry>)> {
+    prop::collection::vec(
+        ("[a-z0-9_-]{1,10}(/[a-z0-9_.-]{1,20}){0,3}", timestamp()),
+        0..32,
+    )
+    .prop_map(|entries| {
+        let mut content = String::new();
+        let mut expected = Vec::with_capacity(entries.len());
+
+        for (file, (timestamp, formatted)) in entries {
+            content.push_str(&format!("\"{file}\",\"{formatted}\"\n"));
+            expected.push(ChangeEntry { file, timestamp });
+        }
+
+        (content, expected)
+    })
+}
+
+/// Retrieval metadata, as it gets stored alongside a document.
+pub fn retrieval_metadata() -> impl Strategy<Value = RetrievalMetadata> {
+    (
+        prop::option::of(timestamp()),
+        prop::option::of("\"[a-zA-Z0-9-]{1,32}\""),
+        prop::option::of("[a-z0-9/._-]{0,40}"),
+        prop::option::of("[a-z]+/[a-z+.-]+"),
+        prop::option::of(any::<u64>()),
+        prop::option::of(timestamp()),
+        prop::option::of(hex_digest()),
+        prop::option::of((any::<u64>(), any::<u64>())),
+    )
+        .prop_map(
+            |(
+                last_modification,
+                etag,
+                path,
+                content_type,
+                content_length,
+                server_date,
+                tls_peer_certificate,
+                timings,
+            )| RetrievalMetadata {
+                last_modification: last_modification.map(|(timestamp, _)| timestamp),
+                etag,
+                final_url: path
+                    .and_then(|path| Url::parse(&format!("https://example.com/{path}")).ok()),
+                content_type,
+                content_length,
+                server_date: server_date.map(|(timestamp, _)| timestamp),
+                tls_peer_certificate,
+                timings: timings.map(|(first_byte, total)| RetrievalTimings { first_byte, total }),
+            },
+        )
+}
+
+/// Headers of an HTTP response, with valid or invalid values for the headers of interest.
+pub fn headers() -> impl Strategy<Value = Vec<(Vec<u8>, Vec<u8>)>> {
+    let name = prop_oneof![
+        Just(header::LAST_MODIFIED.as_str().as_bytes().to_vec()),
+        Just(header::DATE.as_str().as_bytes().to_vec()),
+        Just(header::ETAG.as_str().as_bytes().to_vec()),
+        Just(header::CONTENT_TYPE.as_str().as_bytes().to_vec()),
+        Just(header::CONTENT_LENGTH.as_str().as_bytes().to_vec()),
+        "[a-z-]{1,20}".prop_map(String::into_bytes),
+    ];
+    let value = prop_oneof![
+        timestamp().prop_filter_map("timestamp must be formattable", |(timestamp, _)| {
+            timestamp.format(&Rfc2822).ok().map(String::into_bytes)
+        }),
+        any::<u64>().prop_map(|n| n.to_string().into_bytes()),
+        prop::collection::vec(any::<u8>(), 0..64),
+    ];
+
+    prop::collection::vec((name, value), 0..8)
+}
+
+/// Parsing a digest file must not panic. A parsed digest must be lower-case hex, of an even
+/// length, and parse to itself when used as the content of a digest file.
+pub fn check_digest_file(content: &str) {
+    let Ok(file) = DigestFile::parse(content) else {
+        return;
+    };
+
+    assert!(file
+        .digest
+        .bytes()
+        .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b)));
+    assert_eq!(file.digest.len() % 2, 0);
+    assert_eq!(
+        DigestFile::parse(&file.digest).map(|bare| bare.digest),
+        Ok(file.digest.clone())
+    );
+}
+
+/// Parsing a `changes.csv` file must not panic.
+pub fn check_changes_csv(content: &str) {
+    let _ = ChangeSource::parse(content);
+}
+
+/// Extracting the retrieval metadata from arbitrary headers must not panic. The metadata must
+/// survive being stored, and be considered unchanged compared to itself if it has an ETag.
+pub fn check_headers(headers: &[(Vec<u8>, Vec<u8>)]) {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        if let (Ok(name), Ok(value)) =
+            (HeaderName::from_bytes(name), HeaderValue::from_bytes(value))
+        {
+            map.append(name, value);
+        }
+    }
+
+    let metadata = RetrievalMetadata::from_headers(&map);
+
+    check_retrieval_metadata(&metadata);
+    if metadata.etag.is_some() {
+        assert!(metadata.is_unchanged(&metadata));
+    }
+}
+
+/// Retrieval metadata must be the same after storing and loading it.
+pub fn check_retrieval_metadata(metadata: &RetrievalMetadata) {
+    let Ok(data) = serde_json::to_vec(metadata) else {
+        return;
+    };
+    let loaded = serde_json::from_slice::<RetrievalMetadata>(&data).ok();
+    assert_eq!(loaded.as_ref(), Some(metadata));
+}
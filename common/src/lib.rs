@@ -1,20 +1,42 @@
 #![deny(clippy::unwrap_used)]
 
+pub mod budget;
 pub mod changes;
+pub mod checkpoint;
+pub mod clock;
 pub mod compression;
+pub mod concurrency;
+pub mod deadline;
+pub mod doctor;
 pub mod fetcher;
 pub mod locale;
+pub mod lock;
+pub mod notify;
+pub mod offload;
 pub mod progress;
 pub mod report;
 pub mod retrieve;
+pub mod run;
 pub mod sender;
+pub mod signature;
 pub mod since;
 pub mod source;
+pub mod stage;
 pub mod store;
+pub mod timestamp;
 pub mod utils;
 
+#[cfg(feature = "openpgp")]
+pub mod attestation;
+#[cfg(feature = "openpgp")]
+pub mod encryption;
+#[cfg(feature = "openpgp")]
+pub mod signing;
 #[cfg(feature = "openpgp")]
 pub mod validate;
 
 #[cfg(feature = "clap")]
 pub mod cli;
+
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
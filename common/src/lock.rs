@@ -0,0 +1,239 @@
+//! Advisory locking of files and directories, preventing concurrent runs from corrupting state
+//!
+//! A lock is a file, on which an exclusive advisory lock of the operating system is held, and
+//! which contains the ID of the owning process. A directory is locked using a `.lock` file inside
+//! of it, a file using a file next to it (with a `.lock` suffix). Paths are canonicalized first, so
+//! that different spellings of the same path (like `out` and `out/`) use the same lock file. The operating system releases the lock when the owning process ends, so a crashed
+//! run doesn't leave a stale lock behind.
+//!
+//! The lock file itself is never removed, as a process waiting on the removed file, and another
+//! one creating a new file, could then both hold "the" lock.
+
+use anyhow::{bail, Context};
+use fs2::FileExt;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockOptions {
+    /// Wait for the lock to be released, instead of failing
+    pub wait: bool,
+    /// Maximum time to wait, [`None`] waits forever
+    pub timeout: Option<Duration>,
+    /// Interval of checking if the lock was released, while waiting
+    pub poll_interval: Duration,
+}
+
+impl Default for LockOptions {
+    fn default() -> Self {
+        Self {
+            wait: true,
+            timeout: None,
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl LockOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn wait(mut self, wait: bool) -> Self {
+        self.wait = wait;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.timeout = timeout.into();
+        self
+    }
+
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+/// A held lock, released when dropped
+#[derive(Debug)]
+pub struct Lock {
+    file: PathBuf,
+    handle: File,
+}
+
+impl Lock {
+    /// The lock file of a path.
+    ///
+    /// For an existing directory, this is the `.lock` file inside of it. Otherwise, the path is
+    /// considered a file, and the lock file is next to it. The parent directory must exist.
+    pub fn lock_file(path: &Path) -> std::io::Result<PathBuf> {
+        if path.is_dir() {
+            return Ok(path.canonicalize()?.join(".lock"));
+        }
+
+        let Some(name) = path.file_name() else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Path has no file name: {}", path.display()),
+            ));
+        };
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+
+        let mut name = name.to_owned();
+        name.push(".lock");
+        Ok(parent.canonicalize()?.join(name))
+    }
+
+    /// Acquire the lock of a directory, creating the directory if it doesn't exist yet.
+    pub async fn acquire_dir(path: &Path, options: &LockOptions) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(path)
+            .with_context(|| format!("Failed to create directory: {}", path.display()))?;
+        Self::acquire(path, options).await
+    }
+
+    /// Acquire the lock for a path.
+    pub async fn acquire(path: &Path, options: &LockOptions) -> anyhow::Result<Self> {
+        let file = Self::lock_file(path)
+            .with_context(|| format!("Failed to locate lock of: {}", path.display()))?;
+        let start = SystemTime::now();
+
+        let mut handle = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&file)
+            .with_context(|| format!("Failed to create lock: {}", file.display()))?;
+
+        loop {
+            match handle.try_lock_exclusive() {
+                Ok(()) => break,
+                Err(err) if err.raw_os_error() == fs2::lock_contended_error().raw_os_error() => {}
+                Err(err) => {
+                    return Err(err).with_context(|| format!("Failed to lock: {}", file.display()))
+                }
+            }
+
+            let owner = std::fs::read_to_string(&file).unwrap_or_default();
+            let owner = owner.trim();
+
+            if !options.wait {
+                bail!("Locked by process {owner}: {}", file.display());
+            }
+
+            if let Some(timeout) = options.timeout {
+                if start.elapsed().unwrap_or_default() > timeout {
+                    bail!(
+                        "Timeout waiting for lock of process {owner}: {}",
+                        file.display()
+                    );
+                }
+            }
+
+            log::info!("Waiting for lock of process {owner}: {}", file.display());
+            tokio::time::sleep(options.poll_interval).await;
+        }
+
+        // only the owner of the lock writes to the file, replacing the ID of a previous owner
+        handle
+            .set_len(0)
+            .and_then(|()| writeln!(handle, "{}", std::process::id()))
+            .with_context(|| format!("Failed to write lock: {}", file.display()))?;
+
+        log::debug!("Acquired lock: {}", file.display());
+
+        Ok(Self { file, handle })
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        // closing the handle would release the lock too, but let's be explicit
+        if let Err(err) = FileExt::unlock(&self.handle) {
+            log::warn!("Failed to release lock {}: {err}", self.file.display());
+        } else {
+            log::debug!("Released lock: {}", self.file.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_util::FutureExt;
+
+    #[test]
+    fn test_exclusive() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data");
+        let options = LockOptions::new().wait(false);
+
+        let acquire = || {
+            Lock::acquire(&path, &options)
+                .now_or_never()
+                .expect("must not wait")
+        };
+
+        let lock = acquire().expect("must acquire lock");
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("data.lock")).unwrap(),
+            format!("{}\n", std::process::id())
+        );
+
+        // held by the first lock
+        assert!(acquire().is_err());
+
+        drop(lock);
+        let _lock = acquire().expect("must acquire released lock");
+    }
+
+    #[test]
+    fn test_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = dir.path().join("store");
+        let options = LockOptions::new().wait(false);
+
+        let _lock = Lock::acquire_dir(&store, &options)
+            .now_or_never()
+            .expect("must not wait")
+            .expect("must acquire lock");
+        assert!(store.join(".lock").is_file());
+
+        // a different spelling of the same directory uses the same lock
+        let mut spelling = store.as_os_str().to_owned();
+        spelling.push("/");
+        let spelling = PathBuf::from(spelling);
+        assert_eq!(
+            Lock::lock_file(&spelling).unwrap(),
+            store.canonicalize().unwrap().join(".lock")
+        );
+        assert!(Lock::acquire(&spelling, &options)
+            .now_or_never()
+            .expect("must not wait")
+            .is_err());
+        assert!(Lock::acquire(&store.join("."), &options)
+            .now_or_never()
+            .expect("must not wait")
+            .is_err());
+    }
+
+    #[test]
+    fn test_current_dir() {
+        // the current directory is locked inside, not next to it
+        assert_eq!(
+            Lock::lock_file(Path::new(".")).unwrap(),
+            std::env::current_dir()
+                .unwrap()
+                .canonicalize()
+                .unwrap()
+                .join(".lock")
+        );
+    }
+}
@@ -0,0 +1,56 @@
+//! Notifications about a run
+//!
+//! Instead of notifying about each document, a single notification with aggregated statistics is
+//! sent at the end of a run.
+
+use anyhow::Context;
+use serde::Serialize;
+use std::fmt::Display;
+use url::Url;
+
+/// Sends a summary of a run to a webhook
+///
+/// The summary is sent as JSON, with the human readable summary in the `text` field, and the
+/// structured summary in the `summary` field. The `text` field makes it possible to use
+/// incoming webhooks of chat systems (like Slack or Mattermost) directly.
+#[derive(Clone, Debug)]
+pub struct SummaryNotifier {
+    client: reqwest::Client,
+    webhook: Url,
+}
+
+#[derive(Serialize)]
+struct Payload<'a, S> {
+    text: String,
+    summary: &'a S,
+}
+
+impl SummaryNotifier {
+    pub fn new(webhook: Url) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            webhook,
+        }
+    }
+
+    /// Send the summary of a run
+    pub async fn notify<S>(&self, summary: &S) -> anyhow::Result<()>
+    where
+        S: Serialize + Display,
+    {
+        self.client
+            .post(self.webhook.clone())
+            .json(&Payload {
+                text: summary.to_string(),
+                summary,
+            })
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("Failed to send notification to: {}", self.webhook))?;
+
+        log::info!("Sent run summary to: {}", self.webhook);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+//! Offloading CPU intensive work from the async runtime
+
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinError;
+
+/// Running CPU intensive work (like calculating digests, or verifying signatures) on the blocking
+/// thread pool of the runtime, keeping the reactor responsive.
+///
+/// The number of concurrently running tasks is limited, so that the work doesn't exhaust the
+/// blocking thread pool, which is also used for file I/O.
+#[derive(Clone, Debug)]
+pub struct Offload {
+    permits: Arc<Semaphore>,
+}
+
+impl Default for Offload {
+    /// Use as many threads as there are CPUs available.
+    fn default() -> Self {
+        Self::new(
+            std::thread::available_parallelism()
+                .map(NonZeroUsize::get)
+                .unwrap_or(1),
+        )
+    }
+}
+
+/// Two instances are equal if they share the same limit.
+impl PartialEq for Offload {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.permits, &other.permits)
+    }
+}
+
+impl Eq for Offload {}
+
+impl Offload {
+    /// Create a new instance, running up to `threads` tasks concurrently.
+    pub fn new(threads: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(threads.max(1))),
+        }
+    }
+
+    /// Run a function on the blocking thread pool.
+    ///
+    /// A panic of the function will be resumed in the calling task.
+    pub async fn run<F, T>(&self, f: F) -> T
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        // the semaphore is never closed
+        let _permit = self.permits.acquire().await.ok();
+
+        match tokio::task::spawn_blocking(f).await {
+            Ok(result) => result,
+            Err(err) => resume(err),
+        }
+    }
+}
+
+fn resume(err: JoinError) -> ! {
+    match err.try_into_panic() {
+        Ok(panic) => std::panic::resume_unwind(panic),
+        // blocking tasks cannot be aborted, this only happens when the runtime shuts down
+        Err(err) => panic!("blocking task failed: {err}"),
+    }
+}
@@ -4,9 +4,10 @@ mod summary;
 
 pub use summary::*;
 
+use crate::clock::Clock;
 use std::fmt::Display;
 use std::io::Write;
-use time::macros::format_description;
+use time::{macros::format_description, UtcOffset};
 
 const BOOTSTRAP_VERSION: &str = "5.3.3";
 const BOOTSTRAP_CSS_SRI: &str =
@@ -14,10 +15,29 @@ const BOOTSTRAP_CSS_SRI: &str =
 const BOOTSTRAP_JS_SRI: &str =
     "sha384-0pUGZvbkm6XF6gxjEnlmuGrJXVbNuzT9qBBavbLwCsOGabYfZo0T0to5eqruptLy";
 
+/// A compact stylesheet, covering the styles used by the reports
+const COMPACT_CSS: &str = include_str!("compact.css");
+
 /// Options for rendering reports.
 #[derive(Clone, Debug, Default)]
 pub struct ReportOptions {
     pub bootstrap: Bootstrap,
+    pub theme: Theme,
+    /// The clock providing the date of the report
+    pub clock: Clock,
+}
+
+/// The styling of a report
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Theme {
+    /// Use the Bootstrap resources
+    #[default]
+    Bootstrap,
+    /// Use a compact, built-in stylesheet, embedded into the report
+    ///
+    /// This doesn't require any external resources.
+    Compact,
 }
 
 /// Options for the imported bootstrap resources.
@@ -39,6 +59,13 @@ pub enum Bootstrap {
         /// An optional SRI value for the JS resource
         js_integrity: Option<String>,
     },
+    /// Embed the content of the resources into the report, not requiring any external resources
+    Inline {
+        /// The CSS content
+        css: String,
+        /// The JS content
+        js: Option<String>,
+    },
 }
 
 impl Bootstrap {
@@ -49,6 +76,7 @@ impl Bootstrap {
                 Some(_) => location.clone(),
                 None => format!("{location}/css/bootstrap.min.css" ),
             },
+            Self::Inline { .. } => Default::default(),
         }
     }
 
@@ -56,6 +84,7 @@ impl Bootstrap {
         match self {
             Self::Default => Some(BOOTSTRAP_CSS_SRI.into()),
             Self::Custom { css_integrity, .. } => css_integrity.clone(),
+            Self::Inline { .. } => None,
         }
     }
 
@@ -68,6 +97,7 @@ impl Bootstrap {
                     None => format!("{location}/js/bootstrap.bundle.min.js")
                 }
             }
+            Self::Inline { .. } => Default::default(),
         }
     }
 
@@ -75,8 +105,50 @@ impl Bootstrap {
         match self {
             Self::Default => Some(BOOTSTRAP_JS_SRI.into()),
             Self::Custom { js_integrity, .. } => js_integrity.clone(),
+            Self::Inline { .. } => None,
         }
     }
+
+    /// The elements to add to the head of the document
+    fn head(&self) -> String {
+        match self {
+            Self::Inline { css, .. } => style(css),
+            _ => format!(
+                r#"<link href="{css}" rel="stylesheet" {css_integrity} crossorigin="anonymous">"#,
+                css = html_escape::encode_quoted_attribute(&self.css_location()),
+                css_integrity = integrity(self.css_integrity()),
+            ),
+        }
+    }
+
+    /// The elements to add to the end of the body of the document
+    fn body(&self) -> String {
+        match self {
+            Self::Inline { js, .. } => js
+                .as_deref()
+                .map(|js| format!("<script>{}</script>", js.replace("</script", r"<\/script")))
+                .unwrap_or_default(),
+            _ => format!(
+                r#"<script src="{js}" {js_integrity} crossorigin="anonymous"></script>"#,
+                js = html_escape::encode_quoted_attribute(&self.js_location()),
+                js_integrity = integrity(self.js_integrity()),
+            ),
+        }
+    }
+}
+
+fn style(css: &str) -> String {
+    format!("<style>{}</style>", css.replace("</style", r"<\/style"))
+}
+
+fn integrity(sri: Option<String>) -> String {
+    sri.map(|sri| {
+        format!(
+            r#"integrity="{sri}""#,
+            sri = html_escape::encode_quoted_attribute(&sri)
+        )
+    })
+    .unwrap_or_default()
 }
 
 pub fn render(
@@ -93,7 +165,7 @@ pub fn render(
     <meta charset="utf-8">
     <meta name="viewport" content="width=device-width, initial-scale=1">
     <title>{title}</title>
-    <link href="{css}" rel="stylesheet" {css_integrity} crossorigin="anonymous">
+    {head}
   </head>
   <body>
     <div class="container-fluid">
@@ -102,34 +174,26 @@ pub fn render(
       </h1>
       {report}
     </div>
-    <script src="{js}" {js_integrity} crossorigin="anonymous"></script>
+    {body}
   </body>
 </html>
 "#,
-        date = time::OffsetDateTime::now_local()
-            .unwrap_or_else(|_| time::OffsetDateTime::now_utc())
+        date = options
+            .clock
+            .now_utc()
+            .to_offset(UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC))
             .format(&format_description!(
                 "[year]-[month padding:zero]-[day padding:zero] [hour repr:24]:[minute padding:zero]:[second padding:zero] [offset_hour sign:mandatory]:[offset_minute]"
             ))
             .unwrap_or_else(|_| "Unknown".to_string()),
-        css = html_escape::encode_quoted_attribute(&options.bootstrap.css_location()),
-        js = html_escape::encode_quoted_attribute(&options.bootstrap.js_location()),
-        css_integrity = options
-            .bootstrap
-            .css_integrity()
-            .map(|sri| format!(
-                r#"integrity="{sri}""#,
-                sri = html_escape::encode_quoted_attribute(&sri)
-            ))
-            .unwrap_or_default(),
-        js_integrity = options
-            .bootstrap
-            .css_integrity()
-            .map(|sri| format!(
-                r#"integrity="{sri}""#,
-                sri = html_escape::encode_quoted_attribute(&sri)
-            ))
-            .unwrap_or_default(),
+        head = match options.theme {
+            Theme::Bootstrap => options.bootstrap.head(),
+            Theme::Compact => style(COMPACT_CSS),
+        },
+        body = match options.theme {
+            Theme::Bootstrap => options.bootstrap.body(),
+            Theme::Compact => String::new(),
+        },
         title = html_escape::encode_text(&title.to_string()),
     )?;
 
@@ -1,10 +1,15 @@
 //! Retrieving remote content
 
 use crate::utils::hex::Hex;
+use bytes::{BufMut, Bytes, BytesMut};
 use digest::{Digest, Output};
+use reqwest::{header::HeaderMap, tls::TlsInfo, Response};
+use sha2::Sha256;
 use std::fmt::{Debug, Formatter};
 use std::ops::{Deref, DerefMut};
-use time::OffsetDateTime;
+use std::time::{Duration, Instant};
+use time::{format_description::well_known::Rfc2822, OffsetDateTime};
+use url::Url;
 
 /// The retrieved digest
 #[derive(Clone, PartialEq, Eq)]
@@ -24,6 +29,109 @@ impl<D: Digest> RetrievedDigest<D> {
             Err((&self.expected, actual))
         }
     }
+
+    /// Check if the expected digest was taken from a malformed digest file.
+    pub fn malformed(&self) -> Option<DigestFileError> {
+        match DigestFile::parse(&self.expected) {
+            Err(err) => Some(err),
+            Ok(file) if file.digest.len() != <D as Digest>::output_size() * 2 => {
+                Some(DigestFileError::Length(file.digest.len()))
+            }
+            Ok(_) => None,
+        }
+    }
+}
+
+/// The format of a digest file
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DigestFormat {
+    /// Only the hex encoded digest
+    Bare,
+    /// The digest, followed by the file name, like `sha256sum` writes it
+    Gnu,
+    /// The BSD style: `SHA256 (file) = digest`
+    Bsd,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum DigestFileError {
+    #[error("digest file is empty")]
+    Empty,
+    #[error("unknown digest file format")]
+    Format,
+    #[error("digest is not hex encoded")]
+    NotHex,
+    #[error("digest has an unexpected length: {0}")]
+    Length(usize),
+}
+
+/// The content of a digest file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DigestFile {
+    /// The hex encoded digest, lower-cased
+    pub digest: String,
+    /// The file name, if present
+    pub file: Option<String>,
+    pub format: DigestFormat,
+}
+
+impl DigestFile {
+    /// Parse a digest file, only considering the first non-empty line.
+    pub fn parse(content: &str) -> Result<Self, DigestFileError> {
+        let line = content
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .ok_or(DigestFileError::Empty)?;
+
+        let (digest, file, format) = match line.rsplit_once(" = ") {
+            Some((head, digest)) => {
+                let (_, file) = head
+                    .strip_suffix(')')
+                    .and_then(|head| head.split_once(" ("))
+                    .ok_or(DigestFileError::Format)?;
+                (digest.trim(), Some(file), DigestFormat::Bsd)
+            }
+            None => {
+                let mut parts = line.split_whitespace();
+                let digest = parts.next().ok_or(DigestFileError::Empty)?;
+                match (parts.next(), parts.next()) {
+                    (None, _) => (digest, None, DigestFormat::Bare),
+                    // a leading `*` marks binary mode
+                    (Some(file), None) => (
+                        digest,
+                        Some(file.strip_prefix('*').unwrap_or(file)),
+                        DigestFormat::Gnu,
+                    ),
+                    (Some(_), Some(_)) => return Err(DigestFileError::Format),
+                }
+            }
+        };
+
+        if digest.is_empty() || !digest.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(DigestFileError::NotHex);
+        }
+        if digest.len() % 2 != 0 {
+            return Err(DigestFileError::Length(digest.len()));
+        }
+
+        Ok(Self {
+            digest: digest.to_lowercase(),
+            file: file.map(ToString::to_string),
+            format,
+        })
+    }
+
+    /// Get the expected digest from the content of a digest file.
+    ///
+    /// If the file cannot be parsed, its trimmed content is used, which will fail to validate and
+    /// can later be detected using [`RetrievedDigest::malformed`].
+    pub fn expected(content: &str) -> String {
+        match Self::parse(content) {
+            Ok(file) => file.digest,
+            Err(_) => content.trim().to_string(),
+        }
+    }
 }
 
 impl<D: Digest> Debug for RetrievedDigest<D> {
@@ -42,6 +150,22 @@ pub struct RetrievingDigest<D: Digest> {
     pub current: D,
 }
 
+impl<D: Digest> RetrievingDigest<D> {
+    /// Start building a digest, using the content of a digest file as expected value.
+    pub fn from_digest_file(content: &str) -> Self {
+        Self {
+            expected: DigestFile::expected(content),
+            current: D::new(),
+        }
+    }
+
+    /// Finish the digest, after updating it with the remaining data.
+    pub fn finalize_with(mut self, data: &[u8]) -> RetrievedDigest<D> {
+        self.current.update(data);
+        self.into()
+    }
+}
+
 impl<D> Deref for RetrievingDigest<D>
 where
     D: Digest,
@@ -74,11 +198,191 @@ where
     }
 }
 
+/// Collecting the chunks of a response body.
+///
+/// Chunks are kept as they are received, and only copied once, into a buffer of the final size,
+/// when the body is complete. A body consisting of a single chunk is not copied at all.
+#[derive(Clone, Debug, Default)]
+pub struct RetrievingBody {
+    chunks: Vec<Bytes>,
+    len: usize,
+}
+
+impl RetrievingBody {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, chunk: Bytes) {
+        self.len += chunk.len();
+        self.chunks.push(chunk);
+    }
+
+    /// The number of bytes received so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn freeze(mut self) -> Bytes {
+        match self.chunks.len() {
+            0 => Bytes::new(),
+            1 => self.chunks.pop().unwrap_or_default(),
+            _ => {
+                let mut data = BytesMut::with_capacity(self.len);
+                for chunk in self.chunks {
+                    data.put(chunk);
+                }
+                data.freeze()
+            }
+        }
+    }
+}
+
 /// Metadata of the retrieval process.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct RetrievalMetadata {
     /// Last known modification time
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub last_modification: Option<OffsetDateTime>,
     /// ETag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub etag: Option<String>,
+
+    /// The final URL, after following redirects
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_url: Option<Url>,
+    /// The content type, as reported by the server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// The content length, as reported by the server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_length: Option<u64>,
+    /// The date of the server, when sending the response
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub server_date: Option<OffsetDateTime>,
+    /// The SHA-256 fingerprint of the TLS peer certificate (DER encoded), lower case hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_peer_certificate: Option<String>,
+    /// The timings of the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<RetrievalTimings>,
+}
+
+/// Timings of retrieving a document, in milliseconds.
+///
+/// Resolving the host name and connecting are part of the time to the first byte. They are not
+/// reported on their own, as connections are pooled, and most requests need neither.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetrievalTimings {
+    /// From sending the request, until the response headers were received
+    pub first_byte: u64,
+    /// From sending the request, until the body was received
+    pub total: u64,
+}
+
+/// The timing of a request, attached to its response by the [`crate::fetcher::Fetcher`].
+#[derive(Clone, Copy, Debug)]
+pub struct RequestTiming {
+    /// When the request was sent
+    pub started: Instant,
+    /// Until the response headers were received
+    pub first_byte: Duration,
+}
+
+impl RequestTiming {
+    /// The timings of the request, considering the body to be received by now.
+    pub fn finish(&self) -> RetrievalTimings {
+        RetrievalTimings {
+            first_byte: self.first_byte.as_millis() as u64,
+            total: self.started.elapsed().as_millis() as u64,
+        }
+    }
+
+    /// The timings of the request of a response, if it was sent by the [`crate::fetcher::Fetcher`].
+    pub fn of(response: &Response) -> Option<RetrievalTimings> {
+        response.extensions().get::<Self>().map(Self::finish)
+    }
+}
+
+impl RetrievalMetadata {
+    /// Get the final URL, if it has a different origin than the requested URL.
+    pub fn cross_origin_redirect(&self, requested: &Url) -> Option<&Url> {
+        self.final_url
+            .as_ref()
+            .filter(|url| url.origin() != requested.origin())
+    }
+
+    /// Check if the current metadata (e.g. from a `HEAD` request) indicates an unchanged resource.
+    ///
+    /// The ETag is compared if both sides have one. Otherwise, the last modification timestamp
+    /// and the content length must both be present and equal.
+    pub fn is_unchanged(&self, current: &Self) -> bool {
+        if let (Some(stored), Some(current)) = (&self.etag, &current.etag) {
+            return stored == current;
+        }
+
+        matches!(
+            (self.last_modification, current.last_modification),
+            (Some(stored), Some(current)) if stored == current
+        ) && matches!(
+            (self.content_length, current.content_length),
+            (Some(stored), Some(current)) if stored == current
+        )
+    }
+
+    /// Extract the metadata from an HTTP response.
+    ///
+    /// The TLS peer certificate is only available if the client was created with TLS info
+    /// enabled, which is the case for clients created by the [`crate::fetcher::Fetcher`]. The
+    /// timings only cover the response headers, until they get updated once the body is received.
+    pub fn from_response(response: &Response) -> Self {
+        let tls_peer_certificate = response
+            .extensions()
+            .get::<TlsInfo>()
+            .and_then(TlsInfo::peer_certificate)
+            .map(|cert| Hex(&Sha256::digest(cert)).to_lower());
+
+        Self {
+            final_url: Some(response.url().clone()),
+            content_length: response.content_length(),
+            tls_peer_certificate,
+            timings: RequestTiming::of(response),
+            ..Self::from_headers(response.headers())
+        }
+    }
+
+    /// Extract the metadata from the headers of an HTTP response.
+    ///
+    /// Headers which cannot be parsed are ignored. The content length is taken from the
+    /// `Content-Length` header, which may differ from the length of a decompressed body.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name| headers.get(name).and_then(|s| s.to_str().ok());
+
+        Self {
+            last_modification: header(reqwest::header::LAST_MODIFIED)
+                .and_then(|s| OffsetDateTime::parse(s, &Rfc2822).ok()),
+            etag: header(reqwest::header::ETAG).map(ToString::to_string),
+            final_url: None,
+            content_type: header(reqwest::header::CONTENT_TYPE).map(ToString::to_string),
+            content_length: header(reqwest::header::CONTENT_LENGTH).and_then(|s| s.parse().ok()),
+            server_date: header(reqwest::header::DATE)
+                .and_then(|s| OffsetDateTime::parse(s, &Rfc2822).ok()),
+            tls_peer_certificate: None,
+            timings: None,
+        }
+    }
 }
@@ -0,0 +1,87 @@
+//! Identifying a single run, correlating its traffic, logs, and reports
+
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// The HTTP header carrying the correlation ID of a run
+pub const CORRELATION_HEADER: &str = "X-Correlation-ID";
+
+/// The default user agent of HTTP requests
+pub const USER_AGENT: &str = concat!("csaf-walker/", env!("CARGO_PKG_VERSION"));
+
+/// An ID, correlating the activities of a single run
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct CorrelationId(String);
+
+impl CorrelationId {
+    /// Create a new, random ID.
+    pub fn new() -> Self {
+        Self(uuid::Uuid::new_v4().to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for CorrelationId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for CorrelationId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for CorrelationId {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_string()))
+    }
+}
+
+static RUN_ID: OnceLock<CorrelationId> = OnceLock::new();
+
+/// The correlation ID of the current run (process).
+///
+/// Unless it was set using [`set_run_id`] before, a random ID is generated on first use.
+pub fn run_id() -> &'static CorrelationId {
+    RUN_ID.get_or_init(CorrelationId::new)
+}
+
+/// Set the correlation ID of the current run, e.g. one provided by an orchestrating system.
+///
+/// This fails, returning the provided ID, if the ID was already set or used.
+pub fn set_run_id(id: CorrelationId) -> Result<(), CorrelationId> {
+    RUN_ID.set(id)
+}
+
+/// The outcome of a completed walk
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WalkSummary {
+    /// The correlation ID of the walk
+    pub correlation_id: CorrelationId,
+    /// The time the walk took
+    pub duration: Duration,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_run_id_stable() {
+        let id = run_id().clone();
+        assert_eq!(run_id(), &id);
+        assert_eq!(set_run_id(CorrelationId::new()).map_err(|_| ()), Err(()));
+        assert_eq!(run_id(), &id);
+    }
+}
@@ -2,4 +2,6 @@
 pub enum Error {
     #[error(transparent)]
     OpenId(#[from] openid::error::Error),
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
 }
@@ -6,9 +6,12 @@ mod error;
 
 pub use error::*;
 
-use crate::sender::provider::{TokenInjector, TokenProvider};
+use crate::{
+    run::{run_id, CORRELATION_HEADER},
+    sender::provider::{CredentialsInjector, TokenInjector, TokenProvider},
+};
 use anyhow::Context;
-use reqwest::{header, IntoUrl, Method, RequestBuilder};
+use reqwest::{header, IntoUrl, Method, RequestBuilder, Response, StatusCode, Url};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
@@ -85,6 +88,10 @@ impl HttpSender {
     {
         let mut headers = header::HeaderMap::new();
         headers.insert("User-Agent", header::HeaderValue::from_static(USER_AGENT));
+        headers.insert(
+            CORRELATION_HEADER,
+            header::HeaderValue::from_str(run_id().as_str())?,
+        );
 
         let mut client = reqwest::ClientBuilder::new().default_headers(headers);
 
@@ -127,4 +134,32 @@ impl HttpSender {
             .inject_token(&self.provider)
             .await
     }
+
+    /// Send a request, injecting the token.
+    ///
+    /// The request is built using the `build` function. If it gets rejected as unauthorized, e.g.
+    /// because the token got revoked before it expired, the token is replaced and the request
+    /// gets sent once more.
+    pub async fn execute<F>(&self, method: Method, url: Url, build: F) -> Result<Response, Error>
+    where
+        F: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        let credentials = self.provider.provide_access_token().await?;
+
+        let mut request = self.client.request(method.clone(), url.clone());
+        if let Some(credentials) = &credentials {
+            request = request.credentials(credentials);
+        }
+        let response = build(request).send().await?;
+
+        match (response.status(), credentials) {
+            (StatusCode::UNAUTHORIZED, Some(credentials)) => {
+                log::info!("Request was rejected as unauthorized, retrying with a fresh token");
+                self.provider.invalidate_access_token(&credentials).await?;
+                let request = self.request(method, url).await?;
+                Ok(build(request).send().await?)
+            }
+            _ => Ok(response),
+        }
+    }
 }
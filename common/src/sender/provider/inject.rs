@@ -18,12 +18,23 @@ impl TokenInjector for reqwest::RequestBuilder {
     #[instrument(level = "debug", skip(token_provider), err)]
     async fn inject_token(self, token_provider: &dyn TokenProvider) -> Result<Self, Error> {
         if let Some(credentials) = token_provider.provide_access_token().await? {
-            Ok(match credentials {
-                Credentials::Bearer(token) => self.bearer_auth(token),
-                Credentials::Basic(username, password) => self.basic_auth(username, password),
-            })
+            Ok(self.credentials(&credentials))
         } else {
             Ok(self)
         }
     }
 }
+
+/// Set the authorization header of a request from credentials.
+pub trait CredentialsInjector {
+    fn credentials(self, credentials: &Credentials) -> Self;
+}
+
+impl CredentialsInjector for reqwest::RequestBuilder {
+    fn credentials(self, credentials: &Credentials) -> Self {
+        match credentials {
+            Credentials::Bearer(token) => self.bearer_auth(token),
+            Credentials::Basic(username, password) => self.basic_auth(username, password.as_ref()),
+        }
+    }
+}
@@ -44,6 +44,12 @@ impl Credentials {
 #[async_trait]
 pub trait TokenProvider: Send + Sync {
     async fn provide_access_token(&self) -> Result<Option<Credentials>, Error>;
+
+    /// Invalidate credentials which got rejected by the server, so that the next call to
+    /// [`Self::provide_access_token`] provides fresh ones.
+    async fn invalidate_access_token(&self, _credentials: &Credentials) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -54,6 +60,10 @@ where
     async fn provide_access_token(&self) -> Result<Option<Credentials>, Error> {
         self.as_ref().provide_access_token().await
     }
+
+    async fn invalidate_access_token(&self, credentials: &Credentials) -> Result<(), Error> {
+        self.as_ref().invalidate_access_token(credentials).await
+    }
 }
 
 #[async_trait]
@@ -64,6 +74,10 @@ where
     async fn provide_access_token(&self) -> Result<Option<Credentials>, Error> {
         self.as_ref().provide_access_token().await
     }
+
+    async fn invalidate_access_token(&self, credentials: &Credentials) -> Result<(), Error> {
+        self.as_ref().invalidate_access_token(credentials).await
+    }
 }
 
 #[async_trait]
@@ -71,6 +85,10 @@ impl TokenProvider for Arc<dyn TokenProvider> {
     async fn provide_access_token(&self) -> Result<Option<Credentials>, Error> {
         self.as_ref().provide_access_token().await
     }
+
+    async fn invalidate_access_token(&self, credentials: &Credentials) -> Result<(), Error> {
+        self.as_ref().invalidate_access_token(credentials).await
+    }
 }
 
 /// A token provider which does not provide tokens.
@@ -102,6 +120,13 @@ where
             Some(provider) => provider.provide_access_token().await,
         }
     }
+
+    async fn invalidate_access_token(&self, credentials: &Credentials) -> Result<(), Error> {
+        match self {
+            None => Ok(()),
+            Some(provider) => provider.invalidate_access_token(credentials).await,
+        }
+    }
 }
 
 #[async_trait]
@@ -1,9 +1,13 @@
 use super::{Credentials, Expires, TokenProvider};
 use crate::sender::Error;
+use chrono::Utc;
 use core::fmt::{self, Debug, Formatter};
-use std::{ops::Deref, sync::Arc};
+use std::{ops::Deref, sync::Arc, time::Duration};
 use tokio::sync::RwLock;
 
+/// The delay before trying again, after refreshing the token in the background failed
+const REFRESH_RETRY_DELAY: Duration = Duration::from_secs(30);
+
 #[cfg(feature = "clap")]
 use {anyhow::Context, url::Url};
 
@@ -15,6 +19,7 @@ pub struct OpenIdTokenProviderConfigArguments {
     #[arg(
         id = "oidc_client_id",
         long = "oidc-client-id",
+        env = "CSAF_WALKER_OIDC_CLIENT_ID",
         requires("OpenIdTokenProviderConfigArguments")
     )]
     pub client_id: Option<String>,
@@ -22,6 +27,7 @@ pub struct OpenIdTokenProviderConfigArguments {
     #[arg(
         id = "oidc_client_secret",
         long = "oidc-client-secret",
+        env = "CSAF_WALKER_OIDC_CLIENT_SECRET",
         requires("OpenIdTokenProviderConfigArguments")
     )]
     pub client_secret: Option<String>,
@@ -29,6 +35,7 @@ pub struct OpenIdTokenProviderConfigArguments {
     #[arg(
         id = "oidc_issuer_url",
         long = "oidc-issuer-url",
+        env = "CSAF_WALKER_OIDC_ISSUER_URL",
         requires("OpenIdTokenProviderConfigArguments")
     )]
     pub issuer_url: Option<String>,
@@ -36,6 +43,7 @@ pub struct OpenIdTokenProviderConfigArguments {
     #[arg(
         id = "oidc_refresh_before",
         long = "oidc-refresh-before",
+        env = "CSAF_WALKER_OIDC_REFRESH_BEFORE",
         default_value = "30s"
     )]
     pub refresh_before: humantime::Duration,
@@ -43,13 +51,16 @@ pub struct OpenIdTokenProviderConfigArguments {
     #[arg(
         id = "oidc_tls_insecure",
         long = "oidc-tls-insecure",
-        default_value = "false"
+        env = "CSAF_WALKER_OIDC_TLS_INSECURE",
+        default_value = "false",
+        value_parser = clap::builder::FalseyValueParser::new()
     )]
     pub tls_insecure: bool,
     /// Allows adding additional trust anchors
     #[arg(
         id = "oidc_tls_ca_certificates",
         long = "oidc-tls-ca-certificate",
+        env = "CSAF_WALKER_OIDC_TLS_CA_CERTIFICATE",
         action = clap::ArgAction::Append,
     )]
     pub tls_ca_certificates: Vec<std::path::PathBuf>,
@@ -168,12 +179,21 @@ impl OpenIdTokenProvider {
         .await
         .context("Discover OIDC client")?;
 
-        Ok(Self::new(
+        let provider = Self::new(
             client,
             time::Duration::try_from(<_ as Into<std::time::Duration>>::into(
                 config.refresh_before,
             ))?,
-        ))
+        );
+
+        // fail early on invalid credentials, and keep the token fresh from there on
+        provider
+            .provide_token()
+            .await
+            .context("Fetch initial OIDC token")?;
+        provider.spawn_refresh();
+
+        Ok(provider)
     }
 
     /// return a fresh token, this may be an existing (non-expired) token
@@ -208,30 +228,116 @@ impl OpenIdTokenProvider {
 
         // we hold the write-lock now, and can perform the refresh operation
 
-        let next_token = match lock.take() {
+        let next_token = self.next_token(lock.take()).await?;
+
+        log::debug!("Next token: {:?}", next_token.as_ref());
+
+        let result = next_token.as_ref().clone();
+        lock.replace(next_token);
+
+        // done
+
+        Ok(result)
+    }
+
+    /// Force a fresh token, as the current one got rejected.
+    ///
+    /// If the rejected token was already replaced in the meantime, the current one is kept.
+    async fn replace_rejected_token(&self, rejected: &str) -> Result<(), openid::error::Error> {
+        let mut lock = self.current_token.write().await;
+
+        match lock.deref() {
+            Some(token) if token.as_ref().access_token != rejected => {
+                log::debug!("Rejected token already got replaced");
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        log::debug!("Replacing rejected token ...");
+        let next_token = self.next_token(lock.take()).await?;
+        lock.replace(next_token);
+
+        Ok(())
+    }
+
+    /// Get the next token, refreshing the current one if possible.
+    async fn next_token(
+        &self,
+        current_token: Option<openid::TemporalBearerGuard>,
+    ) -> Result<openid::TemporalBearerGuard, openid::error::Error> {
+        match current_token {
             // if we don't have any token, fetch an initial one
             None => {
                 log::debug!("Fetching initial token... ");
-                self.initial_token().await?
+                self.initial_token().await
             }
             // if we have an expired one, refresh it
-            Some(current_token) => {
+            Some(current_token) if current_token.as_ref().refresh_token.is_some() => {
                 log::debug!("Refreshing token ... ");
-                match current_token.as_ref().refresh_token.is_some() {
-                    true => self.client.refresh_token(current_token, None).await?.into(),
-                    false => self.initial_token().await?,
+                match self.client.refresh_token(current_token, None).await {
+                    Ok(token) => Ok(token.into()),
+                    Err(err) => {
+                        // e.g. the refresh token expired as well, during a long run
+                        log::info!("Failed to refresh token, fetching a new one: {err}");
+                        self.initial_token().await
+                    }
                 }
             }
+            Some(_) => self.initial_token().await,
+        }
+    }
+
+    /// The time until the current token must be refreshed, `None` if it doesn't expire.
+    async fn refresh_delay(&self) -> Option<Duration> {
+        let lock = self.current_token.read().await;
+        let Some(token) = lock.deref() else {
+            return Some(Duration::ZERO);
         };
 
-        log::debug!("Next token: {:?}", next_token.as_ref());
+        let remaining = (token.expires_at()? - Utc::now()).num_seconds();
+        let delay = remaining - self.refresh_before.whole_seconds();
+        // add a second, so that the token is due for a refresh when waking up
+        Some(Duration::from_secs(delay.max(0) as u64 + 1))
+    }
 
-        let result = next_token.as_ref().clone();
-        lock.replace(next_token);
+    /// Refresh the token in the background, before it expires.
+    ///
+    /// Requests don't need to wait for a token refresh that way, and long running runs don't
+    /// depend on the lifetime of the first token. The task ends once the provider got dropped.
+    pub fn spawn_refresh(&self) {
+        let client = self.client.clone();
+        let current_token = Arc::downgrade(&self.current_token);
+        let refresh_before = self.refresh_before;
 
-        // done
+        let provider = move || {
+            Some(Self {
+                client: client.clone(),
+                current_token: current_token.upgrade()?,
+                refresh_before,
+            })
+        };
 
-        Ok(result)
+        tokio::spawn(async move {
+            // only hold on to the token while working with it, so that the provider can be dropped
+            while let Some(current) = provider() {
+                let Some(delay) = current.refresh_delay().await else {
+                    log::debug!("Token doesn't expire, stop refreshing");
+                    break;
+                };
+
+                drop(current);
+                tokio::time::sleep(delay).await;
+
+                let Some(current) = provider() else {
+                    break;
+                };
+                if let Err(err) = current.provide_token().await {
+                    log::warn!("Failed to refresh token: {err}");
+                    tokio::time::sleep(REFRESH_RETRY_DELAY).await;
+                }
+            }
+        });
     }
 
     async fn initial_token(&self) -> Result<openid::TemporalBearerGuard, openid::error::Error> {
@@ -251,4 +357,11 @@ impl TokenProvider for OpenIdTokenProvider {
             .await
             .map(|token| Some(Credentials::Bearer(token.access_token)))?)
     }
+
+    async fn invalidate_access_token(&self, credentials: &Credentials) -> Result<(), Error> {
+        if let Credentials::Bearer(token) = credentials {
+            self.replace_rejected_token(token).await?;
+        }
+        Ok(())
+    }
 }
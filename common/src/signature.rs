@@ -0,0 +1,279 @@
+//! Handling different formats of OpenPGP signatures
+//!
+//! Detached signatures are published either ASCII-armored (`.asc`), or binary (`.sig`). Some
+//! providers also publish cleartext signed documents, embedding the signature. All of them are
+//! normalized into an ASCII-armored, detached signature.
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use bytes::Bytes;
+
+const BEGIN_SIGNATURE: &str = "-----BEGIN PGP SIGNATURE-----";
+const END_SIGNATURE: &str = "-----END PGP SIGNATURE-----";
+const BEGIN_SIGNED_MESSAGE: &str = "-----BEGIN PGP SIGNED MESSAGE-----";
+
+/// Normalize a detached signature into its ASCII-armored form.
+///
+/// The format is detected by its content, not by its file name. Returns [`None`] if the data is
+/// empty.
+pub fn normalize_signature(data: &[u8]) -> Option<String> {
+    let start = data.iter().position(|b| !b.is_ascii_whitespace())?;
+
+    if data[start..].starts_with(BEGIN_SIGNATURE.as_bytes()) {
+        return Some(String::from_utf8_lossy(data).into_owned());
+    }
+
+    Some(armor_signature(data))
+}
+
+/// ASCII-armor a binary signature
+pub fn armor_signature(data: &[u8]) -> String {
+    let mut result = String::with_capacity(data.len() * 4 / 3 + 128);
+
+    result.push_str(BEGIN_SIGNATURE);
+    result.push_str("\n\n");
+
+    let encoded = BASE64_STANDARD.encode(data);
+    for line in encoded.as_bytes().chunks(64) {
+        // base64 is always ASCII
+        result.push_str(&String::from_utf8_lossy(line));
+        result.push('\n');
+    }
+
+    let crc = crc24(data).to_be_bytes();
+    result.push('=');
+    result.push_str(&BASE64_STANDARD.encode(&crc[1..]));
+    result.push('\n');
+
+    result.push_str(END_SIGNATURE);
+    result.push('\n');
+
+    result
+}
+
+/// The CRC-24 checksum of the ASCII armor, as defined by RFC 4880, section 6.1.
+fn crc24(data: &[u8]) -> u32 {
+    const INIT: u32 = 0xB704CE;
+    const POLY: u32 = 0x1864CFB;
+
+    let mut crc = INIT;
+    for b in data {
+        crc ^= (*b as u32) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x1000000 != 0 {
+                crc ^= POLY;
+            }
+        }
+    }
+
+    crc & 0xFFFFFF
+}
+
+/// A reason why a signature file is not a plausible ASCII-armored signature
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum SignatureFileError {
+    #[error("signature file is empty")]
+    Empty,
+    #[error("missing armor header line")]
+    MissingBegin,
+    #[error("missing armor tail line")]
+    MissingEnd,
+    #[error("signature is not base64 encoded")]
+    NotBase64,
+    #[error("armor checksum does not match")]
+    Checksum,
+}
+
+/// Check the syntax of an ASCII-armored signature, without verifying it.
+///
+/// This checks the header and tail lines, the base64 encoding of the body, and the CRC-24
+/// checksum, if present. Armor headers (e.g. `Version: ...`) are skipped.
+pub fn check_armor(signature: &str) -> Result<(), SignatureFileError> {
+    let mut lines = signature.lines().map(str::trim);
+
+    let first = lines
+        .by_ref()
+        .find(|line| !line.is_empty())
+        .ok_or(SignatureFileError::Empty)?;
+    if first != BEGIN_SIGNATURE {
+        return Err(SignatureFileError::MissingBegin);
+    }
+
+    let mut body = String::new();
+    let mut checksum = None;
+    let mut headers = true;
+    let mut ended = false;
+    for line in lines {
+        if line == END_SIGNATURE {
+            ended = true;
+            break;
+        }
+        if headers {
+            if line.is_empty() {
+                headers = false;
+                continue;
+            }
+            if line.contains(": ") {
+                continue;
+            }
+            // tolerate a missing blank line after the header line
+            headers = false;
+        }
+        // base64 padding never starts a line, as the line length is a multiple of four
+        match line.strip_prefix('=') {
+            Some(crc) => checksum = Some(crc),
+            None => body.push_str(line),
+        }
+    }
+    if !ended {
+        return Err(SignatureFileError::MissingEnd);
+    }
+
+    let data = BASE64_STANDARD
+        .decode(body)
+        .map_err(|_| SignatureFileError::NotBase64)?;
+    if data.is_empty() {
+        return Err(SignatureFileError::Empty);
+    }
+
+    if let Some(checksum) = checksum {
+        let checksum = BASE64_STANDARD
+            .decode(checksum)
+            .map_err(|_| SignatureFileError::NotBase64)?;
+        if checksum[..] != crc24(&data).to_be_bytes()[1..] {
+            return Err(SignatureFileError::Checksum);
+        }
+    }
+
+    Ok(())
+}
+
+/// A cleartext signed document, split into its content and a detached signature
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cleartext {
+    /// The content, with the dash-escaping removed
+    pub content: Bytes,
+    /// The ASCII-armored signature
+    pub signature: String,
+}
+
+impl Cleartext {
+    /// Split a cleartext signed document, returns [`None`] if the data is not one.
+    ///
+    /// Unless the content is dash-escaped, or uses `CRLF` line endings, it is a slice of the
+    /// original data, without copying it.
+    pub fn split(data: &Bytes) -> Option<Self> {
+        let text = std::str::from_utf8(data).ok()?;
+        let text = text.trim_start();
+        let text = text.strip_prefix(BEGIN_SIGNED_MESSAGE)?;
+
+        // the remaining text is a suffix of the data, track the offsets of the lines
+        let mut lines = text.split('\n').scan(data.len() - text.len(), |pos, line| {
+            let start = *pos;
+            *pos += line.len() + 1;
+            Some((start, line))
+        });
+
+        // skip the rest of the header line, and the armor headers (e.g. `Hash: SHA256`)
+        lines.next()?;
+        for (_, line) in lines.by_ref() {
+            if line.trim().is_empty() {
+                break;
+            }
+        }
+
+        let mut content = Vec::new();
+        let mut range: Option<(usize, usize)> = None;
+        let mut escaped = false;
+        let mut signed = false;
+        for (start, line) in lines.by_ref() {
+            if line.strip_suffix('\r').unwrap_or(line) == BEGIN_SIGNATURE {
+                signed = true;
+                break;
+            }
+            escaped |= line.ends_with('\r') || line.starts_with("- ");
+            range = Some((range.map_or(start, |(first, _)| first), start + line.len()));
+            content.push(line);
+        }
+        if !signed {
+            return None;
+        }
+
+        // the line break before the signature is not part of the content
+        let content = match (range, escaped) {
+            (None, _) => Bytes::new(),
+            (Some((start, end)), false) => data.slice(start..end),
+            (Some(_), true) => Bytes::from(
+                content
+                    .into_iter()
+                    .map(|line| line.strip_suffix('\r').unwrap_or(line))
+                    .map(|line| line.strip_prefix("- ").unwrap_or(line))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+        };
+
+        let mut armored = String::from(BEGIN_SIGNATURE);
+        armored.push('\n');
+        for (_, line) in lines {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            armored.push_str(line);
+            armored.push('\n');
+            if line == END_SIGNATURE {
+                break;
+            }
+        }
+
+        Some(Self {
+            content,
+            signature: armored,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check_armor() {
+        let armored = armor_signature(b"not really a signature");
+        assert_eq!(check_armor(&armored), Ok(()));
+        assert_eq!(check_armor(&armored.replace('\n', "\r\n")), Ok(()));
+        assert_eq!(
+            check_armor(&armored.replacen("\n\n", "\nVersion: 1\n\n", 1)),
+            Ok(())
+        );
+
+        // without the checksum line
+        let unchecked = armored
+            .lines()
+            .filter(|line| !line.starts_with('='))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(check_armor(&unchecked), Ok(()));
+
+        let other = armor_signature(b"another signature");
+        let crc = other.lines().find(|line| line.starts_with('=')).unwrap();
+        let wrong = armored
+            .lines()
+            .map(|line| if line.starts_with('=') { crc } else { line })
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(check_armor(&wrong), Err(SignatureFileError::Checksum));
+
+        assert_eq!(check_armor(" \n"), Err(SignatureFileError::Empty));
+        assert_eq!(
+            check_armor("<html>Not found</html>"),
+            Err(SignatureFileError::MissingBegin)
+        );
+        assert_eq!(
+            check_armor(&armored.replace(END_SIGNATURE, "")),
+            Err(SignatureFileError::MissingEnd)
+        );
+        assert_eq!(
+            check_armor(&format!("{BEGIN_SIGNATURE}\n\n!!!\n{END_SIGNATURE}\n")),
+            Err(SignatureFileError::NotBase64)
+        );
+    }
+}
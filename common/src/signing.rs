@@ -0,0 +1,103 @@
+//! Signing artifacts, using OpenPGP
+
+use crate::encryption::load_certs;
+use anyhow::anyhow;
+use sequoia_openpgp::{
+    armor,
+    crypto::KeyPair,
+    policy::StandardPolicy,
+    serialize::stream::{Armorer, Message, Signer},
+    Cert, Fingerprint,
+};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Signing artifacts, like reports, using a set of secret keys
+#[derive(Clone, Debug)]
+pub struct Signing {
+    keys: Arc<Vec<Cert>>,
+}
+
+impl PartialEq for Signing {
+    fn eq(&self, other: &Self) -> bool {
+        fingerprints(&self.keys) == fingerprints(&other.keys)
+    }
+}
+
+impl Eq for Signing {}
+
+impl Signing {
+    pub fn new(keys: Vec<Cert>) -> Self {
+        Self {
+            keys: Arc::new(keys),
+        }
+    }
+
+    /// Load (unencrypted) secret keys from keyring files
+    pub fn from_files<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> anyhow::Result<Self> {
+        let mut keys = vec![];
+        for path in paths {
+            keys.extend(load_certs(path.as_ref())?);
+        }
+        Ok(Self::new(keys))
+    }
+
+    /// Create an armored, detached signature of the data, using the first valid signing key
+    pub fn sign_detached(&self, data: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let keypair = self.keypair()?;
+
+        let mut sink = vec![];
+        let message = Message::new(&mut sink);
+        let message = Armorer::new(message).kind(armor::Kind::Signature).build()?;
+        let mut signer = Signer::new(message, keypair).detached().build()?;
+        signer.write_all(data)?;
+        signer.finalize()?;
+
+        Ok(sink)
+    }
+
+    /// Create a binary, detached signature of the data, using the first valid signing key
+    ///
+    /// Returns the fingerprint of the key used, together with the signature.
+    pub fn sign_detached_binary(&self, data: &[u8]) -> anyhow::Result<(Fingerprint, Vec<u8>)> {
+        let keypair = self.keypair()?;
+        let fingerprint = keypair.public().fingerprint();
+
+        let mut sink = vec![];
+        let message = Message::new(&mut sink);
+        let mut signer = Signer::new(message, keypair).detached().build()?;
+        signer.write_all(data)?;
+        signer.finalize()?;
+
+        Ok((fingerprint, sink))
+    }
+
+    fn keypair(&self) -> anyhow::Result<KeyPair> {
+        let policy = StandardPolicy::new();
+
+        self.keys
+            .iter()
+            .flat_map(|cert| {
+                cert.keys()
+                    .with_policy(&policy, None)
+                    .supported()
+                    .alive()
+                    .revoked(false)
+                    .for_signing()
+                    .secret()
+            })
+            .find_map(|key| match key.key().clone().into_keypair() {
+                Ok(pair) => Some(pair),
+                Err(err) => {
+                    log::debug!("Unable to use key {}: {err}", key.fingerprint());
+                    None
+                }
+            })
+            .ok_or_else(|| anyhow!("No valid signing key found"))
+    }
+}
+
+fn fingerprints(certs: &[Cert]) -> Vec<Fingerprint> {
+    certs.iter().map(Cert::fingerprint).collect()
+}
@@ -1,4 +1,6 @@
 //! Handling of detecting changes "since"
+use crate::clock::Clock;
+use crate::deadline::Completion;
 use std::fs::File;
 use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
 use std::ops::Deref;
@@ -56,10 +58,12 @@ impl Deref for Since {
 }
 
 impl Since {
+    /// Create a new instance, taking the start of this run from the clock.
     pub fn new(
         since: Option<impl Into<SystemTime>>,
         since_file: Option<PathBuf>,
         since_file_offset: Duration,
+        clock: &Clock,
     ) -> anyhow::Result<Self> {
         let since = match (since, &since_file) {
             // try file, then fall back to dedicated "since"
@@ -86,7 +90,7 @@ impl Since {
             (None, None) => None,
         };
 
-        let last_run = SystemTime::now();
+        let last_run = clock.now();
 
         Ok(Since {
             since,
@@ -95,6 +99,27 @@ impl Since {
         })
     }
 
+    /// Store the state after a walk, which might have been stopped by its deadline.
+    ///
+    /// If the walk was stopped, the timestamp to resume from is stored instead of the start of
+    /// this run. If that is not known, the stored state is left unchanged.
+    pub fn store_completion(mut self, completion: Option<&Completion>) -> anyhow::Result<()> {
+        match completion {
+            None => self.store(),
+            Some(Completion {
+                resume_since: Some(resume_since),
+                ..
+            }) => {
+                self.last_run = *resume_since;
+                self.store()
+            }
+            Some(_) => {
+                log::warn!("Walk was stopped before discovery completed, keeping since state");
+                Ok(())
+            }
+        }
+    }
+
     pub fn store(self) -> anyhow::Result<()> {
         if let Some(path) = &self.since_file {
             log::info!(
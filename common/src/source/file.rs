@@ -1,4 +1,8 @@
-use crate::retrieve::RetrievedDigest;
+use crate::{
+    offload::Offload,
+    retrieve::{DigestFile, RetrievedDigest},
+    signature::normalize_signature,
+};
 use anyhow::anyhow;
 use bytes::Bytes;
 use digest::Digest;
@@ -16,6 +20,14 @@ pub async fn read_optional(path: impl AsRef<Path>) -> Result<Option<String>, any
     }
 }
 
+pub async fn read_optional_bytes(path: impl AsRef<Path>) -> Result<Option<Vec<u8>>, anyhow::Error> {
+    match tokio::fs::read(path).await {
+        Ok(data) => Ok(Some(data)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
 pub fn to_path(url: &Url) -> Result<PathBuf, anyhow::Error> {
     url.to_file_path()
         .map_err(|()| anyhow!("Failed to convert URL to path: {url}"))
@@ -24,12 +36,15 @@ pub fn to_path(url: &Url) -> Result<PathBuf, anyhow::Error> {
 /// Read the signature file and digests
 ///
 /// The expected locations are:
-/// * `{base}.asc`
+/// * `{base}.asc`, or `{base}.sig` as fallback
 /// * `{base}.sha256`
 /// * `{base}.sha512`
+///
+/// If an [`Offload`] is provided, the digests are calculated on the blocking thread pool.
 pub async fn read_sig_and_digests(
     path: &Path,
     data: &Bytes,
+    offload: Option<&Offload>,
 ) -> anyhow::Result<(
     Option<String>,
     Option<RetrievedDigest<Sha256>>,
@@ -41,29 +56,50 @@ pub async fn read_sig_and_digests(
         read_optional(format!("{}.sha512", path.display())),
     )?;
 
+    let signature = match signature {
+        Some(signature) => Some(signature),
+        None => read_optional_bytes(format!("{}.sig", path.display()))
+            .await?
+            .and_then(|signature| normalize_signature(&signature)),
+    };
+
+    let (sha256, sha512) = match offload {
+        Some(offload) => {
+            let data = data.clone();
+            offload
+                .run(move || calculate_digests(&data, sha256, sha512))
+                .await
+        }
+        None => calculate_digests(data, sha256, sha512),
+    };
+
+    Ok((signature, sha256, sha512))
+}
+
+/// Calculate the digests of the data, for the expected digests which are present
+fn calculate_digests(
+    data: &[u8],
+    sha256: Option<String>,
+    sha512: Option<String>,
+) -> (
+    Option<RetrievedDigest<Sha256>>,
+    Option<RetrievedDigest<Sha512>>,
+) {
     let sha256 = sha256
-        // take the first "word" from the line
-        .and_then(|expected| expected.split(' ').next().map(ToString::to_string))
-        .map(|expected| {
-            let mut actual = Sha256::new();
-            actual.update(data);
-            RetrievedDigest::<Sha256> {
-                expected,
-                actual: actual.finalize(),
-            }
+        .as_deref()
+        .map(DigestFile::expected)
+        .map(|expected| RetrievedDigest::<Sha256> {
+            expected,
+            actual: Sha256::digest(data),
         });
 
     let sha512 = sha512
-        // take the first "word" from the line
-        .and_then(|expected| expected.split(' ').next().map(ToString::to_string))
-        .map(|expected| {
-            let mut actual = Sha512::new();
-            actual.update(data);
-            RetrievedDigest::<Sha512> {
-                expected,
-                actual: actual.finalize(),
-            }
+        .as_deref()
+        .map(DigestFile::expected)
+        .map(|expected| RetrievedDigest::<Sha512> {
+            expected,
+            actual: Sha512::digest(data),
         });
 
-    Ok((signature, sha256, sha512))
+    (sha256, sha512)
 }
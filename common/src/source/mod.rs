@@ -1,2 +1,3 @@
 //! Common helpers for implementing sources
 pub mod file;
+pub mod sidecar;
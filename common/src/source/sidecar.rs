@@ -0,0 +1,90 @@
+//! Learning which sidecar files a distribution doesn't publish
+//!
+//! Probing for signature and digest files, which a provider doesn't publish, multiplies the
+//! number of requests. Once a sidecar file was missing for a number of documents of a
+//! distribution (and never found), probing for it can be skipped.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use url::Url;
+
+/// A type of sidecar file
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Sidecar {
+    /// An ASCII-armored signature
+    Signature,
+    /// A binary signature
+    BinarySignature,
+    Sha256,
+    Sha512,
+}
+
+impl Sidecar {
+    /// The suffix of the sidecar file, appended to the document URL
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Self::Signature => ".asc",
+            Self::BinarySignature => ".sig",
+            Self::Sha256 => ".sha256",
+            Self::Sha512 => ".sha512",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+struct ProbeState {
+    misses: usize,
+    found: bool,
+}
+
+/// Tracking missing sidecar files, by distribution
+#[derive(Debug, Default)]
+pub struct SidecarProbes {
+    /// Number of misses, after which probing stops. [`None`] always probes.
+    limit: Option<usize>,
+    state: Mutex<HashMap<(Url, Sidecar), ProbeState>>,
+}
+
+impl SidecarProbes {
+    pub fn new(limit: impl Into<Option<usize>>) -> Self {
+        Self {
+            limit: limit.into(),
+            state: Default::default(),
+        }
+    }
+
+    /// Check if a sidecar file should be probed for a document of the distribution.
+    pub fn should_probe(&self, distribution: &Url, sidecar: Sidecar) -> bool {
+        let Some(limit) = self.limit else {
+            return true;
+        };
+
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        match state.get(&(distribution.clone(), sidecar)) {
+            Some(probe) => probe.found || probe.misses < limit,
+            None => true,
+        }
+    }
+
+    /// Record the outcome of probing for a sidecar file.
+    pub fn record(&self, distribution: &Url, sidecar: Sidecar, found: bool) {
+        let Some(limit) = self.limit else {
+            return;
+        };
+
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let probe = state.entry((distribution.clone(), sidecar)).or_default();
+
+        if found {
+            probe.found = true;
+        } else {
+            probe.misses += 1;
+            if !probe.found && probe.misses == limit {
+                log::info!(
+                    "Stop probing for '{}' files of {distribution}, after {limit} misses",
+                    sidecar.suffix()
+                );
+            }
+        }
+    }
+}
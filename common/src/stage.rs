@@ -0,0 +1,18 @@
+//! Log targets of the different stages of walking documents
+//!
+//! Events are emitted using [`tracing`], carrying structured fields like `url`, `distribution`,
+//! `stage`, and `outcome`. Without a tracing subscriber, they get forwarded to the [`log`]
+//! facade, using the stage target as log target. This allows filtering the output by stage, e.g.
+//! using `RUST_LOG=walker_common::stage::retrieve=debug`.
+
+/// Fetching remote resources
+pub const FETCH: &str = "walker_common::stage::fetch";
+
+/// Retrieving documents and their companion files
+pub const RETRIEVE: &str = "walker_common::stage::retrieve";
+
+/// Validating digests and signatures of documents
+pub const VALIDATE: &str = "walker_common::stage::validate";
+
+/// Storing documents
+pub const STORE: &str = "walker_common::stage::store";
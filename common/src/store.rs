@@ -1,7 +1,11 @@
-use crate::retrieve::{RetrievalMetadata, RetrievedDigest};
+use crate::{
+    retrieve::{RetrievalMetadata, RetrievedDigest},
+    stage,
+    utils::space::available_space,
+};
 use anyhow::Context;
 use sha2::{Sha256, Sha512};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use tokio::fs;
 
@@ -18,6 +22,31 @@ pub enum StoreError {
     Filename(String),
     #[error("Serialize key error: {0:#}")]
     SerializeKey(anyhow::Error),
+    #[error("Not enough space left on {}: {available} bytes available, {required} bytes required", .path.display())]
+    NoSpace {
+        path: PathBuf,
+        available: u64,
+        required: u64,
+    },
+}
+
+/// Ensure the filesystem of the path has space for the required amount of bytes, while keeping
+/// the reserve free.
+pub fn ensure_space(path: &Path, required: u64, reserve: u64) -> Result<(), StoreError> {
+    let available = available_space(path)
+        .with_context(|| format!("Failed to check available space: {}", path.display()))
+        .map_err(StoreError::Io)?;
+
+    let required = required.saturating_add(reserve);
+    if available < required {
+        return Err(StoreError::NoSpace {
+            path: path.to_path_buf(),
+            available,
+            required,
+        });
+    }
+
+    Ok(())
 }
 
 pub struct Document<'a> {
@@ -39,10 +68,26 @@ pub struct Document<'a> {
     pub no_timestamps: bool,
     #[cfg(any(target_os = "linux", target_os = "macos"))]
     pub no_xattrs: bool,
+
+    /// Encrypt the document before storing it
+    #[cfg(feature = "openpgp")]
+    pub encryption: Option<&'a crate::encryption::Encryption>,
+
+    /// Evidence of the validation, stored alongside the document
+    #[cfg(feature = "openpgp")]
+    pub evidence: Option<&'a crate::validate::evidence::ValidationEvidence>,
+
+    /// Space to keep free on the filesystem, failing instead of storing the document
+    pub min_free_space: Option<u64>,
 }
 
 pub async fn store_document<'a>(file: &Path, document: Document<'a>) -> Result<(), StoreError> {
-    log::debug!("Writing {}", file.display());
+    tracing::debug!(
+        target: stage::STORE,
+        stage = "store",
+        file = %file.display(),
+        "Writing"
+    );
 
     if let Some(parent) = file.parent() {
         fs::create_dir_all(parent)
@@ -51,33 +96,74 @@ pub async fn store_document<'a>(file: &Path, document: Document<'a>) -> Result<(
             .map_err(StoreError::Io)?;
     }
 
-    fs::write(&file, document.data)
+    #[cfg(feature = "openpgp")]
+    let encrypted = match document.encryption {
+        Some(encryption) => Some(
+            encryption
+                .encrypt(document.data)
+                .with_context(|| format!("Failed to encrypt advisory: {}", file.display()))
+                .map_err(StoreError::Io)?,
+        ),
+        None => None,
+    };
+    #[cfg(feature = "openpgp")]
+    let data = encrypted.as_deref().unwrap_or(document.data);
+    #[cfg(not(feature = "openpgp"))]
+    let data = document.data;
+
+    // fail before writing, instead of leaving a partially written document behind
+    if let Some(reserve) = document.min_free_space {
+        ensure_space(file.parent().unwrap_or(file), data.len() as u64, reserve)?;
+    }
+
+    write_replace(file, data)
         .await
         .with_context(|| format!("Failed to write advisory: {}", file.display()))
         .map_err(StoreError::Io)?;
 
     if let Some(sha256) = &document.sha256 {
         let file = format!("{}.sha256", file.display());
-        fs::write(&file, &sha256.expected)
+        write_replace(file.as_ref(), sha256.expected.as_bytes())
             .await
             .with_context(|| format!("Failed to write checksum: {file}"))
             .map_err(StoreError::Io)?;
     }
     if let Some(sha512) = &document.sha512 {
         let file = format!("{}.sha512", file.display());
-        fs::write(&file, &sha512.expected)
+        write_replace(file.as_ref(), sha512.expected.as_bytes())
             .await
             .with_context(|| format!("Failed to write checksum: {file}"))
             .map_err(StoreError::Io)?;
     }
     if let Some(sig) = &document.signature {
         let file = format!("{}.asc", file.display());
-        fs::write(&file, &sig)
+        write_replace(file.as_ref(), sig.as_bytes())
             .await
             .with_context(|| format!("Failed to write signature: {file}"))
             .map_err(StoreError::Io)?;
     }
 
+    let file_metadata = format!("{}.metadata", file.display());
+    let metadata = serde_json::to_vec_pretty(document.metadata)
+        .context("Failed to serialize retrieval metadata")
+        .map_err(StoreError::Io)?;
+    write_replace(file_metadata.as_ref(), &metadata)
+        .await
+        .with_context(|| format!("Failed to write retrieval metadata: {file_metadata}"))
+        .map_err(StoreError::Io)?;
+
+    #[cfg(feature = "openpgp")]
+    if let Some(evidence) = document.evidence {
+        let file_evidence = format!("{}.evidence", file.display());
+        let evidence = serde_json::to_vec_pretty(evidence)
+            .context("Failed to serialize validation evidence")
+            .map_err(StoreError::Io)?;
+        write_replace(file_evidence.as_ref(), &evidence)
+            .await
+            .with_context(|| format!("Failed to write validation evidence: {file_evidence}"))
+            .map_err(StoreError::Io)?;
+    }
+
     if !document.no_timestamps {
         // We use the retrieval metadata timestamp as file timestamp. If that's not available, then
         // we use the change entry timestamp.
@@ -108,3 +194,274 @@ pub async fn store_document<'a>(file: &Path, document: Document<'a>) -> Result<(
 
     Ok(())
 }
+
+/// Load the retrieval metadata, stored alongside a document.
+///
+/// Returns [`None`] if there is no metadata file, or it cannot be parsed.
+pub async fn load_metadata(file: &Path) -> Option<RetrievalMetadata> {
+    let file_metadata = format!("{}.metadata", file.display());
+    let data = fs::read(&file_metadata).await.ok()?;
+
+    serde_json::from_slice(&data)
+        .map_err(|err| {
+            tracing::debug!(
+                target: stage::STORE,
+                stage = "store",
+                file = %file_metadata,
+                error = %err,
+                "Failed to parse retrieval metadata"
+            )
+        })
+        .ok()
+}
+
+/// Load the validation evidence, stored alongside a document.
+///
+/// Returns [`None`] if there is no evidence file, or it cannot be parsed.
+#[cfg(feature = "openpgp")]
+pub async fn load_evidence(file: &Path) -> Option<crate::validate::evidence::ValidationEvidence> {
+    let file_evidence = format!("{}.evidence", file.display());
+    let data = fs::read(&file_evidence).await.ok()?;
+
+    serde_json::from_slice(&data)
+        .map_err(|err| {
+            tracing::debug!(
+                target: stage::STORE,
+                stage = "store",
+                file = %file_evidence,
+                error = %err,
+                "Failed to parse validation evidence"
+            )
+        })
+        .ok()
+}
+
+/// Replace the content of a file, by writing to a temporary file and renaming it.
+///
+/// This never modifies an existing file in place, so that readers never see a partially written
+/// file, and files which are hard linked from a different location are left untouched.
+pub async fn write_replace(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let tmp = replace_tmp(path);
+
+    fs::write(&tmp, data).await?;
+    fs::rename(&tmp, path).await
+}
+
+/// Replace the content of a file, like [`write_replace`], blocking the current thread.
+pub fn write_replace_sync(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let tmp = replace_tmp(path);
+
+    std::fs::write(&tmp, data)?;
+    std::fs::rename(&tmp, path)
+}
+
+fn replace_tmp(path: &Path) -> PathBuf {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    tmp.into()
+}
+
+/// Stage updates of a store, promoting them at the end of a successful run.
+///
+/// The staging directory is a sibling of the target directory, initialized with hard links to
+/// the current content of the target. Stored files must be replaced (using [`write_replace`] or
+/// [`write_replace_sync`]), never modified in place, so that the content of the target stays
+/// untouched until the staging directory gets promoted.
+///
+/// If the target is a symbolic link (on Unix), promoting atomically replaces the link with one
+/// pointing to the staged content. Otherwise, the directories get swapped by renaming them, which
+/// leaves a short moment where the target doesn't exist.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Staging {
+    target: PathBuf,
+    staging: PathBuf,
+}
+
+impl Staging {
+    pub fn new(target: impl Into<PathBuf>) -> Self {
+        let target = target.into();
+        let staging = Self::sibling(&target, "staging");
+        Self { target, staging }
+    }
+
+    /// The directory to store into
+    pub fn path(&self) -> &Path {
+        &self.staging
+    }
+
+    fn sibling(target: &Path, suffix: &str) -> PathBuf {
+        let mut name = target
+            .file_name()
+            .map(|name| name.to_owned())
+            .unwrap_or_else(|| "store".into());
+        name.push(".");
+        name.push(suffix);
+        target.with_file_name(name)
+    }
+
+    /// Prepare the staging directory, discarding leftovers of a previous, failed run.
+    pub fn prepare(&self) -> anyhow::Result<&Path> {
+        if self.staging.exists() {
+            log::info!(
+                "Removing stale staging directory: {}",
+                self.staging.display()
+            );
+            std::fs::remove_dir_all(&self.staging).with_context(|| {
+                format!(
+                    "Failed to remove staging directory: {}",
+                    self.staging.display()
+                )
+            })?;
+        }
+
+        if self.target.exists() {
+            log::info!("Linking current content into: {}", self.staging.display());
+            link_tree(&self.target, &self.staging).with_context(|| {
+                format!(
+                    "Failed to link {} into staging directory {}",
+                    self.target.display(),
+                    self.staging.display()
+                )
+            })?;
+        } else {
+            std::fs::create_dir_all(&self.staging).with_context(|| {
+                format!(
+                    "Failed to create staging directory: {}",
+                    self.staging.display()
+                )
+            })?;
+        }
+
+        Ok(&self.staging)
+    }
+
+    /// Promote the staged content to the target.
+    pub fn promote(self) -> anyhow::Result<()> {
+        log::info!("Promoting staged content to: {}", self.target.display());
+
+        #[cfg(unix)]
+        if self.target.is_symlink() {
+            return self.promote_link();
+        }
+
+        let old = Self::sibling(&self.target, "old");
+        if old.exists() {
+            std::fs::remove_dir_all(&old)
+                .with_context(|| format!("Failed to remove: {}", old.display()))?;
+        }
+
+        if self.target.exists() {
+            std::fs::rename(&self.target, &old).with_context(|| {
+                format!(
+                    "Failed to move {} to {}",
+                    self.target.display(),
+                    old.display()
+                )
+            })?;
+        }
+        std::fs::rename(&self.staging, &self.target).with_context(|| {
+            format!(
+                "Failed to move {} to {}",
+                self.staging.display(),
+                self.target.display()
+            )
+        })?;
+
+        if old.exists() {
+            std::fs::remove_dir_all(&old)
+                .with_context(|| format!("Failed to remove: {}", old.display()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Promote by pointing the target link to a new generation directory.
+    #[cfg(unix)]
+    fn promote_link(self) -> anyhow::Result<()> {
+        let previous = std::fs::canonicalize(&self.target)
+            .with_context(|| format!("Failed to resolve: {}", self.target.display()))?;
+
+        let generation = Self::sibling(
+            &self.target,
+            &SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis()
+                .to_string(),
+        );
+        std::fs::rename(&self.staging, &generation).with_context(|| {
+            format!(
+                "Failed to move {} to {}",
+                self.staging.display(),
+                generation.display()
+            )
+        })?;
+
+        // replace the link atomically, by renaming a new link over it
+        let link = Self::sibling(&self.target, "link");
+        let _ = std::fs::remove_file(&link);
+        std::os::unix::fs::symlink(&generation, &link)
+            .with_context(|| format!("Failed to create link: {}", link.display()))?;
+        std::fs::rename(&link, &self.target)
+            .with_context(|| format!("Failed to replace link: {}", self.target.display()))?;
+
+        std::fs::remove_dir_all(&previous)
+            .with_context(|| format!("Failed to remove: {}", previous.display()))?;
+
+        Ok(())
+    }
+}
+
+/// Recreate a directory tree, hard linking all files.
+fn link_tree(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(target)?;
+
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let target = target.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            link_tree(&entry.path(), &target)?;
+        } else {
+            std::fs::hard_link(entry.path(), target)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_staging_isolation() {
+        let dir = tempfile::tempdir().expect("must create temporary directory");
+        let target = dir.path().join("store");
+        std::fs::create_dir_all(target.join("a")).expect("must create directory");
+        std::fs::write(target.join("a/doc.json"), b"live").expect("must write");
+
+        let staging = Staging::new(&target);
+        let path = staging.prepare().expect("must prepare").to_path_buf();
+        assert_eq!(
+            std::fs::read(path.join("a/doc.json")).expect("must read"),
+            b"live"
+        );
+
+        write_replace_sync(&path.join("a/doc.json"), b"staged").expect("must write");
+        assert_eq!(
+            std::fs::read(path.join("a/doc.json")).expect("must read"),
+            b"staged"
+        );
+        // the hard link is broken, not written through
+        assert_eq!(
+            std::fs::read(target.join("a/doc.json")).expect("must read"),
+            b"live"
+        );
+
+        staging.promote().expect("must promote");
+        assert_eq!(
+            std::fs::read(target.join("a/doc.json")).expect("must read"),
+            b"staged"
+        );
+    }
+}
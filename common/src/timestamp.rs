@@ -0,0 +1,100 @@
+//! Obtaining RFC 3161 timestamps
+
+use anyhow::{anyhow, bail, Context};
+use bytes::Bytes;
+use sha2::{Digest, Sha256};
+use url::Url;
+
+/// The DER encoded algorithm identifier of SHA-256, including the (empty) parameters
+const SHA256_ALGORITHM: [u8; 15] = [
+    0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05, 0x00,
+];
+
+/// Create a DER encoded `TimeStampReq`, for the SHA-256 digest of the data.
+///
+/// The request asks the authority to include its certificate in the response.
+pub fn timestamp_request(data: &[u8]) -> Vec<u8> {
+    let digest = Sha256::digest(data);
+
+    let mut imprint = Vec::with_capacity(51);
+    imprint.extend_from_slice(&SHA256_ALGORITHM);
+    imprint.extend_from_slice(&[0x04, digest.len() as u8]);
+    imprint.extend_from_slice(&digest);
+
+    let mut content = vec![
+        // version
+        0x02, 0x01, 0x01,
+    ];
+    // message imprint
+    content.extend_from_slice(&[0x30, imprint.len() as u8]);
+    content.extend_from_slice(&imprint);
+    // certReq
+    content.extend_from_slice(&[0x01, 0x01, 0xff]);
+
+    let mut request = vec![0x30, content.len() as u8];
+    request.extend_from_slice(&content);
+    request
+}
+
+/// Request a timestamp for data, from a time stamping authority.
+///
+/// Returns the DER encoded `TimeStampResp`, after checking that the timestamp was granted.
+pub async fn request_timestamp(
+    client: &reqwest::Client,
+    authority: &Url,
+    data: &[u8],
+) -> anyhow::Result<Bytes> {
+    let response = client
+        .post(authority.clone())
+        .header(reqwest::header::CONTENT_TYPE, "application/timestamp-query")
+        .body(timestamp_request(data))
+        .send()
+        .await
+        .with_context(|| format!("Failed to request timestamp from: {authority}"))?
+        .error_for_status()?
+        .bytes()
+        .await?;
+
+    match response_status(&response)? {
+        // granted, or granted with modifications
+        0 | 1 => Ok(response),
+        status => bail!("Timestamp request rejected by authority (status: {status})"),
+    }
+}
+
+/// Extract the status of the `PKIStatusInfo` from a `TimeStampResp`.
+fn response_status(response: &[u8]) -> anyhow::Result<u8> {
+    let invalid = || anyhow!("Invalid timestamp response");
+
+    // TimeStampResp ::= SEQUENCE { status PKIStatusInfo, ... }
+    let response = enter(response, 0x30).ok_or_else(invalid)?;
+    // PKIStatusInfo ::= SEQUENCE { status PKIStatus, ... }
+    let info = enter(response, 0x30).ok_or_else(invalid)?;
+    // PKIStatus ::= INTEGER
+    match enter(info, 0x02).ok_or_else(invalid)? {
+        [status] => Ok(*status),
+        _ => Err(invalid()),
+    }
+}
+
+/// Get the content of the DER element at the start of the data, if it has the expected tag.
+fn enter(data: &[u8], tag: u8) -> Option<&[u8]> {
+    let (&actual, data) = data.split_first()?;
+    if actual != tag {
+        return None;
+    }
+
+    let (&len, data) = data.split_first()?;
+    let (len, data) = match len {
+        0..=0x7f => (len as usize, data),
+        0x81..=0x84 => {
+            let num = (len & 0x7f) as usize;
+            let (len, data) = (data.get(..num)?, data.get(num..)?);
+            let len = len.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+            (len, data)
+        }
+        _ => return None,
+    };
+
+    data.get(..len)
+}
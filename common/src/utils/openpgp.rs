@@ -1,7 +1,14 @@
 //! Helpers for working with "OpenPGP".
 use bytes::Bytes;
-use sequoia_openpgp::{cert::CertParser, parse::Parse, Cert};
+use sequoia_openpgp::{
+    cert::CertParser,
+    parse::Parse,
+    policy::{Policy, StandardPolicy},
+    types::RevocationStatus,
+    Cert,
+};
 use std::fmt::Debug;
+use std::time::{Duration, SystemTime};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -19,6 +26,108 @@ pub struct PublicKey {
     pub raw: Bytes,
 }
 
+/// An issue of a key, which causes (or soon will cause) signatures to fail validation.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum KeyIssue {
+    #[error("Key {fingerprint} is revoked")]
+    Revoked { fingerprint: String },
+    #[error("Key {fingerprint} expired at {}", humantime::format_rfc3339_seconds(*expired))]
+    Expired {
+        fingerprint: String,
+        expired: SystemTime,
+    },
+    #[error("Key {fingerprint} expires at {}", humantime::format_rfc3339_seconds(*expires))]
+    Expiring {
+        fingerprint: String,
+        expires: SystemTime,
+    },
+    #[error("Key {fingerprint} has no valid signing (sub)key")]
+    NoSigningKey { fingerprint: String },
+    #[error("Key {fingerprint} is invalid: {reason}")]
+    Invalid { fingerprint: String, reason: String },
+}
+
+impl KeyIssue {
+    /// Check if the issue only warns about an upcoming problem.
+    pub fn is_upcoming(&self) -> bool {
+        matches!(self, Self::Expiring { .. })
+    }
+}
+
+impl PublicKey {
+    /// Check the keys for being revoked, expired, or otherwise unusable for validating signatures.
+    ///
+    /// Keys expiring within `warn_within` are reported as well. The policy date is the same as for
+    /// validating signatures, while expiration and revocation are checked at the current time.
+    pub fn issues(
+        &self,
+        policy_date: Option<SystemTime>,
+        warn_within: Option<Duration>,
+    ) -> Vec<KeyIssue> {
+        let policy = match policy_date {
+            Some(time) => StandardPolicy::at(time),
+            None => StandardPolicy::new(),
+        };
+        let now = SystemTime::now();
+
+        self.certs
+            .iter()
+            .filter_map(|cert| cert_issue(cert, &policy, now, warn_within))
+            .collect()
+    }
+}
+
+fn cert_issue(
+    cert: &Cert,
+    policy: &dyn Policy,
+    now: SystemTime,
+    warn_within: Option<Duration>,
+) -> Option<KeyIssue> {
+    let fingerprint = cert.fingerprint().to_hex();
+
+    if let RevocationStatus::Revoked(_) = cert.revocation_status(policy, now) {
+        return Some(KeyIssue::Revoked { fingerprint });
+    }
+
+    let valid = match cert.with_policy(policy, now) {
+        Ok(valid) => valid,
+        Err(err) => {
+            return Some(KeyIssue::Invalid {
+                fingerprint,
+                reason: err.to_string(),
+            })
+        }
+    };
+
+    if let Some(expires) = valid.primary_key().key_expiration_time() {
+        if expires <= now {
+            return Some(KeyIssue::Expired {
+                fingerprint,
+                expired: expires,
+            });
+        }
+        if warn_within.is_some_and(|warn_within| expires <= now + warn_within) {
+            return Some(KeyIssue::Expiring {
+                fingerprint,
+                expires,
+            });
+        }
+    }
+
+    if valid
+        .keys()
+        .for_signing()
+        .alive()
+        .revoked(false)
+        .next()
+        .is_none()
+    {
+        return Some(KeyIssue::NoSigningKey { fingerprint });
+    }
+
+    None
+}
+
 pub fn validate_keys(bytes: Bytes, fingerprint: Option<&str>) -> Result<PublicKey, Error> {
     let certs = CertParser::from_bytes(&bytes)?.collect::<Result<Vec<_>, _>>()?;
 
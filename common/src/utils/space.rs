@@ -0,0 +1,68 @@
+//! Checking the space of a filesystem
+
+use std::io;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// The space available to the current user, on the filesystem of the path.
+///
+/// The path doesn't need to exist yet, in which case its closest existing ancestor is used.
+pub fn available_space(path: &Path) -> io::Result<u64> {
+    let existing = path
+        .ancestors()
+        .find(|path| path.exists())
+        .unwrap_or(Path::new("."));
+
+    fs2::available_space(existing)
+}
+
+/// The total size of all files in a directory, recursively.
+///
+/// Returns zero if the directory doesn't exist.
+pub fn dir_size(path: &Path) -> io::Result<u64> {
+    let mut size = 0;
+
+    for entry in WalkDir::new(path) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) if err.io_error().map(|err| err.kind()) == Some(io::ErrorKind::NotFound) => {
+                continue
+            }
+            Err(err) => return Err(err.into()),
+        };
+        if entry.file_type().is_file() {
+            size += entry.metadata()?.len();
+        }
+    }
+
+    Ok(size)
+}
+
+/// Format a number of bytes for humans, using binary units.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    match unit {
+        0 => format!("{bytes} B"),
+        _ => format!("{value:.1} {}", UNITS[unit]),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 << 30), "5.0 GiB");
+    }
+}
@@ -0,0 +1,104 @@
+//! Evidence of a successful validation
+use crate::retrieve::RetrievedDigest;
+use digest::Digest;
+use sha2::{Sha256, Sha512};
+use std::time::SystemTime;
+use time::OffsetDateTime;
+
+/// The evidence of validating a document, allowing to persist how it was verified.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationEvidence {
+    /// The digest which matched the document, the strongest one if there were multiple
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<DigestEvidence>,
+    /// The fingerprints of the keys with a valid signature, empty if the document wasn't signed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signers: Vec<String>,
+    /// The OpenPGP policy date used for verifying the signatures
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub policy_date: Option<OffsetDateTime>,
+    /// When the document was validated
+    #[serde(with = "time::serde::rfc3339")]
+    pub validated: OffsetDateTime,
+    /// The integrity checks the document failed, only populated in the collect validation mode
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<ValidationFailure>,
+}
+
+/// A failed integrity check, recorded instead of failing the validation of the document
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum ValidationFailure {
+    /// The digest didn't match the content of the document
+    #[error("Digest mismatch - expected: {expected}, actual: {actual}")]
+    DigestMismatch { expected: String, actual: String },
+    /// The signature could not be verified
+    #[error("Invalid signature: {reason}")]
+    Signature { reason: String },
+    /// A digest or signature file was malformed
+    #[error("Malformed sidecar file: {reason}")]
+    MalformedSidecar { reason: String },
+}
+
+/// A digest matching the content of a document
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DigestEvidence {
+    /// The digest algorithm, e.g. `sha512`
+    pub algorithm: String,
+    /// The (lower-case, hex encoded) digest value
+    pub value: String,
+}
+
+impl ValidationEvidence {
+    /// Create the evidence of a document validated just now.
+    pub fn new(signers: Vec<String>, policy_date: Option<SystemTime>) -> Self {
+        Self {
+            digest: None,
+            signers,
+            policy_date: policy_date.map(OffsetDateTime::from),
+            validated: OffsetDateTime::now_utc(),
+            failures: vec![],
+        }
+    }
+
+    /// Record the failed integrity checks.
+    pub fn with_failures(mut self, failures: Vec<ValidationFailure>) -> Self {
+        self.failures = failures;
+        self
+    }
+
+    /// Check if the document passed all integrity checks.
+    pub fn is_trusted(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Record the strongest of the matching digests.
+    pub fn with_digests(
+        mut self,
+        sha256: &Option<RetrievedDigest<Sha256>>,
+        sha512: &Option<RetrievedDigest<Sha512>>,
+    ) -> Self {
+        self.digest = DigestEvidence::matching("sha512", sha512)
+            .or_else(|| DigestEvidence::matching("sha256", sha256));
+        self
+    }
+}
+
+impl DigestEvidence {
+    /// Create the evidence of a digest, in case it matched the content.
+    ///
+    /// A malformed digest file, ignored in lenient mode, doesn't match.
+    fn matching<D: Digest>(algorithm: &str, digest: &Option<RetrievedDigest<D>>) -> Option<Self> {
+        let digest = digest.as_ref()?;
+        digest.validate().ok()?;
+        Some(Self {
+            algorithm: algorithm.to_string(),
+            value: digest.expected.clone(),
+        })
+    }
+}
@@ -1,14 +1,55 @@
 //! Validation
+pub mod evidence;
 pub mod openpgp;
+pub mod policy;
+pub mod sidecar;
 pub mod source;
+pub mod tls;
 
-use std::time::SystemTime;
+use crate::offload::Offload;
+use std::time::{Duration, SystemTime};
+use time::{Date, Month, UtcOffset};
 
 #[non_exhaustive]
 #[derive(Clone, Debug, Default)]
 pub struct ValidationOptions {
     /// time for policy checks
     pub validation_date: Option<SystemTime>,
+
+    /// report documents violating the TLS requirements of trusted providers
+    pub strict_tls: bool,
+
+    /// only warn about malformed digest files, instead of reporting a digest mismatch
+    pub lenient_digests: bool,
+
+    /// run the validation on the blocking thread pool, instead of the async runtime
+    pub offload: Option<Offload>,
+
+    /// warn about keys expiring within this duration
+    pub key_expiry_warning: Option<Duration>,
+
+    /// the number of different keys, which must have a valid signature on a document (at least one)
+    pub required_signers: usize,
+
+    /// only accept documents with one of these TLP labels, all if empty
+    pub allowed_tlps: Vec<String>,
+
+    /// HTTP client errors (e.g. `404`), which only fail retrieving a single document
+    pub allowed_client_errors: Vec<u16>,
+
+    /// how to handle documents failing the digest or signature checks
+    pub mode: ValidationMode,
+}
+
+/// How to handle documents failing the integrity checks (digests and signatures)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum ValidationMode {
+    /// Fail the validation of the document
+    #[default]
+    Strict,
+    /// Pass on the document, recording the failed checks in its validation evidence
+    Collect,
 }
 
 impl ValidationOptions {
@@ -20,4 +61,60 @@ impl ValidationOptions {
         self.validation_date = validation_date.into();
         self
     }
+
+    pub fn strict_tls(mut self, strict_tls: bool) -> Self {
+        self.strict_tls = strict_tls;
+        self
+    }
+
+    pub fn lenient_digests(mut self, lenient_digests: bool) -> Self {
+        self.lenient_digests = lenient_digests;
+        self
+    }
+
+    pub fn offload(mut self, offload: impl Into<Option<Offload>>) -> Self {
+        self.offload = offload.into();
+        self
+    }
+
+    pub fn required_signers(mut self, required_signers: usize) -> Self {
+        self.required_signers = required_signers;
+        self
+    }
+
+    pub fn key_expiry_warning(mut self, key_expiry_warning: impl Into<Option<Duration>>) -> Self {
+        self.key_expiry_warning = key_expiry_warning.into();
+        self
+    }
+
+    pub fn allowed_tlps<I>(mut self, allowed_tlps: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        self.allowed_tlps = Vec::from_iter(allowed_tlps);
+        self
+    }
+
+    pub fn allowed_client_errors<I>(mut self, allowed_client_errors: I) -> Self
+    where
+        I: IntoIterator<Item = u16>,
+    {
+        self.allowed_client_errors = Vec::from_iter(allowed_client_errors);
+        self
+    }
+
+    pub fn mode(mut self, mode: ValidationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+/// The policy date, which still considers OpenPGP v3 signatures valid.
+pub fn v3_signatures_date() -> SystemTime {
+    SystemTime::from(
+        Date::from_calendar_date(2007, Month::January, 1)
+            .expect("policy date is known to parse")
+            .midnight()
+            .assume_offset(UtcOffset::UTC),
+    )
 }
@@ -15,8 +15,19 @@ use sequoia_openpgp::{
 };
 use std::fmt::Debug;
 
+/// The outcome of verifying the signatures of a document.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerifiedSignatures {
+    /// The fingerprints of the keys, which have a valid signature
+    pub signers: Vec<String>,
+    /// The signatures which could not be verified, e.g. because of an unknown key
+    pub failed: Vec<String>,
+}
+
 struct Helper<'a> {
     keys: &'a [PublicKey],
+    required_signers: usize,
+    result: &'a mut VerifiedSignatures,
 }
 
 impl<'a> VerificationHelper for Helper<'a> {
@@ -25,32 +36,49 @@ impl<'a> VerificationHelper for Helper<'a> {
     }
 
     fn check(&mut self, structure: MessageStructure) -> sequoia_openpgp::Result<()> {
-        let mut good = false;
-
         for (i, layer) in structure.into_iter().enumerate() {
             log::trace!("Message ({i}): {layer:?}");
 
             match (i, layer) {
-                (0, MessageLayer::SignatureGroup { results }) => match results.into_iter().next() {
-                    Some(Ok(_)) => good = true,
-                    Some(Err(err)) => {
-                        return Err(sequoia_openpgp::Error::from(err).into());
-                    }
-                    None => {
+                (0, MessageLayer::SignatureGroup { results }) => {
+                    if results.is_empty() {
                         bail!("No signature");
                     }
-                },
+
+                    // a document may carry signatures of multiple parties, e.g. vendor and aggregator
+                    for result in results {
+                        match result {
+                            Ok(good) => {
+                                let signer = good.ka.cert().fingerprint().to_hex();
+                                if !self.result.signers.contains(&signer) {
+                                    self.result.signers.push(signer);
+                                }
+                            }
+                            Err(err) => self.result.failed.push(err.to_string()),
+                        }
+                    }
+                }
                 _ => {
                     bail!("Unexpected message structure");
                 }
             }
         }
 
-        if !good {
-            bail!("Signature verification failed")
+        let signers = self.result.signers.len();
+        match (signers, self.result.failed.as_slice()) {
+            (0, [err]) => bail!("{err}"),
+            (0, _) => bail!(
+                "Signature verification failed: {}",
+                self.result.failed.join(", ")
+            ),
+            (n, failed) if n < self.required_signers => bail!(
+                "Only {n} of {required} required signers verified ({signers}), failed: {failed}",
+                required = self.required_signers,
+                signers = self.result.signers.join(", "),
+                failed = failed.join(", "),
+            ),
+            _ => Ok(()),
         }
-
-        Ok(())
     }
 }
 
@@ -86,25 +114,37 @@ impl<'a> Policy for LoggingPolicy<'a> {
     }
 }
 
+/// Validate a detached signature of the data.
+///
+/// The data is verified in place, streaming it through the verifier, without copying it into an
+/// intermediate buffer. All signatures are verified, and at least the number of signers required
+/// by the options must have a valid signature. Signatures of unknown keys don't fail the
+/// validation on their own, but are reported as failed.
 pub fn validate_signature(
     options: &ValidationOptions,
     keys: &[PublicKey],
     signature: &str,
     data: impl AsRef<[u8]>,
-) -> Result<(), anyhow::Error> {
+) -> Result<VerifiedSignatures, anyhow::Error> {
     // TODO: we could move this into the context and re-use
     let policy = match options.validation_date {
         Some(time) => StandardPolicy::at(time),
         None => StandardPolicy::new(),
     };
     let policy = LoggingPolicy(policy);
+    let mut result = VerifiedSignatures::default();
     let mut verifier = DetachedVerifierBuilder::from_bytes(&signature)?.with_policy(
         &policy,
         None,
-        Helper { keys },
+        Helper {
+            keys,
+            required_signers: options.required_signers.max(1),
+            result: &mut result,
+        },
     )?;
 
     verifier.verify_bytes(data)?;
+    drop(verifier);
 
-    Ok(())
+    Ok(result)
 }
@@ -0,0 +1,124 @@
+//! Validation policies, loaded from a file
+use crate::validate::{v3_signatures_date, ValidationOptions};
+use anyhow::Context;
+use std::path::Path;
+use time::OffsetDateTime;
+
+/// A validation policy, bundling the requirements of a deployment in a single (YAML) file.
+///
+/// ```yaml
+/// trust:
+///   requiredSigners: 2
+///   strictTls: true
+///   keyExpiryWarning: 14d
+/// allowedTlps: [WHITE, GREEN]
+/// requiredChecks: [check_csaf_base, check_history]
+/// thresholds:
+///   maxErrors: 0
+///   maxWarnings: 10
+/// allowedClientErrors: [404, 410]
+/// ```
+///
+/// The trust requirements, allowed TLP labels, and allowed client errors are part of the
+/// [`ValidationOptions`]. The required checks and thresholds are up to the application running
+/// the checks.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct ValidationPolicy {
+    /// Requirements for trusting a document
+    pub trust: TrustPolicy,
+    /// Only accept documents with one of these TLP labels, all if empty
+    pub allowed_tlps: Vec<String>,
+    /// Only run these checks on a document, all if empty
+    pub required_checks: Vec<String>,
+    /// Limits of findings, before considering a run failed
+    pub thresholds: Thresholds,
+    /// HTTP client errors (e.g. `404`), which only fail retrieving a single document
+    pub allowed_client_errors: Vec<u16>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct TrustPolicy {
+    /// OpenPGP policy date
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub policy_date: Option<OffsetDateTime>,
+    /// Consider OpenPGP v3 signatures valid, conflicts with the policy date
+    pub v3_signatures: bool,
+    /// Require documents to be retrieved using HTTPS with a valid certificate chain
+    pub strict_tls: bool,
+    /// Only warn about malformed digest files
+    pub lenient_digests: bool,
+    /// The number of different keys, which must have a valid signature on a document
+    pub required_signers: usize,
+    /// Warn about keys expiring within this duration, in humantime duration format
+    pub key_expiry_warning: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct Thresholds {
+    /// The maximum number of errors
+    pub max_errors: Option<usize>,
+    /// The maximum number of warnings
+    pub max_warnings: Option<usize>,
+}
+
+impl ValidationPolicy {
+    /// Load a policy from a YAML file.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open validation policy: {}", path.display()))?;
+        serde_yaml::from_reader(std::io::BufReader::new(file))
+            .with_context(|| format!("Failed to parse validation policy: {}", path.display()))
+    }
+}
+
+impl TryFrom<&ValidationPolicy> for ValidationOptions {
+    type Error = anyhow::Error;
+
+    fn try_from(policy: &ValidationPolicy) -> Result<Self, Self::Error> {
+        let trust = &policy.trust;
+
+        let validation_date = match (trust.policy_date, trust.v3_signatures) {
+            (Some(_), true) => {
+                anyhow::bail!("The policy date conflicts with accepting v3 signatures")
+            }
+            (_, true) => Some(v3_signatures_date()),
+            (Some(date), _) => Some(date.into()),
+            (None, false) => None,
+        };
+
+        let key_expiry_warning = trust
+            .key_expiry_warning
+            .as_deref()
+            .map(humantime::parse_duration)
+            .transpose()
+            .context("Invalid key expiry warning")?;
+
+        if let Some(code) = policy
+            .allowed_client_errors
+            .iter()
+            .find(|code| !(400..500).contains(*code))
+        {
+            anyhow::bail!("Not a client error: {code}");
+        }
+
+        Ok(Self::new()
+            .validation_date(validation_date)
+            .strict_tls(trust.strict_tls)
+            .lenient_digests(trust.lenient_digests)
+            .required_signers(trust.required_signers)
+            .key_expiry_warning(key_expiry_warning)
+            .allowed_tlps(policy.allowed_tlps.clone())
+            .allowed_client_errors(policy.allowed_client_errors.clone()))
+    }
+}
+
+impl ValidationOptions {
+    /// Create the options from a validation policy file, see [`ValidationPolicy`].
+    pub fn from_policy_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Self::try_from(&ValidationPolicy::load(path)?)
+    }
+}
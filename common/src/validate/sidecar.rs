@@ -0,0 +1,41 @@
+//! Checking the plausibility of sidecar files
+//!
+//! A provider might serve something other than a digest or signature for a sidecar file, like an
+//! HTML error page with a `200` status code. Verifying the document against such a file would
+//! report a digest mismatch, or an invalid signature, hiding the actual problem.
+
+use crate::{
+    retrieve::{DigestFileError, RetrievedDigest},
+    signature::{check_armor, SignatureFileError},
+};
+use sha2::{Sha256, Sha512};
+
+/// A malformed sidecar file
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum SidecarError {
+    #[error("malformed signature file: {0}")]
+    Signature(#[from] SignatureFileError),
+    #[error("malformed SHA-256 digest file: {0}")]
+    Sha256(DigestFileError),
+    #[error("malformed SHA-512 digest file: {0}")]
+    Sha512(DigestFileError),
+}
+
+/// Check that the digest files, if present, contain a digest of the expected length.
+pub fn check_digests(
+    sha256: &Option<RetrievedDigest<Sha256>>,
+    sha512: &Option<RetrievedDigest<Sha512>>,
+) -> Result<(), SidecarError> {
+    if let Some(err) = sha256.as_ref().and_then(RetrievedDigest::malformed) {
+        return Err(SidecarError::Sha256(err));
+    }
+    if let Some(err) = sha512.as_ref().and_then(RetrievedDigest::malformed) {
+        return Err(SidecarError::Sha512(err));
+    }
+    Ok(())
+}
+
+/// Check that the signature is a syntactically valid ASCII-armored signature.
+pub fn check_signature(signature: &str) -> Result<(), SidecarError> {
+    Ok(check_armor(signature)?)
+}
@@ -1,6 +1,7 @@
 //! Working with the source in the context of validation.
 use crate::{fetcher, fetcher::Fetcher, utils, utils::openpgp::PublicKey};
 use bytes::Bytes;
+use sha1::{Digest, Sha1};
 use std::fmt::{Debug, Display};
 use std::future::Future;
 use url::Url;
@@ -75,3 +76,137 @@ impl KeySource for Fetcher {
             .map_err(KeySourceError::OpenPgp)
     }
 }
+
+/// Alternative locations to retrieve a key from, in case its URL in the provider metadata fails.
+///
+/// Keys are looked up by their fingerprint, and must match it. So a key without a fingerprint in
+/// the provider metadata can't make use of the fallback.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeyFallback {
+    /// Email addresses to look up in their Web Key Directory (WKD)
+    pub wkd: Vec<String>,
+    /// HKPS keyservers to look up the fingerprint
+    pub keyservers: Vec<Url>,
+}
+
+impl KeyFallback {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_wkd(mut self, email: impl Into<String>) -> Self {
+        self.wkd.push(email.into());
+        self
+    }
+
+    pub fn add_keyserver(mut self, keyserver: Url) -> Self {
+        self.keyservers.push(keyserver);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wkd.is_empty() && self.keyservers.is_empty()
+    }
+
+    /// The URLs to try for a fingerprint, in order.
+    ///
+    /// For each email address, the "advanced" and the "direct" WKD URL, followed by the keyservers.
+    pub fn urls(&self, fingerprint: &str) -> Vec<Url> {
+        let wkd = self.wkd.iter().flat_map(|email| wkd_urls(email));
+        let keyservers = self.keyservers.iter().filter_map(|keyserver| {
+            let mut url = keyserver.join("/pks/lookup").ok()?;
+            url.query_pairs_mut()
+                .append_pair("op", "get")
+                .append_pair("options", "mr")
+                .append_pair("search", &format!("0x{fingerprint}"));
+            Some(url)
+        });
+
+        wkd.chain(keyservers).collect()
+    }
+}
+
+/// The WKD URLs of an email address, using the "advanced" and the "direct" method.
+fn wkd_urls(email: &str) -> Vec<Url> {
+    let Some((local, domain)) = email.rsplit_once('@') else {
+        return vec![];
+    };
+    let domain = domain.to_lowercase();
+    let hash = zbase32(&Sha1::digest(local.to_lowercase()));
+
+    [
+        format!("https://openpgpkey.{domain}/.well-known/openpgpkey/{domain}/hu/{hash}"),
+        format!("https://{domain}/.well-known/openpgpkey/hu/{hash}"),
+    ]
+    .into_iter()
+    .filter_map(|url| {
+        let mut url = Url::parse(&url).ok()?;
+        url.query_pairs_mut().append_pair("l", local);
+        Some(url)
+    })
+    .collect()
+}
+
+/// Encode data using z-base-32, as required for the WKD hash.
+fn zbase32(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"ybndrfg8ejkmcpqxot1uwisza345h769";
+
+    let mut result = String::with_capacity((data.len() * 8).div_ceil(5));
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for b in data {
+        buffer = (buffer << 8) | u32::from(*b);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            result.push(ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        result.push(ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+
+    result
+}
+
+impl Fetcher {
+    /// Load a public key, using the fallback in case its URL can't be retrieved.
+    ///
+    /// Only a failure to retrieve the key triggers the fallback. A key which was retrieved, but is
+    /// invalid or doesn't match the fingerprint, is reported as is.
+    pub async fn load_public_key_with_fallback(
+        &self,
+        key: Key<'_>,
+        fallback: &KeyFallback,
+    ) -> Result<PublicKey, KeySourceError<fetcher::Error>> {
+        let err = match self.load_public_key(key.clone()).await {
+            Ok(key) => return Ok(key),
+            Err(KeySourceError::Source(err)) => err,
+            Err(err) => return Err(err),
+        };
+
+        let Some(fingerprint) = key.fingerprint else {
+            return Err(KeySourceError::Source(err));
+        };
+
+        for url in fallback.urls(fingerprint) {
+            match self.fetch::<Option<Bytes>>(url.clone()).await {
+                Ok(Some(bytes)) => match utils::openpgp::validate_keys(bytes, Some(fingerprint)) {
+                    Ok(public_key) => {
+                        log::warn!(
+                            "Failed to load key from {} ({err}), loaded {fingerprint} from: {url}",
+                            key.url
+                        );
+                        return Ok(public_key);
+                    }
+                    Err(err) => log::info!("Invalid fallback key from {url}: {err}"),
+                },
+                Ok(None) => log::debug!("Fallback key not found: {url}"),
+                Err(err) => log::info!("Failed to load fallback key from {url}: {err}"),
+            }
+        }
+
+        Err(KeySourceError::Source(err))
+    }
+}
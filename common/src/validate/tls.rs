@@ -0,0 +1,34 @@
+//! Checking the transport security of retrieved documents
+
+use crate::retrieve::RetrievalMetadata;
+use url::Url;
+
+/// A violation of the TLS requirements
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum TlsViolation {
+    #[error("Document was not retrieved using HTTPS: {0}")]
+    NotHttps(Url),
+    #[error("Document was retrieved without a verified TLS peer certificate: {0}")]
+    MissingPeerCertificate(Url),
+}
+
+/// Check the retrieval metadata against the TLS requirements of a trusted provider.
+///
+/// Only documents which were retrieved through HTTP(S) are checked. As the certificate chain is
+/// verified by the client, a recorded peer certificate also means that the chain was valid.
+pub fn check(metadata: &RetrievalMetadata) -> Result<(), TlsViolation> {
+    let Some(url) = &metadata.final_url else {
+        // not retrieved through HTTP, e.g. from a file source
+        return Ok(());
+    };
+
+    if url.scheme() != "https" {
+        return Err(TlsViolation::NotHttps(url.clone()));
+    }
+
+    if metadata.tls_peer_certificate.is_none() {
+        return Err(TlsViolation::MissingPeerCertificate(url.clone()));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+#[cfg(feature = "csaf-validator-lib")]
+#[path = "src/verification/check/csaf_validator_lib/ops.rs"]
+#[allow(unused)]
+mod ops;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "csaf-validator-lib")]
+    csaf_validator_lib::snapshot()?;
+
+    Ok(())
+}
+
+/// Create a V8 snapshot of the evaluated `csaf-validator-lib` bundle.
+///
+/// Evaluating the bundle takes a noticeable amount of time. Doing it once, at build time, allows
+/// the runtime to start from the snapshot instead. When cross-compiling, no snapshot is created,
+/// and the crate falls back to evaluating the bundle at runtime.
+#[cfg(feature = "csaf-validator-lib")]
+mod csaf_validator_lib {
+    use super::ops;
+    use deno_core::{v8, JsRuntimeForSnapshot, RuntimeOptions};
+    use std::path::PathBuf;
+
+    const BASE: &str = "src/verification/check/csaf_validator_lib";
+
+    pub fn snapshot() -> Result<(), Box<dyn std::error::Error>> {
+        // use the same bundle the crate would have used, based on its build profile
+        let bundle = match std::env::var_os("CARGO_CFG_DEBUG_ASSERTIONS") {
+            Some(_) => "bundle.debug.js",
+            None => "bundle.js",
+        };
+
+        println!("cargo:rerun-if-changed={BASE}/js/{bundle}");
+        println!("cargo:rerun-if-changed={BASE}/ops.rs");
+        println!("cargo:rustc-check-cfg=cfg(csaf_validator_lib_snapshot)");
+
+        // the snapshot is created by the V8 of the host, which only works for the same target
+        if std::env::var_os("HOST") != std::env::var_os("TARGET") {
+            println!("cargo:warning=Cross-compiling, not creating a csaf-validator-lib snapshot");
+            return Ok(());
+        }
+
+        let code = std::fs::read_to_string(format!("{BASE}/js/{bundle}"))?;
+
+        let mut runtime = JsRuntimeForSnapshot::new(RuntimeOptions {
+            extensions: vec![ops::extension()],
+            ..Default::default()
+        });
+
+        // the bundle is an IIFE, a classic script
+        runtime.execute_script("internal://bundle.js", code)?;
+
+        let state: ops::FunctionsState = runtime.op_state().borrow_mut().take();
+        let runner = state
+            .runner_func
+            .ok_or("runner function was not initialized")?;
+
+        // the op state is not part of the snapshot, keep the runner on the global object
+        {
+            let context = runtime.main_context();
+            let scope = &mut runtime.handle_scope();
+            let global = context.open(scope).global(scope);
+            let key = v8::String::new(scope, ops::RUNNER).ok_or("unable to create key")?;
+            let runner = v8::Local::new(scope, runner);
+            global.set(scope, key.into(), runner.into());
+        }
+
+        let snapshot = runtime.snapshot();
+
+        let out = PathBuf::from(std::env::var_os("OUT_DIR").ok_or("missing OUT_DIR")?);
+        std::fs::write(out.join("csaf_validator_lib.snapshot"), snapshot)?;
+        println!("cargo:rustc-cfg=csaf_validator_lib_snapshot");
+
+        Ok(())
+    }
+}
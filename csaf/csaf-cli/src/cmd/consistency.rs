@@ -0,0 +1,50 @@
+use csaf_walker::{
+    consistency,
+    metadata::{MetadataRetriever, MetadataSource},
+};
+use walker_common::cli::client::ClientArguments;
+
+/// Cross-check the different listings (ROLIE feeds, index.txt, changes.csv) of a provider.
+#[derive(clap::Args, Debug)]
+pub struct Consistency {
+    #[command(flatten)]
+    client: ClientArguments,
+
+    /// The provider to check
+    source: String,
+}
+
+impl Consistency {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let fetcher = self.client.new_fetcher().await?;
+        let metadata = MetadataRetriever::new(self.source)
+            .load_metadata(&fetcher)
+            .await?;
+
+        let report = consistency::check(&fetcher, &metadata).await?;
+
+        for (listing, count) in &report.listings {
+            println!("{listing}: {count} documents");
+        }
+
+        if report.is_consistent() {
+            println!("All listings are consistent");
+            return Ok(());
+        }
+
+        println!();
+        for (url, missing) in &report.missing {
+            let missing = missing
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!("{url}: missing from {missing}");
+        }
+
+        anyhow::bail!(
+            "{} documents are missing from some listings",
+            report.missing.len()
+        )
+    }
+}
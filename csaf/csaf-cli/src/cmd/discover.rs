@@ -1,11 +1,20 @@
 use crate::{
     cmd::{DiscoverArguments, FilterArguments},
-    common::filter,
+    common::{aggregator, filter},
 };
+use csaf_walker::discover::{AggregatorOrigin, DiscoverConfig, DistributionContext, RolieEntry};
+use csaf_walker::queue::DirectoryQueue;
 use csaf_walker::source::new_source;
+use csaf_walker::source::ListEntry;
+use csaf_walker::visitors::filter::FilterConfig;
 use csaf_walker::{discover::DiscoveredAdvisory, walker::Walker};
-use std::convert::Infallible;
-use walker_common::{cli::client::ClientArguments, progress::Progress};
+use reqwest::Url;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use walker_common::{
+    cli::client::ClientArguments, clock::Clock, fetcher::FetcherOptions, progress::Progress,
+};
 
 /// Discover advisories, just lists the URLs.
 #[derive(clap::Args, Debug)]
@@ -18,18 +27,60 @@ pub struct Discover {
 
     #[command(flatten)]
     filter: FilterArguments,
+
+    /// The output format
+    #[arg(long, env = "CSAF_WALKER_DISCOVER_OUTPUT", value_enum, default_value_t = Output::Text)]
+    output: Output,
+
+    /// Push the discovered advisories into a work queue (a directory), instead of printing them. The queue is processed by the `work` command.
+    #[arg(long, env = "CSAF_WALKER_QUEUE")]
+    queue: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Output {
+    /// One URL per line
+    Text,
+    /// One JSON object per line, with all metadata of the discovered advisory
+    Json,
 }
 
 impl Discover {
     pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
-        Walker::new(new_source(self.discover, self.client).await?)
+        let output = self.output;
+        let queue = self.queue.map(DirectoryQueue::new).transpose()?;
+
+        let discover = DiscoverConfig::from(self.discover);
+        let options = FetcherOptions::from(self.client);
+        let aggregator = aggregator(&discover, options.clone()).await?;
+
+        Walker::new(new_source(discover, options).await?)
             .with_progress(progress)
+            .with_aggregator(aggregator)
             .walk(filter(
-                self.filter,
-                |discovered: DiscoveredAdvisory| async move {
-                    println!("{}", discovered.url);
+                FilterConfig::try_from(self.filter)?,
+                Clock::system(),
+                move |discovered: DiscoveredAdvisory| {
+                    let queue = queue.clone();
+                    async move {
+                        if let Some(queue) = queue {
+                            return queue.push(&ListEntry::from(&discovered));
+                        }
 
-                    Ok::<_, Infallible>(())
+                        match output {
+                            Output::Text => println!("{}", discovered.url),
+                            Output::Json => {
+                                match serde_json::to_string(&Record::new(&discovered)) {
+                                    Ok(json) => println!("{json}"),
+                                    Err(err) => {
+                                        log::warn!("Failed to encode {}: {err}", discovered.url)
+                                    }
+                                }
+                            }
+                        }
+
+                        Ok::<_, anyhow::Error>(())
+                    }
                 },
             ))
             .await?;
@@ -37,3 +88,90 @@ impl Discover {
         Ok(())
     }
 }
+
+/// A discovered advisory, as emitted by the JSON output
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Record<'a> {
+    url: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retrieval_url: Option<&'a str>,
+    distribution: Distribution<'a>,
+    modified: String,
+    signature: String,
+    sha256: String,
+    sha512: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rolie: Option<Rolie<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    aggregator: Option<&'a AggregatorOrigin>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Distribution<'a> {
+    r#type: &'static str,
+    url: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tlp_label: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rolie<'a> {
+    title: &'a str,
+    published: String,
+    updated: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_length: Option<u64>,
+}
+
+impl<'a> Record<'a> {
+    fn new(advisory: &'a DiscoveredAdvisory) -> Self {
+        let rolie = advisory.rolie.as_ref();
+
+        // ROLIE entries may link to their sidecar files, otherwise the default locations are used
+        let sidecar = |link: Option<&Url>, extension: &str| {
+            link.map(ToString::to_string)
+                .unwrap_or_else(|| format!("{}.{extension}", advisory.url))
+        };
+
+        Self {
+            url: advisory.url.as_str(),
+            retrieval_url: advisory.retrieval_url.as_ref().map(Url::as_str),
+            distribution: Distribution {
+                r#type: match advisory.context.as_ref() {
+                    DistributionContext::Directory(_) => "directory",
+                    DistributionContext::Feed(_) => "feed",
+                },
+                url: advisory.context.url().as_str(),
+                tlp_label: advisory
+                    .context
+                    .tlp_label()
+                    .and_then(|label| serde_json::to_value(label).ok())
+                    .and_then(|label| label.as_str().map(ToString::to_string)),
+            },
+            modified: timestamp(advisory.modified),
+            signature: sidecar(rolie.and_then(|entry| entry.signature.as_ref()), "asc"),
+            sha256: sidecar(rolie.and_then(|entry| entry.sha256.as_ref()), "sha256"),
+            sha512: sidecar(rolie.and_then(|entry| entry.sha512.as_ref()), "sha512"),
+            rolie: rolie.map(Rolie::new),
+            aggregator: advisory.aggregator.as_deref(),
+        }
+    }
+}
+
+impl<'a> Rolie<'a> {
+    fn new(entry: &'a RolieEntry) -> Self {
+        Self {
+            title: &entry.title,
+            published: timestamp(entry.published.into()),
+            updated: timestamp(entry.updated.into()),
+            content_length: entry.content_length,
+        }
+    }
+}
+
+fn timestamp(time: SystemTime) -> String {
+    humantime::format_rfc3339(time).to_string()
+}
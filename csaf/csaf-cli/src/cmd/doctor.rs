@@ -0,0 +1,71 @@
+use crate::cmd::parse_size;
+use anyhow::Context;
+use reqwest::Url;
+use std::path::PathBuf;
+use walker_common::{
+    cli::client::ClientArguments,
+    doctor::{check_connectivity, check_disk_space, check_xattrs, Diagnosis, Status},
+};
+
+/// Check the runtime environment for common problems.
+#[derive(clap::Args, Debug)]
+pub struct Doctor {
+    #[command(flatten)]
+    client: ClientArguments,
+
+    /// The output directory to check, defaults to the local directory.
+    #[arg(short, long, env = "CSAF_WALKER_DATA")]
+    data: Option<PathBuf>,
+
+    /// The URL to check the connection and the clock against.
+    #[arg(
+        long,
+        default_value = "https://www.redhat.com/.well-known/csaf/provider-metadata.json"
+    )]
+    probe_url: Url,
+
+    /// The expected size of the mirror (e.g. `20GiB`). Defaults to the size of the existing mirror.
+    #[arg(long, value_parser = parse_size)]
+    expected_size: Option<u64>,
+}
+
+impl Doctor {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let data = match self.data {
+            Some(data) => data,
+            None => std::env::current_dir().context("Get current working directory")?,
+        };
+
+        let mut diagnoses = vec![
+            check_xattrs(&data),
+            check_disk_space(&data, self.expected_size),
+        ];
+
+        let fetcher = self.client.new_fetcher().await?;
+        diagnoses.extend(check_connectivity(&fetcher, self.probe_url).await);
+
+        for diagnosis in &diagnoses {
+            print(diagnosis);
+        }
+
+        let failed = diagnoses
+            .iter()
+            .filter(|diagnosis| diagnosis.status == Status::Failed)
+            .count();
+        if failed > 0 {
+            anyhow::bail!("{failed} of {} checks failed", diagnoses.len());
+        }
+
+        Ok(())
+    }
+}
+
+fn print(diagnosis: &Diagnosis) {
+    println!(
+        "[{}] {}: {}",
+        diagnosis.status, diagnosis.check, diagnosis.message
+    );
+    if let Some(hint) = &diagnosis.hint {
+        println!("    hint: {hint}");
+    }
+}
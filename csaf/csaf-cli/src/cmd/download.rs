@@ -1,14 +1,24 @@
 use crate::{
-    cmd::{DiscoverArguments, FilterArguments, SkipArguments, StoreArguments},
+    cmd::{
+        CrossCheckArguments, DiscoverArguments, FilterArguments, JournalArguments,
+        RetentionArguments, SkipArguments, StoreArguments,
+    },
     common::walk_visitor,
 };
+use csaf_walker::visitors::filter::FilterConfig;
 use csaf_walker::{
     discover::DiscoverConfig,
     retrieve::RetrievingVisitor,
-    visitors::{skip::SkipExistingVisitor, store::StoreVisitor},
+    visitors::{
+        cross_check::CrossCheckVisitor,
+        journal::JournalVisitor,
+        skip::{SkipExistingVisitor, SkipVisitor},
+        store::StoreVisitor,
+    },
 };
+use std::path::PathBuf;
 use walker_common::{
-    cli::{client::ClientArguments, runner::RunnerArguments},
+    cli::{client::ClientArguments, lock::LockArguments, runner::RunnerArguments},
     progress::Progress,
     since::Since,
 };
@@ -33,12 +43,46 @@ pub struct Download {
 
     #[command(flatten)]
     store: StoreArguments,
+
+    #[command(flatten)]
+    lock: LockArguments,
+
+    #[command(flatten)]
+    retention: RetentionArguments,
+
+    #[command(flatten)]
+    cross_check: CrossCheckArguments,
+
+    #[command(flatten)]
+    journal: JournalArguments,
 }
 
 impl Download {
-    pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
+    /// Only process the listed documents, recording those failing again in the journal.
+    ///
+    /// The documents are processed regardless of when they changed, and the since-state isn't
+    /// updated.
+    pub fn retry(mut self, list: PathBuf, journal: JournalArguments) -> Self {
+        self.discover.list = Some(list);
+        self.skip.since = None;
+        self.skip.since_file = None;
+        self.journal = journal;
+        self
+    }
+
+    pub async fn run(mut self, progress: Progress) -> anyhow::Result<()> {
+        let target = self.store.base()?;
+        let _locks = self
+            .lock
+            .lock(&target, self.skip.since_file.as_deref())
+            .await?;
+
+        let staging = self.store.prepare_staging()?;
         let store: StoreVisitor = self.store.try_into()?;
+        let finisher = store.finisher();
         let base = store.base.clone();
+        let layout = store.layout;
+        let retention_base = base.clone();
 
         let since = Since::new(
             self.skip.since,
@@ -47,28 +91,72 @@ impl Download {
                 .since_file_offset
                 .map(|d| d.into())
                 .unwrap_or_default(),
+            &self.runner.clock(),
         )?;
 
-        walk_visitor(
+        let head = match self.skip.head_check {
+            true => Some(self.client.clone().new_fetcher().await?),
+            false => None,
+        };
+
+        let offload = self.runner.offload();
+        let budget = self.runner.memory_budget();
+        let cross_check = self.cross_check.config(self.client.clone()).await?;
+        let predicates = self.skip.predicates(&self.client).await?;
+        let journal = self.journal.journal();
+        let journal_visitor = journal.clone();
+
+        let result = walk_visitor(
             progress,
             self.client,
-            DiscoverConfig::from(self.discover).with_since(since.since),
-            self.filter,
+            DiscoverConfig::from(self.discover)
+                .with_since(since.since)
+                .with_offload(offload),
+            FilterConfig::try_from(self.filter)?,
             self.runner,
             move |source| async move {
                 let base = base.clone();
-                let visitor = { RetrievingVisitor::new(source.clone(), store) };
+                let visitor = {
+                    RetrievingVisitor::new(
+                        source.clone(),
+                        CrossCheckVisitor {
+                            visitor: JournalVisitor {
+                                visitor: store,
+                                journal: journal_visitor,
+                            },
+                            config: cross_check,
+                        },
+                    )
+                    .with_memory_budget(budget)
+                };
 
-                Ok(SkipExistingVisitor {
-                    visitor,
+                Ok(SkipVisitor {
+                    visitor: SkipExistingVisitor {
+                        visitor,
+                        output: base.clone(),
+                        layout,
+                        since: since.since,
+                        head,
+                    },
                     output: base,
-                    since: since.since,
+                    layout,
+                    predicates,
                 })
             },
         )
-        .await?;
+        .await;
+
+        self.journal.store(journal.as_deref())?;
+        let completion = result?;
+
+        finisher.finish()?;
+        self.retention.apply(&retention_base)?;
+
+        if let Some(staging) = staging {
+            staging.promote()?;
+        }
 
-        since.store()?;
+        since.store_completion(completion.as_ref())?;
 
         Ok(())
     }
@@ -0,0 +1,255 @@
+use anyhow::Context;
+use bytes::Bytes;
+use colored_json::write_colored_json;
+use csaf_walker::{
+    discover::DiscoverConfig,
+    retrieve::{RetrievedAdvisory, RetrievingVisitor},
+    source::{ListEntry, SourceDescriptor},
+    validation::{ValidatedAdvisory, ValidationError, ValidationVisitor},
+    walker::Walker,
+};
+use reqwest::Url;
+use std::io::{stdout, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use walker_common::{
+    cli::{client::ClientArguments, validation::ValidationArguments},
+    fetcher::FetcherOptions,
+    progress::Progress,
+    validate::{sidecar::SidecarError, ValidationOptions},
+};
+
+/// Fetch a single document
+#[derive(clap::Args, Debug)]
+pub struct Fetch {
+    #[command(flatten)]
+    client: ClientArguments,
+
+    #[command(flatten)]
+    validation: ValidationArguments,
+
+    /// URL of the document
+    url: Url,
+
+    /// Fetch and verify the digest and signature files of the document, using the keys of this provider (domain, or URL to the provider metadata), and print the verdict.
+    #[arg(long, env = "CSAF_WALKER_VERIFY")]
+    verify: Option<String>,
+
+    /// Write the document to this file, instead of printing it.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// The result of checking a sidecar file
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+enum Check {
+    /// The sidecar file was present and matched the document
+    Valid,
+    /// The sidecar file was present, but didn't match the document
+    Invalid,
+    /// The sidecar file was present, but isn't a plausible digest or signature
+    Malformed,
+    /// The sidecar file wasn't present
+    Missing,
+    /// The check wasn't performed, as the verification failed before
+    Unchecked,
+}
+
+/// The verdict of verifying a document
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Verdict {
+    url: Url,
+    valid: bool,
+    sha256: Check,
+    sha512: Check,
+    signature: Check,
+    /// The fingerprints of the keys with a valid signature
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    signers: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl Verdict {
+    fn new(url: Url) -> Self {
+        Self {
+            url,
+            valid: false,
+            sha256: Check::Unchecked,
+            sha512: Check::Unchecked,
+            signature: Check::Unchecked,
+            signers: vec![],
+            error: None,
+        }
+    }
+
+    /// Record the digests of a retrieved document.
+    fn digests(mut self, retrieved: &RetrievedAdvisory) -> Self {
+        self.sha256 = match &retrieved.sha256 {
+            Some(digest) if digest.validate().is_ok() => Check::Valid,
+            Some(digest) if digest.malformed().is_some() => Check::Malformed,
+            Some(_) => Check::Invalid,
+            None => Check::Missing,
+        };
+        self.sha512 = match &retrieved.sha512 {
+            Some(digest) if digest.validate().is_ok() => Check::Valid,
+            Some(digest) if digest.malformed().is_some() => Check::Malformed,
+            Some(_) => Check::Invalid,
+            None => Check::Missing,
+        };
+        self
+    }
+
+    fn from_result(url: Url, result: &Result<ValidatedAdvisory, ValidationError>) -> Self {
+        match result {
+            // in the collect mode, a document failing the checks is passed on with its failures
+            Ok(advisory) => Self {
+                valid: advisory.evidence.is_trusted(),
+                signature: match advisory.signature.is_some() {
+                    true if !advisory.evidence.signers.is_empty() => Check::Valid,
+                    true => Check::Invalid,
+                    false => Check::Missing,
+                },
+                signers: advisory.evidence.signers.clone(),
+                error: match advisory.evidence.is_trusted() {
+                    true => None,
+                    false => Some(
+                        advisory
+                            .evidence
+                            .failures
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    ),
+                },
+                ..Self::new(url).digests(advisory)
+            },
+            Err(err) => {
+                let verdict = match err {
+                    ValidationError::Retrieval(_) => Self::new(url),
+                    ValidationError::Signature { retrieved, .. } => Self {
+                        signature: Check::Invalid,
+                        ..Self::new(url).digests(retrieved)
+                    },
+                    ValidationError::MalformedSidecar {
+                        error: SidecarError::Signature(_),
+                        retrieved,
+                    } => Self {
+                        signature: Check::Malformed,
+                        ..Self::new(url).digests(retrieved)
+                    },
+                    ValidationError::DigestMismatch { retrieved, .. }
+                    | ValidationError::MalformedSidecar { retrieved, .. }
+                    | ValidationError::Tls { retrieved, .. }
+                    | ValidationError::Policy { retrieved, .. } => {
+                        Self::new(url).digests(retrieved)
+                    }
+                };
+                Self {
+                    error: Some(err.to_string()),
+                    ..verdict
+                }
+            }
+        }
+    }
+}
+
+impl Fetch {
+    pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
+        match self.verify.clone() {
+            Some(source) => self.verify(progress, source).await,
+            None => self.fetch().await,
+        }
+    }
+
+    async fn fetch(self) -> anyhow::Result<()> {
+        let fetcher = self.client.new_fetcher().await?;
+        let data = fetcher
+            .fetch::<Bytes>(self.url.clone())
+            .await
+            .with_context(|| format!("Failed to fetch: {}", self.url))?;
+
+        write(self.output.as_deref(), &data)
+    }
+
+    async fn verify(self, progress: Progress, source: String) -> anyhow::Result<()> {
+        let options = ValidationOptions::try_from(self.validation)?;
+
+        let entry = ListEntry {
+            url: self.url.clone(),
+            distribution: None,
+            modified: None,
+        };
+        let source = SourceDescriptor::parse(&source)?
+            .into_list_source(
+                DiscoverConfig {
+                    since: None,
+                    source,
+                    decryption: None,
+                    sidecar_miss_limit: None,
+                    offload: None,
+                    resume_threshold: None,
+                    key_fallback: Default::default(),
+                    list: None,
+                    aggregator: None,
+                },
+                FetcherOptions::from(self.client),
+                Some(vec![entry]),
+            )
+            .await?;
+
+        let result = Arc::new(Mutex::new(None));
+
+        {
+            let result = result.clone();
+            Walker::new(source.clone())
+                .with_progress(progress)
+                .walk(RetrievingVisitor::new(
+                    source,
+                    ValidationVisitor::new(
+                        move |advisory: Result<ValidatedAdvisory, ValidationError>| {
+                            let result = result.clone();
+                            async move {
+                                *result.lock().unwrap_or_else(PoisonError::into_inner) =
+                                    Some(advisory);
+                                Ok::<_, anyhow::Error>(())
+                            }
+                        },
+                    )
+                    .with_options(options),
+                ))
+                .await?;
+        }
+
+        let Some(result) = result.lock().unwrap_or_else(PoisonError::into_inner).take() else {
+            anyhow::bail!("Document was not processed: {}", self.url);
+        };
+
+        let verdict = Verdict::from_result(self.url.clone(), &result);
+
+        if let (Some(output), Ok(advisory)) = (&self.output, &result) {
+            write(Some(output), &advisory.data)?;
+        }
+
+        write_colored_json(&verdict, &mut stdout().lock())?;
+        println!();
+
+        if !verdict.valid {
+            anyhow::bail!("Verification failed: {}", self.url);
+        }
+
+        Ok(())
+    }
+}
+
+/// Write the document to a file, or to stdout.
+fn write(output: Option<&Path>, data: &[u8]) -> anyhow::Result<()> {
+    match output {
+        Some(output) => std::fs::write(output, data)
+            .with_context(|| format!("Failed to write: {}", output.display())),
+        None => Ok(stdout().lock().write_all(data)?),
+    }
+}
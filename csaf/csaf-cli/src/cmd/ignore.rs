@@ -0,0 +1,128 @@
+use chrono::NaiveDate;
+use csaf_walker::ignore::{parse_expiry, today, IgnoreEntry, IgnoreList};
+use reqwest::Url;
+use std::path::{Path, PathBuf};
+use walker_common::clock::Clock;
+
+/// Manage the list of known broken documents, which are skipped during discovery.
+#[derive(clap::Args, Debug)]
+pub struct Ignore {
+    /// The JSON file of the ignore list.
+    #[arg(long, env = "CSAF_WALKER_IGNORE_LIST", global = true)]
+    ignore_list: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: IgnoreCommand,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum IgnoreCommand {
+    Add(Add),
+    List(List),
+    Remove(Remove),
+}
+
+impl Ignore {
+    pub fn run(self) -> anyhow::Result<()> {
+        let Some(path) = self.ignore_list else {
+            anyhow::bail!("Missing the ignore list, use --ignore-list");
+        };
+
+        match self.command {
+            IgnoreCommand::Add(cmd) => cmd.run(&path),
+            IgnoreCommand::List(cmd) => cmd.run(&path),
+            IgnoreCommand::Remove(cmd) => cmd.run(&path),
+        }
+    }
+}
+
+/// Ignore a document, creating the ignore list if it doesn't exist yet.
+#[derive(clap::Args, Debug)]
+pub struct Add {
+    /// The URL of the document, as discovered.
+    url: Url,
+
+    /// Why the document is ignored.
+    #[arg(long)]
+    reason: String,
+
+    /// Stop ignoring the document after this day (e.g. `2025-12-31`), or after a duration from today (e.g. `90d`).
+    #[arg(long, value_parser = parse_expiry)]
+    expires: Option<NaiveDate>,
+}
+
+impl Add {
+    pub fn run(self, path: &Path) -> anyhow::Result<()> {
+        let mut list = IgnoreList::load_from(path)?.unwrap_or_default();
+
+        let replaced = list.add(IgnoreEntry {
+            url: self.url.clone(),
+            reason: self.reason,
+            expires: self.expires,
+        });
+        list.store_to(path)?;
+
+        match replaced {
+            true => log::info!("Updated: {}", self.url),
+            false => log::info!("Added: {}", self.url),
+        }
+
+        Ok(())
+    }
+}
+
+/// Show the ignored documents.
+#[derive(clap::Args, Debug)]
+pub struct List {
+    /// Only show entries which have expired.
+    #[arg(long)]
+    expired: bool,
+}
+
+impl List {
+    pub fn run(self, path: &Path) -> anyhow::Result<()> {
+        let Some(list) = IgnoreList::load_from(path)? else {
+            anyhow::bail!("Ignore list not found: {}", path.display());
+        };
+        let today = today(&Clock::system());
+
+        for entry in list.entries {
+            let active = today.map_or(true, |today| entry.is_active(today));
+            if self.expired && active {
+                continue;
+            }
+
+            let expires = match entry.expires {
+                Some(expires) if active => format!(" (expires: {expires})"),
+                Some(expires) => format!(" (expired: {expires})"),
+                None => String::new(),
+            };
+            println!("{}: {}{expires}", entry.url, entry.reason);
+        }
+
+        Ok(())
+    }
+}
+
+/// Stop ignoring a document.
+#[derive(clap::Args, Debug)]
+pub struct Remove {
+    /// The URL of the document.
+    url: Url,
+}
+
+impl Remove {
+    pub fn run(self, path: &Path) -> anyhow::Result<()> {
+        let Some(mut list) = IgnoreList::load_from(path)? else {
+            anyhow::bail!("Ignore list not found: {}", path.display());
+        };
+
+        if list.remove(&self.url).is_none() {
+            anyhow::bail!("Not ignored: {}", self.url);
+        }
+        list.store_to(path)?;
+        log::info!("Removed: {}", self.url);
+
+        Ok(())
+    }
+}
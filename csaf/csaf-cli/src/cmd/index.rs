@@ -0,0 +1,77 @@
+use crate::{cmd::DiscoverArguments, common::walk_standard};
+use csaf_walker::{
+    query::{index, IndexEntry},
+    validation::{ValidatedAdvisory, ValidationError},
+    visitors::filter::FilterConfig,
+};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, PoisonError};
+use walker_common::{
+    cli::{client::ClientArguments, runner::RunnerArguments, validation::ValidationArguments},
+    progress::Progress,
+};
+
+/// Build a persistent index of the advisories of a (local) source, for the `serve` command.
+#[derive(clap::Args, Debug)]
+pub struct Index {
+    #[command(flatten)]
+    client: ClientArguments,
+
+    #[command(flatten)]
+    runner: RunnerArguments,
+
+    #[command(flatten)]
+    discover: DiscoverArguments,
+
+    #[command(flatten)]
+    validation: ValidationArguments,
+
+    /// The index file (an SQLite database), replacing its previous content.
+    #[arg(long, env = "CSAF_WALKER_INDEX")]
+    index: PathBuf,
+}
+
+impl Index {
+    pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
+        let entries = Arc::new(Mutex::new(Vec::<IndexEntry>::new()));
+
+        {
+            let entries = entries.clone();
+            walk_standard(
+                progress,
+                self.client,
+                self.runner,
+                self.discover,
+                FilterConfig::default(),
+                self.validation,
+                move |advisory: Result<ValidatedAdvisory, ValidationError>| {
+                    let entries = entries.clone();
+                    async move {
+                        match advisory {
+                            Ok(adv) => match IndexEntry::parse(adv.url.clone(), &adv.data) {
+                                Some(entry) => entries
+                                    .lock()
+                                    .unwrap_or_else(PoisonError::into_inner)
+                                    .push(entry),
+                                None => log::warn!("Failed to index advisory: {}", adv.url),
+                            },
+                            Err(err) => log::warn!("Skipping advisory: {err}"),
+                        }
+
+                        Ok::<_, anyhow::Error>(())
+                    }
+                },
+            )
+            .await?;
+        }
+
+        let entries = std::mem::take(&mut *entries.lock().unwrap_or_else(PoisonError::into_inner));
+        let count = entries.len();
+
+        // only replace the content of the index once the walk was successful
+        index::Index::open(&self.index)?.replace(entries)?;
+        log::info!("Indexed {count} advisories: {}", self.index.display());
+
+        Ok(())
+    }
+}
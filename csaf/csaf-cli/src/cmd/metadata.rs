@@ -1,17 +1,28 @@
+use anyhow::Context;
 use colored_json::write_colored_json;
 use csaf_walker::{
     discover::DiscoverConfig,
-    metadata::{self, MetadataRetriever},
+    metadata::{
+        self,
+        lint::{lint, lint_reachability, Level},
+        MetadataRetriever, MetadataSource,
+    },
     model::metadata::ProviderMetadata,
     source::{new_source, Source},
 };
 use std::fmt::Display;
 use std::io::stdout;
+use std::path::Path;
+use time::OffsetDateTime;
 use walker_common::cli::client::ClientArguments;
 
 /// Discover provider metadata.
 #[derive(clap::Args, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Metadata {
+    #[command(subcommand)]
+    command: Option<MetadataCommand>,
+
     #[command(flatten)]
     client: ClientArguments,
 
@@ -19,14 +30,45 @@ pub struct Metadata {
     source: String,
 
     /// Try and show all approaches
-    #[arg(short = 'A', long)]
+    #[arg(short = 'A', long, conflicts_with = "trace")]
     all: bool,
+
+    /// Show each step of the discovery process, including its outcome and timing, followed by the resolved metadata
+    #[arg(short = 'T', long)]
+    trace: bool,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum MetadataCommand {
+    Lint(Lint),
+}
+
+/// Check provider metadata for common mistakes.
+#[derive(clap::Args, Debug)]
+pub struct Lint {
+    #[command(flatten)]
+    client: ClientArguments,
+
+    /// The provider metadata to check: a provider domain, the URL to the provider metadata, or a local file
+    source: String,
+
+    /// Maximum age of the `last_updated` timestamp, before the metadata is considered stale
+    #[arg(long, default_value = "90d")]
+    max_age: humantime::Duration,
+
+    /// Don't check if the referenced feeds, directories, and keys can be retrieved
+    #[arg(long)]
+    offline: bool,
 }
 
 impl Metadata {
     pub async fn run(self) -> anyhow::Result<()> {
-        if self.all {
+        if let Some(MetadataCommand::Lint(lint)) = self.command {
+            lint.run().await
+        } else if self.all {
             self.all().await
+        } else if self.trace {
+            self.trace().await
         } else {
             self.default().await
         }
@@ -56,11 +98,45 @@ impl Metadata {
         Ok(())
     }
 
+    async fn trace(self) -> anyhow::Result<()> {
+        let fetcher = self.client.new_fetcher().await?;
+        let trace = MetadataRetriever::new(self.source).trace(&fetcher).await;
+
+        for step in &trace.steps {
+            print!(
+                "{} ({}ms): {} - {}",
+                step.approach, step.duration, step.candidate, step.outcome
+            );
+            if let Some(status) = step.status {
+                print!(" [{status}]");
+            }
+            if let Some(error) = &step.error {
+                print!(": {error}");
+            }
+            println!();
+        }
+        println!();
+
+        match &trace.metadata {
+            Some(metadata) => Self::show_metadata(metadata)?,
+            None => anyhow::bail!("Unable to discover metadata"),
+        }
+
+        Ok(())
+    }
+
     async fn default(self) -> anyhow::Result<()> {
         let source = new_source(
             DiscoverConfig {
                 since: None,
                 source: self.source,
+                decryption: None,
+                sidecar_miss_limit: None,
+                offload: None,
+                resume_threshold: None,
+                key_fallback: Default::default(),
+                list: None,
+                aggregator: None,
             },
             self.client,
         )
@@ -102,3 +178,49 @@ impl Metadata {
         Ok(())
     }
 }
+
+impl Lint {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let fetcher = self.client.new_fetcher().await?;
+
+        let path = Path::new(&self.source);
+        let metadata = if path.is_file() {
+            let data = tokio::fs::read(path)
+                .await
+                .with_context(|| format!("Failed to read: {}", path.display()))?;
+            serde_json::from_slice::<ProviderMetadata>(&data)
+                .with_context(|| format!("Failed to parse: {}", path.display()))?
+        } else {
+            self.source.load_metadata(&fetcher).await?
+        };
+
+        let mut lints = lint(&metadata, OffsetDateTime::now_utc(), self.max_age.into());
+        if !self.offline {
+            lints.extend(lint_reachability(&fetcher, &metadata).await);
+        }
+
+        if lints.is_empty() {
+            println!("No issues found");
+            return Ok(());
+        }
+
+        for lint in &lints {
+            println!("{}: {}", lint.level, lint.message);
+            println!("  suggestion: {}", lint.suggestion);
+        }
+
+        let errors = lints
+            .iter()
+            .filter(|lint| lint.level == Level::Error)
+            .count();
+
+        println!();
+        println!("{errors} error(s), {} warning(s)", lints.len() - errors);
+
+        if errors > 0 {
+            anyhow::bail!("Provider metadata has {errors} error(s)");
+        }
+
+        Ok(())
+    }
+}
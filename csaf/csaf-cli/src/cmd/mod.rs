@@ -1,16 +1,50 @@
 use anyhow::Context;
-use csaf_walker::visitors::{filter::FilterConfig, store::StoreVisitor};
+use csaf_walker::{
+    ignore::IgnoreList,
+    metadata::{MetadataRetriever, MetadataSource},
+    model::store::StoreLayout,
+    retention::{self, RetentionPolicy},
+    verification::check::cve::CheckCveIds,
+    visitors::{
+        cross_check::CrossCheckConfig,
+        filter::{FilterConfig, FilterProfiles},
+        journal::{Journal, JournalFile},
+        skip::SkipPredicate,
+        store::{StoreVisitor, TlpPartitioning, WithdrawnPolicy},
+    },
+};
 use flexible_time::timestamp::StartTimestamp;
-use std::path::PathBuf;
+use reqwest::Url;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use time::OffsetDateTime;
+use walker_common::{
+    cli::client::ClientArguments,
+    encryption::{Decryption, Encryption},
+    store::Staging,
+};
 
+pub mod consistency;
 pub mod discover;
+pub mod doctor;
 pub mod download;
+pub mod fetch;
+pub mod ignore;
+#[cfg(feature = "index")]
+pub mod index;
 pub mod metadata;
 pub mod parse;
 pub mod report;
+pub mod retry;
 pub mod scan;
 pub mod send;
+#[cfg(feature = "serve")]
+pub mod serve;
+#[cfg(feature = "simulate")]
+pub mod simulate;
+pub mod store;
 pub mod sync;
+pub mod work;
 
 #[derive(Debug, clap::Parser)]
 #[command(next_help_heading = "Discovery")]
@@ -19,30 +53,143 @@ pub struct DiscoverArguments {
     ///
     /// CSAF trusted provider base domain (e.g. `redhat.com`), the full URL to the provider metadata file, or a local `file:` source.
     pub source: String,
+
+    /// OpenPGP keyring with the secret key to decrypt documents of an encrypted `file:` source.
+    #[arg(long, env = "CSAF_WALKER_DECRYPTION_KEY", value_parser = parse_decryption)]
+    pub decryption_key: Option<Decryption>,
+
+    /// Stop probing for a type of sidecar file (signature, digest) of a distribution, after it was missing that many times.
+    #[arg(long, env = "CSAF_WALKER_SIDECAR_MISS_LIMIT")]
+    pub sidecar_miss_limit: Option<usize>,
+
+    /// Resume interrupted downloads of documents of at least this size (e.g. `10MiB`), using range requests, instead of starting over.
+    #[arg(long, env = "CSAF_WALKER_RESUME_THRESHOLD", value_parser = parse_size)]
+    pub resume_threshold: Option<u64>,
+
+    /// Look up provider keys, which can't be retrieved from their URL, in the Web Key Directory of these email addresses. Keys must match their fingerprint.
+    #[arg(long, env = "CSAF_WALKER_KEY_WKD")]
+    pub key_wkd: Vec<String>,
+
+    /// Look up provider keys, which can't be retrieved from their URL, on these HKPS keyservers (e.g. `https://keys.openpgp.org`). Keys must match their fingerprint.
+    #[arg(long, env = "CSAF_WALKER_KEYSERVER")]
+    pub keyserver: Vec<Url>,
+
+    /// Only walk the advisories listed in this file, instead of discovering them. The file contains one URL, or JSON object (as emitted by `discover --output json`), per line, or a JSON array. The source is still used for the provider metadata and keys.
+    #[arg(long, env = "CSAF_WALKER_LIST")]
+    pub list: Option<PathBuf>,
+
+    /// Attribute the advisories to the listing of the provider by this aggregator (URL to its `aggregator.json`), e.g. when walking one of its mirrors. The origin is kept in stores and reports.
+    #[arg(long, env = "CSAF_WALKER_AGGREGATOR")]
+    pub aggregator: Option<Url>,
+}
+
+#[derive(Debug, clap::Parser)]
+#[command(next_help_heading = "Cross-check")]
+pub struct CrossCheckArguments {
+    /// Cross-check the digests of retrieved documents with those published by the canonical provider (domain, or URL to the provider metadata). Useful when retrieving from the mirror of an aggregator.
+    #[arg(long, env = "CSAF_WALKER_CROSS_CHECK")]
+    pub cross_check: Option<String>,
+}
+
+impl CrossCheckArguments {
+    /// Create the cross-check configuration, if requested, using the directory distributions of
+    /// the canonical provider.
+    pub async fn config(self, client: ClientArguments) -> anyhow::Result<Option<CrossCheckConfig>> {
+        let Some(source) = self.cross_check else {
+            return Ok(None);
+        };
+
+        let fetcher = client.new_fetcher().await?;
+        let metadata = MetadataRetriever::new(source.clone())
+            .load_metadata(&fetcher)
+            .await
+            .with_context(|| format!("Failed to load canonical provider metadata: {source}"))?;
+
+        let bases = metadata
+            .distributions
+            .into_iter()
+            .filter_map(|distribution| distribution.directory_url)
+            .collect::<Vec<_>>();
+
+        if bases.is_empty() {
+            anyhow::bail!("Canonical provider has no directory based distributions: {source}");
+        }
+
+        Ok(Some(CrossCheckConfig::new(fetcher).bases(bases)))
+    }
+}
+
+fn parse_decryption(path: &str) -> anyhow::Result<Decryption> {
+    Decryption::from_files([path])
 }
 
 #[derive(Debug, clap::Parser)]
 #[command(next_help_heading = "Filters")]
 pub struct FilterArguments {
-    #[arg(long)]
+    #[arg(long, env = "CSAF_WALKER_IGNORE_DISTRIBUTION")]
     /// Distributions to ignore
     pub ignore_distribution: Vec<String>,
 
-    #[arg(long)]
+    #[arg(long, env = "CSAF_WALKER_IGNORE_PREFIX")]
     /// Prefix to ignore
     pub ignore_prefix: Vec<String>,
 
-    #[arg(long)]
+    #[arg(long, env = "CSAF_WALKER_ONLY_PREFIX")]
     /// Ignore all non-matching prefixes
     pub only_prefix: Vec<String>,
+
+    #[arg(long, env = "CSAF_WALKER_ONLY_DISTRIBUTION")]
+    /// Only process advisories of those distributions
+    pub only_distribution: Vec<String>,
+
+    #[arg(long, env = "CSAF_WALKER_MIN_YEAR")]
+    /// Only process advisories with a year folder of at least this year
+    pub min_year: Option<i32>,
+
+    #[arg(long, env = "CSAF_WALKER_TLP_LABEL")]
+    /// Only process advisories of ROLIE feeds with one of those TLP labels
+    pub tlp_label: Vec<String>,
+
+    #[arg(long, env = "CSAF_WALKER_FILTER_PROFILES")]
+    /// A JSON file, containing named filter profiles
+    pub filter_profiles: Option<PathBuf>,
+
+    #[arg(long, env = "CSAF_WALKER_PROFILE", requires = "filter_profiles")]
+    /// Filter profiles to apply, in addition to the other filter arguments
+    pub profile: Vec<String>,
+
+    #[arg(long, env = "CSAF_WALKER_IGNORE_LIST")]
+    /// A JSON file, listing known broken documents to skip, as managed by the `ignore` command
+    pub ignore_list: Option<PathBuf>,
 }
 
-impl From<FilterArguments> for FilterConfig {
-    fn from(filter: FilterArguments) -> Self {
-        FilterConfig::new()
+impl TryFrom<FilterArguments> for FilterConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(filter: FilterArguments) -> Result<Self, Self::Error> {
+        let config = FilterConfig::new()
             .ignored_distributions(filter.ignore_distribution)
             .ignored_prefixes(filter.ignore_prefix)
             .only_prefixes(filter.only_prefix)
+            .only_distributions(filter.only_distribution)
+            .min_year(filter.min_year)
+            .tlp_labels(filter.tlp_label);
+
+        let config = match filter.ignore_list {
+            Some(path) => match IgnoreList::load_from(&path)? {
+                Some(ignore_list) => config.ignore_list(ignore_list),
+                None => anyhow::bail!("Ignore list not found: {}", path.display()),
+            },
+            None => config,
+        };
+
+        Ok(match filter.filter_profiles {
+            Some(path) => {
+                let profiles = FilterProfiles::load(&path)?;
+                config.merge(profiles.select(filter.profile.iter().map(String::as_str))?)
+            }
+            None => config,
+        })
     }
 }
 
@@ -51,28 +198,189 @@ impl From<FilterArguments> for FilterConfig {
 pub struct StoreArguments {
     /// Disable the use of extended attributes, e.g. for etag information.
     #[cfg(any(target_os = "linux", target_os = "macos"))]
-    #[arg(long)]
+    #[arg(
+        long,
+        env = "CSAF_WALKER_NO_XATTRS",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
     pub no_xattrs: bool,
 
     /// Disable applying the modification timestamp to the downloaded file.
-    #[arg(long)]
+    #[arg(
+        long,
+        env = "CSAF_WALKER_NO_TIMESTAMPS",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
     pub no_timestamps: bool,
 
     /// Output path, defaults to the local directory.
-    #[arg(short, long)]
+    #[arg(short, long, env = "CSAF_WALKER_DATA")]
     pub data: Option<PathBuf>,
+
+    /// Encrypt stored documents for the recipients of the OpenPGP keyring
+    #[arg(long, env = "CSAF_WALKER_ENCRYPT_TO")]
+    pub encrypt_to: Vec<PathBuf>,
+
+    /// Store documents in per-TLP subdirectories (e.g. `clear`, `amber`).
+    #[arg(
+        long,
+        env = "CSAF_WALKER_TLP_PARTITION",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub tlp_partition: bool,
+
+    /// Filesystem permissions of a TLP partition directory, in the form of `LABEL=MODE` (octal mode, e.g. `amber=0700`).
+    #[arg(long, env = "CSAF_WALKER_TLP_PERMISSIONS", value_parser = parse_tlp_permission, requires = "tlp_partition")]
+    pub tlp_permissions: Vec<(String, u32)>,
+
+    /// Maintain a `changes.csv` and `index.txt` in the output directory, allowing to consume it as a directory based distribution.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_EMIT_CHANGES",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub emit_changes: bool,
+
+    /// Write into a staging directory, replacing the output directory only after a successful run. Requires an explicit output directory.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_STAGING",
+        requires = "data",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub staging: bool,
+
+    /// When a stored document changes, store the changes (added revisions, product statuses, scores) in a `.diff` file next to it.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_STORE_DIFFS",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub store_diffs: bool,
+
+    /// The layout of the distribution directories. Defaults to the layout of an existing store, or `v1`.
+    #[arg(long, env = "CSAF_WALKER_STORE_LAYOUT")]
+    pub store_layout: Option<Layout>,
+
+    /// Space to keep free on the filesystem of the output directory (e.g. `1GiB`). Storing fails before running out of space.
+    #[arg(long, env = "CSAF_WALKER_MIN_FREE_SPACE", value_parser = parse_size, default_value = "100MiB")]
+    pub min_free_space: u64,
+
+    /// How to store documents withdrawn by their publisher.
+    #[arg(long, env = "CSAF_WALKER_WITHDRAWN", default_value = "keep")]
+    pub withdrawn: Withdrawn,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Withdrawn {
+    /// store them like all other documents
+    Keep,
+    /// remove a stored copy, leaving a `.withdrawn` file with the tracking information in its place
+    Tombstone,
+    /// remove a stored copy
+    Delete,
+}
+
+impl From<Withdrawn> for WithdrawnPolicy {
+    fn from(value: Withdrawn) -> Self {
+        match value {
+            Withdrawn::Keep => Self::Keep,
+            Withdrawn::Tombstone => Self::Tombstone,
+            Withdrawn::Delete => Self::Delete,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Layout {
+    /// directories named after the percent-encoded distribution URL
+    V1,
+    /// directories named after a hash of the distribution URL, mapped by `metadata/layout.json`
+    V2,
+    /// like `v2`, additionally keeping each document in `objects/`, named after its SHA-256 digest
+    Content,
+}
+
+impl From<Layout> for StoreLayout {
+    fn from(value: Layout) -> Self {
+        match value {
+            Layout::V1 => Self::V1,
+            Layout::V2 => Self::V2,
+            Layout::Content => Self::Content,
+        }
+    }
+}
+
+impl StoreArguments {
+    /// The output directory
+    pub fn base(&self) -> anyhow::Result<PathBuf> {
+        match &self.data {
+            Some(base) => Ok(base.clone()),
+            None => std::env::current_dir().context("Get current working directory"),
+        }
+    }
+
+    /// Prepare the staging directory, if staging is enabled, and use it as output directory.
+    pub fn prepare_staging(&mut self) -> anyhow::Result<Option<Staging>> {
+        if !self.staging {
+            return Ok(None);
+        }
+
+        // never replace the current directory, because no output directory was provided
+        let base = self
+            .data
+            .clone()
+            .context("Staging requires an explicit output directory (--data)")?;
+        let staging = Staging::new(base);
+        self.data = Some(staging.prepare()?.to_path_buf());
+
+        Ok(Some(staging))
+    }
+
+    /// The layout of the store, either requested, detected from an existing store, or the default.
+    pub fn layout(&self) -> anyhow::Result<StoreLayout> {
+        if let Some(layout) = self.store_layout {
+            return Ok(layout.into());
+        }
+
+        Ok(StoreLayout::detect(&self.base()?)?.unwrap_or_default())
+    }
+}
+
+fn parse_tlp_permission(value: &str) -> anyhow::Result<(String, u32)> {
+    let (label, mode) = value
+        .split_once('=')
+        .context("Expected the format of 'LABEL=MODE'")?;
+    let mode = u32::from_str_radix(mode.trim_start_matches("0o"), 8)
+        .with_context(|| format!("Invalid octal mode: {mode}"))?;
+    Ok((label.to_lowercase(), mode))
 }
 
 impl TryFrom<StoreArguments> for StoreVisitor {
     type Error = anyhow::Error;
 
     fn try_from(value: StoreArguments) -> Result<Self, Self::Error> {
-        let base = match value.data {
-            Some(base) => base,
-            None => std::env::current_dir().context("Get current working directory")?,
+        let base = value.base()?;
+
+        let encryption = if value.encrypt_to.is_empty() {
+            None
+        } else {
+            Some(Encryption::from_files(&value.encrypt_to)?)
         };
 
-        let result = Self::new(base).no_timestamps(value.no_timestamps);
+        let result = Self::new(base)
+            .layout(value.layout()?)
+            .min_free_space(value.min_free_space)
+            .no_timestamps(value.no_timestamps)
+            .encryption(encryption)
+            .changes(value.emit_changes)
+            .diffs(value.store_diffs)
+            .withdrawn(value.withdrawn.into())
+            .tlp(
+                value
+                    .tlp_partition
+                    .then(|| TlpPartitioning::new().permissions(value.tlp_permissions)),
+            );
 
         #[cfg(any(target_os = "linux", target_os = "macos"))]
         let result = result.no_xattrs(value.no_xattrs);
@@ -81,20 +389,194 @@ impl TryFrom<StoreArguments> for StoreVisitor {
     }
 }
 
+#[derive(Debug, clap::Parser)]
+#[command(next_help_heading = "Journal")]
+pub struct JournalArguments {
+    /// Record documents which failed to be retrieved or validated in this file, instead of aborting the run. Use the `retry` command to process them again.
+    #[arg(long, env = "CSAF_WALKER_JOURNAL")]
+    pub journal: Option<PathBuf>,
+
+    /// The command line recorded in the journal, instead of the current one
+    #[arg(skip)]
+    pub command: Option<Vec<String>>,
+}
+
+impl JournalArguments {
+    /// Create the journal, if requested.
+    pub fn journal(&self) -> Option<Arc<Journal>> {
+        self.journal.as_ref().map(|_| Arc::new(Journal::new()))
+    }
+
+    /// Store the journal, if requested.
+    pub fn store(self, journal: Option<&Journal>) -> anyhow::Result<()> {
+        let (Some(path), Some(journal)) = (self.journal, journal) else {
+            return Ok(());
+        };
+
+        let command = self.command.unwrap_or_else(|| std::env::args().collect());
+        JournalFile::new(command, journal).store_to(&path)?;
+
+        match journal.len() {
+            0 => log::info!("No failed documents"),
+            n => log::warn!(
+                "{n} failed document(s) recorded in journal: {}",
+                path.display()
+            ),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, clap::Parser)]
+#[command(next_help_heading = "Retention")]
+pub struct RetentionArguments {
+    /// Remove stored advisories initially released before the last N calendar years.
+    #[arg(long, env = "CSAF_WALKER_RETAIN_YEARS")]
+    pub retain_years: Option<u32>,
+
+    /// Remove the oldest stored advisories until the store is below this size (e.g. `500MiB`, `2GB`).
+    #[arg(long, env = "CSAF_WALKER_RETAIN_MAX_SIZE", value_parser = parse_size)]
+    pub retain_max_size: Option<u64>,
+
+    /// Only report what would be removed by the retention policy.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_RETAIN_DRY_RUN",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub retain_dry_run: bool,
+}
+
+pub(crate) fn parse_size(value: &str) -> anyhow::Result<u64> {
+    const UNITS: &[(&str, u64)] = &[
+        ("KiB", 1 << 10),
+        ("MiB", 1 << 20),
+        ("GiB", 1 << 30),
+        ("TiB", 1 << 40),
+        ("KB", 1_000),
+        ("MB", 1_000_000),
+        ("GB", 1_000_000_000),
+        ("TB", 1_000_000_000_000),
+        ("B", 1),
+    ];
+
+    let value = value.trim();
+    let (number, factor) = UNITS
+        .iter()
+        .find_map(|(unit, factor)| value.strip_suffix(unit).map(|number| (number, *factor)))
+        .unwrap_or((value, 1));
+
+    let number: u64 = number
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid size: {value}"))?;
+
+    number
+        .checked_mul(factor)
+        .with_context(|| format!("Size too big: {value}"))
+}
+
+impl RetentionArguments {
+    /// Apply the retention policy to the store, if one was configured.
+    pub fn apply(self, base: &Path) -> anyhow::Result<()> {
+        let policy = RetentionPolicy::new()
+            .years(self.retain_years)
+            .max_size(self.retain_max_size);
+
+        if policy.is_empty() {
+            return Ok(());
+        }
+
+        let report = retention::apply(
+            base,
+            &policy,
+            OffsetDateTime::now_utc(),
+            self.retain_dry_run,
+        )?;
+
+        for advisory in &report.removed {
+            if self.retain_dry_run {
+                log::info!("Would remove: {}", advisory.path.display());
+            } else {
+                log::info!("Removed: {}", advisory.path.display());
+            }
+        }
+
+        log::info!(
+            "Retention: {} removed ({} bytes), {} retained ({} bytes){}",
+            report.removed.len(),
+            report.removed_size(),
+            report.retained,
+            report.retained_size,
+            if self.retain_dry_run {
+                " (dry run)"
+            } else {
+                ""
+            }
+        );
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, clap::Parser)]
 #[command(next_help_heading = "Skipping")]
 pub struct SkipArguments {
     /// Provide a timestamp since when files are considered changed.
-    #[arg(short, long)]
+    #[arg(short, long, env = "CSAF_WALKER_SINCE")]
     pub since: Option<StartTimestamp>,
 
     /// A file to read/store the last sync timestamp to at the end of a successful run.
-    #[arg(short = 'S', long)]
+    #[arg(short = 'S', long, env = "CSAF_WALKER_SINCE_FILE")]
     pub since_file: Option<PathBuf>,
 
     /// A delta to add to the value loaded from the since-state file.
-    #[arg(long)]
+    #[arg(long, env = "CSAF_WALKER_SINCE_FILE_OFFSET")]
     pub since_file_offset: Option<humantime::Duration>,
+
+    /// Issue a HEAD request for documents considered changed, skipping them if the ETag, or
+    /// Last-Modified and Content-Length, match the stored retrieval metadata.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_HEAD_CHECK",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub head_check: bool,
+
+    /// Skip documents with a stored copy matching the SHA-256 digest published by the provider. This fetches the digest file of each stored document.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_SKIP_EXISTING",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub skip_existing: bool,
+
+    /// Skip documents with a stored copy modified after the document was changed, even if considered changed by `--since`.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_SKIP_NEWER_LOCAL",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub skip_newer_local: bool,
+}
+
+impl SkipArguments {
+    /// The predicates for skipping documents, based on their stored copy.
+    pub async fn predicates(&self, client: &ClientArguments) -> anyhow::Result<Vec<SkipPredicate>> {
+        let mut result = vec![];
+
+        if self.skip_existing {
+            result.push(SkipPredicate::MatchingDigest(
+                client.clone().new_fetcher().await?,
+            ));
+        }
+        if self.skip_newer_local {
+            result.push(SkipPredicate::NewerLocal);
+        }
+
+        Ok(result)
+    }
 }
 
 #[derive(Debug, clap::Parser)]
@@ -102,18 +584,73 @@ pub struct SkipArguments {
 pub struct VerificationArguments {
     /// The profile to use for the CSAF validator suite
     #[cfg(feature = "csaf-validator-lib")]
-    #[arg(id = "csaf-validator-profile", long, value_enum, default_value_t = ValidatorProfile::Optional)]
+    #[arg(id = "csaf-validator-profile", long, env = "CSAF_WALKER_CSAF_VALIDATOR_PROFILE", value_enum, default_value_t = ValidatorProfile::Optional)]
     pub profile: ValidatorProfile,
 
     /// A timeout checking the CSAF validator suite for a single document
     #[cfg(feature = "csaf-validator-lib")]
-    #[arg(id = "csaf-validator-timeout", long)]
+    #[arg(
+        id = "csaf-validator-timeout",
+        long,
+        env = "CSAF_WALKER_CSAF_VALIDATOR_TIMEOUT"
+    )]
     pub timeout: Option<humantime::Duration>,
 
     /// CSAF validator tests to skip
     #[cfg(feature = "csaf-validator-lib")]
-    #[arg(id = "csaf-validator-skip", long)]
+    #[arg(
+        id = "csaf-validator-skip",
+        long,
+        env = "CSAF_WALKER_CSAF_VALIDATOR_SKIP"
+    )]
     pub skip: Vec<String>,
+
+    /// CSAF validator tests to run, instead of the tests of the profile. Tests are selected by ID (like `6.1.16`), name (like `csaf_2_0`), or an ID prefix (like `6.1.27.*`).
+    #[cfg(feature = "csaf-validator-lib")]
+    #[arg(
+        id = "csaf-validator-test",
+        long = "check-test",
+        env = "CSAF_WALKER_CHECK_TEST"
+    )]
+    pub tests: Vec<String>,
+
+    /// A file caching the results of the checks, so that unchanged documents don't need to be checked again
+    #[arg(long, env = "CSAF_WALKER_CHECK_CACHE")]
+    pub check_cache: Option<PathBuf>,
+
+    /// Check referenced CVE IDs against a local copy of the CVE list. Either a directory (like a checkout of `cvelistV5`) or a file with one ID per line.
+    #[arg(long, env = "CSAF_WALKER_CVE_LIST", conflicts_with = "cve_nvd")]
+    pub cve_list: Option<PathBuf>,
+
+    /// Check referenced CVE IDs using the NVD API
+    #[arg(
+        long,
+        env = "CSAF_WALKER_CVE_NVD",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub cve_nvd: bool,
+
+    /// A file caching the results of NVD lookups
+    #[arg(long, env = "CSAF_WALKER_CVE_NVD_CACHE", requires = "cve_nvd")]
+    pub cve_nvd_cache: Option<PathBuf>,
+
+    /// An API key for the NVD API, raising the rate limit
+    #[arg(long, env = "CSAF_WALKER_NVD_API_KEY", requires = "cve_nvd")]
+    pub nvd_api_key: Option<String>,
+}
+
+impl VerificationArguments {
+    /// Create the check for CVE IDs, if requested.
+    pub fn cve_check(&self) -> anyhow::Result<Option<CheckCveIds>> {
+        Ok(match (&self.cve_list, self.cve_nvd) {
+            (Some(path), _) => Some(CheckCveIds::from_dataset(path)?),
+            (None, true) => Some(CheckCveIds::from_nvd(
+                self.cve_nvd_cache.clone(),
+                self.nvd_api_key.clone(),
+            )?),
+            (None, false) => None,
+        })
+    }
 }
 
 #[cfg(feature = "csaf-validator-lib")]
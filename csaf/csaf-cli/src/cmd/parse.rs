@@ -1,32 +1,94 @@
 use std::path::PathBuf;
 
+use anyhow::{anyhow, bail};
 use csaf::Csaf;
+use jaq_interpret::{Ctx, Filter, FilterT, ParseCtx, RcIter, Val};
 use walker_common::progress::Progress;
 
 /// Parse advisories
 #[derive(clap::Args, Debug)]
 pub struct Parse {
-    file: PathBuf,
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+
+    /// Evaluate a jq expression (e.g. `.document.tracking.id`) for each document, emitting the results as one JSON value per line.
+    #[arg(short = 'x', long, env = "CSAF_WALKER_EXTRACT")]
+    extract: Option<String>,
 }
 
 impl Parse {
     pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
-        progress.start(1);
-        let data = std::fs::read(self.file)?;
-        match serde_json::from_slice::<Csaf>(&data) {
-            Ok(csaf) => {
-                println!(
-                    "  {} ({}): {}",
-                    csaf.document.tracking.id,
-                    csaf.document.tracking.initial_release_date,
-                    csaf.document.title
-                );
-            }
-            Err(err) => {
-                eprintln!("  Format error: {err}");
+        let filter = self.extract.as_deref().map(compile).transpose()?;
+
+        let bar = progress.start(self.files.len());
+
+        for file in self.files {
+            let data = std::fs::read(&file)?;
+            match &filter {
+                Some(filter) => extract(filter, &data)?,
+                None => print(&data),
             }
+            bar.tick();
         }
 
         Ok(())
     }
 }
+
+fn print(data: &[u8]) {
+    match serde_json::from_slice::<Csaf>(data) {
+        Ok(csaf) => {
+            println!(
+                "  {} ({}): {}",
+                csaf.document.tracking.id,
+                csaf.document.tracking.initial_release_date,
+                csaf.document.title
+            );
+        }
+        Err(err) => {
+            eprintln!("  Format error: {err}");
+        }
+    }
+}
+
+/// Compile a jq expression, including the jq standard library.
+fn compile(expression: &str) -> anyhow::Result<Filter> {
+    let mut defs = ParseCtx::new(Vec::new());
+    defs.insert_natives(jaq_core::core());
+    defs.insert_defs(jaq_std::std());
+
+    let (filter, errs) = jaq_parse::parse(expression, jaq_parse::main());
+    if let Some(err) = errs.into_iter().next() {
+        bail!("Failed to parse expression: {err}");
+    }
+    let filter = filter.ok_or_else(|| anyhow!("Empty expression"))?;
+
+    let filter = defs.compile(filter);
+    if let Some((err, _)) = defs.errs.into_iter().next() {
+        bail!("Failed to compile expression: {err}");
+    }
+
+    Ok(filter)
+}
+
+/// Run the filter on a document, printing each result as a line of JSON.
+fn extract(filter: &Filter, data: &[u8]) -> anyhow::Result<()> {
+    let input = match serde_json::from_slice::<serde_json::Value>(data) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("  Format error: {err}");
+            return Ok(());
+        }
+    };
+
+    let inputs = RcIter::new(core::iter::empty());
+    for value in filter.run((Ctx::new([], &inputs), Val::from(input))) {
+        let value = value.map_err(|err| anyhow!("Failed to evaluate expression: {err}"))?;
+        println!(
+            "{}",
+            serde_json::to_string(&serde_json::Value::from(value))?
+        );
+    }
+
+    Ok(())
+}
@@ -2,20 +2,33 @@ use crate::{
     cmd::{DiscoverArguments, FilterArguments, VerificationArguments},
     common::walk_visitor,
 };
+use csaf_walker::visitors::filter::FilterConfig;
 use csaf_walker::{
-    discover::AsDiscovered,
-    report::{render_to_html, DocumentKey, Duplicates, ReportRenderOption, ReportResult},
-    retrieve::RetrievingVisitor,
-    validation::{ValidatedAdvisory, ValidationError, ValidationVisitor},
+    diff::{load_diff, AdvisoryDiff},
+    discover::{
+        AggregatorOrigin, AsDiscovered, DiscoverConfig, DiscoveredAdvisory, DiscoveredContext,
+        DiscoveredVisitor,
+    },
+    model::lifecycle::Lifecycle,
+    report::{
+        aggregate_severity, content_digest, excerpt, render_to_files, Baseline, DocumentKey,
+        DocumentState, Duplicates, ReportRenderOption, ReportResult, ReportState, RunSummary,
+    },
+    retrieve::{AsRetrieved, RetrievingVisitor},
+    validation::{
+        ValidatedAdvisory, ValidatedVisitor, ValidationContext, ValidationError, ValidationVisitor,
+    },
     verification::{
-        check::{init_verifying_visitor, CheckError},
+        cache::CheckCache,
+        check::{init_verifying_visitor, Check, Finding},
         VerificationError, VerifiedAdvisory, VerifyingVisitor,
     },
     visitors::duplicates::DetectDuplicatesVisitor,
 };
 use reqwest::Url;
+use serde_json::Value;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     path::PathBuf,
     sync::{
         atomic::{AtomicUsize, Ordering},
@@ -24,9 +37,15 @@ use std::{
 };
 use tokio::sync::Mutex;
 use walker_common::{
-    cli::{client::ClientArguments, runner::RunnerArguments, validation::ValidationArguments},
+    cli::{
+        client::ClientArguments, gate::GateArguments, notify::NotifyArguments,
+        report::ReportStyleArguments, runner::RunnerArguments, signing::SigningArguments,
+        validation::ValidationArguments,
+    },
+    clock::Clock,
     progress::Progress,
-    utils::url::Urlify,
+    report::ReportOptions,
+    utils::{openpgp::KeyIssue, url::Urlify},
     validate::ValidationOptions,
 };
 
@@ -53,6 +72,21 @@ pub struct Report {
 
     #[command(flatten)]
     render: RenderOptions,
+
+    #[command(flatten)]
+    baseline: BaselineOptions,
+
+    #[command(flatten)]
+    incremental: IncrementalOptions,
+
+    #[command(flatten)]
+    signing: SigningArguments,
+
+    #[command(flatten)]
+    notify: NotifyArguments,
+
+    #[command(flatten)]
+    gate: GateArguments,
 }
 
 #[derive(clap::Args, Debug)]
@@ -69,22 +103,136 @@ pub struct RenderOptions {
     /// The original source URL, used for the summary.
     #[arg(long)]
     pub source_url: Option<Url>,
+
+    /// Split the report into an index page, and pages per distribution, with up to this number of documents each.
+    #[arg(long)]
+    pub page_size: Option<usize>,
+
+    #[command(flatten)]
+    pub style: ReportStyleArguments,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(next_help_heading = "Baseline")]
+pub struct BaselineOptions {
+    /// A JSON file, containing known and accepted findings, which will not be reported
+    #[arg(long, env = "CSAF_WALKER_BASELINE")]
+    pub baseline: Option<PathBuf>,
+
+    /// Write the current findings to the baseline file, instead of suppressing them
+    #[arg(
+        long,
+        env = "CSAF_WALKER_UPDATE_BASELINE",
+        requires = "baseline",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub update_baseline: bool,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(next_help_heading = "Incremental")]
+pub struct IncrementalOptions {
+    /// A JSON file, persisting the findings of each document. Documents which didn't change since the previous run are not checked again, but their previous findings are reused.
+    #[arg(long, env = "CSAF_WALKER_REPORT_STATE")]
+    pub state: Option<PathBuf>,
 }
 
 impl Report {
     pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
-        let options: ValidationOptions = self.validation.into();
+        let offload = self.runner.offload();
+        let clock = self.runner.clock();
+        let policy = self.validation.policy()?;
+        let options = ValidationOptions::try_from(self.validation)?.offload(offload.clone());
+        let allowed_client_errors = options.allowed_client_errors.clone();
+
+        let mut checks = init_verifying_visitor();
+        if let Some(check) = self.verification.cve_check()? {
+            checks.push(("check_cve_ids", Box::new(check)));
+        }
+        let checks = match &policy {
+            Some(policy) => select_checks(checks, &policy.required_checks)?,
+            None => checks,
+        };
+        let gate = match &policy {
+            Some(policy) => self
+                .gate
+                .with_limits(policy.thresholds.max_errors, policy.thresholds.max_warnings),
+            None => self.gate,
+        };
 
         let total = Arc::new(AtomicUsize::default());
         let duplicates: Arc<Mutex<Duplicates>> = Default::default();
-        let errors: Arc<Mutex<BTreeMap<DocumentKey, String>>> = Default::default();
-        let warnings: Arc<Mutex<BTreeMap<DocumentKey, Vec<CheckError>>>> = Default::default();
+        let key_issues: Arc<Mutex<Vec<KeyIssue>>> = Default::default();
+
+        let notifier = self.notify.notifier();
+        let source = self.discover.source.clone();
+
+        let cache = match self.verification.check_cache {
+            Some(path) => Some(Arc::new(CheckCache::load(path).await?)),
+            None => None,
+        };
+
+        let baseline = match &self.baseline.baseline {
+            Some(path) => match Baseline::load_from(path)? {
+                Some(baseline) => baseline,
+                None if self.baseline.update_baseline => Baseline::default(),
+                None => anyhow::bail!("Baseline not found: {}", path.display()),
+            },
+            None => Baseline::default(),
+        };
+
+        let collector = Collector {
+            duplicates: duplicates.clone(),
+            // when updating the baseline, all findings need to be reported
+            suppressions: Arc::new(match self.baseline.update_baseline {
+                true => Baseline::default(),
+                false => baseline.clone(),
+            }),
+            count_severities: notifier.is_some(),
+            clock: clock.clone(),
+            ..Default::default()
+        };
+
+        // incremental processing, reusing the findings of unchanged documents
+
+        let check_names = checks
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .collect::<BTreeSet<_>>();
+        #[cfg(feature = "csaf-validator-lib")]
+        let check_names = {
+            let mut check_names = check_names;
+            if Option::<csaf_walker::verification::check::csaf_validator_lib::Profile>::from(
+                self.verification.profile,
+            )
+            .is_some()
+            {
+                check_names.insert("csaf_validator_lib".to_string());
+            }
+            check_names
+        };
+
+        let previous_state = match &self.incremental.state {
+            Some(path) => ReportState::load_from(path)?.filter(|state| {
+                let reusable = state.checks == check_names;
+                if !reusable {
+                    log::info!("Checks changed, not reusing the report state");
+                }
+                reusable
+            }),
+            None => None,
+        };
+        let state = self
+            .incremental
+            .state
+            .as_ref()
+            .map(|_| Arc::new(Mutex::new(ReportState::new(check_names))));
+        let reused: Arc<Mutex<Vec<(Url, DocumentState)>>> = Default::default();
 
         {
             let total = total.clone();
-            let duplicates = duplicates.clone();
-            let errors = errors.clone();
-            let warnings = warnings.clone();
+            let collector = collector.clone();
+            let state = state.clone();
 
             let visitor = move |advisory: Result<
                 VerifiedAdvisory<ValidatedAdvisory, &'static str>,
@@ -92,8 +240,8 @@ impl Report {
             >| {
                 (*total).fetch_add(1, Ordering::Release);
 
-                let errors = errors.clone();
-                let warnings = warnings.clone();
+                let collector = collector.clone();
+                let state = state.clone();
 
                 async move {
                     let adv = match advisory {
@@ -110,28 +258,79 @@ impl Report {
                                 },
                             };
 
-                            errors.lock().await.insert(name, err.to_string());
+                            collector.errors.lock().await.insert(name, err.to_string());
                             return Ok::<_, anyhow::Error>(());
                         }
                     };
 
-                    if !adv.failures.is_empty() {
-                        let name = DocumentKey::for_document(&adv);
-                        warnings
+                    let mut document = DocumentState::new(&adv, DocumentKey::for_document(&adv));
+                    document.tracking_id = Some(adv.csaf.document.tracking.id.clone());
+                    document.digest = Some(content_digest(&adv.advisory.as_retrieved().data));
+                    document.lifecycle = Some(adv.advisory.lifecycle.clone())
+                        .filter(|lifecycle| !lifecycle.is_current());
+
+                    if collector.count_severities || state.is_some() {
+                        document.severity =
+                            Some(aggregate_severity(&adv.advisory.as_retrieved().data));
+                    }
+
+                    // changes, recorded when syncing into a local store
+
+                    if let Ok(path) = adv.url.to_file_path() {
+                        document.change = load_diff(&path).await;
+                    }
+
+                    let paths = adv
+                        .failures
+                        .values()
+                        .chain(adv.notices.values())
+                        .flatten()
+                        .filter_map(|finding| finding.path.as_deref())
+                        .collect::<BTreeSet<_>>();
+
+                    if !paths.is_empty() {
+                        if let Ok(json) =
+                            serde_json::from_slice::<Value>(&adv.advisory.as_retrieved().data)
+                        {
+                            document.excerpts = paths
+                                .into_iter()
+                                .filter_map(|path| {
+                                    excerpt(&json, path).map(|text| (path.to_string(), text))
+                                })
+                                .collect();
+                        }
+                    }
+
+                    let url = adv.url.clone();
+
+                    document.failures = adv
+                        .failures
+                        .into_iter()
+                        .map(|(check, findings)| (check.to_string(), findings))
+                        .collect();
+                    document.notices = adv
+                        .notices
+                        .into_iter()
+                        .map(|(check, findings)| (check.to_string(), findings))
+                        .collect();
+
+                    if let Some(state) = &state {
+                        state
                             .lock()
                             .await
-                            .entry(name)
-                            .or_default()
-                            .extend(adv.failures.into_values().flatten());
+                            .documents
+                            .insert(url.clone(), document.clone());
                     }
 
+                    collector.collect(url, document).await;
+
                     Ok::<_, anyhow::Error>(())
                 }
             };
 
             // content checks
 
-            let visitor = VerifyingVisitor::with_checks(visitor, init_verifying_visitor());
+            let visitor = VerifyingVisitor::with_checks(visitor, checks);
             #[cfg(feature = "csaf-validator-lib")]
             let visitor = {
                 if let Some(profile) = self.verification.profile.into() {
@@ -140,25 +339,48 @@ impl Report {
                         "csaf_validator_lib",
                         csaf_walker::verification::check::csaf_validator_lib::CsafValidatorLib::new(
                             profile,
-                        ).timeout(timeout).ignore(self.verification.skip),
+                        ).timeout(timeout).ignore(self.verification.skip).tests(self.verification.tests),
                     )
                 } else {
                     visitor
                 }
             };
 
+            let visitor = match &cache {
+                Some(cache) => visitor.with_cache(cache.clone()),
+                None => visitor,
+            };
+
             // validation (can we work with this document?)
 
-            let visitor = ValidationVisitor::new(visitor).with_options(options);
+            let visitor = ValidationVisitor::new(CollectKeyIssues {
+                visitor,
+                issues: key_issues.clone(),
+            })
+            .with_options(options);
+
+            let budget = self.runner.memory_budget();
+
+            let reused = reused.clone();
 
             walk_visitor(
                 progress,
                 self.client,
-                self.discover,
-                self.filter,
+                DiscoverConfig::from(self.discover).with_offload(offload),
+                FilterConfig::try_from(self.filter)?,
                 self.runner,
                 move |source| async move {
-                    let visitor = { RetrievingVisitor::new(source.clone(), visitor) };
+                    let visitor = {
+                        RetrievingVisitor::new(source.clone(), visitor)
+                            .with_memory_budget(budget)
+                            .with_allowed_client_errors(allowed_client_errors)
+                    };
+
+                    let visitor = ReuseUnchanged {
+                        visitor,
+                        state: previous_state.map(Arc::new),
+                        reused,
+                    };
 
                     Ok(DetectDuplicatesVisitor {
                         duplicates,
@@ -169,34 +391,310 @@ impl Report {
             .await?;
         }
 
+        if let Some(cache) = cache {
+            cache.store().await?;
+        }
+
+        // merge the findings of unchanged documents
+
+        let reused = std::mem::take(&mut *reused.lock().await);
+        if !reused.is_empty() {
+            log::info!(
+                "Reusing the findings of {} unchanged document(s)",
+                reused.len()
+            );
+        }
+        for (url, document) in reused {
+            total.fetch_add(1, Ordering::Release);
+            if let Some(state) = &state {
+                state
+                    .lock()
+                    .await
+                    .documents
+                    .insert(url.clone(), document.clone());
+            }
+            collector.collect(url, document).await;
+        }
+
+        if let (Some(path), Some(state)) = (&self.incremental.state, &state) {
+            state.lock().await.store_to(path)?;
+        }
+
+        if let Some(path) = self
+            .baseline
+            .baseline
+            .filter(|_| self.baseline.update_baseline)
+        {
+            let findings = std::mem::take(&mut *collector.findings.lock().await);
+            let baseline = baseline.update(findings);
+            log::info!(
+                "Updating baseline with {} suppression(s): {}",
+                baseline.suppressions.len(),
+                path.display()
+            );
+            baseline.store_to(&path)?;
+        }
+
         let total = (*total).load(Ordering::Acquire);
 
-        Self::render(
-            self.render,
-            ReportResult {
-                total,
-                duplicates: &*duplicates.lock().await,
-                errors: &*errors.lock().await,
-                warnings: &*warnings.lock().await,
-            },
-        )?;
+        let duplicates = duplicates.lock().await;
+        let errors = collector.errors.lock().await;
+        let warnings = collector.warnings.lock().await;
+        let notices = collector.notices.lock().await;
+        let excerpts = collector.excerpts.lock().await;
+        let changes = collector.changes.lock().await;
+        let origins = collector.origins.lock().await;
+        let lifecycles = collector.lifecycles.lock().await;
+        let key_issues = key_issues.lock().await;
+
+        let result = ReportResult {
+            total,
+            duplicates: &duplicates,
+            errors: &errors,
+            warnings: &warnings,
+            notices: &notices,
+            excerpts: &excerpts,
+            suppressed: collector.suppressed.load(Ordering::Acquire),
+            changes: &changes,
+            key_issues: &key_issues,
+            origins: &origins,
+            lifecycles: &lifecycles,
+        };
+
+        let files = Self::render(self.render, result.clone(), clock)?;
+
+        self.signing.apply(&files).await?;
+
+        if let Some(notifier) = notifier {
+            let severities = std::mem::take(&mut *collector.severities.lock().await);
+            let summary = RunSummary::new(&result, severities, Some(source));
+            notifier.notify(&summary).await?;
+        }
+
+        gate.check(errors.len(), warnings.values().map(Vec::len).sum())?;
 
         Ok(())
     }
 
-    fn render(render: RenderOptions, report: ReportResult) -> anyhow::Result<()> {
-        let mut out = std::fs::File::create(&render.output)?;
-
-        render_to_html(
-            &mut out,
+    fn render(
+        render: RenderOptions,
+        report: ReportResult,
+        clock: Clock,
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        render_to_files(
             &report,
             ReportRenderOption {
                 output: render.output,
                 base_url: render.base_url,
                 source_url: render.source_url,
+                page_size: render.page_size,
+                report_options: ReportOptions {
+                    clock,
+                    ..render.style.report_options()?
+                },
             },
-        )?;
+        )
+    }
+}
 
-        Ok(())
+/// Only keep the checks required by a validation policy, all if none are required.
+///
+/// The `csaf_validator_lib` check is controlled by the verification arguments.
+fn select_checks(
+    checks: Vec<(&'static str, Box<dyn Check>)>,
+    required: &[String],
+) -> anyhow::Result<Vec<(&'static str, Box<dyn Check>)>> {
+    if required.is_empty() {
+        return Ok(checks);
+    }
+
+    if let Some(unknown) = required
+        .iter()
+        .find(|name| *name != "csaf_validator_lib" && !checks.iter().any(|(n, _)| n == name))
+    {
+        anyhow::bail!("Unknown check in validation policy: {unknown}");
+    }
+
+    Ok(checks
+        .into_iter()
+        .filter(|(name, _)| required.iter().any(|r| r == name))
+        .collect())
+}
+
+/// Collects the issues of the provider keys, passing everything on to the wrapped visitor.
+struct CollectKeyIssues<V: ValidatedVisitor> {
+    visitor: V,
+    issues: Arc<Mutex<Vec<KeyIssue>>>,
+}
+
+impl<V: ValidatedVisitor> ValidatedVisitor for CollectKeyIssues<V> {
+    type Error = V::Error;
+    type Context = V::Context;
+
+    async fn visit_context(
+        &self,
+        context: &ValidationContext<'_>,
+    ) -> Result<Self::Context, Self::Error> {
+        {
+            let mut issues = self.issues.lock().await;
+            for issue in context.key_issues {
+                if !issues.contains(issue) {
+                    issues.push(issue.clone());
+                }
+            }
+        }
+
+        self.visitor.visit_context(context).await
+    }
+
+    async fn visit_advisory(
+        &self,
+        context: &Self::Context,
+        result: Result<ValidatedAdvisory, ValidationError>,
+    ) -> Result<(), Self::Error> {
+        self.visitor.visit_advisory(context, result).await
+    }
+}
+
+/// Collects the outcome of checked documents, applying the baseline.
+#[derive(Clone, Default)]
+struct Collector {
+    duplicates: Arc<Mutex<Duplicates>>,
+    errors: Arc<Mutex<BTreeMap<DocumentKey, String>>>,
+    warnings: Arc<Mutex<BTreeMap<DocumentKey, Vec<Finding>>>>,
+    notices: Arc<Mutex<BTreeMap<DocumentKey, Vec<Finding>>>>,
+    excerpts: Arc<Mutex<BTreeMap<DocumentKey, BTreeMap<String, String>>>>,
+    changes: Arc<Mutex<BTreeMap<DocumentKey, AdvisoryDiff>>>,
+    origins: Arc<Mutex<BTreeMap<DocumentKey, AggregatorOrigin>>>,
+    lifecycles: Arc<Mutex<BTreeMap<Url, Lifecycle>>>,
+    severities: Arc<Mutex<BTreeMap<String, usize>>>,
+    count_severities: bool,
+    suppressions: Arc<Baseline>,
+    suppressed: Arc<AtomicUsize>,
+    /// The clock, deciding if suppressions have expired
+    clock: Clock,
+    /// All findings, for updating the baseline
+    findings: Arc<Mutex<BTreeSet<(Url, String)>>>,
+}
+
+impl Collector {
+    async fn collect(&self, url: Url, document: DocumentState) {
+        let DocumentState {
+            key,
+            mut failures,
+            mut notices,
+            excerpts,
+            change,
+            severity,
+            tracking_id,
+            digest,
+            origin,
+            lifecycle,
+            ..
+        } = document;
+
+        if let (Some(tracking_id), Some(digest)) = (tracking_id, digest) {
+            self.duplicates
+                .lock()
+                .await
+                .add_tracking_id(tracking_id, key.clone(), digest);
+        }
+
+        if let Some(severity) = severity.filter(|_| self.count_severities) {
+            *self.severities.lock().await.entry(severity).or_default() += 1;
+        }
+
+        if let Some(change) = change {
+            self.changes.lock().await.insert(key.clone(), change);
+        }
+
+        if let Some(origin) = origin {
+            self.origins.lock().await.insert(key.clone(), origin);
+        }
+
+        if let Some(lifecycle) = lifecycle {
+            self.lifecycles.lock().await.insert(url.clone(), lifecycle);
+        }
+
+        // remove suppressed findings, remembering the others for the baseline
+
+        self.findings
+            .lock()
+            .await
+            .extend(failures.keys().map(|check| (url.clone(), check.clone())));
+
+        for results in [&mut failures, &mut notices] {
+            results.retain(|check, messages| {
+                let suppress = self
+                    .suppressions
+                    .is_suppressed_today(&url, check, &self.clock);
+                if suppress {
+                    self.suppressed.fetch_add(messages.len(), Ordering::Release);
+                }
+                !suppress
+            });
+        }
+
+        if !excerpts.is_empty() {
+            self.excerpts.lock().await.insert(key.clone(), excerpts);
+        }
+
+        if !failures.is_empty() {
+            self.warnings
+                .lock()
+                .await
+                .entry(key.clone())
+                .or_default()
+                .extend(failures.into_values().flatten());
+        }
+
+        if !notices.is_empty() {
+            self.notices
+                .lock()
+                .await
+                .entry(key)
+                .or_default()
+                .extend(notices.into_values().flatten());
+        }
+    }
+}
+
+/// Skips documents which didn't change since the previous run, remembering their previous state.
+struct ReuseUnchanged<V: DiscoveredVisitor> {
+    visitor: V,
+    state: Option<Arc<ReportState>>,
+    reused: Arc<Mutex<Vec<(Url, DocumentState)>>>,
+}
+
+impl<V: DiscoveredVisitor> DiscoveredVisitor for ReuseUnchanged<V> {
+    type Error = V::Error;
+    type Context = V::Context;
+
+    async fn visit_context(
+        &self,
+        context: &DiscoveredContext<'_>,
+    ) -> Result<Self::Context, Self::Error> {
+        self.visitor.visit_context(context).await
+    }
+
+    async fn visit_advisory(
+        &self,
+        context: &Self::Context,
+        advisory: DiscoveredAdvisory,
+    ) -> Result<(), Self::Error> {
+        if let Some(document) = self
+            .state
+            .as_ref()
+            .and_then(|state| state.unchanged(&advisory))
+        {
+            log::debug!("Unchanged, reusing previous findings: {}", advisory.url);
+            self.reused
+                .lock()
+                .await
+                .push((advisory.url, document.clone()));
+            return Ok(());
+        }
+
+        self.visitor.visit_advisory(context, advisory).await
     }
 }
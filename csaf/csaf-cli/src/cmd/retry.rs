@@ -0,0 +1,62 @@
+use crate::{cmd::JournalArguments, Cli, Command};
+use anyhow::Context;
+use csaf_walker::visitors::journal::JournalFile;
+use std::path::{Path, PathBuf};
+use walker_common::cli::parse::try_parse_from;
+use walker_common::progress::Progress;
+
+/// Process the documents, which failed during a run, again, using the same arguments.
+#[derive(clap::Args, Debug)]
+pub struct Retry {
+    /// The journal of the run (see `--journal`). It gets replaced with the documents failing again.
+    journal: PathBuf,
+}
+
+impl Retry {
+    pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
+        let Some(file) = JournalFile::load_from(&self.journal)? else {
+            anyhow::bail!("Journal not found: {}", self.journal.display());
+        };
+
+        if file.entries.is_empty() {
+            log::info!("No failed documents to retry");
+            return Ok(());
+        }
+
+        let command = file.command.join(" ");
+        let cli = try_parse_from::<Cli, _, _>(&file.command)
+            .with_context(|| format!("Failed to parse the command of the journal: {command}"))?;
+
+        let list = list_file(&self.journal);
+        std::fs::write(&list, serde_json::to_vec_pretty(&file.list())?)
+            .with_context(|| format!("Failed to write list of advisories: {}", list.display()))?;
+
+        log::info!("Retrying {} document(s): {command}", file.entries.len());
+
+        let journal = JournalArguments {
+            journal: Some(self.journal),
+            command: Some(file.command),
+        };
+
+        let result = match cli.command {
+            Command::Sync(cmd) => cmd.retry(list.clone(), journal).run(progress).await,
+            Command::Download(cmd) => cmd.retry(list.clone(), journal).run(progress).await,
+            _ => Err(anyhow::anyhow!(
+                "Unable to retry the command of the journal: {command}"
+            )),
+        };
+
+        if let Err(err) = std::fs::remove_file(&list) {
+            log::warn!("Failed to remove {}: {err}", list.display());
+        }
+
+        result
+    }
+}
+
+/// The temporary list of advisories, next to the journal
+fn list_file(journal: &Path) -> PathBuf {
+    let mut name = journal.as_os_str().to_owned();
+    name.push(".retry");
+    PathBuf::from(name)
+}
@@ -2,10 +2,26 @@ use crate::{
     cmd::{DiscoverArguments, FilterArguments},
     common::walk_standard,
 };
+use anyhow::{bail, Context};
 use csaf::Csaf;
-use csaf_walker::validation::{ValidatedAdvisory, ValidationError};
+use csaf_walker::{
+    source::SourceDescriptor,
+    validation::{ValidatedAdvisory, ValidationError},
+    visitors::{
+        filter::FilterConfig,
+        store::{copy_dir, DIR_METADATA},
+    },
+};
+use std::path::{Path, PathBuf};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 use walker_common::{
-    cli::{client::ClientArguments, runner::RunnerArguments, validation::ValidationArguments},
+    cli::{
+        client::ClientArguments, gate::GateArguments, runner::RunnerArguments,
+        validation::ValidationArguments,
+    },
     progress::Progress,
 };
 
@@ -26,47 +42,181 @@ pub struct Scan {
 
     #[command(flatten)]
     validation: ValidationArguments,
+
+    #[command(flatten)]
+    gate: GateArguments,
+
+    /// Copy matching documents, including their sidecar files, into this directory, preserving the layout of the store. Requires a `file:` source.
+    #[arg(long, env = "CSAF_WALKER_SCAN_OUTPUT_DIR")]
+    output_dir: Option<PathBuf>,
+
+    /// Create symbolic links to the matching documents, instead of copying them.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_SCAN_SYMLINK",
+        requires = "output_dir",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    symlink: bool,
+}
+
+/// Output of matching documents into a filtered copy of a store.
+struct ScanOutput {
+    /// the (canonical) base of the scanned store
+    source: PathBuf,
+    /// the output directory
+    target: PathBuf,
+    /// link instead of copying
+    symlink: bool,
+}
+
+impl ScanOutput {
+    /// Create the output directory, with the metadata of the scanned store.
+    fn new(source: &str, target: PathBuf, symlink: bool) -> anyhow::Result<Self> {
+        let SourceDescriptor::File(source) = SourceDescriptor::parse(source)? else {
+            bail!("Writing matching documents to an output directory requires a 'file:' source");
+        };
+        let source = std::fs::canonicalize(&source)
+            .with_context(|| format!("Failed to resolve source: {}", source.display()))?;
+
+        std::fs::create_dir_all(&target)
+            .with_context(|| format!("Failed to create output directory: {}", target.display()))?;
+        copy_dir(&source.join(DIR_METADATA), &target.join(DIR_METADATA))
+            .context("Failed to copy store metadata")?;
+
+        Ok(Self {
+            source,
+            target,
+            symlink,
+        })
+    }
+
+    /// Copy (or link) a document, and all of its sidecar files, into the output directory.
+    fn write(&self, advisory: &ValidatedAdvisory) -> anyhow::Result<()> {
+        let file = advisory
+            .url
+            .to_file_path()
+            .map_err(|()| anyhow::anyhow!("Not a local document: {}", advisory.url))?;
+        let relative = file
+            .strip_prefix(&self.source)
+            .with_context(|| format!("Document is outside of the store: {}", file.display()))?;
+
+        let (Some(dir), Some(name)) = (relative.parent(), relative.file_name()) else {
+            bail!("Invalid document path: {}", relative.display());
+        };
+        let name = name.to_string_lossy();
+        let sidecar_prefix = format!("{name}.");
+
+        let target = self.target.join(dir);
+        std::fs::create_dir_all(&target)
+            .with_context(|| format!("Failed to create directory: {}", target.display()))?;
+
+        for entry in std::fs::read_dir(self.source.join(dir))? {
+            let entry = entry?;
+            let entry_name = entry.file_name();
+            let entry_name = entry_name.to_string_lossy();
+            if entry_name == name || entry_name.starts_with(&sidecar_prefix) {
+                self.transfer(&entry.path(), &target.join(entry.file_name()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn transfer(&self, source: &Path, target: &Path) -> anyhow::Result<()> {
+        log::debug!("Output: {} -> {}", source.display(), target.display());
+
+        match std::fs::remove_file(target) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                return Err(err)
+                    .with_context(|| format!("Failed to replace file: {}", target.display()))
+            }
+            _ => {}
+        }
+
+        if self.symlink {
+            symlink(source, target)
+        } else {
+            std::fs::copy(source, target).map(|_| ())
+        }
+        .with_context(|| format!("Failed to write file: {}", target.display()))
+    }
+}
+
+#[cfg(unix)]
+fn symlink(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(source, target)
+}
+
+#[cfg(windows)]
+fn symlink(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(source, target)
 }
 
 impl Scan {
     pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
-        walk_standard(
-            progress,
-            self.client,
-            self.runner,
-            self.discover,
-            self.filter,
-            self.validation,
-            |advisory: Result<ValidatedAdvisory, ValidationError>| async move {
-                match advisory {
-                    Ok(adv) => {
-                        println!("Advisory: {}", adv.url);
-                        log::debug!("  Metadata: {:?}", adv.sha256);
-                        log::debug!("    SHA256: {:?}", adv.sha256);
-                        log::debug!("    SHA512: {:?}", adv.sha512);
-                        match serde_json::from_slice::<Csaf>(&adv.data) {
-                            Ok(csaf) => {
-                                println!(
-                                    "  {} ({}): {}",
-                                    csaf.document.tracking.id,
-                                    csaf.document.tracking.initial_release_date,
-                                    csaf.document.title
-                                );
+        // scanning only finds errors
+        self.gate.errors_only()?;
+
+        let errors = Arc::new(AtomicUsize::default());
+
+        let output = self
+            .output_dir
+            .map(|target| ScanOutput::new(&self.discover.source, target, self.symlink))
+            .transpose()?
+            .map(Arc::new);
+
+        {
+            let errors = errors.clone();
+            walk_standard(
+                progress,
+                self.client,
+                self.runner,
+                self.discover,
+                FilterConfig::try_from(self.filter)?,
+                self.validation,
+                move |advisory: Result<ValidatedAdvisory, ValidationError>| {
+                    let errors = errors.clone();
+                    let output = output.clone();
+                    async move {
+                        match advisory {
+                            Ok(adv) => {
+                                println!("Advisory: {}", adv.url);
+                                log::debug!("  Metadata: {:?}", adv.sha256);
+                                log::debug!("    SHA256: {:?}", adv.sha256);
+                                log::debug!("    SHA512: {:?}", adv.sha512);
+                                match serde_json::from_slice::<Csaf>(&adv.data) {
+                                    Ok(csaf) => {
+                                        println!(
+                                            "  {} ({}): {}",
+                                            csaf.document.tracking.id,
+                                            csaf.document.tracking.initial_release_date,
+                                            csaf.document.title
+                                        );
+                                        if let Some(output) = &output {
+                                            output.write(&adv)?;
+                                        }
+                                    }
+                                    Err(err) => {
+                                        eprintln!("  Format error: {err}");
+                                        errors.fetch_add(1, Ordering::Release);
+                                    }
+                                }
                             }
                             Err(err) => {
-                                eprintln!("  Format error: {err}");
+                                eprintln!("Advisory(ERR): {err}");
+                                errors.fetch_add(1, Ordering::Release);
                             }
                         }
+
+                        Ok::<_, anyhow::Error>(())
                     }
-                    Err(err) => {
-                        eprintln!("Advisory(ERR): {err}");
-                    }
-                }
+                },
+            )
+            .await?;
+        }
 
-                Ok::<_, anyhow::Error>(())
-            },
-        )
-        .await?;
+        self.gate.check(errors.load(Ordering::Acquire), 0)?;
 
         Ok(())
     }
@@ -2,6 +2,7 @@ use crate::{
     cmd::{DiscoverArguments, FilterArguments, SkipArguments},
     common::walk_visitor,
 };
+use csaf_walker::visitors::filter::FilterConfig;
 use csaf_walker::{
     discover::DiscoverConfig, retrieve::RetrievingVisitor, validation::ValidationVisitor,
     visitors::skip::SkipFailedVisitor,
@@ -12,7 +13,7 @@ use walker_common::{
     since::Since,
     validate::ValidationOptions,
 };
-use walker_extras::visitors::{SendArguments, SendVisitor};
+use walker_extras::visitors::{RoutingSendVisitor, SendArguments};
 
 /// Walk a source and send validated/retrieved documents to a sink.
 #[derive(clap::Args, Debug)]
@@ -45,8 +46,10 @@ pub struct Send {
 
 impl Send {
     pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
-        let options: ValidationOptions = self.validation.into();
-        let send: SendVisitor = self.send.into_visitor().await?;
+        let offload = self.runner.offload();
+        let options = ValidationOptions::try_from(self.validation)?.offload(offload.clone());
+        let allowed_client_errors = options.allowed_client_errors.clone();
+        let send: RoutingSendVisitor = self.send.into_routing_visitor().await?;
 
         let since = Since::new(
             self.skip.since,
@@ -55,13 +58,18 @@ impl Send {
                 .since_file_offset
                 .map(|d| d.into())
                 .unwrap_or_default(),
+            &self.runner.clock(),
         )?;
 
-        walk_visitor(
+        let budget = self.runner.memory_budget();
+
+        let completion = walk_visitor(
             progress,
             self.client,
-            DiscoverConfig::from(self.discover).with_since(since.since),
-            self.filter,
+            DiscoverConfig::from(self.discover)
+                .with_since(since.since)
+                .with_offload(offload),
+            FilterConfig::try_from(self.filter)?,
             self.runner,
             move |source| async move {
                 let visitor = {
@@ -72,6 +80,8 @@ impl Send {
                         })
                         .with_options(options)
                     })
+                    .with_memory_budget(budget)
+                    .with_allowed_client_errors(allowed_client_errors)
                 };
 
                 Ok(visitor)
@@ -79,7 +89,7 @@ impl Send {
         )
         .await?;
 
-        since.store()?;
+        since.store_completion(completion.as_ref())?;
 
         Ok(())
     }
@@ -0,0 +1,62 @@
+use actix_web::{web, App, HttpResponse, HttpServer};
+use csaf_walker::query::{index::Index, Query};
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+
+/// Serve a query API for the advisories of an index, built by the `index` command.
+#[derive(clap::Args, Debug)]
+pub struct Serve {
+    /// The index file (an SQLite database).
+    #[arg(long, env = "CSAF_WALKER_INDEX")]
+    index: PathBuf,
+
+    /// The address to listen on.
+    #[arg(long, env = "CSAF_WALKER_BIND", default_value = "127.0.0.1:8080")]
+    bind: String,
+}
+
+impl Serve {
+    pub async fn run(self) -> anyhow::Result<()> {
+        if !self.index.is_file() {
+            anyhow::bail!(
+                "Index not found, create it using the index command: {}",
+                self.index.display()
+            );
+        }
+
+        let index = Index::open(&self.index)?;
+        log::info!(
+            "Serving {} advisories, listening on {}",
+            index.len()?,
+            self.bind
+        );
+
+        let index = web::Data::new(Mutex::new(index));
+        HttpServer::new(move || {
+            App::new()
+                .app_data(index.clone())
+                .route("/api/v1/advisories", web::get().to(advisories))
+        })
+        .bind(&self.bind)?
+        .run()
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Find advisories, e.g. `/api/v1/advisories?cve=CVE-2024-0001&severity=high`
+async fn advisories(index: web::Data<Mutex<Index>>, query: web::Query<Query>) -> HttpResponse {
+    let result = index
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .query(&query);
+
+    match result {
+        Ok(entries) => HttpResponse::Ok().json(entries),
+        Err(err) => {
+            log::warn!("Failed to query index: {err}");
+            HttpResponse::InternalServerError().body(err.to_string())
+        }
+    }
+}
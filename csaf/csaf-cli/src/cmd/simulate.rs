@@ -0,0 +1,135 @@
+use actix_web::{web, App, HttpResponse, HttpServer};
+use csaf_walker::simulation::{generate, SimulatedDistribution, SimulationOptions};
+use reqwest::Url;
+use std::path::{Component, Path, PathBuf};
+
+/// Generate a simulated provider, and serve it locally.
+#[derive(clap::Args, Debug)]
+pub struct Simulate {
+    /// The directory to generate the provider into, used as the document root when serving.
+    #[arg(short, long, env = "CSAF_WALKER_SIMULATION_OUTPUT")]
+    output: PathBuf,
+
+    /// The number of advisories.
+    #[arg(short = 'n', long, default_value = "100")]
+    advisories: usize,
+
+    /// The minimum size of an advisory, in bytes.
+    #[arg(long, default_value = "4096")]
+    min_size: usize,
+
+    /// The maximum size of an advisory, in bytes.
+    #[arg(long, default_value = "65536")]
+    max_size: usize,
+
+    /// The seed for choosing the sizes of the advisories.
+    #[arg(long, default_value = "0")]
+    seed: u64,
+
+    /// Don't sign the advisories.
+    #[arg(long)]
+    unsigned: bool,
+
+    /// The distribution listed in the provider metadata.
+    #[arg(long, value_enum, default_value_t = Distribution::Rolie)]
+    distribution: Distribution,
+
+    /// The address to listen on.
+    #[arg(long, env = "CSAF_WALKER_BIND", default_value = "127.0.0.1:8080")]
+    bind: String,
+
+    /// The base URL of the provider, defaults to the address to listen on.
+    #[arg(long)]
+    base_url: Option<Url>,
+
+    /// Only generate the provider, without serving it.
+    #[arg(long)]
+    no_serve: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum Distribution {
+    Directory,
+    Rolie,
+}
+
+impl From<Distribution> for SimulatedDistribution {
+    fn from(value: Distribution) -> Self {
+        match value {
+            Distribution::Directory => Self::Directory,
+            Distribution::Rolie => Self::Rolie,
+        }
+    }
+}
+
+impl Simulate {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let base = match self.base_url {
+            Some(base) => base,
+            None => Url::parse(&format!("http://{}/", self.bind))?,
+        };
+
+        let options = SimulationOptions::new()
+            .advisories(self.advisories)
+            .sizes(self.min_size, self.max_size)
+            .seed(self.seed)
+            .signed(!self.unsigned)
+            .distribution(self.distribution.into());
+
+        let provider = {
+            let output = self.output.clone();
+            tokio::task::spawn_blocking(move || generate(&base, &output, &options)).await??
+        };
+
+        log::info!(
+            "Generated {} advisories ({} bytes), provider metadata: {}",
+            provider.advisories,
+            provider.bytes,
+            provider.metadata_url
+        );
+        if let Some(fingerprint) = &provider.fingerprint {
+            log::info!("Signed using key: {fingerprint}");
+        }
+
+        if self.no_serve {
+            return Ok(());
+        }
+
+        log::info!("Listening on {}", self.bind);
+
+        let root = web::Data::new(self.output);
+        HttpServer::new(move || {
+            App::new()
+                .app_data(root.clone())
+                .route("/{path:.*}", web::get().to(file))
+        })
+        .bind(&self.bind)?
+        .run()
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Serve a file of the generated provider
+async fn file(root: web::Data<PathBuf>, path: web::Path<String>) -> HttpResponse {
+    let path = Path::new(path.as_str());
+    // only serve files below the root
+    if !path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return HttpResponse::NotFound().finish();
+    }
+
+    let content_type = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => "application/json",
+        Some("csv") => "text/csv",
+        _ => "text/plain",
+    };
+
+    match tokio::fs::read(root.join(path)).await {
+        Ok(data) => HttpResponse::Ok().content_type(content_type).body(data),
+        Err(_) => HttpResponse::NotFound().finish(),
+    }
+}
@@ -0,0 +1,105 @@
+use crate::cmd::Layout;
+use anyhow::Context;
+use csaf_walker::{
+    migrate::{migrate, store_roots},
+    model::store::StoreLayout,
+};
+use std::path::PathBuf;
+use walker_common::cli::lock::LockArguments;
+
+/// Manage a local store.
+#[derive(clap::Args, Debug)]
+pub struct Store {
+    #[command(subcommand)]
+    command: StoreCommand,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum StoreCommand {
+    Info(Info),
+    Migrate(Migrate),
+}
+
+impl Store {
+    pub async fn run(self) -> anyhow::Result<()> {
+        match self.command {
+            StoreCommand::Info(cmd) => cmd.run(),
+            StoreCommand::Migrate(cmd) => cmd.run().await,
+        }
+    }
+}
+
+fn base(data: Option<PathBuf>) -> anyhow::Result<PathBuf> {
+    match data {
+        Some(base) => Ok(base),
+        None => std::env::current_dir().context("Get current working directory"),
+    }
+}
+
+/// Show the layout of a store, and its TLP partitions.
+#[derive(clap::Args, Debug)]
+pub struct Info {
+    /// The store directory, defaults to the local directory.
+    #[arg(short, long, env = "CSAF_WALKER_DATA")]
+    data: Option<PathBuf>,
+}
+
+impl Info {
+    pub fn run(self) -> anyhow::Result<()> {
+        let base = base(self.data)?;
+
+        let roots = store_roots(&base)?;
+        if roots.is_empty() {
+            println!("{}: not a store", base.display());
+        }
+
+        for root in roots {
+            match StoreLayout::detect(&root)? {
+                Some(layout) => println!("{}: {layout}", root.display()),
+                None => println!("{}: not a store", root.display()),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Convert a store to a different layout, without downloading its documents again.
+#[derive(clap::Args, Debug)]
+pub struct Migrate {
+    /// The store directory, defaults to the local directory.
+    #[arg(short, long, env = "CSAF_WALKER_DATA")]
+    data: Option<PathBuf>,
+
+    /// The layout to convert to.
+    #[arg(long)]
+    to: Layout,
+
+    /// Skip comparing the digests of all documents before and after the migration.
+    #[arg(long)]
+    no_verify: bool,
+
+    #[command(flatten)]
+    lock: LockArguments,
+}
+
+impl Migrate {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let base = base(self.data)?;
+
+        let _locks = self.lock.lock(&base, None).await?;
+
+        let to = StoreLayout::from(self.to);
+        let migration = migrate(&base, to, !self.no_verify)?;
+
+        log::info!(
+            "Migrated {} to layout {to}: moved {} distribution directories, linked {} objects, verified {} documents",
+            base.display(),
+            migration.moved,
+            migration.objects,
+            migration.verified,
+        );
+
+        Ok(())
+    }
+}
@@ -1,15 +1,29 @@
 use crate::{
-    cmd::{DiscoverArguments, FilterArguments, SkipArguments, StoreArguments},
+    cmd::{
+        CrossCheckArguments, DiscoverArguments, FilterArguments, JournalArguments,
+        RetentionArguments, SkipArguments, StoreArguments,
+    },
     common::walk_visitor,
 };
 use csaf_walker::discover::DiscoverConfig;
+use csaf_walker::visitors::filter::FilterConfig;
 use csaf_walker::{
     retrieve::RetrievingVisitor,
     validation::ValidationVisitor,
-    visitors::{skip::SkipExistingVisitor, store::StoreVisitor},
+    visitors::{
+        cross_check::CrossCheckVisitor,
+        journal::JournalVisitor,
+        skip::{SkipExistingVisitor, SkipVisitor},
+        store::StoreVisitor,
+    },
 };
+use std::path::PathBuf;
 use walker_common::{
-    cli::{client::ClientArguments, runner::RunnerArguments, validation::ValidationArguments},
+    attestation::{MirrorPredicate, Tool, ValidationPolicy, MIRROR_PREDICATE_TYPE},
+    cli::{
+        attestation::AttestationArguments, client::ClientArguments, lock::LockArguments,
+        runner::RunnerArguments, validation::ValidationArguments,
+    },
     progress::Progress,
     since::Since,
     validate::ValidationOptions,
@@ -38,13 +52,56 @@ pub struct Sync {
 
     #[command(flatten)]
     store: StoreArguments,
+
+    #[command(flatten)]
+    lock: LockArguments,
+
+    #[command(flatten)]
+    retention: RetentionArguments,
+
+    #[command(flatten)]
+    cross_check: CrossCheckArguments,
+
+    #[command(flatten)]
+    journal: JournalArguments,
+
+    #[command(flatten)]
+    attestation: AttestationArguments,
 }
 
 impl Sync {
-    pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
-        let options: ValidationOptions = self.validation.into();
-        let store: StoreVisitor = self.store.try_into()?;
+    /// Only process the listed documents, recording those failing again in the journal.
+    ///
+    /// The documents are processed regardless of when they changed, and the since-state isn't
+    /// updated.
+    pub fn retry(mut self, list: PathBuf, journal: JournalArguments) -> Self {
+        self.discover.list = Some(list);
+        self.skip.since = None;
+        self.skip.since_file = None;
+        self.journal = journal;
+        self
+    }
+
+    pub async fn run(mut self, progress: Progress) -> anyhow::Result<()> {
+        let clock = self.runner.clock();
+        let started_on = clock.now_utc();
+        let target = self.store.base()?;
+        let _locks = self
+            .lock
+            .lock(&target, self.skip.since_file.as_deref())
+            .await?;
+
+        let staging = self.store.prepare_staging()?;
+        let offload = self.runner.offload();
+        let options = ValidationOptions::try_from(self.validation)?.offload(offload.clone());
+        let allowed_client_errors = options.allowed_client_errors.clone();
+        let validation_policy = ValidationPolicy::from(&options);
+        let source = self.discover.source.clone();
+        let store = StoreVisitor::try_from(self.store)?.clock(clock.clone());
+        let finisher = store.finisher();
         let base = store.base.clone();
+        let layout = store.layout;
+        let retention_base = base.clone();
 
         let since = Since::new(
             self.skip.since,
@@ -53,33 +110,85 @@ impl Sync {
                 .since_file_offset
                 .map(|d| d.into())
                 .unwrap_or_default(),
+            &clock,
         )?;
 
-        walk_visitor(
+        let head = match self.skip.head_check {
+            true => Some(self.client.clone().new_fetcher().await?),
+            false => None,
+        };
+
+        let budget = self.runner.memory_budget();
+        let cross_check = self.cross_check.config(self.client.clone()).await?;
+        let predicates = self.skip.predicates(&self.client).await?;
+        let journal = self.journal.journal();
+        let journal_visitor = journal.clone();
+
+        let result = walk_visitor(
             progress,
             self.client,
-            DiscoverConfig::from(self.discover).with_since(since.since),
-            self.filter,
+            DiscoverConfig::from(self.discover)
+                .with_since(since.since)
+                .with_offload(offload),
+            FilterConfig::try_from(self.filter)?,
             self.runner,
             move |source| async move {
                 let base = base.clone();
                 let visitor = {
                     RetrievingVisitor::new(
                         source.clone(),
-                        ValidationVisitor::new(store).with_options(options),
+                        CrossCheckVisitor {
+                            visitor: ValidationVisitor::new(JournalVisitor {
+                                visitor: store,
+                                journal: journal_visitor,
+                            })
+                            .with_options(options),
+                            config: cross_check,
+                        },
                     )
+                    .with_memory_budget(budget)
+                    .with_allowed_client_errors(allowed_client_errors)
                 };
 
-                Ok(SkipExistingVisitor {
-                    visitor,
+                Ok(SkipVisitor {
+                    visitor: SkipExistingVisitor {
+                        visitor,
+                        output: base.clone(),
+                        layout,
+                        since: since.since,
+                        head,
+                    },
                     output: base,
-                    since: since.since,
+                    layout,
+                    predicates,
                 })
             },
         )
-        .await?;
+        .await;
+
+        self.journal.store(journal.as_deref())?;
+        let completion = result?;
+
+        finisher.finish()?;
+        self.retention.apply(&retention_base)?;
+
+        if let Some(staging) = staging {
+            staging.promote()?;
+        }
+
+        since.store_completion(completion.as_ref())?;
 
-        since.store()?;
+        self.attestation
+            .apply(&target, MIRROR_PREDICATE_TYPE, || MirrorPredicate {
+                source,
+                started_on,
+                finished_on: clock.now_utc(),
+                validation_policy,
+                tool: Tool {
+                    name: env!("CARGO_PKG_NAME").to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                },
+            })?;
 
         Ok(())
     }
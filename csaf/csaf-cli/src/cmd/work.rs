@@ -0,0 +1,169 @@
+use crate::cmd::{DiscoverArguments, StoreArguments};
+use csaf_walker::{
+    discover::{DiscoverConfig, DiscoveredContext, DiscoveredVisitor},
+    queue::DirectoryQueue,
+    retrieve::RetrievingVisitor,
+    source::{new_source, Source},
+    validation::ValidationVisitor,
+    visitors::store::StoreVisitor,
+};
+use futures::{stream, StreamExt};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use walker_common::{
+    cli::{client::ClientArguments, runner::RunnerArguments, validation::ValidationArguments},
+    progress::Progress,
+    validate::ValidationOptions,
+};
+
+/// Retrieve, validate, and store advisories from a work queue, shared with other instances.
+#[derive(clap::Args, Debug)]
+pub struct Work {
+    #[command(flatten)]
+    client: ClientArguments,
+
+    #[command(flatten)]
+    runner: RunnerArguments,
+
+    #[command(flatten)]
+    discover: DiscoverArguments,
+
+    #[command(flatten)]
+    validation: ValidationArguments,
+
+    #[command(flatten)]
+    store: StoreArguments,
+
+    #[command(flatten)]
+    queue: QueueArguments,
+}
+
+#[derive(Debug, clap::Parser)]
+#[command(next_help_heading = "Queue")]
+pub struct QueueArguments {
+    /// The work queue (a directory), as filled by `discover --queue`.
+    #[arg(long, env = "CSAF_WALKER_QUEUE")]
+    queue: PathBuf,
+
+    /// Number of jobs to claim from the queue at once.
+    #[arg(long, env = "CSAF_WALKER_QUEUE_BATCH_SIZE", default_value = "100")]
+    batch_size: usize,
+
+    /// The ID of this worker, which must be unique among all workers of the queue. Defaults to the process ID and start time.
+    #[arg(long, env = "CSAF_WALKER_WORKER_ID")]
+    worker_id: Option<String>,
+
+    /// Release jobs which have been claimed for longer than this duration (e.g. by a worker which crashed) back into the queue, before starting.
+    #[arg(long, env = "CSAF_WALKER_QUEUE_RELEASE_AFTER", default_value = "1h")]
+    release_after: humantime::Duration,
+}
+
+impl QueueArguments {
+    fn worker_id(&self) -> String {
+        self.worker_id.clone().unwrap_or_else(|| {
+            let started = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis();
+            format!("{}-{started}", std::process::id())
+        })
+    }
+}
+
+impl Work {
+    pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
+        let queue = DirectoryQueue::new(&self.queue.queue)?;
+        let worker = self.queue.worker_id();
+
+        let released = queue.release_abandoned(self.queue.release_after.into())?;
+        if released > 0 {
+            log::info!("Released {released} abandoned jobs");
+        }
+
+        let offload = self.runner.offload();
+        let options = ValidationOptions::try_from(self.validation)?.offload(offload.clone());
+        let allowed_client_errors = options.allowed_client_errors.clone();
+        let store: StoreVisitor = self.store.try_into()?;
+        let finisher = store.finisher();
+        let limit = match self.runner.workers {
+            0 => usize::MAX,
+            n => n,
+        };
+
+        let source = new_source(
+            DiscoverConfig::from(self.discover).with_offload(offload),
+            self.client,
+        )
+        .await?;
+        let metadata = source.load_metadata().await?;
+        let directories = metadata
+            .distributions
+            .iter()
+            .filter_map(|distribution| distribution.directory_url.clone())
+            .collect::<Vec<_>>();
+
+        let visitor = RetrievingVisitor::new(
+            source.clone(),
+            ValidationVisitor::new(store).with_options(options),
+        )
+        .with_memory_budget(self.runner.memory_budget())
+        .with_allowed_client_errors(allowed_client_errors);
+        let context = visitor
+            .visit_context(&DiscoveredContext {
+                metadata: &metadata,
+            })
+            .await?;
+
+        let mut processed = 0usize;
+        let mut failed = 0usize;
+
+        loop {
+            let jobs = queue.claim(&worker, self.queue.batch_size)?;
+            if jobs.is_empty() {
+                break;
+            }
+
+            log::info!("Claimed {} jobs", jobs.len());
+            let bar = progress.start(jobs.len());
+
+            let results = stream::iter(jobs)
+                .map(|job| {
+                    let advisory = job
+                        .entry
+                        .clone()
+                        .into_discovered(Arc::new(job.entry.distribution_context(&directories)));
+                    let visitor = &visitor;
+                    let context = &context;
+                    let bar = &bar;
+                    async move {
+                        let result = visitor.visit_advisory(context, advisory).await;
+                        bar.tick();
+                        (job, result)
+                    }
+                })
+                .buffer_unordered(limit)
+                .collect::<Vec<_>>()
+                .await;
+
+            for (job, result) in results {
+                if let Err(err) = result {
+                    log::warn!("Failed to process {}: {err}", job.entry.url);
+                    failed += 1;
+                }
+                processed += 1;
+                queue.complete(job)?;
+            }
+        }
+
+        log::info!("Processed {processed} jobs, {failed} failed");
+
+        finisher.finish()?;
+
+        if failed > 0 {
+            anyhow::bail!("Failed to process {failed} of {processed} jobs");
+        }
+
+        Ok(())
+    }
+}
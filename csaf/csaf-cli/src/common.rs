@@ -1,17 +1,25 @@
 use crate::cmd::DiscoverArguments;
+use anyhow::Context;
 use csaf_walker::{
     discover::{DiscoverConfig, DiscoveredVisitor},
+    metadata::load_aggregator,
+    model::aggregator::AggregatorMetadata,
     retrieve::RetrievingVisitor,
     source::{new_source, DispatchSource},
     validation::{ValidatedVisitor, ValidationVisitor},
     visitors::filter::{FilterConfig, FilteringVisitor},
-    walker::Walker,
+    walker::{Error, Walker},
 };
 use std::future::Future;
+use std::sync::Arc;
 use walker_common::{
     cli::{client::ClientArguments, runner::RunnerArguments, validation::ValidationArguments},
+    clock::Clock,
+    concurrency::AdaptiveConcurrency,
+    deadline::Completion,
+    fetcher::{Fetcher, FetcherOptions},
     progress::Progress,
-    validate::ValidationOptions,
+    validate::{source::KeyFallback, ValidationOptions},
 };
 
 pub async fn walk_standard<V>(
@@ -27,7 +35,11 @@ where
     V: ValidatedVisitor,
     V::Error: Send + Sync + 'static,
 {
-    let options: ValidationOptions = validation.into();
+    let offload = runner.offload();
+    let options = ValidationOptions::try_from(validation)?.offload(offload.clone());
+    let allowed_client_errors = options.allowed_client_errors.clone();
+    let discover = DiscoverConfig::with_offload(discover.into(), offload);
+    let budget = runner.memory_budget();
 
     walk_visitor(
         progress,
@@ -39,10 +51,14 @@ where
             Ok(RetrievingVisitor::new(
                 source.clone(),
                 ValidationVisitor::new(visitor).with_options(options),
-            ))
+            )
+            .with_memory_budget(budget)
+            .with_allowed_client_errors(allowed_client_errors))
         },
     )
-    .await
+    .await?;
+
+    Ok(())
 }
 
 impl From<DiscoverArguments> for DiscoverConfig {
@@ -50,18 +66,29 @@ impl From<DiscoverArguments> for DiscoverConfig {
         Self {
             since: None,
             source: value.source,
+            decryption: value.decryption_key,
+            sidecar_miss_limit: value.sidecar_miss_limit,
+            offload: None,
+            resume_threshold: value.resume_threshold,
+            key_fallback: KeyFallback {
+                wkd: value.key_wkd,
+                keyservers: value.keyserver,
+            },
+            list: value.list,
+            aggregator: value.aggregator,
         }
     }
 }
 
 /// Create a [`FilteringVisitor`] from a [`FilterConfig`].
-pub fn filter<V>(filter: impl Into<FilterConfig>, visitor: V) -> FilteringVisitor<V>
+pub fn filter<V>(filter: impl Into<FilterConfig>, clock: Clock, visitor: V) -> FilteringVisitor<V>
 where
     V: DiscoveredVisitor,
 {
     FilteringVisitor {
         visitor,
         config: filter.into(),
+        clock,
     }
 }
 
@@ -72,16 +99,40 @@ pub async fn walk_visitor<F, Fut, V>(
     filter: impl Into<FilterConfig>,
     runner: RunnerArguments,
     f: F,
-) -> anyhow::Result<()>
+) -> anyhow::Result<Option<Completion>>
 where
     F: FnOnce(DispatchSource) -> Fut,
     Fut: Future<Output = anyhow::Result<V>>,
     V: DiscoveredVisitor,
     V::Error: Send + Sync + 'static,
 {
-    let source = new_source(discover, client).await?;
+    let adaptive = runner.adaptive();
+    let discover: DiscoverConfig = discover.into();
+    let options = FetcherOptions::from(client)
+        .adaptive(adaptive.clone())
+        .clock(runner.clock());
+
+    let aggregator = aggregator(&discover, options.clone()).await?;
+    let source = new_source(discover, options).await?;
 
-    walk_source(progress, source, filter, runner, f).await
+    walk_source(progress, source, filter, runner, adaptive, aggregator, f).await
+}
+
+/// Load the metadata of the aggregator, if the advisories should be attributed to one.
+pub async fn aggregator(
+    discover: &DiscoverConfig,
+    options: FetcherOptions,
+) -> anyhow::Result<Option<Arc<AggregatorMetadata>>> {
+    let Some(url) = &discover.aggregator else {
+        return Ok(None);
+    };
+
+    let fetcher = Fetcher::new(options).await?;
+    let aggregator = load_aggregator(&fetcher, url.clone())
+        .await
+        .with_context(|| format!("Failed to load aggregator metadata: {url}"))?;
+
+    Ok(Some(Arc::new(aggregator)))
 }
 
 pub async fn walk_source<F, Fut, V>(
@@ -89,29 +140,44 @@ pub async fn walk_source<F, Fut, V>(
     source: DispatchSource,
     filter_config: impl Into<FilterConfig>,
     runner: RunnerArguments,
+    adaptive: Option<Arc<AdaptiveConcurrency>>,
+    aggregator: Option<Arc<AggregatorMetadata>>,
     f: F,
-) -> anyhow::Result<()>
+) -> anyhow::Result<Option<Completion>>
 where
     F: FnOnce(DispatchSource) -> Fut,
     Fut: Future<Output = anyhow::Result<V>>,
     V: DiscoveredVisitor,
     V::Error: Send + Sync + 'static,
 {
+    let deadline = runner.deadline();
+    let checkpoint = runner.checkpoint()?;
+    let clock = runner.clock();
     let visitor = f(source.clone()).await?;
-    let walker = Walker::new(source).with_progress(progress);
-
-    match runner.workers {
-        1 => {
-            walker.walk(filter(filter_config, visitor)).await?;
-        }
+    let walker = Walker::new(source)
+        .with_progress(progress)
+        .with_deadline(deadline)
+        .with_adaptive_concurrency(adaptive)
+        .with_checkpoint(checkpoint)
+        .with_aggregator(aggregator);
+
+    let result = match runner.workers {
+        1 => walker.walk(filter(filter_config, clock, visitor)).await,
         n => {
             walker
-                .walk_parallel(n, filter(filter_config, visitor))
-                .await?;
+                .walk_parallel(n, filter(filter_config, clock, visitor))
+                .await
         }
-    }
+    };
 
-    Ok(())
+    match result {
+        Ok(_) => Ok(None),
+        Err(Error::Deadline(completion)) => {
+            log::warn!("Deadline exceeded: {completion}");
+            Ok(Some(completion))
+        }
+        Err(err) => Err(err.into()),
+    }
 }
 
 #[cfg(test)]
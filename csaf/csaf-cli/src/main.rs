@@ -3,11 +3,16 @@ mod common;
 
 use clap::Parser;
 use cmd::{
-    discover::Discover, download::Download, metadata::Metadata, parse::Parse, report::Report,
-    scan::Scan, send::Send, sync::Sync,
+    consistency::Consistency, discover::Discover, doctor::Doctor, download::Download, fetch::Fetch,
+    ignore::Ignore, metadata::Metadata, parse::Parse, report::Report, retry::Retry, scan::Scan,
+    send::Send, store::Store, sync::Sync, work::Work,
 };
 use std::process::ExitCode;
-use walker_common::{cli::log::Logging, progress::Progress, utils::measure::MeasureTime};
+use walker_common::{
+    cli::{log::Logging, parse::parse},
+    progress::Progress,
+    utils::measure::MeasureTime,
+};
 
 #[derive(Debug, Parser)]
 #[command(version, about = "CSAF Tool", author, long_about = None)]
@@ -30,6 +35,19 @@ enum Command {
     Report(Report),
     Send(Send),
     Metadata(Metadata),
+    Consistency(Consistency),
+    Work(Work),
+    Fetch(Fetch),
+    Store(Store),
+    Doctor(Doctor),
+    Ignore(Ignore),
+    Retry(Retry),
+    #[cfg(feature = "index")]
+    Index(cmd::index::Index),
+    #[cfg(feature = "serve")]
+    Serve(cmd::serve::Serve),
+    #[cfg(feature = "simulate")]
+    Simulate(cmd::simulate::Simulate),
 }
 
 impl Command {
@@ -43,6 +61,19 @@ impl Command {
             Command::Report(cmd) => cmd.run(progress).await,
             Command::Send(cmd) => cmd.run(progress).await,
             Command::Metadata(cmd) => cmd.run().await,
+            Command::Consistency(cmd) => cmd.run().await,
+            Command::Work(cmd) => cmd.run(progress).await,
+            Command::Fetch(cmd) => cmd.run(progress).await,
+            Command::Store(cmd) => cmd.run().await,
+            Command::Doctor(cmd) => cmd.run().await,
+            Command::Ignore(cmd) => cmd.run(),
+            Command::Retry(cmd) => cmd.run(progress).await,
+            #[cfg(feature = "index")]
+            Command::Index(cmd) => cmd.run(progress).await,
+            #[cfg(feature = "serve")]
+            Command::Serve(cmd) => cmd.run().await,
+            #[cfg(feature = "simulate")]
+            Command::Simulate(cmd) => cmd.run().await,
         }
     }
 }
@@ -65,7 +96,7 @@ impl Cli {
 
 #[tokio::main]
 async fn main() -> ExitCode {
-    if let Err(err) = Cli::parse().run().await {
+    if let Err(err) = parse::<Cli>().run().await {
         log::error!("Failed to execute: {err}");
         for (n, cause) in err.chain().enumerate().skip(1) {
             log::info!("  {n}: {cause}");
@@ -0,0 +1,45 @@
+#ifndef CSAF_WALKER_H
+#define CSAF_WALKER_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define CSAF_WALKER_OK 0
+#define CSAF_WALKER_ERROR -1
+#define CSAF_WALKER_ABORTED 1
+
+/* A validated document, all pointers are only valid for the duration of the callback */
+typedef struct {
+    /* The URL of the document */
+    const char *url;
+    /* The validation error, or NULL if the document is valid */
+    const char *error;
+    /* The content of the document, or NULL if it could not be retrieved */
+    const uint8_t *data;
+    /* The length of the content */
+    size_t data_len;
+    /* The last modification, in seconds since the epoch, or -1 if unknown */
+    int64_t last_modified;
+    /* The ETag, or NULL if unknown */
+    const char *etag;
+} CsafWalkerDocument;
+
+/* Callback for each document, returning a non-zero value aborts the walk */
+typedef int (*CsafWalkerCallback)(const CsafWalkerDocument *document, void *user_data);
+
+/* Walk all documents of a source, which can be a domain, a URL, or a local directory.
+ * A NULL source or callback fails the call, returning CSAF_WALKER_ERROR. */
+int csaf_walker_walk(const char *source, CsafWalkerCallback callback, void *user_data);
+
+/* The error message of the last failed call on the current thread, or NULL */
+const char *csaf_walker_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,102 @@
+//! A blocking API, for applications not using async
+//!
+//! Similar to `reqwest::blocking`, the [`Walker`] owns its own runtime, and wraps the async API
+//! with it. It must not be used from within an async runtime.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use csaf_walker::blocking::Walker;
+//!
+//! fn mirror() -> anyhow::Result<()> {
+//!   let walker = Walker::new("redhat.com", Default::default())?;
+//!   walker.mirror("data/redhat.com")?;
+//!   Ok(())
+//! }
+//! ```
+
+use crate::{
+    discover::{DiscoverConfig, DiscoveredAdvisory},
+    retrieve::RetrievingVisitor,
+    source::{new_source, DispatchSource},
+    validation::{ValidatedAdvisory, ValidationError, ValidationVisitor},
+    visitors::store::StoreVisitor,
+    walker,
+};
+use futures::TryStreamExt;
+use std::{path::PathBuf, pin::pin};
+use tokio::runtime::Runtime;
+use walker_common::{fetcher::FetcherOptions, validate::ValidationOptions};
+
+/// A walker, blocking the current thread
+pub struct Walker {
+    runtime: Runtime,
+    source: DispatchSource,
+    validation: ValidationOptions,
+}
+
+impl Walker {
+    /// Create a new walker, using the same source lookup as [`new_source`].
+    pub fn new(
+        discover: impl Into<DiscoverConfig>,
+        fetcher: impl Into<FetcherOptions>,
+    ) -> anyhow::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let source = runtime.block_on(new_source(discover, fetcher))?;
+
+        Ok(Self {
+            runtime,
+            source,
+            validation: Default::default(),
+        })
+    }
+
+    pub fn with_validation(mut self, validation: impl Into<ValidationOptions>) -> Self {
+        self.validation = validation.into();
+        self
+    }
+
+    /// Discover all advisories, without retrieving them.
+    pub fn discover(&self) -> anyhow::Result<Vec<DiscoveredAdvisory>> {
+        self.runtime.block_on(
+            walker::Walker::new(self.source.clone())
+                .discover_stream()
+                .try_collect(),
+        )
+    }
+
+    /// Retrieve and validate all advisories, calling `f` for each one.
+    pub fn walk<F>(&self, mut f: F) -> anyhow::Result<()>
+    where
+        F: FnMut(Result<ValidatedAdvisory, ValidationError>) -> anyhow::Result<()>,
+    {
+        self.runtime.block_on(async {
+            let mut advisories = pin!(walker::Walker::new(self.source.clone())
+                .validate_stream(1, self.validation.clone()));
+
+            while let Some(advisory) = advisories.try_next().await? {
+                f(advisory)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Mirror all advisories into a local directory, like the `sync` command of `csaf-cli`.
+    pub fn mirror(&self, target: impl Into<PathBuf>) -> anyhow::Result<()> {
+        let store = StoreVisitor::new(target);
+        let finisher = store.finisher();
+        let visitor = RetrievingVisitor::new(
+            self.source.clone(),
+            ValidationVisitor::new(store).with_options(self.validation.clone()),
+        );
+
+        self.runtime
+            .block_on(walker::Walker::new(self.source.clone()).walk(visitor))?;
+
+        finisher.finish()?;
+        Ok(())
+    }
+}
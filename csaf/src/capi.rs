@@ -0,0 +1,221 @@
+//! A minimal C API
+//!
+//! This allows embedding the walker into applications not written in Rust. A shared library can
+//! be built using:
+//!
+//! ```shell
+//! cargo rustc -p csaf-walker --release --features capi --crate-type cdylib
+//! ```
+//!
+//! The matching header file is located at `include/csaf_walker.h`.
+
+use crate::{
+    blocking::Walker,
+    validation::{ValidatedAdvisory, ValidationError},
+};
+use std::{
+    cell::RefCell,
+    ffi::{c_char, c_int, c_void, CStr, CString},
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr,
+};
+use walker_common::utils::url::Urlify;
+
+/// A validated document, passed to the callback.
+///
+/// All pointers are only valid for the duration of the callback.
+#[repr(C)]
+pub struct CsafWalkerDocument {
+    /// The URL of the document
+    pub url: *const c_char,
+    /// The validation error, or null if the document is valid
+    pub error: *const c_char,
+    /// The content of the document, or null if it could not be retrieved
+    pub data: *const u8,
+    /// The length of the content
+    pub data_len: usize,
+    /// The last modification, in seconds since the epoch, or `-1` if unknown
+    pub last_modified: i64,
+    /// The ETag, or null if unknown
+    pub etag: *const c_char,
+}
+
+/// Callback for each document. Returning a non-zero value aborts the walk.
+pub type CsafWalkerCallback =
+    extern "C" fn(document: *const CsafWalkerDocument, user_data: *mut c_void) -> c_int;
+
+/// The walk completed
+pub const CSAF_WALKER_OK: c_int = 0;
+/// The walk failed, the error can be retrieved using [`csaf_walker_last_error`]
+pub const CSAF_WALKER_ERROR: c_int = -1;
+/// The walk was aborted by the callback
+pub const CSAF_WALKER_ABORTED: c_int = 1;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(err: impl ToString) {
+    let err = CString::new(err.to_string().replace('\0', " ")).ok();
+    LAST_ERROR.with(|last| *last.borrow_mut() = err);
+}
+
+/// Get the error message of the last failed call on the current thread.
+///
+/// Returns null if there was none. The pointer is valid until the next call of
+/// [`csaf_walker_walk`] on the same thread.
+#[no_mangle]
+pub extern "C" fn csaf_walker_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |err| err.as_ptr())
+    })
+}
+
+/// Walk all documents of a source, calling the callback for each validated document.
+///
+/// The source can be a domain, a URL to a `provider-metadata.json`, or a local directory.
+///
+/// # Safety
+///
+/// `source` must be a valid, null terminated string. `callback` may be null, which fails the
+/// call. `user_data` is passed on to the callback as is.
+#[no_mangle]
+pub unsafe extern "C" fn csaf_walker_walk(
+    source: *const c_char,
+    callback: Option<CsafWalkerCallback>,
+    user_data: *mut c_void,
+) -> c_int {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+
+    if source.is_null() {
+        set_last_error("Source must not be null");
+        return CSAF_WALKER_ERROR;
+    }
+
+    // a function pointer must not be null, a null callback from C arrives as `None`
+    let Some(callback) = callback else {
+        set_last_error("Callback must not be null");
+        return CSAF_WALKER_ERROR;
+    };
+
+    let source = match CStr::from_ptr(source).to_str() {
+        Ok(source) => source.to_string(),
+        Err(err) => {
+            set_last_error(format!("Invalid source: {err}"));
+            return CSAF_WALKER_ERROR;
+        }
+    };
+
+    let result = catch_unwind(AssertUnwindSafe(|| walk(&source, callback, user_data)));
+
+    match result {
+        Ok(Ok(Outcome::Completed)) => CSAF_WALKER_OK,
+        Ok(Ok(Outcome::Aborted)) => CSAF_WALKER_ABORTED,
+        Ok(Err(err)) => {
+            set_last_error(format!("{err:#}"));
+            CSAF_WALKER_ERROR
+        }
+        Err(_) => {
+            set_last_error("Walker panicked");
+            CSAF_WALKER_ERROR
+        }
+    }
+}
+
+enum Outcome {
+    Completed,
+    Aborted,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Aborted by callback")]
+struct Aborted;
+
+fn walk(
+    source: &str,
+    callback: CsafWalkerCallback,
+    user_data: *mut c_void,
+) -> anyhow::Result<Outcome> {
+    let walker = Walker::new(source, Default::default())?;
+
+    let result = walker.walk(|advisory| {
+        let rc = visit(&advisory, callback, user_data)?;
+        if rc != 0 {
+            return Err(Aborted.into());
+        }
+        Ok(())
+    });
+
+    match result {
+        Ok(()) => Ok(Outcome::Completed),
+        Err(err) if err.is::<Aborted>() => Ok(Outcome::Aborted),
+        Err(err) => Err(err),
+    }
+}
+
+fn visit(
+    advisory: &Result<ValidatedAdvisory, ValidationError>,
+    callback: CsafWalkerCallback,
+    user_data: *mut c_void,
+) -> anyhow::Result<c_int> {
+    let url = CString::new(advisory_url(advisory).as_str())?;
+    let error = match advisory {
+        Ok(_) => None,
+        Err(err) => Some(CString::new(err.to_string().replace('\0', " "))?),
+    };
+
+    let retrieved = match advisory {
+        Ok(advisory) => Some(&advisory.retrieved),
+        Err(ValidationError::Retrieval(_)) => None,
+        Err(
+            ValidationError::DigestMismatch { retrieved, .. }
+            | ValidationError::Signature { retrieved, .. }
+            | ValidationError::MalformedSidecar { retrieved, .. }
+            | ValidationError::Tls { retrieved, .. }
+            | ValidationError::Policy { retrieved, .. },
+        ) => Some(retrieved),
+    };
+
+    let etag = retrieved
+        .and_then(|retrieved| retrieved.metadata.etag.as_deref())
+        .map(CString::new)
+        .transpose()?;
+
+    let document = CsafWalkerDocument {
+        url: url.as_ptr(),
+        error: error.as_ref().map_or(ptr::null(), |error| error.as_ptr()),
+        data: retrieved.map_or(ptr::null(), |retrieved| retrieved.data.as_ptr()),
+        data_len: retrieved.map_or(0, |retrieved| retrieved.data.len()),
+        last_modified: retrieved
+            .and_then(|retrieved| retrieved.metadata.last_modification)
+            .map_or(-1, |timestamp| timestamp.unix_timestamp()),
+        etag: etag.as_ref().map_or(ptr::null(), |etag| etag.as_ptr()),
+    };
+
+    Ok(callback(&document, user_data))
+}
+
+fn advisory_url(advisory: &Result<ValidatedAdvisory, ValidationError>) -> &url::Url {
+    match advisory {
+        Ok(advisory) => advisory.url(),
+        Err(err) => err.url(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_null_arguments() {
+        let source = CString::new("example.com").unwrap();
+
+        let rc = unsafe { csaf_walker_walk(source.as_ptr(), None, ptr::null_mut()) };
+        assert_eq!(rc, CSAF_WALKER_ERROR);
+
+        let error = unsafe { CStr::from_ptr(csaf_walker_last_error()) };
+        assert_eq!(error.to_str().unwrap(), "Callback must not be null");
+    }
+}
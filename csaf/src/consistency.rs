@@ -0,0 +1,202 @@
+//! Cross-checking the different listings of a provider
+//!
+//! A provider may list its documents in ROLIE feeds, as well as in the `index.txt` and
+//! `changes.csv` files of its directory distributions. All those listings are expected to contain
+//! the same documents. A document missing from one of them is a common provider issue, and leads
+//! to incomplete mirrors, depending on which listing a consumer uses.
+
+use crate::{model::metadata::ProviderMetadata, rolie::RolieFeed};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use url::{ParseError, Url};
+use walker_common::{
+    changes::{self, ChangeSource},
+    fetcher::{self, Fetcher, Json},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Fetch error: {0}")]
+    Fetcher(#[from] fetcher::Error),
+    #[error("URL error: {0}")]
+    Url(#[from] ParseError),
+    #[error("Failed to parse 'changes.csv': {0}")]
+    Changes(#[from] changes::Error),
+}
+
+/// A type of listing
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Listing {
+    /// All ROLIE feeds
+    Rolie,
+    /// All `index.txt` files of directory distributions
+    Index,
+    /// All `changes.csv` files of directory distributions
+    Changes,
+}
+
+impl Display for Listing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rolie => f.write_str("ROLIE feeds"),
+            Self::Index => f.write_str("index.txt"),
+            Self::Changes => f.write_str("changes.csv"),
+        }
+    }
+}
+
+/// The outcome of the consistency check
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    /// Number of documents, by listing. Listings not provided by the provider are absent.
+    pub listings: BTreeMap<Listing, usize>,
+    /// Documents missing from some listings, with the listings they are missing from
+    pub missing: BTreeMap<Url, Vec<Listing>>,
+}
+
+impl ConsistencyReport {
+    /// Compare the documents of the listings
+    pub fn new(listings: BTreeMap<Listing, BTreeSet<Url>>) -> Self {
+        let all = listings
+            .values()
+            .flatten()
+            .cloned()
+            .collect::<BTreeSet<_>>();
+
+        let missing = all
+            .into_iter()
+            .filter_map(|url| {
+                let missing = listings
+                    .iter()
+                    .filter(|(_, urls)| !urls.contains(&url))
+                    .map(|(listing, _)| *listing)
+                    .collect::<Vec<_>>();
+                (!missing.is_empty()).then_some((url, missing))
+            })
+            .collect();
+
+        Self {
+            listings: listings
+                .iter()
+                .map(|(listing, urls)| (*listing, urls.len()))
+                .collect(),
+            missing,
+        }
+    }
+
+    /// Check if all listings contain the same documents
+    pub fn is_consistent(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Load all listings of the provider, and compare them.
+pub async fn check(
+    fetcher: &Fetcher,
+    metadata: &ProviderMetadata,
+) -> Result<ConsistencyReport, Error> {
+    let mut listings = BTreeMap::<Listing, BTreeSet<Url>>::new();
+
+    for distribution in &metadata.distributions {
+        for feed in distribution.rolie.iter().flat_map(|rolie| &rolie.feeds) {
+            log::info!("Loading ROLIE feed: {}", feed.url);
+            let Json(feed) = fetcher.fetch::<Json<RolieFeed>>(feed.url.clone()).await?;
+            let urls = listings.entry(Listing::Rolie).or_default();
+            for link in feed
+                .feed
+                .entry
+                .iter()
+                .flat_map(|entry| &entry.link)
+                .filter(|link| link.rel == "self")
+            {
+                urls.insert(Url::parse(&link.href)?);
+            }
+        }
+
+        if let Some(base) = &distribution.directory_url {
+            let base = directory_base(base)?;
+
+            log::info!("Loading index: {base}");
+            if let Some(index) = fetcher
+                .fetch::<Option<String>>(base.join("index.txt")?)
+                .await?
+            {
+                let urls = listings.entry(Listing::Index).or_default();
+                for line in index.lines().map(str::trim).filter(|line| !line.is_empty()) {
+                    urls.insert(base.join(line)?);
+                }
+            }
+
+            if let Some(changes) = fetcher
+                .fetch::<Option<String>>(base.join("changes.csv")?)
+                .await?
+            {
+                let urls = listings.entry(Listing::Changes).or_default();
+                for entry in ChangeSource::parse(&changes)?.entries {
+                    urls.insert(base.join(&entry.file)?);
+                }
+            }
+        }
+    }
+
+    Ok(ConsistencyReport::new(listings))
+}
+
+/// Ensure the directory URL ends with a slash, so that files can be joined
+fn directory_base(url: &Url) -> Result<Url, ParseError> {
+    if url.path().ends_with('/') {
+        Ok(url.clone())
+    } else {
+        Url::parse(&format!("{url}/"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn urls(urls: &[&str]) -> BTreeSet<Url> {
+        urls.iter().map(|url| Url::parse(url).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_missing() {
+        let report = ConsistencyReport::new(BTreeMap::from([
+            (
+                Listing::Rolie,
+                urls(&[
+                    "https://example.com/2023/a.json",
+                    "https://example.com/2023/b.json",
+                ]),
+            ),
+            (Listing::Index, urls(&["https://example.com/2023/a.json"])),
+            (
+                Listing::Changes,
+                urls(&[
+                    "https://example.com/2023/a.json",
+                    "https://example.com/2023/b.json",
+                ]),
+            ),
+        ]));
+
+        assert!(!report.is_consistent());
+        assert_eq!(report.listings[&Listing::Index], 1);
+        assert_eq!(
+            report.missing,
+            BTreeMap::from([(
+                Url::parse("https://example.com/2023/b.json").unwrap(),
+                vec![Listing::Index]
+            )])
+        );
+    }
+
+    #[test]
+    fn test_consistent() {
+        let report = ConsistencyReport::new(BTreeMap::from([
+            (Listing::Rolie, urls(&["https://example.com/2023/a.json"])),
+            (Listing::Changes, urls(&["https://example.com/2023/a.json"])),
+        ]));
+
+        assert!(report.is_consistent());
+    }
+}
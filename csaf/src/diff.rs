@@ -0,0 +1,330 @@
+//! Structured differences between two versions of an advisory
+//!
+//! When an advisory changes, it is interesting to know *what* changed: which revisions were
+//! added, which product statuses changed, and which scores changed. The diff is computed on the
+//! JSON representation, so that it works with documents which don't fully conform to the schema.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// The difference between two versions of an advisory
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdvisoryDiff {
+    /// The previous version (`/document/tracking/version`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_version: Option<String>,
+    /// The current version (`/document/tracking/version`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_version: Option<String>,
+    /// Entries added to the revision history
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub revisions: Vec<Revision>,
+    /// Changed product statuses
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub product_status: Vec<StatusChange>,
+    /// Changed scores
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scores: Vec<ScoreChange>,
+}
+
+/// An entry of the revision history
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Revision {
+    pub number: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+/// Products added to, or removed from, a product status of a vulnerability
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusChange {
+    /// The vulnerability, identified by its CVE, or its first ID
+    pub vulnerability: String,
+    /// The product status (e.g. `fixed`, or `known_affected`)
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<String>,
+}
+
+/// The changed (CVSS base) score of a product, for a vulnerability
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScoreChange {
+    /// The vulnerability, identified by its CVE, or its first ID
+    pub vulnerability: String,
+    pub product: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current: Option<f64>,
+}
+
+impl AdvisoryDiff {
+    /// Compute the difference between the previous and the current version of an advisory.
+    pub fn new(previous: &Value, current: &Value) -> Self {
+        let previous_version = version(previous);
+        let current_version = version(current);
+
+        let known = revisions(previous)
+            .into_iter()
+            .map(|revision| revision.number)
+            .collect::<BTreeSet<_>>();
+        let revisions = revisions(current)
+            .into_iter()
+            .filter(|revision| !known.contains(&revision.number))
+            .collect();
+
+        let previous = vulnerabilities(previous);
+        let current = vulnerabilities(current);
+        let ids = previous
+            .keys()
+            .chain(current.keys())
+            .collect::<BTreeSet<_>>();
+
+        let mut product_status = vec![];
+        let mut scores = vec![];
+
+        for id in ids {
+            let previous = previous.get(id).copied().unwrap_or(&Value::Null);
+            let current = current.get(id).copied().unwrap_or(&Value::Null);
+
+            let previous_status = statuses(previous);
+            let current_status = statuses(current);
+            let status_names = previous_status
+                .keys()
+                .chain(current_status.keys())
+                .collect::<BTreeSet<_>>();
+            for status in status_names {
+                let empty = BTreeSet::new();
+                let previous = previous_status.get(status).unwrap_or(&empty);
+                let current = current_status.get(status).unwrap_or(&empty);
+
+                let change = StatusChange {
+                    vulnerability: id.clone(),
+                    status: status.clone(),
+                    added: current.difference(previous).cloned().collect(),
+                    removed: previous.difference(current).cloned().collect(),
+                };
+                if !change.added.is_empty() || !change.removed.is_empty() {
+                    product_status.push(change);
+                }
+            }
+
+            let previous_scores = product_scores(previous);
+            let current_scores = product_scores(current);
+            let products = previous_scores
+                .keys()
+                .chain(current_scores.keys())
+                .collect::<BTreeSet<_>>();
+            for product in products {
+                let previous = previous_scores.get(product).copied();
+                let current = current_scores.get(product).copied();
+                if previous != current {
+                    scores.push(ScoreChange {
+                        vulnerability: id.clone(),
+                        product: product.clone(),
+                        previous,
+                        current,
+                    });
+                }
+            }
+        }
+
+        Self {
+            previous_version,
+            current_version,
+            revisions,
+            product_status,
+            scores,
+        }
+    }
+
+    /// Check if no relevant changes were found.
+    pub fn is_empty(&self) -> bool {
+        self.previous_version == self.current_version
+            && self.revisions.is_empty()
+            && self.product_status.is_empty()
+            && self.scores.is_empty()
+    }
+}
+
+/// The file storing the diff of a stored document
+pub fn diff_file(file: &Path) -> String {
+    format!("{}.diff", file.display())
+}
+
+/// Load the diff, stored alongside a document.
+///
+/// Returns [`None`] if there is no diff file, or it cannot be parsed.
+pub async fn load_diff(file: &Path) -> Option<AdvisoryDiff> {
+    let file = diff_file(file);
+    match tokio::fs::read(&file).await {
+        Ok(data) => match serde_json::from_slice(&data) {
+            Ok(diff) => Some(diff),
+            Err(err) => {
+                log::warn!("Failed to parse diff ({file}): {err}");
+                None
+            }
+        },
+        Err(err) if err.kind() == ErrorKind::NotFound => None,
+        Err(err) => {
+            log::warn!("Failed to load diff ({file}): {err}");
+            None
+        }
+    }
+}
+
+fn version(document: &Value) -> Option<String> {
+    document
+        .pointer("/document/tracking/version")
+        .and_then(Value::as_str)
+        .map(ToString::to_string)
+}
+
+fn revisions(document: &Value) -> Vec<Revision> {
+    array(document.pointer("/document/tracking/revision_history"))
+        .filter_map(|revision| {
+            Some(Revision {
+                number: revision["number"].as_str()?.to_string(),
+                date: revision["date"].as_str().map(ToString::to_string),
+                summary: revision["summary"].as_str().map(ToString::to_string),
+            })
+        })
+        .collect()
+}
+
+/// Vulnerabilities, by their ID
+fn vulnerabilities(document: &Value) -> BTreeMap<String, &Value> {
+    array(document.get("vulnerabilities"))
+        .enumerate()
+        .map(|(n, vulnerability)| {
+            let id = vulnerability["cve"]
+                .as_str()
+                .or_else(|| vulnerability["ids"][0]["text"].as_str())
+                .map(ToString::to_string)
+                .unwrap_or_else(|| format!("#{}", n + 1));
+            (id, vulnerability)
+        })
+        .collect()
+}
+
+/// Products, by product status
+fn statuses(vulnerability: &Value) -> BTreeMap<String, BTreeSet<String>> {
+    vulnerability["product_status"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(status, products)| (status.clone(), strings(products)))
+        .collect()
+}
+
+/// CVSS base scores, by product. CVSS v3 is preferred over v2.
+fn product_scores(vulnerability: &Value) -> BTreeMap<String, f64> {
+    let mut result = BTreeMap::new();
+
+    for score in array(vulnerability.get("scores")) {
+        let base = score["cvss_v3"]["baseScore"]
+            .as_f64()
+            .or_else(|| score["cvss_v2"]["baseScore"].as_f64());
+        if let Some(base) = base {
+            for product in strings(&score["products"]) {
+                result.insert(product, base);
+            }
+        }
+    }
+
+    result
+}
+
+fn array(value: Option<&Value>) -> impl Iterator<Item = &Value> {
+    value.and_then(Value::as_array).into_iter().flatten()
+}
+
+fn strings(value: &Value) -> BTreeSet<String> {
+    array(Some(value))
+        .filter_map(Value::as_str)
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn document(version: &str, fixed: &[&str], score: f64) -> Value {
+        let revisions = (1..=version.parse::<usize>().expect("version must be numeric"))
+            .map(|n| {
+                json!({
+                    "number": n.to_string(),
+                    "date": "2024-01-01T00:00:00Z",
+                    "summary": format!("Revision {n}"),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "document": {
+                "tracking": {
+                    "version": version,
+                    "revision_history": revisions,
+                }
+            },
+            "vulnerabilities": [{
+                "cve": "CVE-2024-0001",
+                "product_status": {
+                    "fixed": fixed,
+                    "known_affected": ["product-a"],
+                },
+                "scores": [{
+                    "cvss_v3": {"baseScore": score},
+                    "products": ["product-a"],
+                }]
+            }]
+        })
+    }
+
+    #[test]
+    fn test_unchanged() {
+        let doc = document("1", &[], 5.0);
+        assert!(AdvisoryDiff::new(&doc, &doc).is_empty());
+    }
+
+    #[test]
+    fn test_changed() {
+        let previous = document("1", &["product-b"], 5.0);
+        let current = document("2", &["product-c"], 7.5);
+
+        assert_eq!(
+            AdvisoryDiff::new(&previous, &current),
+            AdvisoryDiff {
+                previous_version: Some("1".into()),
+                current_version: Some("2".into()),
+                revisions: vec![Revision {
+                    number: "2".into(),
+                    date: Some("2024-01-01T00:00:00Z".into()),
+                    summary: Some("Revision 2".into()),
+                }],
+                product_status: vec![StatusChange {
+                    vulnerability: "CVE-2024-0001".into(),
+                    status: "fixed".into(),
+                    added: vec!["product-c".into()],
+                    removed: vec!["product-b".into()],
+                }],
+                scores: vec![ScoreChange {
+                    vulnerability: "CVE-2024-0001".into(),
+                    product: "product-a".into(),
+                    previous: Some(5.0),
+                    current: Some(7.5),
+                }],
+            }
+        );
+    }
+}
@@ -1,12 +1,20 @@
 //! Discovering
 
-use crate::model::metadata::ProviderMetadata;
-use std::fmt::Debug;
+use crate::model::{
+    aggregator::AggregatorMetadata,
+    metadata::{Feed, ProviderMetadata, TlpLabel},
+};
+use std::fmt::{Debug, Display, Formatter};
 use std::future::Future;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::SystemTime;
+use time::OffsetDateTime;
 use url::Url;
-use walker_common::utils::url::Urlify;
+use walker_common::{
+    encryption::Decryption, offload::Offload, utils::url::Urlify, validate::source::KeyFallback,
+};
 
 /// Discovery configuration
 pub struct DiscoverConfig {
@@ -19,6 +27,31 @@ pub struct DiscoverConfig {
     /// Only report documents which have changed since the provided date. If a document has no
     /// change information, or this field is [`None`], it will always be reported.
     pub since: Option<SystemTime>,
+
+    /// Decrypt documents of a file source, which have been stored encrypted.
+    pub decryption: Option<Decryption>,
+
+    /// Stop probing for a type of sidecar file of a distribution, after it was missing that many
+    /// times.
+    pub sidecar_miss_limit: Option<usize>,
+
+    /// Calculate digests on the blocking thread pool.
+    pub offload: Option<Offload>,
+
+    /// Resume interrupted downloads of documents of at least that many bytes, instead of
+    /// starting over.
+    pub resume_threshold: Option<u64>,
+
+    /// Alternative locations for keys, which can't be retrieved from their URL in the provider
+    /// metadata.
+    pub key_fallback: KeyFallback,
+
+    /// Only walk the advisories of a list, see [`crate::source::ListSource`].
+    pub list: Option<PathBuf>,
+
+    /// The URL of the metadata of an aggregator listing the provider, used to attribute the
+    /// discovered advisories, see [`crate::walker::Walker::with_aggregator`].
+    pub aggregator: Option<Url>,
 }
 
 impl DiscoverConfig {
@@ -26,6 +59,41 @@ impl DiscoverConfig {
         self.since = since.into();
         self
     }
+
+    pub fn with_decryption(mut self, decryption: impl Into<Option<Decryption>>) -> Self {
+        self.decryption = decryption.into();
+        self
+    }
+
+    pub fn with_sidecar_miss_limit(mut self, sidecar_miss_limit: impl Into<Option<usize>>) -> Self {
+        self.sidecar_miss_limit = sidecar_miss_limit.into();
+        self
+    }
+
+    pub fn with_offload(mut self, offload: impl Into<Option<Offload>>) -> Self {
+        self.offload = offload.into();
+        self
+    }
+
+    pub fn with_resume_threshold(mut self, resume_threshold: impl Into<Option<u64>>) -> Self {
+        self.resume_threshold = resume_threshold.into();
+        self
+    }
+
+    pub fn with_key_fallback(mut self, key_fallback: KeyFallback) -> Self {
+        self.key_fallback = key_fallback;
+        self
+    }
+
+    pub fn with_list(mut self, list: impl Into<Option<PathBuf>>) -> Self {
+        self.list = list.into();
+        self
+    }
+
+    pub fn with_aggregator(mut self, aggregator: impl Into<Option<Url>>) -> Self {
+        self.aggregator = aggregator.into();
+        self
+    }
 }
 
 impl From<&str> for DiscoverConfig {
@@ -33,6 +101,13 @@ impl From<&str> for DiscoverConfig {
         Self {
             since: None,
             source: value.to_string(),
+            decryption: None,
+            sidecar_miss_limit: None,
+            offload: None,
+            resume_threshold: None,
+            key_fallback: Default::default(),
+            list: None,
+            aggregator: None,
         }
     }
 }
@@ -40,7 +115,8 @@ impl From<&str> for DiscoverConfig {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum DistributionContext {
     Directory(Url),
-    Feed(Url),
+    /// A ROLIE feed, including the feed information of the provider metadata
+    Feed(Feed),
 }
 
 impl DistributionContext {
@@ -48,9 +124,22 @@ impl DistributionContext {
     pub fn url(&self) -> &Url {
         match self {
             Self::Directory(url) => url,
-            Self::Feed(url) => url,
+            Self::Feed(feed) => &feed.url,
+        }
+    }
+
+    /// Get the ROLIE feed, if the distribution is one
+    pub fn feed(&self) -> Option<&Feed> {
+        match self {
+            Self::Directory(_) => None,
+            Self::Feed(feed) => Some(feed),
         }
     }
+
+    /// Get the TLP label of the distribution, which is only known for ROLIE feeds
+    pub fn tlp_label(&self) -> Option<&TlpLabel> {
+        self.feed().map(|feed| &feed.tlp_label)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -61,6 +150,163 @@ pub struct DiscoveredAdvisory {
     pub url: Url,
     /// The "last changed" date from the change information
     pub modified: SystemTime,
+    /// The URL to retrieve the advisory from, if different from [`Self::url`]
+    ///
+    /// This allows retrieving the document e.g. through a caching proxy, while still using the
+    /// original URL for reporting and the storage layout.
+    pub retrieval_url: Option<Url>,
+    /// Metadata of the ROLIE entry, if discovered through a ROLIE feed
+    pub rolie: Option<RolieEntry>,
+    /// The aggregator listing the provider, if discovered by walking an aggregator
+    pub aggregator: Option<Arc<AggregatorOrigin>>,
+}
+
+/// The aggregator, and the provider listed by it, an advisory originated from
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregatorOrigin {
+    /// The canonical URL of the aggregator metadata
+    pub aggregator: Url,
+    /// The name of the aggregator
+    pub aggregator_name: String,
+    /// The URL of the provider metadata, as listed by the aggregator
+    pub provider: Url,
+    /// The name of the publisher of the provider
+    pub publisher: String,
+    /// The mirror of the aggregator, if the advisory was discovered through it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mirror: Option<Url>,
+}
+
+impl AggregatorOrigin {
+    /// Look up the provider in the listing of the aggregator.
+    ///
+    /// Returns [`None`] if the provider, or the mirror it is walked through, is not listed.
+    pub fn lookup(aggregator: &AggregatorMetadata, metadata: &ProviderMetadata) -> Option<Self> {
+        let (entry, mirror) = aggregator.find(metadata)?;
+
+        Some(Self {
+            aggregator: aggregator.canonical_url.clone(),
+            aggregator_name: aggregator.aggregator.name.clone(),
+            provider: entry.metadata.url.clone(),
+            publisher: entry.metadata.publisher.name.clone(),
+            mirror: mirror.cloned(),
+        })
+    }
+}
+
+/// The file storing the origin of a stored document
+pub fn origin_file(file: &Path) -> String {
+    format!("{}.origin", file.display())
+}
+
+/// Load the origin, stored alongside a document.
+///
+/// Returns [`None`] if there is no origin file, or it cannot be parsed.
+pub async fn load_origin(file: &Path) -> Option<AggregatorOrigin> {
+    let file = origin_file(file);
+    match tokio::fs::read(&file).await {
+        Ok(data) => match serde_json::from_slice(&data) {
+            Ok(origin) => Some(origin),
+            Err(err) => {
+                log::warn!("Failed to parse origin ({file}): {err}");
+                None
+            }
+        },
+        Err(err) if err.kind() == ErrorKind::NotFound => None,
+        Err(err) => {
+            log::warn!("Failed to load origin ({file}): {err}");
+            None
+        }
+    }
+}
+
+impl Display for AggregatorOrigin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}), listed by {} ({})",
+            self.publisher, self.provider, self.aggregator_name, self.aggregator
+        )?;
+        if let Some(mirror) = &self.mirror {
+            write!(f, ", mirror: {mirror}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Metadata of a ROLIE feed entry
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RolieEntry {
+    /// The title of the entry
+    pub title: String,
+    /// When the entry was first published
+    #[serde(with = "time::serde::rfc3339")]
+    pub published: OffsetDateTime,
+    /// When the entry was last updated
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated: OffsetDateTime,
+    /// The length of the document, if announced by the feed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_length: Option<u64>,
+    /// The signature link of the entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Url>,
+    /// The SHA-256 hash link of the entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<Url>,
+    /// The SHA-512 hash link of the entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha512: Option<Url>,
+}
+
+impl DiscoveredAdvisory {
+    /// Get the URL to retrieve the advisory from
+    pub fn retrieval_url(&self) -> &Url {
+        self.retrieval_url.as_ref().unwrap_or(&self.url)
+    }
+}
+
+/// The origin of an advisory, attached to errors of processing it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Provenance {
+    /// The URL of the advisory
+    pub url: Url,
+    /// The URL of the distribution (directory or ROLIE feed) the advisory was discovered in
+    pub distribution: Url,
+    /// The canonical URL of the provider metadata
+    pub provider: Url,
+    /// The aggregator listing the provider, if walking an aggregator
+    pub aggregator: Option<Arc<AggregatorOrigin>>,
+}
+
+impl Provenance {
+    /// Create a new provenance, from the canonical URL of the provider and the advisory
+    pub fn new(provider: &Url, advisory: &DiscoveredAdvisory) -> Self {
+        Self {
+            url: advisory.url.clone(),
+            distribution: advisory.context.url().clone(),
+            provider: provider.clone(),
+            aggregator: advisory.aggregator.clone(),
+        }
+    }
+}
+
+impl Display for Provenance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (distribution: {}, provider: {}",
+            self.url, self.distribution, self.provider
+        )?;
+        if let Some(aggregator) = &self.aggregator {
+            write!(f, ", aggregator: {}", aggregator.aggregator)?;
+        }
+        f.write_str(")")
+    }
 }
 
 /// Get a document as [`DiscoveredAdvisory`]
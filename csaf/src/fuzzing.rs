@@ -0,0 +1,99 @@
+//! Generators and properties for testing the parsers of remote input
+//!
+//! Extends [`walker_common::fuzzing`] with the CSAF specific formats, like ROLIE feeds.
+
+use crate::rolie::{Content, Entry, Feed, Format, Link, RolieFeed, RolieSource};
+use proptest::prelude::*;
+use walker_common::fuzzing::timestamp;
+
+/// A link of a ROLIE entry, either to the document, its signature, or one of its digests.
+pub fn rolie_link() -> impl Strategy<Value = Link> {
+    (
+        prop_oneof![
+            Just("self"),
+            Just("signature"),
+            Just("hash"),
+            Just("alternate")
+        ],
+        "https://example\\.com/csaf/[a-z0-9_-]{1,20}\\.json(\\.asc|\\.sha256|\\.sha512)?",
+        prop::option::of(any::<u64>()),
+    )
+        .prop_map(|(rel, href, length)| Link {
+            rel: rel.to_string(),
+            href,
+            length,
+        })
+}
+
+/// An entry of a ROLIE feed.
+pub fn rolie_entry() -> impl Strategy<Value = Entry> {
+    (
+        prop::collection::vec(rolie_link(), 0..5),
+        "[a-zA-Z0-9-]{1,20}",
+        timestamp(),
+        timestamp(),
+        ".{0,40}",
+    )
+        .prop_map(|(link, id, (published, _), (updated, _), title)| Entry {
+            link,
+            format: Format {
+                schema: "https://docs.oasis-open.org/csaf/csaf/v2.0/csaf_json_schema.json"
+                    .to_string(),
+                version: "2.0".to_string(),
+            },
+            id: id.clone(),
+            published,
+            title,
+            updated,
+            content: Content {
+                src: format!("https://example.com/csaf/{id}.json"),
+                content_type: "application/json".to_string(),
+            },
+        })
+}
+
+/// A ROLIE feed.
+pub fn rolie_feed() -> impl Strategy<Value = RolieFeed> {
+    (prop::collection::vec(rolie_entry(), 0..16), timestamp()).prop_map(|(entry, (updated, _))| {
+        RolieFeed {
+            feed: Feed {
+                entry,
+                link: vec![],
+                id: "example-csaf-feed-tlp-white".to_string(),
+                title: "Example CSAF feed (TLP:WHITE)".to_string(),
+                category: vec![],
+                updated,
+            },
+        }
+    })
+}
+
+/// Parsing a ROLIE feed must not panic. Each link, which is neither a signature nor a digest,
+/// must result in a file, carrying the metadata of its entry.
+pub fn check_rolie_feed(data: &[u8]) {
+    let Ok(feed) = serde_json::from_slice::<RolieFeed>(data) else {
+        let _ = RolieSource::parse(data);
+        return;
+    };
+
+    let expected = feed
+        .feed
+        .entry
+        .iter()
+        .flat_map(|entry| &entry.link)
+        .filter(|link| link.rel != "signature" && link.rel != "hash")
+        .map(|link| link.href.clone())
+        .collect::<Vec<_>>();
+
+    let source = RolieSource::from_feed(feed);
+
+    assert_eq!(
+        source
+            .files
+            .iter()
+            .map(|file| file.file.clone())
+            .collect::<Vec<_>>(),
+        expected
+    );
+    assert!(source.files.iter().all(|file| file.entry.is_some()));
+}
@@ -0,0 +1,208 @@
+//! Ignoring known broken documents
+//!
+//! Some providers publish documents which are permanently broken, e.g. having an invalid
+//! signature for years. An [`IgnoreList`] records those documents, along with the reason and an
+//! optional expiry, so that they get skipped during discovery instead of being reported on every
+//! run. It is applied by the [`crate::visitors::filter::FilteringVisitor`].
+
+use anyhow::Context;
+use chrono::{Days, NaiveDate};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind};
+use std::path::Path;
+use std::time::Duration;
+use url::Url;
+use walker_common::clock::Clock;
+
+/// A list of documents to ignore
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IgnoreList {
+    #[serde(default)]
+    pub entries: Vec<IgnoreEntry>,
+}
+
+/// Ignore a document
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IgnoreEntry {
+    /// The URL of the document
+    pub url: Url,
+    /// Why the document is ignored
+    pub reason: String,
+    /// The last day the document is ignored, if it expires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<NaiveDate>,
+}
+
+impl IgnoreEntry {
+    /// Check if the entry is still active on the provided day.
+    pub fn is_active(&self, today: NaiveDate) -> bool {
+        self.expires.map_or(true, |expires| today <= expires)
+    }
+}
+
+impl IgnoreList {
+    /// Load an ignore list from a JSON file, returning [`None`] if the file doesn't exist.
+    pub fn load_from(path: &Path) -> anyhow::Result<Option<Self>> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Failed to open ignore list: {}", path.display()))
+            }
+        };
+
+        serde_json::from_reader(BufReader::new(file))
+            .map(Some)
+            .with_context(|| format!("Failed to parse ignore list: {}", path.display()))
+    }
+
+    /// Store the ignore list to a JSON file.
+    pub fn store_to(&self, path: &Path) -> anyhow::Result<()> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create ignore list: {}", path.display()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .with_context(|| format!("Failed to write ignore list: {}", path.display()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Add an entry, replacing an existing entry for the same URL.
+    ///
+    /// Returns `true` if an existing entry was replaced.
+    pub fn add(&mut self, entry: IgnoreEntry) -> bool {
+        match self.entries.iter_mut().find(|e| e.url == entry.url) {
+            Some(existing) => {
+                *existing = entry;
+                true
+            }
+            None => {
+                self.entries.push(entry);
+                false
+            }
+        }
+    }
+
+    /// Remove the entry of a URL, returning it if there was one.
+    pub fn remove(&mut self, url: &Url) -> Option<IgnoreEntry> {
+        let index = self.entries.iter().position(|entry| entry.url == *url)?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Add the entries of another list, replacing existing entries for the same URLs.
+    pub fn extend(&mut self, other: IgnoreList) {
+        for entry in other.entries {
+            self.add(entry);
+        }
+    }
+
+    /// Get the active entry of a document, if it is ignored.
+    pub fn ignored(&self, url: &Url, today: NaiveDate) -> Option<&IgnoreEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.url == *url && entry.is_active(today))
+    }
+}
+
+/// The current day of the clock, in UTC
+pub fn today(clock: &Clock) -> Option<NaiveDate> {
+    let now = clock.now_utc();
+    NaiveDate::from_ymd_opt(now.year(), u8::from(now.month()).into(), now.day().into())
+}
+
+/// Parse an expiry, either as a date (`2025-12-31`), or a duration from today (e.g. `90d`).
+///
+/// Being used as a value parser, this uses the system clock.
+pub fn parse_expiry(value: &str) -> anyhow::Result<NaiveDate> {
+    if let Ok(date) = value.parse::<NaiveDate>() {
+        return Ok(date);
+    }
+
+    let duration: Duration = humantime::parse_duration(value)
+        .with_context(|| format!("Expected a date (YYYY-MM-DD) or a duration: {value}"))?;
+    let days = duration.as_secs().div_ceil(24 * 60 * 60);
+
+    today(&Clock::system())
+        .and_then(|today| today.checked_add_days(Days::new(days)))
+        .with_context(|| format!("Expiry out of range: {value}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::SystemTime;
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).expect("example value must parse")
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("example value must be valid")
+    }
+
+    #[test]
+    fn test_ignored() {
+        let mut list = IgnoreList::default();
+        let a = url("https://example.com/2023/a.json");
+        let b = url("https://example.com/2023/b.json");
+
+        assert!(!list.add(IgnoreEntry {
+            url: a.clone(),
+            reason: "invalid signature".to_string(),
+            expires: None,
+        }));
+        assert!(!list.add(IgnoreEntry {
+            url: b.clone(),
+            reason: "invalid signature".to_string(),
+            expires: Some(date(2024, 1, 31)),
+        }));
+
+        let today = date(2024, 1, 31);
+        assert!(list.ignored(&a, today).is_some());
+        assert!(list.ignored(&b, today).is_some());
+        assert!(list.ignored(&b, date(2024, 2, 1)).is_none());
+        assert!(list
+            .ignored(&url("https://example.com/2023/c.json"), today)
+            .is_none());
+
+        // replace the entry
+        assert!(list.add(IgnoreEntry {
+            url: b.clone(),
+            reason: "broken forever".to_string(),
+            expires: None,
+        }));
+        assert_eq!(list.entries.len(), 2);
+        assert!(list.ignored(&b, date(2024, 2, 1)).is_some());
+
+        assert!(list.remove(&a).is_some());
+        assert!(list.remove(&a).is_none());
+        assert!(list.ignored(&a, today).is_none());
+    }
+
+    #[test]
+    fn test_parse_expiry() {
+        assert_eq!(
+            parse_expiry("2025-12-31").expect("must parse"),
+            date(2025, 12, 31)
+        );
+        assert_eq!(
+            parse_expiry("90d").expect("must parse"),
+            today(&Clock::system()).expect("must be valid") + Days::new(90)
+        );
+        assert!(parse_expiry("someday").is_err());
+    }
+
+    #[test]
+    fn test_today() {
+        // 2023-11-14T22:13:20Z
+        let clock = Clock::manual(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+        assert_eq!(today(&clock), Some(date(2023, 11, 14)));
+
+        clock.advance(Duration::from_secs(2 * 60 * 60));
+        assert_eq!(today(&clock), Some(date(2023, 11, 15)));
+    }
+}
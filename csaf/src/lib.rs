@@ -53,10 +53,17 @@
 //! }
 //! ```
 
+pub mod consistency;
+pub mod diff;
 pub mod discover;
+pub mod ignore;
 pub mod metadata;
+pub mod migrate;
 pub mod model;
+pub mod query;
+pub mod queue;
 pub mod report;
+pub mod retention;
 pub mod retrieve;
 pub mod rolie;
 pub mod source;
@@ -64,8 +71,23 @@ pub mod validation;
 pub mod visitors;
 pub mod walker;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+#[cfg(feature = "capi")]
+pub mod capi;
+
 #[cfg(feature = "csaf")]
 pub mod verification;
 
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+
+#[cfg(feature = "simulation")]
+pub mod simulation;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+
 /// re-export common
 pub use walker_common as common;
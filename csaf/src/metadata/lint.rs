@@ -0,0 +1,335 @@
+//! Checking provider metadata for common mistakes
+
+use crate::model::metadata::ProviderMetadata;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+use time::OffsetDateTime;
+use url::Url;
+use walker_common::fetcher::Fetcher;
+
+/// The level of a lint finding
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// Something which breaks consumers, or violates the specification
+    Error,
+    /// Something which likely is a mistake
+    Warning,
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Error => f.write_str("error"),
+            Self::Warning => f.write_str("warning"),
+        }
+    }
+}
+
+/// A finding of the lint, with a suggestion how to fix it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lint {
+    pub level: Level,
+    pub message: String,
+    pub suggestion: String,
+}
+
+impl Lint {
+    fn error(message: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        Self {
+            level: Level::Error,
+            message: message.into(),
+            suggestion: suggestion.into(),
+        }
+    }
+
+    fn warning(message: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        Self {
+            level: Level::Warning,
+            message: message.into(),
+            suggestion: suggestion.into(),
+        }
+    }
+}
+
+/// Check the content of provider metadata, without accessing any of the referenced resources.
+///
+/// A `last_updated` timestamp older than `max_age` is reported as stale.
+pub fn lint(metadata: &ProviderMetadata, now: OffsetDateTime, max_age: Duration) -> Vec<Lint> {
+    let mut result = vec![];
+
+    if metadata.metadata_version != "2.0" {
+        result.push(Lint::warning(
+            format!(
+                "Unsupported metadata version: {}",
+                metadata.metadata_version
+            ),
+            r#"Set "metadata_version" to "2.0""#,
+        ));
+    }
+
+    // insecure URLs
+
+    for (name, url) in urls(metadata) {
+        if url.scheme() != "https" {
+            result.push(Lint::error(
+                format!("{name} doesn't use HTTPS: {url}"),
+                "Serve all provider resources using HTTPS, and reference them using https:// URLs",
+            ));
+        }
+    }
+
+    // keys
+
+    if metadata.public_openpgp_keys.is_empty() {
+        result.push(Lint::error(
+            "No public OpenPGP keys",
+            r#"Add the keys signing the documents to "public_openpgp_keys", so that consumers can verify the signatures"#,
+        ));
+    }
+
+    for key in &metadata.public_openpgp_keys {
+        if key.fingerprint.is_none() {
+            result.push(Lint::warning(
+                format!("Public key without fingerprint: {}", key.url),
+                r#"Add the "fingerprint" of the key, so that consumers can check they received the right key"#,
+            ));
+        }
+    }
+
+    // last updated
+
+    let last_updated = metadata.last_updated.timestamp();
+    let now = now.unix_timestamp();
+    if last_updated > now {
+        result.push(Lint::warning(
+            format!("Last update is in the future: {}", metadata.last_updated),
+            r#"Set "last_updated" to the time the metadata was actually changed"#,
+        ));
+    } else if (now - last_updated) as u64 > max_age.as_secs() {
+        result.push(Lint::warning(
+            format!(
+                "Last update is older than {}: {}",
+                humantime::Duration::from(max_age),
+                metadata.last_updated
+            ),
+            r#"Update "last_updated" whenever the metadata changes, and check the metadata is still maintained"#,
+        ));
+    }
+
+    // distributions
+
+    if metadata.distributions.is_empty() {
+        result.push(Lint::error(
+            "No distributions",
+            r#"Add a directory or ROLIE based distribution to "distributions""#,
+        ));
+    }
+
+    let mut directories = BTreeSet::new();
+    let mut feeds = BTreeSet::new();
+
+    for (n, distribution) in metadata.distributions.iter().enumerate() {
+        let n = n + 1;
+        let rolie_feeds = distribution
+            .rolie
+            .as_ref()
+            .map(|rolie| rolie.feeds.as_slice())
+            .unwrap_or_default();
+
+        if distribution.directory_url.is_none() && rolie_feeds.is_empty() {
+            result.push(Lint::error(
+                format!("Distribution #{n} has neither a directory URL nor ROLIE feeds"),
+                r#"Add a "directory_url" or ROLIE "feeds" to the distribution, or remove it"#,
+            ));
+        }
+
+        if let Some(url) = &distribution.directory_url {
+            if !directories.insert(normalize(url)) {
+                result.push(Lint::warning(
+                    format!("Duplicate directory distribution: {url}"),
+                    "Remove the duplicate distribution, consumers would process its documents twice",
+                ));
+            }
+        }
+
+        for feed in rolie_feeds {
+            if !feeds.insert(normalize(&feed.url)) {
+                result.push(Lint::warning(
+                    format!("Duplicate ROLIE feed: {}", feed.url),
+                    "Remove the duplicate feed, consumers would process its documents twice",
+                ));
+            }
+        }
+    }
+
+    result
+}
+
+/// Check that the resources referenced by the provider metadata can be retrieved.
+pub async fn lint_reachability(fetcher: &Fetcher, metadata: &ProviderMetadata) -> Vec<Lint> {
+    let mut result = vec![];
+
+    for distribution in &metadata.distributions {
+        for feed in distribution.rolie.iter().flat_map(|rolie| &rolie.feeds) {
+            check_reachable(fetcher, "ROLIE feed", feed.url.clone(), &mut result).await;
+        }
+
+        if let Some(url) = &distribution.directory_url {
+            match normalize(url).join("index.txt") {
+                Ok(index) => check_reachable(fetcher, "Directory index", index, &mut result).await,
+                Err(err) => result.push(Lint::error(
+                    format!("Invalid directory URL: {url}: {err}"),
+                    r#"Fix the "directory_url" of the distribution"#,
+                )),
+            }
+        }
+    }
+
+    for key in &metadata.public_openpgp_keys {
+        check_reachable(fetcher, "Public key", key.url.clone(), &mut result).await;
+    }
+
+    result
+}
+
+async fn check_reachable(fetcher: &Fetcher, name: &str, url: Url, result: &mut Vec<Lint>) {
+    log::info!("Checking {name}: {url}");
+
+    match fetcher.fetch::<Option<String>>(url.clone()).await {
+        Ok(Some(_)) => {}
+        Ok(None) => result.push(Lint::error(
+            format!("{name} not found: {url}"),
+            "Publish the resource, or remove the reference to it",
+        )),
+        Err(err) => result.push(Lint::error(
+            format!("{name} unreachable: {url}: {err}"),
+            "Check the URL, and that the server is reachable from the public internet",
+        )),
+    }
+}
+
+/// All URLs referenced by the metadata, with a name describing them
+fn urls(metadata: &ProviderMetadata) -> Vec<(&'static str, &Url)> {
+    let mut result = vec![("Canonical URL", &metadata.canonical_url)];
+
+    for distribution in &metadata.distributions {
+        if let Some(url) = &distribution.directory_url {
+            result.push(("Directory URL", url));
+        }
+        if let Some(rolie) = &distribution.rolie {
+            result.extend(rolie.feeds.iter().map(|feed| ("ROLIE feed", &feed.url)));
+            result.extend(rolie.categories.iter().map(|url| ("ROLIE category", url)));
+            result.extend(rolie.services.iter().map(|url| ("ROLIE service", url)));
+        }
+    }
+
+    result.extend(
+        metadata
+            .public_openpgp_keys
+            .iter()
+            .map(|key| ("Public key", &key.url)),
+    );
+
+    result
+}
+
+/// Normalize a directory URL to end with a slash, so that equivalent URLs are considered equal
+fn normalize(url: &Url) -> Url {
+    let mut url = url.clone();
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+    url
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn metadata(value: serde_json::Value) -> ProviderMetadata {
+        serde_json::from_value(value).expect("example value must parse")
+    }
+
+    fn example() -> serde_json::Value {
+        json!({
+            "canonical_url": "https://example.com/.well-known/csaf/provider-metadata.json",
+            "distributions": [
+                {"directory_url": "https://example.com/.well-known/csaf/white/"}
+            ],
+            "last_updated": "2024-01-01T00:00:00Z",
+            "metadata_version": "2.0",
+            "public_openpgp_keys": [
+                {
+                    "fingerprint": "0123456789ABCDEF0123456789ABCDEF01234567",
+                    "url": "https://example.com/.well-known/csaf/openpgp/key.asc"
+                }
+            ],
+            "publisher": {
+                "category": "vendor",
+                "contact_details": "security@example.com",
+                "name": "Example",
+                "namespace": "https://example.com"
+            },
+            "role": "csaf_trusted_provider"
+        })
+    }
+
+    /// 2024-01-02T00:00:00Z
+    fn now() -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp(1_704_153_600).expect("must be valid")
+    }
+
+    const MAX_AGE: Duration = Duration::from_secs(90 * 24 * 60 * 60);
+
+    #[test]
+    fn test_clean() {
+        assert_eq!(lint(&metadata(example()), now(), MAX_AGE), vec![]);
+    }
+
+    #[test]
+    fn test_mistakes() {
+        let mut value = example();
+        value["canonical_url"] = json!("http://example.com/provider-metadata.json");
+        value["public_openpgp_keys"] = json!([]);
+        value["distributions"] = json!([
+            {"directory_url": "https://example.com/.well-known/csaf/white"},
+            {"directory_url": "https://example.com/.well-known/csaf/white/"},
+        ]);
+
+        let lints = lint(&metadata(value), now(), MAX_AGE);
+        let messages = lints
+            .iter()
+            .map(|lint| (lint.level, lint.message.as_str()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            messages,
+            vec![
+                (
+                    Level::Error,
+                    "Canonical URL doesn't use HTTPS: http://example.com/provider-metadata.json"
+                ),
+                (Level::Error, "No public OpenPGP keys"),
+                (
+                    Level::Warning,
+                    "Duplicate directory distribution: https://example.com/.well-known/csaf/white/"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stale() {
+        let lints = lint(
+            &metadata(example()),
+            now() + time::Duration::days(365),
+            MAX_AGE,
+        );
+
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].level, Level::Warning);
+        assert!(lints[0].message.starts_with("Last update is older than"));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::model::metadata::ProviderMetadata;
+pub mod lint;
+pub mod trace;
+
+use crate::model::{aggregator::AggregatorMetadata, metadata::ProviderMetadata};
 use async_trait::async_trait;
 use hickory_resolver::{
     error::ResolveErrorKind, name_server::TokioConnectionProvider, AsyncResolver,
@@ -48,6 +51,14 @@ impl MetadataSource for String {
     }
 }
 
+/// Load the metadata of an aggregator, from the URL of its `aggregator.json`.
+pub async fn load_aggregator(fetcher: &Fetcher, url: Url) -> Result<AggregatorMetadata, Error> {
+    Ok(fetcher
+        .fetch::<Json<AggregatorMetadata>>(url)
+        .await?
+        .into_inner())
+}
+
 /// A metadata source implementing the CSAF metadata discovery process.
 #[derive(Clone)]
 pub struct MetadataRetriever {
@@ -88,6 +99,24 @@ impl MetadataRetriever {
         Ok(url)
     }
 
+    /// The URL of the well-known approach
+    pub fn well_known_url(&self) -> String {
+        format!(
+            "https://{}/.well-known/csaf/provider-metadata.json",
+            self.base_url,
+        )
+    }
+
+    /// The URL of a security.txt file, using the provided path
+    pub fn security_txt_url(&self, path: &str) -> String {
+        format!("https://{}/{path}", self.base_url)
+    }
+
+    /// The host name of the DNS approach
+    pub fn dns_host(&self) -> String {
+        format!("csaf.data.security.{}", self.base_url)
+    }
+
     /// Treat the source as a URL and try to retrieve it
     ///
     /// If the source is not a URL, we consider it "not found".
@@ -115,10 +144,7 @@ impl MetadataRetriever {
         &self,
         fetcher: &Fetcher,
     ) -> Result<Option<ProviderMetadata>, Error> {
-        let url = format!(
-            "https://{}/.well-known/csaf/provider-metadata.json",
-            self.base_url,
-        );
+        let url = self.well_known_url();
 
         log::debug!("Trying to retrieve by well-known approach: {url}");
 
@@ -134,7 +160,7 @@ impl MetadataRetriever {
     /// we do a DNS pre-flight check. If the hostname resolves into an IP address, we assume the
     /// following HTTP request should not fail due to a "host not found" error.
     pub async fn approach_dns(&self, fetcher: &Fetcher) -> Result<Option<ProviderMetadata>, Error> {
-        let host = format!("csaf.data.security.{}", self.base_url);
+        let host = self.dns_host();
 
         log::debug!("Trying to retrieve by DNS approach: {host}");
 
@@ -182,7 +208,7 @@ impl MetadataRetriever {
         fetcher: &Fetcher,
         path: &str,
     ) -> Result<Option<ProviderMetadata>, Error> {
-        let url = format!("https://{}/{path}", self.base_url);
+        let url = self.security_txt_url(path);
 
         log::debug!("Trying to retrieve by security.txt approach: {url}");
 
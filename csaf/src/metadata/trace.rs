@@ -0,0 +1,175 @@
+//! Tracing the metadata discovery process
+
+use super::{Error, MetadataRetriever};
+use crate::model::metadata::ProviderMetadata;
+use futures::future::{FutureExt, LocalBoxFuture};
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::time::Instant;
+use walker_common::fetcher::Fetcher;
+
+/// An approach of discovering the provider metadata
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Approach {
+    /// The source is the full URL of the provider metadata
+    FullUrl,
+    /// The well-known location of the provider metadata
+    WellKnown,
+    /// The CSAF entry of the `/.well-known/security.txt` file
+    SecurityTxt,
+    /// The CSAF entry of the legacy `/security.txt` file
+    LegacySecurityTxt,
+    /// The `csaf.data.security` subdomain
+    Dns,
+}
+
+impl Display for Approach {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FullUrl => f.write_str("Direct URL"),
+            Self::WellKnown => f.write_str("Well-known"),
+            Self::SecurityTxt => f.write_str("/.well-known/security.txt"),
+            Self::LegacySecurityTxt => f.write_str("/security.txt"),
+            Self::Dns => f.write_str("DNS"),
+        }
+    }
+}
+
+/// The outcome of an approach
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Outcome {
+    /// The provider metadata was found
+    Found,
+    /// The approach did not apply, or didn't find anything
+    NotFound,
+    /// The approach failed
+    Failed,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Found => f.write_str("found"),
+            Self::NotFound => f.write_str("not found"),
+            Self::Failed => f.write_str("failed"),
+        }
+    }
+}
+
+/// A single candidate tried during the discovery
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceStep {
+    pub approach: Approach,
+    /// The candidate URL (or host name, for the DNS approach)
+    pub candidate: String,
+    pub outcome: Outcome,
+    /// The HTTP status code, if the request failed with an error response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// The error, if the approach failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Time spent on the approach, in milliseconds
+    pub duration: u64,
+}
+
+/// The trace of a discovery, following the same steps as [`MetadataRetriever::load_metadata`].
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveryTrace {
+    pub steps: Vec<TraceStep>,
+    /// The resolved metadata, if any approach succeeded
+    pub metadata: Option<ProviderMetadata>,
+}
+
+impl MetadataRetriever {
+    /// Run the discovery process, recording each candidate which was tried.
+    ///
+    /// Just like the discovery process, this stops at the first approach which either found the
+    /// metadata or failed.
+    pub async fn trace(&self, fetcher: &Fetcher) -> DiscoveryTrace {
+        let mut trace = DiscoveryTrace {
+            steps: vec![],
+            metadata: None,
+        };
+
+        let approaches: [(Approach, String, LocalBoxFuture<_>); 5] = [
+            (
+                Approach::FullUrl,
+                self.base_url.clone(),
+                self.approach_full_url(fetcher).boxed_local(),
+            ),
+            (
+                Approach::WellKnown,
+                self.well_known_url(),
+                self.approach_well_known(fetcher).boxed_local(),
+            ),
+            (
+                Approach::SecurityTxt,
+                self.security_txt_url(".well-known/security.txt"),
+                self.approach_security_txt(fetcher, ".well-known/security.txt")
+                    .boxed_local(),
+            ),
+            (
+                Approach::LegacySecurityTxt,
+                self.security_txt_url("security.txt"),
+                self.approach_security_txt(fetcher, "security.txt")
+                    .boxed_local(),
+            ),
+            (
+                Approach::Dns,
+                self.dns_host(),
+                self.approach_dns(fetcher).boxed_local(),
+            ),
+        ];
+
+        for (approach, candidate, f) in approaches {
+            if !trace.step(approach, candidate, f).await {
+                break;
+            }
+        }
+
+        trace
+    }
+}
+
+impl DiscoveryTrace {
+    /// Record a step, returning `true` if the discovery should continue.
+    async fn step<F>(&mut self, approach: Approach, candidate: String, f: F) -> bool
+    where
+        F: Future<Output = Result<Option<ProviderMetadata>, Error>>,
+    {
+        let start = Instant::now();
+        let result = f.await;
+        let duration = start.elapsed().as_millis() as u64;
+
+        let (outcome, status, error) = match result {
+            Ok(Some(metadata)) => {
+                self.metadata = Some(metadata);
+                (Outcome::Found, None, None)
+            }
+            Ok(None) => (Outcome::NotFound, None, None),
+            Err(err) => {
+                let status = match &err {
+                    Error::Fetch(err) => err.status().map(|status| status.as_u16()),
+                    _ => None,
+                };
+                (Outcome::Failed, status, Some(err.to_string()))
+            }
+        };
+
+        self.steps.push(TraceStep {
+            approach,
+            candidate,
+            outcome,
+            status,
+            error,
+            duration,
+        });
+
+        outcome == Outcome::NotFound
+    }
+}
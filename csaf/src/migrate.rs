@@ -0,0 +1,369 @@
+//! Converting a store to a different layout
+//!
+//! Works on a store, as written by [`crate::visitors::store::StoreVisitor`], including its TLP
+//! partitions. The documents are moved, not copied, so that even large stores can be converted
+//! without downloading them again.
+
+use crate::{
+    model::{
+        metadata::ProviderMetadata,
+        store::{
+            distribution_urls, link_object, object_path, LayoutFile, StoreLayout, DIR_OBJECTS,
+            FILE_LAYOUT,
+        },
+    },
+    visitors::store::{changes_name, DIR_METADATA},
+};
+use anyhow::{bail, Context};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use walker_common::{changes::ChangesFile, utils::hex::Hex};
+
+/// The number of mismatches to report in detail
+const MAX_REPORTED: usize = 10;
+
+/// The outcome of a migration
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Migration {
+    /// Distribution directories which were moved
+    pub moved: usize,
+    /// Documents linked into the objects directory
+    pub objects: usize,
+    /// Documents which were verified after the migration
+    pub verified: usize,
+}
+
+/// The roots of a store: the store itself, and its TLP partitions.
+///
+/// Only directories containing provider metadata are considered.
+pub fn store_roots(base: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut result = vec![];
+
+    if has_metadata(base) {
+        result.push(base.to_path_buf());
+    }
+
+    for entry in std::fs::read_dir(base)
+        .with_context(|| format!("Failed to read directory: {}", base.display()))?
+    {
+        let path = entry?.path();
+        if path.is_dir() && has_metadata(&path) {
+            result.push(path);
+        }
+    }
+
+    Ok(result)
+}
+
+fn has_metadata(base: &Path) -> bool {
+    base.join(DIR_METADATA)
+        .join("provider-metadata.json")
+        .exists()
+}
+
+/// Convert an existing store, including its TLP partitions, to a different layout.
+///
+/// Entries of the `changes.csv` files are renamed accordingly. When `verify` is enabled, the
+/// digest of each document is compared to the one before the migration.
+pub fn migrate(base: &Path, to: StoreLayout, verify: bool) -> anyhow::Result<Migration> {
+    let roots = store_roots(base)?;
+    if roots.is_empty() {
+        bail!("Not a store, missing provider metadata: {}", base.display());
+    }
+
+    let mut result = Migration::default();
+    for root in roots {
+        let migration = migrate_root(&root, to, verify)?;
+        result.moved += migration.moved;
+        result.objects += migration.objects;
+        result.verified += migration.verified;
+    }
+
+    Ok(result)
+}
+
+/// Convert a single store root, without any partitions.
+fn migrate_root(base: &Path, to: StoreLayout, verify: bool) -> anyhow::Result<Migration> {
+    let Some(from) = StoreLayout::detect(base)? else {
+        bail!("Not a store, missing provider metadata: {}", base.display());
+    };
+
+    let mut result = Migration::default();
+
+    if from == to {
+        log::info!("Store already uses layout {to}: {}", base.display());
+        return Ok(result);
+    }
+
+    log::info!("Migrating store from {from} to {to}: {}", base.display());
+
+    let path = base.join(DIR_METADATA).join("provider-metadata.json");
+    let metadata: ProviderMetadata = serde_json::from_slice(
+        &std::fs::read(&path).with_context(|| format!("Failed to read: {}", path.display()))?,
+    )
+    .with_context(|| format!("Failed to parse: {}", path.display()))?;
+    let urls = distribution_urls(&metadata);
+
+    let before = match verify {
+        true => Some(digests(base, from, &urls)?),
+        false => None,
+    };
+
+    let mut changes = match base.join("changes.csv").exists() {
+        true => Some(ChangesFile::load(base)?),
+        false => None,
+    };
+
+    for url in &urls {
+        let source = from.distribution_base(base, url);
+        let target = to.distribution_base(base, url);
+
+        if source == target {
+            continue;
+        }
+
+        if let (Some(changes), Some(source), Some(target)) = (
+            &mut changes,
+            changes_name(base, &source),
+            changes_name(base, &target),
+        ) {
+            changes.rename_prefix(&format!("{source}/"), &format!("{target}/"));
+        }
+
+        if !source.exists() {
+            continue;
+        }
+        if target.exists() {
+            bail!(
+                "Target directory of {url} already exists: {}",
+                target.display()
+            );
+        }
+
+        log::debug!("Moving {} -> {}", source.display(), target.display());
+        std::fs::rename(&source, &target)
+            .with_context(|| format!("Failed to move: {}", source.display()))?;
+        result.moved += 1;
+    }
+
+    if let Some(mut changes) = changes {
+        changes.write(base)?;
+    }
+
+    match to {
+        StoreLayout::Content => {
+            for url in &urls {
+                for file in documents(&to.distribution_base(base, url)) {
+                    link_object(base, &file?)?;
+                    result.objects += 1;
+                }
+            }
+        }
+        _ => {
+            // the documents themselves are kept, the objects are only additional links
+            let objects = base.join(DIR_OBJECTS);
+            if objects.exists() {
+                std::fs::remove_dir_all(&objects)
+                    .with_context(|| format!("Failed to remove: {}", objects.display()))?;
+            }
+        }
+    }
+
+    // write the layout file last, so that an interrupted migration can be detected and repeated
+    match to {
+        StoreLayout::V1 => {
+            let path = base.join(DIR_METADATA).join(FILE_LAYOUT);
+            match std::fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == ErrorKind::NotFound => {}
+                Err(err) => {
+                    return Err(err)
+                        .with_context(|| format!("Failed to remove: {}", path.display()))
+                }
+            }
+        }
+        _ => {
+            let mut file = LayoutFile::new(to);
+            file.extend(urls.iter().cloned());
+            file.store(base)?;
+        }
+    }
+
+    if let Some(before) = before {
+        result.verified = verify_digests(base, to, &urls, &before)?;
+    }
+
+    Ok(result)
+}
+
+/// All documents of a distribution directory.
+fn documents(base: &Path) -> impl Iterator<Item = anyhow::Result<PathBuf>> {
+    WalkDir::new(base)
+        .into_iter()
+        .filter_map(|entry| match entry {
+            Ok(entry)
+                if entry.file_type().is_file()
+                    && entry.file_name().to_string_lossy().ends_with(".json") =>
+            {
+                Some(Ok(entry.into_path()))
+            }
+            Ok(_) => None,
+            // a distribution without any stored documents
+            Err(err) if err.io_error().map(|err| err.kind()) == Some(ErrorKind::NotFound) => None,
+            Err(err) => Some(Err(err.into())),
+        })
+}
+
+fn digest(file: &Path) -> anyhow::Result<String> {
+    let data =
+        std::fs::read(file).with_context(|| format!("Failed to read: {}", file.display()))?;
+    Ok(Hex(&Sha256::digest(data)).to_lower())
+}
+
+/// The digests of all documents, by distribution URL and relative path.
+fn digests(
+    base: &Path,
+    layout: StoreLayout,
+    urls: &[String],
+) -> anyhow::Result<BTreeMap<(String, PathBuf), String>> {
+    let mut result = BTreeMap::new();
+
+    for url in urls {
+        let distribution = layout.distribution_base(base, url);
+        for file in documents(&distribution) {
+            let file = file?;
+            let name = file.strip_prefix(&distribution)?.to_path_buf();
+            result.insert((url.clone(), name), digest(&file)?);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Verify the documents after a migration, returning the number of verified documents.
+fn verify_digests(
+    base: &Path,
+    layout: StoreLayout,
+    urls: &[String],
+    before: &BTreeMap<(String, PathBuf), String>,
+) -> anyhow::Result<usize> {
+    let after = digests(base, layout, urls)?;
+
+    let mut mismatches = vec![];
+    for ((url, name), expected) in before {
+        match after.get(&(url.clone(), name.clone())) {
+            Some(actual) if actual == expected => {}
+            Some(_) => mismatches.push(format!("{url}{}: digest mismatch", name.display())),
+            None => mismatches.push(format!("{url}{}: missing", name.display())),
+        }
+    }
+
+    if layout == StoreLayout::Content {
+        for ((url, name), expected) in &after {
+            let object = object_path(base, expected);
+            if digest(&object).ok().as_deref() != Some(expected.as_str()) {
+                mismatches.push(format!("{url}{}: invalid object", name.display()));
+            }
+        }
+    }
+
+    if !mismatches.is_empty() {
+        let count = mismatches.len();
+        mismatches.truncate(MAX_REPORTED);
+        bail!(
+            "Verification of {} failed for {count} documents: {}",
+            base.display(),
+            mismatches.join(", ")
+        );
+    }
+
+    Ok(before.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn store(base: &Path, url: &str) {
+        std::fs::create_dir_all(base.join(DIR_METADATA)).expect("must create metadata dir");
+        std::fs::write(
+            base.join(DIR_METADATA).join("provider-metadata.json"),
+            serde_json::to_vec(&serde_json::json!({
+                "canonical_url": "https://example.com/.well-known/csaf/provider-metadata.json",
+                "distributions": [{ "directory_url": url }],
+                "last_updated": "2024-01-01T00:00:00Z",
+                "list_on_CSAF_aggregators": false,
+                "metadata_version": "2.0",
+                "mirror_on_CSAF_aggregators": false,
+                "public_openpgp_keys": [],
+                "publisher": {
+                    "category": "vendor",
+                    "contact_details": "csaf@example.com",
+                    "name": "Example",
+                    "namespace": "https://example.com",
+                },
+                "role": "csaf_provider",
+            }))
+            .expect("must serialize metadata"),
+        )
+        .expect("must write metadata");
+
+        let v1 = StoreLayout::V1.distribution_base(base, url).join("2024");
+        std::fs::create_dir_all(&v1).expect("must create distribution dir");
+        std::fs::write(v1.join("a.json"), b"{}").expect("must write document");
+    }
+
+    #[test]
+    fn test_migrate() {
+        let dir = tempfile::tempdir().expect("must create temporary directory");
+        let base = dir.path().to_path_buf();
+        let url = "https://example.com/.well-known/csaf/white/";
+        store(&base, url);
+
+        let migration = migrate(&base, StoreLayout::V2, true).expect("must migrate");
+        assert_eq!(migration.moved, 1);
+        assert_eq!(migration.verified, 1);
+        assert_eq!(
+            StoreLayout::detect(&base).expect("must detect"),
+            Some(StoreLayout::V2)
+        );
+        assert!(StoreLayout::V2
+            .distribution_base(&base, url)
+            .join("2024/a.json")
+            .exists());
+
+        // nothing left to do
+        assert_eq!(
+            migrate(&base, StoreLayout::V2, true).expect("must migrate"),
+            Migration::default()
+        );
+
+        let migration = migrate(&base, StoreLayout::Content, true).expect("must migrate");
+        assert_eq!(migration.moved, 0);
+        assert_eq!(migration.objects, 1);
+        assert_eq!(migration.verified, 1);
+        assert!(base.join(DIR_OBJECTS).exists());
+
+        let migration = migrate(&base, StoreLayout::V1, true).expect("must migrate");
+        assert_eq!(migration.moved, 1);
+        assert_eq!(
+            StoreLayout::detect(&base).expect("must detect"),
+            Some(StoreLayout::V1)
+        );
+        assert!(!base.join(DIR_OBJECTS).exists());
+        assert!(StoreLayout::V1
+            .distribution_base(&base, url)
+            .join("2024/a.json")
+            .exists());
+    }
+
+    #[test]
+    fn test_not_a_store() {
+        let dir = tempfile::tempdir().expect("must create temporary directory");
+
+        assert!(migrate(dir.path(), StoreLayout::V2, true).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+use crate::model::metadata::{ProviderMetadata, Publisher, Role};
+use chrono::{DateTime, Utc};
+use url::Url;
+
+/// The metadata of a CSAF aggregator or lister (`aggregator.json`)
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct AggregatorMetadata {
+    pub aggregator: Aggregator,
+
+    pub aggregator_version: String,
+
+    pub canonical_url: Url,
+
+    #[serde(default)]
+    pub csaf_providers: Vec<AggregatorEntry>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub csaf_publishers: Vec<AggregatorEntry>,
+
+    pub last_updated: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct Aggregator {
+    pub category: AggregatorCategory,
+    pub contact_details: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuing_authority: Option<String>,
+    pub name: String,
+    pub namespace: String,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AggregatorCategory {
+    Aggregator,
+    Lister,
+}
+
+/// A provider or publisher, listed by an aggregator
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct AggregatorEntry {
+    pub metadata: ListedMetadata,
+    /// The locations of the mirrors of the provider, operated by the aggregator
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mirrors: Vec<Url>,
+}
+
+/// An excerpt of the provider metadata, listed by an aggregator
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct ListedMetadata {
+    pub last_updated: DateTime<Utc>,
+    pub publisher: Publisher,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<Role>,
+    /// The URL of the provider metadata
+    pub url: Url,
+}
+
+impl AggregatorMetadata {
+    /// All listed providers and publishers
+    pub fn entries(&self) -> impl Iterator<Item = &AggregatorEntry> {
+        self.csaf_providers.iter().chain(&self.csaf_publishers)
+    }
+
+    /// Find the entry of a provider, either by its own or one of its mirror locations.
+    ///
+    /// Returns the entry, and the mirror in case the provider metadata is one of the mirrors.
+    pub fn find(&self, metadata: &ProviderMetadata) -> Option<(&AggregatorEntry, Option<&Url>)> {
+        let url = &metadata.canonical_url;
+
+        self.entries().find_map(|entry| {
+            if &entry.metadata.url == url {
+                return Some((entry, None));
+            }
+            entry
+                .mirrors
+                .iter()
+                .find(|mirror| is_location_of(mirror, url))
+                .map(|mirror| (entry, Some(mirror)))
+        })
+    }
+}
+
+/// Check if a mirror URL refers to the provider metadata, either by its full URL or by the
+/// directory containing it.
+fn is_location_of(mirror: &Url, metadata: &Url) -> bool {
+    if mirror == metadata {
+        return true;
+    }
+
+    let mut directory = mirror.clone();
+    if !directory.path().ends_with('/') {
+        directory.set_path(&format!("{}/", directory.path()));
+    }
+    directory.join("provider-metadata.json").ok().as_ref() == Some(metadata)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let aggregator: AggregatorMetadata = serde_json::from_value(serde_json::json!({
+            "aggregator": {
+                "category": "aggregator",
+                "contact_details": "csaf@aggregator.example.com",
+                "name": "Example Aggregator",
+                "namespace": "https://aggregator.example.com",
+            },
+            "aggregator_version": "2.0",
+            "canonical_url": "https://aggregator.example.com/.well-known/csaf-aggregator/aggregator.json",
+            "csaf_providers": [{
+                "metadata": {
+                    "last_updated": "2024-01-01T00:00:00Z",
+                    "publisher": {
+                        "category": "vendor",
+                        "contact_details": "csaf@example.com",
+                        "name": "Example",
+                        "namespace": "https://example.com",
+                    },
+                    "role": "csaf_trusted_provider",
+                    "url": "https://example.com/.well-known/csaf/provider-metadata.json",
+                },
+                "mirrors": ["https://aggregator.example.com/.well-known/csaf-aggregator/example"],
+            }],
+            "last_updated": "2024-01-01T00:00:00Z",
+        }))
+        .expect("example value must parse");
+
+        let metadata = |url: &str| -> ProviderMetadata {
+            serde_json::from_value(serde_json::json!({
+                "canonical_url": url,
+                "last_updated": "2024-01-01T00:00:00Z",
+                "metadata_version": "2.0",
+                "publisher": {
+                    "category": "vendor",
+                    "contact_details": "csaf@example.com",
+                    "name": "Example",
+                    "namespace": "https://example.com",
+                },
+            }))
+            .expect("example value must parse")
+        };
+
+        let (_, mirror) = aggregator
+            .find(&metadata(
+                "https://example.com/.well-known/csaf/provider-metadata.json",
+            ))
+            .expect("must find provider");
+        assert_eq!(mirror, None);
+
+        let (_, mirror) = aggregator
+            .find(&metadata(
+                "https://aggregator.example.com/.well-known/csaf-aggregator/example/provider-metadata.json",
+            ))
+            .expect("must find mirror");
+        assert!(mirror.is_some());
+
+        assert!(aggregator
+            .find(&metadata(
+                "https://other.example.com/.well-known/csaf/provider-metadata.json"
+            ))
+            .is_none());
+    }
+}
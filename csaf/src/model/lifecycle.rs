@@ -0,0 +1,300 @@
+//! The lifecycle of an advisory
+//!
+//! Publishers don't delete advisories, but withdraw them (document category `csaf_withdrawn`),
+//! or supersede them by other advisories (document category `csaf_superseded`, referencing the
+//! superseding documents with an external reference, summarized as "Superseding document").
+
+use std::collections::{BTreeMap, BTreeSet};
+use url::Url;
+
+/// The category of withdrawn documents
+pub const CATEGORY_WITHDRAWN: &str = "csaf_withdrawn";
+
+/// The category of superseded documents
+pub const CATEGORY_SUPERSEDED: &str = "csaf_superseded";
+
+/// The status of a document (`/document/tracking/status`)
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackingStatus {
+    Draft,
+    Interim,
+    Final,
+}
+
+impl TrackingStatus {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "draft" => Some(Self::Draft),
+            "interim" => Some(Self::Interim),
+            "final" => Some(Self::Final),
+            _ => None,
+        }
+    }
+}
+
+/// The lifecycle information of a document
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Lifecycle {
+    /// The tracking ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The tracking status
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<TrackingStatus>,
+    /// The version of the document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Whether the document was withdrawn
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub withdrawn: bool,
+    /// Whether the document was superseded
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub superseded: bool,
+    /// The documents superseding this one, if known
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub superseded_by: Vec<Url>,
+}
+
+#[derive(serde::Deserialize)]
+struct Excerpt {
+    document: DocumentExcerpt,
+}
+
+#[derive(serde::Deserialize)]
+struct DocumentExcerpt {
+    #[serde(default)]
+    category: String,
+    #[serde(default)]
+    references: Vec<ReferenceExcerpt>,
+    #[serde(default)]
+    tracking: Option<TrackingExcerpt>,
+}
+
+#[derive(serde::Deserialize)]
+struct ReferenceExcerpt {
+    #[serde(default)]
+    category: Option<String>,
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    url: String,
+}
+
+#[derive(serde::Deserialize)]
+struct TrackingExcerpt {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    version: Option<String>,
+}
+
+impl ReferenceExcerpt {
+    /// Check if the reference points to a superseding document.
+    fn superseding(&self) -> Option<Url> {
+        // references are external, unless stated otherwise
+        let external = self.category.as_deref().map_or(true, |c| c == "external");
+        let superseding = self
+            .summary
+            .trim()
+            .to_lowercase()
+            .starts_with("superseding");
+
+        (external && superseding)
+            .then(|| Url::parse(&self.url).ok())
+            .flatten()
+    }
+}
+
+impl Lifecycle {
+    /// Extract the lifecycle information from the content of a document.
+    ///
+    /// Content which can't be parsed results in the default (a current document, without any
+    /// tracking information).
+    pub fn parse(data: &[u8]) -> Self {
+        let Ok(Excerpt { document }) = serde_json::from_slice::<Excerpt>(data) else {
+            return Self::default();
+        };
+
+        let superseded_by = document
+            .references
+            .iter()
+            .filter_map(ReferenceExcerpt::superseding)
+            .collect::<Vec<_>>();
+        let (id, status, version) = match document.tracking {
+            Some(tracking) => (
+                tracking.id,
+                tracking.status.as_deref().and_then(TrackingStatus::parse),
+                tracking.version,
+            ),
+            None => Default::default(),
+        };
+
+        Self {
+            id,
+            status,
+            version,
+            withdrawn: document.category == CATEGORY_WITHDRAWN,
+            superseded: document.category == CATEGORY_SUPERSEDED || !superseded_by.is_empty(),
+            superseded_by,
+        }
+    }
+
+    /// Check if the document is neither withdrawn, nor superseded.
+    pub fn is_current(&self) -> bool {
+        !self.withdrawn && !self.superseded
+    }
+}
+
+/// Follow the supersessions of documents, by their URL.
+///
+/// Each chain starts with a superseded document, which doesn't supersede any other of the
+/// documents, and follows the (first) superseding document, until reaching a document which
+/// isn't superseded (or unknown). Cycles are stopped at the first repeated document.
+pub fn supersession_chains(documents: &BTreeMap<Url, Lifecycle>) -> Vec<Vec<Url>> {
+    let next = |url: &Url| {
+        documents
+            .get(url)
+            .and_then(|lifecycle| lifecycle.superseded_by.first())
+    };
+
+    let superseding = documents
+        .values()
+        .filter_map(|lifecycle| lifecycle.superseded_by.first())
+        .collect::<BTreeSet<_>>();
+
+    let mut visited = BTreeSet::new();
+    let mut result = vec![];
+
+    // start with the documents not superseding others, then the remaining ones (of cycles)
+    let starts = documents
+        .keys()
+        .filter(|url| !superseding.contains(url))
+        .chain(documents.keys().filter(|url| superseding.contains(url)));
+
+    for start in starts {
+        if next(start).is_none() || visited.contains(start) {
+            continue;
+        }
+
+        let mut chain = vec![start.clone()];
+        visited.insert(start);
+        let mut current = start;
+        while let Some(url) = next(current) {
+            if chain.contains(url) {
+                break;
+            }
+            chain.push(url.clone());
+            visited.insert(url);
+            current = url;
+        }
+        result.push(chain);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).expect("example value must parse")
+    }
+
+    #[test]
+    fn test_parse() {
+        let lifecycle = Lifecycle::parse(
+            serde_json::json!({
+                "document": {
+                    "category": "csaf_superseded",
+                    "references": [
+                        {
+                            "category": "self",
+                            "summary": "Canonical URL",
+                            "url": "https://example.com/2024/a.json",
+                        },
+                        {
+                            "category": "external",
+                            "summary": "Superseding document",
+                            "url": "https://example.com/2024/b.json",
+                        },
+                    ],
+                    "tracking": {
+                        "id": "A",
+                        "status": "final",
+                        "version": "2",
+                    },
+                },
+            })
+            .to_string()
+            .as_bytes(),
+        );
+
+        assert_eq!(
+            lifecycle,
+            Lifecycle {
+                id: Some("A".to_string()),
+                status: Some(TrackingStatus::Final),
+                version: Some("2".to_string()),
+                withdrawn: false,
+                superseded: true,
+                superseded_by: vec![url("https://example.com/2024/b.json")],
+            }
+        );
+        assert!(!lifecycle.is_current());
+
+        let lifecycle = Lifecycle::parse(br#"{"document": {"category": "csaf_withdrawn"}}"#);
+        assert!(lifecycle.withdrawn);
+        assert!(!lifecycle.superseded);
+
+        assert!(Lifecycle::parse(b"not json").is_current());
+    }
+
+    #[test]
+    fn test_supersession_chains() {
+        let superseded_by = |by: &str| Lifecycle {
+            superseded: true,
+            superseded_by: vec![url(by)],
+            ..Default::default()
+        };
+
+        let documents = BTreeMap::from([
+            (
+                url("https://example.com/a.json"),
+                superseded_by("https://example.com/b.json"),
+            ),
+            (
+                url("https://example.com/b.json"),
+                superseded_by("https://example.com/c.json"),
+            ),
+            (url("https://example.com/c.json"), Lifecycle::default()),
+            (
+                url("https://example.com/x.json"),
+                superseded_by("https://example.com/y.json"),
+            ),
+            (
+                url("https://example.com/y.json"),
+                superseded_by("https://example.com/x.json"),
+            ),
+        ]);
+
+        assert_eq!(
+            supersession_chains(&documents),
+            vec![
+                vec![
+                    url("https://example.com/a.json"),
+                    url("https://example.com/b.json"),
+                    url("https://example.com/c.json"),
+                ],
+                vec![
+                    url("https://example.com/x.json"),
+                    url("https://example.com/y.json"),
+                ],
+            ]
+        );
+    }
+}
@@ -37,6 +37,19 @@ pub enum TlpLabel {
     Red,
 }
 
+impl TlpLabel {
+    /// The name of the label, as used in the provider metadata
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unlabeled => "UNLABELED",
+            Self::White => "WHITE",
+            Self::Green => "GREEN",
+            Self::Amber => "AMBER",
+            Self::Red => "RED",
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 pub struct Key {
     #[serde(default, skip_serializing_if = "Option::is_none")]
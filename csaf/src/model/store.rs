@@ -1,8 +1,252 @@
+use crate::{model::metadata::ProviderMetadata, visitors::store::DIR_METADATA};
+use anyhow::Context;
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
+use walker_common::{store::write_replace_sync, utils::hex::Hex};
+
+/// The name of the file describing the layout of a store, in its metadata directory
+pub const FILE_LAYOUT: &str = "layout.json";
+
+/// The directory of a content-addressed store, keeping the stored documents by their digest
+pub const DIR_OBJECTS: &str = "objects";
 
 /// create a distribution base directory
 pub fn distribution_base(base: impl AsRef<Path>, url: &str) -> PathBuf {
-    base.as_ref()
-        .join(utf8_percent_encode(url, NON_ALPHANUMERIC).to_string())
+    StoreLayout::V1.distribution_base(base, url)
+}
+
+/// The layout of a store, defining the names of the distribution directories
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StoreLayout {
+    /// The percent-encoded URL of the distribution
+    #[default]
+    V1,
+    /// A hash of the URL of the distribution, mapped to the URL by the layout file
+    V2,
+    /// Like [`StoreLayout::V2`], additionally keeping each stored document in the objects
+    /// directory, named after its SHA-256 digest
+    Content,
+}
+
+impl Display for StoreLayout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::V1 => f.write_str("v1"),
+            Self::V2 => f.write_str("v2"),
+            Self::Content => f.write_str("content"),
+        }
+    }
+}
+
+impl StoreLayout {
+    pub fn version(&self) -> u32 {
+        match self {
+            Self::V1 => 1,
+            Self::V2 => 2,
+            Self::Content => 3,
+        }
+    }
+
+    pub fn from_version(version: u32) -> Option<Self> {
+        match version {
+            1 => Some(Self::V1),
+            2 => Some(Self::V2),
+            3 => Some(Self::Content),
+            _ => None,
+        }
+    }
+
+    /// The name of the directory of a distribution
+    pub fn distribution_dir(&self, url: &str) -> String {
+        match self {
+            Self::V1 => utf8_percent_encode(url, NON_ALPHANUMERIC).to_string(),
+            // 64 bits are plenty for the few distributions of a provider
+            Self::V2 | Self::Content => Hex(&Sha256::digest(url)[..8]).to_lower(),
+        }
+    }
+
+    /// The base directory of a distribution
+    pub fn distribution_base(&self, base: impl AsRef<Path>, url: &str) -> PathBuf {
+        base.as_ref().join(self.distribution_dir(url))
+    }
+
+    /// Detect the layout of an existing store.
+    ///
+    /// Returns [`None`] if the store doesn't contain any provider metadata yet. A store without a
+    /// layout file uses [`StoreLayout::V1`].
+    pub fn detect(base: &Path) -> anyhow::Result<Option<Self>> {
+        if let Some(file) = LayoutFile::load(base)? {
+            return Self::from_version(file.version)
+                .map(Some)
+                .with_context(|| format!("Unsupported store layout version: {}", file.version));
+        }
+
+        Ok(base
+            .join(DIR_METADATA)
+            .join("provider-metadata.json")
+            .exists()
+            .then_some(Self::V1))
+    }
+}
+
+/// The path of a document in the objects directory, by its (lower-case, hex encoded) SHA-256 digest
+pub fn object_path(base: &Path, digest: &str) -> PathBuf {
+    base.join(DIR_OBJECTS).join(&digest[..2]).join(digest)
+}
+
+/// Keep a stored document in the objects directory of a content-addressed store.
+///
+/// The object is a hard link to the document, so it doesn't take up additional space, and
+/// retains the content once the document gets replaced. Returns the digest of the document.
+pub fn link_object(base: &Path, file: &Path) -> anyhow::Result<String> {
+    let data =
+        std::fs::read(file).with_context(|| format!("Failed to read: {}", file.display()))?;
+    let digest = Hex(&Sha256::digest(&data)).to_lower();
+
+    let object = object_path(base, &digest);
+    if object.exists() {
+        return Ok(digest);
+    }
+
+    if let Some(parent) = object.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    match std::fs::hard_link(file, &object) {
+        Ok(()) => Ok(digest),
+        // stored concurrently, with the same content
+        Err(err) if err.kind() == ErrorKind::AlreadyExists => Ok(digest),
+        Err(err) => Err(err).with_context(|| format!("Failed to link: {}", object.display())),
+    }
+}
+
+/// The URLs of all distributions of the provider metadata, as used for the distribution directories
+pub fn distribution_urls(metadata: &ProviderMetadata) -> Vec<String> {
+    let mut result = vec![];
+
+    for dist in &metadata.distributions {
+        if let Some(directory_url) = &dist.directory_url {
+            result.push(directory_url.to_string());
+        }
+        if let Some(rolie) = &dist.rolie {
+            result.extend(rolie.feeds.iter().map(|feed| feed.url.to_string()));
+        }
+    }
+
+    result
+}
+
+/// The layout file (`metadata/layout.json`) of a store, mapping directories to distribution URLs
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutFile {
+    pub version: u32,
+    /// The URL of the distribution, by directory name
+    #[serde(default)]
+    pub distributions: BTreeMap<String, String>,
+}
+
+impl LayoutFile {
+    pub fn new(layout: StoreLayout) -> Self {
+        Self {
+            version: layout.version(),
+            distributions: Default::default(),
+        }
+    }
+
+    /// Record the directories of distributions.
+    pub fn extend<I>(&mut self, urls: I)
+    where
+        I: IntoIterator<Item = String>,
+    {
+        if let Some(layout) = StoreLayout::from_version(self.version) {
+            for url in urls {
+                self.distributions
+                    .insert(layout.distribution_dir(&url), url);
+            }
+        }
+    }
+
+    /// Load the layout file of a store, returning [`None`] if there is none.
+    pub fn load(base: &Path) -> anyhow::Result<Option<Self>> {
+        let path = base.join(DIR_METADATA).join(FILE_LAYOUT);
+        let data = match std::fs::read(&path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err).with_context(|| format!("Failed to read: {}", path.display()))
+            }
+        };
+
+        serde_json::from_slice(&data)
+            .map(Some)
+            .with_context(|| format!("Failed to parse: {}", path.display()))
+    }
+
+    /// Store the layout file to the metadata directory of a store.
+    pub fn store(&self, base: &Path) -> anyhow::Result<()> {
+        let path = base.join(DIR_METADATA).join(FILE_LAYOUT);
+        let data = serde_json::to_vec_pretty(self)?;
+
+        write_replace_sync(&path, &data)
+            .with_context(|| format!("Failed to write: {}", path.display()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_distribution_dir() {
+        let url = "https://example.com/.well-known/csaf/white/";
+
+        assert_eq!(
+            StoreLayout::V1.distribution_dir(url),
+            "https%3A%2F%2Fexample%2Ecom%2F%2Ewell%2Dknown%2Fcsaf%2Fwhite%2F"
+        );
+
+        let v2 = StoreLayout::V2.distribution_dir(url);
+        assert_eq!(v2.len(), 16);
+        assert_eq!(v2, StoreLayout::V2.distribution_dir(url));
+        assert_ne!(
+            v2,
+            StoreLayout::V2.distribution_dir("https://example.com/.well-known/csaf/green/")
+        );
+    }
+
+    #[test]
+    fn test_detect() {
+        let dir = tempfile::tempdir().expect("must create temporary directory");
+        let base = dir.path().join("store");
+
+        assert_eq!(StoreLayout::detect(&base).expect("must detect"), None);
+
+        std::fs::create_dir_all(base.join(DIR_METADATA)).expect("must create metadata dir");
+        std::fs::write(
+            base.join(DIR_METADATA).join("provider-metadata.json"),
+            b"{}",
+        )
+        .expect("must write metadata");
+        assert_eq!(
+            StoreLayout::detect(&base).expect("must detect"),
+            Some(StoreLayout::V1)
+        );
+
+        let mut file = LayoutFile::new(StoreLayout::V2);
+        file.extend(["https://example.com/".to_string()]);
+        file.store(&base).expect("must store layout");
+        assert_eq!(
+            StoreLayout::detect(&base).expect("must detect"),
+            Some(StoreLayout::V2)
+        );
+        assert_eq!(LayoutFile::load(&base).expect("must load"), Some(file));
+    }
 }
@@ -0,0 +1,392 @@
+//! A persistent index of advisories, stored in an SQLite database
+//!
+//! Dates are stored as Unix timestamps (in nanoseconds), so that they can be compared and
+//! sorted by the database. The severity is stored as its rank, lowest first. Products are
+//! stored in lowercase as well, as SQLite only converts ASCII characters to lowercase.
+
+use super::{IndexEntry, Query, Severity};
+use rusqlite::{params, params_from_iter, types::Value, Connection, Row};
+use std::collections::BTreeSet;
+use std::path::Path;
+use time::OffsetDateTime;
+use url::Url;
+
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS advisories (
+    url TEXT PRIMARY KEY NOT NULL,
+    id TEXT,
+    title TEXT,
+    initial_release_date INTEGER,
+    current_release_date INTEGER,
+    score REAL,
+    severity INTEGER
+);
+CREATE INDEX IF NOT EXISTS advisories_date
+    ON advisories(COALESCE(current_release_date, initial_release_date));
+
+CREATE TABLE IF NOT EXISTS cves (
+    url TEXT NOT NULL,
+    cve TEXT NOT NULL COLLATE NOCASE
+);
+CREATE INDEX IF NOT EXISTS cves_url ON cves(url);
+CREATE INDEX IF NOT EXISTS cves_cve ON cves(cve);
+
+CREATE TABLE IF NOT EXISTS products (
+    url TEXT NOT NULL,
+    product TEXT NOT NULL,
+    search TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS products_url ON products(url);
+"#;
+
+/// The date used for filtering and sorting advisories
+const DATE: &str = "COALESCE(current_release_date, initial_release_date)";
+
+#[derive(Debug, thiserror::Error)]
+pub enum IndexError {
+    #[error("index database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error("invalid URL in index: {0}")]
+    Url(#[from] url::ParseError),
+}
+
+/// An index of advisories, backed by an SQLite database
+pub struct Index {
+    connection: Connection,
+}
+
+impl Index {
+    /// Open the index stored in a file, creating it if necessary.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, IndexError> {
+        Self::new(Connection::open(path)?)
+    }
+
+    /// Create an index which is only kept in memory.
+    pub fn in_memory() -> Result<Self, IndexError> {
+        Self::new(Connection::open_in_memory()?)
+    }
+
+    fn new(connection: Connection) -> Result<Self, IndexError> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+
+    /// Add a document to the index, replacing a previous version of it. Returns `false` if the
+    /// document couldn't be summarized.
+    pub fn add(&mut self, url: Url, data: &[u8]) -> Result<bool, IndexError> {
+        match IndexEntry::parse(url, data) {
+            Some(entry) => {
+                self.insert(&entry)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Insert an entry, replacing a previous entry of the same URL.
+    pub fn insert(&mut self, entry: &IndexEntry) -> Result<(), IndexError> {
+        let tx = self.connection.transaction()?;
+        insert(&tx, entry)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Replace the content of the index with the entries, in a single transaction.
+    ///
+    /// Readers of the index see either the previous or the new content, never a mix of both.
+    pub fn replace(
+        &mut self,
+        entries: impl IntoIterator<Item = IndexEntry>,
+    ) -> Result<(), IndexError> {
+        let tx = self.connection.transaction()?;
+
+        tx.execute_batch("DELETE FROM cves; DELETE FROM products; DELETE FROM advisories;")?;
+        for entry in entries {
+            insert(&tx, &entry)?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Find the advisories matching a query, the most recently updated first.
+    pub fn query(&self, query: &Query) -> Result<Vec<IndexEntry>, IndexError> {
+        let mut conditions = vec![];
+        let mut values = vec![];
+
+        if let Some(cve) = &query.cve {
+            conditions.push("url IN (SELECT url FROM cves WHERE cve = ?)".to_string());
+            values.push(Value::Text(cve.clone()));
+        }
+        if let Some(product) = &query.product {
+            conditions
+                .push("url IN (SELECT url FROM products WHERE instr(search, ?) > 0)".to_string());
+            values.push(Value::Text(product.to_lowercase()));
+        }
+        if let Some(severity) = query.severity {
+            conditions.push("severity >= ?".to_string());
+            values.push(Value::Integer(severity as i64));
+        }
+        if let Some(since) = query.since {
+            conditions.push(format!("{DATE} >= ?"));
+            values.push(Value::Integer(to_timestamp(since)));
+        }
+        if let Some(until) = query.until {
+            conditions.push(format!("{DATE} < ?"));
+            values.push(Value::Integer(to_timestamp(until)));
+        }
+
+        let mut sql = r#"
+SELECT url, id, title, initial_release_date, current_release_date, score
+FROM advisories"#
+            .to_string();
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+        sql.push_str(&format!(" ORDER BY {DATE} DESC, url"));
+
+        let mut stmt = self.connection.prepare(&sql)?;
+        let rows = stmt
+            .query_map(params_from_iter(values), Summary::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        rows.into_iter()
+            .map(|summary| self.entry(summary))
+            .collect()
+    }
+
+    /// Get the number of indexed advisories.
+    pub fn len(&self) -> Result<usize, IndexError> {
+        Ok(self
+            .connection
+            .query_row("SELECT COUNT(*) FROM advisories", [], |row| {
+                row.get::<_, i64>(0)
+            })? as usize)
+    }
+
+    pub fn is_empty(&self) -> Result<bool, IndexError> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Complete the summary of an advisory with its CVEs and products.
+    fn entry(&self, summary: Summary) -> Result<IndexEntry, IndexError> {
+        let cves = self.values("SELECT cve FROM cves WHERE url = ?", &summary.url)?;
+        let products = self.values("SELECT product FROM products WHERE url = ?", &summary.url)?;
+
+        Ok(IndexEntry {
+            url: Url::parse(&summary.url)?,
+            id: summary.id,
+            title: summary.title,
+            initial_release_date: summary.initial_release_date.map(from_timestamp),
+            current_release_date: summary.current_release_date.map(from_timestamp),
+            cves,
+            products,
+            score: summary.score,
+            severity: summary.score.map(Severity::from_score),
+        })
+    }
+
+    fn values(&self, sql: &str, url: &str) -> Result<BTreeSet<String>, IndexError> {
+        let mut stmt = self.connection.prepare_cached(sql)?;
+        let values = stmt
+            .query_map([url], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+        Ok(values)
+    }
+}
+
+/// A row of the advisories table
+struct Summary {
+    url: String,
+    id: Option<String>,
+    title: Option<String>,
+    initial_release_date: Option<i64>,
+    current_release_date: Option<i64>,
+    score: Option<f64>,
+}
+
+impl Summary {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            url: row.get(0)?,
+            id: row.get(1)?,
+            title: row.get(2)?,
+            initial_release_date: row.get(3)?,
+            current_release_date: row.get(4)?,
+            score: row.get(5)?,
+        })
+    }
+}
+
+fn insert(connection: &Connection, entry: &IndexEntry) -> Result<(), IndexError> {
+    let url = entry.url.as_str();
+
+    connection.execute("DELETE FROM cves WHERE url = ?1", [url])?;
+    connection.execute("DELETE FROM products WHERE url = ?1", [url])?;
+    connection.execute(
+        r#"
+INSERT OR REPLACE INTO advisories
+    (url, id, title, initial_release_date, current_release_date, score, severity)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"#,
+        params![
+            url,
+            entry.id,
+            entry.title,
+            entry.initial_release_date.map(to_timestamp),
+            entry.current_release_date.map(to_timestamp),
+            entry.score,
+            entry.severity.map(|severity| severity as i64),
+        ],
+    )?;
+
+    let mut stmt = connection.prepare_cached("INSERT INTO cves (url, cve) VALUES (?1, ?2)")?;
+    for cve in &entry.cves {
+        stmt.execute([url, cve])?;
+    }
+
+    let mut stmt = connection
+        .prepare_cached("INSERT INTO products (url, product, search) VALUES (?1, ?2, ?3)")?;
+    for product in &entry.products {
+        stmt.execute([url, product, &product.to_lowercase()])?;
+    }
+
+    Ok(())
+}
+
+fn to_timestamp(date: OffsetDateTime) -> i64 {
+    date.unix_timestamp_nanos()
+        .clamp(i64::MIN as i128, i64::MAX as i128) as i64
+}
+
+fn from_timestamp(timestamp: i64) -> OffsetDateTime {
+    // any i64 nanosecond value is within the supported range
+    OffsetDateTime::from_unix_timestamp_nanos(timestamp as i128)
+        .unwrap_or(OffsetDateTime::UNIX_EPOCH)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query::test::{document, timestamp};
+
+    fn index() -> Index {
+        let mut index = Index::in_memory().expect("must create index");
+        for (id, date, cve, purl, score) in [
+            (
+                "A",
+                "2024-01-01T00:00:00Z",
+                "CVE-2024-0001",
+                "pkg:rpm/example/server@1.0",
+                9.8,
+            ),
+            (
+                "B",
+                "2024-02-01T00:00:00Z",
+                "CVE-2024-0002",
+                "pkg:rpm/example/client@1.0",
+                5.3,
+            ),
+            (
+                "C",
+                "2024-03-01T00:00:00Z",
+                "CVE-2024-0001",
+                "pkg:rpm/example/client@2.0",
+                7.5,
+            ),
+        ] {
+            let url = Url::parse(&format!("https://example.com/{id}.json"))
+                .expect("example value must parse");
+            assert!(index
+                .add(url, &document(id, date, cve, purl, score))
+                .expect("must add"));
+        }
+        index
+    }
+
+    fn ids(index: &Index, query: Query) -> Vec<String> {
+        index
+            .query(&query)
+            .expect("must query")
+            .into_iter()
+            .filter_map(|entry| entry.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_query() {
+        let index = index();
+
+        assert_eq!(ids(&index, Query::new()), vec!["C", "B", "A"]);
+        assert_eq!(
+            ids(&index, Query::new().cve("cve-2024-0001")),
+            vec!["C", "A"]
+        );
+        assert_eq!(ids(&index, Query::new().product("CLIENT")), vec!["C", "B"]);
+        assert_eq!(
+            ids(&index, Query::new().severity(Severity::High)),
+            vec!["C", "A"]
+        );
+        assert_eq!(
+            ids(
+                &index,
+                Query::new()
+                    .since(timestamp("2024-02-01T00:00:00Z"))
+                    .until(timestamp("2024-03-01T00:00:00Z"))
+            ),
+            vec!["B"]
+        );
+        assert!(ids(
+            &index,
+            Query::new()
+                .cve("CVE-2024-0002")
+                .severity(Severity::Critical)
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_entry() {
+        let url = Url::parse("https://example.com/A.json").expect("example value must parse");
+        let data = document(
+            "A",
+            "2024-01-01T00:00:00Z",
+            "CVE-2024-0001",
+            "pkg:rpm/a@1",
+            7.0,
+        );
+        let expected = IndexEntry::parse(url.clone(), &data).expect("document must parse");
+
+        let mut index = Index::in_memory().expect("must create index");
+        index.add(url.clone(), &data).expect("must add");
+        // adding again replaces the previous version
+        index.add(url, &data).expect("must add");
+
+        assert_eq!(index.len().expect("must count"), 1);
+        assert_eq!(
+            index.query(&Query::new()).expect("must query"),
+            vec![expected]
+        );
+    }
+
+    #[test]
+    fn test_persistent() {
+        let dir = tempfile::tempdir().expect("must create temporary directory");
+        let path = dir.path().join("index.sqlite");
+
+        let entries = index().query(&Query::new()).expect("must query");
+        Index::open(&path)
+            .expect("must open")
+            .replace(entries.clone())
+            .expect("must replace");
+
+        let index = Index::open(&path).expect("must open");
+        assert_eq!(index.query(&Query::new()).expect("must query"), entries);
+
+        let mut index = index;
+        index
+            .replace(entries.into_iter().take(1))
+            .expect("must replace");
+        assert_eq!(ids(&index, Query::new()), vec!["C"]);
+    }
+}
@@ -0,0 +1,314 @@
+//! Querying the advisories of a local mirror
+//!
+//! An [`IndexEntry`] is the summary of an advisory (tracking information, CVEs, products, and
+//! severity), allowing to find advisories by CVE, by product, by severity, or by date without
+//! parsing all documents again. Like the [`crate::diff`], the summary is extracted from the JSON
+//! representation, so that documents which don't fully conform to the schema can be indexed as
+//! well.
+//!
+//! With the `index` feature, the summaries are stored in a persistent [`index::Index`].
+
+#[cfg(feature = "index")]
+pub mod index;
+
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use url::Url;
+
+/// The severity of an advisory, based on the highest CVSS base score of its vulnerabilities
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    None,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    /// The qualitative severity rating of a CVSS (v3) base score
+    pub fn from_score(score: f64) -> Self {
+        match score {
+            s if s >= 9.0 => Self::Critical,
+            s if s >= 7.0 => Self::High,
+            s if s >= 4.0 => Self::Medium,
+            s if s > 0.0 => Self::Low,
+            _ => Self::None,
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => f.write_str("none"),
+            Self::Low => f.write_str("low"),
+            Self::Medium => f.write_str("medium"),
+            Self::High => f.write_str("high"),
+            Self::Critical => f.write_str("critical"),
+        }
+    }
+}
+
+/// The summary of an indexed advisory
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexEntry {
+    /// The URL of the document
+    pub url: Url,
+    /// The tracking ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The initial release date (`/document/tracking/initial_release_date`)
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub initial_release_date: Option<OffsetDateTime>,
+    /// The current release date (`/document/tracking/current_release_date`)
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub current_release_date: Option<OffsetDateTime>,
+    /// The CVEs of the vulnerabilities
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub cves: BTreeSet<String>,
+    /// The names, PURLs, and CPEs of the products in the product tree
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub products: BTreeSet<String>,
+    /// The highest CVSS base score of the vulnerabilities (v3 preferred over v2)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+}
+
+impl IndexEntry {
+    /// Summarize a document. Returns [`None`] if the content isn't a JSON document.
+    pub fn parse(url: Url, data: &[u8]) -> Option<Self> {
+        let document = serde_json::from_slice::<Value>(data).ok()?;
+
+        let string = |pointer: &str| {
+            document
+                .pointer(pointer)
+                .and_then(Value::as_str)
+                .map(ToString::to_string)
+        };
+        let date = |pointer: &str| {
+            document
+                .pointer(pointer)
+                .and_then(Value::as_str)
+                .and_then(|date| OffsetDateTime::parse(date, &Rfc3339).ok())
+        };
+
+        let vulnerabilities = array(document.get("vulnerabilities")).collect::<Vec<_>>();
+        let cves = vulnerabilities
+            .iter()
+            .filter_map(|vulnerability| vulnerability["cve"].as_str())
+            .map(ToString::to_string)
+            .collect();
+        let score = vulnerabilities
+            .iter()
+            .flat_map(|vulnerability| array(vulnerability.get("scores")))
+            .filter_map(|score| {
+                score["cvss_v3"]["baseScore"]
+                    .as_f64()
+                    .or_else(|| score["cvss_v2"]["baseScore"].as_f64())
+            })
+            .reduce(f64::max);
+
+        let mut products = BTreeSet::new();
+        if let Some(product_tree) = document.get("product_tree") {
+            collect_products(product_tree, &mut products);
+        }
+
+        Some(Self {
+            url,
+            id: string("/document/tracking/id"),
+            title: string("/document/title"),
+            initial_release_date: date("/document/tracking/initial_release_date"),
+            current_release_date: date("/document/tracking/current_release_date"),
+            cves,
+            products,
+            score,
+            severity: score.map(Severity::from_score),
+        })
+    }
+}
+
+/// Collect the product names, PURLs, and CPEs of a product tree (or any part of it).
+fn collect_products(value: &Value, products: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::Object(product)) = object.get("product") {
+                collect_product(product, products);
+            }
+            for product in array(object.get("full_product_names")) {
+                if let Value::Object(product) = product {
+                    collect_product(product, products);
+                }
+            }
+            for key in ["branches", "relationships"] {
+                if let Some(value) = object.get(key) {
+                    collect_products(value, products);
+                }
+            }
+            if let Some(Value::Object(product)) = object.get("full_product_name") {
+                collect_product(product, products);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                collect_products(value, products);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_product(product: &serde_json::Map<String, Value>, products: &mut BTreeSet<String>) {
+    let helper = product.get("product_identification_helper");
+    let values = [
+        product.get("name"),
+        helper.and_then(|helper| helper.get("purl")),
+        helper.and_then(|helper| helper.get("cpe")),
+    ];
+
+    products.extend(
+        values
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(ToString::to_string),
+    );
+}
+
+fn array(value: Option<&Value>) -> impl Iterator<Item = &Value> {
+    value.and_then(Value::as_array).into_iter().flatten()
+}
+
+/// A query for advisories. All criteria must match, missing criteria match all advisories.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(default)]
+pub struct Query {
+    /// Advisories with a vulnerability of this CVE (case-insensitive)
+    pub cve: Option<String>,
+    /// Advisories with a product name, PURL, or CPE containing this text (case-insensitive)
+    pub product: Option<String>,
+    /// Advisories of at least this severity
+    pub severity: Option<Severity>,
+    /// Advisories released (or updated) at, or after, this timestamp
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub since: Option<OffsetDateTime>,
+    /// Advisories released (or updated) before this timestamp
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub until: Option<OffsetDateTime>,
+}
+
+impl Query {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cve(mut self, cve: impl Into<String>) -> Self {
+        self.cve = Some(cve.into());
+        self
+    }
+
+    pub fn product(mut self, product: impl Into<String>) -> Self {
+        self.product = Some(product.into());
+        self
+    }
+
+    pub fn severity(mut self, severity: impl Into<Option<Severity>>) -> Self {
+        self.severity = severity.into();
+        self
+    }
+
+    pub fn since(mut self, since: impl Into<Option<OffsetDateTime>>) -> Self {
+        self.since = since.into();
+        self
+    }
+
+    pub fn until(mut self, until: impl Into<Option<OffsetDateTime>>) -> Self {
+        self.until = until.into();
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    pub(super) fn document(id: &str, date: &str, cve: &str, purl: &str, score: f64) -> Vec<u8> {
+        json!({
+            "document": {
+                "title": format!("Advisory {id}"),
+                "tracking": {
+                    "id": id,
+                    "initial_release_date": date,
+                    "current_release_date": date,
+                },
+            },
+            "product_tree": {
+                "branches": [{
+                    "category": "vendor",
+                    "name": "Example",
+                    "branches": [{
+                        "category": "product_version",
+                        "name": "1.0",
+                        "product": {
+                            "name": "Example Server 1.0",
+                            "product_id": "server-1.0",
+                            "product_identification_helper": { "purl": purl },
+                        },
+                    }],
+                }],
+            },
+            "vulnerabilities": [{
+                "cve": cve,
+                "scores": [{
+                    "cvss_v3": { "baseScore": score },
+                    "products": ["server-1.0"],
+                }],
+            }],
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    pub(super) fn timestamp(value: &str) -> OffsetDateTime {
+        OffsetDateTime::parse(value, &Rfc3339).expect("example value must parse")
+    }
+
+    #[test]
+    fn test_parse() {
+        let url = Url::parse("https://example.com/A.json").expect("example value must parse");
+        let entry = IndexEntry::parse(
+            url,
+            &document(
+                "A",
+                "2024-01-01T00:00:00Z",
+                "CVE-2024-0001",
+                "pkg:rpm/a@1",
+                7.0,
+            ),
+        )
+        .expect("document must parse");
+
+        assert_eq!(entry.id.as_deref(), Some("A"));
+        assert_eq!(
+            entry.current_release_date,
+            Some(timestamp("2024-01-01T00:00:00Z"))
+        );
+        assert_eq!(entry.cves, BTreeSet::from(["CVE-2024-0001".to_string()]));
+        assert_eq!(
+            entry.products,
+            BTreeSet::from(["Example Server 1.0".to_string(), "pkg:rpm/a@1".to_string()])
+        );
+        assert_eq!(entry.severity, Some(Severity::High));
+    }
+}
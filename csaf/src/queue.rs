@@ -0,0 +1,264 @@
+//! A work queue, shared by multiple walker instances
+//!
+//! Discovered advisories are pushed into the queue as jobs, one file per job. Workers claim jobs
+//! by atomically moving them into a directory of their own, so that each job gets processed by a
+//! single worker. Completed jobs are removed, failed jobs can be released back into the queue.
+//!
+//! The queue is a directory, which can be shared between machines (e.g. using NFS), as long as
+//! renaming files is atomic.
+
+use crate::source::ListEntry;
+use anyhow::Context;
+use filetime::FileTime;
+use sha2::{Digest, Sha256};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use walker_common::store::write_replace_sync;
+
+const DIR_PENDING: &str = "pending";
+const DIR_CLAIMED: &str = "claimed";
+const SUFFIX: &str = ".job";
+
+/// A work queue, backed by a directory
+#[derive(Clone, Debug)]
+pub struct DirectoryQueue {
+    base: PathBuf,
+}
+
+/// A job, claimed by a worker
+#[derive(Debug)]
+pub struct Job {
+    file: PathBuf,
+    pub entry: ListEntry,
+}
+
+impl DirectoryQueue {
+    /// Open the queue, creating its directories if necessary.
+    pub fn new(base: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let base = base.into();
+
+        for dir in [DIR_PENDING, DIR_CLAIMED] {
+            let dir = base.join(dir);
+            std::fs::create_dir_all(&dir)
+                .with_context(|| format!("Unable to create queue directory: {}", dir.display()))?;
+        }
+
+        Ok(Self { base })
+    }
+
+    fn pending_dir(&self) -> PathBuf {
+        self.base.join(DIR_PENDING)
+    }
+
+    fn claimed_dir(&self) -> PathBuf {
+        self.base.join(DIR_CLAIMED)
+    }
+
+    /// Push an advisory into the queue.
+    ///
+    /// Jobs are identified by the URL of the advisory. Pushing an advisory which is already
+    /// pending replaces the existing job.
+    pub fn push(&self, entry: &ListEntry) -> anyhow::Result<()> {
+        let name = format!("{:x}{SUFFIX}", Sha256::digest(entry.url.as_str()));
+        let data = serde_json::to_vec(entry)?;
+
+        // the temporary file doesn't end with the job suffix, so workers never see a partial job
+        write_replace_sync(&self.pending_dir().join(name), &data)?;
+
+        Ok(())
+    }
+
+    /// Claim up to `limit` pending jobs for a worker.
+    ///
+    /// Returns an empty result if there are no more pending jobs.
+    pub fn claim(&self, worker: &str, limit: usize) -> anyhow::Result<Vec<Job>> {
+        let claimed = self.claimed_dir().join(worker);
+        std::fs::create_dir_all(&claimed)?;
+
+        let mut result = vec![];
+
+        for entry in std::fs::read_dir(self.pending_dir())? {
+            if result.len() >= limit {
+                break;
+            }
+
+            let entry = entry?;
+            let name = entry.file_name();
+            if !is_job(&name.to_string_lossy()) {
+                continue;
+            }
+
+            // record the time of claiming, for detecting abandoned jobs. This must happen before
+            // moving the job, otherwise it might look abandoned right after being claimed.
+            match filetime::set_file_mtime(entry.path(), FileTime::now()) {
+                Ok(()) => {}
+                // claimed by another worker in the meantime
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            }
+
+            let file = claimed.join(&name);
+            match std::fs::rename(entry.path(), &file) {
+                Ok(()) => {}
+                // claimed by another worker in the meantime
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            }
+
+            match serde_json::from_slice(&std::fs::read(&file)?) {
+                Ok(entry) => result.push(Job { file, entry }),
+                Err(err) => {
+                    log::warn!("Discarding invalid job ({}): {err}", file.display());
+                    std::fs::remove_file(&file)?;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Remove a job, as it was processed.
+    pub fn complete(&self, job: Job) -> anyhow::Result<()> {
+        Ok(std::fs::remove_file(&job.file)?)
+    }
+
+    /// Release a job back into the queue, so that it can be claimed again.
+    pub fn release(&self, job: Job) -> anyhow::Result<()> {
+        release(&self.pending_dir(), &job.file)
+    }
+
+    /// Release jobs which have been claimed at least `max_age` ago, e.g. by a worker which
+    /// crashed. Returns the number of released jobs.
+    pub fn release_abandoned(&self, max_age: Duration) -> anyhow::Result<usize> {
+        let pending = self.pending_dir();
+        let now = SystemTime::now();
+        let mut result = 0;
+
+        for worker in std::fs::read_dir(self.claimed_dir())? {
+            let worker = worker?;
+            if !worker.file_type()?.is_dir() {
+                continue;
+            }
+
+            for entry in std::fs::read_dir(worker.path())? {
+                let entry = entry?;
+                if !is_job(&entry.file_name().to_string_lossy()) {
+                    continue;
+                }
+
+                let claimed = entry.metadata()?.modified()?;
+                if now.duration_since(claimed).unwrap_or_default() >= max_age {
+                    release(&pending, &entry.path())?;
+                    result += 1;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Get the number of pending jobs.
+    pub fn pending(&self) -> anyhow::Result<usize> {
+        let mut result = 0;
+        for entry in std::fs::read_dir(self.pending_dir())? {
+            if is_job(&entry?.file_name().to_string_lossy()) {
+                result += 1;
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn is_job(name: &str) -> bool {
+    name.ends_with(SUFFIX) && !name.starts_with('.')
+}
+
+fn release(pending: &Path, file: &Path) -> anyhow::Result<()> {
+    let name = file
+        .file_name()
+        .with_context(|| format!("Invalid job file: {}", file.display()))?;
+    Ok(std::fs::rename(file, pending.join(name))?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use url::Url;
+
+    fn entry(url: &str) -> ListEntry {
+        ListEntry {
+            url: Url::parse(url).expect("example URL must parse"),
+            distribution: None,
+            modified: None,
+        }
+    }
+
+    #[test]
+    fn test_claim() {
+        let dir = tempfile::tempdir().expect("must create temporary directory");
+        let queue = DirectoryQueue::new(dir.path()).expect("queue must be created");
+
+        queue
+            .push(&entry("https://example.com/a.json"))
+            .expect("must push");
+        queue
+            .push(&entry("https://example.com/b.json"))
+            .expect("must push");
+        queue
+            .push(&entry("https://example.com/c.json"))
+            .expect("must push");
+        assert_eq!(queue.pending().expect("must count"), 3);
+
+        let first = queue.claim("first", 2).expect("must claim");
+        let second = queue.claim("second", 2).expect("must claim");
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 1);
+        assert_eq!(queue.pending().expect("must count"), 0);
+
+        for job in first {
+            queue.complete(job).expect("must complete");
+        }
+        for job in second {
+            queue.release(job).expect("must release");
+        }
+        assert_eq!(queue.pending().expect("must count"), 1);
+
+        let _ = queue.claim("third", 10).expect("must claim");
+        assert_eq!(
+            queue
+                .release_abandoned(Duration::ZERO)
+                .expect("must release"),
+            1
+        );
+        assert_eq!(queue.pending().expect("must count"), 1);
+    }
+
+    #[test]
+    fn test_claim_time() {
+        let dir = tempfile::tempdir().expect("must create temporary directory");
+        let queue = DirectoryQueue::new(dir.path()).expect("queue must be created");
+
+        queue
+            .push(&entry("https://example.com/a.json"))
+            .expect("must push");
+
+        // a job which has been pending for a long time
+        for file in std::fs::read_dir(queue.pending_dir()).expect("must list") {
+            let file = file.expect("must read entry").path();
+            filetime::set_file_mtime(&file, FileTime::from_unix_time(0, 0)).expect("must set time");
+        }
+
+        let jobs = queue.claim("first", 10).expect("must claim");
+        assert_eq!(jobs.len(), 1);
+
+        // must not be considered abandoned, as it just got claimed
+        assert_eq!(
+            queue
+                .release_abandoned(Duration::from_secs(60 * 60))
+                .expect("must release"),
+            0
+        );
+        assert_eq!(queue.pending().expect("must count"), 0);
+    }
+}
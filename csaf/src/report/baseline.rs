@@ -0,0 +1,186 @@
+use crate::ignore::today;
+use anyhow::Context;
+use chrono::NaiveDate;
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind};
+use std::path::Path;
+use url::Url;
+use walker_common::clock::Clock;
+
+/// A list of known and accepted findings, which should not fail a report
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Baseline {
+    pub suppressions: Vec<Suppression>,
+}
+
+/// Suppress the findings of a check for a document
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Suppression {
+    /// The URL of the document
+    pub url: Url,
+    /// The ID of the check
+    pub check: String,
+    /// The last day the suppression is active, if it expires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<NaiveDate>,
+}
+
+impl Suppression {
+    /// Check if the suppression is still active on the provided day.
+    pub fn is_active(&self, today: NaiveDate) -> bool {
+        self.expires.map_or(true, |expires| today <= expires)
+    }
+}
+
+impl Baseline {
+    /// Load a baseline from a JSON file, returning [`None`] if the file doesn't exist.
+    pub fn load_from(path: &Path) -> anyhow::Result<Option<Self>> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Failed to open baseline: {}", path.display()))
+            }
+        };
+
+        serde_json::from_reader(BufReader::new(file))
+            .map(Some)
+            .with_context(|| format!("Failed to parse baseline: {}", path.display()))
+    }
+
+    /// Store the baseline to a JSON file.
+    pub fn store_to(&self, path: &Path) -> anyhow::Result<()> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create baseline: {}", path.display()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .with_context(|| format!("Failed to write baseline: {}", path.display()))
+    }
+
+    /// Check if the findings of a check for a document are suppressed.
+    pub fn is_suppressed(&self, url: &Url, check: &str, today: NaiveDate) -> bool {
+        self.suppressions.iter().any(|suppression| {
+            suppression.url == *url && suppression.check == check && suppression.is_active(today)
+        })
+    }
+
+    /// Check if the findings of a check for a document are suppressed, on the current day of the clock.
+    pub fn is_suppressed_today(&self, url: &Url, check: &str, clock: &Clock) -> bool {
+        today(clock).is_some_and(|today| self.is_suppressed(url, check, today))
+    }
+
+    /// Create a new baseline from the current findings.
+    ///
+    /// The expiry of findings which are already part of this baseline will be kept. Suppressions
+    /// for findings which are no longer present will be dropped.
+    pub fn update(&self, findings: impl IntoIterator<Item = (Url, String)>) -> Self {
+        let suppressions = findings
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|(url, check)| {
+                let expires = self
+                    .suppressions
+                    .iter()
+                    .find(|suppression| suppression.url == url && suppression.check == check)
+                    .and_then(|suppression| suppression.expires);
+
+                Suppression {
+                    url,
+                    check,
+                    expires,
+                }
+            })
+            .collect();
+
+        Self { suppressions }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).expect("example value must parse")
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("example value must be valid")
+    }
+
+    #[test]
+    fn test_suppressed() {
+        let baseline = Baseline {
+            suppressions: vec![
+                Suppression {
+                    url: url("https://example.com/2023/a.json"),
+                    check: "check_csaf_base".to_string(),
+                    expires: None,
+                },
+                Suppression {
+                    url: url("https://example.com/2023/b.json"),
+                    check: "check_csaf_base".to_string(),
+                    expires: Some(date(2024, 1, 31)),
+                },
+            ],
+        };
+
+        let today = date(2024, 1, 31);
+        let a = url("https://example.com/2023/a.json");
+        let b = url("https://example.com/2023/b.json");
+
+        assert!(baseline.is_suppressed(&a, "check_csaf_base", today));
+        assert!(!baseline.is_suppressed(&a, "check_history", today));
+        assert!(baseline.is_suppressed(&b, "check_csaf_base", today));
+        assert!(!baseline.is_suppressed(&b, "check_csaf_base", date(2024, 2, 1)));
+    }
+
+    #[test]
+    fn test_update() {
+        let baseline = Baseline {
+            suppressions: vec![
+                Suppression {
+                    url: url("https://example.com/2023/a.json"),
+                    check: "check_csaf_base".to_string(),
+                    expires: Some(date(2024, 1, 31)),
+                },
+                Suppression {
+                    url: url("https://example.com/2023/b.json"),
+                    check: "check_csaf_base".to_string(),
+                    expires: None,
+                },
+            ],
+        };
+
+        let baseline = baseline.update([
+            (
+                url("https://example.com/2023/a.json"),
+                "check_csaf_base".to_string(),
+            ),
+            (
+                url("https://example.com/2023/a.json"),
+                "check_history".to_string(),
+            ),
+        ]);
+
+        assert_eq!(
+            baseline.suppressions,
+            vec![
+                Suppression {
+                    url: url("https://example.com/2023/a.json"),
+                    check: "check_csaf_base".to_string(),
+                    expires: Some(date(2024, 1, 31)),
+                },
+                Suppression {
+                    url: url("https://example.com/2023/a.json"),
+                    check: "check_history".to_string(),
+                    expires: None,
+                },
+            ]
+        );
+    }
+}
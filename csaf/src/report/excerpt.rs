@@ -0,0 +1,71 @@
+use serde_json::Value;
+
+/// The maximum number of lines of an excerpt
+const MAX_LINES: usize = 15;
+
+/// Create an excerpt of the part of a document, located by a JSON pointer.
+///
+/// If it is small enough, the parent of the located value is used, providing some context.
+/// Otherwise, the located value is used. In any case, the excerpt is limited to a few lines.
+pub fn excerpt(document: &Value, pointer: &str) -> Option<String> {
+    let value = document.pointer(pointer)?;
+
+    let parent = pointer
+        .rsplit_once('/')
+        .filter(|(parent, _)| !parent.is_empty())
+        .and_then(|(parent, _)| document.pointer(parent));
+
+    let text = parent
+        .and_then(pretty)
+        .filter(|text| text.lines().count() <= MAX_LINES)
+        .or_else(|| pretty(value))?;
+
+    Some(truncate(&text, MAX_LINES))
+}
+
+fn pretty(value: &Value) -> Option<String> {
+    serde_json::to_string_pretty(value).ok()
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    let mut lines = text.lines();
+    let mut result = lines.by_ref().take(max).collect::<Vec<_>>().join("\n");
+
+    if lines.next().is_some() {
+        result.push_str("\n…");
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parent() {
+        let doc = json!({"document": {"title": ""}});
+
+        assert_eq!(
+            excerpt(&doc, "/document/title").as_deref(),
+            Some("{\n  \"title\": \"\"\n}")
+        );
+    }
+
+    #[test]
+    fn test_truncate() {
+        let doc = json!({"items": (0..100).collect::<Vec<_>>()});
+
+        let excerpt = excerpt(&doc, "/items").expect("must find value");
+        assert_eq!(excerpt.lines().count(), MAX_LINES + 1);
+        assert!(excerpt.ends_with('…'));
+    }
+
+    #[test]
+    fn test_missing() {
+        let doc = json!({"document": {}});
+
+        assert_eq!(excerpt(&doc, "/document/title"), None);
+    }
+}
@@ -1,30 +1,86 @@
 //! Reporting functionality
 
+mod baseline;
+mod excerpt;
 mod render;
+mod split;
+mod state;
+mod summary;
 
+pub use baseline::*;
+pub use excerpt::*;
 pub use render::*;
+pub use state::*;
+pub use summary::*;
 
-use crate::discover::DiscoveredAdvisory;
-use std::borrow::Cow;
+use split::*;
+
+use crate::diff::AdvisoryDiff;
+use crate::discover::{AggregatorOrigin, DiscoveredAdvisory};
+use crate::model::lifecycle::Lifecycle;
+use crate::verification::check::Finding;
+use sha2::{Digest, Sha256};
 use std::collections::{BTreeMap, HashSet};
 use url::Url;
-use walker_common::utils::url::Urlify;
+use walker_common::utils::{hex::Hex, openpgp::KeyIssue, url::Urlify};
 
 #[derive(Clone, Debug)]
 pub struct ReportResult<'d> {
     pub total: usize,
     pub duplicates: &'d Duplicates,
     pub errors: &'d BTreeMap<DocumentKey, String>,
-    pub warnings: &'d BTreeMap<DocumentKey, Vec<Cow<'static, str>>>,
+    pub warnings: &'d BTreeMap<DocumentKey, Vec<Finding>>,
+    /// Warnings and infos of checks, which don't fail the check
+    pub notices: &'d BTreeMap<DocumentKey, Vec<Finding>>,
+    /// Excerpts of the documents, by the JSON pointer of a finding
+    pub excerpts: &'d BTreeMap<DocumentKey, BTreeMap<String, String>>,
+    /// The number of findings suppressed by a baseline
+    pub suppressed: usize,
+    /// Changes to documents, compared to their previously stored version
+    pub changes: &'d BTreeMap<DocumentKey, AdvisoryDiff>,
+    /// Issues of the provider keys, like being expired or revoked
+    pub key_issues: &'d [KeyIssue],
+    /// The aggregators and listed providers, documents originated from
+    pub origins: &'d BTreeMap<DocumentKey, AggregatorOrigin>,
+    /// The lifecycle information of withdrawn and superseded documents, by their URL
+    pub lifecycles: &'d BTreeMap<Url, Lifecycle>,
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct Duplicates {
     pub duplicates: BTreeMap<DocumentKey, usize>,
     pub known: HashSet<DocumentKey>,
+    /// The documents (and the digest of their content), by tracking ID
+    pub tracking_ids: BTreeMap<String, BTreeMap<DocumentKey, String>>,
 }
 
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+impl Duplicates {
+    /// Record the tracking ID of a document, along with the digest of its content.
+    pub fn add_tracking_id(&mut self, id: String, key: DocumentKey, digest: String) {
+        self.tracking_ids.entry(id).or_default().insert(key, digest);
+    }
+
+    /// Tracking IDs used by multiple documents with different content.
+    ///
+    /// The same document, published under different URLs, is not considered a conflict.
+    pub fn conflicting_tracking_ids(
+        &self,
+    ) -> impl Iterator<Item = (&String, &BTreeMap<DocumentKey, String>)> {
+        self.tracking_ids
+            .iter()
+            .filter(|(_, documents)| documents.values().collect::<HashSet<_>>().len() > 1)
+    }
+}
+
+/// Create the (SHA-256) digest of the content of a document, for detecting different content.
+pub fn content_digest(data: &[u8]) -> String {
+    Hex(&Sha256::digest(data)).to_lower()
+}
+
+#[derive(
+    Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "camelCase")]
 pub struct DocumentKey {
     /// the URL to the distribution folder
     pub distribution_url: Url,
@@ -40,3 +96,34 @@ impl DocumentKey {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn key(url: &str) -> DocumentKey {
+        DocumentKey {
+            distribution_url: Url::parse("https://example.com/").expect("example value must parse"),
+            url: url.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_conflicting_tracking_ids() {
+        let mut duplicates = Duplicates::default();
+
+        // same content, different URLs
+        duplicates.add_tracking_id("a".into(), key("a.json"), content_digest(b"a"));
+        duplicates.add_tracking_id("a".into(), key("mirror/a.json"), content_digest(b"a"));
+        // different content
+        duplicates.add_tracking_id("b".into(), key("b.json"), content_digest(b"b1"));
+        duplicates.add_tracking_id("b".into(), key("c.json"), content_digest(b"b2"));
+
+        let conflicts = duplicates
+            .conflicting_tracking_ids()
+            .map(|(id, documents)| (id.as_str(), documents.len()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(conflicts, vec![("b", 2)]);
+    }
+}
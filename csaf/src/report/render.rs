@@ -1,10 +1,25 @@
-use crate::report::{DocumentKey, ReportResult};
+use crate::{
+    diff::AdvisoryDiff,
+    discover::AggregatorOrigin,
+    model::lifecycle::{supersession_chains, Lifecycle},
+    report::{render_split, DocumentKey, Duplicates, Navigation, ReportResult},
+    verification::check::Finding,
+};
 use std::{
+    collections::BTreeMap,
     fmt::{Display, Formatter},
+    fs::File,
+    io::{BufWriter, Write},
     path::PathBuf,
 };
 use url::Url;
-use walker_common::{locale::Formatted, report, report::Summary};
+use walker_common::{
+    locale::Formatted,
+    report,
+    report::{ReportOptions, Summary},
+    run::run_id,
+    utils::openpgp::KeyIssue,
+};
 
 #[derive(Clone, Debug)]
 pub struct ReportRenderOption {
@@ -12,6 +27,33 @@ pub struct ReportRenderOption {
 
     pub base_url: Option<Url>,
     pub source_url: Option<Url>,
+
+    /// Split the report into an index page, and pages per distribution, with up to this number
+    /// of documents each.
+    pub page_size: Option<usize>,
+
+    /// Options for rendering the HTML documents, like the styling
+    pub report_options: ReportOptions,
+}
+
+/// Render the report to the output file of the options.
+///
+/// If a page size is set, the report is split into multiple files. Otherwise, a single file is
+/// created. Returns the paths of all created files.
+pub fn render_to_files(
+    report: &ReportResult,
+    options: ReportRenderOption,
+) -> anyhow::Result<Vec<PathBuf>> {
+    match options.page_size {
+        Some(page_size) => render_split(report, &options, page_size),
+        None => {
+            let output = options.output.clone();
+            let mut out = BufWriter::new(File::create(&output)?);
+            render_to_html(&mut out, report, options)?;
+            out.flush()?;
+            Ok(vec![output])
+        }
+    }
 }
 
 pub fn render_to_html<W: std::io::Write>(
@@ -26,8 +68,9 @@ pub fn render_to_html<W: std::io::Write>(
             result: report,
             base_url: &options.base_url,
             source_url: &options.source_url,
+            navigation: None,
         },
-        &Default::default(),
+        &options.report_options,
     )?;
 
     Ok(())
@@ -36,26 +79,42 @@ pub fn render_to_html<W: std::io::Write>(
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum Title {
     Duplicates,
+    TrackingIds,
     Warnings,
+    Notices,
     Errors,
+    Changes,
+    Keys,
+    Origins,
+    Withdrawn,
+    Supersessions,
 }
 
 impl Display for Title {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Duplicates => f.write_str("Duplicates"),
+            Self::TrackingIds => f.write_str("Tracking IDs"),
             Self::Warnings => f.write_str("Warnings"),
+            Self::Notices => f.write_str("Notices"),
             Self::Errors => f.write_str("Errors"),
+            Self::Changes => f.write_str("Changes"),
+            Self::Keys => f.write_str("Keys"),
+            Self::Origins => f.write_str("Origins"),
+            Self::Withdrawn => f.write_str("Withdrawn"),
+            Self::Supersessions => f.write_str("Supersessions"),
         }
     }
 }
 
-struct HtmlReport<'r> {
-    result: &'r ReportResult<'r>,
+pub(super) struct HtmlReport<'r> {
+    pub(super) result: &'r ReportResult<'r>,
     /// The base of the source, used to generate a relative URL
-    base_url: &'r Option<Url>,
+    pub(super) base_url: &'r Option<Url>,
     /// Override source URL
-    source_url: &'r Option<Url>,
+    pub(super) source_url: &'r Option<Url>,
+    /// Navigation, in case this is a page of a split report
+    pub(super) navigation: Option<&'r Navigation>,
 }
 
 impl HtmlReport<'_> {
@@ -165,11 +224,33 @@ impl HtmlReport<'_> {
     }
 
     fn render_warnings(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let file_count = self.result.warnings.len();
-        let total_count = self.result.warnings.values().map(|w| w.len()).sum();
+        self.render_messages(
+            f,
+            Title::Warnings,
+            "warning(s)",
+            self.result.warnings,
+            false,
+        )
+    }
+
+    fn render_notices(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.render_messages(f, Title::Notices, "notice(s)", self.result.notices, true)
+    }
+
+    /// render findings, optionally prefixed with their severity
+    fn render_messages(
+        &self,
+        f: &mut Formatter<'_>,
+        title: Title,
+        kind: &str,
+        messages: &BTreeMap<DocumentKey, Vec<Finding>>,
+        severity: bool,
+    ) -> std::fmt::Result {
+        let file_count = messages.len();
+        let total_count = messages.values().map(|w| w.len()).sum();
 
         let data = |f: &mut Formatter<'_>| {
-            for (k, v) in self.result.warnings {
+            for (k, v) in messages {
                 let (url, label) = self.link_document(k);
 
                 writeln!(
@@ -183,16 +264,40 @@ impl HtmlReport<'_> {
                     label = html_escape::encode_text(&label),
                 )?;
 
-                for text in v {
+                let excerpts = self.result.excerpts.get(k);
+
+                for finding in v {
+                    let text = match severity {
+                        true => finding.to_string(),
+                        false => finding.message.to_string(),
+                    };
+
                     writeln!(
                         f,
                         r#"
             <li>
-                <code>{v}</code>
-            </li>
-            "#,
+                <code>{v}</code>"#,
                         v = html_escape::encode_text(&text),
                     )?;
+
+                    if let Some((path, excerpt)) = finding.path.as_ref().and_then(|path| {
+                        excerpts
+                            .and_then(|excerpts| excerpts.get(path))
+                            .map(|excerpt| (path, excerpt))
+                    }) {
+                        writeln!(
+                            f,
+                            r#"
+                <details>
+                    <summary><code>{path}</code></summary>
+                    <pre><code>{excerpt}</code></pre>
+                </details>"#,
+                            path = html_escape::encode_text(path),
+                            excerpt = html_escape::encode_text(excerpt),
+                        )?;
+                    }
+
+                    writeln!(f, "</li>")?;
                 }
 
                 writeln!(
@@ -211,9 +316,9 @@ impl HtmlReport<'_> {
             Self::render_table(
                 f,
                 [file_count, total_count],
-                Title::Warnings,
+                title,
                 &format!(
-                    "{total_count} warning(s) in {file_count} file(s) detected",
+                    "{total_count} {kind} in {file_count} file(s) detected",
                     total_count = Formatted(total_count),
                     file_count = Formatted(file_count),
                 ),
@@ -223,6 +328,60 @@ impl HtmlReport<'_> {
         Ok(())
     }
 
+    fn render_changes(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let count = self.result.changes.len();
+
+        let data = |f: &mut Formatter<'_>| {
+            for (k, diff) in self.result.changes {
+                let (url, label) = self.link_document(k);
+
+                writeln!(
+                    f,
+                    r#"
+            <tr>
+                <td><a href="{url}" target="_blank" style="white-space: nowrap;">{label}</a></td>
+                <td><ul>
+"#,
+                    url = html_escape::encode_quoted_attribute(&url),
+                    label = html_escape::encode_text(&label),
+                )?;
+
+                for change in describe_changes(diff) {
+                    writeln!(
+                        f,
+                        "<li>{change}</li>",
+                        change = html_escape::encode_text(&change)
+                    )?;
+                }
+
+                writeln!(
+                    f,
+                    r#"
+                    </ul>
+                </td>
+            </tr>
+"#
+                )?;
+            }
+
+            Ok(())
+        };
+
+        if count > 0 {
+            Self::render_table(
+                f,
+                [count],
+                Title::Changes,
+                &format!(
+                    "{count} file(s) changed since the previous sync",
+                    count = Formatted(count)
+                ),
+                data,
+            )?;
+        }
+        Ok(())
+    }
+
     fn gen_link(&self, key: &DocumentKey) -> Option<(String, String)> {
         let label = key.url.clone();
 
@@ -257,6 +416,11 @@ impl HtmlReport<'_> {
                 (
                     match title {
                         Title::Warnings => "text-bg-warning",
+                        Title::Notices
+                        | Title::Changes
+                        | Title::Origins
+                        | Title::Withdrawn
+                        | Title::Supersessions => "text-bg-info",
                         _ => "text-bg-danger",
                     },
                     Formatted(count).to_string(),
@@ -280,9 +444,13 @@ impl HtmlReport<'_> {
         let mut summary = Vec::new();
 
         summary.push(("Total", Formatted(self.result.total).to_string()));
+        if self.result.suppressed > 0 {
+            summary.push(("Suppressed", Formatted(self.result.suppressed).to_string()));
+        }
         if let Some(source) = self.source_url.as_ref().or(self.base_url.as_ref()) {
             summary.push(("Source", source.to_string()));
         }
+        summary.push(("Correlation ID", run_id().to_string()));
 
         Summary(summary).fmt(f)
     }
@@ -290,14 +458,225 @@ impl HtmlReport<'_> {
 
 impl<'r> Display for HtmlReport<'r> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        self.render_total(f)?;
+        match self.navigation {
+            Some(navigation) => navigation.fmt(f)?,
+            None => {
+                self.render_total(f)?;
+                render_key_issues(f, self.result.key_issues)?;
+                render_origins(f, self.result.origins)?;
+                render_lifecycles(f, self.result.lifecycles)?;
+                render_tracking_ids(f, self.result.duplicates)?;
+            }
+        }
         self.render_duplicates(f)?;
         self.render_errors(f)?;
         self.render_warnings(f)?;
+        self.render_notices(f)?;
+        self.render_changes(f)?;
         Ok(())
     }
 }
 
+/// Render the issues of the provider keys, which are not specific to a page of the report.
+pub(super) fn render_key_issues(f: &mut Formatter<'_>, issues: &[KeyIssue]) -> std::fmt::Result {
+    if issues.is_empty() {
+        return Ok(());
+    }
+
+    HtmlReport::title(f, Title::Keys, [issues.len()])?;
+    writeln!(
+        f,
+        "<p>{count} issue(s) with the provider keys, which may cause signatures to fail</p>",
+        count = Formatted(issues.len())
+    )?;
+    writeln!(f, r#"<ul class="list-unstyled">"#)?;
+    for issue in issues {
+        let class = match issue.is_upcoming() {
+            true => "text-warning",
+            false => "text-danger",
+        };
+        writeln!(
+            f,
+            r#"<li class="{class}"><code>{issue}</code></li>"#,
+            issue = html_escape::encode_text(&issue.to_string()),
+        )?;
+    }
+    writeln!(f, "</ul>")?;
+
+    Ok(())
+}
+
+/// Render the number of documents by the aggregator and listed provider they originated from,
+/// which is not specific to a page of the report.
+pub(super) fn render_origins(
+    f: &mut Formatter<'_>,
+    origins: &BTreeMap<DocumentKey, AggregatorOrigin>,
+) -> std::fmt::Result {
+    if origins.is_empty() {
+        return Ok(());
+    }
+
+    let mut counts = BTreeMap::<&AggregatorOrigin, usize>::new();
+    for origin in origins.values() {
+        *counts.entry(origin).or_default() += 1;
+    }
+
+    HtmlReport::title(f, Title::Origins, [counts.len()])?;
+    writeln!(
+        f,
+        "<p>{count} document(s) discovered through aggregators</p>",
+        count = Formatted(origins.len())
+    )?;
+    writeln!(f, r#"<ul class="list-unstyled">"#)?;
+    for (origin, count) in counts {
+        writeln!(
+            f,
+            "<li>{origin}: {count} document(s)</li>",
+            origin = html_escape::encode_text(&origin.to_string()),
+            count = Formatted(count),
+        )?;
+    }
+    writeln!(f, "</ul>")?;
+
+    Ok(())
+}
+
+/// Render the withdrawn documents, and the chains of superseded documents, which are not specific
+/// to a page of the report.
+pub(super) fn render_lifecycles(
+    f: &mut Formatter<'_>,
+    lifecycles: &BTreeMap<Url, Lifecycle>,
+) -> std::fmt::Result {
+    let withdrawn = lifecycles
+        .iter()
+        .filter(|(_, lifecycle)| lifecycle.withdrawn)
+        .collect::<Vec<_>>();
+
+    if !withdrawn.is_empty() {
+        HtmlReport::title(f, Title::Withdrawn, [withdrawn.len()])?;
+        writeln!(f, r#"<ul class="list-unstyled">"#)?;
+        for (url, lifecycle) in withdrawn {
+            writeln!(
+                f,
+                "<li><code>{url}</code>{id}</li>",
+                url = html_escape::encode_text(url.as_str()),
+                id = lifecycle
+                    .id
+                    .as_deref()
+                    .map(|id| format!(" ({})", html_escape::encode_text(id)))
+                    .unwrap_or_default(),
+            )?;
+        }
+        writeln!(f, "</ul>")?;
+    }
+
+    let chains = supersession_chains(lifecycles);
+    if !chains.is_empty() {
+        HtmlReport::title(f, Title::Supersessions, [chains.len()])?;
+        writeln!(f, r#"<ul class="list-unstyled">"#)?;
+        for chain in chains {
+            let chain = chain
+                .iter()
+                .map(|url| format!("<code>{}</code>", html_escape::encode_text(url.as_str())))
+                .collect::<Vec<_>>();
+            writeln!(f, "<li>{}</li>", chain.join(" → "))?;
+        }
+        writeln!(f, "</ul>")?;
+    }
+
+    Ok(())
+}
+
+/// Render tracking IDs used by documents with different content, which are not specific to a
+/// page of the report.
+pub(super) fn render_tracking_ids(
+    f: &mut Formatter<'_>,
+    duplicates: &Duplicates,
+) -> std::fmt::Result {
+    let conflicts = duplicates.conflicting_tracking_ids().collect::<Vec<_>>();
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+
+    HtmlReport::render_table(
+        f,
+        [conflicts.len()],
+        Title::TrackingIds,
+        &format!(
+            "{count} tracking ID(s) used by documents with different content",
+            count = Formatted(conflicts.len()),
+        ),
+        |f| {
+            for (id, documents) in &conflicts {
+                for (key, digest) in *documents {
+                    writeln!(
+                        f,
+                        r#"
+            <tr>
+                <td><code>{label}</code> <small class="text-secondary">{digest}</small></td>
+                <td><code>{id}</code></td>
+            </tr>
+            "#,
+                        label = html_escape::encode_text(&key.url),
+                        digest = html_escape::encode_text(&digest[..digest.len().min(12)]),
+                        id = html_escape::encode_text(id),
+                    )?;
+                }
+            }
+            Ok(())
+        },
+    )
+}
+
+/// Describe the changes of a diff, one line per change
+fn describe_changes(diff: &AdvisoryDiff) -> Vec<String> {
+    let mut result = vec![];
+
+    if diff.previous_version != diff.current_version {
+        result.push(format!(
+            "Version: {} → {}",
+            diff.previous_version.as_deref().unwrap_or("none"),
+            diff.current_version.as_deref().unwrap_or("none"),
+        ));
+    }
+
+    for revision in &diff.revisions {
+        result.push(format!(
+            "Revision {}: {}",
+            revision.number,
+            revision.summary.as_deref().unwrap_or_default()
+        ));
+    }
+
+    for status in &diff.product_status {
+        let mut text = format!("{} {}:", status.vulnerability, status.status);
+        for product in &status.added {
+            text.push_str(&format!(" +{product}"));
+        }
+        for product in &status.removed {
+            text.push_str(&format!(" -{product}"));
+        }
+        result.push(text);
+    }
+
+    for score in &diff.scores {
+        let score_text = |score: Option<f64>| {
+            score
+                .map(|score| score.to_string())
+                .unwrap_or_else(|| "none".to_string())
+        };
+        result.push(format!(
+            "{} {}: score {} → {}",
+            score.vulnerability,
+            score.product,
+            score_text(score.previous),
+            score_text(score.current),
+        ));
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -310,6 +689,13 @@ mod test {
             duplicates: &Default::default(),
             errors: &Default::default(),
             warnings: &Default::default(),
+            notices: &Default::default(),
+            excerpts: &Default::default(),
+            suppressed: 0,
+            changes: &Default::default(),
+            key_issues: &[],
+            origins: &Default::default(),
+            lifecycles: &Default::default(),
         };
         let _output = PathBuf::default();
         let base_url = Some(Url::parse("file:///foo/bar/").expect("example value must parse"));
@@ -317,6 +703,7 @@ mod test {
             result: &details,
             base_url: &base_url,
             source_url: &None,
+            navigation: None,
         };
 
         let (url, _label) = report.link_document(&DocumentKey {
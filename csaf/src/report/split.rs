@@ -0,0 +1,344 @@
+//! Splitting a report into multiple pages
+
+use crate::report::{
+    render::{
+        render_key_issues, render_lifecycles, render_origins, render_tracking_ids, HtmlReport,
+    },
+    DocumentKey, Duplicates, ReportRenderOption, ReportResult,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Formatter},
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+use url::Url;
+use walker_common::{
+    locale::Formatted,
+    report,
+    report::{ReportOptions, Summary},
+    run::run_id,
+};
+
+const TITLE: &str = "CSAF Report";
+
+/// Navigation of a page of a split report
+pub(super) struct Navigation {
+    distribution: Url,
+    /// The number of the page, starting with 1
+    page: usize,
+    pages: usize,
+    index: String,
+    previous: Option<String>,
+    next: Option<String>,
+}
+
+impl Navigation {
+    fn item(f: &mut Formatter<'_>, label: &str, href: Option<&str>) -> std::fmt::Result {
+        match href {
+            Some(href) => writeln!(
+                f,
+                r#"<li class="page-item"><a class="page-link" href="{href}">{label}</a></li>"#,
+                href = html_escape::encode_quoted_attribute(href),
+            ),
+            None => writeln!(
+                f,
+                r#"<li class="page-item disabled"><span class="page-link">{label}</span></li>"#
+            ),
+        }
+    }
+}
+
+impl Display for Navigation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            r#"<h2>{distribution} <span class="badge text-bg-light rounded-pill">{page} / {pages}</span></h2>"#,
+            distribution = html_escape::encode_text(self.distribution.as_str()),
+            page = Formatted(self.page),
+            pages = Formatted(self.pages),
+        )?;
+
+        writeln!(f, r#"<nav><ul class="pagination">"#)?;
+        Self::item(f, "Index", Some(&self.index))?;
+        Self::item(f, "Previous", self.previous.as_deref())?;
+        Self::item(f, "Next", self.next.as_deref())?;
+        writeln!(f, "</ul></nav>")?;
+
+        Ok(())
+    }
+}
+
+/// A distribution, listed on the index page
+struct IndexEntry<'r> {
+    distribution: &'r Url,
+    duplicates: usize,
+    errors: usize,
+    warnings: usize,
+    notices: usize,
+    changes: usize,
+    pages: Vec<String>,
+}
+
+/// The index page of a split report
+struct HtmlIndex<'r> {
+    result: &'r ReportResult<'r>,
+    options: &'r ReportRenderOption,
+    entries: Vec<IndexEntry<'r>>,
+}
+
+impl Display for HtmlIndex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut summary = Vec::new();
+
+        summary.push(("Total", Formatted(self.result.total).to_string()));
+        if self.result.suppressed > 0 {
+            summary.push(("Suppressed", Formatted(self.result.suppressed).to_string()));
+        }
+        if let Some(source) = self
+            .options
+            .source_url
+            .as_ref()
+            .or(self.options.base_url.as_ref())
+        {
+            summary.push(("Source", source.to_string()));
+        }
+        summary.push(("Correlation ID", run_id().to_string()));
+
+        Summary(summary).fmt(f)?;
+        render_key_issues(f, self.result.key_issues)?;
+        render_origins(f, self.result.origins)?;
+        render_lifecycles(f, self.result.lifecycles)?;
+        render_tracking_ids(f, self.result.duplicates)?;
+
+        writeln!(
+            f,
+            r#"
+    <h2>Distributions</h2>
+    <table class="table">
+        <thead>
+            <tr>
+                <th scope="col">Distribution</th>
+                <th scope="col" class="text-end">Duplicates</th>
+                <th scope="col" class="text-end">Errors</th>
+                <th scope="col" class="text-end">Warnings</th>
+                <th scope="col" class="text-end">Notices</th>
+                <th scope="col" class="text-end">Changes</th>
+                <th scope="col">Pages</th>
+            </tr>
+        </thead>
+
+        <tbody>
+"#
+        )?;
+
+        for entry in &self.entries {
+            writeln!(
+                f,
+                r#"
+            <tr>
+                <td><code>{distribution}</code></td>
+                <td class="text-end">{duplicates}</td>
+                <td class="text-end">{errors}</td>
+                <td class="text-end">{warnings}</td>
+                <td class="text-end">{notices}</td>
+                <td class="text-end">{changes}</td>
+                <td>"#,
+                distribution = html_escape::encode_text(entry.distribution.as_str()),
+                duplicates = Formatted(entry.duplicates),
+                errors = Formatted(entry.errors),
+                warnings = Formatted(entry.warnings),
+                notices = Formatted(entry.notices),
+                changes = Formatted(entry.changes),
+            )?;
+
+            for (n, page) in entry.pages.iter().enumerate() {
+                write!(
+                    f,
+                    r#" <a href="{page}">{n}</a>"#,
+                    page = html_escape::encode_quoted_attribute(page),
+                    n = n + 1,
+                )?;
+            }
+
+            writeln!(f, "</td></tr>")?;
+        }
+
+        writeln!(f, "</tbody></table>")?;
+
+        Ok(())
+    }
+}
+
+/// Render the report into an index page, and pages per distribution.
+///
+/// The index page is written to the output file of the options, the pages next to it. Each page
+/// contains up to `page_size` documents. Only the documents of a single page are held in memory
+/// in addition to the result. Returns the paths of all created files.
+pub(super) fn render_split(
+    result: &ReportResult,
+    options: &ReportRenderOption,
+    page_size: usize,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let page_size = page_size.max(1);
+
+    let mut distributions = BTreeMap::<&Url, BTreeSet<&DocumentKey>>::new();
+    for key in result
+        .duplicates
+        .duplicates
+        .keys()
+        .chain(result.errors.keys())
+        .chain(result.warnings.keys())
+        .chain(result.notices.keys())
+        .chain(result.changes.keys())
+    {
+        distributions
+            .entry(&key.distribution_url)
+            .or_default()
+            .insert(key);
+    }
+
+    let index = options
+        .output
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut entries = Vec::with_capacity(distributions.len());
+    let mut files = Vec::new();
+
+    for (n, (distribution, keys)) in distributions.into_iter().enumerate() {
+        let keys = keys.into_iter().collect::<Vec<_>>();
+        let chunks = keys.chunks(page_size).collect::<Vec<_>>();
+        let pages = (0..chunks.len())
+            .map(|page| page_name(&options.output, n + 1, page + 1))
+            .collect::<Vec<_>>();
+
+        for (page, chunk) in chunks.into_iter().enumerate() {
+            let duplicates = Duplicates {
+                duplicates: select(&result.duplicates.duplicates, chunk),
+                known: Default::default(),
+                // rendered on the index page, as they span distributions
+                tracking_ids: Default::default(),
+            };
+            let errors = select(result.errors, chunk);
+            let warnings = select(result.warnings, chunk);
+            let notices = select(result.notices, chunk);
+            let excerpts = select(result.excerpts, chunk);
+            let changes = select(result.changes, chunk);
+
+            let page_result = ReportResult {
+                total: result.total,
+                duplicates: &duplicates,
+                errors: &errors,
+                warnings: &warnings,
+                notices: &notices,
+                excerpts: &excerpts,
+                suppressed: result.suppressed,
+                changes: &changes,
+                key_issues: result.key_issues,
+                origins: result.origins,
+                lifecycles: result.lifecycles,
+            };
+
+            let navigation = Navigation {
+                distribution: distribution.clone(),
+                page: page + 1,
+                pages: pages.len(),
+                index: index.clone(),
+                previous: page.checked_sub(1).map(|previous| pages[previous].clone()),
+                next: pages.get(page + 1).cloned(),
+            };
+
+            let path = options.output.with_file_name(&pages[page]);
+            write_page(
+                &path,
+                &options.report_options,
+                HtmlReport {
+                    result: &page_result,
+                    base_url: &options.base_url,
+                    source_url: &options.source_url,
+                    navigation: Some(&navigation),
+                },
+            )?;
+            files.push(path);
+        }
+
+        entries.push(IndexEntry {
+            distribution,
+            duplicates: count(&result.duplicates.duplicates, &keys, |_| 1),
+            errors: count(result.errors, &keys, |_| 1),
+            warnings: count(result.warnings, &keys, Vec::len),
+            notices: count(result.notices, &keys, Vec::len),
+            changes: count(result.changes, &keys, |_| 1),
+            pages,
+        });
+    }
+
+    write_page(
+        &options.output,
+        &options.report_options,
+        HtmlIndex {
+            result,
+            options,
+            entries,
+        },
+    )?;
+    files.push(options.output.clone());
+
+    Ok(files)
+}
+
+/// The file name of a page, based on the name of the index page
+fn page_name(output: &Path, distribution: usize, page: usize) -> String {
+    let stem = output
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "report".to_string());
+
+    format!("{stem}-{distribution}-{page}.html")
+}
+
+fn select<V: Clone>(
+    map: &BTreeMap<DocumentKey, V>,
+    keys: &[&DocumentKey],
+) -> BTreeMap<DocumentKey, V> {
+    keys.iter()
+        .filter_map(|key| map.get(*key).map(|value| ((*key).clone(), value.clone())))
+        .collect()
+}
+
+fn count<V>(
+    map: &BTreeMap<DocumentKey, V>,
+    keys: &[&DocumentKey],
+    f: impl Fn(&V) -> usize,
+) -> usize {
+    keys.iter().filter_map(|key| map.get(*key)).map(f).sum()
+}
+
+fn write_page(
+    path: &Path,
+    report_options: &ReportOptions,
+    content: impl Display,
+) -> anyhow::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    report::render(&mut out, TITLE, content, report_options)?;
+    out.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_page_name() {
+        assert_eq!(
+            page_name(Path::new("out/report.html"), 2, 3),
+            "report-2-3.html"
+        );
+        assert_eq!(page_name(Path::new(""), 1, 1), "report-1-1.html");
+    }
+}
@@ -0,0 +1,160 @@
+use crate::diff::AdvisoryDiff;
+use crate::discover::{AggregatorOrigin, DiscoveredAdvisory};
+use crate::model::lifecycle::Lifecycle;
+use crate::report::DocumentKey;
+use crate::verification::check::Finding;
+use anyhow::Context;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind};
+use std::path::Path;
+use time::OffsetDateTime;
+use url::Url;
+
+/// The findings of a previous report run, allowing to only check changed documents.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportState {
+    /// The checks which were performed, a state of different checks cannot be reused
+    pub checks: BTreeSet<String>,
+    /// The state of each document, by its URL
+    pub documents: BTreeMap<Url, DocumentState>,
+}
+
+/// The outcome of checking a document, before applying a baseline
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentState {
+    /// The modification timestamp of the document, when it was processed
+    #[serde(with = "time::serde::rfc3339")]
+    pub modified: OffsetDateTime,
+    /// The key, the document is reported under
+    pub key: DocumentKey,
+    /// The failures, by check
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub failures: BTreeMap<String, Vec<Finding>>,
+    /// The notices, by check
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub notices: BTreeMap<String, Vec<Finding>>,
+    /// Excerpts of the document, by the JSON pointer of a finding
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub excerpts: BTreeMap<String, String>,
+    /// Changes compared to the previously stored version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change: Option<AdvisoryDiff>,
+    /// The aggregated severity of the document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+    /// The tracking ID of the document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracking_id: Option<String>,
+    /// The digest of the content, see [`crate::report::content_digest`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    /// The aggregator listing the provider, if discovered by walking an aggregator
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<AggregatorOrigin>,
+    /// The lifecycle information, if the document was withdrawn or superseded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<Lifecycle>,
+}
+
+impl DocumentState {
+    pub fn new(advisory: &DiscoveredAdvisory, key: DocumentKey) -> Self {
+        Self {
+            modified: advisory.modified.into(),
+            key,
+            failures: Default::default(),
+            notices: Default::default(),
+            excerpts: Default::default(),
+            change: None,
+            severity: None,
+            tracking_id: None,
+            digest: None,
+            origin: advisory.aggregator.as_deref().cloned(),
+            lifecycle: None,
+        }
+    }
+}
+
+impl ReportState {
+    pub fn new(checks: BTreeSet<String>) -> Self {
+        Self {
+            checks,
+            documents: Default::default(),
+        }
+    }
+
+    /// Load the state from a JSON file, returning [`None`] if the file doesn't exist.
+    pub fn load_from(path: &Path) -> anyhow::Result<Option<Self>> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Failed to open report state: {}", path.display()))
+            }
+        };
+
+        serde_json::from_reader(BufReader::new(file))
+            .map(Some)
+            .with_context(|| format!("Failed to parse report state: {}", path.display()))
+    }
+
+    /// Store the state to a JSON file.
+    pub fn store_to(&self, path: &Path) -> anyhow::Result<()> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create report state: {}", path.display()))?;
+        serde_json::to_writer(BufWriter::new(file), self)
+            .with_context(|| format!("Failed to write report state: {}", path.display()))
+    }
+
+    /// Get the previous state of a document, if it didn't change since.
+    pub fn unchanged(&self, advisory: &DiscoveredAdvisory) -> Option<&DocumentState> {
+        self.documents
+            .get(&advisory.url)
+            .filter(|state| state.modified == OffsetDateTime::from(advisory.modified))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::discover::DistributionContext;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+
+    fn advisory(modified: SystemTime) -> DiscoveredAdvisory {
+        let url = Url::parse("https://example.com/advisories/").expect("example value must parse");
+        DiscoveredAdvisory {
+            context: Arc::new(DistributionContext::Directory(url.clone())),
+            url: url.join("a.json").expect("example value must parse"),
+            modified,
+            retrieval_url: None,
+            rolie: None,
+            aggregator: None,
+        }
+    }
+
+    #[test]
+    fn test_unchanged() {
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let advisory = advisory(modified);
+
+        let mut state = ReportState::new(BTreeSet::from(["check".to_string()]));
+        state.documents.insert(
+            advisory.url.clone(),
+            DocumentState::new(&advisory, DocumentKey::for_document(&advisory)),
+        );
+
+        // survive a round trip
+
+        let json = serde_json::to_vec(&state).expect("state must serialize");
+        let state: ReportState = serde_json::from_slice(&json).expect("state must deserialize");
+
+        assert!(state.unchanged(&advisory).is_some());
+        assert!(state
+            .unchanged(&self::advisory(modified + Duration::from_secs(1)))
+            .is_none());
+    }
+}
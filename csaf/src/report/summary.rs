@@ -0,0 +1,165 @@
+use crate::report::ReportResult;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use walker_common::run::run_id;
+
+/// The severity used for documents without an aggregate severity
+pub const SEVERITY_UNKNOWN: &str = "unknown";
+
+/// Aggregated statistics of a run, e.g. for sending a single notification
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunSummary {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// The correlation ID of the run
+    pub correlation_id: String,
+    /// Total number of documents
+    pub total: usize,
+    /// Number of documents, by their (lower-cased) aggregate severity
+    pub severities: BTreeMap<String, usize>,
+    /// Number of documents which changed since the previous sync
+    pub changed: usize,
+    /// Number of documents which failed retrieval or validation
+    pub errors: usize,
+    /// Number of documents with warnings
+    pub warnings: usize,
+    /// Number of documents with notices
+    pub notices: usize,
+    /// Number of findings suppressed by a baseline
+    pub suppressed: usize,
+    /// Number of duplicate URLs
+    pub duplicates: usize,
+    /// Number of tracking IDs used by documents with different content
+    #[serde(default)]
+    pub conflicting_tracking_ids: usize,
+    /// Number of failed documents, by distribution
+    pub provider_issues: BTreeMap<String, usize>,
+    /// Issues of the provider keys, like being expired or revoked
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub key_issues: Vec<String>,
+}
+
+impl RunSummary {
+    pub fn new(
+        result: &ReportResult,
+        severities: BTreeMap<String, usize>,
+        source: Option<String>,
+    ) -> Self {
+        let mut provider_issues = BTreeMap::<String, usize>::new();
+        for key in result.errors.keys() {
+            *provider_issues
+                .entry(key.distribution_url.to_string())
+                .or_default() += 1;
+        }
+
+        Self {
+            source,
+            correlation_id: run_id().to_string(),
+            total: result.total,
+            severities,
+            changed: result.changes.len(),
+            errors: result.errors.len(),
+            warnings: result.warnings.len(),
+            notices: result.notices.len(),
+            suppressed: result.suppressed,
+            duplicates: result.duplicates.duplicates.len(),
+            conflicting_tracking_ids: result.duplicates.conflicting_tracking_ids().count(),
+            provider_issues,
+            key_issues: result.key_issues.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+impl Display for RunSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.source {
+            Some(source) => writeln!(f, "CSAF run summary: {source}")?,
+            None => writeln!(f, "CSAF run summary")?,
+        }
+
+        writeln!(f, "Correlation ID: {}", self.correlation_id)?;
+        writeln!(f, "Documents: {}", self.total)?;
+        if !self.severities.is_empty() {
+            let severities = self
+                .severities
+                .iter()
+                .map(|(severity, count)| format!("{severity}: {count}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(f, "By severity: {severities}")?;
+        }
+        writeln!(f, "Changed: {}", self.changed)?;
+        writeln!(f, "Validation failures: {}", self.errors)?;
+        writeln!(
+            f,
+            "Documents with warnings: {}, with notices: {}",
+            self.warnings, self.notices
+        )?;
+        if self.suppressed > 0 {
+            writeln!(f, "Suppressed findings: {}", self.suppressed)?;
+        }
+        if self.duplicates > 0 {
+            writeln!(f, "Duplicate URLs: {}", self.duplicates)?;
+        }
+        if self.conflicting_tracking_ids > 0 {
+            writeln!(
+                f,
+                "Conflicting tracking IDs: {}",
+                self.conflicting_tracking_ids
+            )?;
+        }
+        for (distribution, count) in &self.provider_issues {
+            writeln!(
+                f,
+                "Provider issues: {count} failed document(s) in {distribution}"
+            )?;
+        }
+        for issue in &self.key_issues {
+            writeln!(f, "Key issue: {issue}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct SeverityDocument {
+    document: SeverityMetadata,
+}
+
+#[derive(Deserialize)]
+struct SeverityMetadata {
+    #[serde(default)]
+    aggregate_severity: Option<AggregateSeverity>,
+}
+
+#[derive(Deserialize)]
+struct AggregateSeverity {
+    text: String,
+}
+
+/// Get the (lower-cased) aggregate severity of a document, or [`SEVERITY_UNKNOWN`].
+pub fn aggregate_severity(data: &[u8]) -> String {
+    serde_json::from_slice::<SeverityDocument>(data)
+        .ok()
+        .and_then(|document| document.document.aggregate_severity)
+        .map(|severity| severity.text.to_lowercase())
+        .unwrap_or_else(|| SEVERITY_UNKNOWN.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_severity() {
+        assert_eq!(
+            aggregate_severity(br#"{"document": {"aggregate_severity": {"text": "Important"}}}"#),
+            "important"
+        );
+        assert_eq!(aggregate_severity(br#"{"document": {}}"#), SEVERITY_UNKNOWN);
+        assert_eq!(aggregate_severity(b"garbage"), SEVERITY_UNKNOWN);
+    }
+}
@@ -0,0 +1,247 @@
+//! Pruning stored advisories, retaining only the recent history
+//!
+//! Works on a store, as written by [`crate::visitors::store::StoreVisitor`]. Advisories are
+//! ordered by their initial release date (oldest first), and then by their path, so that the
+//! outcome is deterministic for the same store content.
+
+use crate::visitors::store::{changes_name, DIR_METADATA};
+use anyhow::Context;
+use std::path::{Path, PathBuf};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use walkdir::WalkDir;
+use walker_common::changes::ChangesFile;
+
+/// Files stored alongside an advisory
+const COMPANIONS: &[&str] = &[
+    ".asc",
+    ".sig",
+    ".sha256",
+    ".sha512",
+    ".metadata",
+    ".diff",
+    ".origin",
+    ".withdrawn",
+];
+
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Retain advisories initially released in the last N calendar years, including the current
+    pub years: Option<u32>,
+    /// Maximum total size (in bytes) of retained advisories, including signatures and digests
+    pub max_size: Option<u64>,
+}
+
+impl RetentionPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn years(mut self, years: impl Into<Option<u32>>) -> Self {
+        self.years = years.into();
+        self
+    }
+
+    pub fn max_size(mut self, max_size: impl Into<Option<u64>>) -> Self {
+        self.max_size = max_size.into();
+        self
+    }
+
+    /// Check if the policy would prune anything at all
+    pub fn is_empty(&self) -> bool {
+        self.years.is_none() && self.max_size.is_none()
+    }
+}
+
+/// A stored advisory
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoredAdvisory {
+    /// The path of the advisory document
+    pub path: PathBuf,
+    /// The initial release date, if it could be read from the document
+    pub initial_release_date: Option<OffsetDateTime>,
+    /// The size of the document, including its companion files
+    pub size: u64,
+}
+
+/// The outcome of applying a retention policy
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RetentionReport {
+    /// Advisories which were (or, in dry-run mode, would be) removed
+    pub removed: Vec<StoredAdvisory>,
+    /// Number of retained advisories
+    pub retained: usize,
+    /// Total size of retained advisories
+    pub retained_size: u64,
+}
+
+impl RetentionReport {
+    /// Total size of removed advisories
+    pub fn removed_size(&self) -> u64 {
+        self.removed.iter().map(|advisory| advisory.size).sum()
+    }
+}
+
+/// Apply a retention policy to a store.
+///
+/// Advisories without a readable initial release date (e.g. encrypted ones) are never pruned by
+/// age, and are considered the most recent when pruning by size. In dry-run mode, nothing gets
+/// removed, but the report lists what would have been.
+///
+/// If the store has a `changes.csv` file, removed advisories will be removed from it as well.
+pub fn apply(
+    base: &Path,
+    policy: &RetentionPolicy,
+    now: OffsetDateTime,
+    dry_run: bool,
+) -> anyhow::Result<RetentionReport> {
+    let mut advisories = scan(base)?;
+
+    // oldest first, unknown dates last, then by path
+    advisories.sort_by(|a, b| {
+        match (a.initial_release_date, b.initial_release_date) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }
+        .then_with(|| a.path.cmp(&b.path))
+    });
+
+    let mut report = RetentionReport::default();
+    let mut retained = vec![];
+
+    let min_year = policy
+        .years
+        .map(|years| now.year() - (years as i32).saturating_sub(1));
+
+    for advisory in advisories {
+        match (min_year, advisory.initial_release_date) {
+            (Some(min_year), Some(date)) if date.year() < min_year => report.removed.push(advisory),
+            _ => retained.push(advisory),
+        }
+    }
+
+    let mut total: u64 = retained.iter().map(|advisory| advisory.size).sum();
+    if let Some(max_size) = policy.max_size {
+        let mut retained_iter = std::mem::take(&mut retained).into_iter();
+        for advisory in retained_iter.by_ref() {
+            if total <= max_size {
+                retained.push(advisory);
+                break;
+            }
+            total -= advisory.size;
+            report.removed.push(advisory);
+        }
+        retained.extend(retained_iter);
+    }
+
+    report.retained = retained.len();
+    report.retained_size = total;
+
+    if !dry_run {
+        for advisory in &report.removed {
+            remove(&advisory.path)?;
+        }
+
+        if base.join("changes.csv").is_file() && !report.removed.is_empty() {
+            let mut changes = ChangesFile::load(base)?;
+            for advisory in &report.removed {
+                if let Some(name) = changes_name(base, &advisory.path) {
+                    changes.remove(&name);
+                }
+            }
+            changes.write(base)?;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Scan the store for advisories, skipping the metadata directory.
+fn scan(base: &Path) -> anyhow::Result<Vec<StoredAdvisory>> {
+    let mut result = vec![];
+
+    for entry in WalkDir::new(base)
+        .min_depth(1)
+        .into_iter()
+        // skip metadata directories, including those of TLP partitions
+        .filter_entry(|entry| !(entry.file_type().is_dir() && entry.file_name() == DIR_METADATA))
+    {
+        let entry = entry?;
+        if !entry.file_type().is_file() || !entry.file_name().to_string_lossy().ends_with(".json") {
+            continue;
+        }
+
+        let path = entry.into_path();
+        let initial_release_date = initial_release_date(&path);
+
+        let mut size = path.metadata()?.len();
+        for companion in COMPANIONS {
+            if let Ok(metadata) = companion_path(&path, companion).metadata() {
+                size += metadata.len();
+            }
+        }
+
+        result.push(StoredAdvisory {
+            path,
+            initial_release_date,
+            size,
+        });
+    }
+
+    Ok(result)
+}
+
+fn initial_release_date(path: &Path) -> Option<OffsetDateTime> {
+    let data = std::fs::read(path).ok()?;
+    let document = serde_json::from_slice::<serde_json::Value>(&data).ok()?;
+    let date = document["document"]["tracking"]["initial_release_date"].as_str()?;
+    OffsetDateTime::parse(date, &Rfc3339).ok()
+}
+
+fn companion_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    path.into()
+}
+
+/// Remove a stored document and its companion files, ignoring files which don't exist.
+///
+/// Returns `true` if the document existed.
+pub(crate) fn remove_stored(path: &Path) -> anyhow::Result<bool> {
+    let existed = match std::fs::remove_file(path) {
+        Ok(()) => true,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => false,
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to remove: {}", path.display()))
+        }
+    };
+
+    remove_companions(path)?;
+
+    Ok(existed)
+}
+
+fn remove(path: &Path) -> anyhow::Result<()> {
+    log::debug!("Removing: {}", path.display());
+
+    std::fs::remove_file(path).with_context(|| format!("Failed to remove: {}", path.display()))?;
+
+    remove_companions(path)
+}
+
+fn remove_companions(path: &Path) -> anyhow::Result<()> {
+    for companion in COMPANIONS {
+        let path = companion_path(path, companion);
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => {
+                return Err(err).with_context(|| format!("Failed to remove: {}", path.display()))
+            }
+        }
+    }
+
+    Ok(())
+}
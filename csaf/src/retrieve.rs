@@ -2,6 +2,7 @@
 
 use crate::{
     discover::{AsDiscovered, DiscoveredAdvisory, DiscoveredContext, DiscoveredVisitor},
+    model::metadata::TlpLabel,
     source::Source,
 };
 use bytes::Bytes;
@@ -10,9 +11,12 @@ use sha2::{Sha256, Sha512};
 use std::fmt::Debug;
 use std::future::Future;
 use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
 use url::Url;
 use walker_common::{
+    budget::MemoryBudget,
     retrieve::{RetrievalMetadata, RetrievedDigest},
+    stage,
     utils::{openpgp::PublicKey, url::Urlify},
     validate::source::{KeySource, KeySourceError},
 };
@@ -37,6 +41,23 @@ pub struct RetrievedAdvisory {
     pub metadata: RetrievalMetadata,
 }
 
+impl RetrievedAdvisory {
+    /// Get the TLP label of the advisory, lower-cased.
+    ///
+    /// The label of the ROLIE feed is preferred, only parsing the document if it is unknown.
+    pub fn tlp_label(&self) -> Option<String> {
+        match self.context.tlp_label() {
+            None | Some(TlpLabel::Unlabeled) => {}
+            Some(label) => return Some(label.as_str().to_lowercase()),
+        }
+
+        let document = serde_json::from_slice::<serde_json::Value>(&self.data).ok()?;
+        document["document"]["distribution"]["tlp"]["label"]
+            .as_str()
+            .map(str::to_lowercase)
+    }
+}
+
 impl Urlify for RetrievedAdvisory {
     fn url(&self) -> &Url {
         &self.url
@@ -85,12 +106,24 @@ pub enum RetrievalError {
         code: StatusCode,
         discovered: DiscoveredAdvisory,
     },
+    #[error("Skipped retrieving: {reason}")]
+    Skipped {
+        reason: String,
+        discovered: DiscoveredAdvisory,
+    },
+    #[error("Cross-check with canonical provider failed: {reason}")]
+    CrossCheck {
+        reason: String,
+        discovered: DiscoveredAdvisory,
+    },
 }
 
 impl RetrievalError {
     pub fn discovered(&self) -> &DiscoveredAdvisory {
         match self {
             Self::InvalidResponse { discovered, .. } => discovered,
+            Self::Skipped { discovered, .. } => discovered,
+            Self::CrossCheck { discovered, .. } => discovered,
         }
     }
 }
@@ -99,6 +132,8 @@ impl Urlify for RetrievalError {
     fn url(&self) -> &Url {
         match self {
             Self::InvalidResponse { discovered, .. } => &discovered.url,
+            Self::Skipped { discovered, .. } => &discovered.url,
+            Self::CrossCheck { discovered, .. } => &discovered.url,
         }
     }
 }
@@ -160,6 +195,8 @@ where
 pub struct RetrievingVisitor<V: RetrievedVisitor, S: Source + KeySource> {
     visitor: V,
     source: S,
+    budget: Option<Arc<MemoryBudget>>,
+    allowed_client_errors: Vec<u16>,
 }
 
 impl<V, S> RetrievingVisitor<V, S>
@@ -168,7 +205,29 @@ where
     S: Source + KeySource,
 {
     pub fn new(source: S, visitor: V) -> Self {
-        Self { visitor, source }
+        Self {
+            visitor,
+            source,
+            budget: None,
+            allowed_client_errors: vec![],
+        }
+    }
+
+    /// Wait for a shared memory budget before retrieving a document, and reserve its size until
+    /// it was processed by the next visitor.
+    pub fn with_memory_budget(mut self, budget: impl Into<Option<Arc<MemoryBudget>>>) -> Self {
+        self.budget = budget.into();
+        self
+    }
+
+    /// Only fail retrieving the document for these HTTP client errors (e.g. `404`), instead of
+    /// failing the walk.
+    pub fn with_allowed_client_errors<I>(mut self, allowed_client_errors: I) -> Self
+    where
+        I: IntoIterator<Item = u16>,
+    {
+        self.allowed_client_errors = Vec::from_iter(allowed_client_errors);
+        self
     }
 }
 
@@ -210,7 +269,10 @@ where
             );
         }
 
-        log::info!(
+        tracing::info!(
+            target: stage::RETRIEVE,
+            stage = "retrieve",
+            keys = keys.len(),
             "Loaded {} public key{}",
             keys.len(),
             (keys.len() != 1).then_some("s").unwrap_or_default()
@@ -238,14 +300,76 @@ where
         context: &Self::Context,
         discovered: DiscoveredAdvisory,
     ) -> Result<(), Self::Error> {
-        let advisory = self
-            .source
-            .load_advisory(discovered)
-            .await
-            .map_err(Error::Source)?;
+        let url = &discovered.url;
+        let distribution = discovered.context.url();
+
+        if let Some(budget) = &self.budget {
+            budget.ready().await;
+        }
+
+        let result = match self.source.load_advisory(discovered.clone()).await {
+            Ok(advisory) => {
+                tracing::debug!(
+                    target: stage::RETRIEVE,
+                    stage = "retrieve",
+                    url = %url,
+                    distribution = %distribution,
+                    outcome = "retrieved",
+                    "Retrieved advisory"
+                );
+                Ok(advisory)
+            }
+            Err(err) => match S::skip_reason(&err) {
+                Some(reason) => {
+                    tracing::info!(
+                        target: stage::RETRIEVE,
+                        stage = "retrieve",
+                        url = %url,
+                        distribution = %distribution,
+                        outcome = "skipped",
+                        reason = %reason,
+                        "Skipped advisory"
+                    );
+                    Err(RetrievalError::Skipped { reason, discovered })
+                }
+                None => match S::status(&err).filter(|code| {
+                    code.is_client_error() && self.allowed_client_errors.contains(&code.as_u16())
+                }) {
+                    Some(code) => {
+                        tracing::info!(
+                            target: stage::RETRIEVE,
+                            stage = "retrieve",
+                            url = %url,
+                            distribution = %distribution,
+                            outcome = "failed",
+                            code = %code,
+                            "Failed to retrieve advisory, with an allowed client error"
+                        );
+                        Err(RetrievalError::InvalidResponse { code, discovered })
+                    }
+                    None => {
+                        tracing::warn!(
+                            target: stage::RETRIEVE,
+                            stage = "retrieve",
+                            url = %url,
+                            distribution = %distribution,
+                            outcome = "failed",
+                            error = %err,
+                            "Failed to retrieve advisory"
+                        );
+                        return Err(Error::Source(err));
+                    }
+                },
+            },
+        };
+
+        let _reservation = match (&self.budget, &result) {
+            (Some(budget), Ok(retrieved)) => Some(budget.reserve(retrieved.data.len())),
+            _ => None,
+        };
 
         self.visitor
-            .visit_advisory(context, Ok(advisory))
+            .visit_advisory(context, result)
             .await
             .map_err(Error::Visitor)?;
 
@@ -2,7 +2,7 @@ mod roliefeed;
 
 pub use roliefeed::*;
 
-use crate::source::HttpSourceError;
+use crate::{discover::RolieEntry, source::HttpSourceError};
 use time::OffsetDateTime;
 use url::{ParseError, Url};
 use walker_common::fetcher::Json;
@@ -35,6 +35,9 @@ pub struct SourceFile {
     /// The timestamp of the last change
     #[serde(with = "time::serde::iso8601")]
     pub timestamp: OffsetDateTime,
+    /// Metadata of the ROLIE entry
+    #[serde(skip)]
+    pub entry: Option<RolieEntry>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize)]
@@ -45,19 +48,59 @@ pub struct RolieSource {
 
 impl RolieSource {
     pub async fn retrieve(fetcher: &Fetcher, base_url: Url) -> Result<Self, Error> {
-        let mut files = vec![];
         let Json(result) = fetcher.fetch::<Json<RolieFeed>>(base_url).await?;
-        for url in result.feed.entry {
-            for link in url.link {
+        Ok(Self::from_feed(result))
+    }
+
+    /// Parse the content of a ROLIE feed.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_feed(serde_json::from_slice(data)?))
+    }
+
+    /// Collect the files of a ROLIE feed.
+    pub fn from_feed(feed: RolieFeed) -> Self {
+        let mut files = vec![];
+        for entry in feed.feed.entry {
+            let mut signature = None;
+            let mut sha256 = None;
+            let mut sha512 = None;
+
+            for link in &entry.link {
+                let target = match (link.rel.as_str(), link.href.rsplit_once('.')) {
+                    ("signature", _) => &mut signature,
+                    ("hash", Some((_, "sha256"))) => &mut sha256,
+                    ("hash", Some((_, "sha512"))) => &mut sha512,
+                    _ => continue,
+                };
+                match Url::parse(&link.href) {
+                    Ok(url) => *target = Some(url),
+                    Err(err) => log::info!("Ignoring invalid link '{}': {err}", link.href),
+                }
+            }
+
+            for link in entry
+                .link
+                .into_iter()
+                .filter(|link| link.rel != "signature" && link.rel != "hash")
+            {
                 files.push(SourceFile {
                     file: link.href,
-                    timestamp: url.updated,
+                    timestamp: entry.updated,
+                    entry: Some(RolieEntry {
+                        title: entry.title.clone(),
+                        published: entry.published,
+                        updated: entry.updated,
+                        content_length: link.length,
+                        signature: signature.clone(),
+                        sha256: sha256.clone(),
+                        sha512: sha512.clone(),
+                    }),
                 })
             }
         }
 
         log::debug!("found {:?} files", files.len());
 
-        Ok(Self { files })
+        Self { files }
     }
 }
@@ -71,4 +71,7 @@ pub struct Link {
     pub rel: String,
 
     pub href: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<u64>,
 }
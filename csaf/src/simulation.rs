@@ -0,0 +1,491 @@
+//! Generating a simulated provider, for load testing
+//!
+//! Creates the files of a provider with a configurable number of synthetic advisories (and
+//! sizes), with valid digests and signatures, listed in a `changes.csv` file and a ROLIE feed.
+//! The files are written into a directory, which can be served by any HTTP server, using the
+//! directory as the document root. Enabled by the `simulation` feature.
+//!
+//! Besides the signatures, the generated files only depend on the options. Using the same seed
+//! creates the same advisories, making benchmarks of different walker configurations comparable.
+
+use crate::model::metadata::{
+    Distribution, Feed, Key, ProviderMetadata, Publisher, Role, Rolie, TlpLabel,
+};
+use crate::rolie::{self, Content, Entry, Format, Link, RolieFeed};
+use anyhow::Context;
+use chrono::{TimeZone, Utc};
+use sequoia_openpgp::{armor::Kind, cert::CertBuilder, serialize::SerializeInto};
+use sha2::{Digest, Sha256, Sha512};
+use std::io::Write;
+use std::path::Path;
+use time::{format_description::well_known::Rfc3339, Duration, Month, OffsetDateTime};
+use url::Url;
+use walker_common::{changes::ChangesFile, signing::Signing, utils::hex::Hex};
+
+/// The directory of the provider metadata, relative to the base URL
+const WELL_KNOWN: &str = ".well-known/csaf/";
+/// The directory of the advisories, relative to [`WELL_KNOWN`]
+const DISTRIBUTION: &str = "white/";
+const FILLER: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. ";
+
+/// The distribution used for listing the advisories in the provider metadata
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SimulatedDistribution {
+    /// A directory distribution, with a `changes.csv` file
+    Directory,
+    /// A ROLIE feed
+    #[default]
+    Rolie,
+}
+
+/// Options for generating a simulated provider
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimulationOptions {
+    /// The number of advisories
+    pub advisories: usize,
+    /// The minimum size of an advisory, in bytes
+    pub min_size: usize,
+    /// The maximum size of an advisory, in bytes
+    pub max_size: usize,
+    /// The seed for choosing the sizes of the advisories
+    pub seed: u64,
+    /// Sign the advisories, using a generated key
+    pub signed: bool,
+    /// The distribution listed in the provider metadata
+    pub distribution: SimulatedDistribution,
+}
+
+impl Default for SimulationOptions {
+    fn default() -> Self {
+        Self {
+            advisories: 100,
+            min_size: 4 * 1024,
+            max_size: 64 * 1024,
+            seed: 0,
+            signed: true,
+            distribution: Default::default(),
+        }
+    }
+}
+
+impl SimulationOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advisories(mut self, advisories: usize) -> Self {
+        self.advisories = advisories;
+        self
+    }
+
+    /// Set the range of sizes of the advisories, in bytes.
+    pub fn sizes(mut self, min_size: usize, max_size: usize) -> Self {
+        self.min_size = min_size;
+        self.max_size = max_size;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn signed(mut self, signed: bool) -> Self {
+        self.signed = signed;
+        self
+    }
+
+    pub fn distribution(mut self, distribution: SimulatedDistribution) -> Self {
+        self.distribution = distribution;
+        self
+    }
+}
+
+/// The outcome of generating a simulated provider
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimulatedProvider {
+    /// The URL of the provider metadata
+    pub metadata_url: Url,
+    /// The number of generated advisories
+    pub advisories: usize,
+    /// The total size of the generated advisories, in bytes
+    pub bytes: u64,
+    /// The fingerprint of the signing key, if the advisories were signed
+    pub fingerprint: Option<String>,
+}
+
+/// Generate a simulated provider, served at the base URL, into the target directory.
+///
+/// The target directory is the document root, the provider metadata is written to
+/// `.well-known/csaf/provider-metadata.json`.
+pub fn generate(
+    base: &Url,
+    target: &Path,
+    options: &SimulationOptions,
+) -> anyhow::Result<SimulatedProvider> {
+    anyhow::ensure!(
+        options.min_size <= options.max_size,
+        "Minimum size must not exceed the maximum size"
+    );
+
+    let well_known = base.join(WELL_KNOWN)?;
+    let directory_url = well_known.join(DISTRIBUTION)?;
+    let feed_url = directory_url.join("feed.json")?;
+    let metadata_url = well_known.join("provider-metadata.json")?;
+
+    let metadata_dir = target.join(WELL_KNOWN);
+    let directory = metadata_dir.join(DISTRIBUTION);
+    std::fs::create_dir_all(&directory)
+        .with_context(|| format!("Failed to create directory: {}", directory.display()))?;
+
+    let (signing, key) = match options.signed {
+        true => {
+            let (signing, fingerprint, key) = generate_key()?;
+            write(&metadata_dir.join("key.asc"), &key)?;
+            let key = Key {
+                fingerprint: Some(fingerprint),
+                url: well_known.join("key.asc")?,
+            };
+            (Some(signing), Some(key))
+        }
+        false => (None, None),
+    };
+
+    let start = time::Date::from_calendar_date(2024, Month::January, 1)?
+        .midnight()
+        .assume_utc();
+    let mut random = Random(options.seed);
+    let mut changes = ChangesFile::default();
+    let mut entries = Vec::with_capacity(options.advisories);
+    let mut bytes = 0u64;
+
+    for n in 0..options.advisories {
+        let released = start + Duration::hours(n as i64);
+        let id = format!("SIM-{}-{:05}", released.year(), n + 1);
+        let file = format!("{}/{}.json", released.year(), id.to_lowercase());
+        let size = match options.max_size - options.min_size {
+            0 => options.min_size,
+            range => options.min_size + (random.next_u64() % (range as u64 + 1)) as usize,
+        };
+
+        let data = advisory(&id, n, released, size)?;
+        bytes += data.len() as u64;
+
+        let path = directory.join(&file);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        let name = format!("{}.json", id.to_lowercase());
+        write(&path, &data)?;
+        write(
+            &sidecar(&path, "sha256"),
+            format!("{}  {name}\n", Hex(&Sha256::digest(&data)).to_lower()).as_bytes(),
+        )?;
+        write(
+            &sidecar(&path, "sha512"),
+            format!("{}  {name}\n", Hex(&Sha512::digest(&data)).to_lower()).as_bytes(),
+        )?;
+        if let Some(signing) = &signing {
+            write(&sidecar(&path, "asc"), &signing.sign_detached(&data)?)?;
+        }
+
+        changes.insert(file.clone(), released);
+        entries.push(entry(
+            &directory_url.join(&file)?,
+            &id,
+            released,
+            &data,
+            options.signed,
+        ));
+    }
+
+    changes.write(&directory)?;
+
+    let updated = match options.advisories {
+        0 => start,
+        n => start + Duration::hours(n as i64 - 1),
+    };
+
+    let feed = RolieFeed {
+        feed: rolie::Feed {
+            entry: entries,
+            link: vec![Link {
+                rel: "self".to_string(),
+                href: feed_url.to_string(),
+                length: None,
+            }],
+            id: "simulated-white".to_string(),
+            title: "Simulated provider - TLP:WHITE".to_string(),
+            category: vec![],
+            updated,
+        },
+    };
+    write(&directory.join("feed.json"), &serde_json::to_vec(&feed)?)?;
+
+    let distribution = match options.distribution {
+        SimulatedDistribution::Directory => Distribution {
+            directory_url: Some(directory_url),
+            rolie: None,
+        },
+        SimulatedDistribution::Rolie => Distribution {
+            directory_url: None,
+            rolie: Some(Rolie {
+                categories: vec![],
+                feeds: vec![Feed {
+                    summary: Some("Simulated advisories".to_string()),
+                    tlp_label: TlpLabel::White,
+                    url: feed_url,
+                }],
+                services: vec![],
+            }),
+        },
+    };
+
+    let metadata = ProviderMetadata {
+        canonical_url: metadata_url.clone(),
+        distributions: vec![distribution],
+        last_updated: Utc
+            .timestamp_opt(updated.unix_timestamp(), 0)
+            .single()
+            .context("Invalid timestamp")?,
+        list_on_csaf_aggregators: false,
+        metadata_version: "2.0".to_string(),
+        mirror_on_csaf_aggregators: false,
+        public_openpgp_keys: key.iter().cloned().collect(),
+        publisher: Publisher {
+            category: "vendor".to_string(),
+            contact_details: "security@example.com".to_string(),
+            issuing_authority: None,
+            name: "Simulated provider".to_string(),
+            namespace: base.to_string(),
+        },
+        role: Role::Provider,
+    };
+    write(
+        &metadata_dir.join("provider-metadata.json"),
+        &serde_json::to_vec_pretty(&metadata)?,
+    )?;
+
+    Ok(SimulatedProvider {
+        metadata_url,
+        advisories: options.advisories,
+        bytes,
+        fingerprint: key.and_then(|key| key.fingerprint),
+    })
+}
+
+/// Create an advisory, padded to the requested size.
+fn advisory(id: &str, n: usize, released: OffsetDateTime, size: usize) -> anyhow::Result<Vec<u8>> {
+    let date = released.format(&Rfc3339)?;
+    let mut document = serde_json::json!({
+        "document": {
+            "category": "csaf_base",
+            "csaf_version": "2.0",
+            "distribution": {
+                "tlp": { "label": "WHITE" }
+            },
+            "notes": [{
+                "category": "general",
+                "title": "Padding",
+                "text": ""
+            }],
+            "publisher": {
+                "category": "vendor",
+                "name": "Simulated provider",
+                "namespace": "https://example.com"
+            },
+            "title": format!("Simulated advisory {id}"),
+            "tracking": {
+                "current_release_date": date,
+                "id": id,
+                "initial_release_date": date,
+                "revision_history": [{
+                    "date": date,
+                    "number": "1",
+                    "summary": "Initial version"
+                }],
+                "status": "final",
+                "version": "1"
+            }
+        },
+        "product_tree": {
+            "full_product_names": [{
+                "name": "Simulated product",
+                "product_id": "SIM-PRODUCT-1"
+            }]
+        },
+        "vulnerabilities": [{
+            "cve": format!("CVE-{}-{}", released.year(), 10000 + n),
+            "product_status": {
+                "known_affected": ["SIM-PRODUCT-1"]
+            }
+        }]
+    });
+
+    let unpadded = serde_json::to_vec(&document)?.len();
+    let padding = size.saturating_sub(unpadded);
+    document["document"]["notes"][0]["text"] = FILLER
+        .chars()
+        .cycle()
+        .take(padding)
+        .collect::<String>()
+        .into();
+
+    Ok(serde_json::to_vec(&document)?)
+}
+
+/// Create the ROLIE entry of an advisory.
+fn entry(url: &Url, id: &str, released: OffsetDateTime, data: &[u8], signed: bool) -> Entry {
+    let mut link = vec![
+        Link {
+            rel: "self".to_string(),
+            href: url.to_string(),
+            length: Some(data.len() as u64),
+        },
+        Link {
+            rel: "hash".to_string(),
+            href: format!("{url}.sha256"),
+            length: None,
+        },
+        Link {
+            rel: "hash".to_string(),
+            href: format!("{url}.sha512"),
+            length: None,
+        },
+    ];
+    if signed {
+        link.push(Link {
+            rel: "signature".to_string(),
+            href: format!("{url}.asc"),
+            length: None,
+        });
+    }
+
+    Entry {
+        link,
+        format: Format {
+            schema: "https://docs.oasis-open.org/csaf/csaf/v2.0/csaf_json_schema.json".to_string(),
+            version: "2.0".to_string(),
+        },
+        id: id.to_string(),
+        published: released,
+        title: format!("Simulated advisory {id}"),
+        updated: released,
+        content: Content {
+            src: url.to_string(),
+            content_type: "application/json".to_string(),
+        },
+    }
+}
+
+/// Generate a signing key, returning its fingerprint and the armored public key.
+fn generate_key() -> anyhow::Result<(Signing, String, Vec<u8>)> {
+    let (cert, _) = CertBuilder::new()
+        .add_userid("Simulated provider <security@example.com>")
+        .add_signing_subkey()
+        .generate()?;
+
+    let mut writer = sequoia_openpgp::armor::Writer::new(Vec::new(), Kind::PublicKey)?;
+    writer.write_all(&cert.to_vec()?)?;
+    let key = writer.finalize()?;
+
+    let fingerprint = cert.fingerprint().to_hex();
+    Ok((Signing::new(vec![cert]), fingerprint, key))
+}
+
+fn sidecar(path: &Path, extension: &str) -> std::path::PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(extension);
+    path.into()
+}
+
+fn write(path: &Path, data: &[u8]) -> anyhow::Result<()> {
+    std::fs::write(path, data).with_context(|| format!("Failed to write: {}", path.display()))
+}
+
+/// A small, seedable pseudo random number generator (xorshift64*)
+struct Random(u64);
+
+impl Random {
+    fn next_u64(&mut self) -> u64 {
+        // the state must not be zero
+        let mut x = match self.0 {
+            0 => 0x9E37_79B9_7F4A_7C15,
+            x => x,
+        };
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.0 = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rolie::RolieSource;
+    use walker_common::{changes::ChangeSource, retrieve::DigestFile, signature::check_armor};
+
+    #[test]
+    fn test_generate() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let target = dir.path().join("provider");
+        let base = Url::parse("http://localhost:8080/")?;
+
+        let provider = generate(
+            &base,
+            &target,
+            &SimulationOptions::new().advisories(3).sizes(1024, 2048),
+        )?;
+
+        assert_eq!(
+            provider.metadata_url.as_str(),
+            "http://localhost:8080/.well-known/csaf/provider-metadata.json"
+        );
+        assert_eq!(provider.advisories, 3);
+        assert!(provider.fingerprint.is_some());
+
+        let directory = target.join(".well-known/csaf/white");
+
+        let changes =
+            ChangeSource::parse(&std::fs::read_to_string(directory.join("changes.csv"))?)?;
+        assert_eq!(changes.entries.len(), 3);
+
+        let feed = RolieSource::parse(&std::fs::read(directory.join("feed.json"))?)?;
+        assert_eq!(feed.files.len(), 3);
+        let entry = feed.files[0].entry.as_ref().expect("must have an entry");
+        assert!(entry.signature.is_some());
+
+        let advisory = directory.join("2024/sim-2024-00001.json");
+        let data = std::fs::read(&advisory)?;
+        assert!((1024..=2048).contains(&data.len()));
+        let digest = DigestFile::parse(&std::fs::read_to_string(sidecar(&advisory, "sha256"))?)?;
+        assert_eq!(digest.digest, Hex(&Sha256::digest(&data)).to_lower());
+        assert_eq!(
+            check_armor(&std::fs::read_to_string(sidecar(&advisory, "asc"))?),
+            Ok(())
+        );
+
+        // the same seed creates the same advisories
+        let other = target.join("other");
+        generate(
+            &base,
+            &other,
+            &SimulationOptions::new()
+                .advisories(3)
+                .sizes(1024, 2048)
+                .signed(false),
+        )?;
+        assert_eq!(
+            std::fs::read(other.join(".well-known/csaf/white/2024/sim-2024-00001.json"))?,
+            data
+        );
+
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{
     discover::DiscoverConfig,
     metadata::MetadataRetriever,
-    source::{DispatchSource, FileOptions, FileSource, HttpOptions, HttpSource},
+    source::{
+        DispatchSource, FileOptions, FileSource, HttpOptions, HttpSource, ListEntry, ListSource,
+    },
 };
 use anyhow::bail;
 use fluent_uri::Uri;
@@ -52,25 +54,64 @@ impl SourceDescriptor {
         discover: DiscoverConfig,
         fetcher: FetcherOptions,
     ) -> anyhow::Result<DispatchSource> {
-        match self {
+        let list = discover
+            .list
+            .as_deref()
+            .map(ListEntry::load_list)
+            .transpose()?;
+
+        self.into_list_source(discover, fetcher, list).await
+    }
+
+    /// Turn this into a source, only walking the provided advisories, if any.
+    pub async fn into_list_source(
+        self,
+        discover: DiscoverConfig,
+        fetcher: FetcherOptions,
+        list: Option<Vec<ListEntry>>,
+    ) -> anyhow::Result<DispatchSource> {
+        let source = match self {
+            Self::File(_) if list.is_some() => {
+                bail!("A list of advisories is only supported with remote sources")
+            }
             Self::File(path) => {
-                Ok(FileSource::new(path, FileOptions::new().since(discover.since))?.into())
+                return Ok(FileSource::new(
+                    path,
+                    FileOptions::new()
+                        .since(discover.since)
+                        .decryption(discover.decryption)
+                        .offload(discover.offload),
+                )?
+                .into())
             }
-            Self::Url(url) => Ok(HttpSource::new(
+            Self::Url(url) => HttpSource::new(
                 url,
                 Fetcher::new(fetcher).await?,
-                HttpOptions::new().since(discover.since),
-            )
-            .into()),
+                HttpOptions::new()
+                    .since(discover.since)
+                    .sidecar_miss_limit(discover.sidecar_miss_limit)
+                    .offload(discover.offload)
+                    .resume_threshold(discover.resume_threshold)
+                    .key_fallback(discover.key_fallback),
+            ),
             Self::Lookup(source) => {
                 let fetcher = Fetcher::new(fetcher).await?;
-                Ok(HttpSource::new(
+                HttpSource::new(
                     MetadataRetriever::new(source),
                     fetcher,
-                    HttpOptions::new().since(discover.since),
+                    HttpOptions::new()
+                        .since(discover.since)
+                        .sidecar_miss_limit(discover.sidecar_miss_limit)
+                        .offload(discover.offload)
+                        .resume_threshold(discover.resume_threshold)
+                        .key_fallback(discover.key_fallback),
                 )
-                .into())
             }
-        }
+        };
+
+        Ok(match list {
+            Some(list) => ListSource::new(source, list).into(),
+            None => source.into(),
+        })
     }
 }
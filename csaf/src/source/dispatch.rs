@@ -2,8 +2,10 @@ use super::Source;
 use crate::discover::{DiscoveredAdvisory, DistributionContext};
 use crate::model::metadata::ProviderMetadata;
 use crate::retrieve::RetrievedAdvisory;
-use crate::source::{FileSource, HttpSource};
+use crate::source::{FileSource, HttpSource, ListSource};
+use reqwest::StatusCode;
 use walker_common::{
+    fetcher,
     utils::openpgp::PublicKey,
     validate::source::{Key, KeySource, KeySourceError, MapSourceError},
 };
@@ -19,6 +21,7 @@ use walker_common::{
 pub enum DispatchSource {
     File(FileSource),
     Http(HttpSource),
+    List(ListSource<HttpSource>),
 }
 
 impl From<FileSource> for DispatchSource {
@@ -33,13 +36,35 @@ impl From<HttpSource> for DispatchSource {
     }
 }
 
+impl From<ListSource<HttpSource>> for DispatchSource {
+    fn from(value: ListSource<HttpSource>) -> Self {
+        Self::List(value)
+    }
+}
+
 impl Source for DispatchSource {
     type Error = anyhow::Error;
 
+    fn skip_reason(error: &Self::Error) -> Option<String> {
+        error
+            .chain()
+            .filter_map(|err| err.downcast_ref::<fetcher::Error>())
+            .any(fetcher::Error::is_circuit_open)
+            .then(|| error.to_string())
+    }
+
+    fn status(error: &Self::Error) -> Option<StatusCode> {
+        error
+            .chain()
+            .filter_map(|err| err.downcast_ref::<fetcher::Error>())
+            .find_map(fetcher::Error::status)
+    }
+
     async fn load_metadata(&self) -> Result<ProviderMetadata, Self::Error> {
         match self {
             Self::File(source) => source.load_metadata().await,
             Self::Http(source) => source.load_metadata().await.map_err(|err| err.into()),
+            Self::List(source) => source.load_metadata().await.map_err(|err| err.into()),
         }
     }
 
@@ -50,6 +75,7 @@ impl Source for DispatchSource {
         match self {
             Self::File(source) => source.load_index(context).await,
             Self::Http(source) => source.load_index(context).await.map_err(|err| err.into()),
+            Self::List(source) => source.load_index(context).await.map_err(|err| err.into()),
         }
     }
 
@@ -63,6 +89,10 @@ impl Source for DispatchSource {
                 .load_advisory(advisory)
                 .await
                 .map_err(|err| err.into()),
+            Self::List(source) => source
+                .load_advisory(advisory)
+                .await
+                .map_err(|err| err.into()),
         }
     }
 }
@@ -80,6 +110,10 @@ impl KeySource for DispatchSource {
                 .load_public_key(key)
                 .await
                 .map_source(|err| err.into()),
+            Self::List(source) => source
+                .load_public_key(key)
+                .await
+                .map_source(|err| err.into()),
         }
     }
 }
@@ -1,9 +1,9 @@
 use crate::{
     discover::DiscoveredAdvisory,
-    discover::DistributionContext,
+    discover::{load_origin, DistributionContext},
     model::{
         metadata::{self, ProviderMetadata},
-        store::distribution_base,
+        store::StoreLayout,
     },
     retrieve::RetrievedAdvisory,
     source::Source,
@@ -21,6 +21,8 @@ use tokio::sync::mpsc;
 use url::Url;
 use walkdir::WalkDir;
 use walker_common::{
+    encryption::Decryption,
+    offload::Offload,
     retrieve::RetrievalMetadata,
     source::file::{read_sig_and_digests, to_path},
     utils::{self, openpgp::PublicKey},
@@ -31,6 +33,10 @@ use walker_common::{
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct FileOptions {
     pub since: Option<SystemTime>,
+    /// decrypt documents, stored encrypted by the store visitor
+    pub decryption: Option<Decryption>,
+    /// calculate digests on the blocking thread pool
+    pub offload: Option<Offload>,
 }
 
 impl FileOptions {
@@ -42,6 +48,16 @@ impl FileOptions {
         self.since = since.into();
         self
     }
+
+    pub fn decryption(mut self, decryption: impl Into<Option<Decryption>>) -> Self {
+        self.decryption = decryption.into();
+        self
+    }
+
+    pub fn offload(mut self, offload: impl Into<Option<Offload>>) -> Self {
+        self.offload = offload.into();
+        self
+    }
 }
 
 /// A file based source, possibly created by the [`crate::visitors::store::StoreVisitor`].
@@ -147,9 +163,12 @@ impl Source for FileSource {
 
         metadata.public_openpgp_keys = self.scan_keys().await?;
 
+        let layout = StoreLayout::detect(&self.base)?.unwrap_or_default();
+
         for dist in &mut metadata.distributions {
             if let Some(directory_url) = &dist.directory_url {
-                let distribution_base = distribution_base(&self.base, directory_url.as_str());
+                let distribution_base =
+                    layout.distribution_base(&self.base, directory_url.as_str());
                 let directory_url = Url::from_directory_path(&distribution_base).map_err(|()| {
                     anyhow!(
                         "Failed to convert directory into URL: {}",
@@ -162,7 +181,7 @@ impl Source for FileSource {
 
             if let Some(rolie) = &mut dist.rolie {
                 for feed in &mut rolie.feeds {
-                    let distribution_base = distribution_base(&self.base, feed.url.as_str());
+                    let distribution_base = layout.distribution_base(&self.base, feed.url.as_str());
                     let feed_url = Url::from_directory_path(&distribution_base).map_err(|()| {
                         anyhow!(
                             "Failed to convert directory into URL: {}",
@@ -215,11 +234,16 @@ impl Source for FileSource {
                 .map_err(|()| anyhow!("Failed to convert to URL: {}", path.display()))?;
 
             let modified = path.metadata()?.modified()?;
+            // keep the attribution of documents, which were stored when walking an aggregator
+            let aggregator = load_origin(path).await.map(Arc::new);
 
             result.push(DiscoveredAdvisory {
                 url,
                 modified,
                 context: context.clone(),
+                retrieval_url: None,
+                rolie: None,
+                aggregator,
             })
         }
 
@@ -230,14 +254,21 @@ impl Source for FileSource {
         &self,
         discovered: DiscoveredAdvisory,
     ) -> Result<RetrievedAdvisory, Self::Error> {
-        let path = discovered
-            .url
+        let url = discovered.retrieval_url();
+        let path = url
             .to_file_path()
-            .map_err(|()| anyhow!("Unable to convert URL into path: {}", discovered.url))?;
-
-        let data = Bytes::from(tokio::fs::read(&path).await?);
+            .map_err(|()| anyhow!("Unable to convert URL into path: {url}"))?;
+
+        let data = tokio::fs::read(&path).await?;
+        let data = Bytes::from(match &self.options.decryption {
+            Some(decryption) => decryption
+                .decrypt(&data)
+                .with_context(|| format!("Failed to decrypt document: {}", path.display()))?,
+            None => data,
+        });
 
-        let (signature, sha256, sha512) = read_sig_and_digests(&path, &data).await?;
+        let (signature, sha256, sha512) =
+            read_sig_and_digests(&path, &data, self.options.offload.as_ref()).await?;
 
         let last_modification = path
             .metadata()
@@ -262,6 +293,7 @@ impl Source for FileSource {
             metadata: RetrievalMetadata {
                 last_modification,
                 etag,
+                ..Default::default()
             },
         })
     }
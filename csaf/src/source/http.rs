@@ -7,27 +7,39 @@ use crate::{
     rolie::{RolieSource, SourceFile},
     source::Source,
 };
-use bytes::{BufMut, Bytes, BytesMut};
+use bytes::Bytes;
 use digest::Digest;
 use futures::try_join;
-use reqwest::Response;
+use reqwest::{header, header::HeaderValue, RequestBuilder, Response, StatusCode};
 use sha2::{Sha256, Sha512};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use std::time::SystemTime;
-use time::{format_description::well_known::Rfc2822, OffsetDateTime};
 use url::{ParseError, Url};
 use walker_common::{
     changes::{self, ChangeEntry, ChangeSource},
-    fetcher::{self, DataProcessor, Fetcher},
-    retrieve::{RetrievalMetadata, RetrievedDigest, RetrievingDigest},
+    fetcher::{self, Data, DataProcessor, Fetcher},
+    offload::Offload,
+    retrieve::{
+        RequestTiming, RetrievalMetadata, RetrievedDigest, RetrievingBody, RetrievingDigest,
+    },
+    signature::normalize_signature,
+    source::sidecar::{Sidecar, SidecarProbes},
     utils::openpgp::PublicKey,
-    validate::source::{Key, KeySource, KeySourceError},
+    validate::source::{Key, KeyFallback, KeySource, KeySourceError},
 };
 
 #[non_exhaustive]
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct HttpOptions {
     pub since: Option<SystemTime>,
+    /// Stop probing for a type of sidecar file of a distribution, after it was missing that many times
+    pub sidecar_miss_limit: Option<usize>,
+    /// Calculate digests on the blocking thread pool
+    pub offload: Option<Offload>,
+    /// Resume interrupted downloads of documents of at least that many bytes
+    pub resume_threshold: Option<u64>,
+    /// Alternative locations for keys, which can't be retrieved from the provider
+    pub key_fallback: KeyFallback,
 }
 
 impl HttpOptions {
@@ -39,6 +51,26 @@ impl HttpOptions {
         self.since = since.into();
         self
     }
+
+    pub fn sidecar_miss_limit(mut self, sidecar_miss_limit: impl Into<Option<usize>>) -> Self {
+        self.sidecar_miss_limit = sidecar_miss_limit.into();
+        self
+    }
+
+    pub fn offload(mut self, offload: impl Into<Option<Offload>>) -> Self {
+        self.offload = offload.into();
+        self
+    }
+
+    pub fn resume_threshold(mut self, resume_threshold: impl Into<Option<u64>>) -> Self {
+        self.resume_threshold = resume_threshold.into();
+        self
+    }
+
+    pub fn key_fallback(mut self, key_fallback: KeyFallback) -> Self {
+        self.key_fallback = key_fallback;
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -46,6 +78,7 @@ pub struct HttpSource {
     fetcher: Fetcher,
     metadata_source: Arc<dyn MetadataSource>,
     options: HttpOptions,
+    probes: Arc<SidecarProbes>,
 }
 
 impl HttpSource {
@@ -57,9 +90,41 @@ impl HttpSource {
         Self {
             metadata_source: Arc::new(metadata),
             fetcher,
+            probes: Arc::new(SidecarProbes::new(options.sidecar_miss_limit)),
             options,
         }
     }
+
+    /// Fetch a sidecar file, preferring the link of the ROLIE entry over probing.
+    ///
+    /// Sidecar files, which were missing too often for a distribution, are not probed anymore.
+    async fn fetch_sidecar<D: Data>(
+        &self,
+        discovered: &DiscoveredAdvisory,
+        link: Option<&Url>,
+        sidecar: Sidecar,
+    ) -> Result<Option<D>, fetcher::Error> {
+        if let Some(link) = link {
+            return self.fetcher.fetch::<Option<D>>(link.clone()).await;
+        }
+
+        let distribution = discovered.context.url();
+        if !self.probes.should_probe(distribution, sidecar) {
+            return Ok(None);
+        }
+
+        let result = self
+            .fetcher
+            .fetch::<Option<D>>(format!(
+                "{}{}",
+                discovered.retrieval_url(),
+                sidecar.suffix()
+            ))
+            .await?;
+        self.probes.record(distribution, sidecar, result.is_some());
+
+        Ok(result)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -89,6 +154,20 @@ impl From<changes::Error> for HttpSourceError {
 impl Source for HttpSource {
     type Error = HttpSourceError;
 
+    fn skip_reason(error: &Self::Error) -> Option<String> {
+        match error {
+            HttpSourceError::Fetcher(err) if err.is_circuit_open() => Some(err.to_string()),
+            _ => None,
+        }
+    }
+
+    fn status(error: &Self::Error) -> Option<StatusCode> {
+        match error {
+            HttpSourceError::Fetcher(err) => err.status(),
+            _ => None,
+        }
+    }
+
     async fn load_metadata(&self) -> Result<ProviderMetadata, Self::Error> {
         Ok(self.metadata_source.load_metadata(&self.fetcher).await?)
     }
@@ -106,6 +185,9 @@ impl Source for HttpSource {
                     url: _,
                     context: _,
                     modified,
+                    retrieval_url: _,
+                    rolie: _,
+                    aggregator: _,
                 }),
                 Some(since),
             ) => modified >= since,
@@ -136,6 +218,9 @@ impl Source for HttpSource {
                             context: discover_context.clone(),
                             url,
                             modified,
+                            retrieval_url: None,
+                            rolie: None,
+                            aggregator: None,
                         })
                     })
                     .filter(since_filter)
@@ -143,18 +228,21 @@ impl Source for HttpSource {
             }
 
             DistributionContext::Feed(feed) => {
-                let source_files = RolieSource::retrieve(&self.fetcher, feed.clone()).await?;
+                let source_files = RolieSource::retrieve(&self.fetcher, feed.url.clone()).await?;
                 Ok(source_files
                     .files
                     .into_iter()
-                    .map(|SourceFile { file, timestamp }| {
-                        let modified = timestamp.into();
-                        let url = Url::parse(&file)?;
+                    .map(|source_file: SourceFile| {
+                        let modified = source_file.timestamp.into();
+                        let url = Url::parse(&source_file.file)?;
 
                         Ok::<_, ParseError>(DiscoveredAdvisory {
                             context: discover_context.clone(),
                             url,
                             modified,
+                            retrieval_url: None,
+                            rolie: source_file.entry,
+                            aggregator: None,
                         })
                     })
                     .filter(since_filter)
@@ -167,35 +255,51 @@ impl Source for HttpSource {
         &self,
         discovered: DiscoveredAdvisory,
     ) -> Result<RetrievedAdvisory, Self::Error> {
+        let url = discovered.retrieval_url();
+        let rolie = discovered.rolie.as_ref();
+        let signature_link = rolie.and_then(|entry| entry.signature.as_ref());
+
         let (signature, sha256, sha512) = try_join!(
-            self.fetcher
-                .fetch::<Option<String>>(format!("{url}.asc", url = discovered.url)),
-            self.fetcher
-                .fetch::<Option<String>>(format!("{url}.sha256", url = discovered.url)),
-            self.fetcher
-                .fetch::<Option<String>>(format!("{url}.sha512", url = discovered.url)),
+            self.fetch_sidecar::<Bytes>(&discovered, signature_link, Sidecar::Signature),
+            self.fetch_sidecar::<String>(
+                &discovered,
+                rolie.and_then(|entry| entry.sha256.as_ref()),
+                Sidecar::Sha256
+            ),
+            self.fetch_sidecar::<String>(
+                &discovered,
+                rolie.and_then(|entry| entry.sha512.as_ref()),
+                Sidecar::Sha512
+            ),
         )?;
 
+        let signature = match signature.as_deref().and_then(normalize_signature) {
+            Some(signature) => Some(signature),
+            None if signature_link.is_none() => self
+                .fetch_sidecar::<Bytes>(&discovered, None, Sidecar::BinarySignature)
+                .await?
+                .and_then(|signature| normalize_signature(&signature)),
+            None => None,
+        };
+
         let sha256 = sha256
-            // take the first "word" from the line
-            .and_then(|expected| expected.split(' ').next().map(ToString::to_string))
-            .map(|expected| RetrievingDigest {
-                expected,
-                current: Sha256::new(),
-            });
+            .as_deref()
+            .map(RetrievingDigest::<Sha256>::from_digest_file);
         let sha512 = sha512
-            // take the first "word" from the line
-            .and_then(|expected| expected.split(' ').next().map(ToString::to_string))
-            .map(|expected| RetrievingDigest {
-                expected,
-                current: Sha512::new(),
-            });
+            .as_deref()
+            .map(RetrievingDigest::<Sha512>::from_digest_file);
 
         let advisory = self
             .fetcher
             .fetch_processed(
-                discovered.url.clone(),
-                FetchingRetrievedAdvisory { sha256, sha512 },
+                url.clone(),
+                FetchingRetrievedAdvisory {
+                    sha256,
+                    sha512,
+                    offload: self.options.offload.clone(),
+                    resume_threshold: self.options.resume_threshold,
+                    partial: Default::default(),
+                },
             )
             .await?;
 
@@ -230,48 +334,158 @@ impl FetchedRetrievedAdvisory {
 pub struct FetchingRetrievedAdvisory {
     pub sha256: Option<RetrievingDigest<Sha256>>,
     pub sha512: Option<RetrievingDigest<Sha512>>,
+    /// Calculate the digests on the blocking thread pool, once the body is complete
+    pub offload: Option<Offload>,
+    /// Resume interrupted downloads of documents of at least that many bytes
+    pub resume_threshold: Option<u64>,
+    /// The state of an interrupted download, to be resumed by the next attempt
+    partial: Mutex<Option<PartialAdvisory>>,
+}
+
+/// The state of an interrupted download.
+struct PartialAdvisory {
+    data: RetrievingBody,
+    sha256: Option<RetrievingDigest<Sha256>>,
+    sha512: Option<RetrievingDigest<Sha512>>,
+    /// The metadata of the original response
+    metadata: RetrievalMetadata,
+    /// The value for the `If-Range` header, ensuring the document didn't change in the meantime
+    validator: HeaderValue,
+}
+
+impl FetchingRetrievedAdvisory {
+    fn partial(&self) -> MutexGuard<'_, Option<PartialAdvisory>> {
+        self.partial.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The validator for resuming the download of a response.
+    ///
+    /// Only strong validators can be used with `If-Range`. Weak ETags are not.
+    fn validator(response: &Response) -> Option<HeaderValue> {
+        let headers = response.headers();
+        match headers.get(header::ETAG) {
+            Some(etag) if !etag.as_bytes().starts_with(b"W/") => Some(etag.clone()),
+            _ => headers.get(header::LAST_MODIFIED).cloned(),
+        }
+    }
+
+    /// The first byte of a partial response, from its `Content-Range` header.
+    fn range_start(response: &Response) -> Option<usize> {
+        let range = response
+            .headers()
+            .get(header::CONTENT_RANGE)?
+            .to_str()
+            .ok()?;
+        let (start, _) = range.strip_prefix("bytes ")?.split_once('-')?;
+        start.trim().parse().ok()
+    }
 }
 
 impl DataProcessor for FetchingRetrievedAdvisory {
     type Type = FetchedRetrievedAdvisory;
 
+    fn prepare(&self, request: RequestBuilder) -> RequestBuilder {
+        match &*self.partial() {
+            Some(partial) => request
+                .header(header::RANGE, format!("bytes={}-", partial.data.len()))
+                .header(header::IF_RANGE, partial.validator.clone()),
+            None => request,
+        }
+    }
+
     async fn process(&self, response: Response) -> Result<Self::Type, reqwest::Error> {
         let mut response = response.error_for_status()?;
 
-        let mut data = BytesMut::new();
-        let mut sha256 = self.sha256.clone();
-        let mut sha512 = self.sha512.clone();
-
-        while let Some(chunk) = response.chunk().await? {
-            if let Some(d) = &mut sha256 {
-                d.update(&chunk);
+        // if the server ignored the range, or the document changed, it sends the full document
+        let partial = self.partial().take();
+        let (mut data, mut sha256, mut sha512, mut metadata, validator) = match partial {
+            Some(partial)
+                if response.status() == StatusCode::PARTIAL_CONTENT
+                    && Self::range_start(&response) == Some(partial.data.len()) =>
+            {
+                log::info!(
+                    "Resuming download at {} bytes: {}",
+                    partial.data.len(),
+                    response.url()
+                );
+                (
+                    partial.data,
+                    partial.sha256,
+                    partial.sha512,
+                    partial.metadata,
+                    Some(partial.validator),
+                )
             }
-            if let Some(d) = &mut sha512 {
-                d.update(&chunk);
+            _ => {
+                if response.status() == StatusCode::PARTIAL_CONTENT {
+                    // not what we asked for, the digests will not match
+                    log::warn!("Unexpected range of partial content: {}", response.url());
+                }
+                (
+                    RetrievingBody::new(),
+                    self.sha256.clone(),
+                    self.sha512.clone(),
+                    RetrievalMetadata::from_response(&response),
+                    Self::validator(&response),
+                )
             }
-            data.put(chunk);
-        }
+        };
 
-        let etag = response
-            .headers()
-            .get(reqwest::header::ETAG)
-            .and_then(|s| s.to_str().ok())
-            .map(ToString::to_string);
+        loop {
+            let chunk = match response.chunk().await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => break,
+                Err(err) => {
+                    let expected = metadata.content_length.unwrap_or(data.len() as u64);
+                    if let (Some(threshold), Some(validator)) = (self.resume_threshold, validator) {
+                        if expected >= threshold && !data.is_empty() {
+                            *self.partial() = Some(PartialAdvisory {
+                                data,
+                                sha256,
+                                sha512,
+                                metadata,
+                                validator,
+                            });
+                        }
+                    }
+                    return Err(err);
+                }
+            };
+
+            if self.offload.is_none() {
+                if let Some(d) = &mut sha256 {
+                    d.update(&chunk);
+                }
+                if let Some(d) = &mut sha512 {
+                    d.update(&chunk);
+                }
+            }
+            data.push(chunk);
+        }
 
-        let last_modification = response
-            .headers()
-            .get(reqwest::header::LAST_MODIFIED)
-            .and_then(|s| s.to_str().ok())
-            .and_then(|s| OffsetDateTime::parse(s, &Rfc2822).ok());
+        metadata.timings = RequestTiming::of(&response);
+
+        let data = data.freeze();
+        let (sha256, sha512) = match &self.offload {
+            Some(offload) => {
+                let data = data.clone();
+                offload
+                    .run(move || {
+                        (
+                            sha256.map(|d| d.finalize_with(&data)),
+                            sha512.map(|d| d.finalize_with(&data)),
+                        )
+                    })
+                    .await
+            }
+            None => (sha256.map(|d| d.into()), sha512.map(|d| d.into())),
+        };
 
         Ok(FetchedRetrievedAdvisory {
-            data: data.freeze(),
-            sha256: sha256.map(|d| d.into()),
-            sha512: sha512.map(|d| d.into()),
-            metadata: RetrievalMetadata {
-                last_modification,
-                etag,
-            },
+            data,
+            sha256,
+            sha512,
+            metadata,
         })
     }
 }
@@ -283,6 +497,8 @@ impl KeySource for HttpSource {
         &self,
         key_source: Key<'a>,
     ) -> Result<PublicKey, KeySourceError<Self::Error>> {
-        self.fetcher.load_public_key(key_source).await
+        self.fetcher
+            .load_public_key_with_fallback(key_source, &self.options.key_fallback)
+            .await
     }
 }
@@ -0,0 +1,364 @@
+use crate::{
+    discover::{DiscoveredAdvisory, DistributionContext},
+    model::metadata::{Distribution, Feed, ProviderMetadata, Rolie, TlpLabel},
+    retrieve::RetrievedAdvisory,
+    source::Source,
+};
+use anyhow::Context;
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
+use time::OffsetDateTime;
+use url::Url;
+use walker_common::{
+    utils::openpgp::PublicKey,
+    validate::source::{Key, KeySource, KeySourceError},
+};
+
+/// An entry of a list of advisories
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListEntry {
+    /// The URL of the advisory
+    pub url: Url,
+    /// The distribution the advisory belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distribution: Option<ListDistribution>,
+    /// The "last changed" date, considered to be now if missing
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "time::serde::rfc3339::option"
+    )]
+    pub modified: Option<OffsetDateTime>,
+}
+
+/// The distribution of a listed advisory
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListDistribution {
+    /// The URL of the distribution
+    pub url: Url,
+    /// The type of the distribution, either `directory` (the default) or `feed`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    /// The TLP label of a ROLIE feed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tlp_label: Option<TlpLabel>,
+}
+
+impl ListDistribution {
+    fn is_feed(&self) -> bool {
+        self.r#type.as_deref() == Some("feed")
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum ListItem {
+    Url(Url),
+    Entry(ListEntry),
+}
+
+impl From<ListItem> for ListEntry {
+    fn from(value: ListItem) -> Self {
+        match value {
+            ListItem::Url(url) => Self {
+                url,
+                distribution: None,
+                modified: None,
+            },
+            ListItem::Entry(entry) => entry,
+        }
+    }
+}
+
+impl From<&DiscoveredAdvisory> for ListEntry {
+    fn from(value: &DiscoveredAdvisory) -> Self {
+        let r#type = match value.context.as_ref() {
+            DistributionContext::Directory(_) => "directory",
+            DistributionContext::Feed(_) => "feed",
+        };
+
+        Self {
+            url: value.url.clone(),
+            distribution: Some(ListDistribution {
+                url: value.context.url().clone(),
+                r#type: Some(r#type.to_string()),
+                tlp_label: value.context.tlp_label().cloned(),
+            }),
+            modified: Some(value.modified.into()),
+        }
+    }
+}
+
+impl ListEntry {
+    /// Get the distribution of the entry.
+    ///
+    /// Entries without distribution information are assigned to the (longest) directory
+    /// distribution containing them, or to the directory containing the advisory.
+    pub fn distribution_context(&self, directories: &[Url]) -> DistributionContext {
+        match &self.distribution {
+            Some(distribution) if distribution.is_feed() => DistributionContext::Feed(Feed {
+                summary: None,
+                tlp_label: distribution
+                    .tlp_label
+                    .clone()
+                    .unwrap_or(TlpLabel::Unlabeled),
+                url: distribution.url.clone(),
+            }),
+            Some(distribution) => DistributionContext::Directory(distribution.url.clone()),
+            None => DistributionContext::Directory(
+                directories
+                    .iter()
+                    .filter(|base| {
+                        self.url
+                            .as_str()
+                            .starts_with(directory_base((*base).clone()).as_str())
+                    })
+                    .max_by_key(|base| base.as_str().len())
+                    .cloned()
+                    .or_else(|| self.url.join("./").ok())
+                    .unwrap_or_else(|| self.url.clone()),
+            ),
+        }
+    }
+
+    /// Turn the entry into a discovered advisory. See [`Self::distribution_context`].
+    pub fn into_discovered(self, context: Arc<DistributionContext>) -> DiscoveredAdvisory {
+        DiscoveredAdvisory {
+            context,
+            url: self.url,
+            modified: self
+                .modified
+                .map(SystemTime::from)
+                .unwrap_or_else(SystemTime::now),
+            retrieval_url: None,
+            rolie: None,
+            aggregator: None,
+        }
+    }
+
+    /// Parse a list of advisories.
+    ///
+    /// This can either be a JSON array, or one entry per line. An entry can be a plain URL, or a
+    /// JSON object, like the ones emitted by the JSON output of the `discover` command. Empty
+    /// lines, and lines starting with `#`, are ignored.
+    pub fn parse_list(content: &str) -> anyhow::Result<Vec<Self>> {
+        if content.trim_start().starts_with('[') {
+            let items: Vec<ListItem> = serde_json::from_str(content)?;
+            return Ok(items.into_iter().map(Self::from).collect());
+        }
+
+        let mut result = vec![];
+
+        for (n, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let item = match line.starts_with('{') {
+                true => serde_json::from_str(line)
+                    .map(ListItem::Entry)
+                    .with_context(|| format!("Invalid entry in line {}", n + 1))?,
+                false => Url::parse(line)
+                    .map(ListItem::Url)
+                    .with_context(|| format!("Invalid URL in line {}", n + 1))?,
+            };
+            result.push(item.into());
+        }
+
+        Ok(result)
+    }
+
+    /// Load a list of advisories from a file. See [`Self::parse_list`].
+    pub fn load_list(path: &Path) -> anyhow::Result<Vec<Self>> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read list of advisories: {}", path.display()))?;
+        Self::parse_list(&content)
+    }
+}
+
+/// A source, only walking the advisories of a list.
+///
+/// The provider metadata, public keys, and the advisories themselves are loaded through the
+/// wrapped source. However, the distributions of the provider metadata are replaced by the
+/// distributions of the listed advisories. Advisories without distribution information are
+/// assigned to the directory distribution of the provider containing them, or to the directory
+/// containing the advisory.
+#[derive(Clone)]
+pub struct ListSource<S: Source> {
+    source: S,
+    entries: Arc<Vec<ListEntry>>,
+    /// The listed advisories, by distribution URL, assigned when loading the metadata
+    assigned: Arc<Mutex<HashMap<Url, Vec<ListEntry>>>>,
+}
+
+impl<S: Source> ListSource<S> {
+    pub fn new(source: S, entries: Vec<ListEntry>) -> Self {
+        Self {
+            source,
+            entries: Arc::new(entries),
+            assigned: Default::default(),
+        }
+    }
+
+    /// Assign the entries to distributions, given the directory distributions of the provider.
+    fn assign(&self, directories: &[Url]) -> Vec<Distribution> {
+        let mut distributions = vec![];
+        let mut assigned = HashMap::<Url, Vec<ListEntry>>::new();
+
+        for entry in self.entries.iter() {
+            let context = entry.distribution_context(directories);
+            let url = context.url().clone();
+            let distribution = match context {
+                DistributionContext::Directory(url) => Distribution {
+                    directory_url: Some(url),
+                    rolie: None,
+                },
+                DistributionContext::Feed(feed) => Distribution {
+                    directory_url: None,
+                    rolie: Some(Rolie {
+                        categories: vec![],
+                        feeds: vec![feed],
+                        services: vec![],
+                    }),
+                },
+            };
+
+            let entries = assigned.entry(url).or_insert_with(|| {
+                distributions.push(distribution);
+                vec![]
+            });
+            entries.push(entry.clone());
+        }
+
+        *self.assigned.lock().unwrap_or_else(PoisonError::into_inner) = assigned;
+
+        distributions
+    }
+}
+
+/// Ensure the URL ends with a slash, so that it is a prefix of the contained documents
+fn directory_base(mut url: Url) -> Url {
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+    url
+}
+
+impl<S: Source> Source for ListSource<S> {
+    type Error = S::Error;
+
+    fn skip_reason(error: &Self::Error) -> Option<String> {
+        S::skip_reason(error)
+    }
+
+    fn status(error: &Self::Error) -> Option<StatusCode> {
+        S::status(error)
+    }
+
+    async fn load_metadata(&self) -> Result<ProviderMetadata, Self::Error> {
+        let mut metadata = self.source.load_metadata().await?;
+
+        let directories = metadata
+            .distributions
+            .iter()
+            .filter_map(|distribution| distribution.directory_url.clone())
+            .collect::<Vec<_>>();
+
+        metadata.distributions = self.assign(&directories);
+
+        Ok(metadata)
+    }
+
+    async fn load_index(
+        &self,
+        context: DistributionContext,
+    ) -> Result<Vec<DiscoveredAdvisory>, Self::Error> {
+        let entries = self
+            .assigned
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(context.url())
+            .cloned()
+            .unwrap_or_default();
+
+        let context = Arc::new(context);
+
+        Ok(entries
+            .into_iter()
+            .map(|entry| entry.into_discovered(context.clone()))
+            .collect())
+    }
+
+    async fn load_advisory(
+        &self,
+        advisory: DiscoveredAdvisory,
+    ) -> Result<RetrievedAdvisory, Self::Error> {
+        self.source.load_advisory(advisory).await
+    }
+}
+
+impl<S: Source + KeySource> KeySource for ListSource<S> {
+    type Error = <S as KeySource>::Error;
+
+    async fn load_public_key<'a>(
+        &self,
+        key: Key<'a>,
+    ) -> Result<PublicKey, KeySourceError<Self::Error>> {
+        self.source.load_public_key(key).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        let entries = ListEntry::parse_list(
+            r#"
+# comment
+https://example.com/csaf/2024/a.json
+{"url": "https://example.com/csaf/2024/b.json", "distribution": {"type": "feed", "url": "https://example.com/feed.json", "tlpLabel": "WHITE"}, "modified": "2024-01-01T00:00:00Z", "sha256": "ignored"}
+"#,
+        )
+        .expect("must parse");
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0].url.as_str(),
+            "https://example.com/csaf/2024/a.json"
+        );
+        assert_eq!(entries[0].distribution, None);
+        assert_eq!(
+            entries[1].url.as_str(),
+            "https://example.com/csaf/2024/b.json"
+        );
+        assert!(entries[1]
+            .distribution
+            .as_ref()
+            .is_some_and(|d| d.is_feed()));
+        assert!(entries[1].modified.is_some());
+    }
+
+    #[test]
+    fn test_parse_array() {
+        let entries = ListEntry::parse_list(
+            r#"["https://example.com/a.json", {"url": "https://example.com/b.json"}]"#,
+        )
+        .expect("must parse");
+
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.url.as_str())
+                .collect::<Vec<_>>(),
+            vec!["https://example.com/a.json", "https://example.com/b.json"]
+        );
+    }
+}
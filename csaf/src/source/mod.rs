@@ -4,17 +4,22 @@ mod descriptor;
 mod dispatch;
 mod file;
 mod http;
+mod list;
+mod replay;
 
 pub use descriptor::*;
 pub use dispatch::*;
 pub use file::*;
 pub use http::*;
+pub use list::*;
+pub use replay::*;
 
 use crate::{
     discover::{DiscoverConfig, DiscoveredAdvisory, DistributionContext},
     model::metadata::ProviderMetadata,
     retrieve::RetrievedAdvisory,
 };
+use reqwest::StatusCode;
 use std::fmt::{Debug, Display};
 use std::future::Future;
 use std::str::FromStr;
@@ -35,6 +40,18 @@ pub trait Source: Clone {
         &self,
         advisory: DiscoveredAdvisory,
     ) -> impl Future<Output = Result<RetrievedAdvisory, Self::Error>>;
+
+    /// Check if an error of loading a single advisory only skips it, instead of failing the walk.
+    ///
+    /// Returns the reason for skipping, e.g. because the host was considered unavailable.
+    fn skip_reason(_error: &Self::Error) -> Option<String> {
+        None
+    }
+
+    /// Get the HTTP status code, if loading a single advisory failed with an error response.
+    fn status(_error: &Self::Error) -> Option<StatusCode> {
+        None
+    }
 }
 
 /// A common way to create a new CSAF source.
@@ -0,0 +1,246 @@
+use crate::{
+    discover::{DiscoveredAdvisory, DistributionContext, RolieEntry},
+    model::metadata::ProviderMetadata,
+    retrieve::RetrievedAdvisory,
+    source::{ListEntry, Source},
+};
+use anyhow::{anyhow, Context};
+use bytes::Bytes;
+use sha2::{Digest, Sha256, Sha512};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use url::Url;
+use walker_common::{
+    retrieve::{RetrievalMetadata, RetrievedDigest},
+    utils::openpgp::PublicKey,
+    validate::source::{Key, KeySource, KeySourceError},
+};
+
+pub(crate) const FILE_METADATA: &str = "provider-metadata.json";
+pub(crate) const DIR_KEYS: &str = "keys";
+pub(crate) const DIR_ADVISORIES: &str = "advisories";
+
+/// A recorded advisory, as written by [`crate::visitors::record::RecordingVisitor`]
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RecordedAdvisory {
+    #[serde(flatten)]
+    pub entry: ListEntry,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retrieval_url: Option<Url>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rolie: Option<RolieEntry>,
+    #[serde(flatten)]
+    pub outcome: RecordedOutcome,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase", tag = "outcome")]
+pub(crate) enum RecordedOutcome {
+    /// The advisory was retrieved, its content is stored next to the record
+    #[serde(rename_all = "camelCase")]
+    Retrieved {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signature: Option<String>,
+        /// the expected SHA-256 digest
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sha256: Option<String>,
+        /// the expected SHA-512 digest
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sha512: Option<String>,
+        #[serde(default)]
+        metadata: RetrievalMetadata,
+    },
+    /// Retrieving the advisory failed
+    Failed { reason: String },
+}
+
+/// The base name of the files recording an advisory.
+pub(crate) fn record_name(url: &Url) -> String {
+    format!("{:x}", Sha256::digest(url.as_str()))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    /// Retrieving the advisory failed, when it was recorded
+    #[error("Recorded failure: {0}")]
+    Recorded(String),
+    #[error(transparent)]
+    Replay(#[from] anyhow::Error),
+}
+
+/// A source, replaying a walk recorded by [`crate::visitors::record::RecordingVisitor`].
+///
+/// The provider metadata, public keys, and the outcome of retrieving each advisory are replayed
+/// as they were recorded, including digest mismatches. Advisories which failed to be retrieved
+/// are skipped, with the recorded reason. Without network access, this allows running the full
+/// pipeline deterministically against the quirks of a real provider.
+#[derive(Clone, Debug)]
+pub struct ReplaySource {
+    base: Arc<PathBuf>,
+}
+
+impl ReplaySource {
+    pub fn new(base: impl Into<PathBuf>) -> Self {
+        Self {
+            base: Arc::new(base.into()),
+        }
+    }
+
+    fn advisories(&self) -> PathBuf {
+        self.base.join(DIR_ADVISORIES)
+    }
+
+    async fn read_record(path: &Path) -> anyhow::Result<RecordedAdvisory> {
+        let data = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("Failed to read recorded advisory: {}", path.display()))?;
+        serde_json::from_slice(&data)
+            .with_context(|| format!("Failed to parse recorded advisory: {}", path.display()))
+    }
+
+    async fn read_metadata(&self) -> anyhow::Result<ProviderMetadata> {
+        let path = self.base.join(FILE_METADATA);
+        let data = tokio::fs::read(&path)
+            .await
+            .with_context(|| format!("Failed to read recorded metadata: {}", path.display()))?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    async fn read_index(
+        &self,
+        context: Arc<DistributionContext>,
+    ) -> anyhow::Result<Vec<DiscoveredAdvisory>> {
+        let mut entries = match tokio::fs::read_dir(self.advisories()).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut result = vec![];
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+
+            let record = Self::read_record(&path).await?;
+            if record
+                .entry
+                .distribution
+                .as_ref()
+                .map(|distribution| &distribution.url)
+                != Some(context.url())
+            {
+                continue;
+            }
+
+            let mut advisory = record.entry.into_discovered(context.clone());
+            advisory.retrieval_url = record.retrieval_url;
+            advisory.rolie = record.rolie;
+            result.push(advisory);
+        }
+
+        // the order of recording depends on the walk, replay in a stable order
+        result.sort_by(|a, b| a.url.cmp(&b.url));
+
+        Ok(result)
+    }
+
+    async fn read_advisory(
+        &self,
+        discovered: DiscoveredAdvisory,
+    ) -> Result<RetrievedAdvisory, ReplayError> {
+        let name = record_name(&discovered.url);
+        let record = Self::read_record(&self.advisories().join(format!("{name}.json"))).await?;
+
+        let (signature, sha256, sha512, metadata) = match record.outcome {
+            RecordedOutcome::Retrieved {
+                signature,
+                sha256,
+                sha512,
+                metadata,
+            } => (signature, sha256, sha512, metadata),
+            RecordedOutcome::Failed { reason } => return Err(ReplayError::Recorded(reason)),
+        };
+
+        let path = self.advisories().join(format!("{name}.data"));
+        let data = Bytes::from(
+            tokio::fs::read(&path)
+                .await
+                .with_context(|| format!("Failed to read recorded content: {}", path.display()))?,
+        );
+
+        Ok(RetrievedAdvisory {
+            discovered,
+            signature,
+            sha256: sha256.map(|expected| RetrievedDigest {
+                expected,
+                actual: Sha256::digest(&data),
+            }),
+            sha512: sha512.map(|expected| RetrievedDigest {
+                expected,
+                actual: Sha512::digest(&data),
+            }),
+            data,
+            metadata,
+        })
+    }
+}
+
+impl Source for ReplaySource {
+    type Error = ReplayError;
+
+    fn skip_reason(error: &Self::Error) -> Option<String> {
+        match error {
+            ReplayError::Recorded(reason) => Some(reason.clone()),
+            ReplayError::Replay(_) => None,
+        }
+    }
+
+    async fn load_metadata(&self) -> Result<ProviderMetadata, Self::Error> {
+        Ok(self.read_metadata().await?)
+    }
+
+    async fn load_index(
+        &self,
+        context: DistributionContext,
+    ) -> Result<Vec<DiscoveredAdvisory>, Self::Error> {
+        Ok(self.read_index(Arc::new(context)).await?)
+    }
+
+    async fn load_advisory(
+        &self,
+        discovered: DiscoveredAdvisory,
+    ) -> Result<RetrievedAdvisory, Self::Error> {
+        self.read_advisory(discovered).await
+    }
+}
+
+impl KeySource for ReplaySource {
+    type Error = anyhow::Error;
+
+    async fn load_public_key<'a>(
+        &self,
+        key: Key<'a>,
+    ) -> Result<PublicKey, KeySourceError<Self::Error>> {
+        // keys are recorded in the order of the provider metadata
+        let metadata = self.read_metadata().await.map_err(KeySourceError::Source)?;
+        let n = metadata
+            .public_openpgp_keys
+            .iter()
+            .position(|recorded| &recorded.url == key.url)
+            .ok_or_else(|| KeySourceError::Source(anyhow!("Key not recorded: {}", key.url)))?;
+
+        let path = self.base.join(DIR_KEYS).join(format!("{n}.pgp"));
+        let data = tokio::fs::read(&path)
+            .await
+            .with_context(|| format!("Failed to read recorded key: {}", path.display()))
+            .map_err(KeySourceError::Source)?;
+
+        walker_common::utils::openpgp::validate_keys(data.into(), key.fingerprint)
+            .map_err(KeySourceError::OpenPgp)
+    }
+}
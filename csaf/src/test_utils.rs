@@ -0,0 +1,522 @@
+//! Fixtures for testing visitors and sources
+//!
+//! Provides builders for the data types passed along the visitor chain, an in-memory provider,
+//! serving a spec-shaped layout (provider metadata, a directory distribution, digests), and a
+//! visitor collecting what it was called with. Enabled by the `test-utils` feature.
+//!
+//! ```rust
+//! use csaf_walker::retrieve::RetrievingVisitor;
+//! use csaf_walker::test_utils::{document, CollectingVisitor, MockProvider};
+//! use csaf_walker::walker::Walker;
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let provider = MockProvider::new("https://example.com")?
+//!     .advisory("2024/example-2024-0001.json", document("EXAMPLE-2024-0001"))?;
+//! let collector = CollectingVisitor::new();
+//!
+//! Walker::new(provider.clone())
+//!     .walk(RetrievingVisitor::new(provider, collector.clone()))
+//!     .await?;
+//!
+//! collector.assert_urls(["https://example.com/.well-known/csaf/2024/example-2024-0001.json"]);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    discover::{DiscoveredAdvisory, DistributionContext},
+    model::metadata::{Distribution, ProviderMetadata, Publisher, Role},
+    retrieve::{RetrievalContext, RetrievalError, RetrievedAdvisory, RetrievedVisitor},
+    source::Source,
+};
+use anyhow::anyhow;
+use bytes::Bytes;
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
+use url::Url;
+use walker_common::{
+    retrieve::{RetrievalMetadata, RetrievedDigest},
+    utils::{hex::Hex, openpgp::PublicKey},
+    validate::source::{Key, KeySource, KeySourceError},
+};
+
+/// The path of the provider metadata, and the directory distribution, relative to the base URL
+pub const WELL_KNOWN: &str = ".well-known/csaf/";
+
+/// Create provider metadata, with a single directory distribution.
+pub fn provider_metadata(base: &Url) -> anyhow::Result<ProviderMetadata> {
+    let directory = base.join(WELL_KNOWN)?;
+
+    Ok(ProviderMetadata {
+        canonical_url: directory.join("provider-metadata.json")?,
+        distributions: vec![Distribution {
+            directory_url: Some(directory),
+            rolie: None,
+        }],
+        last_updated: Default::default(),
+        list_on_csaf_aggregators: false,
+        metadata_version: "2.0".to_string(),
+        mirror_on_csaf_aggregators: false,
+        public_openpgp_keys: vec![],
+        publisher: Publisher {
+            category: "vendor".to_string(),
+            contact_details: "security@example.com".to_string(),
+            issuing_authority: None,
+            name: "Example".to_string(),
+            namespace: base.to_string(),
+        },
+        role: Role::Provider,
+    })
+}
+
+/// Create a minimal CSAF document, with a tracking ID and a TLP:WHITE label.
+pub fn document(id: &str) -> serde_json::Value {
+    serde_json::json!({
+        "document": {
+            "category": "csaf_base",
+            "csaf_version": "2.0",
+            "distribution": {
+                "tlp": { "label": "WHITE" }
+            },
+            "publisher": {
+                "category": "vendor",
+                "name": "Example",
+                "namespace": "https://example.com"
+            },
+            "title": id,
+            "tracking": {
+                "current_release_date": "2024-01-01T00:00:00Z",
+                "id": id,
+                "initial_release_date": "2024-01-01T00:00:00Z",
+                "revision_history": [{
+                    "date": "2024-01-01T00:00:00Z",
+                    "number": "1",
+                    "summary": "Initial version"
+                }],
+                "status": "final",
+                "version": "1"
+            }
+        }
+    })
+}
+
+/// Create a discovered advisory, belonging to the directory distribution containing it.
+pub fn discovered(url: &str) -> anyhow::Result<DiscoveredAdvisory> {
+    let url = Url::parse(url)?;
+    let directory = url.join("./")?;
+
+    Ok(DiscoveredAdvisory {
+        context: Arc::new(DistributionContext::Directory(directory)),
+        url,
+        modified: SystemTime::now(),
+        retrieval_url: None,
+        rolie: None,
+        aggregator: None,
+    })
+}
+
+/// Create a retrieved advisory, with matching digests and without a signature.
+pub fn retrieved(discovered: DiscoveredAdvisory, data: impl Into<Bytes>) -> RetrievedAdvisory {
+    let data = data.into();
+
+    RetrievedAdvisory {
+        discovered,
+        signature: None,
+        sha256: Some(RetrievedDigest {
+            expected: Hex(&Sha256::digest(&data)).to_lower(),
+            actual: Sha256::digest(&data),
+        }),
+        sha512: Some(RetrievedDigest {
+            expected: Hex(&Sha512::digest(&data)).to_lower(),
+            actual: Sha512::digest(&data),
+        }),
+        data,
+        metadata: RetrievalMetadata::default(),
+    }
+}
+
+#[derive(Clone, Debug)]
+struct MockAdvisory {
+    data: Bytes,
+    modified: SystemTime,
+    /// a wrong digest to report, instead of the actual one
+    sha256: Option<String>,
+}
+
+/// An in-memory provider, usable as [`Source`] and [`KeySource`].
+///
+/// All advisories are served from the directory distribution at `<base>/.well-known/csaf/`.
+#[derive(Clone, Debug)]
+pub struct MockProvider {
+    metadata: ProviderMetadata,
+    directory: Url,
+    advisories: Arc<BTreeMap<Url, MockAdvisory>>,
+    keys: Arc<BTreeMap<Url, Bytes>>,
+}
+
+impl MockProvider {
+    pub fn new(base: &str) -> anyhow::Result<Self> {
+        let metadata = provider_metadata(&Url::parse(base)?)?;
+        let directory = Url::parse(base)?.join(WELL_KNOWN)?;
+
+        Ok(Self {
+            metadata,
+            directory,
+            advisories: Default::default(),
+            keys: Default::default(),
+        })
+    }
+
+    /// Add an advisory, with a path relative to the directory distribution.
+    pub fn advisory(self, path: &str, document: serde_json::Value) -> anyhow::Result<Self> {
+        self.raw_advisory(path, serde_json::to_vec(&document)?)
+    }
+
+    /// Add an advisory, with raw content, which doesn't need to be valid JSON.
+    pub fn raw_advisory(mut self, path: &str, data: impl Into<Bytes>) -> anyhow::Result<Self> {
+        let url = self.directory.join(path)?;
+        Arc::make_mut(&mut self.advisories).insert(
+            url,
+            MockAdvisory {
+                data: data.into(),
+                modified: SystemTime::now(),
+                sha256: None,
+            },
+        );
+        Ok(self)
+    }
+
+    /// Report a wrong SHA-256 digest for a previously added advisory.
+    pub fn wrong_digest(mut self, path: &str) -> anyhow::Result<Self> {
+        let url = self.directory.join(path)?;
+        let advisory = Arc::make_mut(&mut self.advisories)
+            .get_mut(&url)
+            .ok_or_else(|| anyhow!("Unknown advisory: {url}"))?;
+        advisory.sha256 = Some(Hex(&Sha256::digest(b"wrong")).to_lower());
+        Ok(self)
+    }
+
+    /// Add a public key (armored or binary), announced through the provider metadata.
+    pub fn key(
+        mut self,
+        path: &str,
+        fingerprint: Option<String>,
+        data: impl Into<Bytes>,
+    ) -> anyhow::Result<Self> {
+        let url = self.directory.join(path)?;
+        self.metadata
+            .public_openpgp_keys
+            .push(crate::model::metadata::Key {
+                fingerprint,
+                url: url.clone(),
+            });
+        Arc::make_mut(&mut self.keys).insert(url, data.into());
+        Ok(self)
+    }
+
+    /// The URL of the directory distribution.
+    pub fn directory(&self) -> &Url {
+        &self.directory
+    }
+}
+
+impl Source for MockProvider {
+    type Error = anyhow::Error;
+
+    async fn load_metadata(&self) -> Result<ProviderMetadata, Self::Error> {
+        Ok(self.metadata.clone())
+    }
+
+    async fn load_index(
+        &self,
+        context: DistributionContext,
+    ) -> Result<Vec<DiscoveredAdvisory>, Self::Error> {
+        let context = Arc::new(context);
+
+        Ok(self
+            .advisories
+            .iter()
+            .filter(|(url, _)| url.as_str().starts_with(context.url().as_str()))
+            .map(|(url, advisory)| DiscoveredAdvisory {
+                context: context.clone(),
+                url: url.clone(),
+                modified: advisory.modified,
+                retrieval_url: None,
+                rolie: None,
+                aggregator: None,
+            })
+            .collect())
+    }
+
+    async fn load_advisory(
+        &self,
+        discovered: DiscoveredAdvisory,
+    ) -> Result<RetrievedAdvisory, Self::Error> {
+        let advisory = self
+            .advisories
+            .get(discovered.retrieval_url())
+            .ok_or_else(|| anyhow!("Not found: {}", discovered.url))?;
+
+        let mut result = retrieved(discovered, advisory.data.clone());
+        if let (Some(expected), Some(sha256)) = (&advisory.sha256, &mut result.sha256) {
+            sha256.expected = expected.clone();
+        }
+        Ok(result)
+    }
+}
+
+impl KeySource for MockProvider {
+    type Error = anyhow::Error;
+
+    async fn load_public_key<'a>(
+        &self,
+        key: Key<'a>,
+    ) -> Result<PublicKey, KeySourceError<Self::Error>> {
+        let data = self
+            .keys
+            .get(key.url)
+            .ok_or_else(|| KeySourceError::Source(anyhow!("Not found: {}", key.url)))?;
+        walker_common::utils::openpgp::validate_keys(data.clone(), key.fingerprint)
+            .map_err(KeySourceError::OpenPgp)
+    }
+}
+
+/// A visitor, collecting the results it gets called with.
+///
+/// Clones share the collected results, so that a clone can be handed to the walker.
+#[derive(Clone, Debug, Default)]
+pub struct CollectingVisitor {
+    results: Arc<Mutex<Vec<Result<RetrievedAdvisory, String>>>>,
+}
+
+impl CollectingVisitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The URLs of all successfully visited advisories, sorted.
+    pub fn urls(&self) -> Vec<String> {
+        let mut result = self
+            .lock()
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+            .map(|advisory| advisory.url.to_string())
+            .collect::<Vec<_>>();
+        result.sort();
+        result
+    }
+
+    /// All successfully visited advisories, sorted by URL.
+    pub fn advisories(&self) -> Vec<RetrievedAdvisory> {
+        let mut result = self
+            .lock()
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+            .cloned()
+            .collect::<Vec<_>>();
+        result.sort_by(|a, b| a.url.cmp(&b.url));
+        result
+    }
+
+    /// The errors, as strings.
+    pub fn errors(&self) -> Vec<String> {
+        self.lock()
+            .iter()
+            .filter_map(|result| result.as_ref().err())
+            .cloned()
+            .collect()
+    }
+
+    /// Assert that exactly the advisories with the provided URLs were successfully visited.
+    #[track_caller]
+    pub fn assert_urls<'a>(&self, expected: impl IntoIterator<Item = &'a str>) {
+        let mut expected = expected.into_iter().map(String::from).collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(self.urls(), expected);
+    }
+
+    /// Assert that no errors were reported.
+    #[track_caller]
+    pub fn assert_no_errors(&self) {
+        assert_eq!(self.errors(), Vec::<String>::new());
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Result<RetrievedAdvisory, String>>> {
+        self.results.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl RetrievedVisitor for CollectingVisitor {
+    type Error = anyhow::Error;
+    type Context = ();
+
+    async fn visit_context(&self, _context: &RetrievalContext<'_>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn visit_advisory(
+        &self,
+        _context: &Self::Context,
+        result: Result<RetrievedAdvisory, RetrievalError>,
+    ) -> Result<(), Self::Error> {
+        self.lock().push(result.map_err(|err| err.to_string()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        model::aggregator::AggregatorMetadata,
+        retrieve::RetrievingVisitor,
+        walker::{
+            DistributionEnd, DistributionStart, EndContext, StartContext, Walker, WalkerHooks,
+        },
+    };
+
+    #[tokio::test]
+    async fn test_walk() -> anyhow::Result<()> {
+        let provider = MockProvider::new("https://example.com")?
+            .advisory("2024/a.json", document("A"))?
+            .advisory("2024/b.json", document("B"))?;
+        let collector = CollectingVisitor::new();
+
+        Walker::new(provider.clone())
+            .walk(RetrievingVisitor::new(provider, collector.clone()))
+            .await?;
+
+        collector.assert_no_errors();
+        collector.assert_urls([
+            "https://example.com/.well-known/csaf/2024/a.json",
+            "https://example.com/.well-known/csaf/2024/b.json",
+        ]);
+
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_walk_aggregator() -> anyhow::Result<()> {
+        let provider =
+            MockProvider::new("https://example.com")?.advisory("2024/a.json", document("A"))?;
+        let collector = CollectingVisitor::new();
+
+        let aggregator: AggregatorMetadata = serde_json::from_value(serde_json::json!({
+            "aggregator": {
+                "category": "aggregator",
+                "contact_details": "csaf@aggregator.example.com",
+                "name": "Example Aggregator",
+                "namespace": "https://aggregator.example.com",
+            },
+            "aggregator_version": "2.0",
+            "canonical_url": "https://aggregator.example.com/.well-known/csaf-aggregator/aggregator.json",
+            "csaf_providers": [{
+                "metadata": {
+                    "last_updated": "2024-01-01T00:00:00Z",
+                    "publisher": {
+                        "category": "vendor",
+                        "contact_details": "csaf@example.com",
+                        "name": "Example",
+                        "namespace": "https://example.com",
+                    },
+                    "url": "https://example.com/.well-known/csaf/provider-metadata.json",
+                },
+            }],
+            "last_updated": "2024-01-01T00:00:00Z",
+        }))?;
+
+        Walker::new(provider.clone())
+            .with_aggregator(Arc::new(aggregator))
+            .walk(RetrievingVisitor::new(provider, collector.clone()))
+            .await?;
+
+        let advisories = collector.advisories();
+        let origin = advisories[0]
+            .aggregator
+            .as_deref()
+            .expect("must be attributed");
+        assert_eq!(origin.aggregator_name, "Example Aggregator");
+        assert_eq!(
+            origin.provider.as_str(),
+            "https://example.com/.well-known/csaf/provider-metadata.json"
+        );
+        assert_eq!(origin.mirror, None);
+
+        Ok(())
+    }
+
+    #[derive(Clone, Default)]
+    struct RecordingHooks(Arc<Mutex<Vec<String>>>);
+
+    impl WalkerHooks for RecordingHooks {
+        fn on_start(&self, context: &StartContext<'_>) {
+            self.push(format!("start: {}", context.distributions.len()));
+        }
+
+        fn on_distribution_start(&self, context: DistributionStart<'_>) {
+            context
+                .index
+                .retain(|advisory| !advisory.url.path().ends_with("/b.json"));
+            self.push(format!(
+                "distribution start: {}",
+                context.distribution.url()
+            ));
+        }
+
+        fn on_distribution_end(&self, context: &DistributionEnd<'_>) {
+            self.push(format!("distribution end: {}", context.advisories));
+        }
+
+        fn on_end(&self, context: &EndContext<'_>) {
+            self.push(format!("end: {}", context.error.is_none()));
+        }
+    }
+
+    impl RecordingHooks {
+        fn push(&self, event: String) {
+            self.0
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(event);
+        }
+
+        fn take(&self) -> Vec<String> {
+            std::mem::take(&mut self.0.lock().unwrap_or_else(PoisonError::into_inner))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_walk_hooks() -> anyhow::Result<()> {
+        let provider = MockProvider::new("https://example.com")?
+            .advisory("2024/a.json", document("A"))?
+            .advisory("2024/b.json", document("B"))?;
+        let expected = [
+            "start: 1",
+            "distribution start: https://example.com/.well-known/csaf/",
+            "distribution end: 1",
+            "end: true",
+        ];
+
+        let hooks = RecordingHooks::default();
+        let collector = CollectingVisitor::new();
+        Walker::new(provider.clone())
+            .with_hooks(hooks.clone())
+            .walk(RetrievingVisitor::new(provider.clone(), collector.clone()))
+            .await?;
+
+        collector.assert_urls(["https://example.com/.well-known/csaf/2024/a.json"]);
+        assert_eq!(hooks.take(), expected);
+
+        let collector = CollectingVisitor::new();
+        Walker::new(provider.clone())
+            .with_hooks(hooks.clone())
+            .walk_parallel(4, RetrievingVisitor::new(provider, collector.clone()))
+            .await?;
+
+        collector.assert_urls(["https://example.com/.well-known/csaf/2024/a.json"]);
+        assert_eq!(hooks.take(), expected);
+
+        Ok(())
+    }
+}
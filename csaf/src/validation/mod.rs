@@ -1,19 +1,30 @@
 //! Validation
 
 use crate::discover::{AsDiscovered, DiscoveredAdvisory};
+use crate::model::lifecycle::Lifecycle;
 use crate::retrieve::{
     AsRetrieved, RetrievalContext, RetrievalError, RetrievedAdvisory, RetrievedVisitor,
 };
+use crate::visitors::filter::normalize_tlp;
 use digest::Digest;
 use std::fmt::{Debug, Display, Formatter};
 use std::future::Future;
 use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
 use url::Url;
 use walker_common::{
     retrieve::RetrievedDigest,
-    utils::openpgp::PublicKey,
+    signature::Cleartext,
+    stage,
+    utils::openpgp::{KeyIssue, PublicKey},
     utils::url::Urlify,
-    validate::{openpgp, ValidationOptions},
+    validate::{
+        evidence::{ValidationEvidence, ValidationFailure},
+        openpgp,
+        sidecar::{self, SidecarError},
+        tls::{self, TlsViolation},
+        ValidationMode, ValidationOptions,
+    },
 };
 
 /// A validated CSAF document
@@ -22,10 +33,61 @@ use walker_common::{
 /// * The document could be retrieved
 /// * The digest matches or was absent
 /// * The signature was valid
+///
+/// Unless running in the [`ValidationMode::Collect`] mode, which passes on documents failing the
+/// digest or signature checks, recording the failures in the evidence.
+///
+/// The [`ValidationEvidence`] records how this was verified. The [`Lifecycle`] tells if the
+/// advisory was withdrawn, or superseded by others.
 #[derive(Clone, Debug)]
 pub struct ValidatedAdvisory {
     /// The retrieved advisory
     pub retrieved: RetrievedAdvisory,
+    /// The final URL, in case the advisory was served through a cross-origin redirect
+    pub cross_origin_redirect: Option<Url>,
+    /// The evidence of the validation, like the matching digest and the keys which signed it
+    pub evidence: ValidationEvidence,
+    /// The tracking status, and whether the advisory was withdrawn or superseded
+    pub lifecycle: Lifecycle,
+}
+
+impl ValidatedAdvisory {
+    fn new(retrieved: RetrievedAdvisory, evidence: ValidationEvidence) -> Self {
+        let cross_origin_redirect = retrieved
+            .metadata
+            .cross_origin_redirect(retrieved.retrieval_url())
+            .cloned();
+
+        if let Some(url) = &cross_origin_redirect {
+            tracing::warn!(
+                target: stage::VALIDATE,
+                stage = "validate",
+                url = %retrieved.url,
+                distribution = %retrieved.context.url(),
+                redirect = %url,
+                "Advisory was served through a cross-origin redirect"
+            );
+        }
+
+        let lifecycle = Lifecycle::parse(&retrieved.data);
+        if !lifecycle.is_current() {
+            tracing::debug!(
+                target: stage::VALIDATE,
+                stage = "validate",
+                url = %retrieved.url,
+                withdrawn = lifecycle.withdrawn,
+                superseded = lifecycle.superseded,
+                "Advisory is no longer current"
+            );
+        }
+
+        Self {
+            retrieved,
+            cross_origin_redirect,
+            evidence,
+            lifecycle,
+        }
+    }
 }
 
 impl Urlify for ValidatedAdvisory {
@@ -76,6 +138,49 @@ pub enum ValidationError {
         error: anyhow::Error,
         retrieved: RetrievedAdvisory,
     },
+    MalformedSidecar {
+        error: SidecarError,
+        retrieved: RetrievedAdvisory,
+    },
+    Tls {
+        error: TlsViolation,
+        retrieved: RetrievedAdvisory,
+    },
+    Policy {
+        reason: String,
+        retrieved: RetrievedAdvisory,
+    },
+}
+
+impl ValidationError {
+    /// Convert a failed integrity check into a failure, handing back the document.
+    ///
+    /// Other errors are returned unchanged.
+    fn into_failure(self) -> Result<(ValidationFailure, RetrievedAdvisory), Self> {
+        match self {
+            Self::DigestMismatch {
+                expected,
+                actual,
+                retrieved,
+            } => Ok((
+                ValidationFailure::DigestMismatch { expected, actual },
+                retrieved,
+            )),
+            Self::Signature { error, retrieved } => Ok((
+                ValidationFailure::Signature {
+                    reason: error.to_string(),
+                },
+                retrieved,
+            )),
+            Self::MalformedSidecar { error, retrieved } => Ok((
+                ValidationFailure::MalformedSidecar {
+                    reason: error.to_string(),
+                },
+                retrieved,
+            )),
+            other => Err(other),
+        }
+    }
 }
 
 impl AsDiscovered for ValidationError {
@@ -84,6 +189,9 @@ impl AsDiscovered for ValidationError {
             Self::Retrieval(err) => err.discovered(),
             Self::DigestMismatch { retrieved, .. } => retrieved.as_discovered(),
             Self::Signature { retrieved, .. } => retrieved.as_discovered(),
+            Self::MalformedSidecar { retrieved, .. } => retrieved.as_discovered(),
+            Self::Tls { retrieved, .. } => retrieved.as_discovered(),
+            Self::Policy { retrieved, .. } => retrieved.as_discovered(),
         }
     }
 }
@@ -94,6 +202,9 @@ impl Urlify for ValidationError {
             Self::Retrieval(err) => err.url(),
             Self::DigestMismatch { retrieved, .. } => &retrieved.url,
             Self::Signature { retrieved, .. } => &retrieved.url,
+            Self::MalformedSidecar { retrieved, .. } => &retrieved.url,
+            Self::Tls { retrieved, .. } => &retrieved.url,
+            Self::Policy { retrieved, .. } => &retrieved.url,
         }
     }
 }
@@ -116,12 +227,26 @@ impl Display for ValidationError {
             } => {
                 write!(f, "Invalid signature: {error}",)
             }
+            Self::MalformedSidecar {
+                error,
+                retrieved: _,
+            } => write!(f, "Malformed sidecar file: {error}"),
+            Self::Tls {
+                error,
+                retrieved: _,
+            } => write!(f, "TLS requirements not met: {error}"),
+            Self::Policy {
+                reason,
+                retrieved: _,
+            } => write!(f, "Validation policy not met: {reason}"),
         }
     }
 }
 
 pub struct ValidationContext<'c> {
     pub retrieval: &'c RetrievalContext<'c>,
+    /// Issues of the provider keys, like being expired or revoked
+    pub key_issues: &'c [KeyIssue],
 }
 
 impl<'c> Deref for ValidationContext<'c> {
@@ -181,7 +306,7 @@ where
     options: ValidationOptions,
 }
 
-enum ValidationProcessError {
+pub(crate) enum ValidationProcessError {
     /// Failed, but passing on to visitor
     Proceed(ValidationError),
     /// Failed, aborting processing
@@ -217,6 +342,11 @@ where
         self
     }
 
+    pub fn with_mode(mut self, mode: ValidationMode) -> Self {
+        self.options.mode = mode;
+        self
+    }
+
     /// Perform the actual validation.
     ///
     /// Returning either a processing error, or a result which will will be forwarded to the visitor.
@@ -225,56 +355,222 @@ where
         context: &InnerValidationContext<V::Context>,
         retrieved: RetrievedAdvisory,
     ) -> Result<ValidatedAdvisory, ValidationProcessError> {
-        if let Err((expected, actual)) = Self::validate_digest(&retrieved.sha256) {
-            return Err(ValidationProcessError::Proceed(
+        validate_offloaded(&self.options, &context.keys, retrieved).await
+    }
+}
+
+/// Validate a retrieved advisory, using the provided options and keys.
+pub(crate) fn validate(
+    options: &ValidationOptions,
+    keys: &[PublicKey],
+    mut retrieved: RetrievedAdvisory,
+) -> Result<ValidatedAdvisory, ValidationProcessError> {
+    if options.strict_tls {
+        if let Err(error) = tls::check(&retrieved.metadata) {
+            return Err(ValidationProcessError::Proceed(ValidationError::Tls {
+                error,
+                retrieved,
+            }));
+        }
+    }
+
+    let mut failures = vec![];
+
+    // when lenient, malformed digest files are ignored by the digest validation
+    let malformed = match options.lenient_digests {
+        true => None,
+        false => sidecar::check_digests(&retrieved.sha256, &retrieved.sha512).err(),
+    };
+
+    if let Some(error) = malformed {
+        retrieved = fail(
+            options,
+            &mut failures,
+            ValidationError::MalformedSidecar { error, retrieved },
+        )?;
+    } else {
+        if let Err((expected, actual)) = validate_digest(options, &retrieved.url, &retrieved.sha256)
+        {
+            retrieved = fail(
+                options,
+                &mut failures,
                 ValidationError::DigestMismatch {
                     expected,
                     actual,
                     retrieved,
                 },
-            ));
+            )?;
         }
-        if let Err((expected, actual)) = Self::validate_digest(&retrieved.sha512) {
-            return Err(ValidationProcessError::Proceed(
+        if let Err((expected, actual)) = validate_digest(options, &retrieved.url, &retrieved.sha512)
+        {
+            retrieved = fail(
+                options,
+                &mut failures,
                 ValidationError::DigestMismatch {
                     expected,
                     actual,
                     retrieved,
                 },
-            ));
+            )?;
+        }
+    }
+
+    // a cleartext signed document carries its signature, digests refer to the published document
+    if retrieved.signature.is_none() {
+        if let Some(cleartext) = Cleartext::split(&retrieved.data) {
+            retrieved.data = cleartext.content;
+            retrieved.signature = Some(cleartext.signature);
         }
+    }
 
-        if let Some(signature) = &retrieved.signature {
-            match openpgp::validate_signature(
-                &self.options,
-                &context.keys,
-                signature,
-                &retrieved.data,
-            ) {
-                Ok(()) => Ok(ValidatedAdvisory { retrieved }),
-                Err(error) => Err(ValidationProcessError::Proceed(
-                    ValidationError::Signature { error, retrieved },
-                )),
-            }
+    if let Err(reason) = validate_tlp(options, &retrieved) {
+        return Err(ValidationProcessError::Proceed(ValidationError::Policy {
+            reason,
+            retrieved,
+        }));
+    }
+
+    let mut signers = vec![];
+    if let Some(signature) = retrieved.signature.clone() {
+        if let Err(error) = sidecar::check_signature(&signature) {
+            retrieved = fail(
+                options,
+                &mut failures,
+                ValidationError::MalformedSidecar { error, retrieved },
+            )?;
         } else {
-            Ok(ValidatedAdvisory { retrieved })
+            match openpgp::validate_signature(options, keys, &signature, &retrieved.data) {
+                Ok(verified) => {
+                    for failed in &verified.failed {
+                        tracing::info!(
+                            target: stage::VALIDATE,
+                            stage = "validate",
+                            url = %retrieved.url,
+                            error = %failed,
+                            "Ignoring additional signature, which could not be verified"
+                        );
+                    }
+                    tracing::debug!(
+                        target: stage::VALIDATE,
+                        stage = "validate",
+                        url = %retrieved.url,
+                        signers = %verified.signers.join(", "),
+                        "Verified signature"
+                    );
+                    signers = verified.signers;
+                }
+                Err(error) => {
+                    retrieved = fail(
+                        options,
+                        &mut failures,
+                        ValidationError::Signature { error, retrieved },
+                    )?;
+                }
+            }
+        }
+    }
+
+    let evidence = ValidationEvidence::new(signers, options.validation_date)
+        .with_digests(&retrieved.sha256, &retrieved.sha512)
+        .with_failures(failures);
+    Ok(ValidatedAdvisory::new(retrieved, evidence))
+}
+
+/// Fail the validation of the document, or in the collect mode, record the failed integrity
+/// check and continue with the document.
+fn fail(
+    options: &ValidationOptions,
+    failures: &mut Vec<ValidationFailure>,
+    error: ValidationError,
+) -> Result<RetrievedAdvisory, ValidationProcessError> {
+    match options.mode {
+        ValidationMode::Strict => Err(ValidationProcessError::Proceed(error)),
+        ValidationMode::Collect => {
+            let (failure, retrieved) = error
+                .into_failure()
+                .map_err(ValidationProcessError::Proceed)?;
+            tracing::warn!(
+                target: stage::VALIDATE,
+                stage = "validate",
+                url = %retrieved.url,
+                failure = %failure,
+                "Passing on advisory, which failed an integrity check"
+            );
+            failures.push(failure);
+            Ok(retrieved)
         }
     }
+}
 
-    /// ensure that the digest matches if we have one
-    fn validate_digest<D: Digest>(
-        digest: &Option<RetrievedDigest<D>>,
-    ) -> Result<(), (String, String)> {
-        if let Some(digest) = &digest {
-            digest.validate().map_err(|(s1, s2)| (s1.to_string(), s2))?;
+/// Validate a retrieved advisory, on the blocking thread pool if the options request it.
+pub(crate) async fn validate_offloaded(
+    options: &ValidationOptions,
+    keys: &Arc<Vec<PublicKey>>,
+    retrieved: RetrievedAdvisory,
+) -> Result<ValidatedAdvisory, ValidationProcessError> {
+    match &options.offload {
+        Some(offload) => {
+            let options = options.clone();
+            let keys = keys.clone();
+            offload
+                .run(move || validate(&options, &keys, retrieved))
+                .await
         }
+        None => validate(options, keys, retrieved),
+    }
+}
+
+/// ensure that the TLP label is allowed, documents without a label are accepted
+fn validate_tlp(options: &ValidationOptions, retrieved: &RetrievedAdvisory) -> Result<(), String> {
+    if options.allowed_tlps.is_empty() {
+        return Ok(());
+    }
+
+    let Some(label) = retrieved.tlp_label() else {
+        return Ok(());
+    };
+
+    let normalized = normalize_tlp(&label);
+    if options
+        .allowed_tlps
+        .iter()
+        .any(|allowed| normalize_tlp(allowed) == normalized)
+    {
         Ok(())
+    } else {
+        Err(format!("TLP label not allowed: {label}"))
+    }
+}
+
+/// ensure that the digest matches if we have one
+fn validate_digest<D: Digest>(
+    options: &ValidationOptions,
+    url: &Url,
+    digest: &Option<RetrievedDigest<D>>,
+) -> Result<(), (String, String)> {
+    if let Some(digest) = &digest {
+        if let Err((expected, actual)) = digest.validate() {
+            if options.lenient_digests {
+                if let Some(err) = digest.malformed() {
+                    tracing::warn!(
+                        target: stage::VALIDATE,
+                        stage = "validate",
+                        url = %url,
+                        error = %err,
+                        "Ignoring malformed digest file"
+                    );
+                    return Ok(());
+                }
+            }
+            return Err((expected.to_string(), actual));
+        }
     }
+    Ok(())
 }
 
 pub struct InnerValidationContext<VC> {
     context: VC,
-    keys: Vec<PublicKey>,
+    keys: Arc<Vec<PublicKey>>,
 }
 
 impl<V> RetrievedVisitor for ValidationVisitor<V>
@@ -288,11 +584,33 @@ where
         &self,
         context: &RetrievalContext<'_>,
     ) -> Result<Self::Context, Self::Error> {
-        let keys = context.keys.clone();
+        let keys = Arc::new(context.keys.clone());
+
+        let key_issues = context
+            .keys
+            .iter()
+            .flat_map(|key| {
+                key.issues(
+                    self.options.validation_date,
+                    self.options.key_expiry_warning,
+                )
+            })
+            .collect::<Vec<_>>();
+        for issue in &key_issues {
+            tracing::warn!(
+                target: stage::VALIDATE,
+                stage = "validate",
+                issue = %issue,
+                "Provider key issue: {issue}"
+            );
+        }
 
         let context = self
             .visitor
-            .visit_context(&ValidationContext { retrieval: context })
+            .visit_context(&ValidationContext {
+                retrieval: context,
+                key_issues: &key_issues,
+            })
             .await
             .map_err(Error::Visitor)?;
 
@@ -306,11 +624,34 @@ where
     ) -> Result<(), Self::Error> {
         match outcome {
             Ok(advisory) => {
+                let url = advisory.url.clone();
+                let distribution = advisory.context.url().clone();
+
                 let result = match self.validate(context, advisory).await {
                     Ok(result) => Ok(result),
                     Err(ValidationProcessError::Proceed(err)) => Err(err),
                     Err(ValidationProcessError::Abort(err)) => return Err(Error::Validation(err)),
                 };
+
+                match &result {
+                    Ok(_) => tracing::debug!(
+                        target: stage::VALIDATE,
+                        stage = "validate",
+                        url = %url,
+                        distribution = %distribution,
+                        outcome = "valid",
+                        "Validated advisory"
+                    ),
+                    Err(err) => tracing::info!(
+                        target: stage::VALIDATE,
+                        stage = "validate",
+                        url = %url,
+                        distribution = %distribution,
+                        outcome = "invalid",
+                        error = %err,
+                        "Failed to validate advisory"
+                    ),
+                }
                 self.visitor
                     .visit_advisory(&context.context, result)
                     .await
@@ -326,3 +667,45 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{discovered, document, retrieved};
+
+    fn tampered() -> RetrievedAdvisory {
+        let mut retrieved = retrieved(
+            discovered("https://example.com/.well-known/csaf/white/2024/a.json").unwrap(),
+            document("a").to_string(),
+        );
+        if let Some(sha256) = &mut retrieved.sha256 {
+            sha256.expected = "0".repeat(64);
+        }
+        retrieved
+    }
+
+    #[test]
+    fn test_collect() {
+        let Err(ValidationProcessError::Proceed(ValidationError::DigestMismatch { .. })) =
+            validate(&ValidationOptions::new(), &[], tampered())
+        else {
+            panic!("strict mode must fail the document");
+        };
+
+        let Ok(validated) = validate(
+            &ValidationOptions::new().mode(ValidationMode::Collect),
+            &[],
+            tampered(),
+        ) else {
+            panic!("collect mode must pass on the document");
+        };
+
+        assert!(!validated.evidence.is_trusted());
+        assert!(matches!(
+            validated.evidence.failures.as_slice(),
+            [ValidationFailure::DigestMismatch { expected, .. }] if expected == &"0".repeat(64)
+        ));
+        // the remaining digest still matched
+        assert_eq!(validated.evidence.digest.unwrap().algorithm, "sha512");
+    }
+}
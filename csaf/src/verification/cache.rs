@@ -0,0 +1,149 @@
+//! Caching check results
+
+use crate::verification::check::Finding;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+use walker_common::store::write_replace;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CacheError {
+    #[error("failed to access cache file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to encode cache: {0}")]
+    Encode(#[from] serde_json::Error),
+}
+
+/// A persistent cache of check results, keyed by the SHA-256 digest of a document.
+///
+/// Each entry records the fingerprint of the checks which created it. Changing the checks, or
+/// their configuration, changes the fingerprint and so invalidates the entry.
+///
+/// Only entries which were used, or created, since loading the cache will be stored again. So
+/// documents which are no longer present get removed from the cache.
+#[derive(Debug)]
+pub struct CheckCache {
+    path: PathBuf,
+    previous: BTreeMap<String, CacheEntry>,
+    current: Mutex<BTreeMap<String, CacheEntry>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct CacheEntry {
+    /// The fingerprint of the checks which created the entry
+    fingerprint: String,
+    /// The findings, in the order of the checks
+    findings: Vec<Vec<Finding>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    entries: BTreeMap<String, CacheEntry>,
+}
+
+impl CheckCache {
+    /// Create a new, empty cache, which will be stored at the provided path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            previous: Default::default(),
+            current: Default::default(),
+        }
+    }
+
+    /// Load the cache from a file.
+    ///
+    /// A missing file results in an empty cache. A file which cannot be decoded is discarded.
+    pub async fn load(path: impl Into<PathBuf>) -> Result<Self, CacheError> {
+        let mut cache = Self::new(path);
+
+        let data = match tokio::fs::read(&cache.path).await {
+            Ok(data) => data,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(cache),
+            Err(err) => return Err(err.into()),
+        };
+
+        match serde_json::from_slice::<CacheFile>(&data) {
+            Ok(file) => {
+                log::debug!("Loaded {} cached check results", file.entries.len());
+                cache.previous = file.entries;
+            }
+            Err(err) => {
+                log::warn!("Discarding check cache ({}): {err}", cache.path.display());
+            }
+        }
+
+        Ok(cache)
+    }
+
+    /// Store the cache to its file.
+    pub async fn store(&self) -> Result<(), CacheError> {
+        let data = {
+            let current = self.current.lock().unwrap_or_else(PoisonError::into_inner);
+            serde_json::to_vec(&CacheFile {
+                entries: current.clone(),
+            })?
+        };
+
+        write_replace(&self.path, &data).await?;
+
+        Ok(())
+    }
+
+    /// Get the findings of a document, if they were created by checks with the same fingerprint.
+    pub(crate) fn get(&self, digest: &str, fingerprint: &str) -> Option<Vec<Vec<Finding>>> {
+        let mut current = self.current.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let entry = match current.get(digest) {
+            Some(entry) => entry,
+            None => {
+                let entry = self.previous.get(digest)?;
+                current.entry(digest.to_string()).or_insert(entry.clone())
+            }
+        };
+
+        (entry.fingerprint == fingerprint).then(|| entry.findings.clone())
+    }
+
+    /// Record the findings of a document.
+    pub(crate) fn insert(&self, digest: String, fingerprint: String, findings: Vec<Vec<Finding>>) {
+        self.current
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(
+                digest,
+                CacheEntry {
+                    fingerprint,
+                    findings,
+                },
+            );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint() {
+        let cache = CheckCache::new("cache.json");
+
+        assert_eq!(cache.get("a", "1"), None);
+
+        cache.insert(
+            "a".to_string(),
+            "1".to_string(),
+            vec![vec![Finding::warning("foo")], vec![]],
+        );
+
+        assert_eq!(
+            cache.get("a", "1"),
+            Some(vec![vec![Finding::warning("foo")], vec![]])
+        );
+        // different checks, different fingerprint
+        assert_eq!(cache.get("a", "2"), None);
+        assert_eq!(cache.get("b", "1"), None);
+    }
+}
@@ -1,4 +1,4 @@
-"undefined"!=typeof globalThis?globalThis:"undefined"!=typeof window?window:"undefined"!=typeof global?global:"undefined"!=typeof self&&self;function e(e){return e&&e.__esModule&&Object.prototype.hasOwnProperty.call(e,"default")?e.default:e}var t={exports:{}},a={};!function(e){function t(e,t){return{validate:e,compare:t}}Object.defineProperty(e,"__esModule",{value:!0}),e.formatNames=e.fastFormats=e.fullFormats=void 0,e.fullFormats={date:t(s,u),time:t(i,p),"date-time":t((function(e){const t=e.split(r);return 2===t.length&&s(t[0])&&i(t[1],!0)}),o),duration:/^P(?!$)((\d+Y)?(\d+M)?(\d+D)?(T(?=\d)(\d+H)?(\d+M)?(\d+S)?)?|(\d+W)?)$/,uri:function(e){return g.test(e)&&c.test(e)},"uri-reference":/^(?:[a-z][a-z0-9+\-.]*:)?(?:\/?\/(?:(?:[a-z0-9\-._~!$&'()*+,;=:]|%[0-9a-f]{2})*@)?(?:\[(?:(?:(?:(?:[0-9a-f]{1,4}:){6}|::(?:[0-9a-f]{1,4}:){5}|(?:[0-9a-f]{1,4})?::(?:[0-9a-f]{1,4}:){4}|(?:(?:[0-9a-f]{1,4}:){0,1}[0-9a-f]{1,4})?::(?:[0-9a-f]{1,4}:){3}|(?:(?:[0-9a-f]{1,4}:){0,2}[0-9a-f]{1,4})?::(?:[0-9a-f]{1,4}:){2}|(?:(?:[0-9a-f]{1,4}:){0,3}[0-9a-f]{1,4})?::[0-9a-f]{1,4}:|(?:(?:[0-9a-f]{1,4}:){0,4}[0-9a-f]{1,4})?::)(?:[0-9a-f]{1,4}:[0-9a-f]{1,4}|(?:(?:25[0-5]|2[0-4]\d|[01]?\d\d?)\.){3}(?:25[0-5]|2[0-4]\d|[01]?\d\d?))|(?:(?:[0-9a-f]{1,4}:){0,5}[0-9a-f]{1,4})?::[0-9a-f]{1,4}|(?:(?:[0-9a-f]{1,4}:){0,6}[0-9a-f]{1,4})?::)|[Vv][0-9a-f]+\.[a-z0-9\-._~!$&'()*+,;=:]+)\]|(?:(?:25[0-5]|2[0-4]\d|[01]?\d\d?)\.){3}(?:25[0-5]|2[0-4]\d|[01]?\d\d?)|(?:[a-z0-9\-._~!$&'"()*+,;=]|%[0-9a-f]{2})*)(?::\d*)?(?:\/(?:[a-z0-9\-._~!$&'"()*+,;=:@]|%[0-9a-f]{2})*)*|\/(?:(?:[a-z0-9\-._~!$&'"()*+,;=:@]|%[0-9a-f]{2})+(?:\/(?:[a-z0-9\-._~!$&'"()*+,;=:@]|%[0-9a-f]{2})*)*)?|(?:[a-z0-9\-._~!$&'"()*+,;=:@]|%[0-9a-f]{2})+(?:\/(?:[a-z0-9\-._~!$&'"()*+,;=:@]|%[0-9a-f]{2})*)*)?(?:\?(?:[a-z0-9\-._~!$&'"()*+,;=:@/?]|%[0-9a-f]{2})*)?(?:#(?:[a-z0-9\-._~!$&'"()*+,;=:@/?]|%[0-9a-f]{2})*)?$/i,"uri-template":/^(?:(?:[^\x00-\x20"'<>%\\^`{|}]|%[0-9a-f]{2})|\{[+#./;?&=,!@|]?(?:[a-z0-9_]|%[0-9a-f]{2})+(?::[1-9][0-9]{0,3}|\*)?(?:,(?:[a-z0-9_]|%[0-9a-f]{2})+(?::[1-9][0-9]{0,3}|\*)?)*\})*$/i,url:/^(?:https?|ftp):\/\/(?:\S+(?::\S*)?@)?(?:(?!(?:10|127)(?:\.\d{1,3}){3})(?!(?:169\.254|192\.168)(?:\.\d{1,3}){2})(?!172\.(?:1[6-9]|2\d|3[0-1])(?:\.\d{1,3}){2})(?:[1-9]\d?|1\d\d|2[01]\d|22[0-3])(?:\.(?:1?\d{1,2}|2[0-4]\d|25[0-5])){2}(?:\.(?:[1-9]\d?|1\d\d|2[0-4]\d|25[0-4]))|(?:(?:[a-z0-9\u{00a1}-\u{ffff}]+-)*[a-z0-9\u{00a1}-\u{ffff}]+)(?:\.(?:[a-z0-9\u{00a1}-\u{ffff}]+-)*[a-z0-9\u{00a1}-\u{ffff}]+)*(?:\.(?:[a-z\u{00a1}-\u{ffff}]{2,})))(?::\d{2,5})?(?:\/[^\s]*)?$/iu,email:/^[a-z0-9!#$%&'*+/=?^_`{|}~-]+(?:\.[a-z0-9!#$%&'*+/=?^_`{|}~-]+)*@(?:[a-z0-9](?:[a-z0-9-]*[a-z0-9])?\.)+[a-z0-9](?:[a-z0-9-]*[a-z0-9])?$/i,hostname:/^(?=.{1,253}\.?$)[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?(?:\.[a-z0-9](?:[-0-9a-z]{0,61}[0-9a-z])?)*\.?$/i,ipv4:/^(?:(?:25[0-5]|2[0-4]\d|[01]?\d\d?)\.){3}(?:25[0-5]|2[0-4]\d|[01]?\d\d?)$/,ipv6:/^((([0-9a-f]{1,4}:){7}([0-9a-f]{1,4}|:))|(([0-9a-f]{1,4}:){6}(:[0-9a-f]{1,4}|((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3})|:))|(([0-9a-f]{1,4}:){5}(((:[0-9a-f]{1,4}){1,2})|:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3})|:))|(([0-9a-f]{1,4}:){4}(((:[0-9a-f]{1,4}){1,3})|((:[0-9a-f]{1,4})?:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}))|:))|(([0-9a-f]{1,4}:){3}(((:[0-9a-f]{1,4}){1,4})|((:[0-9a-f]{1,4}){0,2}:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}))|:))|(([0-9a-f]{1,4}:){2}(((:[0-9a-f]{1,4}){1,5})|((:[0-9a-f]{1,4}){0,3}:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}))|:))|(([0-9a-f]{1,4}:){1}(((:[0-9a-f]{1,4}){1,6})|((:[0-9a-f]{1,4}){0,4}:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}))|:))|(:(((:[0-9a-f]{1,4}){1,7})|((:[0-9a-f]{1,4}){0,5}:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}))|:)))$/i,regex:function(e){if(x.test(e))return!1;try{return new RegExp(e),!0}catch(e){return!1}},uuid:/^(?:urn:uuid:)?[0-9a-f]{8}-(?:[0-9a-f]{4}-){3}[0-9a-f]{12}$/i,"json-pointer":/^(?:\/(?:[^~/]|~0|~1)*)*$/,"json-pointer-uri-fragment":/^#(?:\/(?:[a-z0-9_\-.!$&'()*+,;:=@]|%[0-9a-f]{2}|~0|~1)*)*$/i,"relative-json-pointer":/^(?:0|[1-9][0-9]*)(?:#|(?:\/(?:[^~/]|~0|~1)*)*)$/,byte:function(e){return f.lastIndex=0,f.test(e)},int32:{type:"number",validate:function(e){return Number.isInteger(e)&&e<=d&&e>=y}},int64:{type:"number",validate:function(e){return Number.isInteger(e)}},float:{type:"number",validate:b},double:{type:"number",validate:b},password:!0,binary:!0},e.fastFormats={...e.fullFormats,date:t(/^\d\d\d\d-[0-1]\d-[0-3]\d$/,u),time:t(/^(?:[0-2]\d:[0-5]\d:[0-5]\d|23:59:60)(?:\.\d+)?(?:z|[+-]\d\d(?::?\d\d)?)?$/i,p),"date-time":t(/^\d\d\d\d-[0-1]\d-[0-3]\d[t\s](?:[0-2]\d:[0-5]\d:[0-5]\d|23:59:60)(?:\.\d+)?(?:z|[+-]\d\d(?::?\d\d)?)$/i,o),uri:/^(?:[a-z][a-z0-9+\-.]*:)(?:\/?\/)?[^\s]*$/i,"uri-reference":/^(?:(?:[a-z][a-z0-9+\-.]*:)?\/?\/)?(?:[^\\\s#][^\s#]*)?(?:#[^\\\s]*)?$/i,email:/^[a-z0-9.!#$%&'*+/=?^_`{|}~-]+@[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?(?:\.[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?)*$/i},e.formatNames=Object.keys(e.fullFormats);const a=/^(\d\d\d\d)-(\d\d)-(\d\d)$/,n=[0,31,28,31,30,31,30,31,31,30,31,30,31];function s(e){const t=a.exec(e);if(!t)return!1;const s=+t[1],u=+t[2],l=+t[3];return u>=1&&u<=12&&l>=1&&l<=(2===u&&function(e){return e%4==0&&(e%100!=0||e%400==0)}(s)?29:n[u])}function u(e,t){if(e&&t)return e>t?1:e<t?-1:0}const l=/^(\d\d):(\d\d):(\d\d)(\.\d+)?(z|[+-]\d\d(?::?\d\d)?)?$/i;function i(e,t){const a=l.exec(e);if(!a)return!1;const n=+a[1],s=+a[2],u=+a[3],i=a[5];return(n<=23&&s<=59&&u<=59||23===n&&59===s&&60===u)&&(!t||""!==i)}function p(e,t){if(!e||!t)return;const a=l.exec(e),n=l.exec(t);return a&&n?(e=a[1]+a[2]+a[3]+(a[4]||""))>(t=n[1]+n[2]+n[3]+(n[4]||""))?1:e<t?-1:0:void 0}const r=/t|\s/i;function o(e,t){if(!e||!t)return;const[a,n]=e.split(r),[s,l]=t.split(r),i=u(a,s);return void 0!==i?i||p(n,l):void 0}const g=/\/|:/,c=/^(?:[a-z][a-z0-9+\-.]*:)(?:\/?\/(?:(?:[a-z0-9\-._~!$&'()*+,;=:]|%[0-9a-f]{2})*@)?(?:\[(?:(?:(?:(?:[0-9a-f]{1,4}:){6}|::(?:[0-9a-f]{1,4}:){5}|(?:[0-9a-f]{1,4})?::(?:[0-9a-f]{1,4}:){4}|(?:(?:[0-9a-f]{1,4}:){0,1}[0-9a-f]{1,4})?::(?:[0-9a-f]{1,4}:){3}|(?:(?:[0-9a-f]{1,4}:){0,2}[0-9a-f]{1,4})?::(?:[0-9a-f]{1,4}:){2}|(?:(?:[0-9a-f]{1,4}:){0,3}[0-9a-f]{1,4})?::[0-9a-f]{1,4}:|(?:(?:[0-9a-f]{1,4}:){0,4}[0-9a-f]{1,4})?::)(?:[0-9a-f]{1,4}:[0-9a-f]{1,4}|(?:(?:25[0-5]|2[0-4]\d|[01]?\d\d?)\.){3}(?:25[0-5]|2[0-4]\d|[01]?\d\d?))|(?:(?:[0-9a-f]{1,4}:){0,5}[0-9a-f]{1,4})?::[0-9a-f]{1,4}|(?:(?:[0-9a-f]{1,4}:){0,6}[0-9a-f]{1,4})?::)|[Vv][0-9a-f]+\.[a-z0-9\-._~!$&'()*+,;=:]+)\]|(?:(?:25[0-5]|2[0-4]\d|[01]?\d\d?)\.){3}(?:25[0-5]|2[0-4]\d|[01]?\d\d?)|(?:[a-z0-9\-._~!$&'()*+,;=]|%[0-9a-f]{2})*)(?::\d*)?(?:\/(?:[a-z0-9\-._~!$&'()*+,;=:@]|%[0-9a-f]{2})*)*|\/(?:(?:[a-z0-9\-._~!$&'()*+,;=:@]|%[0-9a-f]{2})+(?:\/(?:[a-z0-9\-._~!$&'()*+,;=:@]|%[0-9a-f]{2})*)*)?|(?:[a-z0-9\-._~!$&'()*+,;=:@]|%[0-9a-f]{2})+(?:\/(?:[a-z0-9\-._~!$&'()*+,;=:@]|%[0-9a-f]{2})*)*)(?:\?(?:[a-z0-9\-._~!$&'()*+,;=:@/?]|%[0-9a-f]{2})*)?(?:#(?:[a-z0-9\-._~!$&'()*+,;=:@/?]|%[0-9a-f]{2})*)?$/i;const f=/^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==|[A-Za-z0-9+/]{3}=)?$/gm;const y=-(2**31),d=2**31-1;function b(){return!0}const x=/[^\\]\\Z/}(a);var n,s={},u={exports:{}},l={},i={},p={},r={},o={},g={};function c(){return n||(n=1,function(e){Object.defineProperty(e,"__esModule",{value:!0}),e.regexpCode=e.getEsmExportName=e.getProperty=e.safeStringify=e.stringify=e.strConcat=e.addCodeArg=e.str=e._=e.nil=e._Code=e.Name=e.IDENTIFIER=e._CodeOrName=void 0;class t{}e._CodeOrName=t,e.IDENTIFIER=/^[a-z$_][a-z$_0-9]*$/i;class a extends t{constructor(t){if(super(),!e.IDENTIFIER.test(t))throw new Error("CodeGen: name must be a valid identifier");this.str=t}toString(){return this.str}emptyStr(){return!1}get names(){return{[this.str]:1}}}e.Name=a;class n extends t{constructor(e){super(),this._items="string"==typeof e?[e]:e}toString(){return this.str}emptyStr(){if(this._items.length>1)return!1;const e=this._items[0];return""===e||'""'===e}get str(){var e;return null!==(e=this._str)&&void 0!==e?e:this._str=this._items.reduce(((e,t)=>`${e}${t}`),"")}get names(){var e;return null!==(e=this._names)&&void 0!==e?e:this._names=this._items.reduce(((e,t)=>(t instanceof a&&(e[t.str]=(e[t.str]||0)+1),e)),{})}}function s(e,...t){const a=[e[0]];let s=0;for(;s<t.length;)i(a,t[s]),a.push(e[++s]);return new n(a)}e._Code=n,e.nil=new n(""),e._=s;const u=new n("+");function l(e,...t){const a=[r(e[0])];let s=0;for(;s<t.length;)a.push(u),i(a,t[s]),a.push(u,r(e[++s]));return function(e){let t=1;for(;t<e.length-1;){if(e[t]===u){const a=p(e[t-1],e[t+1]);if(void 0!==a){e.splice(t-1,3,a);continue}e[t++]="+"}t++}}(a),new n(a)}function i(e,t){var s;t instanceof n?e.push(...t._items):t instanceof a?e.push(t):e.push("number"==typeof(s=t)||"boolean"==typeof s||null===s?s:r(Array.isArray(s)?s.join(","):s))}function p(e,t){if('""'===t)return e;if('""'===e)return t;if("string"==typeof e){if(t instanceof a||'"'!==e[e.length-1])return;return"string"!=typeof t?`${e.slice(0,-1)}${t}"`:'"'===t[0]?e.slice(0,-1)+t.slice(1):void 0}return"string"!=typeof t||'"'!==t[0]||e instanceof a?void 0:`"${e}${t.slice(1)}`}function r(e){return JSON.stringify(e).replace(/\u2028/g,"\\u2028").replace(/\u2029/g,"\\u2029")}e.str=l,e.addCodeArg=i,e.strConcat=function(e,t){return t.emptyStr()?e:e.emptyStr()?t:l`${e}${t}`},e.stringify=function(e){return new n(r(e))},e.safeStringify=r,e.getProperty=function(t){return"string"==typeof t&&e.IDENTIFIER.test(t)?new n(`.${t}`):s`[${t}]`},e.getEsmExportName=function(t){if("string"==typeof t&&e.IDENTIFIER.test(t))return new n(`${t}`);throw new Error(`CodeGen: invalid export name: ${t}, use explicit $id name mapping`)},e.regexpCode=function(e){return new n(e.toString())}}(g)),g}var f,y,d={};function b(){return f||(f=1,function(e){Object.defineProperty(e,"__esModule",{value:!0}),e.ValueScope=e.ValueScopeName=e.Scope=e.varKinds=e.UsedValueState=void 0;const t=c();class a extends Error{constructor(e){super(`CodeGen: "code" for ${e} not defined`),this.value=e.value}}var n;!function(e){e[e.Started=0]="Started",e[e.Completed=1]="Completed"}(n=e.UsedValueState||(e.UsedValueState={})),e.varKinds={const:new t.Name("const"),let:new t.Name("let"),var:new t.Name("var")};class s{constructor({prefixes:e,parent:t}={}){this._names={},this._prefixes=e,this._parent=t}toName(e){return e instanceof t.Name?e:this.name(e)}name(e){return new t.Name(this._newName(e))}_newName(e){return`${e}${(this._names[e]||this._nameGroup(e)).index++}`}_nameGroup(e){var t,a;if((null===(a=null===(t=this._parent)||void 0===t?void 0:t._prefixes)||void 0===a?void 0:a.has(e))||this._prefixes&&!this._prefixes.has(e))throw new Error(`CodeGen: prefix "${e}" is not allowed in this scope`);return this._names[e]={prefix:e,index:0}}}e.Scope=s;class u extends t.Name{constructor(e,t){super(t),this.prefix=e}setValue(e,{property:a,itemIndex:n}){this.value=e,this.scopePath=t._`.${new t.Name(a)}[${n}]`}}e.ValueScopeName=u;const l=t._`\n`;e.ValueScope=class extends s{constructor(e){super(e),this._values={},this._scope=e.scope,this.opts={...e,_n:e.lines?l:t.nil}}get(){return this._scope}name(e){return new u(e,this._newName(e))}value(e,t){var a;if(void 0===t.ref)throw new Error("CodeGen: ref must be passed in value");const n=this.toName(e),{prefix:s}=n,u=null!==(a=t.key)&&void 0!==a?a:t.ref;let l=this._values[s];if(l){const e=l.get(u);if(e)return e}else l=this._values[s]=new Map;l.set(u,n);const i=this._scope[s]||(this._scope[s]=[]),p=i.length;return i[p]=t.ref,n.setValue(t,{property:s,itemIndex:p}),n}getValue(e,t){const a=this._values[e];if(a)return a.get(t)}scopeRefs(e,a=this._values){return this._reduceValues(a,(a=>{if(void 0===a.scopePath)throw new Error(`CodeGen: name "${a}" has no value`);return t._`${e}${a.scopePath}`}))}scopeCode(e=this._values,t,a){return this._reduceValues(e,(e=>{if(void 0===e.value)throw new Error(`CodeGen: name "${e}" has no value`);return e.value.code}),t,a)}_reduceValues(s,u,l={},i){let p=t.nil;for(const r in s){const o=s[r];if(!o)continue;const g=l[r]=l[r]||new Map;o.forEach((s=>{if(g.has(s))return;g.set(s,n.Started);let l=u(s);if(l){const a=this.opts.es5?e.varKinds.var:e.varKinds.const;p=t._`${p}${a} ${s} = ${l};${this.opts._n}`}else{if(!(l=null==i?void 0:i(s)))throw new a(s);p=t._`${p}${l}${this.opts._n}`}g.set(s,n.Completed)}))}return p}}}(d)),d}function x(){return y||(y=1,function(e){Object.defineProperty(e,"__esModule",{value:!0}),e.or=e.and=e.not=e.CodeGen=e.operators=e.varKinds=e.ValueScopeName=e.ValueScope=e.Scope=e.Name=e.regexpCode=e.stringify=e.getProperty=e.nil=e.strConcat=e.str=e._=void 0;const t=c(),a=b();var n=c();Object.defineProperty(e,"_",{enumerable:!0,get:function(){return n._}}),Object.defineProperty(e,"str",{enumerable:!0,get:function(){return n.str}}),Object.defineProperty(e,"strConcat",{enumerable:!0,get:function(){return n.strConcat}}),Object.defineProperty(e,"nil",{enumerable:!0,get:function(){return n.nil}}),Object.defineProperty(e,"getProperty",{enumerable:!0,get:function(){return n.getProperty}}),Object.defineProperty(e,"stringify",{enumerable:!0,get:function(){return n.stringify}}),Object.defineProperty(e,"regexpCode",{enumerable:!0,get:function(){return n.regexpCode}}),Object.defineProperty(e,"Name",{enumerable:!0,get:function(){return n.Name}});var s=b();Object.defineProperty(e,"Scope",{enumerable:!0,get:function(){return s.Scope}}),Object.defineProperty(e,"ValueScope",{enumerable:!0,get:function(){return s.ValueScope}}),Object.defineProperty(e,"ValueScopeName",{enumerable:!0,get:function(){return s.ValueScopeName}}),Object.defineProperty(e,"varKinds",{enumerable:!0,get:function(){return s.varKinds}}),e.operators={GT:new t._Code(">"),GTE:new t._Code(">="),LT:new t._Code("<"),LTE:new t._Code("<="),EQ:new t._Code("==="),NEQ:new t._Code("!=="),NOT:new t._Code("!"),OR:new t._Code("||"),AND:new t._Code("&&"),ADD:new t._Code("+")};class u{optimizeNodes(){return this}optimizeNames(e,t){return this}}class l extends u{constructor(e,t,a){super(),this.varKind=e,this.name=t,this.rhs=a}render({es5:e,_n:t}){const n=e?a.varKinds.var:this.varKind,s=void 0===this.rhs?"":` = ${this.rhs}`;return`${n} ${this.name}${s};`+t}optimizeNames(e,t){if(e[this.name.str])return this.rhs&&(this.rhs=T(this.rhs,e,t)),this}get names(){return this.rhs instanceof t._CodeOrName?this.rhs.names:{}}}class i extends u{constructor(e,t,a){super(),this.lhs=e,this.rhs=t,this.sideEffects=a}render({_n:e}){return`${this.lhs} = ${this.rhs};`+e}optimizeNames(e,a){if(!(this.lhs instanceof t.Name)||e[this.lhs.str]||this.sideEffects)return this.rhs=T(this.rhs,e,a),this}get names(){return k(this.lhs instanceof t.Name?{}:{...this.lhs.names},this.rhs)}}class p extends i{constructor(e,t,a,n){super(e,a,n),this.op=t}render({_n:e}){return`${this.lhs} ${this.op}= ${this.rhs};`+e}}class r extends u{constructor(e){super(),this.label=e,this.names={}}render({_n:e}){return`${this.label}:`+e}}class o extends u{constructor(e){super(),this.label=e,this.names={}}render({_n:e}){return`break${this.label?` ${this.label}`:""};`+e}}class g extends u{constructor(e){super(),this.error=e}render({_n:e}){return`throw ${this.error};`+e}get names(){return this.error.names}}class f extends u{constructor(e){super(),this.code=e}render({_n:e}){return`${this.code};`+e}optimizeNodes(){return`${this.code}`?this:void 0}optimizeNames(e,t){return this.code=T(this.code,e,t),this}get names(){return this.code instanceof t._CodeOrName?this.code.names:{}}}class y extends u{constructor(e=[]){super(),this.nodes=e}render(e){return this.nodes.reduce(((t,a)=>t+a.render(e)),"")}optimizeNodes(){const{nodes:e}=this;let t=e.length;for(;t--;){const a=e[t].optimizeNodes();Array.isArray(a)?e.splice(t,1,...a):a?e[t]=a:e.splice(t,1)}return e.length>0?this:void 0}optimizeNames(e,t){const{nodes:a}=this;let n=a.length;for(;n--;){const s=a[n];s.optimizeNames(e,t)||(D(e,s.names),a.splice(n,1))}return a.length>0?this:void 0}get names(){return this.nodes.reduce(((e,t)=>A(e,t.names)),{})}}class d extends y{render(e){return"{"+e._n+super.render(e)+"}"+e._n}}class x extends y{}class h extends d{}h.kind="else";class m extends d{constructor(e,t){super(t),this.condition=e}render(e){let t=`if(${this.condition})`+super.render(e);return this.else&&(t+="else "+this.else.render(e)),t}optimizeNodes(){super.optimizeNodes();const e=this.condition;if(!0===e)return this.nodes;let t=this.else;if(t){const e=t.optimizeNodes();t=this.else=Array.isArray(e)?new h(e):e}return t?!1===e?t instanceof m?t:t.nodes:this.nodes.length?this:new m(R(e),t instanceof m?[t]:t.nodes):!1!==e&&this.nodes.length?this:void 0}optimizeNames(e,t){var a;if(this.else=null===(a=this.else)||void 0===a?void 0:a.optimizeNames(e,t),super.optimizeNames(e,t)||this.else)return this.condition=T(this.condition,e,t),this}get names(){const e=super.names;return k(e,this.condition),this.else&&A(e,this.else.names),e}}m.kind="if";class _ extends d{}_.kind="for";class v extends _{constructor(e){super(),this.iteration=e}render(e){return`for(${this.iteration})`+super.render(e)}optimizeNames(e,t){if(super.optimizeNames(e,t))return this.iteration=T(this.iteration,e,t),this}get names(){return A(super.names,this.iteration.names)}}class E extends _{constructor(e,t,a,n){super(),this.varKind=e,this.name=t,this.from=a,this.to=n}render(e){const t=e.es5?a.varKinds.var:this.varKind,{name:n,from:s,to:u}=this;return`for(${t} ${n}=${s}; ${n}<${u}; ${n}++)`+super.render(e)}get names(){const e=k(super.names,this.from);return k(e,this.to)}}class w extends _{constructor(e,t,a,n){super(),this.loop=e,this.varKind=t,this.name=a,this.iterable=n}render(e){return`for(${this.varKind} ${this.name} ${this.loop} ${this.iterable})`+super.render(e)}optimizeNames(e,t){if(super.optimizeNames(e,t))return this.iterable=T(this.iterable,e,t),this}get names(){return A(super.names,this.iterable.names)}}class S extends d{constructor(e,t,a){super(),this.name=e,this.args=t,this.async=a}render(e){return`${this.async?"async ":""}function ${this.name}(${this.args})`+super.render(e)}}S.kind="func";class C extends y{render(e){return"return "+super.render(e)}}C.kind="return";class O extends d{render(e){let t="try"+super.render(e);return this.catch&&(t+=this.catch.render(e)),this.finally&&(t+=this.finally.render(e)),t}optimizeNodes(){var e,t;return super.optimizeNodes(),null===(e=this.catch)||void 0===e||e.optimizeNodes(),null===(t=this.finally)||void 0===t||t.optimizeNodes(),this}optimizeNames(e,t){var a,n;return super.optimizeNames(e,t),null===(a=this.catch)||void 0===a||a.optimizeNames(e,t),null===(n=this.finally)||void 0===n||n.optimizeNames(e,t),this}get names(){const e=super.names;return this.catch&&A(e,this.catch.names),this.finally&&A(e,this.finally.names),e}}class I extends d{constructor(e){super(),this.error=e}render(e){return`catch(${this.error})`+super.render(e)}}I.kind="catch";class N extends d{render(e){return"finally"+super.render(e)}}N.kind="finally";function A(e,t){for(const a in t)e[a]=(e[a]||0)+(t[a]||0);return e}function k(e,a){return a instanceof t._CodeOrName?A(e,a.names):e}function T(e,a,n){return e instanceof t.Name?u(e):(s=e)instanceof t._Code&&s._items.some((e=>e instanceof t.Name&&1===a[e.str]&&void 0!==n[e.str]))?new t._Code(e._items.reduce(((e,a)=>(a instanceof t.Name&&(a=u(a)),a instanceof t._Code?e.push(...a._items):e.push(a),e)),[])):e;var s;function u(e){const t=n[e.str];return void 0===t||1!==a[e.str]?e:(delete a[e.str],t)}}function D(e,t){for(const a in t)e[a]=(e[a]||0)-(t[a]||0)}function R(e){return"boolean"==typeof e||"number"==typeof e||null===e?!e:t._`!${L(e)}`}e.CodeGen=class{constructor(e,t={}){this._values={},this._blockStarts=[],this._constants={},this.opts={...t,_n:t.lines?"\n":""},this._extScope=e,this._scope=new a.Scope({parent:e}),this._nodes=[new x]}toString(){return this._root.render(this.opts)}name(e){return this._scope.name(e)}scopeName(e){return this._extScope.name(e)}scopeValue(e,t){const a=this._extScope.value(e,t);return(this._values[a.prefix]||(this._values[a.prefix]=new Set)).add(a),a}getScopeValue(e,t){return this._extScope.getValue(e,t)}scopeRefs(e){return this._extScope.scopeRefs(e,this._values)}scopeCode(){return this._extScope.scopeCode(this._values)}_def(e,t,a,n){const s=this._scope.toName(t);return void 0!==a&&n&&(this._constants[s.str]=a),this._leafNode(new l(e,s,a)),s}const(e,t,n){return this._def(a.varKinds.const,e,t,n)}let(e,t,n){return this._def(a.varKinds.let,e,t,n)}var(e,t,n){return this._def(a.varKinds.var,e,t,n)}assign(e,t,a){return this._leafNode(new i(e,t,a))}add(t,a){return this._leafNode(new p(t,e.operators.ADD,a))}code(e){return"function"==typeof e?e():e!==t.nil&&this._leafNode(new f(e)),this}object(...e){const a=["{"];for(const[n,s]of e)a.length>1&&a.push(","),a.push(n),(n!==s||this.opts.es5)&&(a.push(":"),(0,t.addCodeArg)(a,s));return a.push("}"),new t._Code(a)}if(e,t,a){if(this._blockNode(new m(e)),t&&a)this.code(t).else().code(a).endIf();else if(t)this.code(t).endIf();else if(a)throw new Error('CodeGen: "else" body without "then" body');return this}elseIf(e){return this._elseNode(new m(e))}else(){return this._elseNode(new h)}endIf(){return this._endBlockNode(m,h)}_for(e,t){return this._blockNode(e),t&&this.code(t).endFor(),this}for(e,t){return this._for(new v(e),t)}forRange(e,t,n,s,u=(this.opts.es5?a.varKinds.var:a.varKinds.let)){const l=this._scope.toName(e);return this._for(new E(u,l,t,n),(()=>s(l)))}forOf(e,n,s,u=a.varKinds.const){const l=this._scope.toName(e);if(this.opts.es5){const e=n instanceof t.Name?n:this.var("_arr",n);return this.forRange("_i",0,t._`${e}.length`,(a=>{this.var(l,t._`${e}[${a}]`),s(l)}))}return this._for(new w("of",u,l,n),(()=>s(l)))}forIn(e,n,s,u=(this.opts.es5?a.varKinds.var:a.varKinds.const)){if(this.opts.ownProperties)return this.forOf(e,t._`Object.keys(${n})`,s);const l=this._scope.toName(e);return this._for(new w("in",u,l,n),(()=>s(l)))}endFor(){return this._endBlockNode(_)}label(e){return this._leafNode(new r(e))}break(e){return this._leafNode(new o(e))}return(e){const t=new C;if(this._blockNode(t),this.code(e),1!==t.nodes.length)throw new Error('CodeGen: "return" should have one node');return this._endBlockNode(C)}try(e,t,a){if(!t&&!a)throw new Error('CodeGen: "try" without "catch" and "finally"');const n=new O;if(this._blockNode(n),this.code(e),t){const e=this.name("e");this._currNode=n.catch=new I(e),t(e)}return a&&(this._currNode=n.finally=new N,this.code(a)),this._endBlockNode(I,N)}throw(e){return this._leafNode(new g(e))}block(e,t){return this._blockStarts.push(this._nodes.length),e&&this.code(e).endBlock(t),this}endBlock(e){const t=this._blockStarts.pop();if(void 0===t)throw new Error("CodeGen: not in self-balancing block");const a=this._nodes.length-t;if(a<0||void 0!==e&&a!==e)throw new Error(`CodeGen: wrong number of nodes: ${a} vs ${e} expected`);return this._nodes.length=t,this}func(e,a=t.nil,n,s){return this._blockNode(new S(e,a,n)),s&&this.code(s).endFunc(),this}endFunc(){return this._endBlockNode(S)}optimize(e=1){for(;e-- >0;)this._root.optimizeNodes(),this._root.optimizeNames(this._root.names,this._constants)}_leafNode(e){return this._currNode.nodes.push(e),this}_blockNode(e){this._currNode.nodes.push(e),this._nodes.push(e)}_endBlockNode(e,t){const a=this._currNode;if(a instanceof e||t&&a instanceof t)return this._nodes.pop(),this;throw new Error(`CodeGen: not in block "${t?`${e.kind}/${t.kind}`:e.kind}"`)}_elseNode(e){const t=this._currNode;if(!(t instanceof m))throw new Error('CodeGen: "else" without "if"');return this._currNode=t.else=e,this}get _root(){return this._nodes[0]}get _currNode(){const e=this._nodes;return e[e.length-1]}set _currNode(e){const t=this._nodes;t[t.length-1]=e}},e.not=R;const P=$(e.operators.AND);e.and=function(...e){return e.reduce(P)};const M=$(e.operators.OR);function $(e){return(a,n)=>a===t.nil?n:n===t.nil?a:t._`${L(a)} ${e} ${L(n)}`}function L(e){return e instanceof t.Name?e:t._`(${e})`}e.or=function(...e){return e.reduce(M)}}(o)),o}var h={};!function(e){Object.defineProperty(e,"__esModule",{value:!0}),e.checkStrictMode=e.getErrorPath=e.Type=e.useFunc=e.setEvaluated=e.evaluatedPropsToName=e.mergeEvaluated=e.eachItem=e.unescapeJsonPointer=e.escapeJsonPointer=e.escapeFragment=e.unescapeFragment=e.schemaRefOrVal=e.schemaHasRulesButRef=e.schemaHasRules=e.checkUnknownRules=e.alwaysValidSchema=e.toHash=void 0;const t=x(),a=c();function n(e,t=e.schema){const{opts:a,self:n}=e;if(!a.strictSchema)return;if("boolean"==typeof t)return;const s=n.RULES.keywords;for(const a in t)s[a]||f(e,`unknown keyword: "${a}"`)}function s(e,t){if("boolean"==typeof e)return!e;for(const a in e)if(t[a])return!0;return!1}function u(e){return"number"==typeof e?`${e}`:e.replace(/~/g,"~0").replace(/\//g,"~1")}function l(e){return e.replace(/~1/g,"/").replace(/~0/g,"~")}function i({mergeNames:e,mergeToName:a,mergeValues:n,resultToName:s}){return(u,l,i,p)=>{const r=void 0===i?l:i instanceof t.Name?(l instanceof t.Name?e(u,l,i):a(u,l,i),i):l instanceof t.Name?(a(u,i,l),l):n(l,i);return p!==t.Name||r instanceof t.Name?r:s(u,r)}}function p(e,a){if(!0===a)return e.var("props",!0);const n=e.var("props",t._`{}`);return void 0!==a&&r(e,n,a),n}function r(e,a,n){Object.keys(n).forEach((n=>e.assign(t._`${a}${(0,t.getProperty)(n)}`,!0)))}e.toHash=function(e){const t={};for(const a of e)t[a]=!0;return t},e.alwaysValidSchema=function(e,t){return"boolean"==typeof t?t:0===Object.keys(t).length||(n(e,t),!s(t,e.self.RULES.all))},e.checkUnknownRules=n,e.schemaHasRules=s,e.schemaHasRulesButRef=function(e,t){if("boolean"==typeof e)return!e;for(const a in e)if("$ref"!==a&&t.all[a])return!0;return!1},e.schemaRefOrVal=function({topSchemaRef:e,schemaPath:a},n,s,u){if(!u){if("number"==typeof n||"boolean"==typeof n)return n;if("string"==typeof n)return t._`${n}`}return t._`${e}${a}${(0,t.getProperty)(s)}`},e.unescapeFragment=function(e){return l(decodeURIComponent(e))},e.escapeFragment=function(e){return encodeURIComponent(u(e))},e.escapeJsonPointer=u,e.unescapeJsonPointer=l,e.eachItem=function(e,t){if(Array.isArray(e))for(const a of e)t(a);else t(e)},e.mergeEvaluated={props:i({mergeNames:(e,a,n)=>e.if(t._`${n} !== true && ${a} !== undefined`,(()=>{e.if(t._`${a} === true`,(()=>e.assign(n,!0)),(()=>e.assign(n,t._`${n} || {}`).code(t._`Object.assign(${n}, ${a})`)))})),mergeToName:(e,a,n)=>e.if(t._`${n} !== true`,(()=>{!0===a?e.assign(n,!0):(e.assign(n,t._`${n} || {}`),r(e,n,a))})),mergeValues:(e,t)=>!0===e||{...e,...t},resultToName:p}),items:i({mergeNames:(e,a,n)=>e.if(t._`${n} !== true && ${a} !== undefined`,(()=>e.assign(n,t._`${a} === true ? true : ${n} > ${a} ? ${n} : ${a}`))),mergeToName:(e,a,n)=>e.if(t._`${n} !== true`,(()=>e.assign(n,!0===a||t._`${n} > ${a} ? ${n} : ${a}`))),mergeValues:(e,t)=>!0===e||Math.max(e,t),resultToName:(e,t)=>e.var("items",t)})},e.evaluatedPropsToName=p,e.setEvaluated=r;const o={};var g;function f(e,t,a=e.opts.strictSchema){if(a){if(t=`strict mode: ${t}`,!0===a)throw new Error(t);e.self.logger.warn(t)}}e.useFunc=function(e,t){return e.scopeValue("func",{ref:t,code:o[t.code]||(o[t.code]=new a._Code(t.code))})},function(e){e[e.Num=0]="Num",e[e.Str=1]="Str"}(g=e.Type||(e.Type={})),e.getErrorPath=function(e,a,n){if(e instanceof t.Name){const s=a===g.Num;return n?s?t._`"[" + ${e} + "]"`:t._`"['" + ${e} + "']"`:s?t._`"/" + ${e}`:t._`"/" + ${e}.replace(/~/g, "~0").replace(/\\//g, "~1")`}return n?(0,t.getProperty)(e).toString():"/"+u(e)},e.checkStrictMode=f}(h);var m={};Object.defineProperty(m,"__esModule",{value:!0});const _=x(),v={data:new _.Name("data"),valCxt:new _.Name("valCxt"),instancePath:new _.Name("instancePath"),parentData:new _.Name("parentData"),parentDataProperty:new _.Name("parentDataProperty"),rootData:new _.Name("rootData"),dynamicAnchors:new _.Name("dynamicAnchors"),vErrors:new _.Name("vErrors"),errors:new _.Name("errors"),this:new _.Name("this"),self:new _.Name("self"),scope:new _.Name("scope"),json:new _.Name("json"),jsonPos:new _.Name("jsonPos"),jsonLen:new _.Name("jsonLen"),jsonPart:new _.Name("jsonPart")};var E,w;function S(){return E||(E=1,function(e){Object.defineProperty(e,"__esModule",{value:!0}),e.extendErrors=e.resetErrorsCount=e.reportExtraError=e.reportError=e.keyword$DataError=e.keywordError=void 0;const t=x(),a=h,n=m;function s(e,a){const s=e.const("err",a);e.if(t._`${n.default.vErrors} === null`,(()=>e.assign(n.default.vErrors,t._`[${s}]`)),t._`${n.default.vErrors}.push(${s})`),e.code(t._`${n.default.errors}++`)}function u(e,a){const{gen:n,validateName:s,schemaEnv:u}=e;u.$async?n.throw(t._`new ${e.ValidationError}(${a})`):(n.assign(t._`${s}.errors`,a),n.return(!1))}e.keywordError={message:({keyword:e})=>t.str`must pass "${e}" keyword validation`},e.keyword$DataError={message:({keyword:e,schemaType:a})=>a?t.str`"${e}" keyword must be ${a} ($data)`:t.str`"${e}" keyword is invalid ($data)`},e.reportError=function(a,n=e.keywordError,l,p){const{it:r}=a,{gen:o,compositeRule:g,allErrors:c}=r,f=i(a,n,l);(null!=p?p:g||c)?s(o,f):u(r,t._`[${f}]`)},e.reportExtraError=function(t,a=e.keywordError,l){const{it:p}=t,{gen:r,compositeRule:o,allErrors:g}=p;s(r,i(t,a,l)),o||g||u(p,n.default.vErrors)},e.resetErrorsCount=function(e,a){e.assign(n.default.errors,a),e.if(t._`${n.default.vErrors} !== null`,(()=>e.if(a,(()=>e.assign(t._`${n.default.vErrors}.length`,a)),(()=>e.assign(n.default.vErrors,null)))))},e.extendErrors=function({gen:e,keyword:a,schemaValue:s,data:u,errsCount:l,it:i}){if(void 0===l)throw new Error("ajv implementation error");const p=e.name("err");e.forRange("i",l,n.default.errors,(l=>{e.const(p,t._`${n.default.vErrors}[${l}]`),e.if(t._`${p}.instancePath === undefined`,(()=>e.assign(t._`${p}.instancePath`,(0,t.strConcat)(n.default.instancePath,i.errorPath)))),e.assign(t._`${p}.schemaPath`,t.str`${i.errSchemaPath}/${a}`),i.opts.verbose&&(e.assign(t._`${p}.schema`,s),e.assign(t._`${p}.data`,u))}))};const l={keyword:new t.Name("keyword"),schemaPath:new t.Name("schemaPath"),params:new t.Name("params"),propertyName:new t.Name("propertyName"),message:new t.Name("message"),schema:new t.Name("schema"),parentSchema:new t.Name("parentSchema")};function i(e,a,s){const{createErrors:u}=e.it;return!1===u?t._`{}`:function(e,a,s={}){const{gen:u,it:i}=e,o=[p(i,s),r(e,s)];return function(e,{params:a,message:s},u){const{keyword:i,data:p,schemaValue:r,it:o}=e,{opts:g,propertyName:c,topSchemaRef:f,schemaPath:y}=o;u.push([l.keyword,i],[l.params,"function"==typeof a?a(e):a||t._`{}`]),g.messages&&u.push([l.message,"function"==typeof s?s(e):s]);g.verbose&&u.push([l.schema,r],[l.parentSchema,t._`${f}${y}`],[n.default.data,p]);c&&u.push([l.propertyName,c])}(e,a,o),u.object(...o)}(e,a,s)}function p({errorPath:e},{instancePath:s}){const u=s?t.str`${e}${(0,a.getErrorPath)(s,a.Type.Str)}`:e;return[n.default.instancePath,(0,t.strConcat)(n.default.instancePath,u)]}function r({keyword:e,it:{errSchemaPath:n}},{schemaPath:s,parentSchema:u}){let i=u?n:t.str`${n}/${e}`;return s&&(i=t.str`${i}${(0,a.getErrorPath)(s,a.Type.Str)}`),[l.schemaPath,i]}}(r)),r}m.default=v;var C={},O={};Object.defineProperty(O,"__esModule",{value:!0}),O.getRules=O.isJSONType=void 0;const I=new Set(["string","number","integer","boolean","null","object","array"]);O.isJSONType=function(e){return"string"==typeof e&&I.has(e)},O.getRules=function(){const e={number:{type:"number",rules:[]},string:{type:"string",rules:[]},array:{type:"array",rules:[]},object:{type:"object",rules:[]}};return{types:{...e,integer:!0,boolean:!0,null:!0},rules:[{rules:[]},e.number,e.string,e.array,e.object],post:{rules:[]},all:{},keywords:{}}};var N,A={};function k(){if(N)return A;function e(e,a){return a.rules.some((a=>t(e,a)))}function t(e,t){var a;return void 0!==e[t.keyword]||(null===(a=t.definition.implements)||void 0===a?void 0:a.some((t=>void 0!==e[t])))}return N=1,Object.defineProperty(A,"__esModule",{value:!0}),A.shouldUseRule=A.shouldUseGroup=A.schemaHasRulesForType=void 0,A.schemaHasRulesForType=function({schema:t,self:a},n){const s=a.RULES.types[n];return s&&!0!==s&&e(t,s)},A.shouldUseGroup=e,A.shouldUseRule=t,A}!function(e){Object.defineProperty(e,"__esModule",{value:!0}),e.reportTypeError=e.checkDataTypes=e.checkDataType=e.coerceAndCheckDataType=e.getJSONTypes=e.getSchemaTypes=e.DataType=void 0;const t=O,a=k(),n=S(),s=x(),u=h;var l;function i(e){const a=Array.isArray(e)?e:e?[e]:[];if(a.every(t.isJSONType))return a;throw new Error("type must be JSONType or JSONType[]: "+a.join(","))}!function(e){e[e.Correct=0]="Correct",e[e.Wrong=1]="Wrong"}(l=e.DataType||(e.DataType={})),e.getSchemaTypes=function(e){const t=i(e.type);if(t.includes("null")){if(!1===e.nullable)throw new Error("type: null contradicts nullable: false")}else{if(!t.length&&void 0!==e.nullable)throw new Error('"nullable" cannot be used without "type"');!0===e.nullable&&t.push("null")}return t},e.getJSONTypes=i,e.coerceAndCheckDataType=function(e,t){const{gen:n,data:u,opts:i}=e,r=function(e,t){return t?e.filter((e=>p.has(e)||"array"===t&&"array"===e)):[]}(t,i.coerceTypes),g=t.length>0&&!(0===r.length&&1===t.length&&(0,a.schemaHasRulesForType)(e,t[0]));if(g){const a=o(t,u,i.strictNumbers,l.Wrong);n.if(a,(()=>{r.length?function(e,t,a){const{gen:n,data:u,opts:l}=e,i=n.let("dataType",s._`typeof ${u}`),r=n.let("coerced",s._`undefined`);"array"===l.coerceTypes&&n.if(s._`${i} == 'object' && Array.isArray(${u}) && ${u}.length == 1`,(()=>n.assign(u,s._`${u}[0]`).assign(i,s._`typeof ${u}`).if(o(t,u,l.strictNumbers),(()=>n.assign(r,u)))));n.if(s._`${r} !== undefined`);for(const e of a)(p.has(e)||"array"===e&&"array"===l.coerceTypes)&&g(e);function g(e){switch(e){case"string":return void n.elseIf(s._`${i} == "number" || ${i} == "boolean"`).assign(r,s._`"" + ${u}`).elseIf(s._`${u} === null`).assign(r,s._`""`);case"number":return void n.elseIf(s._`${i} == "boolean" || ${u} === null
+!function(){"use strict";"undefined"!=typeof globalThis?globalThis:"undefined"!=typeof window?window:"undefined"!=typeof global?global:"undefined"!=typeof self&&self;function e(e){return e&&e.__esModule&&Object.prototype.hasOwnProperty.call(e,"default")?e.default:e}var t={exports:{}},a={};!function(e){function t(e,t){return{validate:e,compare:t}}Object.defineProperty(e,"__esModule",{value:!0}),e.formatNames=e.fastFormats=e.fullFormats=void 0,e.fullFormats={date:t(s,u),time:t(i,p),"date-time":t((function(e){const t=e.split(r);return 2===t.length&&s(t[0])&&i(t[1],!0)}),o),duration:/^P(?!$)((\d+Y)?(\d+M)?(\d+D)?(T(?=\d)(\d+H)?(\d+M)?(\d+S)?)?|(\d+W)?)$/,uri:function(e){return g.test(e)&&c.test(e)},"uri-reference":/^(?:[a-z][a-z0-9+\-.]*:)?(?:\/?\/(?:(?:[a-z0-9\-._~!$&'()*+,;=:]|%[0-9a-f]{2})*@)?(?:\[(?:(?:(?:(?:[0-9a-f]{1,4}:){6}|::(?:[0-9a-f]{1,4}:){5}|(?:[0-9a-f]{1,4})?::(?:[0-9a-f]{1,4}:){4}|(?:(?:[0-9a-f]{1,4}:){0,1}[0-9a-f]{1,4})?::(?:[0-9a-f]{1,4}:){3}|(?:(?:[0-9a-f]{1,4}:){0,2}[0-9a-f]{1,4})?::(?:[0-9a-f]{1,4}:){2}|(?:(?:[0-9a-f]{1,4}:){0,3}[0-9a-f]{1,4})?::[0-9a-f]{1,4}:|(?:(?:[0-9a-f]{1,4}:){0,4}[0-9a-f]{1,4})?::)(?:[0-9a-f]{1,4}:[0-9a-f]{1,4}|(?:(?:25[0-5]|2[0-4]\d|[01]?\d\d?)\.){3}(?:25[0-5]|2[0-4]\d|[01]?\d\d?))|(?:(?:[0-9a-f]{1,4}:){0,5}[0-9a-f]{1,4})?::[0-9a-f]{1,4}|(?:(?:[0-9a-f]{1,4}:){0,6}[0-9a-f]{1,4})?::)|[Vv][0-9a-f]+\.[a-z0-9\-._~!$&'()*+,;=:]+)\]|(?:(?:25[0-5]|2[0-4]\d|[01]?\d\d?)\.){3}(?:25[0-5]|2[0-4]\d|[01]?\d\d?)|(?:[a-z0-9\-._~!$&'"()*+,;=]|%[0-9a-f]{2})*)(?::\d*)?(?:\/(?:[a-z0-9\-._~!$&'"()*+,;=:@]|%[0-9a-f]{2})*)*|\/(?:(?:[a-z0-9\-._~!$&'"()*+,;=:@]|%[0-9a-f]{2})+(?:\/(?:[a-z0-9\-._~!$&'"()*+,;=:@]|%[0-9a-f]{2})*)*)?|(?:[a-z0-9\-._~!$&'"()*+,;=:@]|%[0-9a-f]{2})+(?:\/(?:[a-z0-9\-._~!$&'"()*+,;=:@]|%[0-9a-f]{2})*)*)?(?:\?(?:[a-z0-9\-._~!$&'"()*+,;=:@/?]|%[0-9a-f]{2})*)?(?:#(?:[a-z0-9\-._~!$&'"()*+,;=:@/?]|%[0-9a-f]{2})*)?$/i,"uri-template":/^(?:(?:[^\x00-\x20"'<>%\\^`{|}]|%[0-9a-f]{2})|\{[+#./;?&=,!@|]?(?:[a-z0-9_]|%[0-9a-f]{2})+(?::[1-9][0-9]{0,3}|\*)?(?:,(?:[a-z0-9_]|%[0-9a-f]{2})+(?::[1-9][0-9]{0,3}|\*)?)*\})*$/i,url:/^(?:https?|ftp):\/\/(?:\S+(?::\S*)?@)?(?:(?!(?:10|127)(?:\.\d{1,3}){3})(?!(?:169\.254|192\.168)(?:\.\d{1,3}){2})(?!172\.(?:1[6-9]|2\d|3[0-1])(?:\.\d{1,3}){2})(?:[1-9]\d?|1\d\d|2[01]\d|22[0-3])(?:\.(?:1?\d{1,2}|2[0-4]\d|25[0-5])){2}(?:\.(?:[1-9]\d?|1\d\d|2[0-4]\d|25[0-4]))|(?:(?:[a-z0-9\u{00a1}-\u{ffff}]+-)*[a-z0-9\u{00a1}-\u{ffff}]+)(?:\.(?:[a-z0-9\u{00a1}-\u{ffff}]+-)*[a-z0-9\u{00a1}-\u{ffff}]+)*(?:\.(?:[a-z\u{00a1}-\u{ffff}]{2,})))(?::\d{2,5})?(?:\/[^\s]*)?$/iu,email:/^[a-z0-9!#$%&'*+/=?^_`{|}~-]+(?:\.[a-z0-9!#$%&'*+/=?^_`{|}~-]+)*@(?:[a-z0-9](?:[a-z0-9-]*[a-z0-9])?\.)+[a-z0-9](?:[a-z0-9-]*[a-z0-9])?$/i,hostname:/^(?=.{1,253}\.?$)[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?(?:\.[a-z0-9](?:[-0-9a-z]{0,61}[0-9a-z])?)*\.?$/i,ipv4:/^(?:(?:25[0-5]|2[0-4]\d|[01]?\d\d?)\.){3}(?:25[0-5]|2[0-4]\d|[01]?\d\d?)$/,ipv6:/^((([0-9a-f]{1,4}:){7}([0-9a-f]{1,4}|:))|(([0-9a-f]{1,4}:){6}(:[0-9a-f]{1,4}|((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3})|:))|(([0-9a-f]{1,4}:){5}(((:[0-9a-f]{1,4}){1,2})|:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3})|:))|(([0-9a-f]{1,4}:){4}(((:[0-9a-f]{1,4}){1,3})|((:[0-9a-f]{1,4})?:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}))|:))|(([0-9a-f]{1,4}:){3}(((:[0-9a-f]{1,4}){1,4})|((:[0-9a-f]{1,4}){0,2}:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}))|:))|(([0-9a-f]{1,4}:){2}(((:[0-9a-f]{1,4}){1,5})|((:[0-9a-f]{1,4}){0,3}:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}))|:))|(([0-9a-f]{1,4}:){1}(((:[0-9a-f]{1,4}){1,6})|((:[0-9a-f]{1,4}){0,4}:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}))|:))|(:(((:[0-9a-f]{1,4}){1,7})|((:[0-9a-f]{1,4}){0,5}:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}))|:)))$/i,regex:function(e){if(x.test(e))return!1;try{return new RegExp(e),!0}catch(e){return!1}},uuid:/^(?:urn:uuid:)?[0-9a-f]{8}-(?:[0-9a-f]{4}-){3}[0-9a-f]{12}$/i,"json-pointer":/^(?:\/(?:[^~/]|~0|~1)*)*$/,"json-pointer-uri-fragment":/^#(?:\/(?:[a-z0-9_\-.!$&'()*+,;:=@]|%[0-9a-f]{2}|~0|~1)*)*$/i,"relative-json-pointer":/^(?:0|[1-9][0-9]*)(?:#|(?:\/(?:[^~/]|~0|~1)*)*)$/,byte:function(e){return f.lastIndex=0,f.test(e)},int32:{type:"number",validate:function(e){return Number.isInteger(e)&&e<=d&&e>=y}},int64:{type:"number",validate:function(e){return Number.isInteger(e)}},float:{type:"number",validate:b},double:{type:"number",validate:b},password:!0,binary:!0},e.fastFormats={...e.fullFormats,date:t(/^\d\d\d\d-[0-1]\d-[0-3]\d$/,u),time:t(/^(?:[0-2]\d:[0-5]\d:[0-5]\d|23:59:60)(?:\.\d+)?(?:z|[+-]\d\d(?::?\d\d)?)?$/i,p),"date-time":t(/^\d\d\d\d-[0-1]\d-[0-3]\d[t\s](?:[0-2]\d:[0-5]\d:[0-5]\d|23:59:60)(?:\.\d+)?(?:z|[+-]\d\d(?::?\d\d)?)$/i,o),uri:/^(?:[a-z][a-z0-9+\-.]*:)(?:\/?\/)?[^\s]*$/i,"uri-reference":/^(?:(?:[a-z][a-z0-9+\-.]*:)?\/?\/)?(?:[^\\\s#][^\s#]*)?(?:#[^\\\s]*)?$/i,email:/^[a-z0-9.!#$%&'*+/=?^_`{|}~-]+@[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?(?:\.[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?)*$/i},e.formatNames=Object.keys(e.fullFormats);const a=/^(\d\d\d\d)-(\d\d)-(\d\d)$/,n=[0,31,28,31,30,31,30,31,31,30,31,30,31];function s(e){const t=a.exec(e);if(!t)return!1;const s=+t[1],u=+t[2],l=+t[3];return u>=1&&u<=12&&l>=1&&l<=(2===u&&function(e){return e%4==0&&(e%100!=0||e%400==0)}(s)?29:n[u])}function u(e,t){if(e&&t)return e>t?1:e<t?-1:0}const l=/^(\d\d):(\d\d):(\d\d)(\.\d+)?(z|[+-]\d\d(?::?\d\d)?)?$/i;function i(e,t){const a=l.exec(e);if(!a)return!1;const n=+a[1],s=+a[2],u=+a[3],i=a[5];return(n<=23&&s<=59&&u<=59||23===n&&59===s&&60===u)&&(!t||""!==i)}function p(e,t){if(!e||!t)return;const a=l.exec(e),n=l.exec(t);return a&&n?(e=a[1]+a[2]+a[3]+(a[4]||""))>(t=n[1]+n[2]+n[3]+(n[4]||""))?1:e<t?-1:0:void 0}const r=/t|\s/i;function o(e,t){if(!e||!t)return;const[a,n]=e.split(r),[s,l]=t.split(r),i=u(a,s);return void 0!==i?i||p(n,l):void 0}const g=/\/|:/,c=/^(?:[a-z][a-z0-9+\-.]*:)(?:\/?\/(?:(?:[a-z0-9\-._~!$&'()*+,;=:]|%[0-9a-f]{2})*@)?(?:\[(?:(?:(?:(?:[0-9a-f]{1,4}:){6}|::(?:[0-9a-f]{1,4}:){5}|(?:[0-9a-f]{1,4})?::(?:[0-9a-f]{1,4}:){4}|(?:(?:[0-9a-f]{1,4}:){0,1}[0-9a-f]{1,4})?::(?:[0-9a-f]{1,4}:){3}|(?:(?:[0-9a-f]{1,4}:){0,2}[0-9a-f]{1,4})?::(?:[0-9a-f]{1,4}:){2}|(?:(?:[0-9a-f]{1,4}:){0,3}[0-9a-f]{1,4})?::[0-9a-f]{1,4}:|(?:(?:[0-9a-f]{1,4}:){0,4}[0-9a-f]{1,4})?::)(?:[0-9a-f]{1,4}:[0-9a-f]{1,4}|(?:(?:25[0-5]|2[0-4]\d|[01]?\d\d?)\.){3}(?:25[0-5]|2[0-4]\d|[01]?\d\d?))|(?:(?:[0-9a-f]{1,4}:){0,5}[0-9a-f]{1,4})?::[0-9a-f]{1,4}|(?:(?:[0-9a-f]{1,4}:){0,6}[0-9a-f]{1,4})?::)|[Vv][0-9a-f]+\.[a-z0-9\-._~!$&'()*+,;=:]+)\]|(?:(?:25[0-5]|2[0-4]\d|[01]?\d\d?)\.){3}(?:25[0-5]|2[0-4]\d|[01]?\d\d?)|(?:[a-z0-9\-._~!$&'()*+,;=]|%[0-9a-f]{2})*)(?::\d*)?(?:\/(?:[a-z0-9\-._~!$&'()*+,;=:@]|%[0-9a-f]{2})*)*|\/(?:(?:[a-z0-9\-._~!$&'()*+,;=:@]|%[0-9a-f]{2})+(?:\/(?:[a-z0-9\-._~!$&'()*+,;=:@]|%[0-9a-f]{2})*)*)?|(?:[a-z0-9\-._~!$&'()*+,;=:@]|%[0-9a-f]{2})+(?:\/(?:[a-z0-9\-._~!$&'()*+,;=:@]|%[0-9a-f]{2})*)*)(?:\?(?:[a-z0-9\-._~!$&'()*+,;=:@/?]|%[0-9a-f]{2})*)?(?:#(?:[a-z0-9\-._~!$&'()*+,;=:@/?]|%[0-9a-f]{2})*)?$/i;const f=/^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==|[A-Za-z0-9+/]{3}=)?$/gm;const y=-(2**31),d=2**31-1;function b(){return!0}const x=/[^\\]\\Z/}(a);var n,s={},u={exports:{}},l={},i={},p={},r={},o={},g={};function c(){return n||(n=1,function(e){Object.defineProperty(e,"__esModule",{value:!0}),e.regexpCode=e.getEsmExportName=e.getProperty=e.safeStringify=e.stringify=e.strConcat=e.addCodeArg=e.str=e._=e.nil=e._Code=e.Name=e.IDENTIFIER=e._CodeOrName=void 0;class t{}e._CodeOrName=t,e.IDENTIFIER=/^[a-z$_][a-z$_0-9]*$/i;class a extends t{constructor(t){if(super(),!e.IDENTIFIER.test(t))throw new Error("CodeGen: name must be a valid identifier");this.str=t}toString(){return this.str}emptyStr(){return!1}get names(){return{[this.str]:1}}}e.Name=a;class n extends t{constructor(e){super(),this._items="string"==typeof e?[e]:e}toString(){return this.str}emptyStr(){if(this._items.length>1)return!1;const e=this._items[0];return""===e||'""'===e}get str(){var e;return null!==(e=this._str)&&void 0!==e?e:this._str=this._items.reduce(((e,t)=>`${e}${t}`),"")}get names(){var e;return null!==(e=this._names)&&void 0!==e?e:this._names=this._items.reduce(((e,t)=>(t instanceof a&&(e[t.str]=(e[t.str]||0)+1),e)),{})}}function s(e,...t){const a=[e[0]];let s=0;for(;s<t.length;)i(a,t[s]),a.push(e[++s]);return new n(a)}e._Code=n,e.nil=new n(""),e._=s;const u=new n("+");function l(e,...t){const a=[r(e[0])];let s=0;for(;s<t.length;)a.push(u),i(a,t[s]),a.push(u,r(e[++s]));return function(e){let t=1;for(;t<e.length-1;){if(e[t]===u){const a=p(e[t-1],e[t+1]);if(void 0!==a){e.splice(t-1,3,a);continue}e[t++]="+"}t++}}(a),new n(a)}function i(e,t){var s;t instanceof n?e.push(...t._items):t instanceof a?e.push(t):e.push("number"==typeof(s=t)||"boolean"==typeof s||null===s?s:r(Array.isArray(s)?s.join(","):s))}function p(e,t){if('""'===t)return e;if('""'===e)return t;if("string"==typeof e){if(t instanceof a||'"'!==e[e.length-1])return;return"string"!=typeof t?`${e.slice(0,-1)}${t}"`:'"'===t[0]?e.slice(0,-1)+t.slice(1):void 0}return"string"!=typeof t||'"'!==t[0]||e instanceof a?void 0:`"${e}${t.slice(1)}`}function r(e){return JSON.stringify(e).replace(/\u2028/g,"\\u2028").replace(/\u2029/g,"\\u2029")}e.str=l,e.addCodeArg=i,e.strConcat=function(e,t){return t.emptyStr()?e:e.emptyStr()?t:l`${e}${t}`},e.stringify=function(e){return new n(r(e))},e.safeStringify=r,e.getProperty=function(t){return"string"==typeof t&&e.IDENTIFIER.test(t)?new n(`.${t}`):s`[${t}]`},e.getEsmExportName=function(t){if("string"==typeof t&&e.IDENTIFIER.test(t))return new n(`${t}`);throw new Error(`CodeGen: invalid export name: ${t}, use explicit $id name mapping`)},e.regexpCode=function(e){return new n(e.toString())}}(g)),g}var f,y,d={};function b(){return f||(f=1,function(e){Object.defineProperty(e,"__esModule",{value:!0}),e.ValueScope=e.ValueScopeName=e.Scope=e.varKinds=e.UsedValueState=void 0;const t=c();class a extends Error{constructor(e){super(`CodeGen: "code" for ${e} not defined`),this.value=e.value}}var n;!function(e){e[e.Started=0]="Started",e[e.Completed=1]="Completed"}(n=e.UsedValueState||(e.UsedValueState={})),e.varKinds={const:new t.Name("const"),let:new t.Name("let"),var:new t.Name("var")};class s{constructor({prefixes:e,parent:t}={}){this._names={},this._prefixes=e,this._parent=t}toName(e){return e instanceof t.Name?e:this.name(e)}name(e){return new t.Name(this._newName(e))}_newName(e){return`${e}${(this._names[e]||this._nameGroup(e)).index++}`}_nameGroup(e){var t,a;if((null===(a=null===(t=this._parent)||void 0===t?void 0:t._prefixes)||void 0===a?void 0:a.has(e))||this._prefixes&&!this._prefixes.has(e))throw new Error(`CodeGen: prefix "${e}" is not allowed in this scope`);return this._names[e]={prefix:e,index:0}}}e.Scope=s;class u extends t.Name{constructor(e,t){super(t),this.prefix=e}setValue(e,{property:a,itemIndex:n}){this.value=e,this.scopePath=t._`.${new t.Name(a)}[${n}]`}}e.ValueScopeName=u;const l=t._`\n`;e.ValueScope=class extends s{constructor(e){super(e),this._values={},this._scope=e.scope,this.opts={...e,_n:e.lines?l:t.nil}}get(){return this._scope}name(e){return new u(e,this._newName(e))}value(e,t){var a;if(void 0===t.ref)throw new Error("CodeGen: ref must be passed in value");const n=this.toName(e),{prefix:s}=n,u=null!==(a=t.key)&&void 0!==a?a:t.ref;let l=this._values[s];if(l){const e=l.get(u);if(e)return e}else l=this._values[s]=new Map;l.set(u,n);const i=this._scope[s]||(this._scope[s]=[]),p=i.length;return i[p]=t.ref,n.setValue(t,{property:s,itemIndex:p}),n}getValue(e,t){const a=this._values[e];if(a)return a.get(t)}scopeRefs(e,a=this._values){return this._reduceValues(a,(a=>{if(void 0===a.scopePath)throw new Error(`CodeGen: name "${a}" has no value`);return t._`${e}${a.scopePath}`}))}scopeCode(e=this._values,t,a){return this._reduceValues(e,(e=>{if(void 0===e.value)throw new Error(`CodeGen: name "${e}" has no value`);return e.value.code}),t,a)}_reduceValues(s,u,l={},i){let p=t.nil;for(const r in s){const o=s[r];if(!o)continue;const g=l[r]=l[r]||new Map;o.forEach((s=>{if(g.has(s))return;g.set(s,n.Started);let l=u(s);if(l){const a=this.opts.es5?e.varKinds.var:e.varKinds.const;p=t._`${p}${a} ${s} = ${l};${this.opts._n}`}else{if(!(l=null==i?void 0:i(s)))throw new a(s);p=t._`${p}${l}${this.opts._n}`}g.set(s,n.Completed)}))}return p}}}(d)),d}function x(){return y||(y=1,function(e){Object.defineProperty(e,"__esModule",{value:!0}),e.or=e.and=e.not=e.CodeGen=e.operators=e.varKinds=e.ValueScopeName=e.ValueScope=e.Scope=e.Name=e.regexpCode=e.stringify=e.getProperty=e.nil=e.strConcat=e.str=e._=void 0;const t=c(),a=b();var n=c();Object.defineProperty(e,"_",{enumerable:!0,get:function(){return n._}}),Object.defineProperty(e,"str",{enumerable:!0,get:function(){return n.str}}),Object.defineProperty(e,"strConcat",{enumerable:!0,get:function(){return n.strConcat}}),Object.defineProperty(e,"nil",{enumerable:!0,get:function(){return n.nil}}),Object.defineProperty(e,"getProperty",{enumerable:!0,get:function(){return n.getProperty}}),Object.defineProperty(e,"stringify",{enumerable:!0,get:function(){return n.stringify}}),Object.defineProperty(e,"regexpCode",{enumerable:!0,get:function(){return n.regexpCode}}),Object.defineProperty(e,"Name",{enumerable:!0,get:function(){return n.Name}});var s=b();Object.defineProperty(e,"Scope",{enumerable:!0,get:function(){return s.Scope}}),Object.defineProperty(e,"ValueScope",{enumerable:!0,get:function(){return s.ValueScope}}),Object.defineProperty(e,"ValueScopeName",{enumerable:!0,get:function(){return s.ValueScopeName}}),Object.defineProperty(e,"varKinds",{enumerable:!0,get:function(){return s.varKinds}}),e.operators={GT:new t._Code(">"),GTE:new t._Code(">="),LT:new t._Code("<"),LTE:new t._Code("<="),EQ:new t._Code("==="),NEQ:new t._Code("!=="),NOT:new t._Code("!"),OR:new t._Code("||"),AND:new t._Code("&&"),ADD:new t._Code("+")};class u{optimizeNodes(){return this}optimizeNames(e,t){return this}}class l extends u{constructor(e,t,a){super(),this.varKind=e,this.name=t,this.rhs=a}render({es5:e,_n:t}){const n=e?a.varKinds.var:this.varKind,s=void 0===this.rhs?"":` = ${this.rhs}`;return`${n} ${this.name}${s};`+t}optimizeNames(e,t){if(e[this.name.str])return this.rhs&&(this.rhs=T(this.rhs,e,t)),this}get names(){return this.rhs instanceof t._CodeOrName?this.rhs.names:{}}}class i extends u{constructor(e,t,a){super(),this.lhs=e,this.rhs=t,this.sideEffects=a}render({_n:e}){return`${this.lhs} = ${this.rhs};`+e}optimizeNames(e,a){if(!(this.lhs instanceof t.Name)||e[this.lhs.str]||this.sideEffects)return this.rhs=T(this.rhs,e,a),this}get names(){return k(this.lhs instanceof t.Name?{}:{...this.lhs.names},this.rhs)}}class p extends i{constructor(e,t,a,n){super(e,a,n),this.op=t}render({_n:e}){return`${this.lhs} ${this.op}= ${this.rhs};`+e}}class r extends u{constructor(e){super(),this.label=e,this.names={}}render({_n:e}){return`${this.label}:`+e}}class o extends u{constructor(e){super(),this.label=e,this.names={}}render({_n:e}){return`break${this.label?` ${this.label}`:""};`+e}}class g extends u{constructor(e){super(),this.error=e}render({_n:e}){return`throw ${this.error};`+e}get names(){return this.error.names}}class f extends u{constructor(e){super(),this.code=e}render({_n:e}){return`${this.code};`+e}optimizeNodes(){return`${this.code}`?this:void 0}optimizeNames(e,t){return this.code=T(this.code,e,t),this}get names(){return this.code instanceof t._CodeOrName?this.code.names:{}}}class y extends u{constructor(e=[]){super(),this.nodes=e}render(e){return this.nodes.reduce(((t,a)=>t+a.render(e)),"")}optimizeNodes(){const{nodes:e}=this;let t=e.length;for(;t--;){const a=e[t].optimizeNodes();Array.isArray(a)?e.splice(t,1,...a):a?e[t]=a:e.splice(t,1)}return e.length>0?this:void 0}optimizeNames(e,t){const{nodes:a}=this;let n=a.length;for(;n--;){const s=a[n];s.optimizeNames(e,t)||(D(e,s.names),a.splice(n,1))}return a.length>0?this:void 0}get names(){return this.nodes.reduce(((e,t)=>A(e,t.names)),{})}}class d extends y{render(e){return"{"+e._n+super.render(e)+"}"+e._n}}class x extends y{}class h extends d{}h.kind="else";class m extends d{constructor(e,t){super(t),this.condition=e}render(e){let t=`if(${this.condition})`+super.render(e);return this.else&&(t+="else "+this.else.render(e)),t}optimizeNodes(){super.optimizeNodes();const e=this.condition;if(!0===e)return this.nodes;let t=this.else;if(t){const e=t.optimizeNodes();t=this.else=Array.isArray(e)?new h(e):e}return t?!1===e?t instanceof m?t:t.nodes:this.nodes.length?this:new m(R(e),t instanceof m?[t]:t.nodes):!1!==e&&this.nodes.length?this:void 0}optimizeNames(e,t){var a;if(this.else=null===(a=this.else)||void 0===a?void 0:a.optimizeNames(e,t),super.optimizeNames(e,t)||this.else)return this.condition=T(this.condition,e,t),this}get names(){const e=super.names;return k(e,this.condition),this.else&&A(e,this.else.names),e}}m.kind="if";class _ extends d{}_.kind="for";class v extends _{constructor(e){super(),this.iteration=e}render(e){return`for(${this.iteration})`+super.render(e)}optimizeNames(e,t){if(super.optimizeNames(e,t))return this.iteration=T(this.iteration,e,t),this}get names(){return A(super.names,this.iteration.names)}}class E extends _{constructor(e,t,a,n){super(),this.varKind=e,this.name=t,this.from=a,this.to=n}render(e){const t=e.es5?a.varKinds.var:this.varKind,{name:n,from:s,to:u}=this;return`for(${t} ${n}=${s}; ${n}<${u}; ${n}++)`+super.render(e)}get names(){const e=k(super.names,this.from);return k(e,this.to)}}class w extends _{constructor(e,t,a,n){super(),this.loop=e,this.varKind=t,this.name=a,this.iterable=n}render(e){return`for(${this.varKind} ${this.name} ${this.loop} ${this.iterable})`+super.render(e)}optimizeNames(e,t){if(super.optimizeNames(e,t))return this.iterable=T(this.iterable,e,t),this}get names(){return A(super.names,this.iterable.names)}}class S extends d{constructor(e,t,a){super(),this.name=e,this.args=t,this.async=a}render(e){return`${this.async?"async ":""}function ${this.name}(${this.args})`+super.render(e)}}S.kind="func";class C extends y{render(e){return"return "+super.render(e)}}C.kind="return";class O extends d{render(e){let t="try"+super.render(e);return this.catch&&(t+=this.catch.render(e)),this.finally&&(t+=this.finally.render(e)),t}optimizeNodes(){var e,t;return super.optimizeNodes(),null===(e=this.catch)||void 0===e||e.optimizeNodes(),null===(t=this.finally)||void 0===t||t.optimizeNodes(),this}optimizeNames(e,t){var a,n;return super.optimizeNames(e,t),null===(a=this.catch)||void 0===a||a.optimizeNames(e,t),null===(n=this.finally)||void 0===n||n.optimizeNames(e,t),this}get names(){const e=super.names;return this.catch&&A(e,this.catch.names),this.finally&&A(e,this.finally.names),e}}class I extends d{constructor(e){super(),this.error=e}render(e){return`catch(${this.error})`+super.render(e)}}I.kind="catch";class N extends d{render(e){return"finally"+super.render(e)}}N.kind="finally";function A(e,t){for(const a in t)e[a]=(e[a]||0)+(t[a]||0);return e}function k(e,a){return a instanceof t._CodeOrName?A(e,a.names):e}function T(e,a,n){return e instanceof t.Name?u(e):(s=e)instanceof t._Code&&s._items.some((e=>e instanceof t.Name&&1===a[e.str]&&void 0!==n[e.str]))?new t._Code(e._items.reduce(((e,a)=>(a instanceof t.Name&&(a=u(a)),a instanceof t._Code?e.push(...a._items):e.push(a),e)),[])):e;var s;function u(e){const t=n[e.str];return void 0===t||1!==a[e.str]?e:(delete a[e.str],t)}}function D(e,t){for(const a in t)e[a]=(e[a]||0)-(t[a]||0)}function R(e){return"boolean"==typeof e||"number"==typeof e||null===e?!e:t._`!${L(e)}`}e.CodeGen=class{constructor(e,t={}){this._values={},this._blockStarts=[],this._constants={},this.opts={...t,_n:t.lines?"\n":""},this._extScope=e,this._scope=new a.Scope({parent:e}),this._nodes=[new x]}toString(){return this._root.render(this.opts)}name(e){return this._scope.name(e)}scopeName(e){return this._extScope.name(e)}scopeValue(e,t){const a=this._extScope.value(e,t);return(this._values[a.prefix]||(this._values[a.prefix]=new Set)).add(a),a}getScopeValue(e,t){return this._extScope.getValue(e,t)}scopeRefs(e){return this._extScope.scopeRefs(e,this._values)}scopeCode(){return this._extScope.scopeCode(this._values)}_def(e,t,a,n){const s=this._scope.toName(t);return void 0!==a&&n&&(this._constants[s.str]=a),this._leafNode(new l(e,s,a)),s}const(e,t,n){return this._def(a.varKinds.const,e,t,n)}let(e,t,n){return this._def(a.varKinds.let,e,t,n)}var(e,t,n){return this._def(a.varKinds.var,e,t,n)}assign(e,t,a){return this._leafNode(new i(e,t,a))}add(t,a){return this._leafNode(new p(t,e.operators.ADD,a))}code(e){return"function"==typeof e?e():e!==t.nil&&this._leafNode(new f(e)),this}object(...e){const a=["{"];for(const[n,s]of e)a.length>1&&a.push(","),a.push(n),(n!==s||this.opts.es5)&&(a.push(":"),(0,t.addCodeArg)(a,s));return a.push("}"),new t._Code(a)}if(e,t,a){if(this._blockNode(new m(e)),t&&a)this.code(t).else().code(a).endIf();else if(t)this.code(t).endIf();else if(a)throw new Error('CodeGen: "else" body without "then" body');return this}elseIf(e){return this._elseNode(new m(e))}else(){return this._elseNode(new h)}endIf(){return this._endBlockNode(m,h)}_for(e,t){return this._blockNode(e),t&&this.code(t).endFor(),this}for(e,t){return this._for(new v(e),t)}forRange(e,t,n,s,u=(this.opts.es5?a.varKinds.var:a.varKinds.let)){const l=this._scope.toName(e);return this._for(new E(u,l,t,n),(()=>s(l)))}forOf(e,n,s,u=a.varKinds.const){const l=this._scope.toName(e);if(this.opts.es5){const e=n instanceof t.Name?n:this.var("_arr",n);return this.forRange("_i",0,t._`${e}.length`,(a=>{this.var(l,t._`${e}[${a}]`),s(l)}))}return this._for(new w("of",u,l,n),(()=>s(l)))}forIn(e,n,s,u=(this.opts.es5?a.varKinds.var:a.varKinds.const)){if(this.opts.ownProperties)return this.forOf(e,t._`Object.keys(${n})`,s);const l=this._scope.toName(e);return this._for(new w("in",u,l,n),(()=>s(l)))}endFor(){return this._endBlockNode(_)}label(e){return this._leafNode(new r(e))}break(e){return this._leafNode(new o(e))}return(e){const t=new C;if(this._blockNode(t),this.code(e),1!==t.nodes.length)throw new Error('CodeGen: "return" should have one node');return this._endBlockNode(C)}try(e,t,a){if(!t&&!a)throw new Error('CodeGen: "try" without "catch" and "finally"');const n=new O;if(this._blockNode(n),this.code(e),t){const e=this.name("e");this._currNode=n.catch=new I(e),t(e)}return a&&(this._currNode=n.finally=new N,this.code(a)),this._endBlockNode(I,N)}throw(e){return this._leafNode(new g(e))}block(e,t){return this._blockStarts.push(this._nodes.length),e&&this.code(e).endBlock(t),this}endBlock(e){const t=this._blockStarts.pop();if(void 0===t)throw new Error("CodeGen: not in self-balancing block");const a=this._nodes.length-t;if(a<0||void 0!==e&&a!==e)throw new Error(`CodeGen: wrong number of nodes: ${a} vs ${e} expected`);return this._nodes.length=t,this}func(e,a=t.nil,n,s){return this._blockNode(new S(e,a,n)),s&&this.code(s).endFunc(),this}endFunc(){return this._endBlockNode(S)}optimize(e=1){for(;e-- >0;)this._root.optimizeNodes(),this._root.optimizeNames(this._root.names,this._constants)}_leafNode(e){return this._currNode.nodes.push(e),this}_blockNode(e){this._currNode.nodes.push(e),this._nodes.push(e)}_endBlockNode(e,t){const a=this._currNode;if(a instanceof e||t&&a instanceof t)return this._nodes.pop(),this;throw new Error(`CodeGen: not in block "${t?`${e.kind}/${t.kind}`:e.kind}"`)}_elseNode(e){const t=this._currNode;if(!(t instanceof m))throw new Error('CodeGen: "else" without "if"');return this._currNode=t.else=e,this}get _root(){return this._nodes[0]}get _currNode(){const e=this._nodes;return e[e.length-1]}set _currNode(e){const t=this._nodes;t[t.length-1]=e}},e.not=R;const P=$(e.operators.AND);e.and=function(...e){return e.reduce(P)};const M=$(e.operators.OR);function $(e){return(a,n)=>a===t.nil?n:n===t.nil?a:t._`${L(a)} ${e} ${L(n)}`}function L(e){return e instanceof t.Name?e:t._`(${e})`}e.or=function(...e){return e.reduce(M)}}(o)),o}var h={};!function(e){Object.defineProperty(e,"__esModule",{value:!0}),e.checkStrictMode=e.getErrorPath=e.Type=e.useFunc=e.setEvaluated=e.evaluatedPropsToName=e.mergeEvaluated=e.eachItem=e.unescapeJsonPointer=e.escapeJsonPointer=e.escapeFragment=e.unescapeFragment=e.schemaRefOrVal=e.schemaHasRulesButRef=e.schemaHasRules=e.checkUnknownRules=e.alwaysValidSchema=e.toHash=void 0;const t=x(),a=c();function n(e,t=e.schema){const{opts:a,self:n}=e;if(!a.strictSchema)return;if("boolean"==typeof t)return;const s=n.RULES.keywords;for(const a in t)s[a]||f(e,`unknown keyword: "${a}"`)}function s(e,t){if("boolean"==typeof e)return!e;for(const a in e)if(t[a])return!0;return!1}function u(e){return"number"==typeof e?`${e}`:e.replace(/~/g,"~0").replace(/\//g,"~1")}function l(e){return e.replace(/~1/g,"/").replace(/~0/g,"~")}function i({mergeNames:e,mergeToName:a,mergeValues:n,resultToName:s}){return(u,l,i,p)=>{const r=void 0===i?l:i instanceof t.Name?(l instanceof t.Name?e(u,l,i):a(u,l,i),i):l instanceof t.Name?(a(u,i,l),l):n(l,i);return p!==t.Name||r instanceof t.Name?r:s(u,r)}}function p(e,a){if(!0===a)return e.var("props",!0);const n=e.var("props",t._`{}`);return void 0!==a&&r(e,n,a),n}function r(e,a,n){Object.keys(n).forEach((n=>e.assign(t._`${a}${(0,t.getProperty)(n)}`,!0)))}e.toHash=function(e){const t={};for(const a of e)t[a]=!0;return t},e.alwaysValidSchema=function(e,t){return"boolean"==typeof t?t:0===Object.keys(t).length||(n(e,t),!s(t,e.self.RULES.all))},e.checkUnknownRules=n,e.schemaHasRules=s,e.schemaHasRulesButRef=function(e,t){if("boolean"==typeof e)return!e;for(const a in e)if("$ref"!==a&&t.all[a])return!0;return!1},e.schemaRefOrVal=function({topSchemaRef:e,schemaPath:a},n,s,u){if(!u){if("number"==typeof n||"boolean"==typeof n)return n;if("string"==typeof n)return t._`${n}`}return t._`${e}${a}${(0,t.getProperty)(s)}`},e.unescapeFragment=function(e){return l(decodeURIComponent(e))},e.escapeFragment=function(e){return encodeURIComponent(u(e))},e.escapeJsonPointer=u,e.unescapeJsonPointer=l,e.eachItem=function(e,t){if(Array.isArray(e))for(const a of e)t(a);else t(e)},e.mergeEvaluated={props:i({mergeNames:(e,a,n)=>e.if(t._`${n} !== true && ${a} !== undefined`,(()=>{e.if(t._`${a} === true`,(()=>e.assign(n,!0)),(()=>e.assign(n,t._`${n} || {}`).code(t._`Object.assign(${n}, ${a})`)))})),mergeToName:(e,a,n)=>e.if(t._`${n} !== true`,(()=>{!0===a?e.assign(n,!0):(e.assign(n,t._`${n} || {}`),r(e,n,a))})),mergeValues:(e,t)=>!0===e||{...e,...t},resultToName:p}),items:i({mergeNames:(e,a,n)=>e.if(t._`${n} !== true && ${a} !== undefined`,(()=>e.assign(n,t._`${a} === true ? true : ${n} > ${a} ? ${n} : ${a}`))),mergeToName:(e,a,n)=>e.if(t._`${n} !== true`,(()=>e.assign(n,!0===a||t._`${n} > ${a} ? ${n} : ${a}`))),mergeValues:(e,t)=>!0===e||Math.max(e,t),resultToName:(e,t)=>e.var("items",t)})},e.evaluatedPropsToName=p,e.setEvaluated=r;const o={};var g;function f(e,t,a=e.opts.strictSchema){if(a){if(t=`strict mode: ${t}`,!0===a)throw new Error(t);e.self.logger.warn(t)}}e.useFunc=function(e,t){return e.scopeValue("func",{ref:t,code:o[t.code]||(o[t.code]=new a._Code(t.code))})},function(e){e[e.Num=0]="Num",e[e.Str=1]="Str"}(g=e.Type||(e.Type={})),e.getErrorPath=function(e,a,n){if(e instanceof t.Name){const s=a===g.Num;return n?s?t._`"[" + ${e} + "]"`:t._`"['" + ${e} + "']"`:s?t._`"/" + ${e}`:t._`"/" + ${e}.replace(/~/g, "~0").replace(/\\//g, "~1")`}return n?(0,t.getProperty)(e).toString():"/"+u(e)},e.checkStrictMode=f}(h);var m={};Object.defineProperty(m,"__esModule",{value:!0});const _=x(),v={data:new _.Name("data"),valCxt:new _.Name("valCxt"),instancePath:new _.Name("instancePath"),parentData:new _.Name("parentData"),parentDataProperty:new _.Name("parentDataProperty"),rootData:new _.Name("rootData"),dynamicAnchors:new _.Name("dynamicAnchors"),vErrors:new _.Name("vErrors"),errors:new _.Name("errors"),this:new _.Name("this"),self:new _.Name("self"),scope:new _.Name("scope"),json:new _.Name("json"),jsonPos:new _.Name("jsonPos"),jsonLen:new _.Name("jsonLen"),jsonPart:new _.Name("jsonPart")};var E,w;function S(){return E||(E=1,function(e){Object.defineProperty(e,"__esModule",{value:!0}),e.extendErrors=e.resetErrorsCount=e.reportExtraError=e.reportError=e.keyword$DataError=e.keywordError=void 0;const t=x(),a=h,n=m;function s(e,a){const s=e.const("err",a);e.if(t._`${n.default.vErrors} === null`,(()=>e.assign(n.default.vErrors,t._`[${s}]`)),t._`${n.default.vErrors}.push(${s})`),e.code(t._`${n.default.errors}++`)}function u(e,a){const{gen:n,validateName:s,schemaEnv:u}=e;u.$async?n.throw(t._`new ${e.ValidationError}(${a})`):(n.assign(t._`${s}.errors`,a),n.return(!1))}e.keywordError={message:({keyword:e})=>t.str`must pass "${e}" keyword validation`},e.keyword$DataError={message:({keyword:e,schemaType:a})=>a?t.str`"${e}" keyword must be ${a} ($data)`:t.str`"${e}" keyword is invalid ($data)`},e.reportError=function(a,n=e.keywordError,l,p){const{it:r}=a,{gen:o,compositeRule:g,allErrors:c}=r,f=i(a,n,l);(null!=p?p:g||c)?s(o,f):u(r,t._`[${f}]`)},e.reportExtraError=function(t,a=e.keywordError,l){const{it:p}=t,{gen:r,compositeRule:o,allErrors:g}=p;s(r,i(t,a,l)),o||g||u(p,n.default.vErrors)},e.resetErrorsCount=function(e,a){e.assign(n.default.errors,a),e.if(t._`${n.default.vErrors} !== null`,(()=>e.if(a,(()=>e.assign(t._`${n.default.vErrors}.length`,a)),(()=>e.assign(n.default.vErrors,null)))))},e.extendErrors=function({gen:e,keyword:a,schemaValue:s,data:u,errsCount:l,it:i}){if(void 0===l)throw new Error("ajv implementation error");const p=e.name("err");e.forRange("i",l,n.default.errors,(l=>{e.const(p,t._`${n.default.vErrors}[${l}]`),e.if(t._`${p}.instancePath === undefined`,(()=>e.assign(t._`${p}.instancePath`,(0,t.strConcat)(n.default.instancePath,i.errorPath)))),e.assign(t._`${p}.schemaPath`,t.str`${i.errSchemaPath}/${a}`),i.opts.verbose&&(e.assign(t._`${p}.schema`,s),e.assign(t._`${p}.data`,u))}))};const l={keyword:new t.Name("keyword"),schemaPath:new t.Name("schemaPath"),params:new t.Name("params"),propertyName:new t.Name("propertyName"),message:new t.Name("message"),schema:new t.Name("schema"),parentSchema:new t.Name("parentSchema")};function i(e,a,s){const{createErrors:u}=e.it;return!1===u?t._`{}`:function(e,a,s={}){const{gen:u,it:i}=e,o=[p(i,s),r(e,s)];return function(e,{params:a,message:s},u){const{keyword:i,data:p,schemaValue:r,it:o}=e,{opts:g,propertyName:c,topSchemaRef:f,schemaPath:y}=o;u.push([l.keyword,i],[l.params,"function"==typeof a?a(e):a||t._`{}`]),g.messages&&u.push([l.message,"function"==typeof s?s(e):s]);g.verbose&&u.push([l.schema,r],[l.parentSchema,t._`${f}${y}`],[n.default.data,p]);c&&u.push([l.propertyName,c])}(e,a,o),u.object(...o)}(e,a,s)}function p({errorPath:e},{instancePath:s}){const u=s?t.str`${e}${(0,a.getErrorPath)(s,a.Type.Str)}`:e;return[n.default.instancePath,(0,t.strConcat)(n.default.instancePath,u)]}function r({keyword:e,it:{errSchemaPath:n}},{schemaPath:s,parentSchema:u}){let i=u?n:t.str`${n}/${e}`;return s&&(i=t.str`${i}${(0,a.getErrorPath)(s,a.Type.Str)}`),[l.schemaPath,i]}}(r)),r}m.default=v;var C={},O={};Object.defineProperty(O,"__esModule",{value:!0}),O.getRules=O.isJSONType=void 0;const I=new Set(["string","number","integer","boolean","null","object","array"]);O.isJSONType=function(e){return"string"==typeof e&&I.has(e)},O.getRules=function(){const e={number:{type:"number",rules:[]},string:{type:"string",rules:[]},array:{type:"array",rules:[]},object:{type:"object",rules:[]}};return{types:{...e,integer:!0,boolean:!0,null:!0},rules:[{rules:[]},e.number,e.string,e.array,e.object],post:{rules:[]},all:{},keywords:{}}};var N,A={};function k(){if(N)return A;function e(e,a){return a.rules.some((a=>t(e,a)))}function t(e,t){var a;return void 0!==e[t.keyword]||(null===(a=t.definition.implements)||void 0===a?void 0:a.some((t=>void 0!==e[t])))}return N=1,Object.defineProperty(A,"__esModule",{value:!0}),A.shouldUseRule=A.shouldUseGroup=A.schemaHasRulesForType=void 0,A.schemaHasRulesForType=function({schema:t,self:a},n){const s=a.RULES.types[n];return s&&!0!==s&&e(t,s)},A.shouldUseGroup=e,A.shouldUseRule=t,A}!function(e){Object.defineProperty(e,"__esModule",{value:!0}),e.reportTypeError=e.checkDataTypes=e.checkDataType=e.coerceAndCheckDataType=e.getJSONTypes=e.getSchemaTypes=e.DataType=void 0;const t=O,a=k(),n=S(),s=x(),u=h;var l;function i(e){const a=Array.isArray(e)?e:e?[e]:[];if(a.every(t.isJSONType))return a;throw new Error("type must be JSONType or JSONType[]: "+a.join(","))}!function(e){e[e.Correct=0]="Correct",e[e.Wrong=1]="Wrong"}(l=e.DataType||(e.DataType={})),e.getSchemaTypes=function(e){const t=i(e.type);if(t.includes("null")){if(!1===e.nullable)throw new Error("type: null contradicts nullable: false")}else{if(!t.length&&void 0!==e.nullable)throw new Error('"nullable" cannot be used without "type"');!0===e.nullable&&t.push("null")}return t},e.getJSONTypes=i,e.coerceAndCheckDataType=function(e,t){const{gen:n,data:u,opts:i}=e,r=function(e,t){return t?e.filter((e=>p.has(e)||"array"===t&&"array"===e)):[]}(t,i.coerceTypes),g=t.length>0&&!(0===r.length&&1===t.length&&(0,a.schemaHasRulesForType)(e,t[0]));if(g){const a=o(t,u,i.strictNumbers,l.Wrong);n.if(a,(()=>{r.length?function(e,t,a){const{gen:n,data:u,opts:l}=e,i=n.let("dataType",s._`typeof ${u}`),r=n.let("coerced",s._`undefined`);"array"===l.coerceTypes&&n.if(s._`${i} == 'object' && Array.isArray(${u}) && ${u}.length == 1`,(()=>n.assign(u,s._`${u}[0]`).assign(i,s._`typeof ${u}`).if(o(t,u,l.strictNumbers),(()=>n.assign(r,u)))));n.if(s._`${r} !== undefined`);for(const e of a)(p.has(e)||"array"===e&&"array"===l.coerceTypes)&&g(e);function g(e){switch(e){case"string":return void n.elseIf(s._`${i} == "number" || ${i} == "boolean"`).assign(r,s._`"" + ${u}`).elseIf(s._`${u} === null`).assign(r,s._`""`);case"number":return void n.elseIf(s._`${i} == "boolean" || ${u} === null
               || (${i} == "string" && ${u} && ${u} == +${u})`).assign(r,s._`+${u}`);case"integer":return void n.elseIf(s._`${i} === "boolean" || ${u} === null
               || (${i} === "string" && ${u} && ${u} == +${u} && !(${u} % 1))`).assign(r,s._`+${u}`);case"boolean":return void n.elseIf(s._`${u} === "false" || ${u} === 0 || ${u} === null`).assign(r,!1).elseIf(s._`${u} === "true" || ${u} === 1`).assign(r,!0);case"null":return n.elseIf(s._`${u} === "" || ${u} === 0 || ${u} === false`),void n.assign(r,null);case"array":n.elseIf(s._`${i} === "string" || ${i} === "number"
               || ${i} === "boolean" || ${u} === null`).assign(r,s._`[${u}]`)}}n.else(),c(e),n.endIf(),n.if(s._`${r} !== undefined`,(()=>{n.assign(u,r),function({gen:e,parentData:t,parentDataProperty:a},n){e.if(s._`${t} !== undefined`,(()=>e.assign(s._`${t}[${a}]`,n)))}(e,r)}))}(e,t,r):c(e)}))}return g};const p=new Set(["string","number","integer","boolean","null"]);function r(e,t,a,n=l.Correct){const u=n===l.Correct?s.operators.EQ:s.operators.NEQ;let i;switch(e){case"null":return s._`${t} ${u} null`;case"array":i=s._`Array.isArray(${t})`;break;case"object":i=s._`${t} && typeof ${t} == "object" && !Array.isArray(${t})`;break;case"integer":i=p(s._`!(${t} % 1) && !isNaN(${t})`);break;case"number":i=p();break;default:return s._`typeof ${t} ${u} ${e}`}return n===l.Correct?i:(0,s.not)(i);function p(e=s.nil){return(0,s.and)(s._`typeof ${t} == "number"`,e,a?s._`isFinite(${t})`:s.nil)}}function o(e,t,a,n){if(1===e.length)return r(e[0],t,a,n);let l;const i=(0,u.toHash)(e);if(i.array&&i.object){const e=s._`typeof ${t} != "object"`;l=i.null?e:s._`!${t} || ${e}`,delete i.null,delete i.array,delete i.object}else l=s.nil;i.number&&delete i.integer;for(const e in i)l=(0,s.and)(l,r(e,t,a,n));return l}e.checkDataType=r,e.checkDataTypes=o;const g={message:({schema:e})=>`must be ${e}`,params:({schema:e,schemaValue:t})=>"string"==typeof e?s._`{type: ${e}}`:s._`{type: ${t}}`};function c(e){const t=function(e){const{gen:t,data:a,schema:n}=e,s=(0,u.schemaRefOrVal)(e,n,"type");return{gen:t,keyword:"type",data:a,schema:n.type,schemaCode:s,schemaValue:s,parentSchema:n,params:{},it:e}}(e);(0,n.reportError)(t,g)}e.reportTypeError=c}(C);var T,D={};var R={},P={};Object.defineProperty(P,"__esModule",{value:!0}),P.validateUnion=P.validateArray=P.usePattern=P.callValidateCode=P.schemaProperties=P.allSchemaProperties=P.noPropertyInData=P.propertyInData=P.isOwnProperty=P.hasPropFunc=P.reportMissingProp=P.checkMissingProp=P.checkReportMissingProp=void 0;const M=x(),$=h,L=m,W=h;function F(e){return e.scopeValue("func",{ref:Object.prototype.hasOwnProperty,code:M._`Object.prototype.hasOwnProperty`})}function j(e,t,a){return M._`${F(e)}.call(${t}, ${a})`}function U(e,t,a,n){const s=M._`${t}${(0,M.getProperty)(a)} === undefined`;return n?(0,M.or)(s,(0,M.not)(j(e,t,a))):s}function z(e){return e?Object.keys(e).filter((e=>"__proto__"!==e)):[]}P.checkReportMissingProp=function(e,t){const{gen:a,data:n,it:s}=e;a.if(U(a,n,t,s.opts.ownProperties),(()=>{e.setParams({missingProperty:M._`${t}`},!0),e.error()}))},P.checkMissingProp=function({gen:e,data:t,it:{opts:a}},n,s){return(0,M.or)(...n.map((n=>(0,M.and)(U(e,t,n,a.ownProperties),M._`${s} = ${n}`))))},P.reportMissingProp=function(e,t){e.setParams({missingProperty:t},!0),e.error()},P.hasPropFunc=F,P.isOwnProperty=j,P.propertyInData=function(e,t,a,n){const s=M._`${t}${(0,M.getProperty)(a)} !== undefined`;return n?M._`${s} && ${j(e,t,a)}`:s},P.noPropertyInData=U,P.allSchemaProperties=z,P.schemaProperties=function(e,t){return z(t).filter((a=>!(0,$.alwaysValidSchema)(e,t[a])))},P.callValidateCode=function({schemaCode:e,data:t,it:{gen:a,topSchemaRef:n,schemaPath:s,errorPath:u},it:l},i,p,r){const o=r?M._`${e}, ${t}, ${n}${s}`:t,g=[[L.default.instancePath,(0,M.strConcat)(L.default.instancePath,u)],[L.default.parentData,l.parentData],[L.default.parentDataProperty,l.parentDataProperty],[L.default.rootData,L.default.rootData]];l.opts.dynamicRef&&g.push([L.default.dynamicAnchors,L.default.dynamicAnchors]);const c=M._`${o}, ${a.object(...g)}`;return p!==M.nil?M._`${i}.call(${p}, ${c})`:M._`${i}(${c})`};const V=M._`new RegExp`;var H;function q(){if(H)return R;H=1,Object.defineProperty(R,"__esModule",{value:!0}),R.validateKeywordUsage=R.validSchemaType=R.funcKeywordCode=R.macroKeywordCode=void 0;const e=x(),t=m,a=P,n=S();function s(t){const{gen:a,data:n,it:s}=t;a.if(s.parentData,(()=>a.assign(n,e._`${s.parentData}[${s.parentDataProperty}]`)))}function u(t,a,n){if(void 0===n)throw new Error(`keyword "${a}" failed to compile`);return t.scopeValue("keyword","function"==typeof n?{ref:n}:{ref:n,code:(0,e.stringify)(n)})}return R.macroKeywordCode=function(t,a){const{gen:n,keyword:s,schema:l,parentSchema:i,it:p}=t,r=a.macro.call(p.self,l,i,p),o=u(n,s,r);!1!==p.opts.validateSchema&&p.self.validateSchema(r,!0);const g=n.name("valid");t.subschema({schema:r,schemaPath:e.nil,errSchemaPath:`${p.errSchemaPath}/${s}`,topSchemaRef:o,compositeRule:!0},g),t.pass(g,(()=>t.error(!0)))},R.funcKeywordCode=function(l,i){var p;const{gen:r,keyword:o,schema:g,parentSchema:c,$data:f,it:y}=l;!function({schemaEnv:e},t){if(t.async&&!e.$async)throw new Error("async keyword in sync schema")}(y,i);const d=!f&&i.compile?i.compile.call(y.self,g,c,y):i.validate,b=u(r,o,d),x=r.let("valid");function h(n=(i.async?e._`await `:e.nil)){const s=y.opts.passContext?t.default.this:t.default.self,u=!("compile"in i&&!f||!1===i.schema);r.assign(x,e._`${n}${(0,a.callValidateCode)(l,b,s,u)}`,i.modifying)}function m(t){var a;r.if((0,e.not)(null!==(a=i.valid)&&void 0!==a?a:x),t)}l.block$data(x,(function(){if(!1===i.errors)h(),i.modifying&&s(l),m((()=>l.error()));else{const a=i.async?function(){const t=r.let("ruleErrs",null);return r.try((()=>h(e._`await `)),(a=>r.assign(x,!1).if(e._`${a} instanceof ${y.ValidationError}`,(()=>r.assign(t,e._`${a}.errors`)),(()=>r.throw(a))))),t}():function(){const t=e._`${b}.errors`;return r.assign(t,null),h(e.nil),t}();i.modifying&&s(l),m((()=>function(a,s){const{gen:u}=a;u.if(e._`Array.isArray(${s})`,(()=>{u.assign(t.default.vErrors,e._`${t.default.vErrors} === null ? ${s} : ${t.default.vErrors}.concat(${s})`).assign(t.default.errors,e._`${t.default.vErrors}.length`),(0,n.extendErrors)(a)}),(()=>a.error()))}(l,a)))}})),l.ok(null!==(p=i.valid)&&void 0!==p?p:x)},R.validSchemaType=function(e,t,a=!1){return!t.length||t.some((t=>"array"===t?Array.isArray(e):"object"===t?e&&"object"==typeof e&&!Array.isArray(e):typeof e==t||a&&void 0===e))},R.validateKeywordUsage=function({schema:e,opts:t,self:a,errSchemaPath:n},s,u){if(Array.isArray(s.keyword)?!s.keyword.includes(u):s.keyword!==u)throw new Error("ajv implementation error");const l=s.dependencies;if(null==l?void 0:l.some((t=>!Object.prototype.hasOwnProperty.call(e,t))))throw new Error(`parent schema must have dependencies of ${u}: ${l.join(",")}`);if(s.validateSchema){if(!s.validateSchema(e[u])){const e=`keyword "${u}" value is invalid at path "${n}": `+a.errorsText(s.validateSchema.errors);if("log"!==t.validateSchema)throw new Error(e);a.logger.error(e)}}},R}P.usePattern=function({gen:e,it:{opts:t}},a){const n=t.unicodeRegExp?"u":"",{regExp:s}=t.code,u=s(a,n);return e.scopeValue("pattern",{key:u.toString(),ref:u,code:M._`${"new RegExp"===s.code?V:(0,W.useFunc)(e,s)}(${a}, ${n})`})},P.validateArray=function(e){const{gen:t,data:a,keyword:n,it:s}=e,u=t.name("valid");if(s.allErrors){const e=t.let("valid",!0);return l((()=>t.assign(e,!1))),e}return t.var(u,!0),l((()=>t.break())),u;function l(s){const l=t.const("len",M._`${a}.length`);t.forRange("i",0,l,(a=>{e.subschema({keyword:n,dataProp:a,dataPropType:$.Type.Num},u),t.if((0,M.not)(u),s)}))}},P.validateUnion=function(e){const{gen:t,schema:a,keyword:n,it:s}=e;if(!Array.isArray(a))throw new Error("ajv implementation error");if(a.some((e=>(0,$.alwaysValidSchema)(s,e)))&&!s.opts.unevaluated)return;const u=t.let("valid",!1),l=t.name("_valid");t.block((()=>a.forEach(((a,s)=>{const i=e.subschema({keyword:n,schemaProp:s,compositeRule:!0},l);t.assign(u,M._`${u} || ${l}`);e.mergeValidEvaluated(i,l)||t.if((0,M.not)(u))})))),e.result(u,(()=>e.reset()),(()=>e.error(!0)))};var Y,B={};var X={},G=function e(t,a){if(t===a)return!0;if(t&&a&&"object"==typeof t&&"object"==typeof a){if(t.constructor!==a.constructor)return!1;var n,s,u;if(Array.isArray(t)){if((n=t.length)!=a.length)return!1;for(s=n;0!=s--;)if(!e(t[s],a[s]))return!1;return!0}if(t.constructor===RegExp)return t.source===a.source&&t.flags===a.flags;if(t.valueOf!==Object.prototype.valueOf)return t.valueOf()===a.valueOf();if(t.toString!==Object.prototype.toString)return t.toString()===a.toString();if((n=(u=Object.keys(t)).length)!==Object.keys(a).length)return!1;for(s=n;0!=s--;)if(!Object.prototype.hasOwnProperty.call(a,u[s]))return!1;for(s=n;0!=s--;){var l=u[s];if(!e(t[l],a[l]))return!1}return!0}return t!=t&&a!=a},K={exports:{}},Z=K.exports=function(e,t,a){"function"==typeof t&&(a=t,t={}),J(t,"function"==typeof(a=t.cb||a)?a:a.pre||function(){},a.post||function(){},e,"",e)};function J(e,t,a,n,s,u,l,i,p,r){if(n&&"object"==typeof n&&!Array.isArray(n)){for(var o in t(n,s,u,l,i,p,r),n){var g=n[o];if(Array.isArray(g)){if(o in Z.arrayKeywords)for(var c=0;c<g.length;c++)J(e,t,a,g[c],s+"/"+o+"/"+c,u,s,o,n,c)}else if(o in Z.propsKeywords){if(g&&"object"==typeof g)for(var f in g)J(e,t,a,g[f],s+"/"+o+"/"+f.replace(/~/g,"~0").replace(/\//g,"~1"),u,s,o,n,f)}else(o in Z.keywords||e.allKeys&&!(o in Z.skipKeywords))&&J(e,t,a,g,s+"/"+o,u,s,o,n)}a(n,s,u,l,i,p,r)}}Z.keywords={additionalItems:!0,items:!0,contains:!0,additionalProperties:!0,propertyNames:!0,not:!0,if:!0,then:!0,else:!0},Z.arrayKeywords={items:!0,allOf:!0,anyOf:!0,oneOf:!0},Z.propsKeywords={$defs:!0,definitions:!0,properties:!0,patternProperties:!0,dependencies:!0},Z.skipKeywords={default:!0,enum:!0,const:!0,required:!0,maximum:!0,minimum:!0,exclusiveMaximum:!0,exclusiveMinimum:!0,multipleOf:!0,maxLength:!0,minLength:!0,pattern:!0,format:!0,maxItems:!0,minItems:!0,uniqueItems:!0,maxProperties:!0,minProperties:!0};var Q=K.exports;Object.defineProperty(X,"__esModule",{value:!0}),X.getSchemaRefs=X.resolveUrl=X.normalizeId=X._getFullPath=X.getFullPath=X.inlineRef=void 0;const ee=h,te=G,ae=Q,ne=new Set(["type","format","pattern","maxLength","minLength","maxProperties","minProperties","maxItems","minItems","maximum","minimum","uniqueItems","multipleOf","required","enum","const"]);X.inlineRef=function(e,t=!0){return"boolean"==typeof e||(!0===t?!ue(e):!!t&&le(e)<=t)};const se=new Set(["$ref","$recursiveRef","$recursiveAnchor","$dynamicRef","$dynamicAnchor"]);function ue(e){for(const t in e){if(se.has(t))return!0;const a=e[t];if(Array.isArray(a)&&a.some(ue))return!0;if("object"==typeof a&&ue(a))return!0}return!1}function le(e){let t=0;for(const a in e){if("$ref"===a)return 1/0;if(t++,!ne.has(a)&&("object"==typeof e[a]&&(0,ee.eachItem)(e[a],(e=>t+=le(e))),t===1/0))return 1/0}return t}function ie(e,t="",a){!1!==a&&(t=oe(t));const n=e.parse(t);return pe(e,n)}function pe(e,t){return e.serialize(t).split("#")[0]+"#"}X.getFullPath=ie,X._getFullPath=pe;const re=/#\/?$/;function oe(e){return e?e.replace(re,""):""}X.normalizeId=oe,X.resolveUrl=function(e,t,a){return a=oe(a),e.resolve(t,a)};const ge=/^[a-z_][-a-z0-9._]*$/i;var ce;function fe(){if(ce)return i;ce=1,Object.defineProperty(i,"__esModule",{value:!0}),i.getData=i.KeywordCxt=i.validateFunctionCode=void 0;const e=function(){if(w)return p;w=1,Object.defineProperty(p,"__esModule",{value:!0}),p.boolOrEmptySchema=p.topBoolOrEmptySchema=void 0;const e=S(),t=x(),a=m,n={message:"boolean schema is false"};function s(t,a){const{gen:s,data:u}=t,l={gen:s,keyword:"false schema",data:u,schema:!1,schemaCode:!1,schemaValue:!1,params:{},it:t};(0,e.reportError)(l,n,void 0,a)}return p.topBoolOrEmptySchema=function(e){const{gen:n,schema:u,validateName:l}=e;!1===u?s(e,!1):"object"==typeof u&&!0===u.$async?n.return(a.default.data):(n.assign(t._`${l}.errors`,null),n.return(!0))},p.boolOrEmptySchema=function(e,t){const{gen:a,schema:n}=e;!1===n?(a.var(t,!1),s(e)):a.var(t,!0)},p}(),t=C,a=k(),n=C,s=function(){if(T)return D;T=1,Object.defineProperty(D,"__esModule",{value:!0}),D.assignDefaults=void 0;const e=x(),t=h;function a(a,n,s){const{gen:u,compositeRule:l,data:i,opts:p}=a;if(void 0===s)return;const r=e._`${i}${(0,e.getProperty)(n)}`;if(l)return void(0,t.checkStrictMode)(a,`default is ignored for: ${r}`);let o=e._`${r} === undefined`;"empty"===p.useDefaults&&(o=e._`${o} || ${r} === null || ${r} === ""`),u.if(o,e._`${r} = ${(0,e.stringify)(s)}`)}return D.assignDefaults=function(e,t){const{properties:n,items:s}=e.schema;if("object"===t&&n)for(const t in n)a(e,t,n[t].default);else"array"===t&&Array.isArray(s)&&s.forEach(((t,n)=>a(e,n,t.default)))},D}(),u=q(),l=function(){if(Y)return B;Y=1,Object.defineProperty(B,"__esModule",{value:!0}),B.extendSubschemaMode=B.extendSubschemaData=B.getSubschema=void 0;const e=x(),t=h;return B.getSubschema=function(a,{keyword:n,schemaProp:s,schema:u,schemaPath:l,errSchemaPath:i,topSchemaRef:p}){if(void 0!==n&&void 0!==u)throw new Error('both "keyword" and "schema" passed, only one allowed');if(void 0!==n){const u=a.schema[n];return void 0===s?{schema:u,schemaPath:e._`${a.schemaPath}${(0,e.getProperty)(n)}`,errSchemaPath:`${a.errSchemaPath}/${n}`}:{schema:u[s],schemaPath:e._`${a.schemaPath}${(0,e.getProperty)(n)}${(0,e.getProperty)(s)}`,errSchemaPath:`${a.errSchemaPath}/${n}/${(0,t.escapeFragment)(s)}`}}if(void 0!==u){if(void 0===l||void 0===i||void 0===p)throw new Error('"schemaPath", "errSchemaPath" and "topSchemaRef" are required with "schema"');return{schema:u,schemaPath:l,topSchemaRef:p,errSchemaPath:i}}throw new Error('either "keyword" or "schema" must be passed')},B.extendSubschemaData=function(a,n,{dataProp:s,dataPropType:u,data:l,dataTypes:i,propertyName:p}){if(void 0!==l&&void 0!==s)throw new Error('both "data" and "dataProp" passed, only one allowed');const{gen:r}=n;if(void 0!==s){const{errorPath:l,dataPathArr:i,opts:p}=n;o(r.let("data",e._`${n.data}${(0,e.getProperty)(s)}`,!0)),a.errorPath=e.str`${l}${(0,t.getErrorPath)(s,u,p.jsPropertySyntax)}`,a.parentDataProperty=e._`${s}`,a.dataPathArr=[...i,a.parentDataProperty]}function o(e){a.data=e,a.dataLevel=n.dataLevel+1,a.dataTypes=[],n.definedProperties=new Set,a.parentData=n.data,a.dataNames=[...n.dataNames,e]}void 0!==l&&(o(l instanceof e.Name?l:r.let("data",l,!0)),void 0!==p&&(a.propertyName=p)),i&&(a.dataTypes=i)},B.extendSubschemaMode=function(e,{jtdDiscriminator:t,jtdMetadata:a,compositeRule:n,createErrors:s,allErrors:u}){void 0!==n&&(e.compositeRule=n),void 0!==s&&(e.createErrors=s),void 0!==u&&(e.allErrors=u),e.jtdDiscriminator=t,e.jtdMetadata=a},B}(),r=x(),o=m,g=X,c=h,f=S();function y({gen:e,validateName:t,schema:a,schemaEnv:n,opts:s},u){s.code.es5?e.func(t,r._`${o.default.data}, ${o.default.valCxt}`,n.$async,(()=>{e.code(r._`"use strict"; ${d(a,s)}`),function(e,t){e.if(o.default.valCxt,(()=>{e.var(o.default.instancePath,r._`${o.default.valCxt}.${o.default.instancePath}`),e.var(o.default.parentData,r._`${o.default.valCxt}.${o.default.parentData}`),e.var(o.default.parentDataProperty,r._`${o.default.valCxt}.${o.default.parentDataProperty}`),e.var(o.default.rootData,r._`${o.default.valCxt}.${o.default.rootData}`),t.dynamicRef&&e.var(o.default.dynamicAnchors,r._`${o.default.valCxt}.${o.default.dynamicAnchors}`)}),(()=>{e.var(o.default.instancePath,r._`""`),e.var(o.default.parentData,r._`undefined`),e.var(o.default.parentDataProperty,r._`undefined`),e.var(o.default.rootData,o.default.data),t.dynamicRef&&e.var(o.default.dynamicAnchors,r._`{}`)}))}(e,s),e.code(u)})):e.func(t,r._`${o.default.data}, ${function(e){return r._`{${o.default.instancePath}="", ${o.default.parentData}, ${o.default.parentDataProperty}, ${o.default.rootData}=${o.default.data}${e.dynamicRef?r._`, ${o.default.dynamicAnchors}={}`:r.nil}}={}`}(s)}`,n.$async,(()=>e.code(d(a,s)).code(u)))}function d(e,t){const a="object"==typeof e&&e[t.schemaId];return a&&(t.code.source||t.code.process)?r._`/*# sourceURL=${a} */`:r.nil}function b(t,a){v(t)&&(E(t),_(t))?function(e,t){const{schema:a,gen:n,opts:s}=e;s.$comment&&a.$comment&&I(e);(function(e){const t=e.schema[e.opts.schemaId];t&&(e.baseId=(0,g.resolveUrl)(e.opts.uriResolver,e.baseId,t))})(e),function(e){if(e.schema.$async&&!e.schemaEnv.$async)throw new Error("async schema in sync schema")}(e);const u=n.const("_errs",o.default.errors);O(e,u),n.var(t,r._`${u} === ${o.default.errors}`)}(t,a):(0,e.boolOrEmptySchema)(t,a)}function _({schema:e,self:t}){if("boolean"==typeof e)return!e;for(const a in e)if(t.RULES.all[a])return!0;return!1}function v(e){return"boolean"!=typeof e.schema}function E(e){(0,c.checkUnknownRules)(e),function(e){const{schema:t,errSchemaPath:a,opts:n,self:s}=e;t.$ref&&n.ignoreKeywordsWithRef&&(0,c.schemaHasRulesButRef)(t,s.RULES)&&s.logger.warn(`$ref: keywords ignored in schema at path "${a}"`)}(e)}function O(e,a){if(e.opts.jtd)return N(e,[],!1,a);const n=(0,t.getSchemaTypes)(e.schema);N(e,n,!(0,t.coerceAndCheckDataType)(e,n),a)}function I({gen:e,schemaEnv:t,schema:a,errSchemaPath:n,opts:s}){const u=a.$comment;if(!0===s.$comment)e.code(r._`${o.default.self}.logger.log(${u})`);else if("function"==typeof s.$comment){const a=r.str`${n}/$comment`,s=e.scopeValue("root",{ref:t.root});e.code(r._`${o.default.self}.opts.$comment(${u}, ${a}, ${s}.schema)`)}}function N(e,t,s,u){const{gen:l,schema:i,data:p,allErrors:g,opts:f,self:y}=e,{RULES:d}=y;function b(c){(0,a.shouldUseGroup)(i,c)&&(c.type?(l.if((0,n.checkDataType)(c.type,p,f.strictNumbers)),A(e,c),1===t.length&&t[0]===c.type&&s&&(l.else(),(0,n.reportTypeError)(e)),l.endIf()):A(e,c),g||l.if(r._`${o.default.errors} === ${u||0}`))}!i.$ref||!f.ignoreKeywordsWithRef&&(0,c.schemaHasRulesButRef)(i,d)?(f.jtd||function(e,t){if(e.schemaEnv.meta||!e.opts.strictTypes)return;(function(e,t){if(!t.length)return;if(!e.dataTypes.length)return void(e.dataTypes=t);t.forEach((t=>{P(e.dataTypes,t)||M(e,`type "${t}" not allowed by context "${e.dataTypes.join(",")}"`)})),function(e,t){const a=[];for(const n of e.dataTypes)P(t,n)?a.push(n):t.includes("integer")&&"number"===n&&a.push("integer");e.dataTypes=a}(e,t)})(e,t),e.opts.allowUnionTypes||function(e,t){t.length>1&&(2!==t.length||!t.includes("null"))&&M(e,"use allowUnionTypes to allow union type keyword")}(e,t);!function(e,t){const n=e.self.RULES.all;for(const s in n){const u=n[s];if("object"==typeof u&&(0,a.shouldUseRule)(e.schema,u)){const{type:a}=u.definition;a.length&&!a.some((e=>R(t,e)))&&M(e,`missing type "${a.join(",")}" for keyword "${s}"`)}}}(e,e.dataTypes)}(e,t),l.block((()=>{for(const e of d.rules)b(e);b(d.post)}))):l.block((()=>L(e,"$ref",d.all.$ref.definition)))}function A(e,t){const{gen:n,schema:u,opts:{useDefaults:l}}=e;l&&(0,s.assignDefaults)(e,t.type),n.block((()=>{for(const n of t.rules)(0,a.shouldUseRule)(u,n)&&L(e,n.keyword,n.definition,t.type)}))}function R(e,t){return e.includes(t)||"number"===t&&e.includes("integer")}function P(e,t){return e.includes(t)||"integer"===t&&e.includes("number")}function M(e,t){t+=` at "${e.schemaEnv.baseId+e.errSchemaPath}" (strictTypes)`,(0,c.checkStrictMode)(e,t,e.opts.strictTypes)}i.validateFunctionCode=function(t){v(t)&&(E(t),_(t))?function(e){const{schema:t,opts:a,gen:n}=e;y(e,(()=>{a.$comment&&t.$comment&&I(e),function(e){const{schema:t,opts:a}=e;void 0!==t.default&&a.useDefaults&&a.strictSchema&&(0,c.checkStrictMode)(e,"default is ignored in the schema root")}(e),n.let(o.default.vErrors,null),n.let(o.default.errors,0),a.unevaluated&&function(e){const{gen:t,validateName:a}=e;e.evaluated=t.const("evaluated",r._`${a}.evaluated`),t.if(r._`${e.evaluated}.dynamicProps`,(()=>t.assign(r._`${e.evaluated}.props`,r._`undefined`))),t.if(r._`${e.evaluated}.dynamicItems`,(()=>t.assign(r._`${e.evaluated}.items`,r._`undefined`)))}(e),O(e),function(e){const{gen:t,schemaEnv:a,validateName:n,ValidationError:s,opts:u}=e;a.$async?t.if(r._`${o.default.errors} === 0`,(()=>t.return(o.default.data)),(()=>t.throw(r._`new ${s}(${o.default.vErrors})`))):(t.assign(r._`${n}.errors`,o.default.vErrors),u.unevaluated&&function({gen:e,evaluated:t,props:a,items:n}){a instanceof r.Name&&e.assign(r._`${t}.props`,a);n instanceof r.Name&&e.assign(r._`${t}.items`,n)}(e),t.return(r._`${o.default.errors} === 0`))}(e)}))}(t):y(t,(()=>(0,e.topBoolOrEmptySchema)(t)))};class ${constructor(e,t,a){if((0,u.validateKeywordUsage)(e,t,a),this.gen=e.gen,this.allErrors=e.allErrors,this.keyword=a,this.data=e.data,this.schema=e.schema[a],this.$data=t.$data&&e.opts.$data&&this.schema&&this.schema.$data,this.schemaValue=(0,c.schemaRefOrVal)(e,this.schema,a,this.$data),this.schemaType=t.schemaType,this.parentSchema=e.schema,this.params={},this.it=e,this.def=t,this.$data)this.schemaCode=e.gen.const("vSchema",j(this.$data,e));else if(this.schemaCode=this.schemaValue,!(0,u.validSchemaType)(this.schema,t.schemaType,t.allowUndefined))throw new Error(`${a} value must be ${JSON.stringify(t.schemaType)}`);("code"in t?t.trackErrors:!1!==t.errors)&&(this.errsCount=e.gen.const("_errs",o.default.errors))}result(e,t,a){this.failResult((0,r.not)(e),t,a)}failResult(e,t,a){this.gen.if(e),a?a():this.error(),t?(this.gen.else(),t(),this.allErrors&&this.gen.endIf()):this.allErrors?this.gen.endIf():this.gen.else()}pass(e,t){this.failResult((0,r.not)(e),void 0,t)}fail(e){if(void 0===e)return this.error(),void(this.allErrors||this.gen.if(!1));this.gen.if(e),this.error(),this.allErrors?this.gen.endIf():this.gen.else()}fail$data(e){if(!this.$data)return this.fail(e);const{schemaCode:t}=this;this.fail(r._`${t} !== undefined && (${(0,r.or)(this.invalid$data(),e)})`)}error(e,t,a){if(t)return this.setParams(t),this._error(e,a),void this.setParams({});this._error(e,a)}_error(e,t){(e?f.reportExtraError:f.reportError)(this,this.def.error,t)}$dataError(){(0,f.reportError)(this,this.def.$dataError||f.keyword$DataError)}reset(){if(void 0===this.errsCount)throw new Error('add "trackErrors" to keyword definition');(0,f.resetErrorsCount)(this.gen,this.errsCount)}ok(e){this.allErrors||this.gen.if(e)}setParams(e,t){t?Object.assign(this.params,e):this.params=e}block$data(e,t,a=r.nil){this.gen.block((()=>{this.check$data(e,a),t()}))}check$data(e=r.nil,t=r.nil){if(!this.$data)return;const{gen:a,schemaCode:n,schemaType:s,def:u}=this;a.if((0,r.or)(r._`${n} === undefined`,t)),e!==r.nil&&a.assign(e,!0),(s.length||u.validateSchema)&&(a.elseIf(this.invalid$data()),this.$dataError(),e!==r.nil&&a.assign(e,!1)),a.else()}invalid$data(){const{gen:e,schemaCode:t,schemaType:a,def:s,it:u}=this;return(0,r.or)(function(){if(a.length){if(!(t instanceof r.Name))throw new Error("ajv implementation error");const e=Array.isArray(a)?a:[a];return r._`${(0,n.checkDataTypes)(e,t,u.opts.strictNumbers,n.DataType.Wrong)}`}return r.nil}(),function(){if(s.validateSchema){const a=e.scopeValue("validate$data",{ref:s.validateSchema});return r._`!${a}(${t})`}return r.nil}())}subschema(e,t){const a=(0,l.getSubschema)(this.it,e);(0,l.extendSubschemaData)(a,this.it,e),(0,l.extendSubschemaMode)(a,e);const n={...this.it,...a,items:void 0,props:void 0};return b(n,t),n}mergeEvaluated(e,t){const{it:a,gen:n}=this;a.opts.unevaluated&&(!0!==a.props&&void 0!==e.props&&(a.props=c.mergeEvaluated.props(n,e.props,a.props,t)),!0!==a.items&&void 0!==e.items&&(a.items=c.mergeEvaluated.items(n,e.items,a.items,t)))}mergeValidEvaluated(e,t){const{it:a,gen:n}=this;if(a.opts.unevaluated&&(!0!==a.props||!0!==a.items))return n.if(t,(()=>this.mergeEvaluated(e,r.Name))),!0}}function L(e,t,a,n){const s=new $(e,a,t);"code"in a?a.code(s,n):s.$data&&a.validate?(0,u.funcKeywordCode)(s,a):"macro"in a?(0,u.macroKeywordCode)(s,a):(a.compile||a.validate)&&(0,u.funcKeywordCode)(s,a)}i.KeywordCxt=$;const W=/^\/(?:[^~]|~0|~1)*$/,F=/^([0-9]+)(#|\/(?:[^~]|~0|~1)*)?$/;function j(e,{dataLevel:t,dataNames:a,dataPathArr:n}){let s,u;if(""===e)return o.default.rootData;if("/"===e[0]){if(!W.test(e))throw new Error(`Invalid JSON-pointer: ${e}`);s=e,u=o.default.rootData}else{const l=F.exec(e);if(!l)throw new Error(`Invalid JSON-pointer: ${e}`);const i=+l[1];if(s=l[2],"#"===s){if(i>=t)throw new Error(p("property/index",i));return n[t-i]}if(i>t)throw new Error(p("data",i));if(u=a[t-i],!s)return u}let l=u;const i=s.split("/");for(const e of i)e&&(u=r._`${u}${(0,r.getProperty)((0,c.unescapeJsonPointer)(e))}`,l=r._`${l} && ${u}`);return l;function p(e,a){return`Cannot access ${e} ${a} levels up, current level is ${t}`}}return i.getData=j,i}X.getSchemaRefs=function(e,t){if("boolean"==typeof e)return{};const{schemaId:a,uriResolver:n}=this.opts,s=oe(e[a]||t),u={"":s},l=ie(n,s,!1),i={},p=new Set;return ae(e,{allKeys:!0},((e,t,n,s)=>{if(void 0===s)return;const g=l+t;let c=u[s];function f(t){const a=this.opts.uriResolver.resolve;if(t=oe(c?a(c,t):t),p.has(t))throw o(t);p.add(t);let n=this.refs[t];return"string"==typeof n&&(n=this.refs[n]),"object"==typeof n?r(e,n.schema,t):t!==oe(g)&&("#"===t[0]?(r(e,i[t],t),i[t]=e):this.refs[t]=g),t}function y(e){if("string"==typeof e){if(!ge.test(e))throw new Error(`invalid anchor "${e}"`);f.call(this,`#${e}`)}}"string"==typeof e[a]&&(c=f.call(this,e[a])),y.call(this,e.$anchor),y.call(this,e.$dynamicAnchor),u[t]=c})),i;function r(e,t,a){if(void 0!==t&&!te(e,t))throw o(a)}function o(e){return new Error(`reference "${e}" resolves to more than one schema`)}};var ye,de={};function be(){if(ye)return de;ye=1,Object.defineProperty(de,"__esModule",{value:!0});class e extends Error{constructor(e){super("validation failed"),this.errors=e,this.ajv=this.validation=!0}}return de.default=e,de}var xe,he={};function me(){if(xe)return he;xe=1,Object.defineProperty(he,"__esModule",{value:!0});const e=X;class t extends Error{constructor(t,a,n,s){super(s||`can't resolve reference ${n} from id ${a}`),this.missingRef=(0,e.resolveUrl)(t,a,n),this.missingSchema=(0,e.normalizeId)((0,e.getFullPath)(t,this.missingRef))}}return he.default=t,he}var _e={};Object.defineProperty(_e,"__esModule",{value:!0}),_e.resolveSchema=_e.getCompilingSchema=_e.resolveRef=_e.compileSchema=_e.SchemaEnv=void 0;const ve=x(),Ee=be(),we=m,Se=X,Ce=h,Oe=fe();class Ie{constructor(e){var t;let a;this.refs={},this.dynamicAnchors={},"object"==typeof e.schema&&(a=e.schema),this.schema=e.schema,this.schemaId=e.schemaId,this.root=e.root||this,this.baseId=null!==(t=e.baseId)&&void 0!==t?t:(0,Se.normalizeId)(null==a?void 0:a[e.schemaId||"$id"]),this.schemaPath=e.schemaPath,this.localRefs=e.localRefs,this.meta=e.meta,this.$async=null==a?void 0:a.$async,this.refs={}}}function Ne(e){const t=ke.call(this,e);if(t)return t;const a=(0,Se.getFullPath)(this.opts.uriResolver,e.root.baseId),{es5:n,lines:s}=this.opts.code,{ownProperties:u}=this.opts,l=new ve.CodeGen(this.scope,{es5:n,lines:s,ownProperties:u});let i;e.$async&&(i=l.scopeValue("Error",{ref:Ee.default,code:ve._`require("ajv/dist/runtime/validation_error").default`}));const p=l.scopeName("validate");e.validateName=p;const r={gen:l,allErrors:this.opts.allErrors,data:we.default.data,parentData:we.default.parentData,parentDataProperty:we.default.parentDataProperty,dataNames:[we.default.data],dataPathArr:[ve.nil],dataLevel:0,dataTypes:[],definedProperties:new Set,topSchemaRef:l.scopeValue("schema",!0===this.opts.code.source?{ref:e.schema,code:(0,ve.stringify)(e.schema)}:{ref:e.schema}),validateName:p,ValidationError:i,schema:e.schema,schemaEnv:e,rootId:a,baseId:e.baseId||a,schemaPath:ve.nil,errSchemaPath:e.schemaPath||(this.opts.jtd?"":"#"),errorPath:ve._`""`,opts:this.opts,self:this};let o;try{this._compilations.add(e),(0,Oe.validateFunctionCode)(r),l.optimize(this.opts.code.optimize);const t=l.toString();o=`${l.scopeRefs(we.default.scope)}return ${t}`,this.opts.code.process&&(o=this.opts.code.process(o,e));const a=new Function(`${we.default.self}`,`${we.default.scope}`,o)(this,this.scope.get());if(this.scope.value(p,{ref:a}),a.errors=null,a.schema=e.schema,a.schemaEnv=e,e.$async&&(a.$async=!0),!0===this.opts.code.source&&(a.source={validateName:p,validateCode:t,scopeValues:l._values}),this.opts.unevaluated){const{props:e,items:t}=r;a.evaluated={props:e instanceof ve.Name?void 0:e,items:t instanceof ve.Name?void 0:t,dynamicProps:e instanceof ve.Name,dynamicItems:t instanceof ve.Name},a.source&&(a.source.evaluated=(0,ve.stringify)(a.evaluated))}return e.validate=a,e}catch(t){throw delete e.validate,delete e.validateName,o&&this.logger.error("Error compiling schema, function code:",o),t}finally{this._compilations.delete(e)}}function Ae(e){return(0,Se.inlineRef)(e.schema,this.opts.inlineRefs)?e.schema:e.validate?e:Ne.call(this,e)}function ke(e){for(const n of this._compilations)if(a=e,(t=n).schema===a.schema&&t.root===a.root&&t.baseId===a.baseId)return n;var t,a}function Te(e,t){let a;for(;"string"==typeof(a=this.refs[t]);)t=a;return a||this.schemas[t]||De.call(this,e,t)}function De(e,t){const a=this.opts.uriResolver.parse(t),n=(0,Se._getFullPath)(this.opts.uriResolver,a);let s=(0,Se.getFullPath)(this.opts.uriResolver,e.baseId,void 0);if(Object.keys(e.schema).length>0&&n===s)return Pe.call(this,a,e);const u=(0,Se.normalizeId)(n),l=this.refs[u]||this.schemas[u];if("string"==typeof l){const t=De.call(this,e,l);if("object"!=typeof(null==t?void 0:t.schema))return;return Pe.call(this,a,t)}if("object"==typeof(null==l?void 0:l.schema)){if(l.validate||Ne.call(this,l),u===(0,Se.normalizeId)(t)){const{schema:t}=l,{schemaId:a}=this.opts,n=t[a];return n&&(s=(0,Se.resolveUrl)(this.opts.uriResolver,s,n)),new Ie({schema:t,schemaId:a,root:e,baseId:s})}return Pe.call(this,a,l)}}_e.SchemaEnv=Ie,_e.compileSchema=Ne,_e.resolveRef=function(e,t,a){var n;a=(0,Se.resolveUrl)(this.opts.uriResolver,t,a);const s=e.refs[a];if(s)return s;let u=Te.call(this,e,a);if(void 0===u){const s=null===(n=e.localRefs)||void 0===n?void 0:n[a],{schemaId:l}=this.opts;s&&(u=new Ie({schema:s,schemaId:l,root:e,baseId:t}))}return void 0!==u?e.refs[a]=Ae.call(this,u):void 0},_e.getCompilingSchema=ke,_e.resolveSchema=De;const Re=new Set(["properties","patternProperties","enum","dependencies","definitions"]);function Pe(e,{baseId:t,schema:a,root:n}){var s;if("/"!==(null===(s=e.fragment)||void 0===s?void 0:s[0]))return;for(const n of e.fragment.slice(1).split("/")){if("boolean"==typeof a)return;const e=a[(0,Ce.unescapeFragment)(n)];if(void 0===e)return;const s="object"==typeof(a=e)&&a[this.opts.schemaId];!Re.has(n)&&s&&(t=(0,Se.resolveUrl)(this.opts.uriResolver,t,s))}let u;if("boolean"!=typeof a&&a.$ref&&!(0,Ce.schemaHasRulesButRef)(a,this.RULES)){const e=(0,Se.resolveUrl)(this.opts.uriResolver,t,a.$ref);u=De.call(this,n,e)}const{schemaId:l}=this.opts;return u=u||new Ie({schema:a,schemaId:l,root:n,baseId:t}),u.schema!==u.root.schema?u:void 0}var Me={$id:"https://raw.githubusercontent.com/ajv-validator/ajv/master/lib/refs/data.json#",description:"Meta-schema for $data reference (JSON AnySchema extension proposal)",type:"object",required:["$data"],properties:{$data:{type:"string",anyOf:[{format:"relative-json-pointer"},{format:"json-pointer"}]}},additionalProperties:!1},$e={},Le={exports:{}};!function(e){function t(){for(var e=arguments.length,t=Array(e),a=0;a<e;a++)t[a]=arguments[a];if(t.length>1){t[0]=t[0].slice(0,-1);for(var n=t.length-1,s=1;s<n;++s)t[s]=t[s].slice(1,-1);return t[n]=t[n].slice(1),t.join("")}return t[0]}function a(e){return"(?:"+e+")"}function n(e){return void 0===e?"undefined":null===e?"null":Object.prototype.toString.call(e).split(" ").pop().split("]").shift().toLowerCase()}function s(e){return e.toUpperCase()}function u(e){return null!=e?e instanceof Array?e:"number"!=typeof e.length||e.split||e.setInterval||e.call?[e]:Array.prototype.slice.call(e):[]}function l(e,t){var a=e;if(t)for(var n in t)a[n]=t[n];return a}function i(e){var n="[A-Za-z]",s="[0-9]",u=t(s,"[A-Fa-f]"),l=a(a("%[EFef]"+u+"%"+u+u+"%"+u+u)+"|"+a("%[89A-Fa-f]"+u+"%"+u+u)+"|"+a("%"+u+u)),i="[\\!\\$\\&\\'\\(\\)\\*\\+\\,\\;\\=]",p=t("[\\:\\/\\?\\#\\[\\]\\@]",i),r=e?"[\\uE000-\\uF8FF]":"[]",o=t(n,s,"[\\-\\.\\_\\~]",e?"[\\xA0-\\u200D\\u2010-\\u2029\\u202F-\\uD7FF\\uF900-\\uFDCF\\uFDF0-\\uFFEF]":"[]");a(n+t(n,s,"[\\+\\-\\.]")+"*"),a(a(l+"|"+t(o,i,"[\\:]"))+"*");var g=a(a("25[0-5]")+"|"+a("2[0-4]"+s)+"|"+a("1"+s+s)+"|"+a("0?[1-9]"+s)+"|0?0?"+s),c=a(g+"\\."+g+"\\."+g+"\\."+g),f=a(u+"{1,4}"),y=a(a(f+"\\:"+f)+"|"+c),d=a(a(f+"\\:")+"{6}"+y),b=a("\\:\\:"+a(f+"\\:")+"{5}"+y),x=a(a(f)+"?\\:\\:"+a(f+"\\:")+"{4}"+y),h=a(a(a(f+"\\:")+"{0,1}"+f)+"?\\:\\:"+a(f+"\\:")+"{3}"+y),m=a(a(a(f+"\\:")+"{0,2}"+f)+"?\\:\\:"+a(f+"\\:")+"{2}"+y),_=a(a(a(f+"\\:")+"{0,3}"+f)+"?\\:\\:"+f+"\\:"+y),v=a(a(a(f+"\\:")+"{0,4}"+f)+"?\\:\\:"+y),E=a(a(a(f+"\\:")+"{0,5}"+f)+"?\\:\\:"+f),w=a(a(a(f+"\\:")+"{0,6}"+f)+"?\\:\\:"),S=a([d,b,x,h,m,_,v,E,w].join("|")),C=a(a(o+"|"+l)+"+");a("[vV]"+u+"+\\."+t(o,i,"[\\:]")+"+"),a(a(l+"|"+t(o,i))+"*");var O=a(l+"|"+t(o,i,"[\\:\\@]"));return a(a(l+"|"+t(o,i,"[\\@]"))+"+"),a(a(O+"|"+t("[\\/\\?]",r))+"*"),{NOT_SCHEME:new RegExp(t("[^]",n,s,"[\\+\\-\\.]"),"g"),NOT_USERINFO:new RegExp(t("[^\\%\\:]",o,i),"g"),NOT_HOST:new RegExp(t("[^\\%\\[\\]\\:]",o,i),"g"),NOT_PATH:new RegExp(t("[^\\%\\/\\:\\@]",o,i),"g"),NOT_PATH_NOSCHEME:new RegExp(t("[^\\%\\/\\@]",o,i),"g"),NOT_QUERY:new RegExp(t("[^\\%]",o,i,"[\\:\\@\\/\\?]",r),"g"),NOT_FRAGMENT:new RegExp(t("[^\\%]",o,i,"[\\:\\@\\/\\?]"),"g"),ESCAPE:new RegExp(t("[^]",o,i),"g"),UNRESERVED:new RegExp(o,"g"),OTHER_CHARS:new RegExp(t("[^\\%]",o,p),"g"),PCT_ENCODED:new RegExp(l,"g"),IPV4ADDRESS:new RegExp("^("+c+")$"),IPV6ADDRESS:new RegExp("^\\[?("+S+")"+a(a("\\%25|\\%(?!"+u+"{2})")+"("+C+")")+"?\\]?$")}}var p=i(!1),r=i(!0),o=function(){function e(e,t){var a=[],n=!0,s=!1,u=void 0;try{for(var l,i=e[Symbol.iterator]();!(n=(l=i.next()).done)&&(a.push(l.value),!t||a.length!==t);n=!0);}catch(e){s=!0,u=e}finally{try{!n&&i.return&&i.return()}finally{if(s)throw u}}return a}return function(t,a){if(Array.isArray(t))return t;if(Symbol.iterator in Object(t))return e(t,a);throw new TypeError("Invalid attempt to destructure non-iterable instance")}}(),g=function(e){if(Array.isArray(e)){for(var t=0,a=Array(e.length);t<e.length;t++)a[t]=e[t];return a}return Array.from(e)},c=2147483647,f=36,y=1,d=26,b=38,x=700,h=72,m=128,_="-",v=/^xn--/,E=/[^\0-\x7E]/,w=/[\x2E\u3002\uFF0E\uFF61]/g,S={overflow:"Overflow: input needs wider integers to process","not-basic":"Illegal input >= 0x80 (not a basic code point)","invalid-input":"Invalid input"},C=f-y,O=Math.floor,I=String.fromCharCode;function N(e){throw new RangeError(S[e])}function A(e,t){for(var a=[],n=e.length;n--;)a[n]=t(e[n]);return a}function k(e,t){var a=e.split("@"),n="";return a.length>1&&(n=a[0]+"@",e=a[1]),n+A((e=e.replace(w,".")).split("."),t).join(".")}function T(e){for(var t=[],a=0,n=e.length;a<n;){var s=e.charCodeAt(a++);if(s>=55296&&s<=56319&&a<n){var u=e.charCodeAt(a++);56320==(64512&u)?t.push(((1023&s)<<10)+(1023&u)+65536):(t.push(s),a--)}else t.push(s)}return t}var D=function(e){return e-48<10?e-22:e-65<26?e-65:e-97<26?e-97:f},R=function(e,t){return e+22+75*(e<26)-((0!=t)<<5)},P=function(e,t,a){var n=0;for(e=a?O(e/x):e>>1,e+=O(e/t);e>C*d>>1;n+=f)e=O(e/C);return O(n+(C+1)*e/(e+b))},M=function(e){var t=[],a=e.length,n=0,s=m,u=h,l=e.lastIndexOf(_);l<0&&(l=0);for(var i=0;i<l;++i)e.charCodeAt(i)>=128&&N("not-basic"),t.push(e.charCodeAt(i));for(var p=l>0?l+1:0;p<a;){for(var r=n,o=1,g=f;;g+=f){p>=a&&N("invalid-input");var b=D(e.charCodeAt(p++));(b>=f||b>O((c-n)/o))&&N("overflow"),n+=b*o;var x=g<=u?y:g>=u+d?d:g-u;if(b<x)break;var v=f-x;o>O(c/v)&&N("overflow"),o*=v}var E=t.length+1;u=P(n-r,E,0==r),O(n/E)>c-s&&N("overflow"),s+=O(n/E),n%=E,t.splice(n++,0,s)}return String.fromCodePoint.apply(String,t)},$=function(e){var t=[],a=(e=T(e)).length,n=m,s=0,u=h,l=!0,i=!1,p=void 0;try{for(var r,o=e[Symbol.iterator]();!(l=(r=o.next()).done);l=!0){var g=r.value;g<128&&t.push(I(g))}}catch(e){i=!0,p=e}finally{try{!l&&o.return&&o.return()}finally{if(i)throw p}}var b=t.length,x=b;for(b&&t.push(_);x<a;){var v=c,E=!0,w=!1,S=void 0;try{for(var C,A=e[Symbol.iterator]();!(E=(C=A.next()).done);E=!0){var k=C.value;k>=n&&k<v&&(v=k)}}catch(e){w=!0,S=e}finally{try{!E&&A.return&&A.return()}finally{if(w)throw S}}var D=x+1;v-n>O((c-s)/D)&&N("overflow"),s+=(v-n)*D,n=v;var M=!0,$=!1,L=void 0;try{for(var W,F=e[Symbol.iterator]();!(M=(W=F.next()).done);M=!0){var j=W.value;if(j<n&&++s>c&&N("overflow"),j==n){for(var U=s,z=f;;z+=f){var V=z<=u?y:z>=u+d?d:z-u;if(U<V)break;var H=U-V,q=f-V;t.push(I(R(V+H%q,0))),U=O(H/q)}t.push(I(R(U,0))),u=P(s,D,x==b),s=0,++x}}}catch(e){$=!0,L=e}finally{try{!M&&F.return&&F.return()}finally{if($)throw L}}++s,++n}return t.join("")},L=function(e){return k(e,(function(e){return v.test(e)?M(e.slice(4).toLowerCase()):e}))},W=function(e){return k(e,(function(e){return E.test(e)?"xn--"+$(e):e}))},F={version:"2.1.0",ucs2:{decode:T,encode:function(e){return String.fromCodePoint.apply(String,g(e))}},decode:M,encode:$,toASCII:W,toUnicode:L},j={};function U(e){var t=e.charCodeAt(0);return t<16?"%0"+t.toString(16).toUpperCase():t<128?"%"+t.toString(16).toUpperCase():t<2048?"%"+(t>>6|192).toString(16).toUpperCase()+"%"+(63&t|128).toString(16).toUpperCase():"%"+(t>>12|224).toString(16).toUpperCase()+"%"+(t>>6&63|128).toString(16).toUpperCase()+"%"+(63&t|128).toString(16).toUpperCase()}function z(e){for(var t="",a=0,n=e.length;a<n;){var s=parseInt(e.substr(a+1,2),16);if(s<128)t+=String.fromCharCode(s),a+=3;else if(s>=194&&s<224){if(n-a>=6){var u=parseInt(e.substr(a+4,2),16);t+=String.fromCharCode((31&s)<<6|63&u)}else t+=e.substr(a,6);a+=6}else if(s>=224){if(n-a>=9){var l=parseInt(e.substr(a+4,2),16),i=parseInt(e.substr(a+7,2),16);t+=String.fromCharCode((15&s)<<12|(63&l)<<6|63&i)}else t+=e.substr(a,9);a+=9}else t+=e.substr(a,3),a+=3}return t}function V(e,t){function a(e){var a=z(e);return a.match(t.UNRESERVED)?a:e}return e.scheme&&(e.scheme=String(e.scheme).replace(t.PCT_ENCODED,a).toLowerCase().replace(t.NOT_SCHEME,"")),void 0!==e.userinfo&&(e.userinfo=String(e.userinfo).replace(t.PCT_ENCODED,a).replace(t.NOT_USERINFO,U).replace(t.PCT_ENCODED,s)),void 0!==e.host&&(e.host=String(e.host).replace(t.PCT_ENCODED,a).toLowerCase().replace(t.NOT_HOST,U).replace(t.PCT_ENCODED,s)),void 0!==e.path&&(e.path=String(e.path).replace(t.PCT_ENCODED,a).replace(e.scheme?t.NOT_PATH:t.NOT_PATH_NOSCHEME,U).replace(t.PCT_ENCODED,s)),void 0!==e.query&&(e.query=String(e.query).replace(t.PCT_ENCODED,a).replace(t.NOT_QUERY,U).replace(t.PCT_ENCODED,s)),void 0!==e.fragment&&(e.fragment=String(e.fragment).replace(t.PCT_ENCODED,a).replace(t.NOT_FRAGMENT,U).replace(t.PCT_ENCODED,s)),e}function H(e){return e.replace(/^0*(.*)/,"$1")||"0"}function q(e,t){var a=e.match(t.IPV4ADDRESS)||[],n=o(a,2)[1];return n?n.split(".").map(H).join("."):e}function Y(e,t){var a=e.match(t.IPV6ADDRESS)||[],n=o(a,3),s=n[1],u=n[2];if(s){for(var l=s.toLowerCase().split("::").reverse(),i=o(l,2),p=i[0],r=i[1],g=r?r.split(":").map(H):[],c=p.split(":").map(H),f=t.IPV4ADDRESS.test(c[c.length-1]),y=f?7:8,d=c.length-y,b=Array(y),x=0;x<y;++x)b[x]=g[x]||c[d+x]||"";f&&(b[y-1]=q(b[y-1],t));var h=b.reduce((function(e,t,a){if(!t||"0"===t){var n=e[e.length-1];n&&n.index+n.length===a?n.length++:e.push({index:a,length:1})}return e}),[]).sort((function(e,t){return t.length-e.length}))[0],m=void 0;if(h&&h.length>1){var _=b.slice(0,h.index),v=b.slice(h.index+h.length);m=_.join(":")+"::"+v.join(":")}else m=b.join(":");return u&&(m+="%"+u),m}return e}var B=/^(?:([^:\/?#]+):)?(?:\/\/((?:([^\/?#@]*)@)?(\[[^\/?#\]]+\]|[^\/?#:]*)(?:\:(\d*))?))?([^?#]*)(?:\?([^#]*))?(?:#((?:.|\n|\r)*))?/i,X=void 0==="".match(/(){0}/)[1];function G(e){var t=arguments.length>1&&void 0!==arguments[1]?arguments[1]:{},a={},n=!1!==t.iri?r:p;"suffix"===t.reference&&(e=(t.scheme?t.scheme+":":"")+"//"+e);var s=e.match(B);if(s){X?(a.scheme=s[1],a.userinfo=s[3],a.host=s[4],a.port=parseInt(s[5],10),a.path=s[6]||"",a.query=s[7],a.fragment=s[8],isNaN(a.port)&&(a.port=s[5])):(a.scheme=s[1]||void 0,a.userinfo=-1!==e.indexOf("@")?s[3]:void 0,a.host=-1!==e.indexOf("//")?s[4]:void 0,a.port=parseInt(s[5],10),a.path=s[6]||"",a.query=-1!==e.indexOf("?")?s[7]:void 0,a.fragment=-1!==e.indexOf("#")?s[8]:void 0,isNaN(a.port)&&(a.port=e.match(/\/\/(?:.|\n)*\:(?:\/|\?|\#|$)/)?s[4]:void 0)),a.host&&(a.host=Y(q(a.host,n),n)),void 0!==a.scheme||void 0!==a.userinfo||void 0!==a.host||void 0!==a.port||a.path||void 0!==a.query?void 0===a.scheme?a.reference="relative":void 0===a.fragment?a.reference="absolute":a.reference="uri":a.reference="same-document",t.reference&&"suffix"!==t.reference&&t.reference!==a.reference&&(a.error=a.error||"URI is not a "+t.reference+" reference.");var u=j[(t.scheme||a.scheme||"").toLowerCase()];if(t.unicodeSupport||u&&u.unicodeSupport)V(a,n);else{if(a.host&&(t.domainHost||u&&u.domainHost))try{a.host=F.toASCII(a.host.replace(n.PCT_ENCODED,z).toLowerCase())}catch(e){a.error=a.error||"Host's domain name can not be converted to ASCII via punycode: "+e}V(a,p)}u&&u.parse&&u.parse(a,t)}else a.error=a.error||"URI can not be parsed.";return a}function K(e,t){var a=!1!==t.iri?r:p,n=[];return void 0!==e.userinfo&&(n.push(e.userinfo),n.push("@")),void 0!==e.host&&n.push(Y(q(String(e.host),a),a).replace(a.IPV6ADDRESS,(function(e,t,a){return"["+t+(a?"%25"+a:"")+"]"}))),"number"!=typeof e.port&&"string"!=typeof e.port||(n.push(":"),n.push(String(e.port))),n.length?n.join(""):void 0}var Z=/^\.\.?\//,J=/^\/\.(\/|$)/,Q=/^\/\.\.(\/|$)/,ee=/^\/?(?:.|\n)*?(?=\/|$)/;function te(e){for(var t=[];e.length;)if(e.match(Z))e=e.replace(Z,"");else if(e.match(J))e=e.replace(J,"/");else if(e.match(Q))e=e.replace(Q,"/"),t.pop();else if("."===e||".."===e)e="";else{var a=e.match(ee);if(!a)throw new Error("Unexpected dot segment condition");var n=a[0];e=e.slice(n.length),t.push(n)}return t.join("")}function ae(e){var t=arguments.length>1&&void 0!==arguments[1]?arguments[1]:{},a=t.iri?r:p,n=[],s=j[(t.scheme||e.scheme||"").toLowerCase()];if(s&&s.serialize&&s.serialize(e,t),e.host)if(a.IPV6ADDRESS.test(e.host));else if(t.domainHost||s&&s.domainHost)try{e.host=t.iri?F.toUnicode(e.host):F.toASCII(e.host.replace(a.PCT_ENCODED,z).toLowerCase())}catch(a){e.error=e.error||"Host's domain name can not be converted to "+(t.iri?"Unicode":"ASCII")+" via punycode: "+a}V(e,a),"suffix"!==t.reference&&e.scheme&&(n.push(e.scheme),n.push(":"));var u=K(e,t);if(void 0!==u&&("suffix"!==t.reference&&n.push("//"),n.push(u),e.path&&"/"!==e.path.charAt(0)&&n.push("/")),void 0!==e.path){var l=e.path;t.absolutePath||s&&s.absolutePath||(l=te(l)),void 0===u&&(l=l.replace(/^\/\//,"/%2F")),n.push(l)}return void 0!==e.query&&(n.push("?"),n.push(e.query)),void 0!==e.fragment&&(n.push("#"),n.push(e.fragment)),n.join("")}function ne(e,t){var a=arguments.length>2&&void 0!==arguments[2]?arguments[2]:{},n={};return arguments[3]||(e=G(ae(e,a),a),t=G(ae(t,a),a)),!(a=a||{}).tolerant&&t.scheme?(n.scheme=t.scheme,n.userinfo=t.userinfo,n.host=t.host,n.port=t.port,n.path=te(t.path||""),n.query=t.query):(void 0!==t.userinfo||void 0!==t.host||void 0!==t.port?(n.userinfo=t.userinfo,n.host=t.host,n.port=t.port,n.path=te(t.path||""),n.query=t.query):(t.path?("/"===t.path.charAt(0)?n.path=te(t.path):(void 0===e.userinfo&&void 0===e.host&&void 0===e.port||e.path?e.path?n.path=e.path.slice(0,e.path.lastIndexOf("/")+1)+t.path:n.path=t.path:n.path="/"+t.path,n.path=te(n.path)),n.query=t.query):(n.path=e.path,void 0!==t.query?n.query=t.query:n.query=e.query),n.userinfo=e.userinfo,n.host=e.host,n.port=e.port),n.scheme=e.scheme),n.fragment=t.fragment,n}function se(e,t,a){var n=l({scheme:"null"},a);return ae(ne(G(e,n),G(t,n),n,!0),n)}function ue(e,t){return"string"==typeof e?e=ae(G(e,t),t):"object"===n(e)&&(e=G(ae(e,t),t)),e}function le(e,t,a){return"string"==typeof e?e=ae(G(e,a),a):"object"===n(e)&&(e=ae(e,a)),"string"==typeof t?t=ae(G(t,a),a):"object"===n(t)&&(t=ae(t,a)),e===t}function ie(e,t){return e&&e.toString().replace(t&&t.iri?r.ESCAPE:p.ESCAPE,U)}function pe(e,t){return e&&e.toString().replace(t&&t.iri?r.PCT_ENCODED:p.PCT_ENCODED,z)}var re={scheme:"http",domainHost:!0,parse:function(e,t){return e.host||(e.error=e.error||"HTTP URIs must have a host."),e},serialize:function(e,t){var a="https"===String(e.scheme).toLowerCase();return e.port!==(a?443:80)&&""!==e.port||(e.port=void 0),e.path||(e.path="/"),e}},oe={scheme:"https",domainHost:re.domainHost,parse:re.parse,serialize:re.serialize};function ge(e){return"boolean"==typeof e.secure?e.secure:"wss"===String(e.scheme).toLowerCase()}var ce={scheme:"ws",domainHost:!0,parse:function(e,t){var a=e;return a.secure=ge(a),a.resourceName=(a.path||"/")+(a.query?"?"+a.query:""),a.path=void 0,a.query=void 0,a},serialize:function(e,t){if(e.port!==(ge(e)?443:80)&&""!==e.port||(e.port=void 0),"boolean"==typeof e.secure&&(e.scheme=e.secure?"wss":"ws",e.secure=void 0),e.resourceName){var a=e.resourceName.split("?"),n=o(a,2),s=n[0],u=n[1];e.path=s&&"/"!==s?s:void 0,e.query=u,e.resourceName=void 0}return e.fragment=void 0,e}},fe={scheme:"wss",domainHost:ce.domainHost,parse:ce.parse,serialize:ce.serialize},ye={},de="[A-Za-z0-9\\-\\.\\_\\~\\xA0-\\u200D\\u2010-\\u2029\\u202F-\\uD7FF\\uF900-\\uFDCF\\uFDF0-\\uFFEF]",be="[0-9A-Fa-f]",xe=a(a("%[EFef]"+be+"%"+be+be+"%"+be+be)+"|"+a("%[89A-Fa-f]"+be+"%"+be+be)+"|"+a("%"+be+be)),he="[A-Za-z0-9\\!\\$\\%\\'\\*\\+\\-\\^\\_\\`\\{\\|\\}\\~]",me=t("[\\!\\$\\%\\'\\(\\)\\*\\+\\,\\-\\.0-9\\<\\>A-Z\\x5E-\\x7E]",'[\\"\\\\]'),_e="[\\!\\$\\'\\(\\)\\*\\+\\,\\;\\:\\@]",ve=new RegExp(de,"g"),Ee=new RegExp(xe,"g"),we=new RegExp(t("[^]",he,"[\\.]",'[\\"]',me),"g"),Se=new RegExp(t("[^]",de,_e),"g"),Ce=Se;function Oe(e){var t=z(e);return t.match(ve)?t:e}var Ie={scheme:"mailto",parse:function(e,t){var a=e,n=a.to=a.path?a.path.split(","):[];if(a.path=void 0,a.query){for(var s=!1,u={},l=a.query.split("&"),i=0,p=l.length;i<p;++i){var r=l[i].split("=");switch(r[0]){case"to":for(var o=r[1].split(","),g=0,c=o.length;g<c;++g)n.push(o[g]);break;case"subject":a.subject=pe(r[1],t);break;case"body":a.body=pe(r[1],t);break;default:s=!0,u[pe(r[0],t)]=pe(r[1],t)}}s&&(a.headers=u)}a.query=void 0;for(var f=0,y=n.length;f<y;++f){var d=n[f].split("@");if(d[0]=pe(d[0]),t.unicodeSupport)d[1]=pe(d[1],t).toLowerCase();else try{d[1]=F.toASCII(pe(d[1],t).toLowerCase())}catch(e){a.error=a.error||"Email address's domain name can not be converted to ASCII via punycode: "+e}n[f]=d.join("@")}return a},serialize:function(e,t){var a=e,n=u(e.to);if(n){for(var l=0,i=n.length;l<i;++l){var p=String(n[l]),r=p.lastIndexOf("@"),o=p.slice(0,r).replace(Ee,Oe).replace(Ee,s).replace(we,U),g=p.slice(r+1);try{g=t.iri?F.toUnicode(g):F.toASCII(pe(g,t).toLowerCase())}catch(e){a.error=a.error||"Email address's domain name can not be converted to "+(t.iri?"Unicode":"ASCII")+" via punycode: "+e}n[l]=o+"@"+g}a.path=n.join(",")}var c=e.headers=e.headers||{};e.subject&&(c.subject=e.subject),e.body&&(c.body=e.body);var f=[];for(var y in c)c[y]!==ye[y]&&f.push(y.replace(Ee,Oe).replace(Ee,s).replace(Se,U)+"="+c[y].replace(Ee,Oe).replace(Ee,s).replace(Ce,U));return f.length&&(a.query=f.join("&")),a}},Ne=/^([^\:]+)\:(.*)/,Ae={scheme:"urn",parse:function(e,t){var a=e.path&&e.path.match(Ne),n=e;if(a){var s=t.scheme||n.scheme||"urn",u=a[1].toLowerCase(),l=a[2],i=s+":"+(t.nid||u),p=j[i];n.nid=u,n.nss=l,n.path=void 0,p&&(n=p.parse(n,t))}else n.error=n.error||"URN can not be parsed.";return n},serialize:function(e,t){var a=t.scheme||e.scheme||"urn",n=e.nid,s=a+":"+(t.nid||n),u=j[s];u&&(e=u.serialize(e,t));var l=e,i=e.nss;return l.path=(n||t.nid)+":"+i,l}},ke=/^[0-9A-Fa-f]{8}(?:\-[0-9A-Fa-f]{4}){3}\-[0-9A-Fa-f]{12}$/,Te={scheme:"urn:uuid",parse:function(e,t){var a=e;return a.uuid=a.nss,a.nss=void 0,t.tolerant||a.uuid&&a.uuid.match(ke)||(a.error=a.error||"UUID is not valid."),a},serialize:function(e,t){var a=e;return a.nss=(e.uuid||"").toLowerCase(),a}};j[re.scheme]=re,j[oe.scheme]=oe,j[ce.scheme]=ce,j[fe.scheme]=fe,j[Ie.scheme]=Ie,j[Ae.scheme]=Ae,j[Te.scheme]=Te,e.SCHEMES=j,e.pctEncChar=U,e.pctDecChars=z,e.parse=G,e.removeDotSegments=te,e.serialize=ae,e.resolveComponents=ne,e.resolve=se,e.normalize=ue,e.equal=le,e.escapeComponent=ie,e.unescapeComponent=pe,Object.defineProperty(e,"__esModule",{value:!0})}(Le.exports);var We=Le.exports;Object.defineProperty($e,"__esModule",{value:!0});const Fe=We;Fe.code='require("ajv/dist/runtime/uri").default',$e.default=Fe,function(e){Object.defineProperty(e,"__esModule",{value:!0}),e.CodeGen=e.Name=e.nil=e.stringify=e.str=e._=e.KeywordCxt=void 0;var t=fe();Object.defineProperty(e,"KeywordCxt",{enumerable:!0,get:function(){return t.KeywordCxt}});var a=x();Object.defineProperty(e,"_",{enumerable:!0,get:function(){return a._}}),Object.defineProperty(e,"str",{enumerable:!0,get:function(){return a.str}}),Object.defineProperty(e,"stringify",{enumerable:!0,get:function(){return a.stringify}}),Object.defineProperty(e,"nil",{enumerable:!0,get:function(){return a.nil}}),Object.defineProperty(e,"Name",{enumerable:!0,get:function(){return a.Name}}),Object.defineProperty(e,"CodeGen",{enumerable:!0,get:function(){return a.CodeGen}});const n=be(),s=me(),u=O,l=_e,i=x(),p=X,r=C,o=h,g=Me,c=$e,f=(e,t)=>new RegExp(e,t);f.code="new RegExp";const y=["removeAdditional","useDefaults","coerceTypes"],d=new Set(["validate","serialize","parse","wrapper","root","schema","keyword","pattern","formats","validate$data","func","obj","Error"]),b={errorDataPath:"",format:"`validateFormats: false` can be used instead.",nullable:'"nullable" keyword is supported by default.',jsonPointers:"Deprecated jsPropertySyntax can be used instead.",extendRefs:"Deprecated ignoreKeywordsWithRef can be used instead.",missingRefs:"Pass empty schema with $id that should be ignored to ajv.addSchema.",processCode:"Use option `code: {process: (code, schemaEnv: object) => string}`",sourceCode:"Use option `code: {source: true}`",strictDefaults:"It is default now, see option `strict`.",strictKeywords:"It is default now, see option `strict`.",uniqueItems:'"uniqueItems" keyword is always validated.',unknownFormats:"Disable strict mode or pass `true` to `ajv.addFormat` (or `formats` option).",cache:"Map is used as cache, schema object as key.",serialize:"Map is used as cache, schema object as key.",ajvErrors:"It is default now."},m={ignoreKeywordsWithRef:"",jsPropertySyntax:"",unicode:'"minLength"/"maxLength" account for unicode characters by default.'};function _(e){var t,a,n,s,u,l,i,p,r,o,g,y,d,b,x,h,m,_,v,E,w,S,C,O,I;const N=e.strict,A=null===(t=e.code)||void 0===t?void 0:t.optimize,k=!0===A||void 0===A?1:A||0,T=null!==(n=null===(a=e.code)||void 0===a?void 0:a.regExp)&&void 0!==n?n:f,D=null!==(s=e.uriResolver)&&void 0!==s?s:c.default;return{strictSchema:null===(l=null!==(u=e.strictSchema)&&void 0!==u?u:N)||void 0===l||l,strictNumbers:null===(p=null!==(i=e.strictNumbers)&&void 0!==i?i:N)||void 0===p||p,strictTypes:null!==(o=null!==(r=e.strictTypes)&&void 0!==r?r:N)&&void 0!==o?o:"log",strictTuples:null!==(y=null!==(g=e.strictTuples)&&void 0!==g?g:N)&&void 0!==y?y:"log",strictRequired:null!==(b=null!==(d=e.strictRequired)&&void 0!==d?d:N)&&void 0!==b&&b,code:e.code?{...e.code,optimize:k,regExp:T}:{optimize:k,regExp:T},loopRequired:null!==(x=e.loopRequired)&&void 0!==x?x:200,loopEnum:null!==(h=e.loopEnum)&&void 0!==h?h:200,meta:null===(m=e.meta)||void 0===m||m,messages:null===(_=e.messages)||void 0===_||_,inlineRefs:null===(v=e.inlineRefs)||void 0===v||v,schemaId:null!==(E=e.schemaId)&&void 0!==E?E:"$id",addUsedSchema:null===(w=e.addUsedSchema)||void 0===w||w,validateSchema:null===(S=e.validateSchema)||void 0===S||S,validateFormats:null===(C=e.validateFormats)||void 0===C||C,unicodeRegExp:null===(O=e.unicodeRegExp)||void 0===O||O,int32range:null===(I=e.int32range)||void 0===I||I,uriResolver:D}}class v{constructor(e={}){this.schemas={},this.refs={},this.formats={},this._compilations=new Set,this._loading={},this._cache=new Map,e=this.opts={...e,..._(e)};const{es5:t,lines:a}=this.opts.code;this.scope=new i.ValueScope({scope:{},prefixes:d,es5:t,lines:a}),this.logger=function(e){if(!1===e)return k;if(void 0===e)return console;if(e.log&&e.warn&&e.error)return e;throw new Error("logger must implement log, warn and error methods")}(e.logger);const n=e.validateFormats;e.validateFormats=!1,this.RULES=(0,u.getRules)(),E.call(this,b,e,"NOT SUPPORTED"),E.call(this,m,e,"DEPRECATED","warn"),this._metaOpts=A.call(this),e.formats&&I.call(this),this._addVocabularies(),this._addDefaultMetaSchema(),e.keywords&&N.call(this,e.keywords),"object"==typeof e.meta&&this.addMetaSchema(e.meta),S.call(this),e.validateFormats=n}_addVocabularies(){this.addKeyword("$async")}_addDefaultMetaSchema(){const{$data:e,meta:t,schemaId:a}=this.opts;let n=g;"id"===a&&(n={...g},n.id=n.$id,delete n.$id),t&&e&&this.addMetaSchema(n,n[a],!1)}defaultMeta(){const{meta:e,schemaId:t}=this.opts;return this.opts.defaultMeta="object"==typeof e?e[t]||e:void 0}validate(e,t){let a;if("string"==typeof e){if(a=this.getSchema(e),!a)throw new Error(`no schema with key or ref "${e}"`)}else a=this.compile(e);const n=a(t);return"$async"in a||(this.errors=a.errors),n}compile(e,t){const a=this._addSchema(e,t);return a.validate||this._compileSchemaEnv(a)}compileAsync(e,t){if("function"!=typeof this.opts.loadSchema)throw new Error("options.loadSchema should be a function");const{loadSchema:a}=this.opts;return n.call(this,e,t);async function n(e,t){await u.call(this,e.$schema);const a=this._addSchema(e,t);return a.validate||l.call(this,a)}async function u(e){e&&!this.getSchema(e)&&await n.call(this,{$ref:e},!0)}async function l(e){try{return this._compileSchemaEnv(e)}catch(t){if(!(t instanceof s.default))throw t;return i.call(this,t),await p.call(this,t.missingSchema),l.call(this,e)}}function i({missingSchema:e,missingRef:t}){if(this.refs[e])throw new Error(`AnySchema ${e} is loaded but ${t} cannot be resolved`)}async function p(e){const a=await r.call(this,e);this.refs[e]||await u.call(this,a.$schema),this.refs[e]||this.addSchema(a,e,t)}async function r(e){const t=this._loading[e];if(t)return t;try{return await(this._loading[e]=a(e))}finally{delete this._loading[e]}}}addSchema(e,t,a,n=this.opts.validateSchema){if(Array.isArray(e)){for(const t of e)this.addSchema(t,void 0,a,n);return this}let s;if("object"==typeof e){const{schemaId:t}=this.opts;if(s=e[t],void 0!==s&&"string"!=typeof s)throw new Error(`schema ${t} must be string`)}return t=(0,p.normalizeId)(t||s),this._checkUnique(t),this.schemas[t]=this._addSchema(e,a,t,n,!0),this}addMetaSchema(e,t,a=this.opts.validateSchema){return this.addSchema(e,t,!0,a),this}validateSchema(e,t){if("boolean"==typeof e)return!0;let a;if(a=e.$schema,void 0!==a&&"string"!=typeof a)throw new Error("$schema must be a string");if(a=a||this.opts.defaultMeta||this.defaultMeta(),!a)return this.logger.warn("meta-schema not available"),this.errors=null,!0;const n=this.validate(a,e);if(!n&&t){const e="schema is invalid: "+this.errorsText();if("log"!==this.opts.validateSchema)throw new Error(e);this.logger.error(e)}return n}getSchema(e){let t;for(;"string"==typeof(t=w.call(this,e));)e=t;if(void 0===t){const{schemaId:a}=this.opts,n=new l.SchemaEnv({schema:{},schemaId:a});if(t=l.resolveSchema.call(this,n,e),!t)return;this.refs[e]=t}return t.validate||this._compileSchemaEnv(t)}removeSchema(e){if(e instanceof RegExp)return this._removeAllSchemas(this.schemas,e),this._removeAllSchemas(this.refs,e),this;switch(typeof e){case"undefined":return this._removeAllSchemas(this.schemas),this._removeAllSchemas(this.refs),this._cache.clear(),this;case"string":{const t=w.call(this,e);return"object"==typeof t&&this._cache.delete(t.schema),delete this.schemas[e],delete this.refs[e],this}case"object":{const t=e;this._cache.delete(t);let a=e[this.opts.schemaId];return a&&(a=(0,p.normalizeId)(a),delete this.schemas[a],delete this.refs[a]),this}default:throw new Error("ajv.removeSchema: invalid parameter")}}addVocabulary(e){for(const t of e)this.addKeyword(t);return this}addKeyword(e,t){let a;if("string"==typeof e)a=e,"object"==typeof t&&(this.logger.warn("these parameters are deprecated, see docs for addKeyword"),t.keyword=a);else{if("object"!=typeof e||void 0!==t)throw new Error("invalid addKeywords parameters");if(a=(t=e).keyword,Array.isArray(a)&&!a.length)throw new Error("addKeywords: keyword must be string or non-empty array")}if(D.call(this,a,t),!t)return(0,o.eachItem)(a,(e=>R.call(this,e))),this;M.call(this,t);const n={...t,type:(0,r.getJSONTypes)(t.type),schemaType:(0,r.getJSONTypes)(t.schemaType)};return(0,o.eachItem)(a,0===n.type.length?e=>R.call(this,e,n):e=>n.type.forEach((t=>R.call(this,e,n,t)))),this}getKeyword(e){const t=this.RULES.all[e];return"object"==typeof t?t.definition:!!t}removeKeyword(e){const{RULES:t}=this;delete t.keywords[e],delete t.all[e];for(const a of t.rules){const t=a.rules.findIndex((t=>t.keyword===e));t>=0&&a.rules.splice(t,1)}return this}addFormat(e,t){return"string"==typeof t&&(t=new RegExp(t)),this.formats[e]=t,this}errorsText(e=this.errors,{separator:t=", ",dataVar:a="data"}={}){return e&&0!==e.length?e.map((e=>`${a}${e.instancePath} ${e.message}`)).reduce(((e,a)=>e+t+a)):"No errors"}$dataMetaSchema(e,t){const a=this.RULES.all;e=JSON.parse(JSON.stringify(e));for(const n of t){const t=n.split("/").slice(1);let s=e;for(const e of t)s=s[e];for(const e in a){const t=a[e];if("object"!=typeof t)continue;const{$data:n}=t.definition,u=s[e];n&&u&&(s[e]=L(u))}}return e}_removeAllSchemas(e,t){for(const a in e){const n=e[a];t&&!t.test(a)||("string"==typeof n?delete e[a]:n&&!n.meta&&(this._cache.delete(n.schema),delete e[a]))}}_addSchema(e,t,a,n=this.opts.validateSchema,s=this.opts.addUsedSchema){let u;const{schemaId:i}=this.opts;if("object"==typeof e)u=e[i];else{if(this.opts.jtd)throw new Error("schema must be object");if("boolean"!=typeof e)throw new Error("schema must be object or boolean")}let r=this._cache.get(e);if(void 0!==r)return r;a=(0,p.normalizeId)(u||a);const o=p.getSchemaRefs.call(this,e,a);return r=new l.SchemaEnv({schema:e,schemaId:i,meta:t,baseId:a,localRefs:o}),this._cache.set(r.schema,r),s&&!a.startsWith("#")&&(a&&this._checkUnique(a),this.refs[a]=r),n&&this.validateSchema(e,!0),r}_checkUnique(e){if(this.schemas[e]||this.refs[e])throw new Error(`schema with key or id "${e}" already exists`)}_compileSchemaEnv(e){if(e.meta?this._compileMetaSchema(e):l.compileSchema.call(this,e),!e.validate)throw new Error("ajv implementation error");return e.validate}_compileMetaSchema(e){const t=this.opts;this.opts=this._metaOpts;try{l.compileSchema.call(this,e)}finally{this.opts=t}}}function E(e,t,a,n="error"){for(const s in e){const u=s;u in t&&this.logger[n](`${a}: option ${s}. ${e[u]}`)}}function w(e){return e=(0,p.normalizeId)(e),this.schemas[e]||this.refs[e]}function S(){const e=this.opts.schemas;if(e)if(Array.isArray(e))this.addSchema(e);else for(const t in e)this.addSchema(e[t],t)}function I(){for(const e in this.opts.formats){const t=this.opts.formats[e];t&&this.addFormat(e,t)}}function N(e){if(Array.isArray(e))this.addVocabulary(e);else{this.logger.warn("keywords option as map is deprecated, pass array");for(const t in e){const a=e[t];a.keyword||(a.keyword=t),this.addKeyword(a)}}}function A(){const e={...this.opts};for(const t of y)delete e[t];return e}e.default=v,v.ValidationError=n.default,v.MissingRefError=s.default;const k={log(){},warn(){},error(){}};const T=/^[a-z_$][a-z0-9_$:-]*$/i;function D(e,t){const{RULES:a}=this;if((0,o.eachItem)(e,(e=>{if(a.keywords[e])throw new Error(`Keyword ${e} is already defined`);if(!T.test(e))throw new Error(`Keyword ${e} has invalid name`)})),t&&t.$data&&!("code"in t)&&!("validate"in t))throw new Error('$data keyword must have "code" or "validate" function')}function R(e,t,a){var n;const s=null==t?void 0:t.post;if(a&&s)throw new Error('keyword with "post" flag cannot have "type"');const{RULES:u}=this;let l=s?u.post:u.rules.find((({type:e})=>e===a));if(l||(l={type:a,rules:[]},u.rules.push(l)),u.keywords[e]=!0,!t)return;const i={keyword:e,definition:{...t,type:(0,r.getJSONTypes)(t.type),schemaType:(0,r.getJSONTypes)(t.schemaType)}};t.before?P.call(this,l,i,t.before):l.rules.push(i),u.all[e]=i,null===(n=t.implements)||void 0===n||n.forEach((e=>this.addKeyword(e)))}function P(e,t,a){const n=e.rules.findIndex((e=>e.keyword===a));n>=0?e.rules.splice(n,0,t):(e.rules.push(t),this.logger.warn(`rule ${a} is not defined`))}function M(e){let{metaSchema:t}=e;void 0!==t&&(e.$data&&this.opts.$data&&(t=L(t)),e.validateSchema=this.compile(t,!0))}const $={$ref:"https://raw.githubusercontent.com/ajv-validator/ajv/master/lib/refs/data.json#"};function L(e){return{anyOf:[e,$]}}}(l);var je={},Ue={},ze={};Object.defineProperty(ze,"__esModule",{value:!0});const Ve={keyword:"id",code(){throw new Error('NOT SUPPORTED: keyword "id", use "$id" for schema ID')}};ze.default=Ve;var He={};Object.defineProperty(He,"__esModule",{value:!0}),He.callRef=He.getValidate=void 0;const qe=me(),Ye=P,Be=x(),Xe=m,Ge=_e,Ke=h,Ze={keyword:"$ref",schemaType:"string",code(e){const{gen:t,schema:a,it:n}=e,{baseId:s,schemaEnv:u,validateName:l,opts:i,self:p}=n,{root:r}=u;if(("#"===a||"#/"===a)&&s===r.baseId)return function(){if(u===r)return Qe(e,l,u,u.$async);const a=t.scopeValue("root",{ref:r});return Qe(e,Be._`${a}.validate`,r,r.$async)}();const o=Ge.resolveRef.call(p,r,s,a);if(void 0===o)throw new qe.default(n.opts.uriResolver,s,a);return o instanceof Ge.SchemaEnv?function(t){const a=Je(e,t);Qe(e,a,t,t.$async)}(o):function(n){const s=t.scopeValue("schema",!0===i.code.source?{ref:n,code:(0,Be.stringify)(n)}:{ref:n}),u=t.name("valid"),l=e.subschema({schema:n,dataTypes:[],schemaPath:Be.nil,topSchemaRef:s,errSchemaPath:a},u);e.mergeEvaluated(l),e.ok(u)}(o)}};function Je(e,t){const{gen:a}=e;return t.validate?a.scopeValue("validate",{ref:t.validate}):Be._`${a.scopeValue("wrapper",{ref:t})}.validate`}function Qe(e,t,a,n){const{gen:s,it:u}=e,{allErrors:l,schemaEnv:i,opts:p}=u,r=p.passContext?Xe.default.this:Be.nil;function o(e){const t=Be._`${e}.errors`;s.assign(Xe.default.vErrors,Be._`${Xe.default.vErrors} === null ? ${t} : ${Xe.default.vErrors}.concat(${t})`),s.assign(Xe.default.errors,Be._`${Xe.default.vErrors}.length`)}function g(e){var t;if(!u.opts.unevaluated)return;const n=null===(t=null==a?void 0:a.validate)||void 0===t?void 0:t.evaluated;if(!0!==u.props)if(n&&!n.dynamicProps)void 0!==n.props&&(u.props=Ke.mergeEvaluated.props(s,n.props,u.props));else{const t=s.var("props",Be._`${e}.evaluated.props`);u.props=Ke.mergeEvaluated.props(s,t,u.props,Be.Name)}if(!0!==u.items)if(n&&!n.dynamicItems)void 0!==n.items&&(u.items=Ke.mergeEvaluated.items(s,n.items,u.items));else{const t=s.var("items",Be._`${e}.evaluated.items`);u.items=Ke.mergeEvaluated.items(s,t,u.items,Be.Name)}}n?function(){if(!i.$async)throw new Error("async schema referenced by sync schema");const a=s.let("valid");s.try((()=>{s.code(Be._`await ${(0,Ye.callValidateCode)(e,t,r)}`),g(t),l||s.assign(a,!0)}),(e=>{s.if(Be._`!(${e} instanceof ${u.ValidationError})`,(()=>s.throw(e))),o(e),l||s.assign(a,!1)})),e.ok(a)}():e.result((0,Ye.callValidateCode)(e,t,r),(()=>g(t)),(()=>o(t)))}He.getValidate=Je,He.callRef=Qe,He.default=Ze,Object.defineProperty(Ue,"__esModule",{value:!0});const et=He,tt=["$schema","$id","$defs","$vocabulary",{keyword:"$comment"},"definitions",ze.default,et.default];Ue.default=tt;var at={},nt={};Object.defineProperty(nt,"__esModule",{value:!0});const st=x(),ut=st.operators,lt={maximum:{okStr:"<=",ok:ut.LTE,fail:ut.GT},minimum:{okStr:">=",ok:ut.GTE,fail:ut.LT},exclusiveMaximum:{okStr:"<",ok:ut.LT,fail:ut.GTE},exclusiveMinimum:{okStr:">",ok:ut.GT,fail:ut.LTE}},it={message:({keyword:e,schemaCode:t})=>st.str`must be ${lt[e].okStr} ${t}`,params:({keyword:e,schemaCode:t})=>st._`{comparison: ${lt[e].okStr}, limit: ${t}}`},pt={keyword:Object.keys(lt),type:"number",schemaType:"number",$data:!0,error:it,code(e){const{keyword:t,data:a,schemaCode:n}=e;e.fail$data(st._`${a} ${lt[t].fail} ${n} || isNaN(${a})`)}};nt.default=pt;var rt={};Object.defineProperty(rt,"__esModule",{value:!0});const ot=x(),gt={keyword:"multipleOf",type:"number",schemaType:"number",$data:!0,error:{message:({schemaCode:e})=>ot.str`must be multiple of ${e}`,params:({schemaCode:e})=>ot._`{multipleOf: ${e}}`},code(e){const{gen:t,data:a,schemaCode:n,it:s}=e,u=s.opts.multipleOfPrecision,l=t.let("res"),i=u?ot._`Math.abs(Math.round(${l}) - ${l}) > 1e-${u}`:ot._`${l} !== parseInt(${l})`;e.fail$data(ot._`(${n} === 0 || (${l} = ${a}/${n}, ${i}))`)}};rt.default=gt;var ct={},ft={};function yt(e){const t=e.length;let a,n=0,s=0;for(;s<t;)n++,a=e.charCodeAt(s++),a>=55296&&a<=56319&&s<t&&(a=e.charCodeAt(s),56320==(64512&a)&&s++);return n}Object.defineProperty(ft,"__esModule",{value:!0}),ft.default=yt,yt.code='require("ajv/dist/runtime/ucs2length").default',Object.defineProperty(ct,"__esModule",{value:!0});const dt=x(),bt=h,xt=ft,ht={message({keyword:e,schemaCode:t}){const a="maxLength"===e?"more":"fewer";return dt.str`must NOT have ${a} than ${t} characters`},params:({schemaCode:e})=>dt._`{limit: ${e}}`},mt={keyword:["maxLength","minLength"],type:"string",schemaType:"number",$data:!0,error:ht,code(e){const{keyword:t,data:a,schemaCode:n,it:s}=e,u="maxLength"===t?dt.operators.GT:dt.operators.LT,l=!1===s.opts.unicode?dt._`${a}.length`:dt._`${(0,bt.useFunc)(e.gen,xt.default)}(${a})`;e.fail$data(dt._`${l} ${u} ${n}`)}};ct.default=mt;var _t={};Object.defineProperty(_t,"__esModule",{value:!0});const vt=P,Et=x(),wt={keyword:"pattern",type:"string",schemaType:"string",$data:!0,error:{message:({schemaCode:e})=>Et.str`must match pattern "${e}"`,params:({schemaCode:e})=>Et._`{pattern: ${e}}`},code(e){const{data:t,$data:a,schema:n,schemaCode:s,it:u}=e,l=u.opts.unicodeRegExp?"u":"",i=a?Et._`(new RegExp(${s}, ${l}))`:(0,vt.usePattern)(e,n);e.fail$data(Et._`!${i}.test(${t})`)}};_t.default=wt;var St={};Object.defineProperty(St,"__esModule",{value:!0});const Ct=x(),Ot={message({keyword:e,schemaCode:t}){const a="maxProperties"===e?"more":"fewer";return Ct.str`must NOT have ${a} than ${t} properties`},params:({schemaCode:e})=>Ct._`{limit: ${e}}`},It={keyword:["maxProperties","minProperties"],type:"object",schemaType:"number",$data:!0,error:Ot,code(e){const{keyword:t,data:a,schemaCode:n}=e,s="maxProperties"===t?Ct.operators.GT:Ct.operators.LT;e.fail$data(Ct._`Object.keys(${a}).length ${s} ${n}`)}};St.default=It;var Nt={};Object.defineProperty(Nt,"__esModule",{value:!0});const At=P,kt=x(),Tt=h,Dt={keyword:"required",type:"object",schemaType:"array",$data:!0,error:{message:({params:{missingProperty:e}})=>kt.str`must have required property '${e}'`,params:({params:{missingProperty:e}})=>kt._`{missingProperty: ${e}}`},code(e){const{gen:t,schema:a,schemaCode:n,data:s,$data:u,it:l}=e,{opts:i}=l;if(!u&&0===a.length)return;const p=a.length>=i.loopRequired;if(l.allErrors?function(){if(p||u)e.block$data(kt.nil,r);else for(const t of a)(0,At.checkReportMissingProp)(e,t)}():function(){const l=t.let("missing");if(p||u){const a=t.let("valid",!0);e.block$data(a,(()=>function(a,u){e.setParams({missingProperty:a}),t.forOf(a,n,(()=>{t.assign(u,(0,At.propertyInData)(t,s,a,i.ownProperties)),t.if((0,kt.not)(u),(()=>{e.error(),t.break()}))}),kt.nil)}(l,a))),e.ok(a)}else t.if((0,At.checkMissingProp)(e,a,l)),(0,At.reportMissingProp)(e,l),t.else()}(),i.strictRequired){const t=e.parentSchema.properties,{definedProperties:n}=e.it;for(const e of a)if(void 0===(null==t?void 0:t[e])&&!n.has(e)){const t=`required property "${e}" is not defined at "${l.schemaEnv.baseId+l.errSchemaPath}" (strictRequired)`;(0,Tt.checkStrictMode)(l,t,l.opts.strictRequired)}}function r(){t.forOf("prop",n,(a=>{e.setParams({missingProperty:a}),t.if((0,At.noPropertyInData)(t,s,a,i.ownProperties),(()=>e.error()))}))}}};Nt.default=Dt;var Rt={};Object.defineProperty(Rt,"__esModule",{value:!0});const Pt=x(),Mt={message({keyword:e,schemaCode:t}){const a="maxItems"===e?"more":"fewer";return Pt.str`must NOT have ${a} than ${t} items`},params:({schemaCode:e})=>Pt._`{limit: ${e}}`},$t={keyword:["maxItems","minItems"],type:"array",schemaType:"number",$data:!0,error:Mt,code(e){const{keyword:t,data:a,schemaCode:n}=e,s="maxItems"===t?Pt.operators.GT:Pt.operators.LT;e.fail$data(Pt._`${a}.length ${s} ${n}`)}};Rt.default=$t;var Lt={},Wt={};Object.defineProperty(Wt,"__esModule",{value:!0});const Ft=G;Ft.code='require("ajv/dist/runtime/equal").default',Wt.default=Ft,Object.defineProperty(Lt,"__esModule",{value:!0});const jt=C,Ut=x(),zt=h,Vt=Wt,Ht={keyword:"uniqueItems",type:"array",schemaType:"boolean",$data:!0,error:{message:({params:{i:e,j:t}})=>Ut.str`must NOT have duplicate items (items ## ${t} and ${e} are identical)`,params:({params:{i:e,j:t}})=>Ut._`{i: ${e}, j: ${t}}`},code(e){const{gen:t,data:a,$data:n,schema:s,parentSchema:u,schemaCode:l,it:i}=e;if(!n&&!s)return;const p=t.let("valid"),r=u.items?(0,jt.getSchemaTypes)(u.items):[];function o(n,s){const u=t.name("item"),l=(0,jt.checkDataTypes)(r,u,i.opts.strictNumbers,jt.DataType.Wrong),o=t.const("indices",Ut._`{}`);t.for(Ut._`;${n}--;`,(()=>{t.let(u,Ut._`${a}[${n}]`),t.if(l,Ut._`continue`),r.length>1&&t.if(Ut._`typeof ${u} == "string"`,Ut._`${u} += "_"`),t.if(Ut._`typeof ${o}[${u}] == "number"`,(()=>{t.assign(s,Ut._`${o}[${u}]`),e.error(),t.assign(p,!1).break()})).code(Ut._`${o}[${u}] = ${n}`)}))}function g(n,s){const u=(0,zt.useFunc)(t,Vt.default),l=t.name("outer");t.label(l).for(Ut._`;${n}--;`,(()=>t.for(Ut._`${s} = ${n}; ${s}--;`,(()=>t.if(Ut._`${u}(${a}[${n}], ${a}[${s}])`,(()=>{e.error(),t.assign(p,!1).break(l)}))))))}e.block$data(p,(function(){const n=t.let("i",Ut._`${a}.length`),s=t.let("j");e.setParams({i:n,j:s}),t.assign(p,!0),t.if(Ut._`${n} > 1`,(()=>(r.length>0&&!r.some((e=>"object"===e||"array"===e))?o:g)(n,s)))}),Ut._`${l} === false`),e.ok(p)}};Lt.default=Ht;var qt={};Object.defineProperty(qt,"__esModule",{value:!0});const Yt=x(),Bt=h,Xt=Wt,Gt={keyword:"const",$data:!0,error:{message:"must be equal to constant",params:({schemaCode:e})=>Yt._`{allowedValue: ${e}}`},code(e){const{gen:t,data:a,$data:n,schemaCode:s,schema:u}=e;n||u&&"object"==typeof u?e.fail$data(Yt._`!${(0,Bt.useFunc)(t,Xt.default)}(${a}, ${s})`):e.fail(Yt._`${u} !== ${a}`)}};qt.default=Gt;var Kt={};Object.defineProperty(Kt,"__esModule",{value:!0});const Zt=x(),Jt=h,Qt=Wt,ea={keyword:"enum",schemaType:"array",$data:!0,error:{message:"must be equal to one of the allowed values",params:({schemaCode:e})=>Zt._`{allowedValues: ${e}}`},code(e){const{gen:t,data:a,$data:n,schema:s,schemaCode:u,it:l}=e;if(!n&&0===s.length)throw new Error("enum must have non-empty array");const i=s.length>=l.opts.loopEnum;let p;const r=()=>null!=p?p:p=(0,Jt.useFunc)(t,Qt.default);let o;if(i||n)o=t.let("valid"),e.block$data(o,(function(){t.assign(o,!1),t.forOf("v",u,(e=>t.if(Zt._`${r()}(${a}, ${e})`,(()=>t.assign(o,!0).break()))))}));else{if(!Array.isArray(s))throw new Error("ajv implementation error");const e=t.const("vSchema",u);o=(0,Zt.or)(...s.map(((t,n)=>function(e,t){const n=s[t];return"object"==typeof n&&null!==n?Zt._`${r()}(${a}, ${e}[${t}])`:Zt._`${a} === ${n}`}(e,n))))}e.pass(o)}};Kt.default=ea,Object.defineProperty(at,"__esModule",{value:!0});const ta=rt,aa=ct,na=_t,sa=St,ua=Nt,la=Rt,ia=Lt,pa=qt,ra=Kt,oa=[nt.default,ta.default,aa.default,na.default,sa.default,ua.default,la.default,ia.default,{keyword:"type",schemaType:["string","array"]},{keyword:"nullable",schemaType:"boolean"},pa.default,ra.default];at.default=oa;var ga={},ca={};Object.defineProperty(ca,"__esModule",{value:!0}),ca.validateAdditionalItems=void 0;const fa=x(),ya=h,da={keyword:"additionalItems",type:"array",schemaType:["boolean","object"],before:"uniqueItems",error:{message:({params:{len:e}})=>fa.str`must NOT have more than ${e} items`,params:({params:{len:e}})=>fa._`{limit: ${e}}`},code(e){const{parentSchema:t,it:a}=e,{items:n}=t;Array.isArray(n)?ba(e,n):(0,ya.checkStrictMode)(a,'"additionalItems" is ignored when "items" is not an array of schemas')}};function ba(e,t){const{gen:a,schema:n,data:s,keyword:u,it:l}=e;l.items=!0;const i=a.const("len",fa._`${s}.length`);if(!1===n)e.setParams({len:t.length}),e.pass(fa._`${i} <= ${t.length}`);else if("object"==typeof n&&!(0,ya.alwaysValidSchema)(l,n)){const n=a.var("valid",fa._`${i} <= ${t.length}`);a.if((0,fa.not)(n),(()=>function(n){a.forRange("i",t.length,i,(t=>{e.subschema({keyword:u,dataProp:t,dataPropType:ya.Type.Num},n),l.allErrors||a.if((0,fa.not)(n),(()=>a.break()))}))}(n))),e.ok(n)}}ca.validateAdditionalItems=ba,ca.default=da;var xa={},ha={};Object.defineProperty(ha,"__esModule",{value:!0}),ha.validateTuple=void 0;const ma=x(),_a=h,va=P,Ea={keyword:"items",type:"array",schemaType:["object","array","boolean"],before:"uniqueItems",code(e){const{schema:t,it:a}=e;if(Array.isArray(t))return wa(e,"additionalItems",t);a.items=!0,(0,_a.alwaysValidSchema)(a,t)||e.ok((0,va.validateArray)(e))}};function wa(e,t,a=e.schema){const{gen:n,parentSchema:s,data:u,keyword:l,it:i}=e;!function(e){const{opts:n,errSchemaPath:s}=i,u=a.length,p=u===e.minItems&&(u===e.maxItems||!1===e[t]);if(n.strictTuples&&!p){const e=`"${l}" is ${u}-tuple, but minItems or maxItems/${t} are not specified or different at path "${s}"`;(0,_a.checkStrictMode)(i,e,n.strictTuples)}}(s),i.opts.unevaluated&&a.length&&!0!==i.items&&(i.items=_a.mergeEvaluated.items(n,a.length,i.items));const p=n.name("valid"),r=n.const("len",ma._`${u}.length`);a.forEach(((t,a)=>{(0,_a.alwaysValidSchema)(i,t)||(n.if(ma._`${r} > ${a}`,(()=>e.subschema({keyword:l,schemaProp:a,dataProp:a},p))),e.ok(p))}))}ha.validateTuple=wa,ha.default=Ea,Object.defineProperty(xa,"__esModule",{value:!0});const Sa=ha,Ca={keyword:"prefixItems",type:"array",schemaType:["array"],before:"uniqueItems",code:e=>(0,Sa.validateTuple)(e,"items")};xa.default=Ca;var Oa={};Object.defineProperty(Oa,"__esModule",{value:!0});const Ia=x(),Na=h,Aa=P,ka=ca,Ta={keyword:"items",type:"array",schemaType:["object","boolean"],before:"uniqueItems",error:{message:({params:{len:e}})=>Ia.str`must NOT have more than ${e} items`,params:({params:{len:e}})=>Ia._`{limit: ${e}}`},code(e){const{schema:t,parentSchema:a,it:n}=e,{prefixItems:s}=a;n.items=!0,(0,Na.alwaysValidSchema)(n,t)||(s?(0,ka.validateAdditionalItems)(e,s):e.ok((0,Aa.validateArray)(e)))}};Oa.default=Ta;var Da={};Object.defineProperty(Da,"__esModule",{value:!0});const Ra=x(),Pa=h,Ma={keyword:"contains",type:"array",schemaType:["object","boolean"],before:"uniqueItems",trackErrors:!0,error:{message:({params:{min:e,max:t}})=>void 0===t?Ra.str`must contain at least ${e} valid item(s)`:Ra.str`must contain at least ${e} and no more than ${t} valid item(s)`,params:({params:{min:e,max:t}})=>void 0===t?Ra._`{minContains: ${e}}`:Ra._`{minContains: ${e}, maxContains: ${t}}`},code(e){const{gen:t,schema:a,parentSchema:n,data:s,it:u}=e;let l,i;const{minContains:p,maxContains:r}=n;u.opts.next?(l=void 0===p?1:p,i=r):l=1;const o=t.const("len",Ra._`${s}.length`);if(e.setParams({min:l,max:i}),void 0===i&&0===l)return void(0,Pa.checkStrictMode)(u,'"minContains" == 0 without "maxContains": "contains" keyword ignored');if(void 0!==i&&l>i)return(0,Pa.checkStrictMode)(u,'"minContains" > "maxContains" is always invalid'),void e.fail();if((0,Pa.alwaysValidSchema)(u,a)){let t=Ra._`${o} >= ${l}`;return void 0!==i&&(t=Ra._`${t} && ${o} <= ${i}`),void e.pass(t)}u.items=!0;const g=t.name("valid");function c(){const e=t.name("_valid"),a=t.let("count",0);f(e,(()=>t.if(e,(()=>function(e){t.code(Ra._`${e}++`),void 0===i?t.if(Ra._`${e} >= ${l}`,(()=>t.assign(g,!0).break())):(t.if(Ra._`${e} > ${i}`,(()=>t.assign(g,!1).break())),1===l?t.assign(g,!0):t.if(Ra._`${e} >= ${l}`,(()=>t.assign(g,!0))))}(a)))))}function f(a,n){t.forRange("i",0,o,(t=>{e.subschema({keyword:"contains",dataProp:t,dataPropType:Pa.Type.Num,compositeRule:!0},a),n()}))}void 0===i&&1===l?f(g,(()=>t.if(g,(()=>t.break())))):0===l?(t.let(g,!0),void 0!==i&&t.if(Ra._`${s}.length > 0`,c)):(t.let(g,!1),c()),e.result(g,(()=>e.reset()))}};Da.default=Ma;var $a={};!function(e){Object.defineProperty(e,"__esModule",{value:!0}),e.validateSchemaDeps=e.validatePropertyDeps=e.error=void 0;const t=x(),a=h,n=P;e.error={message:({params:{property:e,depsCount:a,deps:n}})=>{const s=1===a?"property":"properties";return t.str`must have ${s} ${n} when property ${e} is present`},params:({params:{property:e,depsCount:a,deps:n,missingProperty:s}})=>t._`{property: ${e},
@@ -189,4 +189,4 @@ var id,pd,rd={_:{assert:Jc,DateTimeBuilder:Pf,DateTimeParseContext:Mf,DateTimePr
 /*
  * @copyright (c) 2015-present, Philipp Thürwächter, Pattrick Hüper & js-joda contributors
  * @license BSD-3-Clause (see LICENSE in the root directory of this source tree)
- */,nativeJs:function(e,t){if(void 0===t&&(t=Nf.systemDefault()),Gc(e,"date"),Gc(t,"zone"),e instanceof Date)return Gy.ofEpochMilli(e.getTime()).atZone(t);if("function"==typeof e.toDate&&e.toDate()instanceof Date)return Gy.ofEpochMilli(e.toDate().getTime()).atZone(t);throw new zc("date must be a javascript Date or a moment instance")},ArithmeticException:Uc,DateTimeException:Wc,DateTimeParseException:Fc,IllegalArgumentException:zc,IllegalStateException:Vc,UnsupportedTemporalTypeException:jc,NullPointerException:Hc,Clock:Ky,DayOfWeek:xf,Duration:uf,Instant:Gy,LocalDate:Hy,LocalTime:By,LocalDateTime:Yy,OffsetTime:Wy,OffsetDateTime:Uy,Month:mf,MonthDay:Iy,ParsePosition:Ef,Period:vf,Year:Ty,YearConstants:lf,YearMonth:Ay,ZonedDateTime:jy,ZoneOffset:Rf,ZoneId:Nf,ZoneRegion:fy,ZoneOffsetTransition:ed,ZoneRules:Af,ZoneRulesProvider:cy,ChronoLocalDate:Of,ChronoLocalDateTime:qy,ChronoZonedDateTime:Fy,IsoChronology:Ly,ChronoField:gf,ChronoUnit:pf,IsoFields:Wf,Temporal:Cf,TemporalAccessor:ff,TemporalAdjuster:Dy,TemporalAdjusters:Ry,TemporalAmount:nf,TemporalField:rf,TemporalQueries:cf,TemporalQuery:yf,TemporalUnit:sf,ValueRange:of,DateTimeFormatter:Cy,DateTimeFormatterBuilder:my,DecimalStyle:Jf,ResolverStyle:Sf,SignStyle:Qf,TextStyle:ey},od=(id=rd,pd=[],function(e){return~pd.indexOf(e)||(e(id),pd.push(e)),id});rd.use=od;const gd=(e,t)=>{try{const a=e instanceof jy?e:jy.parse(e),n=t instanceof jy?t:jy.parse(t),s=uf.between(a,n);return s.isZero()?0:s.isNegative()?1:-1}catch(e){return 0}},{gt:cd,valid:fd}=fp;const yd=e=>Array.isArray(e?.document?.tracking?.revision_history)&&e.document.tracking.revision_history.every((e=>"string"==typeof e.number&&"string"==typeof e.date));const{hasTrackingRevisionHistory:dd,hasTrackingVersionField:bd,hasTrackingStatusField:xd}=bp;const{valid:hd,major:md,prerelease:_d}=fp,{hasTrackingVersionField:vd,hasTrackingStatusField:Ed}=bp;const{valid:wd,major:Sd}=fp,{hasTrackingVersionField:Cd,hasTrackingStatusField:Od,hasTrackingRevisionHistory:Id}=bp;const{valid:Nd,prerelease:Ad}=fp,{hasTrackingVersionField:kd,hasTrackingStatusField:Td,hasTrackingRevisionHistory:Dd}=bp;const{valid:Rd,prerelease:Pd}=fp,{hasTrackingVersionField:Md,hasTrackingStatusField:$d}=bp;const Ld=e=>Array.isArray(e?.document?.tracking?.revision_history)&&e.document.tracking.revision_history.every((e=>"string"==typeof e.number&&"string"==typeof e.date)),{hasTrackingVersionField:Wd,hasTrackingStatusField:Fd,hasTrackingRevisionHistory:jd}=bp;const Ud=e=>{if("object"!=typeof e||!e)return!1;const t=e;return Array.isArray(t.vulnerabilities)&&t.vulnerabilities.every((e=>"string"==typeof e.cve||void 0===e.cve))};const zd=e=>{if("object"!=typeof e||!e)return!1;const t=e;return Array.isArray(t.vulnerabilities)&&t.vulnerabilities.every((e=>Array.isArray(e.involvements)&&e.involvements.every((e=>!("string"!=typeof e.date&&void 0!==e.date||"string"!=typeof e.party&&void 0!==e.party||"string"!=typeof e.status&&void 0!==e.status)))))};const Vd=(e,t,a=[])=>{Array.isArray(e.branches)&&e.branches.forEach(((e,n)=>{const s=a.concat([n]);Array.isArray(e.product?.product_identification_helper?.hashes)&&e.product.product_identification_helper.hashes.forEach(((e,a)=>{Hd(e,(({fileHash:e,fileHashIndex:n})=>{t({branchIndexes:s,hashIndex:a,fileHash:e,fileHashIndex:n})}))})),Vd(e,t,s)}))},Hd=(e,t)=>{if(!Array.isArray(e.file_hashes))return;const a=new Set;e.file_hashes.forEach(((e,n)=>{null!=e.algorithm&&(a.has(e.algorithm)&&t({fileHash:e,fileHashIndex:n}),a.add(e.algorithm))}))};const qd=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{remediations:{elements:{additionalProperties:!0,optionalProperties:{group_ids:{elements:{type:"string"}},product_ids:{elements:{type:"string"}}}}}}}}}});const Yd=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,optionalProperties:{revision_history:{elements:{additionalProperties:!0,optionalProperties:{number:{type:"string"}}}},version:{type:"string"}}}}}}});function Bd(e){return parseInt(e).toString()===e?"INTEGER":"SEMANTIC"}const Xd=new Sg,Gd=Xd.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,properties:{branches:{elements:{additionalProperties:!0,properties:{}}}}}}}),Kd=Xd.compile({additionalProperties:!0,optionalProperties:{category:{type:"string"},name:{type:"string"},branches:{elements:{additionalProperties:!0,properties:{}}}}});const Zd=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{flags:{elements:{additionalProperties:!0,optionalProperties:{group_ids:{elements:{type:"string"}},product_ids:{elements:{type:"string"}}}}}}}}}});const Jd=(new Sg).compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{product_groups:{elements:{optionalProperties:{group_id:{type:"string"},product_ids:{elements:{type:"string"}}}}}}},vulnerabilities:{elements:{additionalProperties:!0,properties:{flags:{elements:{additionalProperties:!0,optionalProperties:{group_ids:{elements:{type:"string"}},product_ids:{elements:{type:"string"}}}}}}}}}});var Qd=Object.freeze({__proto__:null,mandatoryTest_6_1_1:function(e){const t=[];let a=!0;const n=xp({document:e}),s=function({document:e}){const t=[],a=e.product_tree?.product_groups;if(a)for(let e=0;e<a.length;++e){const n=a[e].product_ids;if(n)for(let a=0;a<n.length;++a){const s=n[a];s&&t.push({id:s,instancePath:`/product_tree/product_groups/${e}/product_ids/${a}`})}}const n=e.product_tree?.relationships;if(n)for(let e=0;e<n.length;++e){const a=n[e],s=a.product_reference;s&&t.push({id:s,instancePath:`/product_tree/relationships/${e}/product_reference`});const u=a.relates_to_product_reference;u&&t.push({id:u,instancePath:`/product_tree/relationships/${e}/relates_to_product_reference`})}const s=e.vulnerabilities;if(s)for(let e=0;e<s.length;++e){const a=s[e];hp(`/vulnerabilities/${e}/product_status`,a,t),Ep(`/vulnerabilities/${e}/remediations`,a,t),vp(`/vulnerabilities/${e}/scores`,a,t),_p(`/vulnerabilities/${e}/threats`,a,t)}return t}({document:e}),u=wp(n,s);return u.length>0&&(a=!1,u.forEach((e=>{t.push({message:"definition of product id missing",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_10:function(e){const t=[];let a=!0;if(Array.isArray(e.vulnerabilities)){e.vulnerabilities.forEach(((e,n)=>{if(!Array.isArray(e.scores))return;e.scores.forEach(((e,s)=>{if("string"==typeof e.cvss_v2?.vectorString){const u=e.cvss_v2;hc({vectorValues:u.vectorString.split("/"),vectorMapping:xc,cvss:u,onError({attributeKey:e}){a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v2/${e}`,message:"value is not consistent with the vector string"})}})}if("string"==typeof e.cvss_v3?.vectorString&&("3.1"===e.cvss_v3.version||"3.0"===e.cvss_v3.version)){const u=e.cvss_v3;hc({vectorValues:u.vectorString.split("/").slice(1),vectorMapping:bc,cvss:u,onError({attributeKey:e}){a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v3/${e}`,message:"value is not consistent with the vector string"})}})}}))}))}return{errors:t,isValid:a}},mandatoryTest_6_1_11:function(e){const t=[];let a=!0;if(_c(e))for(let n=0;n<e.vulnerabilities.length;++n){const s=e.vulnerabilities[n];if(vc(s)){const e=mc.weaknesses.find((e=>e.id===s.cwe.id));if(!e){a=!1,t.push({instancePath:`/vulnerabilities/${n}/cwe/id`,message:"no weakness with this id is recognized"});continue}if(e.name!==s.cwe.name){a=!1,t.push({instancePath:`/vulnerabilities/${n}/cwe/name`,message:"the name does not match the weakness with the given id"});continue}}}return{isValid:a,errors:t}},mandatoryTest_6_1_12:function(e){const t=[];let a=!0;return Ac(e)&&!Cc(e.document.lang)&&(a=!1,t.push({message:"is not a valid language-tag",instancePath:"/document/lang"})),kc(e)&&(Cc(e.document.source_lang)||(a=!1,t.push({message:"is not a valid language-tag",instancePath:"/document/source_lang"}))),{errors:t,isValid:a}},mandatoryTest_6_1_13:function(e){const t=[];let a=!0;return Array.isArray(e.product_tree?.full_product_names)&&e.product_tree?.full_product_names.forEach(((e,n)=>{$c(e,(()=>{a=!1,t.push({instancePath:`/product_tree/full_product_names/${n}/product_identification_helper/purl`,message:"invalid purl"})}))})),Array.isArray(e.product_tree?.relationships)&&e.product_tree.relationships.forEach(((e,n)=>{$c(e.full_product_name,(()=>{a=!1,t.push({instancePath:`/product_tree/relationships/${n}/full_product_name/product_identification_helper/purl`,message:"invalid purl"})}))})),e.product_tree&&Mc(e.product_tree,(({branchIndexes:e})=>{a=!1;const n=e.reduce(((e,t)=>`${e}/branches/${t}`),"/product_tree");t.push({instancePath:`${n}/product/product_identification_helper/purl`,message:"invalid purl"})})),{errors:t,isValid:a}},mandatoryTest_6_1_14:function(e){const t=[];let a=!0;if(yd(e)){Array.from(new Set(e.document.tracking.revision_history.slice().sort(((e,t)=>gd(e.date,t.date)||dp(t.number,e.number))).map((e=>fd(e.number)??`${e.number}.0.0`))).keys()).filter((e=>null!==fd(e))).every(((e,t,a)=>0===t||cd(e,a[t-1])))||(a=!1,t.push({instancePath:"/document/tracking/revision_history",message:"the items must be in ascending order according to the date"}))}return{errors:t,isValid:a}},mandatoryTest_6_1_15:function(e){let t=!0;const a=[];return"translator"!==e.document?.publisher?.category||e.document.source_lang||(t=!1,a.push({instancePath:"/document/publisher/category",message:"source language attribute is missing"})),{isValid:t,errors:a}},mandatoryTest_6_1_16:function(e){let t=!0;const a=[];if(dd(e)&&bd(e)&&xd(e)&&e.document.tracking.revision_history.length>0){const n=t=>"draft"==e.document.tracking.status?t.split(/[+-]/)[0]:t.split("+")[0];n(e.document.tracking.revision_history.slice().sort(((e,t)=>gd(t.date,e.date)||dp(e.number,t.number)))[0].number)!==n(e.document.tracking.version)&&(t=!1,a.push({message:"version does not match latest revision",instancePath:"/document/tracking/version"}))}return{errors:a,isValid:t}},mandatoryTest_6_1_17:function(e){let t=!0;const a=[];return vd(e)&&Ed(e)&&"draft"!==e.document.tracking.status&&("0"===e.document.tracking.version||hd(e.document.tracking.version)&&(0===md(e.document.tracking.version)||_d(e.document.tracking.version)))&&(t=!1,a.push({message:"the status is not compatible with the version",instancePath:"/document/tracking/status"})),{errors:a,isValid:t}},mandatoryTest_6_1_18:function(e){let t=!0;const a=[];return Cd(e)&&Od(e)&&Id(e)&&("final"===e.document.tracking.status||"interim"===e.document.tracking.status)&&e.document.tracking.revision_history.some((e=>"0"===e.number||wd(e.number)&&0===Sd(e.number)))&&(t=!1,a.push({message:"some revision-history entries are not compatible with the status",instancePath:"/document/tracking/status"})),{errors:a,isValid:t}},mandatoryTest_6_1_19:function(e){let t=!0;const a=[];if(kd(e)&&Td(e)&&Dd(e))for(let n=0;n<e.document.tracking.revision_history.length;++n){const s=e.document.tracking.revision_history[n];Nd(s.number)&&Ad(s.number)&&(t=!1,a.push({message:"contains prerelease part",instancePath:`/document/tracking/revision_history/${n}/number`}))}return{errors:a,isValid:t}},mandatoryTest_6_1_2:function(e){const t=[];let a=!0;const n=function({document:e}){const t=[],a=e.product_tree?.full_product_names;if(a)for(let e=0;e<a.length;++e){const n=a[e];n.product_id&&t.push({id:n.product_id,name:n.name??"",instancePath:`/product_tree/full_product_names/${e}/product_id`})}const n=e.product_tree?.relationships;if(n)for(let e=0;e<n.length;++e){const a=n[e].full_product_name;a&&a.product_id&&t.push({id:a.product_id,name:a.name??"",instancePath:`/product_tree/relationships/${e}/full_product_name/product_id`})}const s=e.product_tree?.branches;s&&Cp(s,t,"/product_tree/branches");return t}({document:e}),s=Sp(n);return s.length>0&&(a=!1,s.forEach((e=>{t.push({message:"duplicate definition product id",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_20:function(e){let t=!0;const a=[];return Md(e)&&$d(e)&&("final"===e.document.tracking.status||"interim"===e.document.tracking.status)&&Rd(e.document.tracking.version)&&Pd(e.document.tracking.version)&&(t=!1,a.push({message:"pre-release part is not allowed for status",instancePath:"/document/tracking/version"})),{errors:a,isValid:t}},mandatoryTest_6_1_21:function(e){const t=[];let a=!0;if(Ld(e)){const n=Array.from(new Set(e.document.tracking.revision_history.slice().sort(((e,t)=>gd(e.date,t.date))).map((e=>parseInt(e.number)))).keys());n.length>0&&![0,1].includes(n[0])&&(a=!1,t.push({instancePath:"/document/tracking/revision_history",message:"revision history does not start with a version of 0 or 1 when sorted by date"}));for(let e=0;e<n.length;++e){const s=e+Number(n[0]);n[e]>s&&(a=!1,t.push({instancePath:"/document/tracking/revision_history",message:`major version ${s} was omitted`}))}}return{errors:t,isValid:a}},mandatoryTest_6_1_22:function(e){let t=!0;const a=[];if(Wd(e)&&Fd(e)&&jd(e)){let n={};e.document.tracking.revision_history.forEach(((e,s)=>{n[e.number]=n[e.number]??[],n[e.number].push(s),n[e.number].length>1&&(t=!1,a.push({message:"version was already used",instancePath:`/document/tracking/revision_history/${s}/number`}))}))}return{errors:a,isValid:t}},mandatoryTest_6_1_23:function(e){const t=[];let a=!0;if(Ud(e)){const n=new Set;e.vulnerabilities.forEach(((e,s)=>{void 0!==e.cve&&(n.has(e.cve)&&(a=!1,t.push({message:"CVE identifier was already used",instancePath:`/vulnerabilities/${s}/cve`})),n.add(e.cve))}))}return{errors:t,isValid:a}},mandatoryTest_6_1_24:function(e){const t=[];let a=!0;return zd(e)&&e.vulnerabilities.forEach(((e,n)=>{const s=new Map;e.involvements.forEach(((e,u)=>{if("string"==typeof e.date&&"string"==typeof e.party){const l=s.get(e.date)??new Set;l.has(`${e.party}`)&&(a=!1,t.push({message:"status for party was already given for the same date",instancePath:`/vulnerabilities/${n}/involvements/${u}`})),l.add(`${e.party}`),s.set(e.date,l)}}))})),{errors:t,isValid:a}},mandatoryTest_6_1_25:function(e){const t=[];let a=!0;return Array.isArray(e.product_tree?.full_product_names)&&e.product_tree?.full_product_names.forEach(((e,n)=>{Array.isArray(e.product_identification_helper?.hashes)&&e.product_identification_helper.hashes.forEach(((e,s)=>{Hd(e,(({fileHash:e,fileHashIndex:u})=>{a=!1,t.push({instancePath:`/product_tree/full_product_names/${n}/product_identification_helper/hashes/${s}/file_hashes/${u}`,message:`there is already a hash with the algorithm ${e.algorithm}`})}))}))})),Array.isArray(e.product_tree?.relationships)&&e.product_tree.relationships.forEach(((e,n)=>{Array.isArray(e.full_product_name?.product_identification_helper?.hashes)&&e.full_product_name.product_identification_helper.hashes.forEach(((e,s)=>{Hd(e,(({fileHash:e,fileHashIndex:u})=>{a=!1,t.push({instancePath:`/product_tree/relationships/${n}/full_product_name/product_identification_helper/hashes/${s}/file_hashes/${u}`,message:`there is already a hash with the algorithm ${e.algorithm}`})}))}))})),e.product_tree&&Vd(e.product_tree,(({branchIndexes:e,hashIndex:n,fileHashIndex:s,fileHash:u})=>{a=!1;const l=e.reduce(((e,t)=>`${e}/branches/${t}`),"/product_tree");t.push({instancePath:`${l}/product/product_identification_helper/hashes/${n}/file_hashes/${s}`,message:`there is already a hash with the algorithm ${u.algorithm}`})})),{errors:t,isValid:a}},mandatoryTest_6_1_26:function(e){const t=[];let a=!0;if("string"==typeof e.document?.category){const n=e.document.category,s=["securityincidentresponse","informationaladvisory","securityadvisory","vex"];if(["csaf_base","csaf_security_incident_response","csaf_informational_advisory","csaf_security_advisory","csaf_vex"].includes(n))return{errors:t,isValid:a};if(n.toLowerCase().startsWith("csaf_"))return a=!1,t.push({instancePath:"/document/category",message:"reserved prefix used"}),{errors:t,isValid:a};s.includes(n.replace(/[_-\s]+/g,"").toLowerCase())&&(a=!1,t.push({instancePath:"/document/category",message:"value prohibited"}))}return{errors:t,isValid:a}},mandatoryTest_6_1_27_1:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_incident_response","csaf_informational_advisory"]).has(e.document?.category))return{errors:t,isValid:a};const n=new Set(["description","details","general","summary"]);return a=Array.isArray(e.document?.notes)&&e.document.notes.some((e=>n.has(e.category))),a||t.push({instancePath:"/document/notes",message:"need at least one document note with a category of description, details, general or summary"}),{errors:t,isValid:a}},mandatoryTest_6_1_27_10:function(e){const t=[];let a=!0;const n=e.vulnerabilities;return"csaf_vex"===e.document?.category&&Array.isArray(n)?(n.forEach(((n,s)=>{const u=n?.product_status;n&&u&&Array.isArray(u.known_affected)&&u.known_affected.forEach(((u,l)=>{(Array.isArray(n.remediations)?n.remediations:[]).some((t=>{if(!t)return!1;if(Array.isArray(t.product_ids)&&t.product_ids.includes(u))return!0;const a=e.product_tree?.product_groups;return!!(Array.isArray(t.group_ids)&&Array.isArray(a)&&t.group_ids.some((e=>{const t=a.find((t=>t.group_id===e));return t&&Array.isArray(t.product_ids)&&t.product_ids.includes(u)})))}))||(a=!1,t.push({instancePath:`/vulnerabilities/${s}/product_status/known_affected/${l}`,message:"no suitable action statement found"}))}))})),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_11:function(e){const t=[];let a=!0;return new Set(["csaf_security_advisory","csaf_vex"]).has(e.document?.category)?(a=Boolean(e.vulnerabilities),a||t.push({instancePath:"/",message:"needs vulnerabilities"}),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_2:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_incident_response","csaf_informational_advisory"]).has(e.document?.category))return{errors:t,isValid:a};const n=new Set(["external"]);return a=Array.isArray(e.document?.references)&&e.document.references.some((e=>n.has(e.category))),a||t.push({instancePath:"/document/references",message:'need at least one document reference with the category "external"'}),{errors:t,isValid:a}},mandatoryTest_6_1_27_3:function(e){const t=[];let a=!0;return new Set(["csaf_informational_advisory"]).has(e.document?.category)?(a=void 0===e.vulnerabilities,a||t.push({instancePath:"/vulnerabilities",message:"must not exist"}),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_4:function(e){const t=[];let a=!0;return new Set(["csaf_security_advisory","csaf_vex"]).has(e.document?.category)?(a=Boolean(e.product_tree),a||t.push({instancePath:"/",message:"needs a product_tree"}),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_5:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_advisory","csaf_vex"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{e.notes||(a=!1,t.push({instancePath:`/vulnerabilities/${n}`,message:"needs a `notes` attribute"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_6:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_advisory"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{e.product_status||(a=!1,t.push({instancePath:`/vulnerabilities/${n}`,message:"needs a `product_status` attribute"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_7:function(e){const t=[];let a=!0;if(!new Set(["csaf_vex"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{if(!e.product_status)return a=!1,void t.push({instancePath:`/vulnerabilities/${n}`,message:"needs a `product_status` attribute"});["fixed","known_affected","known_not_affected","under_investigation"].some((t=>Array.isArray(e.product_status[t])))||(a=!1,t.push({instancePath:`/vulnerabilities/${n}/product_status`,message:"needs at least one the following attributes: `fixed`, `known_affected`, `known_not_affected`, `under_investigation`"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_8:function(e){const t=[];let a=!0;if(!new Set(["csaf_vex"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{["ids","cve"].every((t=>void 0===e[t]))&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}`,message:"needs at least one the following attributes: `ids`, `cve`"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_9:function(e){const t=[];let a=!0;const n=e.vulnerabilities;return"csaf_vex"===e.document?.category&&Array.isArray(n)?(n.forEach(((n,s)=>{const u=n?.product_status;n&&u&&Array.isArray(u.known_not_affected)&&u.known_not_affected.forEach(((u,l)=>{const i=(Array.isArray(n.threats)?n.threats:[]).some((t=>{if(!t||"impact"!==t.category)return!1;if(Array.isArray(t.product_ids)&&t.product_ids.includes(u))return!0;const a=e.product_tree?.product_groups;return!!(Array.isArray(t.group_ids)&&Array.isArray(a)&&t.group_ids.some((e=>{const t=a.find((t=>t.group_id===e));return t&&Array.isArray(t.product_ids)&&t.product_ids.includes(u)})))})),p=(Array.isArray(n.flags)?n.flags:[]).some((t=>{if(!t)return!1;if(Array.isArray(t.product_ids)&&t.product_ids.includes(u))return!0;const a=e.product_tree?.product_groups;return!!(Array.isArray(t.group_ids)&&Array.isArray(a)&&t.group_ids.some((e=>{const t=a.find((t=>t.group_id===e));return t&&Array.isArray(t.product_ids)&&t.product_ids.includes(u)})))}));i||p||(a=!1,t.push({instancePath:`/vulnerabilities/${s}/product_status/known_not_affected/${l}`,message:"no suitable impact statement found"}))}))})),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_28:function(e){const t=[];let a=!0;return"string"==typeof e.document?.source_lang&&"string"==typeof e.document?.lang&&e.document.source_lang===e.document.lang&&(a=!1,t.push({instancePath:"/document/lang",message:"is the same as `/document/source_lang`"},{instancePath:"/document/source_lang",message:"is the same as `/document/lang`"})),{isValid:a,errors:t}},mandatoryTest_6_1_29:function(e){const t={errors:[],isValid:!0};return qd(e)?(e.vulnerabilities.forEach(((e,a)=>{e.remediations.forEach(((e,n)=>{e.group_ids||e.product_ids||(t.isValid=!1,t.errors.push({instancePath:`/vulnerabilities/${a}/remediations/${n}`,message:"remediation without product reference"}))}))})),t):t},mandatoryTest_6_1_3:function(e){const t=[];let a=!0;if(!Array.isArray(e.product_tree?.relationships))return{isValid:a,errors:t};const n=e.product_tree.relationships;return n.forEach(((e,s)=>{const u=new Set;Op([],n,s,null,(({key:e})=>{"string"!=typeof e||u.has(e)||(u.add(e),a=!1,t.push({instancePath:`/product_tree/relationships/${s}/${e}`,message:"circular reference"}))}))})),{isValid:a,errors:t}},mandatoryTest_6_1_30:function(e){const t={errors:[],isValid:!0};if(!Yd(e))return t;let a=null;return e.document.tracking.revision_history?.forEach(((e,n)=>{if("string"==typeof e.number){const s=Bd(e.number);null===a&&(a=s),a!==s&&(t.isValid=!1,t.errors.push({instancePath:`/document/tracking/revisions/${n}/number`,message:"mixed integer and semantic versioning"}))}})),null!=a&&"string"==typeof e.document.tracking.version&&a!==Bd(e.document.tracking.version)&&(t.isValid=!1,t.errors.push({instancePath:"/document/tracking/version",message:"mixed integer and semantic versioning"})),t},mandatoryTest_6_1_31:function(e){const t={errors:[],isValid:!0};return Gd(e)?(function e({path:a,branches:n}){n.forEach(((n,s)=>{Kd(n)&&("product_version"===n.category&&"string"==typeof n.name&&(["<","<=",">",">="].some((e=>n.name?.toLowerCase().includes(e)))||["after","all","before","earlier","later","prior","versions"].some((e=>n.name?.toLowerCase().split(/\s/).some((t=>e===t)))))&&(t.isValid=!1,t.errors.push({instancePath:`${a}/${s}/name`,message:"version range in product version"})),Array.isArray(n.branches)&&e({path:`${a}/${s}/branches`,branches:n.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree.branches}),t):t},mandatoryTest_6_1_32:function(e){const t={errors:[],isValid:!0};return Zd(e)?(e.vulnerabilities.forEach(((e,a)=>{e.flags.forEach(((e,n)=>{e.group_ids||e.product_ids||(t.isValid=!1,t.errors.push({instancePath:`/vulnerabilities/${a}/flags/${n}`,message:"flag without product reference"}))}))})),t):t},mandatoryTest_6_1_33:function(e){const t={errors:[],isValid:!0};return Jd(e)?(e.vulnerabilities.forEach(((a,n)=>{const s=new Set;a.flags.forEach(((a,u)=>{let l=!1;function i(){l||(t.isValid=!1,t.errors.push({instancePath:`/vulnerabilities/${n}/flags/${u}`,message:"multiple flags with vex justification codes per product"})),l=!0}a.product_ids?.forEach((e=>{s.has(e)&&i(),s.add(e)})),a.group_ids?.forEach((t=>{const a=e.product_tree.product_groups?.find((e=>e.group_id===t))?.product_ids;a?.forEach((e=>{s.has(e)&&i(),s.add(e)}))}))}))})),t):t},mandatoryTest_6_1_4:function(e){const t=[];let a=!0;const n=Np({document:e}),s=function({document:e}){const t=[],a=e.vulnerabilities;if(a)for(let e=0;e<a.length;++e){const n=a[e];Ap(`/vulnerabilities/${e}/remediations`,n,t),kp(`/vulnerabilities/${e}/threats`,n,t)}return t}({document:e}),u=Ip(n,s);return u.length>0&&(a=!1,u.forEach((e=>{t.push({message:"definition of group id missing",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_5:function(e){const t=[];let a=!0;const n=function({document:e}){const t=[],a=e.product_tree?.product_groups;if(a)for(let e=0;e<a.length;++e){const n=a[e];n.group_id&&t.push({id:n.group_id,name:n.summary??"",instancePath:`/product_tree/product_groups/${e}/group_id`})}return t}({document:e}),s=Tp(n);return s.length>0&&(a=!1,s.forEach((e=>{t.push({message:"duplicate definition product group id",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_6:function(e){const t=[];let a=!0;if(Array.isArray(e.vulnerabilities)){e.vulnerabilities.forEach(((e,n)=>{const s=e.product_status;if(!s)return;const u=[new Set([].concat(Array.isArray(s.first_affected)?s.first_affected:[]).concat(Array.isArray(s.known_affected)?s.known_affected:[]).concat(Array.isArray(s.last_affected)?s.last_affected:[])),new Set(Array.isArray(s.known_not_affected)?s.known_not_affected:[]),new Set([].concat(Array.isArray(s.first_fixed)?s.first_fixed:[]).concat(Array.isArray(s.fixed)?s.fixed:[])),new Set(Array.isArray(s.under_investigation)?s.under_investigation:[])];u.forEach(((e,s)=>{const l=u.slice(s+1);e.forEach((e=>{l.some((t=>t.has(e)))&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/product_status`,message:`product id "${e}" is mentioned in contradicting product status groups`}))}))}))}))}return{isValid:a,errors:t}},mandatoryTest_6_1_7:function(e){const t=[];let a=!0;return Dp(e)&&e.vulnerabilities.forEach(((e,n)=>{const s=new Map;e.scores?.forEach(((e,u)=>{e.products?.forEach(((l,i)=>{const p=s.get(l)??new Set;s.set(l,p),(void 0!==e.cvss_v2?.version&&p.has(e.cvss_v2.version)||void 0!==e.cvss_v3?.version&&p.has(e.cvss_v3.version))&&(a=!1,t.push({message:`product is already included in these cvss-versions: ${Array.from(p.keys()).join(", ")}`,instancePath:`/vulnerabilities/${n}/scores/${u}/products/${i}`})),void 0!==e.cvss_v2?.version&&p.add(e.cvss_v2.version),void 0!==e.cvss_v3?.version&&p.add(e.cvss_v3.version)}))}))})),{errors:t,isValid:a}},mandatoryTest_6_1_8:function(e){const t={errors:[],isValid:!0};if(!Cg(e))return t;for(const[a,n]of e.vulnerabilities?.entries()??[])for(const[e,s]of n.scores?.entries()??[]){if(s.cvss_v2){if(!Og(s.cvss_v2)){t.isValid=!1;for(const n of Og.errors??[])t.errors.push({instancePath:`/vulnerabilities/${a}/scores/${e}/cvss_v2${n.instancePath}`,message:n.message??""})}}if(s.cvss_v3){if(!Ig(s.cvss_v3)){t.isValid=!1;for(const n of Ig.errors??[])t.errors.push({instancePath:`/vulnerabilities/${a}/scores/${e}/cvss_v3${n.instancePath}`,message:n.message??""})}}}return t},mandatoryTest_6_1_9:function(e){const t=[];let a=!0;if(Array.isArray(e.vulnerabilities)){e.vulnerabilities.forEach(((e,n)=>{if(!Array.isArray(e.scores))return;e.scores.forEach(((e,s)=>{if("string"==typeof e.cvss_v2?.vectorString){const u=e.cvss_v2,l=function(e){try{return{success:!0,baseMetricScore:tc.getBaseScore(e),temporalMetricScore:tc.getTemporalScore(e),environmentalMetricScore:ac(e)}}catch(e){return{success:!1,baseMetricScore:-1,temporalMetricScore:-1,environmentalMetricScore:-1}}}(u.vectorString);if(l.success)for(const{score:e,expectedScore:i,name:p}of[{score:u.baseScore,expectedScore:l.baseMetricScore,name:"baseScore"},{score:u.temporalScore,expectedScore:l.temporalMetricScore,name:"temporalScore"},{score:u.environmentalScore,expectedScore:l.environmentalMetricScore,name:"environmentalScore"}])"number"==typeof e&&e!==Number(i)&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v2/${p}`,message:"invalid calculated value"}))}if("string"==typeof e.cvss_v3?.vectorString&&("3.1"===e.cvss_v3.version||"3.0"===e.cvss_v3.version)){const u=e.cvss_v3,l=("3.0"===u.version?fc:yc).calculateCVSSFromVector(u.vectorString);if(l.success){for(const{score:e,expectedScore:i,name:p}of[{score:u.baseScore,expectedScore:l.baseMetricScore,name:"baseScore"},{score:u.temporalScore,expectedScore:l.temporalMetricScore,name:"temporalScore"},{score:u.environmentalScore,expectedScore:l.environmentalMetricScore,name:"environmentalScore"}])"number"==typeof e&&e!==Number(i)&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v3/${p}`,message:"invalid calculated value"}));for(const{severity:e,expectedSeverity:i,name:p}of[{severity:u.baseSeverity,expectedSeverity:l.baseSeverity,name:"baseSeverity"},{severity:u.temporalSeverity,expectedSeverity:l.temporalSeverity,name:"temporalSeverity"},{severity:u.environmentalSeverity,expectedSeverity:l.environmentalSeverity,name:"environmentalSeverity"}])"string"==typeof e&&e!==i.toUpperCase()&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v3/${p}`,message:"invalid calculated value"}))}}}))}))}return{errors:t,isValid:a}}});const eb=new Sg,tb=eb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}},full_product_names:{elements:{additionalProperties:!0,properties:{}}},relationships:{elements:{additionalProperties:!0,properties:{}}}}}},optionalProperties:{document:{additionalProperties:!0,optionalProperties:{category:{type:"string"}}}}}),ab={additionalProperties:!0,properties:{product_id:{type:"string"}}},nb=eb.compile(ab),sb={additionalProperties:!0,optionalProperties:{product:ab,branches:{elements:{additionalProperties:!0,properties:{}}}}},ub=eb.compile(sb),lb={additionalProperties:!0,properties:{full_product_name:ab}},ib=eb.compile(lb);const pb=eb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,properties:{product_groups:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}}}}}}}}}),rb=eb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,properties:{relationships:{elements:{additionalProperties:!0,optionalProperties:{product_reference:{type:"string"},relates_to_product_reference:{type:"string"}}}}}}}}),ob=eb.compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{product_status:{additionalProperties:!0,optionalProperties:{first_affected:{elements:{type:"string"}},first_fixed:{elements:{type:"string"}},fixed:{elements:{type:"string"}},known_affected:{elements:{type:"string"}},known_not_affected:{elements:{type:"string"}},last_affected:{elements:{type:"string"}},recommended:{elements:{type:"string"}},under_investigation:{elements:{type:"string"}}}}}}}}}),gb=eb.compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{remediations:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}}}}},scores:{elements:{additionalProperties:!0,optionalProperties:{products:{elements:{type:"string"}}}}},threats:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}}}}}}}}}});function cb(e,t){let a=!1;return!a&&pb(e)&&(a=e.product_tree.product_groups.some((e=>e.product_ids?.includes(t)??!1))),!a&&rb(e)&&(a=e.product_tree.relationships.some((e=>e.product_reference===t||e.relates_to_product_reference===t))),!a&&ob(e)&&(a=e.vulnerabilities.some((e=>["first_affected","first_fixed","fixed","known_affected","known_not_affected","last_affected","recommended","under_investigation"].some((a=>e.product_status?.[a]?.includes(t)??!1))))),!a&&gb(e)&&(a=e.vulnerabilities.some((e=>e.remediations?.some((e=>e.product_ids?.includes(t)))||e.scores?.some((e=>e.products?.includes(t)))||e.threats?.some((e=>e.product_ids?.includes(t)))||!1))),a}const fb=(new Sg).compile({additionalProperties:!0,optionalProperties:{product_tree:{additionalProperties:!0,optionalProperties:{product_groups:{elements:{additionalProperties:!0,optionalProperties:{group_id:{type:"string"},product_ids:{elements:{type:"string"}}}}}}}},properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{product_status:{additionalProperties:!0,optionalProperties:{first_affected:{elements:{type:"string"}},known_affected:{elements:{type:"string"}},last_affected:{elements:{type:"string"}},under_investigation:{elements:{type:"string"}}}}},optionalProperties:{remediations:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}},group_ids:{elements:{type:"string"}}}}}}}}}});const yb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{product_status:{additionalProperties:!0,optionalProperties:{first_affected:{elements:{type:"string"}},known_affected:{elements:{type:"string"}},last_affected:{elements:{type:"string"}}}}},optionalProperties:{scores:{elements:{additionalProperties:!0,optionalProperties:{products:{elements:{type:"string"}}}}}}}}}});const db=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,properties:{revision_history:{elements:{additionalProperties:!0,properties:{}}}}}}}}});const bb=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,properties:{initial_release_date:{type:"timestamp"},revision_history:{elements:{additionalProperties:!0,properties:{date:{type:"timestamp"}}}}}}}}}});const xb=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,properties:{current_release_date:{type:"timestamp"},revision_history:{elements:{additionalProperties:!0,properties:{date:{type:"timestamp"}}}}}}}}}});const hb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{involvements:{elements:{additionalProperties:!0,properties:{}}}}}}}});const mb=new Sg,_b=mb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}},full_product_names:{elements:{additionalProperties:!0,properties:{}}},relationships:{elements:{additionalProperties:!0,properties:{}}}}}}}),vb=mb.compile({additionalProperties:!0,properties:{product_identification_helper:{additionalProperties:!0,properties:{hashes:{elements:{additionalProperties:!0,properties:{}}}}}}}),Eb=mb.compile({additionalProperties:!0,properties:{full_product_name:{additionalProperties:!0,properties:{product_identification_helper:{additionalProperties:!0,properties:{hashes:{elements:{additionalProperties:!0,properties:{}}}}}}}}}),wb=mb.compile({additionalProperties:!0,optionalProperties:{product:{additionalProperties:!0,properties:{product_identification_helper:{additionalProperties:!0,properties:{hashes:{elements:{additionalProperties:!0,properties:{}}}}}}}}});const Sb=(new Sg).compile({additionalProperties:!0,properties:{file_hashes:{elements:{additionalProperties:!0,properties:{}}}}});function Cb(e,t){const a={warnings:[]};return function(e,t){const a={warnings:[]};if(!_b(e))return a;e.product_tree.full_product_names?.forEach(((e,a)=>{vb(e)&&e.product_identification_helper.hashes.forEach(((e,n)=>{t({path:`/product_tree/full_product_names/${a}/product_identification_helper/hashes/${n}`,hash:e})}))}));const n=(e,a)=>{a.forEach(((a,s)=>{wb(a)&&(a.product?.product_identification_helper.hashes.forEach(((a,n)=>{t({path:`${e}${s}/product_identification_helper/hashes/${n}`,hash:a})})),n(`${e}${s}/branches/`,Array.isArray(a.branches)?a.branches:[]))}))};n("/product_tree/branches/",e.product_tree.branches??[]),e.product_tree.relationships?.forEach(((e,a)=>{Eb(e)&&e.full_product_name.product_identification_helper.hashes.forEach(((e,n)=>{t({path:`/product_tree/relationships/${a}/product_identification_helper/hashes/${n}`,hash:e})}))}))}(e,(({path:e,hash:n})=>{if(!Sb(n))return;const s=function(e){return new Set(e.file_hashes.map((e=>e.algorithm)).filter((e=>"string"==typeof e)))}(n);s.has(t)&&1===s.size&&a.warnings.push({instancePath:e,message:`use of ${t} as the only hash algorithm`})})),a}const Ob=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{distribution:{additionalProperties:!0,properties:{tlp:{additionalProperties:!0,properties:{label:{type:"string"}}}}}}}}});const Ib=new Sg,Nb=Ib.compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{references:{elements:{additionalProperties:!0,properties:{}}},tracking:{additionalProperties:!0,properties:{id:{type:"string"}}}}}}}),Ab=Ib.compile({additionalProperties:!0,properties:{category:{type:"string"},url:{type:"string"}}});const kb=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{lang:{type:"string"}}}}});const Tb=new Sg,Db=Tb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}},full_product_names:{elements:{additionalProperties:!0,properties:{}}},relationships:{elements:{additionalProperties:!0,properties:{}}}}}}}),Rb=Tb.compile({additionalProperties:!0,properties:{full_product_name:{additionalProperties:!0,optionalProperties:{product_identification_helper:{additionalProperties:!0,properties:{}}}}}}),Pb=Tb.compile({additionalProperties:!0,optionalProperties:{product:{additionalProperties:!0,optionalProperties:{product_identification_helper:{additionalProperties:!0,properties:{}}}}}});const Mb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{ids:{elements:{additionalProperties:!0,optionalProperties:{text:{type:"string"}}}}}}}}});const $b=new Sg,Lb=$b.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}}}}}}),Wb=$b.compile({additionalProperties:!0,optionalProperties:{category:{type:"string"},name:{type:"string"}}});const Fb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{product_status:{additionalProperties:!0,optionalProperties:{fixed:{elements:{type:"string"}},first_fixed:{elements:{type:"string"}}}},scores:{elements:{additionalProperties:!0,optionalProperties:{cvss_v3:{additionalProperties:!0,optionalProperties:{environmentalScore:{type:"float64"},vectorString:{type:"string"},version:{type:"string"}}},cvss_v2:{additionalProperties:!0,optionalProperties:{environmentalScore:{type:"float64"},vectorString:{type:"string"},version:{type:"string"}}},products:{elements:{type:"string"}}}}}}}}}});const jb=rc.map((e=>[e[0],e[1],Object.fromEntries(Object.entries(e[2]).map((([e,t])=>[e,t.id])))])),Ub=dc;function zb({version:e,vectorString:t,metrics:a}){const n=new Map(t.split("/").map((e=>{const[t,a]=e.split(":");return[t,a]})).filter((([,e])=>e)));if("3.1"===e||"3.0"===e){const t=Vb({mapping:Ub,metrics:a,vector:n}).map((e=>e[1])),s=("3.1"===e?yc:fc).calculateCVSSFromMetrics(...t);return s.success?Number(s.environmentalMetricScore):null}{const e=function(e){try{return{success:!0,environmentalMetricScore:ac(e)}}catch(e){return{success:!1,environmentalMetricScore:-1}}}(Object.fromEntries(Vb({mapping:jb,metrics:a,vector:n})));return e.success?e.environmentalMetricScore:null}}function Vb({vector:e,metrics:t,mapping:a}){return a.map((a=>{const n=a[1],s=a[0],u=a[2],l=t[s];return[n,e.get(n)??u[l]]}))}const Hb=Mu.compile($u);var qb=Object.freeze({__proto__:null,optionalTest_6_2_1:function(e){const t=[],a={warnings:t};return tb(e)&&"csaf_informational_advisory"!==e.document?.category?(function a({path:n,branches:s}){s.forEach(((s,u)=>{ub(s)&&("string"!=typeof s.product?.product_id||cb(e,s.product.product_id)||t.push({instancePath:`${n}/${u}/product/product_id`,message:"is not referenced"}),Array.isArray(s.branches)&&a({path:`${n}/${u}/branches`,branches:s.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree?.branches??[]}),e.product_tree.full_product_names?.forEach(((t,n)=>{nb(t)&&(cb(e,t.product_id)||a.warnings.push({instancePath:`/product_tree/full_product_names/${n}/product_id`,message:"is not referenced"}))})),e.product_tree.relationships?.forEach(((t,n)=>{ib(t)&&(cb(e,t.full_product_name.product_id)||a.warnings.push({instancePath:`/product_tree/relationships/${n}/full_product_name/product_id`,message:"is not referenced"}))})),a):a},optionalTest_6_2_10:function(e){const t={warnings:[]};return Ob(e)||t.warnings.push({message:"missing tlp label",instancePath:"/document"}),t},optionalTest_6_2_11:function(e){const t={warnings:[]};function a(){t.warnings.push({message:"missing canonical url",instancePath:"/document/references"})}return Nb(e)?(e.document.references.some((t=>Ab(t)&&"self"===t.category&&t.url.startsWith("https://")&&t.url.endsWith(e.document.tracking.id.toLowerCase().replace(/[^+\-a-z0-9]+/g,"_")+".json")))||a(),t):(a(),t)},optionalTest_6_2_12:function(e){const t={warnings:[]};return kb(e)||t.warnings.push({message:"missing document language",instancePath:"/document"}),t},optionalTest_6_2_13:function(e){const t={warnings:[]},a=(e,n,s)=>{if("object"!=typeof s||null==s)return;Array.isArray(s)&&s.forEach(((t,s)=>{a(e,n+"/"+s,t)}));const u=Object.keys(s),l=u.slice().sort(((t,a)=>e.compare(t,a)));JSON.stringify(u)!==JSON.stringify(l)&&t.warnings.push({instancePath:n,message:"not sorted alphabetically"});for(const t of u)a(e,n+"/"+t,s[t])};return a(new Intl.Collator,"",e),t},optionalTest_6_2_14:function(e){const t={warnings:[]};return"string"==typeof e.document?.lang&&Oc(e.document.lang)&&t.warnings.push({instancePath:"/document/lang",message:"use of private language"}),"string"==typeof e.document?.source_lang&&Oc(e.document.source_lang)&&t.warnings.push({instancePath:"/document/source_lang",message:"use of private language"}),t},optionalTest_6_2_15:function(e){const t={warnings:[]},a="i-default";return e.document?.lang===a&&t.warnings.push({instancePath:"/document/lang",message:"use of default language"}),e.document?.source_lang===a&&t.warnings.push({instancePath:"/document/source_lang",message:"use of default language"}),t},optionalTest_6_2_16:function(e){const t={warnings:[]};return Db(e)?(e.product_tree.full_product_names?.forEach(((e,a)=>{e.product_identification_helper||t.warnings.push({instancePath:`/product_tree/full_product_names/${a}`,message:"missing product identification helper"})})),e.product_tree.branches&&function e({path:a,branches:n}){n.forEach(((n,s)=>{Pb(n)&&(n.product&&!n.product.product_identification_helper&&t.warnings.push({instancePath:`${a}/${s}/product`,message:"missing product identification helper"}),Array.isArray(n.branches)&&e({path:`${a}/${s}/branches`,branches:n.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree.branches}),e.product_tree.relationships?.forEach(((e,a)=>{Rb(e)&&(e.full_product_name.product_identification_helper||t.warnings.push({instancePath:`/product_tree/relationships/${a}/full_product_name`,message:"missing product identification helper"}))})),t):t},optionalTest_6_2_17:function(e){const t={warnings:[]};return Mb(e)?(e.vulnerabilities.forEach(((e,a)=>{e.ids?.forEach(((e,n)=>{e.text?.match(/^CVE-[0-9]{4}-[0-9]{4,}$/)&&t.warnings.push({instancePath:`/vulnerabilities/${a}/ids/${n}`,message:"contains CVE"})}))})),t):t},optionalTest_6_2_18:function(e){const t={warnings:[]};return Lb(e)?(e.product_tree.branches&&function e({path:a,branches:n}){n.forEach(((n,s)=>{Wb(n)&&("product_version_range"!==n.category||"string"!=typeof n.name||n.name.match(new RegExp("^vers:[a-z\\.\\-\\+][a-z0-9\\.\\-\\+]*/.+"))||t.warnings.push({instancePath:`${a}/${s}`,message:"product version range without vers"}),Array.isArray(n.branches)&&e({path:`${a}/${s}/branches`,branches:n.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree.branches}),t):t},optionalTest_6_2_19:function(e){const t={warnings:[]};return Fb(e)?(e.vulnerabilities.forEach(((e,a)=>{const n=new Set([...e.product_status?.first_fixed??[],...e.product_status?.fixed??[]]);for(const s of n)e.scores?.forEach(((e,n)=>{if(e.products?.includes(s)){if(e.cvss_v3){const u="3.1"===e.cvss_v3.version||"3.0"===e.cvss_v3.version?zb({version:e.cvss_v3.version,vectorString:e.cvss_v3.vectorString??"",metrics:e.cvss_v3}):null;("number"==typeof e.cvss_v3.environmentalScore&&e.cvss_v3.environmentalScore>0||"number"==typeof u&&u>0||null===u)&&t.warnings.push({instancePath:`/vulnerabilities/${a}/scores/${n}/cvss_v3`,message:`environmental score should be 0 since "${s}" is listed as fixed`})}if(e.cvss_v2){const u=zb({version:"2.0",vectorString:e.cvss_v2.vectorString??"",metrics:e.cvss_v2});("number"==typeof e.cvss_v2.environmentalScore&&e.cvss_v2.environmentalScore>0||"number"==typeof u&&0!==u||null===u)&&t.warnings.push({instancePath:`/vulnerabilities/${a}/scores/${n}/cvss_v2`,message:`environmental score should be 0 since "${s}" is listed as fixed`})}}}))})),t):t},optionalTest_6_2_2:function(e){const t={warnings:[]};return fb(e)?(e.vulnerabilities?.forEach(((a,n)=>{const s=a.product_status;["first_affected","known_affected","last_affected","under_investigation"].forEach((u=>{const l=s[u];l?.forEach(((s,l)=>{const i=a.remediations?.some((t=>t.product_ids?.includes(s)||t.group_ids?.map((t=>e.product_tree?.product_groups?.find((e=>e.group_id===t)))).some((e=>e?.product_ids?.includes(s)))));i||t.warnings.push({message:"missing remediation",instancePath:`/vulnerabilities/${n}/product_status/${u}/${l}`})}))}))})),t):t},optionalTest_6_2_20:function(e){const t={warnings:[]};if(!Hb(e)){const e=Hb.errors?.filter((e=>"additionalProperties"===e.keyword))??[];for(const a of e)t.warnings.push({instancePath:a.instancePath,message:a.message??""})}return t},optionalTest_6_2_3:function(e){const t={warnings:[]};return yb(e)?(e.vulnerabilities?.forEach(((e,a)=>{const n=e.product_status;["first_affected","known_affected","last_affected"].forEach((s=>{const u=n[s];u?.forEach(((n,u)=>{const l=e.scores?.some((e=>e.products?.includes(n)));l||t.warnings.push({message:"missing score",instancePath:`/vulnerabilities/${a}/product_status/${s}/${u}`})}))}))})),t):t},optionalTest_6_2_4:function(e){const t=[],a={warnings:t};return db(e)?(e.document.tracking.revision_history?.forEach(((e,a)=>{const{number:n}=e;"string"==typeof n&&n.includes("+")&&t.push({message:"build metadata in revision history",instancePath:`/document/tracking/revision_history/${a}/number`})})),a):a},optionalTest_6_2_5:function(e){const t=[],a={warnings:t};if(!bb(e))return a;const n=e.document.tracking.revision_history.slice().sort(((e,t)=>gd(e.date,t.date)))[0];return n&&gd(e.document.tracking.initial_release_date,n.date)<0&&t.push({message:"older initial release date than revision history",instancePath:"/document/tracking/initial_release_date"}),a},optionalTest_6_2_6:function(e){const t=[],a={warnings:t};if(!xb(e))return a;const n=e.document.tracking.revision_history.slice().sort(((e,t)=>gd(t.date,e.date)))[0];return n&&gd(e.document.tracking.current_release_date,n.date)<0&&t.push({message:"older current release date than revision history",instancePath:"/document/tracking/current_release_date"}),a},optionalTest_6_2_7:function(e){const t={warnings:[]};return hb(e)?(e.vulnerabilities.forEach(((e,a)=>{e.involvements?.forEach(((e,n)=>{e.date||t.warnings.push({message:"missing date",instancePath:`/vulnerabilities/${a}/involvements/${n}`})}))})),t):t},optionalTest_6_2_8:function(e){return Cb(e,"md5")},optionalTest_6_2_9:function(e){return Cb(e,"sha1")}});Deno.core.ops.op_register_func((async function(e,t,a){let n=[];for(const t of e)switch(t){case"schema":n=n.concat(Object.values(Fu));break;case"mandatory":n=n.concat(Object.values(Qd));break;case"optional":n=n.concat(Object.values(qb));break;default:throw new Error(`Unknown validation set: ${t}`)}return n=n.filter((e=>!a.has(e.name))),async function(e,t){let a=!0;const n=[];for(const s of e){const e=await s(t),u="boolean"!=typeof e.isValid||e.isValid;n.push({isValid:u,errors:e.errors??[],warnings:e.warnings??[],infos:e.infos??[],name:s.name}),a=a&&u}return{tests:n,isValid:a}}(n,t)}));
+ */,nativeJs:function(e,t){if(void 0===t&&(t=Nf.systemDefault()),Gc(e,"date"),Gc(t,"zone"),e instanceof Date)return Gy.ofEpochMilli(e.getTime()).atZone(t);if("function"==typeof e.toDate&&e.toDate()instanceof Date)return Gy.ofEpochMilli(e.toDate().getTime()).atZone(t);throw new zc("date must be a javascript Date or a moment instance")},ArithmeticException:Uc,DateTimeException:Wc,DateTimeParseException:Fc,IllegalArgumentException:zc,IllegalStateException:Vc,UnsupportedTemporalTypeException:jc,NullPointerException:Hc,Clock:Ky,DayOfWeek:xf,Duration:uf,Instant:Gy,LocalDate:Hy,LocalTime:By,LocalDateTime:Yy,OffsetTime:Wy,OffsetDateTime:Uy,Month:mf,MonthDay:Iy,ParsePosition:Ef,Period:vf,Year:Ty,YearConstants:lf,YearMonth:Ay,ZonedDateTime:jy,ZoneOffset:Rf,ZoneId:Nf,ZoneRegion:fy,ZoneOffsetTransition:ed,ZoneRules:Af,ZoneRulesProvider:cy,ChronoLocalDate:Of,ChronoLocalDateTime:qy,ChronoZonedDateTime:Fy,IsoChronology:Ly,ChronoField:gf,ChronoUnit:pf,IsoFields:Wf,Temporal:Cf,TemporalAccessor:ff,TemporalAdjuster:Dy,TemporalAdjusters:Ry,TemporalAmount:nf,TemporalField:rf,TemporalQueries:cf,TemporalQuery:yf,TemporalUnit:sf,ValueRange:of,DateTimeFormatter:Cy,DateTimeFormatterBuilder:my,DecimalStyle:Jf,ResolverStyle:Sf,SignStyle:Qf,TextStyle:ey},od=(id=rd,pd=[],function(e){return~pd.indexOf(e)||(e(id),pd.push(e)),id});rd.use=od;const gd=(e,t)=>{try{const a=e instanceof jy?e:jy.parse(e),n=t instanceof jy?t:jy.parse(t),s=uf.between(a,n);return s.isZero()?0:s.isNegative()?1:-1}catch(e){return 0}},{gt:cd,valid:fd}=fp;const yd=e=>Array.isArray(e?.document?.tracking?.revision_history)&&e.document.tracking.revision_history.every((e=>"string"==typeof e.number&&"string"==typeof e.date));const{hasTrackingRevisionHistory:dd,hasTrackingVersionField:bd,hasTrackingStatusField:xd}=bp;const{valid:hd,major:md,prerelease:_d}=fp,{hasTrackingVersionField:vd,hasTrackingStatusField:Ed}=bp;const{valid:wd,major:Sd}=fp,{hasTrackingVersionField:Cd,hasTrackingStatusField:Od,hasTrackingRevisionHistory:Id}=bp;const{valid:Nd,prerelease:Ad}=fp,{hasTrackingVersionField:kd,hasTrackingStatusField:Td,hasTrackingRevisionHistory:Dd}=bp;const{valid:Rd,prerelease:Pd}=fp,{hasTrackingVersionField:Md,hasTrackingStatusField:$d}=bp;const Ld=e=>Array.isArray(e?.document?.tracking?.revision_history)&&e.document.tracking.revision_history.every((e=>"string"==typeof e.number&&"string"==typeof e.date)),{hasTrackingVersionField:Wd,hasTrackingStatusField:Fd,hasTrackingRevisionHistory:jd}=bp;const Ud=e=>{if("object"!=typeof e||!e)return!1;const t=e;return Array.isArray(t.vulnerabilities)&&t.vulnerabilities.every((e=>"string"==typeof e.cve||void 0===e.cve))};const zd=e=>{if("object"!=typeof e||!e)return!1;const t=e;return Array.isArray(t.vulnerabilities)&&t.vulnerabilities.every((e=>Array.isArray(e.involvements)&&e.involvements.every((e=>!("string"!=typeof e.date&&void 0!==e.date||"string"!=typeof e.party&&void 0!==e.party||"string"!=typeof e.status&&void 0!==e.status)))))};const Vd=(e,t,a=[])=>{Array.isArray(e.branches)&&e.branches.forEach(((e,n)=>{const s=a.concat([n]);Array.isArray(e.product?.product_identification_helper?.hashes)&&e.product.product_identification_helper.hashes.forEach(((e,a)=>{Hd(e,(({fileHash:e,fileHashIndex:n})=>{t({branchIndexes:s,hashIndex:a,fileHash:e,fileHashIndex:n})}))})),Vd(e,t,s)}))},Hd=(e,t)=>{if(!Array.isArray(e.file_hashes))return;const a=new Set;e.file_hashes.forEach(((e,n)=>{null!=e.algorithm&&(a.has(e.algorithm)&&t({fileHash:e,fileHashIndex:n}),a.add(e.algorithm))}))};const qd=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{remediations:{elements:{additionalProperties:!0,optionalProperties:{group_ids:{elements:{type:"string"}},product_ids:{elements:{type:"string"}}}}}}}}}});const Yd=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,optionalProperties:{revision_history:{elements:{additionalProperties:!0,optionalProperties:{number:{type:"string"}}}},version:{type:"string"}}}}}}});function Bd(e){return parseInt(e).toString()===e?"INTEGER":"SEMANTIC"}const Xd=new Sg,Gd=Xd.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,properties:{branches:{elements:{additionalProperties:!0,properties:{}}}}}}}),Kd=Xd.compile({additionalProperties:!0,optionalProperties:{category:{type:"string"},name:{type:"string"},branches:{elements:{additionalProperties:!0,properties:{}}}}});const Zd=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{flags:{elements:{additionalProperties:!0,optionalProperties:{group_ids:{elements:{type:"string"}},product_ids:{elements:{type:"string"}}}}}}}}}});const Jd=(new Sg).compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{product_groups:{elements:{optionalProperties:{group_id:{type:"string"},product_ids:{elements:{type:"string"}}}}}}},vulnerabilities:{elements:{additionalProperties:!0,properties:{flags:{elements:{additionalProperties:!0,optionalProperties:{group_ids:{elements:{type:"string"}},product_ids:{elements:{type:"string"}}}}}}}}}});var Qd=Object.freeze({__proto__:null,mandatoryTest_6_1_1:function(e){const t=[];let a=!0;const n=xp({document:e}),s=function({document:e}){const t=[],a=e.product_tree?.product_groups;if(a)for(let e=0;e<a.length;++e){const n=a[e].product_ids;if(n)for(let a=0;a<n.length;++a){const s=n[a];s&&t.push({id:s,instancePath:`/product_tree/product_groups/${e}/product_ids/${a}`})}}const n=e.product_tree?.relationships;if(n)for(let e=0;e<n.length;++e){const a=n[e],s=a.product_reference;s&&t.push({id:s,instancePath:`/product_tree/relationships/${e}/product_reference`});const u=a.relates_to_product_reference;u&&t.push({id:u,instancePath:`/product_tree/relationships/${e}/relates_to_product_reference`})}const s=e.vulnerabilities;if(s)for(let e=0;e<s.length;++e){const a=s[e];hp(`/vulnerabilities/${e}/product_status`,a,t),Ep(`/vulnerabilities/${e}/remediations`,a,t),vp(`/vulnerabilities/${e}/scores`,a,t),_p(`/vulnerabilities/${e}/threats`,a,t)}return t}({document:e}),u=wp(n,s);return u.length>0&&(a=!1,u.forEach((e=>{t.push({message:"definition of product id missing",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_10:function(e){const t=[];let a=!0;if(Array.isArray(e.vulnerabilities)){e.vulnerabilities.forEach(((e,n)=>{if(!Array.isArray(e.scores))return;e.scores.forEach(((e,s)=>{if("string"==typeof e.cvss_v2?.vectorString){const u=e.cvss_v2;hc({vectorValues:u.vectorString.split("/"),vectorMapping:xc,cvss:u,onError({attributeKey:e}){a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v2/${e}`,message:"value is not consistent with the vector string"})}})}if("string"==typeof e.cvss_v3?.vectorString&&("3.1"===e.cvss_v3.version||"3.0"===e.cvss_v3.version)){const u=e.cvss_v3;hc({vectorValues:u.vectorString.split("/").slice(1),vectorMapping:bc,cvss:u,onError({attributeKey:e}){a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v3/${e}`,message:"value is not consistent with the vector string"})}})}}))}))}return{errors:t,isValid:a}},mandatoryTest_6_1_11:function(e){const t=[];let a=!0;if(_c(e))for(let n=0;n<e.vulnerabilities.length;++n){const s=e.vulnerabilities[n];if(vc(s)){const e=mc.weaknesses.find((e=>e.id===s.cwe.id));if(!e){a=!1,t.push({instancePath:`/vulnerabilities/${n}/cwe/id`,message:"no weakness with this id is recognized"});continue}if(e.name!==s.cwe.name){a=!1,t.push({instancePath:`/vulnerabilities/${n}/cwe/name`,message:"the name does not match the weakness with the given id"});continue}}}return{isValid:a,errors:t}},mandatoryTest_6_1_12:function(e){const t=[];let a=!0;return Ac(e)&&!Cc(e.document.lang)&&(a=!1,t.push({message:"is not a valid language-tag",instancePath:"/document/lang"})),kc(e)&&(Cc(e.document.source_lang)||(a=!1,t.push({message:"is not a valid language-tag",instancePath:"/document/source_lang"}))),{errors:t,isValid:a}},mandatoryTest_6_1_13:function(e){const t=[];let a=!0;return Array.isArray(e.product_tree?.full_product_names)&&e.product_tree?.full_product_names.forEach(((e,n)=>{$c(e,(()=>{a=!1,t.push({instancePath:`/product_tree/full_product_names/${n}/product_identification_helper/purl`,message:"invalid purl"})}))})),Array.isArray(e.product_tree?.relationships)&&e.product_tree.relationships.forEach(((e,n)=>{$c(e.full_product_name,(()=>{a=!1,t.push({instancePath:`/product_tree/relationships/${n}/full_product_name/product_identification_helper/purl`,message:"invalid purl"})}))})),e.product_tree&&Mc(e.product_tree,(({branchIndexes:e})=>{a=!1;const n=e.reduce(((e,t)=>`${e}/branches/${t}`),"/product_tree");t.push({instancePath:`${n}/product/product_identification_helper/purl`,message:"invalid purl"})})),{errors:t,isValid:a}},mandatoryTest_6_1_14:function(e){const t=[];let a=!0;if(yd(e)){Array.from(new Set(e.document.tracking.revision_history.slice().sort(((e,t)=>gd(e.date,t.date)||dp(t.number,e.number))).map((e=>fd(e.number)??`${e.number}.0.0`))).keys()).filter((e=>null!==fd(e))).every(((e,t,a)=>0===t||cd(e,a[t-1])))||(a=!1,t.push({instancePath:"/document/tracking/revision_history",message:"the items must be in ascending order according to the date"}))}return{errors:t,isValid:a}},mandatoryTest_6_1_15:function(e){let t=!0;const a=[];return"translator"!==e.document?.publisher?.category||e.document.source_lang||(t=!1,a.push({instancePath:"/document/publisher/category",message:"source language attribute is missing"})),{isValid:t,errors:a}},mandatoryTest_6_1_16:function(e){let t=!0;const a=[];if(dd(e)&&bd(e)&&xd(e)&&e.document.tracking.revision_history.length>0){const n=t=>"draft"==e.document.tracking.status?t.split(/[+-]/)[0]:t.split("+")[0];n(e.document.tracking.revision_history.slice().sort(((e,t)=>gd(t.date,e.date)||dp(e.number,t.number)))[0].number)!==n(e.document.tracking.version)&&(t=!1,a.push({message:"version does not match latest revision",instancePath:"/document/tracking/version"}))}return{errors:a,isValid:t}},mandatoryTest_6_1_17:function(e){let t=!0;const a=[];return vd(e)&&Ed(e)&&"draft"!==e.document.tracking.status&&("0"===e.document.tracking.version||hd(e.document.tracking.version)&&(0===md(e.document.tracking.version)||_d(e.document.tracking.version)))&&(t=!1,a.push({message:"the status is not compatible with the version",instancePath:"/document/tracking/status"})),{errors:a,isValid:t}},mandatoryTest_6_1_18:function(e){let t=!0;const a=[];return Cd(e)&&Od(e)&&Id(e)&&("final"===e.document.tracking.status||"interim"===e.document.tracking.status)&&e.document.tracking.revision_history.some((e=>"0"===e.number||wd(e.number)&&0===Sd(e.number)))&&(t=!1,a.push({message:"some revision-history entries are not compatible with the status",instancePath:"/document/tracking/status"})),{errors:a,isValid:t}},mandatoryTest_6_1_19:function(e){let t=!0;const a=[];if(kd(e)&&Td(e)&&Dd(e))for(let n=0;n<e.document.tracking.revision_history.length;++n){const s=e.document.tracking.revision_history[n];Nd(s.number)&&Ad(s.number)&&(t=!1,a.push({message:"contains prerelease part",instancePath:`/document/tracking/revision_history/${n}/number`}))}return{errors:a,isValid:t}},mandatoryTest_6_1_2:function(e){const t=[];let a=!0;const n=function({document:e}){const t=[],a=e.product_tree?.full_product_names;if(a)for(let e=0;e<a.length;++e){const n=a[e];n.product_id&&t.push({id:n.product_id,name:n.name??"",instancePath:`/product_tree/full_product_names/${e}/product_id`})}const n=e.product_tree?.relationships;if(n)for(let e=0;e<n.length;++e){const a=n[e].full_product_name;a&&a.product_id&&t.push({id:a.product_id,name:a.name??"",instancePath:`/product_tree/relationships/${e}/full_product_name/product_id`})}const s=e.product_tree?.branches;s&&Cp(s,t,"/product_tree/branches");return t}({document:e}),s=Sp(n);return s.length>0&&(a=!1,s.forEach((e=>{t.push({message:"duplicate definition product id",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_20:function(e){let t=!0;const a=[];return Md(e)&&$d(e)&&("final"===e.document.tracking.status||"interim"===e.document.tracking.status)&&Rd(e.document.tracking.version)&&Pd(e.document.tracking.version)&&(t=!1,a.push({message:"pre-release part is not allowed for status",instancePath:"/document/tracking/version"})),{errors:a,isValid:t}},mandatoryTest_6_1_21:function(e){const t=[];let a=!0;if(Ld(e)){const n=Array.from(new Set(e.document.tracking.revision_history.slice().sort(((e,t)=>gd(e.date,t.date))).map((e=>parseInt(e.number)))).keys());n.length>0&&![0,1].includes(n[0])&&(a=!1,t.push({instancePath:"/document/tracking/revision_history",message:"revision history does not start with a version of 0 or 1 when sorted by date"}));for(let e=0;e<n.length;++e){const s=e+Number(n[0]);n[e]>s&&(a=!1,t.push({instancePath:"/document/tracking/revision_history",message:`major version ${s} was omitted`}))}}return{errors:t,isValid:a}},mandatoryTest_6_1_22:function(e){let t=!0;const a=[];if(Wd(e)&&Fd(e)&&jd(e)){let n={};e.document.tracking.revision_history.forEach(((e,s)=>{n[e.number]=n[e.number]??[],n[e.number].push(s),n[e.number].length>1&&(t=!1,a.push({message:"version was already used",instancePath:`/document/tracking/revision_history/${s}/number`}))}))}return{errors:a,isValid:t}},mandatoryTest_6_1_23:function(e){const t=[];let a=!0;if(Ud(e)){const n=new Set;e.vulnerabilities.forEach(((e,s)=>{void 0!==e.cve&&(n.has(e.cve)&&(a=!1,t.push({message:"CVE identifier was already used",instancePath:`/vulnerabilities/${s}/cve`})),n.add(e.cve))}))}return{errors:t,isValid:a}},mandatoryTest_6_1_24:function(e){const t=[];let a=!0;return zd(e)&&e.vulnerabilities.forEach(((e,n)=>{const s=new Map;e.involvements.forEach(((e,u)=>{if("string"==typeof e.date&&"string"==typeof e.party){const l=s.get(e.date)??new Set;l.has(`${e.party}`)&&(a=!1,t.push({message:"status for party was already given for the same date",instancePath:`/vulnerabilities/${n}/involvements/${u}`})),l.add(`${e.party}`),s.set(e.date,l)}}))})),{errors:t,isValid:a}},mandatoryTest_6_1_25:function(e){const t=[];let a=!0;return Array.isArray(e.product_tree?.full_product_names)&&e.product_tree?.full_product_names.forEach(((e,n)=>{Array.isArray(e.product_identification_helper?.hashes)&&e.product_identification_helper.hashes.forEach(((e,s)=>{Hd(e,(({fileHash:e,fileHashIndex:u})=>{a=!1,t.push({instancePath:`/product_tree/full_product_names/${n}/product_identification_helper/hashes/${s}/file_hashes/${u}`,message:`there is already a hash with the algorithm ${e.algorithm}`})}))}))})),Array.isArray(e.product_tree?.relationships)&&e.product_tree.relationships.forEach(((e,n)=>{Array.isArray(e.full_product_name?.product_identification_helper?.hashes)&&e.full_product_name.product_identification_helper.hashes.forEach(((e,s)=>{Hd(e,(({fileHash:e,fileHashIndex:u})=>{a=!1,t.push({instancePath:`/product_tree/relationships/${n}/full_product_name/product_identification_helper/hashes/${s}/file_hashes/${u}`,message:`there is already a hash with the algorithm ${e.algorithm}`})}))}))})),e.product_tree&&Vd(e.product_tree,(({branchIndexes:e,hashIndex:n,fileHashIndex:s,fileHash:u})=>{a=!1;const l=e.reduce(((e,t)=>`${e}/branches/${t}`),"/product_tree");t.push({instancePath:`${l}/product/product_identification_helper/hashes/${n}/file_hashes/${s}`,message:`there is already a hash with the algorithm ${u.algorithm}`})})),{errors:t,isValid:a}},mandatoryTest_6_1_26:function(e){const t=[];let a=!0;if("string"==typeof e.document?.category){const n=e.document.category,s=["securityincidentresponse","informationaladvisory","securityadvisory","vex"];if(["csaf_base","csaf_security_incident_response","csaf_informational_advisory","csaf_security_advisory","csaf_vex"].includes(n))return{errors:t,isValid:a};if(n.toLowerCase().startsWith("csaf_"))return a=!1,t.push({instancePath:"/document/category",message:"reserved prefix used"}),{errors:t,isValid:a};s.includes(n.replace(/[_-\s]+/g,"").toLowerCase())&&(a=!1,t.push({instancePath:"/document/category",message:"value prohibited"}))}return{errors:t,isValid:a}},mandatoryTest_6_1_27_1:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_incident_response","csaf_informational_advisory"]).has(e.document?.category))return{errors:t,isValid:a};const n=new Set(["description","details","general","summary"]);return a=Array.isArray(e.document?.notes)&&e.document.notes.some((e=>n.has(e.category))),a||t.push({instancePath:"/document/notes",message:"need at least one document note with a category of description, details, general or summary"}),{errors:t,isValid:a}},mandatoryTest_6_1_27_10:function(e){const t=[];let a=!0;const n=e.vulnerabilities;return"csaf_vex"===e.document?.category&&Array.isArray(n)?(n.forEach(((n,s)=>{const u=n?.product_status;n&&u&&Array.isArray(u.known_affected)&&u.known_affected.forEach(((u,l)=>{(Array.isArray(n.remediations)?n.remediations:[]).some((t=>{if(!t)return!1;if(Array.isArray(t.product_ids)&&t.product_ids.includes(u))return!0;const a=e.product_tree?.product_groups;return!!(Array.isArray(t.group_ids)&&Array.isArray(a)&&t.group_ids.some((e=>{const t=a.find((t=>t.group_id===e));return t&&Array.isArray(t.product_ids)&&t.product_ids.includes(u)})))}))||(a=!1,t.push({instancePath:`/vulnerabilities/${s}/product_status/known_affected/${l}`,message:"no suitable action statement found"}))}))})),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_11:function(e){const t=[];let a=!0;return new Set(["csaf_security_advisory","csaf_vex"]).has(e.document?.category)?(a=Boolean(e.vulnerabilities),a||t.push({instancePath:"/",message:"needs vulnerabilities"}),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_2:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_incident_response","csaf_informational_advisory"]).has(e.document?.category))return{errors:t,isValid:a};const n=new Set(["external"]);return a=Array.isArray(e.document?.references)&&e.document.references.some((e=>n.has(e.category))),a||t.push({instancePath:"/document/references",message:'need at least one document reference with the category "external"'}),{errors:t,isValid:a}},mandatoryTest_6_1_27_3:function(e){const t=[];let a=!0;return new Set(["csaf_informational_advisory"]).has(e.document?.category)?(a=void 0===e.vulnerabilities,a||t.push({instancePath:"/vulnerabilities",message:"must not exist"}),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_4:function(e){const t=[];let a=!0;return new Set(["csaf_security_advisory","csaf_vex"]).has(e.document?.category)?(a=Boolean(e.product_tree),a||t.push({instancePath:"/",message:"needs a product_tree"}),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_5:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_advisory","csaf_vex"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{e.notes||(a=!1,t.push({instancePath:`/vulnerabilities/${n}`,message:"needs a `notes` attribute"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_6:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_advisory"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{e.product_status||(a=!1,t.push({instancePath:`/vulnerabilities/${n}`,message:"needs a `product_status` attribute"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_7:function(e){const t=[];let a=!0;if(!new Set(["csaf_vex"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{if(!e.product_status)return a=!1,void t.push({instancePath:`/vulnerabilities/${n}`,message:"needs a `product_status` attribute"});["fixed","known_affected","known_not_affected","under_investigation"].some((t=>Array.isArray(e.product_status[t])))||(a=!1,t.push({instancePath:`/vulnerabilities/${n}/product_status`,message:"needs at least one the following attributes: `fixed`, `known_affected`, `known_not_affected`, `under_investigation`"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_8:function(e){const t=[];let a=!0;if(!new Set(["csaf_vex"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{["ids","cve"].every((t=>void 0===e[t]))&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}`,message:"needs at least one the following attributes: `ids`, `cve`"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_9:function(e){const t=[];let a=!0;const n=e.vulnerabilities;return"csaf_vex"===e.document?.category&&Array.isArray(n)?(n.forEach(((n,s)=>{const u=n?.product_status;n&&u&&Array.isArray(u.known_not_affected)&&u.known_not_affected.forEach(((u,l)=>{const i=(Array.isArray(n.threats)?n.threats:[]).some((t=>{if(!t||"impact"!==t.category)return!1;if(Array.isArray(t.product_ids)&&t.product_ids.includes(u))return!0;const a=e.product_tree?.product_groups;return!!(Array.isArray(t.group_ids)&&Array.isArray(a)&&t.group_ids.some((e=>{const t=a.find((t=>t.group_id===e));return t&&Array.isArray(t.product_ids)&&t.product_ids.includes(u)})))})),p=(Array.isArray(n.flags)?n.flags:[]).some((t=>{if(!t)return!1;if(Array.isArray(t.product_ids)&&t.product_ids.includes(u))return!0;const a=e.product_tree?.product_groups;return!!(Array.isArray(t.group_ids)&&Array.isArray(a)&&t.group_ids.some((e=>{const t=a.find((t=>t.group_id===e));return t&&Array.isArray(t.product_ids)&&t.product_ids.includes(u)})))}));i||p||(a=!1,t.push({instancePath:`/vulnerabilities/${s}/product_status/known_not_affected/${l}`,message:"no suitable impact statement found"}))}))})),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_28:function(e){const t=[];let a=!0;return"string"==typeof e.document?.source_lang&&"string"==typeof e.document?.lang&&e.document.source_lang===e.document.lang&&(a=!1,t.push({instancePath:"/document/lang",message:"is the same as `/document/source_lang`"},{instancePath:"/document/source_lang",message:"is the same as `/document/lang`"})),{isValid:a,errors:t}},mandatoryTest_6_1_29:function(e){const t={errors:[],isValid:!0};return qd(e)?(e.vulnerabilities.forEach(((e,a)=>{e.remediations.forEach(((e,n)=>{e.group_ids||e.product_ids||(t.isValid=!1,t.errors.push({instancePath:`/vulnerabilities/${a}/remediations/${n}`,message:"remediation without product reference"}))}))})),t):t},mandatoryTest_6_1_3:function(e){const t=[];let a=!0;if(!Array.isArray(e.product_tree?.relationships))return{isValid:a,errors:t};const n=e.product_tree.relationships;return n.forEach(((e,s)=>{const u=new Set;Op([],n,s,null,(({key:e})=>{"string"!=typeof e||u.has(e)||(u.add(e),a=!1,t.push({instancePath:`/product_tree/relationships/${s}/${e}`,message:"circular reference"}))}))})),{isValid:a,errors:t}},mandatoryTest_6_1_30:function(e){const t={errors:[],isValid:!0};if(!Yd(e))return t;let a=null;return e.document.tracking.revision_history?.forEach(((e,n)=>{if("string"==typeof e.number){const s=Bd(e.number);null===a&&(a=s),a!==s&&(t.isValid=!1,t.errors.push({instancePath:`/document/tracking/revisions/${n}/number`,message:"mixed integer and semantic versioning"}))}})),null!=a&&"string"==typeof e.document.tracking.version&&a!==Bd(e.document.tracking.version)&&(t.isValid=!1,t.errors.push({instancePath:"/document/tracking/version",message:"mixed integer and semantic versioning"})),t},mandatoryTest_6_1_31:function(e){const t={errors:[],isValid:!0};return Gd(e)?(function e({path:a,branches:n}){n.forEach(((n,s)=>{Kd(n)&&("product_version"===n.category&&"string"==typeof n.name&&(["<","<=",">",">="].some((e=>n.name?.toLowerCase().includes(e)))||["after","all","before","earlier","later","prior","versions"].some((e=>n.name?.toLowerCase().split(/\s/).some((t=>e===t)))))&&(t.isValid=!1,t.errors.push({instancePath:`${a}/${s}/name`,message:"version range in product version"})),Array.isArray(n.branches)&&e({path:`${a}/${s}/branches`,branches:n.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree.branches}),t):t},mandatoryTest_6_1_32:function(e){const t={errors:[],isValid:!0};return Zd(e)?(e.vulnerabilities.forEach(((e,a)=>{e.flags.forEach(((e,n)=>{e.group_ids||e.product_ids||(t.isValid=!1,t.errors.push({instancePath:`/vulnerabilities/${a}/flags/${n}`,message:"flag without product reference"}))}))})),t):t},mandatoryTest_6_1_33:function(e){const t={errors:[],isValid:!0};return Jd(e)?(e.vulnerabilities.forEach(((a,n)=>{const s=new Set;a.flags.forEach(((a,u)=>{let l=!1;function i(){l||(t.isValid=!1,t.errors.push({instancePath:`/vulnerabilities/${n}/flags/${u}`,message:"multiple flags with vex justification codes per product"})),l=!0}a.product_ids?.forEach((e=>{s.has(e)&&i(),s.add(e)})),a.group_ids?.forEach((t=>{const a=e.product_tree.product_groups?.find((e=>e.group_id===t))?.product_ids;a?.forEach((e=>{s.has(e)&&i(),s.add(e)}))}))}))})),t):t},mandatoryTest_6_1_4:function(e){const t=[];let a=!0;const n=Np({document:e}),s=function({document:e}){const t=[],a=e.vulnerabilities;if(a)for(let e=0;e<a.length;++e){const n=a[e];Ap(`/vulnerabilities/${e}/remediations`,n,t),kp(`/vulnerabilities/${e}/threats`,n,t)}return t}({document:e}),u=Ip(n,s);return u.length>0&&(a=!1,u.forEach((e=>{t.push({message:"definition of group id missing",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_5:function(e){const t=[];let a=!0;const n=function({document:e}){const t=[],a=e.product_tree?.product_groups;if(a)for(let e=0;e<a.length;++e){const n=a[e];n.group_id&&t.push({id:n.group_id,name:n.summary??"",instancePath:`/product_tree/product_groups/${e}/group_id`})}return t}({document:e}),s=Tp(n);return s.length>0&&(a=!1,s.forEach((e=>{t.push({message:"duplicate definition product group id",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_6:function(e){const t=[];let a=!0;if(Array.isArray(e.vulnerabilities)){e.vulnerabilities.forEach(((e,n)=>{const s=e.product_status;if(!s)return;const u=[new Set([].concat(Array.isArray(s.first_affected)?s.first_affected:[]).concat(Array.isArray(s.known_affected)?s.known_affected:[]).concat(Array.isArray(s.last_affected)?s.last_affected:[])),new Set(Array.isArray(s.known_not_affected)?s.known_not_affected:[]),new Set([].concat(Array.isArray(s.first_fixed)?s.first_fixed:[]).concat(Array.isArray(s.fixed)?s.fixed:[])),new Set(Array.isArray(s.under_investigation)?s.under_investigation:[])];u.forEach(((e,s)=>{const l=u.slice(s+1);e.forEach((e=>{l.some((t=>t.has(e)))&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/product_status`,message:`product id "${e}" is mentioned in contradicting product status groups`}))}))}))}))}return{isValid:a,errors:t}},mandatoryTest_6_1_7:function(e){const t=[];let a=!0;return Dp(e)&&e.vulnerabilities.forEach(((e,n)=>{const s=new Map;e.scores?.forEach(((e,u)=>{e.products?.forEach(((l,i)=>{const p=s.get(l)??new Set;s.set(l,p),(void 0!==e.cvss_v2?.version&&p.has(e.cvss_v2.version)||void 0!==e.cvss_v3?.version&&p.has(e.cvss_v3.version))&&(a=!1,t.push({message:`product is already included in these cvss-versions: ${Array.from(p.keys()).join(", ")}`,instancePath:`/vulnerabilities/${n}/scores/${u}/products/${i}`})),void 0!==e.cvss_v2?.version&&p.add(e.cvss_v2.version),void 0!==e.cvss_v3?.version&&p.add(e.cvss_v3.version)}))}))})),{errors:t,isValid:a}},mandatoryTest_6_1_8:function(e){const t={errors:[],isValid:!0};if(!Cg(e))return t;for(const[a,n]of e.vulnerabilities?.entries()??[])for(const[e,s]of n.scores?.entries()??[]){if(s.cvss_v2){if(!Og(s.cvss_v2)){t.isValid=!1;for(const n of Og.errors??[])t.errors.push({instancePath:`/vulnerabilities/${a}/scores/${e}/cvss_v2${n.instancePath}`,message:n.message??""})}}if(s.cvss_v3){if(!Ig(s.cvss_v3)){t.isValid=!1;for(const n of Ig.errors??[])t.errors.push({instancePath:`/vulnerabilities/${a}/scores/${e}/cvss_v3${n.instancePath}`,message:n.message??""})}}}return t},mandatoryTest_6_1_9:function(e){const t=[];let a=!0;if(Array.isArray(e.vulnerabilities)){e.vulnerabilities.forEach(((e,n)=>{if(!Array.isArray(e.scores))return;e.scores.forEach(((e,s)=>{if("string"==typeof e.cvss_v2?.vectorString){const u=e.cvss_v2,l=function(e){try{return{success:!0,baseMetricScore:tc.getBaseScore(e),temporalMetricScore:tc.getTemporalScore(e),environmentalMetricScore:ac(e)}}catch(e){return{success:!1,baseMetricScore:-1,temporalMetricScore:-1,environmentalMetricScore:-1}}}(u.vectorString);if(l.success)for(const{score:e,expectedScore:i,name:p}of[{score:u.baseScore,expectedScore:l.baseMetricScore,name:"baseScore"},{score:u.temporalScore,expectedScore:l.temporalMetricScore,name:"temporalScore"},{score:u.environmentalScore,expectedScore:l.environmentalMetricScore,name:"environmentalScore"}])"number"==typeof e&&e!==Number(i)&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v2/${p}`,message:"invalid calculated value"}))}if("string"==typeof e.cvss_v3?.vectorString&&("3.1"===e.cvss_v3.version||"3.0"===e.cvss_v3.version)){const u=e.cvss_v3,l=("3.0"===u.version?fc:yc).calculateCVSSFromVector(u.vectorString);if(l.success){for(const{score:e,expectedScore:i,name:p}of[{score:u.baseScore,expectedScore:l.baseMetricScore,name:"baseScore"},{score:u.temporalScore,expectedScore:l.temporalMetricScore,name:"temporalScore"},{score:u.environmentalScore,expectedScore:l.environmentalMetricScore,name:"environmentalScore"}])"number"==typeof e&&e!==Number(i)&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v3/${p}`,message:"invalid calculated value"}));for(const{severity:e,expectedSeverity:i,name:p}of[{severity:u.baseSeverity,expectedSeverity:l.baseSeverity,name:"baseSeverity"},{severity:u.temporalSeverity,expectedSeverity:l.temporalSeverity,name:"temporalSeverity"},{severity:u.environmentalSeverity,expectedSeverity:l.environmentalSeverity,name:"environmentalSeverity"}])"string"==typeof e&&e!==i.toUpperCase()&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v3/${p}`,message:"invalid calculated value"}))}}}))}))}return{errors:t,isValid:a}}});const eb=new Sg,tb=eb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}},full_product_names:{elements:{additionalProperties:!0,properties:{}}},relationships:{elements:{additionalProperties:!0,properties:{}}}}}},optionalProperties:{document:{additionalProperties:!0,optionalProperties:{category:{type:"string"}}}}}),ab={additionalProperties:!0,properties:{product_id:{type:"string"}}},nb=eb.compile(ab),sb={additionalProperties:!0,optionalProperties:{product:ab,branches:{elements:{additionalProperties:!0,properties:{}}}}},ub=eb.compile(sb),lb={additionalProperties:!0,properties:{full_product_name:ab}},ib=eb.compile(lb);const pb=eb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,properties:{product_groups:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}}}}}}}}}),rb=eb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,properties:{relationships:{elements:{additionalProperties:!0,optionalProperties:{product_reference:{type:"string"},relates_to_product_reference:{type:"string"}}}}}}}}),ob=eb.compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{product_status:{additionalProperties:!0,optionalProperties:{first_affected:{elements:{type:"string"}},first_fixed:{elements:{type:"string"}},fixed:{elements:{type:"string"}},known_affected:{elements:{type:"string"}},known_not_affected:{elements:{type:"string"}},last_affected:{elements:{type:"string"}},recommended:{elements:{type:"string"}},under_investigation:{elements:{type:"string"}}}}}}}}}),gb=eb.compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{remediations:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}}}}},scores:{elements:{additionalProperties:!0,optionalProperties:{products:{elements:{type:"string"}}}}},threats:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}}}}}}}}}});function cb(e,t){let a=!1;return!a&&pb(e)&&(a=e.product_tree.product_groups.some((e=>e.product_ids?.includes(t)??!1))),!a&&rb(e)&&(a=e.product_tree.relationships.some((e=>e.product_reference===t||e.relates_to_product_reference===t))),!a&&ob(e)&&(a=e.vulnerabilities.some((e=>["first_affected","first_fixed","fixed","known_affected","known_not_affected","last_affected","recommended","under_investigation"].some((a=>e.product_status?.[a]?.includes(t)??!1))))),!a&&gb(e)&&(a=e.vulnerabilities.some((e=>e.remediations?.some((e=>e.product_ids?.includes(t)))||e.scores?.some((e=>e.products?.includes(t)))||e.threats?.some((e=>e.product_ids?.includes(t)))||!1))),a}const fb=(new Sg).compile({additionalProperties:!0,optionalProperties:{product_tree:{additionalProperties:!0,optionalProperties:{product_groups:{elements:{additionalProperties:!0,optionalProperties:{group_id:{type:"string"},product_ids:{elements:{type:"string"}}}}}}}},properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{product_status:{additionalProperties:!0,optionalProperties:{first_affected:{elements:{type:"string"}},known_affected:{elements:{type:"string"}},last_affected:{elements:{type:"string"}},under_investigation:{elements:{type:"string"}}}}},optionalProperties:{remediations:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}},group_ids:{elements:{type:"string"}}}}}}}}}});const yb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{product_status:{additionalProperties:!0,optionalProperties:{first_affected:{elements:{type:"string"}},known_affected:{elements:{type:"string"}},last_affected:{elements:{type:"string"}}}}},optionalProperties:{scores:{elements:{additionalProperties:!0,optionalProperties:{products:{elements:{type:"string"}}}}}}}}}});const db=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,properties:{revision_history:{elements:{additionalProperties:!0,properties:{}}}}}}}}});const bb=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,properties:{initial_release_date:{type:"timestamp"},revision_history:{elements:{additionalProperties:!0,properties:{date:{type:"timestamp"}}}}}}}}}});const xb=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,properties:{current_release_date:{type:"timestamp"},revision_history:{elements:{additionalProperties:!0,properties:{date:{type:"timestamp"}}}}}}}}}});const hb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{involvements:{elements:{additionalProperties:!0,properties:{}}}}}}}});const mb=new Sg,_b=mb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}},full_product_names:{elements:{additionalProperties:!0,properties:{}}},relationships:{elements:{additionalProperties:!0,properties:{}}}}}}}),vb=mb.compile({additionalProperties:!0,properties:{product_identification_helper:{additionalProperties:!0,properties:{hashes:{elements:{additionalProperties:!0,properties:{}}}}}}}),Eb=mb.compile({additionalProperties:!0,properties:{full_product_name:{additionalProperties:!0,properties:{product_identification_helper:{additionalProperties:!0,properties:{hashes:{elements:{additionalProperties:!0,properties:{}}}}}}}}}),wb=mb.compile({additionalProperties:!0,optionalProperties:{product:{additionalProperties:!0,properties:{product_identification_helper:{additionalProperties:!0,properties:{hashes:{elements:{additionalProperties:!0,properties:{}}}}}}}}});const Sb=(new Sg).compile({additionalProperties:!0,properties:{file_hashes:{elements:{additionalProperties:!0,properties:{}}}}});function Cb(e,t){const a={warnings:[]};return function(e,t){const a={warnings:[]};if(!_b(e))return a;e.product_tree.full_product_names?.forEach(((e,a)=>{vb(e)&&e.product_identification_helper.hashes.forEach(((e,n)=>{t({path:`/product_tree/full_product_names/${a}/product_identification_helper/hashes/${n}`,hash:e})}))}));const n=(e,a)=>{a.forEach(((a,s)=>{wb(a)&&(a.product?.product_identification_helper.hashes.forEach(((a,n)=>{t({path:`${e}${s}/product_identification_helper/hashes/${n}`,hash:a})})),n(`${e}${s}/branches/`,Array.isArray(a.branches)?a.branches:[]))}))};n("/product_tree/branches/",e.product_tree.branches??[]),e.product_tree.relationships?.forEach(((e,a)=>{Eb(e)&&e.full_product_name.product_identification_helper.hashes.forEach(((e,n)=>{t({path:`/product_tree/relationships/${a}/product_identification_helper/hashes/${n}`,hash:e})}))}))}(e,(({path:e,hash:n})=>{if(!Sb(n))return;const s=function(e){return new Set(e.file_hashes.map((e=>e.algorithm)).filter((e=>"string"==typeof e)))}(n);s.has(t)&&1===s.size&&a.warnings.push({instancePath:e,message:`use of ${t} as the only hash algorithm`})})),a}const Ob=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{distribution:{additionalProperties:!0,properties:{tlp:{additionalProperties:!0,properties:{label:{type:"string"}}}}}}}}});const Ib=new Sg,Nb=Ib.compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{references:{elements:{additionalProperties:!0,properties:{}}},tracking:{additionalProperties:!0,properties:{id:{type:"string"}}}}}}}),Ab=Ib.compile({additionalProperties:!0,properties:{category:{type:"string"},url:{type:"string"}}});const kb=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{lang:{type:"string"}}}}});const Tb=new Sg,Db=Tb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}},full_product_names:{elements:{additionalProperties:!0,properties:{}}},relationships:{elements:{additionalProperties:!0,properties:{}}}}}}}),Rb=Tb.compile({additionalProperties:!0,properties:{full_product_name:{additionalProperties:!0,optionalProperties:{product_identification_helper:{additionalProperties:!0,properties:{}}}}}}),Pb=Tb.compile({additionalProperties:!0,optionalProperties:{product:{additionalProperties:!0,optionalProperties:{product_identification_helper:{additionalProperties:!0,properties:{}}}}}});const Mb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{ids:{elements:{additionalProperties:!0,optionalProperties:{text:{type:"string"}}}}}}}}});const $b=new Sg,Lb=$b.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}}}}}}),Wb=$b.compile({additionalProperties:!0,optionalProperties:{category:{type:"string"},name:{type:"string"}}});const Fb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{product_status:{additionalProperties:!0,optionalProperties:{fixed:{elements:{type:"string"}},first_fixed:{elements:{type:"string"}}}},scores:{elements:{additionalProperties:!0,optionalProperties:{cvss_v3:{additionalProperties:!0,optionalProperties:{environmentalScore:{type:"float64"},vectorString:{type:"string"},version:{type:"string"}}},cvss_v2:{additionalProperties:!0,optionalProperties:{environmentalScore:{type:"float64"},vectorString:{type:"string"},version:{type:"string"}}},products:{elements:{type:"string"}}}}}}}}}});const jb=rc.map((e=>[e[0],e[1],Object.fromEntries(Object.entries(e[2]).map((([e,t])=>[e,t.id])))])),Ub=dc;function zb({version:e,vectorString:t,metrics:a}){const n=new Map(t.split("/").map((e=>{const[t,a]=e.split(":");return[t,a]})).filter((([,e])=>e)));if("3.1"===e||"3.0"===e){const t=Vb({mapping:Ub,metrics:a,vector:n}).map((e=>e[1])),s=("3.1"===e?yc:fc).calculateCVSSFromMetrics(...t);return s.success?Number(s.environmentalMetricScore):null}{const e=function(e){try{return{success:!0,environmentalMetricScore:ac(e)}}catch(e){return{success:!1,environmentalMetricScore:-1}}}(Object.fromEntries(Vb({mapping:jb,metrics:a,vector:n})));return e.success?e.environmentalMetricScore:null}}function Vb({vector:e,metrics:t,mapping:a}){return a.map((a=>{const n=a[1],s=a[0],u=a[2],l=t[s];return[n,e.get(n)??u[l]]}))}const Hb=Mu.compile($u);var qb=Object.freeze({__proto__:null,optionalTest_6_2_1:function(e){const t=[],a={warnings:t};return tb(e)&&"csaf_informational_advisory"!==e.document?.category?(function a({path:n,branches:s}){s.forEach(((s,u)=>{ub(s)&&("string"!=typeof s.product?.product_id||cb(e,s.product.product_id)||t.push({instancePath:`${n}/${u}/product/product_id`,message:"is not referenced"}),Array.isArray(s.branches)&&a({path:`${n}/${u}/branches`,branches:s.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree?.branches??[]}),e.product_tree.full_product_names?.forEach(((t,n)=>{nb(t)&&(cb(e,t.product_id)||a.warnings.push({instancePath:`/product_tree/full_product_names/${n}/product_id`,message:"is not referenced"}))})),e.product_tree.relationships?.forEach(((t,n)=>{ib(t)&&(cb(e,t.full_product_name.product_id)||a.warnings.push({instancePath:`/product_tree/relationships/${n}/full_product_name/product_id`,message:"is not referenced"}))})),a):a},optionalTest_6_2_10:function(e){const t={warnings:[]};return Ob(e)||t.warnings.push({message:"missing tlp label",instancePath:"/document"}),t},optionalTest_6_2_11:function(e){const t={warnings:[]};function a(){t.warnings.push({message:"missing canonical url",instancePath:"/document/references"})}return Nb(e)?(e.document.references.some((t=>Ab(t)&&"self"===t.category&&t.url.startsWith("https://")&&t.url.endsWith(e.document.tracking.id.toLowerCase().replace(/[^+\-a-z0-9]+/g,"_")+".json")))||a(),t):(a(),t)},optionalTest_6_2_12:function(e){const t={warnings:[]};return kb(e)||t.warnings.push({message:"missing document language",instancePath:"/document"}),t},optionalTest_6_2_13:function(e){const t={warnings:[]},a=(e,n,s)=>{if("object"!=typeof s||null==s)return;Array.isArray(s)&&s.forEach(((t,s)=>{a(e,n+"/"+s,t)}));const u=Object.keys(s),l=u.slice().sort(((t,a)=>e.compare(t,a)));JSON.stringify(u)!==JSON.stringify(l)&&t.warnings.push({instancePath:n,message:"not sorted alphabetically"});for(const t of u)a(e,n+"/"+t,s[t])};return a(new Intl.Collator,"",e),t},optionalTest_6_2_14:function(e){const t={warnings:[]};return"string"==typeof e.document?.lang&&Oc(e.document.lang)&&t.warnings.push({instancePath:"/document/lang",message:"use of private language"}),"string"==typeof e.document?.source_lang&&Oc(e.document.source_lang)&&t.warnings.push({instancePath:"/document/source_lang",message:"use of private language"}),t},optionalTest_6_2_15:function(e){const t={warnings:[]},a="i-default";return e.document?.lang===a&&t.warnings.push({instancePath:"/document/lang",message:"use of default language"}),e.document?.source_lang===a&&t.warnings.push({instancePath:"/document/source_lang",message:"use of default language"}),t},optionalTest_6_2_16:function(e){const t={warnings:[]};return Db(e)?(e.product_tree.full_product_names?.forEach(((e,a)=>{e.product_identification_helper||t.warnings.push({instancePath:`/product_tree/full_product_names/${a}`,message:"missing product identification helper"})})),e.product_tree.branches&&function e({path:a,branches:n}){n.forEach(((n,s)=>{Pb(n)&&(n.product&&!n.product.product_identification_helper&&t.warnings.push({instancePath:`${a}/${s}/product`,message:"missing product identification helper"}),Array.isArray(n.branches)&&e({path:`${a}/${s}/branches`,branches:n.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree.branches}),e.product_tree.relationships?.forEach(((e,a)=>{Rb(e)&&(e.full_product_name.product_identification_helper||t.warnings.push({instancePath:`/product_tree/relationships/${a}/full_product_name`,message:"missing product identification helper"}))})),t):t},optionalTest_6_2_17:function(e){const t={warnings:[]};return Mb(e)?(e.vulnerabilities.forEach(((e,a)=>{e.ids?.forEach(((e,n)=>{e.text?.match(/^CVE-[0-9]{4}-[0-9]{4,}$/)&&t.warnings.push({instancePath:`/vulnerabilities/${a}/ids/${n}`,message:"contains CVE"})}))})),t):t},optionalTest_6_2_18:function(e){const t={warnings:[]};return Lb(e)?(e.product_tree.branches&&function e({path:a,branches:n}){n.forEach(((n,s)=>{Wb(n)&&("product_version_range"!==n.category||"string"!=typeof n.name||n.name.match(new RegExp("^vers:[a-z\\.\\-\\+][a-z0-9\\.\\-\\+]*/.+"))||t.warnings.push({instancePath:`${a}/${s}`,message:"product version range without vers"}),Array.isArray(n.branches)&&e({path:`${a}/${s}/branches`,branches:n.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree.branches}),t):t},optionalTest_6_2_19:function(e){const t={warnings:[]};return Fb(e)?(e.vulnerabilities.forEach(((e,a)=>{const n=new Set([...e.product_status?.first_fixed??[],...e.product_status?.fixed??[]]);for(const s of n)e.scores?.forEach(((e,n)=>{if(e.products?.includes(s)){if(e.cvss_v3){const u="3.1"===e.cvss_v3.version||"3.0"===e.cvss_v3.version?zb({version:e.cvss_v3.version,vectorString:e.cvss_v3.vectorString??"",metrics:e.cvss_v3}):null;("number"==typeof e.cvss_v3.environmentalScore&&e.cvss_v3.environmentalScore>0||"number"==typeof u&&u>0||null===u)&&t.warnings.push({instancePath:`/vulnerabilities/${a}/scores/${n}/cvss_v3`,message:`environmental score should be 0 since "${s}" is listed as fixed`})}if(e.cvss_v2){const u=zb({version:"2.0",vectorString:e.cvss_v2.vectorString??"",metrics:e.cvss_v2});("number"==typeof e.cvss_v2.environmentalScore&&e.cvss_v2.environmentalScore>0||"number"==typeof u&&0!==u||null===u)&&t.warnings.push({instancePath:`/vulnerabilities/${a}/scores/${n}/cvss_v2`,message:`environmental score should be 0 since "${s}" is listed as fixed`})}}}))})),t):t},optionalTest_6_2_2:function(e){const t={warnings:[]};return fb(e)?(e.vulnerabilities?.forEach(((a,n)=>{const s=a.product_status;["first_affected","known_affected","last_affected","under_investigation"].forEach((u=>{const l=s[u];l?.forEach(((s,l)=>{const i=a.remediations?.some((t=>t.product_ids?.includes(s)||t.group_ids?.map((t=>e.product_tree?.product_groups?.find((e=>e.group_id===t)))).some((e=>e?.product_ids?.includes(s)))));i||t.warnings.push({message:"missing remediation",instancePath:`/vulnerabilities/${n}/product_status/${u}/${l}`})}))}))})),t):t},optionalTest_6_2_20:function(e){const t={warnings:[]};if(!Hb(e)){const e=Hb.errors?.filter((e=>"additionalProperties"===e.keyword))??[];for(const a of e)t.warnings.push({instancePath:a.instancePath,message:a.message??""})}return t},optionalTest_6_2_3:function(e){const t={warnings:[]};return yb(e)?(e.vulnerabilities?.forEach(((e,a)=>{const n=e.product_status;["first_affected","known_affected","last_affected"].forEach((s=>{const u=n[s];u?.forEach(((n,u)=>{const l=e.scores?.some((e=>e.products?.includes(n)));l||t.warnings.push({message:"missing score",instancePath:`/vulnerabilities/${a}/product_status/${s}/${u}`})}))}))})),t):t},optionalTest_6_2_4:function(e){const t=[],a={warnings:t};return db(e)?(e.document.tracking.revision_history?.forEach(((e,a)=>{const{number:n}=e;"string"==typeof n&&n.includes("+")&&t.push({message:"build metadata in revision history",instancePath:`/document/tracking/revision_history/${a}/number`})})),a):a},optionalTest_6_2_5:function(e){const t=[],a={warnings:t};if(!bb(e))return a;const n=e.document.tracking.revision_history.slice().sort(((e,t)=>gd(e.date,t.date)))[0];return n&&gd(e.document.tracking.initial_release_date,n.date)<0&&t.push({message:"older initial release date than revision history",instancePath:"/document/tracking/initial_release_date"}),a},optionalTest_6_2_6:function(e){const t=[],a={warnings:t};if(!xb(e))return a;const n=e.document.tracking.revision_history.slice().sort(((e,t)=>gd(t.date,e.date)))[0];return n&&gd(e.document.tracking.current_release_date,n.date)<0&&t.push({message:"older current release date than revision history",instancePath:"/document/tracking/current_release_date"}),a},optionalTest_6_2_7:function(e){const t={warnings:[]};return hb(e)?(e.vulnerabilities.forEach(((e,a)=>{e.involvements?.forEach(((e,n)=>{e.date||t.warnings.push({message:"missing date",instancePath:`/vulnerabilities/${a}/involvements/${n}`})}))})),t):t},optionalTest_6_2_8:function(e){return Cb(e,"md5")},optionalTest_6_2_9:function(e){return Cb(e,"sha1")}});Deno.core.ops.op_register_func((async function(e,t,a,o){let n=[];for(const t of e)switch(t){case"schema":n=n.concat(Object.values(Fu));break;case"mandatory":n=n.concat(Object.values(Qd));break;case"optional":n=n.concat(Object.values(qb));break;default:throw new Error(`Unknown validation set: ${t}`)}return n=n.filter((e=>!a.has(e.name)&&(0===o.length||o.some((t=>{const a=e.name.indexOf("Test_"),n=a<0?e.name:e.name.substring(a+5).replaceAll("_",".");return t.endsWith(".*")?n.startsWith(t.slice(0,-1)):n===t||e.name===t}))))),async function(e,t){let a=!0;const n=[];for(const s of e){const e=await s(t),u="boolean"!=typeof e.isValid||e.isValid;n.push({isValid:u,errors:e.errors??[],warnings:e.warnings??[],infos:e.infos??[],name:s.name}),a=a&&u}return{tests:n,isValid:a}}(n,t)}));}();
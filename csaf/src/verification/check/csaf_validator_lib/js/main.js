@@ -18,7 +18,39 @@ function print(msg) {
 
 const DEBUG = false;
 
-async function runValidation(validations, doc, ignore) {
+/**
+ * Get the ID of a test (like `6.1.16`) from its name (like `mandatoryTest_6_1_16`).
+ * @param name the name of the test function.
+ */
+function testId(name) {
+  const idx = name.indexOf("Test_");
+  if (idx < 0) {
+    return name;
+  }
+  return name.substring(idx + 5).replaceAll("_", ".");
+}
+
+/**
+ * Check if a test is selected. A pattern is either a test ID, a test name, or a prefix of
+ * test IDs followed by `.*`.
+ * @param only the selected tests, all tests are selected if empty.
+ * @param test the test function.
+ */
+function isSelected(only, test) {
+  if (only.length === 0) {
+    return true;
+  }
+
+  const id = testId(test.name);
+  return only.some((pattern) => {
+    if (pattern.endsWith(".*")) {
+      return id.startsWith(pattern.slice(0, -1));
+    }
+    return id === pattern || test.name === pattern;
+  });
+}
+
+async function runValidation(validations, doc, ignore, only) {
   let tests = [];
 
   for (const validation of validations) {
@@ -38,7 +70,7 @@ async function runValidation(validations, doc, ignore) {
   }
 
   tests = tests.filter((test) => {
-    return !ignore.has(test.name);
+    return !ignore.has(test.name) && isSelected(only, test);
   });
 
   if (DEBUG) {
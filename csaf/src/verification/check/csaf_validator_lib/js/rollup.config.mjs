@@ -8,14 +8,14 @@ export default {
   output: [
     {
       file: 'bundle.js',
-      format: 'es',
+      format: 'iife',
       plugins: [
         terser()
       ]
     },
     {
       file: 'bundle.debug.js',
-      format: 'es',
+      format: 'iife',
       compact: false,
     }
   ],
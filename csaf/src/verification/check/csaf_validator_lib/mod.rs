@@ -1,39 +1,35 @@
 //! A validator based on the `csaf_validator_lib`
 
 mod deno;
+mod ops;
 
-use crate::verification::check::{Check, CheckError};
+use crate::verification::check::{Check, CheckError, Finding, Severity};
 use anyhow::anyhow;
 use async_trait::async_trait;
 use csaf::Csaf;
 use deno_core::{
-    _ops::RustToV8NoScope, op2, serde_v8, v8, Extension, JsRuntime, OpDecl, PollEventLoopOptions,
-    RuntimeOptions, StaticModuleLoader,
+    _ops::RustToV8NoScope, serde_v8, v8, JsRuntime, PollEventLoopOptions, RuntimeOptions,
 };
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
 use std::collections::HashSet;
 use std::fmt::Debug;
-use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Condvar};
+use std::sync::{Arc, Condvar, OnceLock};
 use std::time::Duration;
 use tokio::sync::Mutex;
-use url::Url;
+use walker_common::utils::hex::Hex;
 
-const MODULE_ID: &str = "internal://bundle.js";
+/// The snapshot of the evaluated bundle, created by the build script
+#[cfg(csaf_validator_lib_snapshot)]
+static SNAPSHOT: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/csaf_validator_lib.snapshot"));
 
-#[derive(Default)]
-pub struct FunctionsState {
-    pub runner_func: Option<v8::Global<v8::Function>>,
-}
-
-#[op2]
-pub fn op_register_func(
-    #[state] function_state: &mut FunctionsState,
-    #[global] f: v8::Global<v8::Function>,
-) {
-    function_state.runner_func.replace(f);
-}
+/// The bundle, evaluated at runtime when the build script couldn't create a snapshot
+#[cfg(all(not(csaf_validator_lib_snapshot), debug_assertions))]
+static BUNDLE: &str = include_str!("js/bundle.debug.js");
+#[cfg(all(not(csaf_validator_lib_snapshot), not(debug_assertions)))]
+static BUNDLE: &str = include_str!("js/bundle.js");
 
 struct InnerCheck {
     runtime: JsRuntime,
@@ -41,40 +37,40 @@ struct InnerCheck {
 }
 
 impl InnerCheck {
+    #[cfg(csaf_validator_lib_snapshot)]
     pub async fn new() -> anyhow::Result<Self> {
-        let specifier = Url::parse(MODULE_ID).expect("internal module ID must parse");
-        #[cfg(debug_assertions)]
-        let code = include_str!("js/bundle.debug.js");
-        #[cfg(not(debug_assertions))]
-        let code = include_str!("js/bundle.js");
-
-        let ext = Extension {
-            ops: std::borrow::Cow::Borrowed(&[{
-                const DECL: OpDecl = op_register_func();
-                DECL
-            }]),
-            op_state_fn: Some(Box::new(|state| {
-                state.put(FunctionsState::default());
-            })),
+        let mut runtime = JsRuntime::new(RuntimeOptions {
+            startup_snapshot: Some(SNAPSHOT),
+            extensions: vec![ops::extension()],
             ..Default::default()
+        });
+
+        let runner = {
+            let context = runtime.main_context();
+            let scope = &mut runtime.handle_scope();
+            let global = context.open(scope).global(scope);
+            let key = v8::String::new(scope, ops::RUNNER)
+                .ok_or_else(|| anyhow!("unable to create runner key"))?;
+            let runner = global
+                .get(scope, key.into())
+                .and_then(|runner| v8::Local::<v8::Function>::try_from(runner).ok())
+                .ok_or_else(|| anyhow!("runner function was not initialized"))?;
+            v8::Global::new(scope, runner)
         };
 
+        Ok(InnerCheck { runtime, runner })
+    }
+
+    #[cfg(not(csaf_validator_lib_snapshot))]
+    pub async fn new() -> anyhow::Result<Self> {
         let mut runtime = JsRuntime::new(RuntimeOptions {
-            module_loader: Some(Rc::new(StaticModuleLoader::with(specifier, code))),
-            extensions: vec![ext],
+            extensions: vec![ops::extension()],
             ..Default::default()
         });
 
-        let module = Url::parse(MODULE_ID)?;
-        let mod_id = runtime.load_main_es_module(&module).await?;
-        let result = runtime.mod_evaluate(mod_id);
-        runtime
-            .run_event_loop(PollEventLoopOptions::default())
-            .await?;
-
-        result.await?;
+        runtime.execute_script("internal://bundle.js", BUNDLE)?;
 
-        let state: FunctionsState = runtime.op_state().borrow_mut().take();
+        let state: ops::FunctionsState = runtime.op_state().borrow_mut().take();
         let runner = state
             .runner_func
             .ok_or_else(|| anyhow!("runner function was not initialized"))?;
@@ -87,6 +83,7 @@ impl InnerCheck {
         doc: S,
         validations: &[ValidationSet],
         ignore: &HashSet<String>,
+        tests: &[String],
         timeout: Option<Duration>,
     ) -> anyhow::Result<Option<D>>
     where
@@ -119,7 +116,12 @@ impl InnerCheck {
                 v8::Global::new(scope, set.to_v8())
             };
 
-            [validations, doc, ignore]
+            let tests = {
+                let tests = serde_v8::to_v8(scope, tests)?;
+                v8::Global::new(scope, tests)
+            };
+
+            [validations, doc, ignore, tests]
         };
 
         let cancelled = Arc::new(AtomicBool::new(false));
@@ -218,6 +220,12 @@ pub enum ValidationSet {
     Optional,
 }
 
+const ALL_VALIDATIONS: [ValidationSet; 3] = [
+    ValidationSet::Schema,
+    ValidationSet::Mandatory,
+    ValidationSet::Optional,
+];
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Profile {
     Schema,
@@ -230,6 +238,7 @@ pub struct CsafValidatorLib {
     validations: Vec<ValidationSet>,
     timeout: Option<Duration>,
     ignore: HashSet<String>,
+    tests: Vec<String>,
 }
 
 impl CsafValidatorLib {
@@ -250,6 +259,7 @@ impl CsafValidatorLib {
             runtime,
             validations,
             ignore: Default::default(),
+            tests: Default::default(),
             timeout: None,
         }
     }
@@ -284,11 +294,53 @@ impl CsafValidatorLib {
             .extend(ignore.into_iter().map(|s| s.to_string()));
         self
     }
+
+    /// Only run the selected tests, instead of the tests of the profile.
+    ///
+    /// A test is selected by its ID (like `6.1.16`), its name (like `csaf_2_0`), or a prefix of its
+    /// ID followed by `.*` (like `6.1.27.*`). Tests of all profiles can be selected. If no tests
+    /// are selected, the tests of the profile are run.
+    pub fn tests(mut self, tests: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.tests = tests.into_iter().map(|s| s.to_string()).collect();
+        self
+    }
 }
 
 #[async_trait(? Send)]
 impl Check for CsafValidatorLib {
     async fn check(&self, csaf: &Csaf) -> anyhow::Result<Vec<CheckError>> {
+        Ok(self
+            .findings(csaf)
+            .await?
+            .into_iter()
+            .filter(|finding| finding.severity == Severity::Error)
+            .map(|finding| finding.message)
+            .collect())
+    }
+
+    fn version(&self) -> Cow<'static, str> {
+        static DIGEST: OnceLock<String> = OnceLock::new();
+
+        // the snapshot changes with the bundle
+        #[cfg(csaf_validator_lib_snapshot)]
+        let source = SNAPSHOT;
+        #[cfg(not(csaf_validator_lib_snapshot))]
+        let source = BUNDLE.as_bytes();
+
+        let bundle = DIGEST.get_or_init(|| Hex(&Sha256::digest(source)).to_lower());
+
+        let mut ignore = self.ignore.iter().collect::<Vec<_>>();
+        ignore.sort_unstable();
+
+        Cow::Owned(format!(
+            "{version}/{bundle}/{validations:?}/{ignore:?}/{tests:?}",
+            version = env!("CARGO_PKG_VERSION"),
+            validations = self.validations,
+            tests = self.tests,
+        ))
+    }
+
+    async fn findings(&self, csaf: &Csaf) -> anyhow::Result<Vec<Finding>> {
         let mut inner = {
             let mut inner_lock = self.runtime.lock().await;
             match inner_lock.pop() {
@@ -297,14 +349,20 @@ impl Check for CsafValidatorLib {
             }
         };
 
+        // selected tests may come from any profile
+        let validations = match self.tests.is_empty() {
+            true => self.validations.as_slice(),
+            false => ALL_VALIDATIONS.as_slice(),
+        };
+
         let test_result = inner
-            .validate::<_, TestResult>(csaf, &self.validations, &self.ignore, self.timeout)
+            .validate::<_, TestResult>(csaf, validations, &self.ignore, &self.tests, self.timeout)
             .await?;
 
         log::trace!("Result: {test_result:?}");
 
         let Some(test_result) = test_result else {
-            return Ok(vec!["check timed out".into()]);
+            return Ok(vec![Finding::error("check timed out")]);
         };
 
         // not timed out, not failed, we can re-use it
@@ -313,22 +371,12 @@ impl Check for CsafValidatorLib {
         let mut result = vec![];
 
         for entry in test_result.tests {
-            // we currently only report "failed" tests
-            if entry.is_valid {
-                continue;
-            }
-
-            for error in entry.errors {
-                result.push(
-                    format!(
-                        "{name} ({instance_path}): {message}",
-                        name = entry.name,
-                        instance_path = error.instance_path,
-                        message = error.message
-                    )
-                    .into(),
-                );
+            // errors are only relevant for "failed" tests
+            if !entry.is_valid {
+                result.extend(entry.findings(Severity::Error, &entry.errors));
             }
+            result.extend(entry.findings(Severity::Warning, &entry.warnings));
+            result.extend(entry.findings(Severity::Info, &entry.infos));
         }
 
         Ok(result)
@@ -354,6 +402,27 @@ struct Entry {
     pub infos: Vec<TestResultEntry>,
 }
 
+impl Entry {
+    fn findings<'a>(
+        &'a self,
+        severity: Severity,
+        entries: &'a [TestResultEntry],
+    ) -> impl Iterator<Item = Finding> + 'a {
+        entries.iter().map(move |entry| {
+            Finding::new(
+                severity,
+                format!(
+                    "{name} ({instance_path}): {message}",
+                    name = self.name,
+                    instance_path = entry.instance_path,
+                    message = entry.message
+                ),
+            )
+            .with_path(&entry.instance_path)
+        })
+    }
+}
+
 #[derive(Clone, Debug, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct TestResultEntry {
@@ -366,9 +435,9 @@ mod test {
     use super::*;
     use csaf::document::*;
     use log::LevelFilter;
-    use std::borrow::Cow;
     use std::io::BufReader;
     use std::path::Path;
+    use url::Url;
 
     fn load_file(path: impl AsRef<Path>) -> Csaf {
         serde_json::from_reader(BufReader::new(
@@ -434,6 +503,29 @@ mod test {
         assert!(!result.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_findings() {
+        let _ = env_logger::builder()
+            .filter_level(LevelFilter::Info)
+            .try_init();
+
+        let check = CsafValidatorLib::new(Profile::Optional);
+
+        let findings = check.findings(&invalid_doc()).await;
+        log::info!("Findings: {findings:#?}");
+        let findings = findings.expect("must succeed");
+
+        let errors = findings
+            .iter()
+            .filter(|finding| finding.severity == Severity::Error)
+            .map(|finding| finding.message.clone())
+            .collect::<Vec<_>>();
+        assert!(!errors.is_empty());
+
+        let result = check.check(&invalid_doc()).await.expect("must succeed");
+        assert_eq!(result, errors);
+    }
+
     /// run twice to ensure we can re-use the runtime
     #[tokio::test]
     async fn test_twice() {
@@ -522,6 +614,24 @@ mod test {
         assert!(result.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_selected() {
+        let _ = env_logger::builder()
+            .filter_level(LevelFilter::Info)
+            .try_init();
+
+        let check = CsafValidatorLib::new(Profile::Optional).tests(["csaf_2_0", "6.1.27.*"]);
+
+        let result = check.check(&invalid_doc()).await;
+        log::info!("Result: {result:#?}");
+        let result = result.expect("must succeed");
+
+        assert!(!result.is_empty());
+        assert!(result.iter().all(|error| {
+            error.starts_with("csaf_2_0 ") || error.starts_with("mandatoryTest_6_1_27_")
+        }));
+    }
+
     #[tokio::test]
     async fn test_ignore() {
         let _ = env_logger::builder()
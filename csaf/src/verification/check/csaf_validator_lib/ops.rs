@@ -0,0 +1,35 @@
+//! Ops provided to the bundle
+//!
+//! This module is also used by the build script, creating the runtime snapshot. Both runtimes
+//! must register the same extension.
+
+use deno_core::{op2, v8, Extension, OpDecl};
+
+/// The name of the global property holding the runner function in the snapshot
+pub const RUNNER: &str = "__csafValidatorRunner";
+
+#[derive(Default)]
+pub struct FunctionsState {
+    pub runner_func: Option<v8::Global<v8::Function>>,
+}
+
+#[op2]
+pub fn op_register_func(
+    #[state] function_state: &mut FunctionsState,
+    #[global] f: v8::Global<v8::Function>,
+) {
+    function_state.runner_func.replace(f);
+}
+
+pub fn extension() -> Extension {
+    Extension {
+        ops: std::borrow::Cow::Borrowed(&[{
+            const DECL: OpDecl = op_register_func();
+            DECL
+        }]),
+        op_state_fn: Some(Box::new(|state| {
+            state.put(FunctionsState::default());
+        })),
+        ..Default::default()
+    }
+}
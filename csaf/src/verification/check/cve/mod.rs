@@ -0,0 +1,317 @@
+//! Cross-referencing CVE IDs with the CVE list
+
+use crate::verification::check::{Check, CheckError, Finding, Severity};
+use anyhow::Context;
+use async_trait::async_trait;
+use csaf::Csaf;
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use time::OffsetDateTime;
+use url::Url;
+use walkdir::WalkDir;
+use walker_common::utils::hex::Hex;
+
+/// The default endpoint of the NVD CVE API
+pub const NVD_API: &str = "https://services.nvd.nist.gov/rest/json/cves/2.0";
+
+/// Check that the CVE IDs of the vulnerabilities are well-formed, and known to the CVE list.
+///
+/// Unknown IDs are reported as warnings, as they may be typos, or not (yet) published.
+pub struct CheckCveIds {
+    source: Source,
+    /// Identifies the state of the source, for caching the results of the check
+    fingerprint: String,
+}
+
+enum Source {
+    /// A local copy of the CVE list
+    Dataset { ids: HashSet<String> },
+    /// Looking up IDs using the NVD API
+    Nvd(Nvd),
+}
+
+struct Nvd {
+    client: reqwest::Client,
+    url: Url,
+    api_key: Option<String>,
+    /// The file caching the results of lookups
+    cache: Option<PathBuf>,
+    /// If an ID is known, by ID
+    known: Mutex<BTreeMap<String, bool>>,
+}
+
+impl CheckCveIds {
+    /// Use a local copy of the CVE list, allowing to work offline.
+    ///
+    /// This can either be a directory (like a checkout of the `cvelistV5` repository), containing
+    /// a `CVE-*.json` file for each record, or a file listing one ID per line.
+    pub fn from_dataset(path: &Path) -> anyhow::Result<Self> {
+        let ids = match path.is_dir() {
+            true => scan_dataset(path)?,
+            false => std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read CVE list: {}", path.display()))?
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_uppercase)
+                .collect(),
+        };
+
+        log::info!("Loaded {} CVE IDs from: {}", ids.len(), path.display());
+
+        Ok(Self::from_ids(ids))
+    }
+
+    fn from_ids(ids: HashSet<String>) -> Self {
+        let mut sorted = ids.iter().collect::<Vec<_>>();
+        sorted.sort_unstable();
+
+        let mut digest = Sha256::new();
+        for id in sorted {
+            digest.update(id);
+            digest.update(b"\n");
+        }
+
+        Self {
+            fingerprint: Hex(&digest.finalize()).to_lower(),
+            source: Source::Dataset { ids },
+        }
+    }
+
+    /// Look up IDs using the NVD API, caching the results in a file, if provided.
+    pub fn from_nvd(cache: Option<PathBuf>, api_key: Option<String>) -> anyhow::Result<Self> {
+        let known = match &cache {
+            Some(cache) => match std::fs::read(cache) {
+                Ok(data) => serde_json::from_slice(&data)
+                    .with_context(|| format!("Failed to parse CVE cache: {}", cache.display()))?,
+                Err(err) if err.kind() == ErrorKind::NotFound => Default::default(),
+                Err(err) => {
+                    return Err(err)
+                        .with_context(|| format!("Failed to read CVE cache: {}", cache.display()))
+                }
+            },
+            None => Default::default(),
+        };
+
+        Ok(Self {
+            // the NVD changes over time, re-check at least once per day
+            fingerprint: format!("nvd-{}", OffsetDateTime::now_utc().date()),
+            source: Source::Nvd(Nvd {
+                client: reqwest::Client::new(),
+                url: Url::parse(NVD_API)?,
+                api_key,
+                cache,
+                known: Mutex::new(known),
+            }),
+        })
+    }
+}
+
+/// Collect the IDs from the file names of the records of a CVE list checkout.
+fn scan_dataset(path: &Path) -> anyhow::Result<HashSet<String>> {
+    let mut ids = HashSet::new();
+
+    for entry in WalkDir::new(path) {
+        let entry =
+            entry.with_context(|| format!("Failed to scan CVE list: {}", path.display()))?;
+        let Some(name) = entry.file_name().to_str() else {
+            continue;
+        };
+        if let Some(id) = name.strip_suffix(".json").filter(|id| is_cve_id(id)) {
+            ids.insert(id.to_string());
+        }
+    }
+
+    Ok(ids)
+}
+
+/// Check if the value has the format of a CVE ID, like `CVE-2024-1234`.
+fn is_cve_id(id: &str) -> bool {
+    let mut parts = id.splitn(3, '-');
+    let (Some("CVE"), Some(year), Some(number)) = (parts.next(), parts.next(), parts.next()) else {
+        return false;
+    };
+
+    year.len() == 4
+        && year.bytes().all(|b| b.is_ascii_digit())
+        && number.len() >= 4
+        && number.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NvdResponse {
+    total_results: usize,
+}
+
+impl Nvd {
+    async fn is_known(&self, id: &str) -> anyhow::Result<bool> {
+        if let Some(known) = self.known().get(id) {
+            return Ok(*known);
+        }
+
+        let mut request = self.client.get(self.url.clone()).query(&[("cveId", id)]);
+        if let Some(api_key) = &self.api_key {
+            request = request.header("apiKey", api_key);
+        }
+
+        let response = request.send().await?;
+        let known = match response.status() {
+            StatusCode::NOT_FOUND => false,
+            _ => {
+                response
+                    .error_for_status()?
+                    .json::<NvdResponse>()
+                    .await?
+                    .total_results
+                    > 0
+            }
+        };
+
+        self.known().insert(id.to_string(), known);
+
+        Ok(known)
+    }
+
+    fn known(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, bool>> {
+        self.known.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn store(&self) -> anyhow::Result<()> {
+        let Some(cache) = &self.cache else {
+            return Ok(());
+        };
+
+        let data = serde_json::to_vec(&*self.known())?;
+        std::fs::write(cache, data)
+            .with_context(|| format!("Failed to write CVE cache: {}", cache.display()))
+    }
+}
+
+#[async_trait(?Send)]
+impl Check for CheckCveIds {
+    async fn check(&self, csaf: &Csaf) -> anyhow::Result<Vec<CheckError>> {
+        Ok(self
+            .findings(csaf)
+            .await?
+            .into_iter()
+            .filter(|finding| finding.severity == Severity::Error)
+            .map(|finding| finding.message)
+            .collect())
+    }
+
+    async fn findings(&self, csaf: &Csaf) -> anyhow::Result<Vec<Finding>> {
+        let mut result = vec![];
+        let mut looked_up = false;
+
+        for (n, vuln) in csaf.vulnerabilities.iter().flatten().enumerate() {
+            let Some(cve) = &vuln.cve else {
+                continue;
+            };
+            let path = format!("/vulnerabilities/{n}/cve");
+
+            if !is_cve_id(cve) {
+                result.push(Finding::error(format!("Malformed CVE ID: {cve}")).with_path(path));
+                continue;
+            }
+
+            let known = match &self.source {
+                Source::Dataset { ids } => ids.contains(cve),
+                Source::Nvd(nvd) => {
+                    looked_up = true;
+                    match nvd.is_known(cve).await {
+                        Ok(known) => known,
+                        Err(err) => {
+                            result.push(
+                                Finding::info(format!("Unable to look up {cve}: {err}"))
+                                    .with_path(path),
+                            );
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            if !known {
+                result.push(
+                    Finding::warning(format!("Unknown CVE ID: {cve}, possibly a typo"))
+                        .with_path(path),
+                );
+            }
+        }
+
+        if let (true, Source::Nvd(nvd)) = (looked_up, &self.source) {
+            nvd.store()?;
+        }
+
+        Ok(result)
+    }
+
+    fn version(&self) -> Cow<'static, str> {
+        Cow::Owned(format!(
+            "{}+{}",
+            env!("CARGO_PKG_VERSION"),
+            self.fingerprint
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_cve_id() {
+        assert!(is_cve_id("CVE-2024-1234"));
+        assert!(is_cve_id("CVE-2021-4428812"));
+
+        assert!(!is_cve_id("CVE-2024-123"));
+        assert!(!is_cve_id("CVE-24-1234"));
+        assert!(!is_cve_id("cve-2024-1234"));
+        assert!(!is_cve_id("CVE-2024-12a4"));
+        assert!(!is_cve_id("CVE 2024 1234"));
+    }
+
+    #[tokio::test]
+    async fn test_dataset() {
+        // one of the IDs is missing
+        let check = CheckCveIds::from_ids(HashSet::from([
+            "CVE-2020-0543".to_string(),
+            "CVE-2020-0548".to_string(),
+            "CVE-2020-0549".to_string(),
+            "CVE-2020-8695".to_string(),
+            "CVE-2020-8696".to_string(),
+            "CVE-2020-8698".to_string(),
+            "CVE-2020-24489".to_string(),
+            "CVE-2020-24511".to_string(),
+        ]));
+
+        let mut csaf: Csaf =
+            serde_json::from_str(include_str!("../../../../test-data/rhsa-2021_3029.json"))
+                .expect("example data must parse");
+        if let Some(vuln) = csaf.vulnerabilities.iter_mut().flatten().next() {
+            vuln.cve = Some("CVE-2020-543".to_string());
+        }
+
+        let findings = check.findings(&csaf).await.expect("check must succeed");
+
+        assert_eq!(
+            findings,
+            vec![
+                Finding::error("Malformed CVE ID: CVE-2020-543")
+                    .with_path("/vulnerabilities/0/cve"),
+                Finding::warning("Unknown CVE ID: CVE-2020-24512, possibly a typo")
+                    .with_path("/vulnerabilities/8/cve"),
+            ]
+        );
+        assert_eq!(
+            check.check(&csaf).await.expect("check must succeed"),
+            vec![CheckError::from("Malformed CVE ID: CVE-2020-543")]
+        );
+    }
+}
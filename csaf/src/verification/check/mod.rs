@@ -1,6 +1,10 @@
 use crate::verification::check::{
     base::{check_csaf_base, check_csaf_document_tracking_revision_history},
     informational_advisory::check_vulnerabilities_not_exits,
+    product_tree::{
+        check_product_tree_duplicate_ids, check_product_tree_references,
+        check_product_tree_relationship_cycles,
+    },
     security_incident_response::{check_csaf_document_notes, check_csaf_document_references},
     vex::{
         check_all_products_v11ies_exits_in_product_tree,
@@ -12,9 +16,12 @@ use crate::verification::check::{
 use async_trait::async_trait;
 use csaf::Csaf;
 use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
 
 pub mod base;
+pub mod cve;
 pub mod informational_advisory;
+pub mod product_tree;
 pub mod security_advisory;
 pub mod security_incident_response;
 pub mod vex;
@@ -24,10 +31,95 @@ pub mod csaf_validator_lib;
 
 pub type CheckError = Cow<'static, str>;
 
+/// The severity of a [`Finding`]
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Info => f.write_str("info"),
+            Self::Warning => f.write_str("warning"),
+            Self::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// A finding of a check, tagged with its severity
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct Finding {
+    pub severity: Severity,
+    pub message: CheckError,
+    /// The JSON pointer to the offending part of the document, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl Finding {
+    pub fn new(severity: Severity, message: impl Into<CheckError>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            path: None,
+        }
+    }
+
+    /// Set the JSON pointer to the offending part of the document.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    pub fn error(message: impl Into<CheckError>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    pub fn warning(message: impl Into<CheckError>) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    pub fn info(message: impl Into<CheckError>) -> Self {
+        Self::new(Severity::Info, message)
+    }
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
 #[async_trait(?Send)]
 pub trait Check {
     /// Perform a check on a CSAF document
     async fn check(&self, csaf: &Csaf) -> anyhow::Result<Vec<CheckError>>;
+
+    /// Perform a check on a CSAF document, also reporting warnings and infos.
+    ///
+    /// By default, this reports the outcome of [`Check::check`] as errors.
+    async fn findings(&self, csaf: &Csaf) -> anyhow::Result<Vec<Finding>> {
+        Ok(self
+            .check(csaf)
+            .await?
+            .into_iter()
+            .map(Finding::error)
+            .collect())
+    }
+
+    /// The version of the check, including its configuration.
+    ///
+    /// Cached results of a check are only re-used if the version didn't change. By default, this
+    /// is the version of this crate.
+    fn version(&self) -> Cow<'static, str> {
+        Cow::Borrowed(env!("CARGO_PKG_VERSION"))
+    }
 }
 
 /// Implementation to allow a simple function style check
@@ -104,5 +196,17 @@ pub fn init_verifying_visitor() -> Vec<(&'static str, Box<dyn Check>)> {
             "check_branches_relationships_product_match",
             Box::new(check_branches_relationships_product_match),
         ),
+        (
+            "check_product_tree_references",
+            Box::new(check_product_tree_references),
+        ),
+        (
+            "check_product_tree_duplicate_ids",
+            Box::new(check_product_tree_duplicate_ids),
+        ),
+        (
+            "check_product_tree_relationship_cycles",
+            Box::new(check_product_tree_relationship_cycles),
+        ),
     ]
 }
@@ -0,0 +1,329 @@
+//! Consistency checks of the product tree, independent of the document category
+
+use crate::verification::check::{CheckError, Checking};
+use csaf::{
+    definitions::{BranchesT, ProductIdT},
+    Csaf,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Collect all product IDs defined by the product tree, with the location of the definition.
+fn defined_product_ids(csaf: &Csaf) -> Vec<(&str, &'static str)> {
+    fn branches<'a>(branches: &'a BranchesT, ids: &mut Vec<(&'a str, &'static str)>) {
+        for branch in &branches.0 {
+            if let Some(product) = &branch.product {
+                ids.push((&product.product_id.0, "branches"));
+            }
+            if let Some(bs) = &branch.branches {
+                branches(bs, ids);
+            }
+        }
+    }
+
+    let mut ids = vec![];
+
+    let Some(product_tree) = &csaf.product_tree else {
+        return ids;
+    };
+
+    if let Some(bs) = &product_tree.branches {
+        branches(bs, &mut ids);
+    }
+    for product in product_tree.full_product_names.iter().flatten() {
+        ids.push((&product.product_id.0, "full_product_names"));
+    }
+    for relationship in product_tree.relationships.iter().flatten() {
+        ids.push((
+            &relationship.full_product_name.product_id.0,
+            "relationships",
+        ));
+    }
+
+    ids
+}
+
+/// Collect all product IDs referenced in the document, with the location of the reference.
+fn referenced_product_ids(csaf: &Csaf) -> Vec<(&ProductIdT, String)> {
+    let mut refs = vec![];
+
+    if let Some(product_tree) = &csaf.product_tree {
+        for (n, relationship) in product_tree.relationships.iter().flatten().enumerate() {
+            let location = format!("/product_tree/relationships/{n}");
+            refs.push((&relationship.product_reference, location.clone()));
+            refs.push((&relationship.relates_to_product_reference, location));
+        }
+        for (n, group) in product_tree.product_groups.iter().flatten().enumerate() {
+            for id in &group.product_ids {
+                refs.push((id, format!("/product_tree/product_groups/{n}")));
+            }
+        }
+    }
+
+    for (n, vuln) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        if let Some(status) = &vuln.product_status {
+            for (name, ids) in [
+                ("first_affected", &status.first_affected),
+                ("first_fixed", &status.first_fixed),
+                ("fixed", &status.fixed),
+                ("known_affected", &status.known_affected),
+                ("known_not_affected", &status.known_not_affected),
+                ("last_affected", &status.last_affected),
+                ("recommended", &status.recommended),
+                ("under_investigation", &status.under_investigation),
+            ] {
+                for id in ids.iter().flatten() {
+                    refs.push((id, format!("/vulnerabilities/{n}/product_status/{name}")));
+                }
+            }
+        }
+        for (m, remediation) in vuln.remediations.iter().flatten().enumerate() {
+            for id in remediation.product_ids.iter().flatten() {
+                refs.push((id, format!("/vulnerabilities/{n}/remediations/{m}")));
+            }
+        }
+        for (m, score) in vuln.scores.iter().flatten().enumerate() {
+            for id in &score.products {
+                refs.push((id, format!("/vulnerabilities/{n}/scores/{m}")));
+            }
+        }
+        for (m, threat) in vuln.threats.iter().flatten().enumerate() {
+            for id in threat.product_ids.iter().flatten() {
+                refs.push((id, format!("/vulnerabilities/{n}/threats/{m}")));
+            }
+        }
+        for (m, flag) in vuln.flags.iter().flatten().enumerate() {
+            for id in flag.product_ids.iter().flatten() {
+                refs.push((id, format!("/vulnerabilities/{n}/flags/{m}")));
+            }
+        }
+    }
+
+    refs
+}
+
+/// All referenced product IDs must be defined in the product tree
+pub fn check_product_tree_references(csaf: &Csaf) -> Vec<CheckError> {
+    let defined = defined_product_ids(csaf)
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect::<HashSet<_>>();
+
+    let mut checking = Checking::new();
+    for (id, location) in referenced_product_ids(csaf) {
+        checking = checking.require(
+            format!(
+                "The product {:?}, referenced at {location}, is not defined in the product tree",
+                id.0
+            ),
+            defined.contains(id.0.as_str()),
+        );
+    }
+    checking.done()
+}
+
+/// Product IDs must only be defined once
+pub fn check_product_tree_duplicate_ids(csaf: &Csaf) -> Vec<CheckError> {
+    let mut definitions = BTreeMap::<_, Vec<_>>::new();
+    for (id, location) in defined_product_ids(csaf) {
+        definitions.entry(id).or_default().push(location);
+    }
+
+    let mut checking = Checking::new();
+    for (id, locations) in definitions {
+        let count = locations.len();
+        let locations = locations.into_iter().collect::<BTreeSet<_>>();
+        checking = checking.require(
+            format!(
+                "The product {id:?} is defined {count} times (in: {})",
+                locations.into_iter().collect::<Vec<_>>().join(", ")
+            ),
+            count <= 1,
+        );
+    }
+
+    let mut groups = HashSet::new();
+    if let Some(product_tree) = &csaf.product_tree {
+        for group in product_tree.product_groups.iter().flatten() {
+            checking = checking.require(
+                format!(
+                    "The product group {:?} is defined more than once",
+                    group.group_id.0
+                ),
+                groups.insert(&group.group_id.0),
+            );
+        }
+    }
+
+    checking.done()
+}
+
+/// Relationships must not form a cycle, a product must not (indirectly) be built from itself
+pub fn check_product_tree_relationship_cycles(csaf: &Csaf) -> Vec<CheckError> {
+    let Some(relationships) = csaf
+        .product_tree
+        .as_ref()
+        .and_then(|product_tree| product_tree.relationships.as_ref())
+    else {
+        return vec![];
+    };
+
+    // the products a product is composed of
+    let mut edges = HashMap::<&str, Vec<&str>>::new();
+    for relationship in relationships {
+        edges
+            .entry(&relationship.full_product_name.product_id.0)
+            .or_default()
+            .extend([
+                relationship.product_reference.0.as_str(),
+                relationship.relates_to_product_reference.0.as_str(),
+            ]);
+    }
+
+    let mut nodes = edges.keys().copied().collect::<Vec<_>>();
+    nodes.sort_unstable();
+
+    let mut cycles = Cycles {
+        edges: &edges,
+        done: HashSet::new(),
+        path: vec![],
+        result: vec![],
+    };
+    for node in nodes {
+        cycles.visit(node);
+    }
+
+    cycles.result
+}
+
+/// A depth first search, recording each cycle once
+struct Cycles<'a> {
+    edges: &'a HashMap<&'a str, Vec<&'a str>>,
+    /// Nodes which were fully processed
+    done: HashSet<&'a str>,
+    /// The current path
+    path: Vec<&'a str>,
+    result: Vec<CheckError>,
+}
+
+impl<'a> Cycles<'a> {
+    fn visit(&mut self, node: &'a str) {
+        if self.done.contains(node) {
+            return;
+        }
+
+        if let Some(start) = self.path.iter().position(|n| *n == node) {
+            let mut cycle = self.path[start..].to_vec();
+            cycle.push(node);
+            self.result.push(
+                format!(
+                    "The relationships of the product tree form a cycle: {}",
+                    cycle.join(" -> ")
+                )
+                .into(),
+            );
+            return;
+        }
+
+        self.path.push(node);
+        for next in self.edges.get(node).into_iter().flatten() {
+            self.visit(next);
+        }
+        self.path.pop();
+
+        self.done.insert(node);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn document(f: impl FnOnce(&mut Value)) -> Csaf {
+        let mut value: Value =
+            serde_json::from_str(include_str!("../../../../test-data/rhsa-2021_3029.json"))
+                .expect("example data must parse");
+        f(&mut value);
+        serde_json::from_value(value).expect("modified example data must parse")
+    }
+
+    fn add_relationship(value: &mut Value, id: &str, product: &str, relates_to: &str) {
+        value["product_tree"]["relationships"]
+            .as_array_mut()
+            .expect("example data must have relationships")
+            .push(json!({
+                "category": "default_component_of",
+                "full_product_name": {
+                    "name": id,
+                    "product_id": id,
+                },
+                "product_reference": product,
+                "relates_to_product_reference": relates_to,
+            }));
+    }
+
+    #[test]
+    fn test_valid() {
+        let csaf = document(|_| {});
+
+        assert!(check_product_tree_duplicate_ids(&csaf).is_empty());
+        assert!(check_product_tree_relationship_cycles(&csaf).is_empty());
+    }
+
+    /// The example data already references an unknown product
+    #[test]
+    fn test_dangling_reference() {
+        let csaf = document(|value| {
+            value["vulnerabilities"][0]["product_status"]["fixed"]
+                .as_array_mut()
+                .expect("example data must have fixed products")
+                .push(json!("unknown"));
+        });
+
+        assert_eq!(
+            check_product_tree_references(&csaf),
+            vec![
+                CheckError::from(
+                    r#"The product "unknown", referenced at /vulnerabilities/0/product_status/fixed, is not defined in the product tree"#
+                ),
+                CheckError::from(
+                    r#"The product "notexits-7ComputeNode-7.7.EUS:microcode_ctl-2:2.1-53.18.el7_7.src", referenced at /vulnerabilities/5/product_status/fixed, is not defined in the product tree"#
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_id() {
+        let csaf = document(|value| {
+            add_relationship(
+                value,
+                "7ComputeNode-7.7.EUS",
+                "microcode_ctl-2:2.1-53.18.el7_7.src",
+                "7ComputeNode-7.7.EUS",
+            );
+        });
+
+        assert_eq!(
+            check_product_tree_duplicate_ids(&csaf),
+            vec![CheckError::from(
+                r#"The product "7ComputeNode-7.7.EUS" is defined 2 times (in: branches, relationships)"#
+            )]
+        );
+    }
+
+    #[test]
+    fn test_cycle() {
+        let csaf = document(|value| {
+            add_relationship(value, "a", "b", "7ComputeNode-7.7.EUS");
+            add_relationship(value, "b", "a", "7ComputeNode-7.7.EUS");
+        });
+
+        assert_eq!(
+            check_product_tree_relationship_cycles(&csaf),
+            vec![CheckError::from(
+                "The relationships of the product tree form a cycle: a -> b -> a"
+            )]
+        );
+    }
+}
@@ -8,19 +8,25 @@ use crate::{
         AsRetrieved, RetrievalContext, RetrievalError, RetrievedAdvisory, RetrievedVisitor,
     },
     validation::{ValidatedAdvisory, ValidatedVisitor, ValidationContext, ValidationError},
-    verification::check::{Check, CheckError},
+    verification::{
+        cache::CheckCache,
+        check::{Check, Finding, Severity},
+    },
 };
 use csaf::Csaf;
 use serde::de::Error as _;
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display};
 use std::future::Future;
 use std::hash::Hash;
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
 use url::Url;
-use walker_common::utils::url::Urlify;
+use walker_common::utils::{hex::Hex, url::Urlify};
 
+pub mod cache;
 pub mod check;
 
 #[derive(Debug)]
@@ -31,7 +37,9 @@ where
 {
     pub advisory: A,
     pub csaf: Csaf,
-    pub failures: HashMap<I, Vec<CheckError>>,
+    pub failures: HashMap<I, Vec<Finding>>,
+    /// Warnings and infos, which don't fail a check
+    pub notices: HashMap<I, Vec<Finding>>,
     pub successes: HashSet<I>,
 }
 
@@ -145,6 +153,8 @@ where
 {
     visitor: V,
     checks: Vec<(I, Box<dyn Check>)>,
+    /// The cache, and the fingerprint of the checks
+    cache: Option<(Arc<CheckCache>, String)>,
     _marker: PhantomData<(A, E)>,
 }
 
@@ -159,6 +169,7 @@ where
         Self {
             visitor,
             checks: vec![],
+            cache: None,
             _marker: Default::default(),
         }
     }
@@ -167,10 +178,31 @@ where
         Self {
             visitor,
             checks,
+            cache: None,
             _marker: Default::default(),
         }
     }
 
+    /// Re-use the results of the checks for documents which didn't change.
+    ///
+    /// The cache is only valid for the checks added at the time of calling this function, so it
+    /// must be called after all checks have been added.
+    pub fn with_cache(mut self, cache: Arc<CheckCache>) -> Self
+    where
+        I: Display,
+    {
+        let checks = self
+            .checks
+            .iter()
+            .map(|(index, check)| format!("{index}={}", check.version()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let fingerprint = Hex(&Sha256::digest(checks)).to_lower();
+
+        self.cache = Some((cache, fingerprint));
+        self
+    }
+
     pub fn add<F: Check + 'static>(mut self, index: I, check: F) -> Self {
         self.checks.push((index, Box::new(check)));
         self
@@ -178,11 +210,16 @@ where
 
     async fn verify(&self, advisory: A) -> Result<VerifiedAdvisory<A, I>, VerificationError<E, A>> {
         let data = advisory.as_retrieved().data.clone();
+        let cached = self.cache.is_some();
 
-        let csaf = match tokio::task::spawn_blocking(move || serde_json::from_slice::<Csaf>(&data))
-            .await
-        {
-            Ok(Ok(csaf)) => csaf,
+        let parsed = tokio::task::spawn_blocking(move || {
+            let digest = cached.then(|| Hex(&Sha256::digest(&data)).to_lower());
+            serde_json::from_slice::<Csaf>(&data).map(|csaf| (csaf, digest))
+        })
+        .await;
+
+        let (csaf, digest) = match parsed {
+            Ok(Ok(result)) => result,
             Ok(Err(error)) => return Err(VerificationError::Parsing { error, advisory }),
             Err(_) => {
                 return Err(VerificationError::Parsing {
@@ -192,16 +229,45 @@ where
             }
         };
 
+        let cache = self.cache.as_ref().zip(digest);
+
+        let results = match cache
+            .as_ref()
+            .and_then(|((cache, fingerprint), digest)| cache.get(digest, fingerprint))
+            .filter(|results| results.len() == self.checks.len())
+        {
+            Some(results) => results,
+            None => {
+                let mut results = Vec::with_capacity(self.checks.len());
+                for (_, check) in &self.checks {
+                    match check.as_ref().findings(&csaf).await {
+                        Ok(result) => results.push(result),
+                        Err(error) => return Err(VerificationError::Check { error, advisory }),
+                    };
+                }
+
+                if let Some(((cache, fingerprint), digest)) = cache {
+                    cache.insert(digest, fingerprint.clone(), results.clone());
+                }
+
+                results
+            }
+        };
+
         let mut failures = HashMap::new();
+        let mut notices = HashMap::new();
         let mut successes = HashSet::new();
 
-        for (index, check) in &self.checks {
-            let result = match check.as_ref().check(&csaf).await {
-                Ok(result) => result,
-                Err(error) => return Err(VerificationError::Check { error, advisory }),
-            };
-            if !result.is_empty() {
-                failures.insert(index.clone(), result);
+        for ((index, _), result) in self.checks.iter().zip(results) {
+            let (errors, others): (Vec<_>, Vec<_>) = result
+                .into_iter()
+                .partition(|finding| finding.severity == Severity::Error);
+
+            if !others.is_empty() {
+                notices.insert(index.clone(), others);
+            }
+            if !errors.is_empty() {
+                failures.insert(index.clone(), errors);
             } else {
                 successes.insert(index.clone());
             }
@@ -211,6 +277,7 @@ where
             advisory,
             csaf,
             failures,
+            notices,
             successes,
         })
     }
@@ -0,0 +1,164 @@
+use crate::retrieve::{RetrievalContext, RetrievalError, RetrievedAdvisory, RetrievedVisitor};
+use sha2::{Digest, Sha256, Sha512};
+use url::Url;
+use walker_common::{
+    fetcher::{self, Fetcher},
+    retrieve::DigestFile,
+    utils::hex::Hex,
+};
+
+/// A visitor, cross-checking retrieved advisories with the digests published by the canonical
+/// provider.
+///
+/// When retrieving advisories from a mirror (e.g. of an aggregator), the mirror might be
+/// compromised or stale. For each retrieved advisory, the digest file (`.sha256`, or `.sha512`)
+/// of the same document is fetched from the canonical provider, and compared to the digest of
+/// the retrieved content. A mismatch, or a failure to fetch the digest, fails the advisory.
+///
+/// The canonical location of an advisory is its path, relative to its distribution, resolved
+/// against each of the canonical distribution bases. If there are no bases, the (original) URL
+/// of the advisory is used, which works when the retrieval URL was rewritten to point to a
+/// mirror (see [`crate::visitors::rewrite::RewriteVisitor`]).
+///
+/// Advisories which the canonical provider doesn't publish a digest for are passed on
+/// unchanged, with a warning. Without a configuration, all advisories are passed on unchanged.
+pub struct CrossCheckVisitor<V: RetrievedVisitor> {
+    pub visitor: V,
+
+    pub config: Option<CrossCheckConfig>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CrossCheckConfig {
+    /// The fetcher used to fetch the digests from the canonical provider
+    pub fetcher: Fetcher,
+    /// The directory distribution bases of the canonical provider
+    pub bases: Vec<Url>,
+}
+
+impl CrossCheckConfig {
+    pub fn new(fetcher: Fetcher) -> Self {
+        Self {
+            fetcher,
+            bases: vec![],
+        }
+    }
+
+    pub fn bases<I>(mut self, bases: I) -> Self
+    where
+        I: IntoIterator<Item = Url>,
+    {
+        self.bases = bases.into_iter().map(directory_base).collect();
+        self
+    }
+
+    /// The candidates of canonical locations of an advisory
+    fn canonical_urls(&self, advisory: &RetrievedAdvisory) -> Vec<Url> {
+        if self.bases.is_empty() {
+            return vec![advisory.url.clone()];
+        }
+
+        let Some((_, relative)) = advisory.discovered.relative_base_and_url() else {
+            return vec![];
+        };
+
+        self.bases
+            .iter()
+            .filter_map(|base| base.join(&relative).ok())
+            .collect()
+    }
+
+    /// Cross-check the advisory, returning the reason if it failed.
+    async fn check(&self, advisory: &RetrievedAdvisory) -> Result<(), String> {
+        for url in self.canonical_urls(advisory) {
+            let expected = match self.fetch_digest(&url, "sha256").await? {
+                Some(expected) => Some((expected, digest::<Sha256>(&advisory.data))),
+                None => self
+                    .fetch_digest(&url, "sha512")
+                    .await?
+                    .map(|expected| (expected, digest::<Sha512>(&advisory.data))),
+            };
+
+            if let Some((expected, actual)) = expected {
+                return match expected == actual {
+                    true => Ok(()),
+                    false => Err(format!(
+                        "digest mismatch with {url} (expected: {expected}, actual: {actual})"
+                    )),
+                };
+            }
+        }
+
+        log::warn!(
+            "No digest published by the canonical provider, unable to cross-check: {}",
+            advisory.url
+        );
+
+        Ok(())
+    }
+
+    async fn fetch_digest(&self, url: &Url, kind: &str) -> Result<Option<String>, String> {
+        let url = Url::parse(&format!("{url}.{kind}")).map_err(|err| err.to_string())?;
+
+        let content = self
+            .fetcher
+            .fetch::<Option<String>>(url.clone())
+            .await
+            .map_err(|err: fetcher::Error| format!("failed to fetch {url}: {err}"))?;
+
+        content
+            .map(|content| {
+                DigestFile::parse(&content)
+                    .map(|file| file.digest)
+                    .map_err(|err| format!("invalid digest file {url}: {err}"))
+            })
+            .transpose()
+    }
+}
+
+impl<V: RetrievedVisitor> RetrievedVisitor for CrossCheckVisitor<V> {
+    type Error = V::Error;
+    type Context = V::Context;
+
+    async fn visit_context(
+        &self,
+        context: &RetrievalContext<'_>,
+    ) -> Result<Self::Context, Self::Error> {
+        self.visitor.visit_context(context).await
+    }
+
+    async fn visit_advisory(
+        &self,
+        context: &Self::Context,
+        result: Result<RetrievedAdvisory, RetrievalError>,
+    ) -> Result<(), Self::Error> {
+        let result = match (result, &self.config) {
+            (Ok(advisory), Some(config)) => match config.check(&advisory).await {
+                Ok(()) => Ok(advisory),
+                Err(reason) => {
+                    log::warn!("Cross-check failed for {}: {reason}", advisory.url);
+                    Err(RetrievalError::CrossCheck {
+                        reason,
+                        discovered: advisory.discovered,
+                    })
+                }
+            },
+            (result, _) => result,
+        };
+
+        self.visitor.visit_advisory(context, result).await
+    }
+}
+
+/// The lower-cased, hex encoded digest of the data
+fn digest<D: Digest>(data: &[u8]) -> String {
+    Hex(&D::digest(data)).to_lower()
+}
+
+/// Ensure the URL ends with a slash, so that relative paths can be joined
+fn directory_base(mut url: Url) -> Url {
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+    url
+}
@@ -1,15 +1,23 @@
 use crate::discover::{DiscoveredAdvisory, DiscoveredContext, DiscoveredVisitor};
-use std::collections::HashSet;
+use crate::ignore::{today, IgnoreList};
+use anyhow::Context;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use walker_common::clock::Clock;
 
 /// A visitor, skipping advisories for existing files.
 pub struct FilteringVisitor<V: DiscoveredVisitor> {
     pub visitor: V,
 
     pub config: FilterConfig,
+
+    /// The clock, deciding if entries of the ignore list have expired
+    pub clock: Clock,
 }
 
 #[non_exhaustive]
-#[derive(Clone, Default, Debug, PartialEq, Eq)]
+#[derive(Clone, Default, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct FilterConfig {
     /// A set of distributions to ignore
     ///
@@ -18,6 +26,52 @@ pub struct FilterConfig {
     pub ignored_distributions: HashSet<String>,
     pub ignored_prefixes: Vec<String>,
     pub only_prefixes: Vec<String>,
+    /// Only process advisories of those distributions, if not empty
+    pub only_distributions: HashSet<String>,
+    /// Only process advisories with a year folder (the year of the initial release) of at least
+    /// this year. Advisories without a year folder are always processed.
+    pub min_year: Option<i32>,
+    /// Only process advisories of ROLIE feeds with one of those TLP labels (case-insensitive,
+    /// `CLEAR` being an alias for `WHITE`), if not empty. Advisories of distributions without
+    /// TLP information are always processed.
+    pub tlp_labels: HashSet<String>,
+    /// Documents to skip, as they are known to be broken
+    pub ignore_list: IgnoreList,
+}
+
+/// Named filter configurations, e.g. loaded from a configuration file
+#[derive(Clone, Default, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(transparent)]
+pub struct FilterProfiles(pub HashMap<String, FilterConfig>);
+
+impl FilterProfiles {
+    /// Load profiles from a JSON file, mapping profile names to filter configurations
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open filter profiles: {}", path.display()))?;
+        serde_json::from_reader(std::io::BufReader::new(file))
+            .with_context(|| format!("Failed to parse filter profiles: {}", path.display()))
+    }
+
+    /// Get a profile by name
+    pub fn get(&self, name: &str) -> Option<&FilterConfig> {
+        self.0.get(name)
+    }
+
+    /// Combine the named profiles into a single filter configuration.
+    pub fn select<'a>(
+        &self,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> anyhow::Result<FilterConfig> {
+        names
+            .into_iter()
+            .try_fold(FilterConfig::default(), |config, name| {
+                match self.get(name) {
+                    Some(profile) => Ok(config.merge(profile.clone())),
+                    None => anyhow::bail!("Unknown filter profile: {name}"),
+                }
+            })
+    }
 }
 
 impl FilterConfig {
@@ -88,6 +142,116 @@ impl FilterConfig {
         self.only_prefixes.extend(only_prefixes);
         self
     }
+
+    pub fn only_distributions<I>(mut self, only_distributions: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        self.only_distributions = HashSet::from_iter(only_distributions);
+        self
+    }
+
+    pub fn add_only_distribution(mut self, only_distribution: impl Into<String>) -> Self {
+        self.only_distributions.insert(only_distribution.into());
+        self
+    }
+
+    pub fn extend_only_distributions<I>(mut self, only_distributions: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        self.only_distributions.extend(only_distributions);
+        self
+    }
+
+    pub fn min_year(mut self, min_year: impl Into<Option<i32>>) -> Self {
+        self.min_year = min_year.into();
+        self
+    }
+
+    pub fn tlp_labels<I>(mut self, tlp_labels: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        self.tlp_labels = HashSet::from_iter(tlp_labels);
+        self
+    }
+
+    pub fn add_tlp_label(mut self, tlp_label: impl Into<String>) -> Self {
+        self.tlp_labels.insert(tlp_label.into());
+        self
+    }
+
+    pub fn extend_tlp_labels<I>(mut self, tlp_labels: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        self.tlp_labels.extend(tlp_labels);
+        self
+    }
+
+    pub fn ignore_list(mut self, ignore_list: IgnoreList) -> Self {
+        self.ignore_list = ignore_list;
+        self
+    }
+
+    /// Combine with another configuration, so that both configurations must accept an advisory.
+    pub fn merge(mut self, other: FilterConfig) -> Self {
+        self.ignored_distributions
+            .extend(other.ignored_distributions);
+        self.ignored_prefixes.extend(other.ignored_prefixes);
+        self.only_prefixes.extend(other.only_prefixes);
+
+        self.only_distributions =
+            Self::intersect(self.only_distributions, other.only_distributions);
+        self.tlp_labels = Self::intersect(
+            self.tlp_labels
+                .into_iter()
+                .map(|label| normalize_tlp(&label))
+                .collect(),
+            other
+                .tlp_labels
+                .into_iter()
+                .map(|label| normalize_tlp(&label))
+                .collect(),
+        );
+
+        self.min_year = self.min_year.max(other.min_year);
+        self.ignore_list.extend(other.ignore_list);
+
+        self
+    }
+
+    /// Intersect two sets, an empty set meaning "everything".
+    fn intersect(a: HashSet<String>, b: HashSet<String>) -> HashSet<String> {
+        match (a.is_empty(), b.is_empty()) {
+            (true, _) => b,
+            (_, true) => a,
+            _ => a.intersection(&b).cloned().collect(),
+        }
+    }
+}
+
+/// Normalize a TLP label for comparison
+pub fn normalize_tlp(label: &str) -> String {
+    let label = label.trim().to_uppercase();
+    let label = label.strip_prefix("TLP:").unwrap_or(&label);
+    match label {
+        "CLEAR" => "WHITE".to_string(),
+        label => label.to_string(),
+    }
+}
+
+/// The year folder of an advisory, the last path segment before the file name consisting of four
+/// digits.
+fn year_folder(advisory: &DiscoveredAdvisory) -> Option<i32> {
+    advisory
+        .url
+        .path_segments()?
+        .rev()
+        .skip(1)
+        .find(|segment| segment.len() == 4 && segment.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|segment| segment.parse().ok())
 }
 
 impl<V: DiscoveredVisitor> DiscoveredVisitor for FilteringVisitor<V> {
@@ -115,6 +279,41 @@ impl<V: DiscoveredVisitor> DiscoveredVisitor for FilteringVisitor<V> {
         {
             return Ok(());
         };
+
+        // "only" distributions
+
+        if !self.config.only_distributions.is_empty()
+            && !self
+                .config
+                .only_distributions
+                .contains(advisory.context.url().as_str())
+        {
+            return Ok(());
+        }
+
+        // TLP label of the feed
+
+        if !self.config.tlp_labels.is_empty() {
+            if let Some(label) = advisory.context.tlp_label() {
+                if !self
+                    .config
+                    .tlp_labels
+                    .iter()
+                    .any(|expected| normalize_tlp(expected) == label.as_str())
+                {
+                    return Ok(());
+                }
+            }
+        }
+
+        // year folder
+
+        if let (Some(min_year), Some(year)) = (self.config.min_year, year_folder(&advisory)) {
+            if year < min_year {
+                return Ok(());
+            }
+        }
+
         // eval name
 
         let name = advisory
@@ -139,6 +338,17 @@ impl<V: DiscoveredVisitor> DiscoveredVisitor for FilteringVisitor<V> {
             }
         }
 
+        // known broken documents
+
+        if !self.config.ignore_list.is_empty() {
+            if let Some(entry) = today(&self.clock)
+                .and_then(|today| self.config.ignore_list.ignored(&advisory.url, today))
+            {
+                log::info!("Ignoring {}: {}", advisory.url, entry.reason);
+                return Ok(());
+            }
+        }
+
         // ok to proceed
 
         self.visitor.visit_advisory(context, advisory).await
@@ -0,0 +1,252 @@
+//! Journaling failed advisories
+//!
+//! A few documents failing to be retrieved or validated (e.g. because of a timeout) shouldn't
+//! require walking the whole provider again. The [`JournalVisitor`] records those documents in a
+//! [`Journal`], which can be stored as a [`JournalFile`]. Its entries form a list of advisories
+//! (see [`crate::source::ListSource`]), so that only the failed documents can be processed again.
+
+use crate::{
+    discover::{AsDiscovered, DiscoveredAdvisory},
+    retrieve::{RetrievalContext, RetrievalError, RetrievedAdvisory, RetrievedVisitor},
+    source::ListEntry,
+    validation::{ValidatedAdvisory, ValidatedVisitor, ValidationContext, ValidationError},
+};
+use anyhow::Context;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind};
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+use time::OffsetDateTime;
+
+/// A document which failed to be processed
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    #[serde(flatten)]
+    pub entry: ListEntry,
+    /// Why processing the document failed
+    pub error: String,
+}
+
+/// The journal of a run, collecting the failed documents
+#[derive(Debug, Default)]
+pub struct Journal {
+    entries: Mutex<Vec<JournalEntry>>,
+}
+
+impl Journal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a failed document.
+    pub fn record(&self, discovered: &DiscoveredAdvisory, error: impl Display) {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(JournalEntry {
+                entry: discovered.into(),
+                error: error.to_string(),
+            });
+    }
+
+    /// The failed documents recorded so far, in the order they failed.
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// The stored journal of a run
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalFile {
+    /// The command line of the run, allowing to process the entries the same way again
+    #[serde(default)]
+    pub command: Vec<String>,
+    /// When the journal was created
+    #[serde(with = "time::serde::rfc3339")]
+    pub created: OffsetDateTime,
+    #[serde(default)]
+    pub entries: Vec<JournalEntry>,
+}
+
+impl JournalFile {
+    pub fn new(command: Vec<String>, journal: &Journal) -> Self {
+        Self {
+            command,
+            created: OffsetDateTime::now_utc(),
+            entries: journal.entries(),
+        }
+    }
+
+    /// Load a journal from a JSON file, returning [`None`] if the file doesn't exist.
+    pub fn load_from(path: &Path) -> anyhow::Result<Option<Self>> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Failed to open journal: {}", path.display()))
+            }
+        };
+
+        serde_json::from_reader(BufReader::new(file))
+            .map(Some)
+            .with_context(|| format!("Failed to parse journal: {}", path.display()))
+    }
+
+    /// Store the journal to a JSON file.
+    pub fn store_to(&self, path: &Path) -> anyhow::Result<()> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create journal: {}", path.display()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .with_context(|| format!("Failed to write journal: {}", path.display()))
+    }
+
+    /// The failed documents, as a list of advisories to walk.
+    pub fn list(&self) -> Vec<ListEntry> {
+        self.entries
+            .iter()
+            .map(|entry| entry.entry.clone())
+            .collect()
+    }
+}
+
+/// A visitor, recording failed advisories in a [`Journal`].
+///
+/// Failed advisories are recorded, and then passed on to the wrapped visitor. However, errors
+/// the wrapped visitor returns for a failed advisory (like the
+/// [`crate::visitors::store::StoreVisitor`] does) are only logged, so that the walk continues
+/// and collects all failed documents, instead of being aborted by the first one. Errors for
+/// successful advisories are still returned.
+///
+/// The visitor can wrap a [`RetrievedVisitor`] or a [`ValidatedVisitor`]. Without a journal,
+/// all advisories are passed on unchanged.
+pub struct JournalVisitor<V> {
+    pub visitor: V,
+
+    pub journal: Option<Arc<Journal>>,
+}
+
+impl<V: RetrievedVisitor> RetrievedVisitor for JournalVisitor<V> {
+    type Error = V::Error;
+    type Context = V::Context;
+
+    async fn visit_context(
+        &self,
+        context: &RetrievalContext<'_>,
+    ) -> Result<Self::Context, Self::Error> {
+        self.visitor.visit_context(context).await
+    }
+
+    async fn visit_advisory(
+        &self,
+        context: &Self::Context,
+        result: Result<RetrievedAdvisory, RetrievalError>,
+    ) -> Result<(), Self::Error> {
+        let (Err(err), Some(journal)) = (&result, &self.journal) else {
+            return self.visitor.visit_advisory(context, result).await;
+        };
+
+        let url = err.discovered().url.clone();
+        journal.record(err.discovered(), err);
+
+        if let Err(err) = self.visitor.visit_advisory(context, result).await {
+            log::warn!("Failed to process {url}, recorded in journal: {err}");
+        }
+
+        Ok(())
+    }
+}
+
+impl<V: ValidatedVisitor> ValidatedVisitor for JournalVisitor<V> {
+    type Error = V::Error;
+    type Context = V::Context;
+
+    async fn visit_context(
+        &self,
+        context: &ValidationContext<'_>,
+    ) -> Result<Self::Context, Self::Error> {
+        self.visitor.visit_context(context).await
+    }
+
+    async fn visit_advisory(
+        &self,
+        context: &Self::Context,
+        result: Result<ValidatedAdvisory, ValidationError>,
+    ) -> Result<(), Self::Error> {
+        let (Err(err), Some(journal)) = (&result, &self.journal) else {
+            return self.visitor.visit_advisory(context, result).await;
+        };
+
+        let url = err.as_discovered().url.clone();
+        journal.record(err.as_discovered(), err);
+
+        if let Err(err) = self.visitor.visit_advisory(context, result).await {
+            log::warn!("Failed to process {url}, recorded in journal: {err}");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::discovered;
+
+    #[tokio::test]
+    async fn test_journal() -> anyhow::Result<()> {
+        let journal = Arc::new(Journal::new());
+        let visitor = JournalVisitor {
+            visitor: |result: Result<RetrievedAdvisory, RetrievalError>| async move {
+                result.map(|_| ()).map_err(|err| err.to_string())
+            },
+            journal: Some(journal.clone()),
+        };
+
+        let discovered = discovered("https://example.com/2023/a.json")?;
+        RetrievedVisitor::visit_advisory(
+            &visitor,
+            &(),
+            Err(RetrievalError::Skipped {
+                discovered: discovered.clone(),
+                reason: "host unavailable".to_string(),
+            }),
+        )
+        .await?;
+
+        let entries = journal.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].entry.url, discovered.url);
+
+        // round trip through the stored journal, and back to a list
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("journal.json");
+        assert_eq!(JournalFile::load_from(&path)?, None);
+
+        let file = JournalFile::new(vec!["csaf".to_string(), "sync".to_string()], &journal);
+        file.store_to(&path)?;
+        let loaded = JournalFile::load_from(&path)?.expect("journal must exist");
+        assert_eq!(loaded, file);
+        assert_eq!(loaded.list(), vec![ListEntry::from(&discovered)]);
+
+        Ok(())
+    }
+}
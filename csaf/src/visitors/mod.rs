@@ -1,6 +1,10 @@
 //! Ready-to use visitors
 
+pub mod cross_check;
 pub mod duplicates;
 pub mod filter;
+pub mod journal;
+pub mod record;
+pub mod rewrite;
 pub mod skip;
 pub mod store;
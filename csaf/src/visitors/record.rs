@@ -0,0 +1,209 @@
+use crate::{
+    retrieve::{RetrievalContext, RetrievalError, RetrievedAdvisory, RetrievedVisitor},
+    source::{
+        record_name, ListEntry, RecordedAdvisory, RecordedOutcome, DIR_ADVISORIES, DIR_KEYS,
+        FILE_METADATA,
+    },
+};
+use anyhow::Context;
+use std::fmt::{Debug, Display};
+use std::path::{Path, PathBuf};
+use walker_common::store::write_replace;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RecordError<VE: Display + Debug> {
+    #[error("Failed to record: {0}")]
+    Record(anyhow::Error),
+    #[error("{0}")]
+    Visitor(VE),
+}
+
+/// A visitor, recording the outcome of a walk into a directory.
+///
+/// The provider metadata, the public keys, and for each advisory its discovery information,
+/// content, signature, expected digests, and retrieval metadata (or the reason retrieving it
+/// failed) are recorded. The recording can be replayed using [`crate::source::ReplaySource`],
+/// e.g. for regression tests against the quirks of a real provider. Results are passed on to
+/// the wrapped visitor unchanged.
+///
+/// The visitor needs to be placed directly after the [`crate::retrieve::RetrievingVisitor`] to
+/// record what was actually retrieved.
+pub struct RecordingVisitor<V: RetrievedVisitor> {
+    pub visitor: V,
+
+    /// The directory to record into
+    pub output: PathBuf,
+}
+
+impl<V: RetrievedVisitor> RecordingVisitor<V> {
+    pub fn new(output: impl Into<PathBuf>, visitor: V) -> Self {
+        Self {
+            visitor,
+            output: output.into(),
+        }
+    }
+
+    async fn record_context(&self, context: &RetrievalContext<'_>) -> anyhow::Result<()> {
+        for dir in [DIR_ADVISORIES, DIR_KEYS] {
+            let dir = self.output.join(dir);
+            tokio::fs::create_dir_all(&dir)
+                .await
+                .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        }
+
+        write(
+            &self.output.join(FILE_METADATA),
+            &serde_json::to_vec_pretty(context.metadata)?,
+        )
+        .await?;
+
+        // keys are loaded in the order of the provider metadata
+        for (n, key) in context.keys.iter().enumerate() {
+            write(
+                &self.output.join(DIR_KEYS).join(format!("{n}.pgp")),
+                &key.raw,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn record_advisory(
+        &self,
+        result: &Result<RetrievedAdvisory, RetrievalError>,
+    ) -> anyhow::Result<()> {
+        let (discovered, outcome) = match result {
+            Ok(advisory) => (
+                &advisory.discovered,
+                RecordedOutcome::Retrieved {
+                    signature: advisory.signature.clone(),
+                    sha256: advisory.sha256.as_ref().map(|d| d.expected.clone()),
+                    sha512: advisory.sha512.as_ref().map(|d| d.expected.clone()),
+                    metadata: advisory.metadata.clone(),
+                },
+            ),
+            Err(err) => (
+                err.discovered(),
+                RecordedOutcome::Failed {
+                    reason: match err {
+                        RetrievalError::Skipped { reason, .. } => reason.clone(),
+                        err => err.to_string(),
+                    },
+                },
+            ),
+        };
+
+        let name = record_name(&discovered.url);
+        let base = self.output.join(DIR_ADVISORIES);
+
+        if let Ok(advisory) = result {
+            write(&base.join(format!("{name}.data")), &advisory.data).await?;
+        }
+
+        let record = RecordedAdvisory {
+            entry: ListEntry::from(discovered),
+            retrieval_url: discovered.retrieval_url.clone(),
+            rolie: discovered.rolie.clone(),
+            outcome,
+        };
+        write(
+            &base.join(format!("{name}.json")),
+            &serde_json::to_vec_pretty(&record)?,
+        )
+        .await
+    }
+}
+
+async fn write(path: &Path, data: &[u8]) -> anyhow::Result<()> {
+    write_replace(path, data)
+        .await
+        .with_context(|| format!("Failed to write: {}", path.display()))
+}
+
+impl<V: RetrievedVisitor> RetrievedVisitor for RecordingVisitor<V> {
+    type Error = RecordError<V::Error>;
+    type Context = V::Context;
+
+    async fn visit_context(
+        &self,
+        context: &RetrievalContext<'_>,
+    ) -> Result<Self::Context, Self::Error> {
+        self.record_context(context)
+            .await
+            .map_err(RecordError::Record)?;
+
+        self.visitor
+            .visit_context(context)
+            .await
+            .map_err(RecordError::Visitor)
+    }
+
+    async fn visit_advisory(
+        &self,
+        context: &Self::Context,
+        result: Result<RetrievedAdvisory, RetrievalError>,
+    ) -> Result<(), Self::Error> {
+        self.record_advisory(&result)
+            .await
+            .map_err(RecordError::Record)?;
+
+        self.visitor
+            .visit_advisory(context, result)
+            .await
+            .map_err(RecordError::Visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        retrieve::RetrievingVisitor,
+        source::ReplaySource,
+        test_utils::{document, CollectingVisitor, MockProvider},
+        walker::Walker,
+    };
+
+    #[tokio::test]
+    async fn test_record_replay() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let output = dir.path().join("recording");
+
+        let provider = MockProvider::new("https://example.com")?
+            .advisory("2024/a.json", document("A"))?
+            .advisory("2024/b.json", document("B"))?
+            .wrong_digest("2024/b.json")?;
+
+        let recorded = CollectingVisitor::new();
+        Walker::new(provider.clone())
+            .walk(RetrievingVisitor::new(
+                provider,
+                RecordingVisitor::new(&output, recorded.clone()),
+            ))
+            .await?;
+
+        let replayed = CollectingVisitor::new();
+        let source = ReplaySource::new(&output);
+        Walker::new(source.clone())
+            .walk(RetrievingVisitor::new(source, replayed.clone()))
+            .await?;
+
+        assert_eq!(replayed.urls(), recorded.urls());
+
+        let advisories = replayed.advisories();
+        assert_eq!(advisories.len(), 2);
+        assert_eq!(advisories[0].data, recorded.advisories()[0].data);
+        assert!(advisories[0]
+            .sha256
+            .as_ref()
+            .is_some_and(|d| d.validate().is_ok()));
+        // the wrong digest is replayed as well
+        assert!(advisories[1]
+            .sha256
+            .as_ref()
+            .is_some_and(|d| d.validate().is_err()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+use crate::discover::{DiscoveredAdvisory, DiscoveredContext, DiscoveredVisitor};
+use regex::Regex;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use url::{ParseError, Url};
+
+/// A visitor, rewriting the URL an advisory will be retrieved from.
+///
+/// The original URL of the advisory is kept, so that reporting and storing still refer to the
+/// original location. Only the [`DiscoveredAdvisory::retrieval_url`] is changed.
+pub struct RewriteVisitor<V: DiscoveredVisitor> {
+    pub visitor: V,
+
+    pub config: RewriteConfig,
+}
+
+/// A rule for rewriting URLs
+#[derive(Clone)]
+pub enum RewriteRule {
+    /// Replace a prefix of the URL
+    Prefix { from: String, to: String },
+    /// Replace the first match of a regular expression, allowing references like `$1`
+    Regex { regex: Regex, replacement: String },
+    /// Rewrite using a function, returning [`None`] if the URL doesn't match
+    Custom(Arc<dyn Fn(&Url) -> Option<Url> + Send + Sync>),
+}
+
+impl Debug for RewriteRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Prefix { from, to } => f
+                .debug_struct("Prefix")
+                .field("from", from)
+                .field("to", to)
+                .finish(),
+            Self::Regex { regex, replacement } => f
+                .debug_struct("Regex")
+                .field("regex", regex)
+                .field("replacement", replacement)
+                .finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl RewriteRule {
+    pub fn prefix(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self::Prefix {
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+
+    pub fn regex(regex: &str, replacement: impl Into<String>) -> Result<Self, regex::Error> {
+        Ok(Self::Regex {
+            regex: Regex::new(regex)?,
+            replacement: replacement.into(),
+        })
+    }
+
+    pub fn custom<F>(f: F) -> Self
+    where
+        F: Fn(&Url) -> Option<Url> + Send + Sync + 'static,
+    {
+        Self::Custom(Arc::new(f))
+    }
+
+    /// Apply the rule, returning [`None`] if the rule doesn't match.
+    pub fn apply(&self, url: &Url) -> Option<Result<Url, ParseError>> {
+        match self {
+            Self::Prefix { from, to } => url
+                .as_str()
+                .strip_prefix(from.as_str())
+                .map(|rest| Url::parse(&format!("{to}{rest}"))),
+            Self::Regex { regex, replacement } => regex
+                .is_match(url.as_str())
+                .then(|| Url::parse(&regex.replace(url.as_str(), replacement.as_str()))),
+            Self::Custom(f) => f(url).map(Ok),
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, Default, Debug)]
+pub struct RewriteConfig {
+    /// Rules, evaluated in order. The first matching rule wins.
+    pub rules: Vec<RewriteRule>,
+}
+
+impl RewriteConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn rules<I>(mut self, rules: I) -> Self
+    where
+        I: IntoIterator<Item = RewriteRule>,
+    {
+        self.rules = Vec::from_iter(rules);
+        self
+    }
+
+    pub fn add_rule(mut self, rule: RewriteRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn extend_rules<I>(mut self, rules: I) -> Self
+    where
+        I: IntoIterator<Item = RewriteRule>,
+    {
+        self.rules.extend(rules);
+        self
+    }
+
+    /// Rewrite a URL, returning [`None`] if no rule matched.
+    pub fn rewrite(&self, url: &Url) -> Option<Result<Url, ParseError>> {
+        self.rules.iter().find_map(|rule| rule.apply(url))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error<VE: std::fmt::Display + Debug> {
+    #[error("Failed to rewrite URL '{url}': {err}")]
+    Rewrite { url: Url, err: ParseError },
+    #[error(transparent)]
+    Visitor(VE),
+}
+
+impl<V: DiscoveredVisitor> DiscoveredVisitor for RewriteVisitor<V> {
+    type Error = Error<V::Error>;
+    type Context = V::Context;
+
+    async fn visit_context(
+        &self,
+        discovered: &DiscoveredContext<'_>,
+    ) -> Result<Self::Context, Self::Error> {
+        self.visitor
+            .visit_context(discovered)
+            .await
+            .map_err(Error::Visitor)
+    }
+
+    async fn visit_advisory(
+        &self,
+        context: &Self::Context,
+        mut advisory: DiscoveredAdvisory,
+    ) -> Result<(), Self::Error> {
+        if let Some(result) = self.config.rewrite(advisory.retrieval_url()) {
+            let url = result.map_err(|err| Error::Rewrite {
+                url: advisory.url.clone(),
+                err,
+            })?;
+            log::debug!("Rewrote URL: {} -> {url}", advisory.url);
+            advisory.retrieval_url = Some(url);
+        }
+
+        self.visitor
+            .visit_advisory(context, advisory)
+            .await
+            .map_err(Error::Visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_prefix() {
+        let config = RewriteConfig::new().add_rule(RewriteRule::prefix(
+            "https://provider.example.com/",
+            "https://cache.internal/provider/",
+        ));
+
+        let url = Url::parse("https://provider.example.com/csaf/2023/a.json").unwrap();
+        assert_eq!(
+            config.rewrite(&url).unwrap().unwrap().as_str(),
+            "https://cache.internal/provider/csaf/2023/a.json"
+        );
+
+        let url = Url::parse("https://other.example.com/csaf/2023/a.json").unwrap();
+        assert!(config.rewrite(&url).is_none());
+    }
+
+    #[test]
+    fn test_regex() {
+        let config = RewriteConfig::new().add_rule(
+            RewriteRule::regex(r"^https://([^/]+)/", "https://cache.internal/$1/").unwrap(),
+        );
+
+        let url = Url::parse("https://provider.example.com/csaf/a.json").unwrap();
+        assert_eq!(
+            config.rewrite(&url).unwrap().unwrap().as_str(),
+            "https://cache.internal/provider.example.com/csaf/a.json"
+        );
+    }
+}
@@ -1,11 +1,19 @@
 use crate::discover::{DiscoveredAdvisory, DiscoveredContext, DiscoveredVisitor};
-use crate::model::store::distribution_base;
+use crate::model::store::StoreLayout;
 use crate::validation::{ValidatedAdvisory, ValidatedVisitor, ValidationContext, ValidationError};
+use sha2::{Digest, Sha256};
 use std::fmt::{Debug, Display};
-use std::path::PathBuf;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use tokio::fs;
-use walker_common::utils::url::Urlify;
+use url::Url;
+use walker_common::{
+    fetcher::Fetcher,
+    retrieve::DigestFile,
+    store::load_metadata,
+    utils::{hex::Hex, url::Urlify},
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error<VE: Display + Debug> {
@@ -21,10 +29,35 @@ pub enum Error<VE: Display + Debug> {
 pub struct SkipExistingVisitor<V: DiscoveredVisitor> {
     pub visitor: V,
     pub output: PathBuf,
+    /// The layout of the output directory
+    pub layout: StoreLayout,
     /// The time "since" when we consider changes "new"
     ///
     /// Overrides the "file modified" timestamp which is used by default.
     pub since: Option<SystemTime>,
+    /// Issue a `HEAD` request for documents considered changed, and skip them if the
+    /// response matches the stored retrieval metadata.
+    pub head: Option<Fetcher>,
+}
+
+impl<V: DiscoveredVisitor> SkipExistingVisitor<V> {
+    /// Check if the remote document is unchanged, compared to the stored retrieval metadata.
+    async fn is_unchanged(&self, path: &Path, url: &Url) -> bool {
+        let Some(fetcher) = &self.head else {
+            return false;
+        };
+        let Some(stored) = load_metadata(path).await else {
+            return false;
+        };
+
+        match fetcher.head(url.clone()).await {
+            Ok(current) => stored.is_unchanged(&current),
+            Err(err) => {
+                log::debug!("Failed to check {url}, retrieving: {err}");
+                false
+            }
+        }
+    }
 }
 
 impl<V: DiscoveredVisitor> DiscoveredVisitor for SkipExistingVisitor<V> {
@@ -50,7 +83,10 @@ impl<V: DiscoveredVisitor> DiscoveredVisitor for SkipExistingVisitor<V> {
             Some(name) => name,
             None => return Err(Error::Name),
         };
-        let path = distribution_base(&self.output, advisory.context.url().as_str()).join(&name);
+        let path = self
+            .layout
+            .distribution_base(&self.output, advisory.context.url().as_str())
+            .join(&name);
 
         if fs::try_exists(&path).await? {
             // if we have a "since", we use it as the file modification timestamp
@@ -71,6 +107,11 @@ impl<V: DiscoveredVisitor> DiscoveredVisitor for SkipExistingVisitor<V> {
                 // the file was modified after the change date, skip it
                 return Ok(());
             }
+
+            if self.is_unchanged(&path, advisory.retrieval_url()).await {
+                log::debug!("Unchanged, according to HEAD request: {}", advisory.url);
+                return Ok(());
+            }
         } else {
             log::debug!("File did not exist: {}", path.display());
         }
@@ -82,6 +123,145 @@ impl<V: DiscoveredVisitor> DiscoveredVisitor for SkipExistingVisitor<V> {
     }
 }
 
+/// A predicate for skipping an advisory, based on its stored copy.
+#[derive(Clone, Debug)]
+pub enum SkipPredicate {
+    /// Skip if the stored copy has the SHA-256 digest published by the provider.
+    ///
+    /// The digest of the stored copy is taken from its `.sha256` file, or calculated from the
+    /// stored document if there is none. The published digest is fetched using the fetcher,
+    /// from the ROLIE hash link or the default location.
+    MatchingDigest(Fetcher),
+    /// Skip if the stored copy was modified after the advisory was changed, even if a "since"
+    /// timestamp would consider it changed.
+    NewerLocal,
+}
+
+impl SkipPredicate {
+    fn reason(&self) -> &'static str {
+        match self {
+            Self::MatchingDigest(_) => "matching digest",
+            Self::NewerLocal => "newer local copy",
+        }
+    }
+
+    /// Check if the advisory, with its stored copy at the path, should be skipped.
+    async fn matches(&self, advisory: &DiscoveredAdvisory, path: &Path) -> bool {
+        match self {
+            Self::MatchingDigest(fetcher) => {
+                let Some(local) = local_digest(path).await else {
+                    return false;
+                };
+
+                match remote_digest(fetcher, advisory).await {
+                    Ok(Some(remote)) => local == remote,
+                    Ok(None) => false,
+                    Err(err) => {
+                        log::debug!("Failed to fetch digest of {}: {err}", advisory.url);
+                        false
+                    }
+                }
+            }
+            Self::NewerLocal => match fs::metadata(path).await.and_then(|md| md.modified()) {
+                Ok(modified) => modified >= advisory.modified,
+                Err(_) => false,
+            },
+        }
+    }
+}
+
+/// The SHA-256 digest of a stored document, lower-cased.
+async fn local_digest(path: &Path) -> Option<String> {
+    let mut sidecar = path.as_os_str().to_owned();
+    sidecar.push(".sha256");
+
+    match fs::read_to_string(&sidecar).await {
+        Ok(content) => return Some(DigestFile::expected(&content)),
+        Err(err) if err.kind() == ErrorKind::NotFound => {}
+        Err(_) => return None,
+    }
+
+    let data = fs::read(path).await.ok()?;
+    Some(Hex(&Sha256::digest(data)).to_lower())
+}
+
+/// The SHA-256 digest, as published by the provider.
+async fn remote_digest(
+    fetcher: &Fetcher,
+    advisory: &DiscoveredAdvisory,
+) -> anyhow::Result<Option<String>> {
+    let url = match advisory
+        .rolie
+        .as_ref()
+        .and_then(|rolie| rolie.sha256.clone())
+    {
+        Some(url) => url,
+        None => Url::parse(&format!("{}.sha256", advisory.retrieval_url()))?,
+    };
+
+    Ok(fetcher
+        .fetch::<Option<String>>(url)
+        .await?
+        .map(|content| DigestFile::expected(&content)))
+}
+
+/// A visitor, skipping advisories when any of the predicates matches their stored copy.
+///
+/// Advisories without a stored copy are never skipped.
+pub struct SkipVisitor<V: DiscoveredVisitor> {
+    pub visitor: V,
+    pub output: PathBuf,
+    /// The layout of the output directory
+    pub layout: StoreLayout,
+    pub predicates: Vec<SkipPredicate>,
+}
+
+impl<V: DiscoveredVisitor> DiscoveredVisitor for SkipVisitor<V> {
+    type Error = Error<V::Error>;
+    type Context = V::Context;
+
+    async fn visit_context(
+        &self,
+        context: &DiscoveredContext<'_>,
+    ) -> Result<Self::Context, Self::Error> {
+        self.visitor
+            .visit_context(context)
+            .await
+            .map_err(Error::Visitor)
+    }
+
+    async fn visit_advisory(
+        &self,
+        context: &Self::Context,
+        advisory: DiscoveredAdvisory,
+    ) -> Result<(), Self::Error> {
+        if !self.predicates.is_empty() {
+            let name = match advisory.context.url().clone().make_relative(&advisory.url) {
+                Some(name) => name,
+                None => return Err(Error::Name),
+            };
+            let path = self
+                .layout
+                .distribution_base(&self.output, advisory.context.url().as_str())
+                .join(&name);
+
+            if fs::try_exists(&path).await? {
+                for predicate in &self.predicates {
+                    if predicate.matches(&advisory, &path).await {
+                        log::debug!("Skipping ({}): {}", predicate.reason(), advisory.url);
+                        return Ok(());
+                    }
+                }
+            }
+        }
+
+        self.visitor
+            .visit_advisory(context, advisory)
+            .await
+            .map_err(Error::Visitor)
+    }
+}
+
 /// A visitor which will skip (with a warning) any failed document.
 pub struct SkipFailedVisitor<V> {
     pub visitor: V,
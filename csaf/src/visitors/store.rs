@@ -1,21 +1,117 @@
 use crate::{
-    model::{metadata::ProviderMetadata, store::distribution_base},
+    diff::{diff_file, AdvisoryDiff},
+    discover::origin_file,
+    model::{
+        lifecycle::Lifecycle,
+        metadata::ProviderMetadata,
+        store::{distribution_urls, link_object, LayoutFile, StoreLayout},
+    },
+    retention::remove_stored,
     retrieve::{RetrievalContext, RetrievalError, RetrievedAdvisory, RetrievedVisitor},
     validation::{ValidatedAdvisory, ValidatedVisitor, ValidationContext, ValidationError},
 };
 use anyhow::Context;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use sequoia_openpgp::{armor::Kind, serialize::SerializeInto, Cert};
+use std::collections::{hash_map::Entry, BTreeMap, HashMap, HashSet};
 use std::io::{ErrorKind, Write};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use time::OffsetDateTime;
 use tokio::fs;
+use url::Url;
 use walker_common::{
-    store::{store_document, Document, StoreError},
+    changes::ChangesFile,
+    clock::Clock,
+    encryption::Encryption,
+    retrieve::RetrievalTimings,
+    stage,
+    store::{
+        ensure_space, store_document, write_replace, write_replace_sync, Document, StoreError,
+    },
     utils::openpgp::PublicKey,
+    validate::evidence::ValidationEvidence,
 };
 
 pub const DIR_METADATA: &str = "metadata";
 
+/// The name of the statistics file, in the metadata directory
+pub const FILE_STATS: &str = "stats.json";
+
+/// The partition name for documents without a (parsable) TLP label
+pub const TLP_UNKNOWN: &str = "unknown";
+
+/// Characters to encode in the file names of a `changes.csv` file
+const CHANGES_ENCODE: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'.')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Partition stored documents by their TLP label.
+///
+/// Each document will be stored in a subdirectory of the store base, named after the lower-cased
+/// TLP label of the document (e.g. `clear` or `amber`). Each partition has the same layout as a
+/// non-partitioned store, including the metadata, so that it can be used as a
+/// [`crate::source::FileSource`] on its own.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TlpPartitioning {
+    /// Filesystem permissions (mode) of the partition directories, by lower-cased TLP label
+    pub permissions: HashMap<String, u32>,
+}
+
+impl TlpPartitioning {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn permissions<I>(mut self, permissions: I) -> Self
+    where
+        I: IntoIterator<Item = (String, u32)>,
+    {
+        self.permissions = HashMap::from_iter(permissions);
+        self
+    }
+
+    pub fn add_permission(mut self, label: impl Into<String>, mode: u32) -> Self {
+        self.permissions.insert(label.into().to_lowercase(), mode);
+        self
+    }
+}
+
+/// How to store documents, which have been withdrawn by their publisher
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum WithdrawnPolicy {
+    /// Store withdrawn documents like all others
+    #[default]
+    Keep,
+    /// Remove a stored copy, leaving a `.withdrawn` file (a [`Tombstone`]) in its place
+    Tombstone,
+    /// Remove a stored copy, and don't store withdrawn documents
+    Delete,
+}
+
+/// The record of a withdrawn document, stored by [`WithdrawnPolicy::Tombstone`]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tombstone {
+    /// The URL of the document
+    pub url: Url,
+    /// When the document was withdrawn, according to its modification timestamp
+    #[serde(with = "time::serde::rfc3339")]
+    pub withdrawn: OffsetDateTime,
+    /// The lifecycle information of the withdrawn document
+    pub lifecycle: Lifecycle,
+}
+
+/// The file name of the tombstone of a document
+pub fn tombstone_file(file: impl AsRef<Path>) -> String {
+    format!("{}.withdrawn", file.as_ref().display())
+}
+
 /// Stores all data so that it can be used as a [`crate::source::Source`] later.
 #[non_exhaustive]
 pub struct StoreVisitor {
@@ -28,6 +124,193 @@ pub struct StoreVisitor {
     /// whether to store additional metadata (like the etag) using extended attributes
     #[cfg(any(target_os = "linux", target_os = "macos"))]
     pub no_xattrs: bool,
+
+    /// encrypt documents for the recipients before storing them
+    pub encryption: Option<Encryption>,
+
+    /// partition documents by their TLP label
+    pub tlp: Option<TlpPartitioning>,
+
+    /// partitions which have already been initialized
+    tlp_partitions: Mutex<HashSet<String>>,
+
+    /// maintain a `changes.csv` and `index.txt` in the store root
+    pub changes: bool,
+
+    /// the changes files, by store root
+    changes_files: Arc<Mutex<HashMap<PathBuf, ChangesFile>>>,
+
+    /// store the differences to the previously stored version of a document
+    pub diffs: bool,
+
+    /// the layout of the distribution directories
+    pub layout: StoreLayout,
+
+    /// space to keep free on the filesystem of the store
+    pub min_free_space: Option<u64>,
+
+    /// how to store withdrawn documents
+    pub withdrawn: WithdrawnPolicy,
+
+    /// the clock, used for the timestamps of the statistics
+    pub clock: Clock,
+
+    /// statistics of the current run
+    stats: Arc<Mutex<Stats>>,
+}
+
+/// Statistics of a run, stored as `metadata/stats.json` in the store.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreStats {
+    /// Start of the run
+    #[serde(with = "time::serde::rfc3339")]
+    pub started: OffsetDateTime,
+    /// End of the run
+    #[serde(with = "time::serde::rfc3339")]
+    pub finished: OffsetDateTime,
+    /// Number of stored documents
+    pub documents: u64,
+    /// Number of bytes of the stored documents (before encryption)
+    pub bytes: u64,
+    /// Number of documents which could not be stored, by phase
+    pub errors: PhaseErrors,
+    /// Time spent, by phase, in seconds
+    pub durations: PhaseDurations,
+    /// Timings of retrieving the documents, by host
+    #[serde(default)]
+    pub hosts: BTreeMap<String, HostTimings>,
+}
+
+impl StoreStats {
+    /// Load the statistics of the previous run of a store, returning [`None`] if there are none.
+    pub fn load(base: &Path) -> anyhow::Result<Option<Self>> {
+        let path = base.join(DIR_METADATA).join(FILE_STATS);
+        let data = match std::fs::read(&path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err).with_context(|| format!("Failed to read: {}", path.display()))
+            }
+        };
+
+        serde_json::from_slice(&data)
+            .map(Some)
+            .with_context(|| format!("Failed to parse: {}", path.display()))
+    }
+}
+
+/// Timings of retrieving the documents of a host, in milliseconds
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostTimings {
+    /// Number of documents with timings
+    pub documents: u64,
+    /// Until the response headers were received
+    pub first_byte: Percentiles,
+    /// Until the body was received
+    pub total: Percentiles,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Percentiles {
+    pub p50: u64,
+    pub p95: u64,
+}
+
+impl Percentiles {
+    /// Calculate the percentiles (using the nearest rank) of a sorted list of values.
+    fn from_sorted(values: &[u64]) -> Self {
+        let rank = |p: usize| {
+            let n = (values.len() * p).div_ceil(100);
+            values.get(n.saturating_sub(1)).copied().unwrap_or_default()
+        };
+
+        Self {
+            p50: rank(50),
+            p95: rank(95),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseErrors {
+    /// Failed to retrieve the document
+    pub retrieval: u64,
+    /// Failed to validate the document (digest, signature, TLS)
+    pub validation: u64,
+    /// Failed to store the document
+    pub store: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseDurations {
+    /// The whole run
+    pub total: f64,
+    /// Storing documents, summed up over all documents
+    pub store: f64,
+}
+
+#[derive(Debug)]
+struct Stats {
+    started: OffsetDateTime,
+    start: Instant,
+    documents: u64,
+    bytes: u64,
+    errors: PhaseErrors,
+    store: Duration,
+    timings: HashMap<String, Vec<RetrievalTimings>>,
+}
+
+impl Stats {
+    fn new(clock: &Clock) -> Self {
+        Self {
+            started: clock.now_utc(),
+            start: Instant::now(),
+            documents: 0,
+            bytes: 0,
+            errors: Default::default(),
+            store: Duration::ZERO,
+            timings: Default::default(),
+        }
+    }
+
+    fn finish(&self, clock: &Clock) -> StoreStats {
+        StoreStats {
+            started: self.started,
+            finished: clock.now_utc(),
+            documents: self.documents,
+            bytes: self.bytes,
+            errors: self.errors.clone(),
+            durations: PhaseDurations {
+                total: self.start.elapsed().as_secs_f64(),
+                store: self.store.as_secs_f64(),
+            },
+            hosts: self
+                .timings
+                .iter()
+                .map(|(host, timings)| {
+                    let sorted = |f: fn(&RetrievalTimings) -> u64| {
+                        let mut values = timings.iter().map(f).collect::<Vec<_>>();
+                        values.sort_unstable();
+                        values
+                    };
+
+                    (
+                        host.clone(),
+                        HostTimings {
+                            documents: timings.len() as u64,
+                            first_byte: Percentiles::from_sorted(&sorted(|t| t.first_byte)),
+                            total: Percentiles::from_sorted(&sorted(|t| t.total)),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
 }
 
 impl StoreVisitor {
@@ -37,6 +320,17 @@ impl StoreVisitor {
             no_timestamps: false,
             #[cfg(any(target_os = "linux", target_os = "macos"))]
             no_xattrs: false,
+            encryption: None,
+            tlp: None,
+            tlp_partitions: Default::default(),
+            changes: false,
+            changes_files: Default::default(),
+            diffs: false,
+            layout: Default::default(),
+            min_free_space: None,
+            withdrawn: Default::default(),
+            clock: Clock::system(),
+            stats: Arc::new(Mutex::new(Stats::new(&Clock::system()))),
         }
     }
 
@@ -50,6 +344,147 @@ impl StoreVisitor {
         self.no_xattrs = no_xattrs;
         self
     }
+
+    pub fn encryption(mut self, encryption: impl Into<Option<Encryption>>) -> Self {
+        self.encryption = encryption.into();
+        self
+    }
+
+    pub fn tlp(mut self, tlp: impl Into<Option<TlpPartitioning>>) -> Self {
+        self.tlp = tlp.into();
+        self
+    }
+
+    /// Maintain a `changes.csv` and `index.txt` in the store root, listing all stored documents.
+    ///
+    /// This allows consuming the store as a directory based distribution, e.g. through
+    /// [`crate::source::HttpSource`]. The files get written by [`StoreFinisher::finish`].
+    pub fn changes(mut self, changes: bool) -> Self {
+        self.changes = changes;
+        self
+    }
+
+    /// Store the differences to the previously stored version of a document in a `.diff` file.
+    ///
+    /// The diff is only updated when the content of the document changed. It is not created for
+    /// encrypted documents, as the previous version cannot be read.
+    pub fn diffs(mut self, diffs: bool) -> Self {
+        self.diffs = diffs;
+        self
+    }
+
+    /// Set the layout of the distribution directories.
+    ///
+    /// Using [`StoreLayout::V2`], the directories are named after a hash of the distribution URL,
+    /// instead of the (long) percent-encoded URL. The mapping of directories to URLs is recorded
+    /// in the layout file of the metadata directory.
+    pub fn layout(mut self, layout: StoreLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Keep at least this amount of space (in bytes) free on the filesystem of the store.
+    ///
+    /// Before storing any document, the available space is checked against the amount of data
+    /// stored by the previous run. Storing a document fails if it would leave less space, instead
+    /// of running out of space while writing it.
+    pub fn min_free_space(mut self, min_free_space: impl Into<Option<u64>>) -> Self {
+        self.min_free_space = min_free_space.into();
+        self
+    }
+
+    /// Set how to store documents, which have been withdrawn by their publisher.
+    ///
+    /// Unless withdrawn documents are kept, a previously stored copy gets removed, and the
+    /// document is not stored.
+    pub fn withdrawn(mut self, withdrawn: WithdrawnPolicy) -> Self {
+        self.withdrawn = withdrawn;
+        self
+    }
+
+    /// Set the clock, used for the start and end time of the statistics.
+    ///
+    /// The run is considered to start when the clock is set.
+    pub fn clock(mut self, clock: Clock) -> Self {
+        self.stats = Arc::new(Mutex::new(Stats::new(&clock)));
+        self.clock = clock;
+        self
+    }
+
+    /// Get a handle for writing the results of the run, once the walk completed successfully.
+    ///
+    /// The handle stays available after the visitor has been moved into the walk.
+    pub fn finisher(&self) -> StoreFinisher {
+        StoreFinisher {
+            base: self.base.clone(),
+            clock: self.clock.clone(),
+            changes_files: self.changes_files.clone(),
+            stats: self.stats.clone(),
+        }
+    }
+
+    /// Write the results of the run, like [`StoreFinisher::finish`].
+    pub fn finish(self) -> anyhow::Result<StoreStats> {
+        self.finisher().finish()
+    }
+}
+
+/// Writes the results of a run of a [`StoreVisitor`]: the changes files (if enabled) and the
+/// statistics of the run.
+///
+/// Nothing gets written for a run which failed, or got cancelled, as long as this isn't called.
+pub struct StoreFinisher {
+    base: PathBuf,
+    clock: Clock,
+    changes_files: Arc<Mutex<HashMap<PathBuf, ChangesFile>>>,
+    stats: Arc<Mutex<Stats>>,
+}
+
+impl StoreFinisher {
+    /// Write the changes files and the statistics of the run, returning the statistics.
+    pub fn finish(self) -> anyhow::Result<StoreStats> {
+        let files = std::mem::take(
+            &mut *self
+                .changes_files
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+
+        for (root, mut changes) in files {
+            log::info!("Writing changes: {}", root.display());
+            changes
+                .write(&root)
+                .with_context(|| format!("Failed to write changes to {}", root.display()))?;
+        }
+
+        let stats = self
+            .stats
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .finish(&self.clock);
+        log::info!(
+            "Stored {} documents ({} bytes), {} errors",
+            stats.documents,
+            stats.bytes,
+            stats.errors.retrieval + stats.errors.validation + stats.errors.store
+        );
+
+        write_stats(&self.base, &stats)
+            .with_context(|| format!("Failed to write statistics to {}", self.base.display()))?;
+
+        Ok(stats)
+    }
+}
+
+/// Write the statistics of the run to the metadata directory.
+fn write_stats(base: &Path, stats: &StoreStats) -> anyhow::Result<()> {
+    let metadir = base.join(DIR_METADATA);
+    std::fs::create_dir_all(&metadir)?;
+
+    let data = serde_json::to_vec_pretty(stats)?;
+    write_replace_sync(&metadir.join(FILE_STATS), &data)?;
+
+    Ok(())
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -76,6 +511,7 @@ impl RetrievedVisitor for StoreVisitor {
         &self,
         context: &RetrievalContext<'_>,
     ) -> Result<Self::Context, Self::Error> {
+        self.preflight()?;
         self.store_provider_metadata(context.metadata).await?;
         self.prepare_distributions(context.metadata).await?;
         self.store_keys(context.keys).await?;
@@ -88,7 +524,14 @@ impl RetrievedVisitor for StoreVisitor {
         _context: &Self::Context,
         result: Result<RetrievedAdvisory, RetrievalError>,
     ) -> Result<(), Self::Error> {
-        self.store(&result?).await?;
+        let advisory = match result {
+            Ok(advisory) => advisory,
+            Err(err) => {
+                self.stats().errors.retrieval += 1;
+                return Err(err.into());
+            }
+        };
+        self.store_recorded(&advisory, None, None).await?;
         Ok(())
     }
 }
@@ -101,6 +544,7 @@ impl ValidatedVisitor for StoreVisitor {
         &self,
         context: &ValidationContext<'_>,
     ) -> Result<Self::Context, Self::Error> {
+        self.preflight()?;
         self.store_provider_metadata(context.metadata).await?;
         self.prepare_distributions(context.metadata).await?;
         self.store_keys(context.retrieval.keys).await?;
@@ -112,44 +556,119 @@ impl ValidatedVisitor for StoreVisitor {
         _context: &Self::Context,
         result: Result<ValidatedAdvisory, ValidationError>,
     ) -> Result<(), Self::Error> {
-        self.store(&result?.retrieved).await?;
+        let advisory = match result {
+            Ok(advisory) => advisory,
+            Err(err) => {
+                let mut stats = self.stats();
+                match err {
+                    ValidationError::Retrieval(_) => stats.errors.retrieval += 1,
+                    _ => stats.errors.validation += 1,
+                }
+                return Err(err.into());
+            }
+        };
+        self.store_recorded(
+            &advisory.retrieved,
+            Some(&advisory.evidence),
+            Some(&advisory.lifecycle),
+        )
+        .await?;
         Ok(())
     }
 }
 
 impl StoreVisitor {
-    async fn prepare_distributions(&self, metadata: &ProviderMetadata) -> Result<(), StoreError> {
-        for dist in &metadata.distributions {
-            if let Some(directory_url) = &dist.directory_url {
-                let base = distribution_base(&self.base, directory_url.as_str());
-                log::debug!("Creating base distribution directory: {}", base.display());
+    fn stats(&self) -> std::sync::MutexGuard<'_, Stats> {
+        self.stats.lock().unwrap_or_else(PoisonError::into_inner)
+    }
 
-                fs::create_dir_all(&base)
-                    .await
-                    .with_context(|| {
-                        format!(
-                            "Unable to create distribution directory: {}",
-                            base.display()
-                        )
-                    })
-                    .map_err(StoreError::Io)?;
+    /// Store an advisory, recording it in the statistics.
+    async fn store_recorded(
+        &self,
+        advisory: &RetrievedAdvisory,
+        evidence: Option<&ValidationEvidence>,
+        lifecycle: Option<&Lifecycle>,
+    ) -> Result<(), StoreError> {
+        let start = Instant::now();
+        let result = self.store(advisory, evidence, lifecycle).await;
+
+        let mut stats = self.stats();
+        stats.store += start.elapsed();
+        if let (Some(host), Some(timings)) = (
+            advisory.discovered.url.host_str(),
+            &advisory.metadata.timings,
+        ) {
+            stats
+                .timings
+                .entry(host.to_string())
+                .or_default()
+                .push(timings.clone());
+        }
+        match &result {
+            Ok(()) => {
+                stats.documents += 1;
+                stats.bytes += advisory.data.len() as u64;
             }
-            if let Some(rolie) = &dist.rolie {
-                for feed in &rolie.feeds {
-                    let base = distribution_base(&self.base, feed.url.as_str());
-                    fs::create_dir_all(&base)
-                        .await
-                        .with_context(|| {
-                            format!(
-                                "Unable to create distribution directory: {}",
-                                base.display()
-                            )
-                        })
-                        .map_err(StoreError::Io)?;
-                }
+            Err(_) => stats.errors.store += 1,
+        }
+
+        result
+    }
+
+    /// Check the available space before storing, expecting as much data as the previous run stored.
+    fn preflight(&self) -> Result<(), StoreError> {
+        let Some(reserve) = self.min_free_space else {
+            return Ok(());
+        };
+
+        let estimated = match StoreStats::load(&self.base) {
+            Ok(stats) => stats.map(|stats| stats.bytes).unwrap_or_default(),
+            Err(err) => {
+                log::warn!("Unable to estimate the required space: {err:#}");
+                0
+            }
+        };
+
+        ensure_space(&self.base, estimated, reserve)
+    }
+
+    async fn prepare_distributions(&self, metadata: &ProviderMetadata) -> Result<(), StoreError> {
+        if let Some(existing) = StoreLayout::detect(&self.base).map_err(StoreError::Io)? {
+            if existing != self.layout {
+                return Err(StoreError::Io(anyhow::anyhow!(
+                    "The store uses layout {existing}, instead of {}, it must be migrated first: {}",
+                    self.layout,
+                    self.base.display()
+                )));
             }
         }
 
+        let urls = distribution_urls(metadata);
+
+        for url in &urls {
+            let base = self.layout.distribution_base(&self.base, url);
+            log::debug!("Creating base distribution directory: {}", base.display());
+
+            fs::create_dir_all(&base)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Unable to create distribution directory: {}",
+                        base.display()
+                    )
+                })
+                .map_err(StoreError::Io)?;
+        }
+
+        // the v1 layout is the default, and doesn't need a layout file
+        if self.layout != StoreLayout::V1 {
+            let mut file = LayoutFile::load(&self.base)
+                .map_err(StoreError::Io)?
+                .unwrap_or_else(|| LayoutFile::new(self.layout));
+            file.extend(urls);
+            file.store(&self.base).map_err(StoreError::Io)?;
+        }
+
         Ok(())
     }
 
@@ -166,17 +685,13 @@ impl StoreVisitor {
             .map_err(StoreError::Io)?;
 
         let file = metadir.join("provider-metadata.json");
-        let mut out = std::fs::File::create(&file)
-            .with_context(|| {
-                format!(
-                    "Unable to open provider metadata file for writing: {}",
-                    file.display()
-                )
-            })
-            .map_err(StoreError::Io)?;
-        serde_json::to_writer_pretty(&mut out, metadata)
+        let data = serde_json::to_vec_pretty(metadata)
             .context("Failed serializing provider metadata")
             .map_err(StoreError::Io)?;
+        write_replace(&file, &data)
+            .await
+            .with_context(|| format!("Unable to write provider metadata file: {}", file.display()))
+            .map_err(StoreError::Io)?;
         Ok(())
     }
 
@@ -209,24 +724,87 @@ impl StoreVisitor {
 
         let data = Self::serialize_key(cert).map_err(StoreError::SerializeKey)?;
 
-        fs::write(&name, data)
+        write_replace(&name, &data)
             .await
             .with_context(|| format!("Failed to store key: {}", name.display()))
             .map_err(StoreError::Io)?;
         Ok(())
     }
 
+    /// Get (and initialize if necessary) the TLP partition for the advisory.
+    fn tlp_partition(
+        &self,
+        tlp: &TlpPartitioning,
+        advisory: &RetrievedAdvisory,
+    ) -> Result<PathBuf, StoreError> {
+        let label = advisory
+            .tlp_label()
+            .unwrap_or_else(|| TLP_UNKNOWN.to_string());
+        let partition = self.base.join(&label);
+
+        let new = self
+            .tlp_partitions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(label.clone());
+
+        if new {
+            log::debug!("Initializing TLP partition: {}", partition.display());
+
+            std::fs::create_dir_all(&partition)
+                .with_context(|| {
+                    format!(
+                        "Unable to create TLP partition directory: {}",
+                        partition.display()
+                    )
+                })
+                .map_err(StoreError::Io)?;
+
+            #[cfg(unix)]
+            if let Some(mode) = tlp.permissions.get(&label) {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&partition, std::fs::Permissions::from_mode(*mode))
+                    .with_context(|| {
+                        format!(
+                            "Unable to set permissions of TLP partition: {}",
+                            partition.display()
+                        )
+                    })
+                    .map_err(StoreError::Io)?;
+            }
+            #[cfg(not(unix))]
+            let _ = tlp;
+
+            // every partition gets a copy of the metadata
+            copy_dir(&self.base.join(DIR_METADATA), &partition.join(DIR_METADATA))
+                .with_context(|| format!("Unable to copy metadata to: {}", partition.display()))
+                .map_err(StoreError::Io)?;
+        }
+
+        Ok(partition)
+    }
+
     fn serialize_key(cert: &Cert) -> Result<Vec<u8>, anyhow::Error> {
         let mut writer = sequoia_openpgp::armor::Writer::new(Vec::new(), Kind::PublicKey)?;
         writer.write_all(&cert.to_vec()?)?;
         Ok(writer.finalize()?)
     }
 
-    async fn store(&self, advisory: &RetrievedAdvisory) -> Result<(), StoreError> {
-        log::info!(
-            "Storing: {} (modified: {:?})",
-            advisory.url,
-            advisory.metadata.last_modification
+    /// Store an advisory, using its lifecycle information if known. Otherwise, it gets parsed
+    /// when needed.
+    async fn store(
+        &self,
+        advisory: &RetrievedAdvisory,
+        evidence: Option<&ValidationEvidence>,
+        lifecycle: Option<&Lifecycle>,
+    ) -> Result<(), StoreError> {
+        tracing::info!(
+            target: stage::STORE,
+            stage = "store",
+            url = %advisory.url,
+            distribution = %advisory.context.url(),
+            modified = ?advisory.metadata.last_modification,
+            "Storing advisory"
         );
 
         let relative_url_result = advisory.context.url().make_relative(&advisory.url);
@@ -235,12 +813,36 @@ impl StoreVisitor {
             None => return Err(StoreError::Filename(advisory.url.to_string())),
         };
 
+        let base = match &self.tlp {
+            Some(tlp) => self.tlp_partition(tlp, advisory)?,
+            None => self.base.clone(),
+        };
+
         // create a distribution base
-        let distribution_base = distribution_base(&self.base, advisory.context.url().as_str());
+        let distribution_base = self
+            .layout
+            .distribution_base(&base, advisory.context.url().as_str());
 
         // put the file there
         let file = distribution_base.join(name);
 
+        if self.withdrawn != WithdrawnPolicy::Keep {
+            let lifecycle = match lifecycle {
+                Some(lifecycle) => lifecycle.clone(),
+                None => Lifecycle::parse(&advisory.data),
+            };
+            if lifecycle.withdrawn {
+                return self
+                    .store_withdrawn(advisory, &base, &file, lifecycle)
+                    .await;
+            }
+        }
+
+        let diff = match self.diffs && self.encryption.is_none() {
+            true => Self::diff(&file, &advisory.data).await,
+            false => None,
+        };
+
         store_document(
             &file,
             Document {
@@ -253,10 +855,260 @@ impl StoreVisitor {
                 no_timestamps: self.no_timestamps,
                 #[cfg(any(target_os = "linux", target_os = "macos"))]
                 no_xattrs: self.no_xattrs,
+                encryption: self.encryption.as_ref(),
+                evidence,
+                min_free_space: self.min_free_space,
             },
         )
         .await?;
 
+        if let Some(diff) = diff {
+            let diff_file = diff_file(&file);
+            let data = serde_json::to_vec_pretty(&diff)
+                .context("Failed to serialize diff")
+                .map_err(StoreError::Io)?;
+            write_replace(diff_file.as_ref(), &data)
+                .await
+                .with_context(|| format!("Failed to write diff: {diff_file}"))
+                .map_err(StoreError::Io)?;
+        }
+
+        if let Some(origin) = &advisory.aggregator {
+            let origin_file = origin_file(&file);
+            let data = serde_json::to_vec_pretty(origin)
+                .context("Failed to serialize origin")
+                .map_err(StoreError::Io)?;
+            write_replace(origin_file.as_ref(), &data)
+                .await
+                .with_context(|| format!("Failed to write origin: {origin_file}"))
+                .map_err(StoreError::Io)?;
+        }
+
+        // a document might be published again, after it was withdrawn
+        if self.withdrawn == WithdrawnPolicy::Tombstone {
+            let tombstone_file = tombstone_file(&file);
+            match fs::remove_file(&tombstone_file).await {
+                Ok(()) => {
+                    log::info!("Removed tombstone of re-published document: {tombstone_file}")
+                }
+                Err(err) if err.kind() == ErrorKind::NotFound => {}
+                Err(err) => {
+                    return Err(StoreError::Io(anyhow::anyhow!(
+                        "Failed to remove tombstone {tombstone_file}: {err}"
+                    )))
+                }
+            }
+        }
+
+        if self.layout == StoreLayout::Content {
+            link_object(&base, &file).map_err(StoreError::Io)?;
+        }
+
+        if self.changes {
+            self.record_change(&base, &file, advisory.modified.into())?;
+        }
+
+        Ok(())
+    }
+
+    /// Remove the stored copy of a withdrawn document, leaving a tombstone if requested.
+    async fn store_withdrawn(
+        &self,
+        advisory: &RetrievedAdvisory,
+        root: &Path,
+        file: &Path,
+        lifecycle: Lifecycle,
+    ) -> Result<(), StoreError> {
+        let existed = remove_stored(file).map_err(StoreError::Io)?;
+        match existed {
+            true => log::info!("Removed withdrawn document: {}", file.display()),
+            false => log::info!("Not storing withdrawn document: {}", advisory.url),
+        }
+
+        if self.withdrawn == WithdrawnPolicy::Tombstone {
+            let tombstone_file = tombstone_file(file);
+            let tombstone = Tombstone {
+                url: advisory.url.clone(),
+                withdrawn: advisory.modified.into(),
+                lifecycle,
+            };
+            let data = serde_json::to_vec_pretty(&tombstone)
+                .context("Failed to serialize tombstone")
+                .map_err(StoreError::Io)?;
+            write_replace(tombstone_file.as_ref(), &data)
+                .await
+                .with_context(|| format!("Failed to write tombstone: {tombstone_file}"))
+                .map_err(StoreError::Io)?;
+        }
+
+        if self.changes {
+            self.update_changes(root, file, |changes, name| {
+                changes.remove(&name);
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Compute the difference to the previously stored version of a document.
+    ///
+    /// Returns [`None`] if there is no previous version, it is unchanged, or any version cannot
+    /// be parsed.
+    async fn diff(file: &Path, data: &[u8]) -> Option<AdvisoryDiff> {
+        let previous = match fs::read(file).await {
+            Ok(previous) => previous,
+            Err(err) => {
+                if err.kind() != ErrorKind::NotFound {
+                    log::warn!(
+                        "Failed to read previous version ({}): {err}",
+                        file.display()
+                    );
+                }
+                return None;
+            }
+        };
+
+        if previous == data {
+            return None;
+        }
+
+        let previous = serde_json::from_slice::<serde_json::Value>(&previous).ok()?;
+        let current = serde_json::from_slice::<serde_json::Value>(data).ok()?;
+
+        let diff = AdvisoryDiff::new(&previous, &current);
+        (!diff.is_empty()).then_some(diff)
+    }
+
+    /// Record a stored document for the `changes.csv` of the store root.
+    fn record_change(
+        &self,
+        root: &Path,
+        file: &Path,
+        timestamp: OffsetDateTime,
+    ) -> Result<(), StoreError> {
+        self.update_changes(root, file, |changes, name| changes.insert(name, timestamp))
+    }
+
+    /// Update the changes of the store root, with the name of a document.
+    fn update_changes(
+        &self,
+        root: &Path,
+        file: &Path,
+        f: impl FnOnce(&mut ChangesFile, String),
+    ) -> Result<(), StoreError> {
+        let Some(name) = changes_name(root, file) else {
+            return Err(StoreError::Filename(file.display().to_string()));
+        };
+
+        let mut files = self
+            .changes_files
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let changes = match files.entry(root.to_path_buf()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(
+                ChangesFile::load(root)
+                    .with_context(|| format!("Failed to load existing changes: {}", root.display()))
+                    .map_err(StoreError::Io)?,
+            ),
+        };
+
+        f(changes, name);
+
         Ok(())
     }
 }
+
+/// The name of a stored document in the `changes.csv` file of the store root.
+pub fn changes_name(root: &Path, file: &Path) -> Option<String> {
+    let relative = file.strip_prefix(root).ok()?;
+
+    Some(
+        relative
+            .iter()
+            .map(|segment| {
+                utf8_percent_encode(&segment.to_string_lossy(), CHANGES_ENCODE).to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+/// Recursively copy a directory, ignoring a missing source.
+pub fn copy_dir(source: &Path, target: &Path) -> std::io::Result<()> {
+    let entries = match std::fs::read_dir(source) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+
+    std::fs::create_dir_all(target)?;
+
+    for entry in entries {
+        let entry = entry?;
+        let target = target.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            // replace, as the target may be hard linked into a live store by staging
+            write_replace_sync(&target, &std::fs::read(entry.path())?)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use walker_common::store::Staging;
+
+    #[test]
+    fn test_copy_dir_staged() {
+        let dir = tempfile::tempdir().expect("must create temporary directory");
+        let target = dir.path().join("store");
+        let source = dir.path().join("source");
+        std::fs::create_dir_all(target.join(DIR_METADATA)).expect("must create directory");
+        std::fs::create_dir_all(&source).expect("must create directory");
+        std::fs::write(target.join(DIR_METADATA).join("metadata.json"), b"live")
+            .expect("must write");
+        std::fs::write(source.join("metadata.json"), b"new").expect("must write");
+
+        let staging = Staging::new(&target);
+        let path = staging.prepare().expect("must prepare");
+
+        copy_dir(&source, &path.join(DIR_METADATA)).expect("must copy");
+        assert_eq!(
+            std::fs::read(path.join(DIR_METADATA).join("metadata.json")).expect("must read"),
+            b"new"
+        );
+        assert_eq!(
+            std::fs::read(target.join(DIR_METADATA).join("metadata.json")).expect("must read"),
+            b"live"
+        );
+    }
+
+    #[test]
+    fn test_finish() {
+        let dir = tempfile::tempdir().expect("must create temporary directory");
+        let stats = dir.path().join(DIR_METADATA).join(FILE_STATS);
+
+        // a failed run doesn't write anything
+        drop(StoreVisitor::new(dir.path()).changes(true));
+        assert!(!stats.exists());
+
+        let store = StoreVisitor::new(dir.path()).changes(true);
+        let finisher = store.finisher();
+        drop(store);
+
+        let result = finisher.finish().expect("must finish");
+        assert_eq!(result.documents, 0);
+        assert_eq!(
+            StoreStats::load(dir.path())
+                .expect("must load")
+                .map(|stats| stats.documents),
+            Some(0)
+        );
+    }
+}
@@ -1,15 +1,35 @@
 //! The actual walker
 
 use crate::discover::{
-    DiscoveredAdvisory, DiscoveredContext, DiscoveredVisitor, DistributionContext,
+    AggregatorOrigin, DiscoveredAdvisory, DiscoveredContext, DiscoveredVisitor,
+    DistributionContext, Provenance,
 };
-use crate::model::metadata::Distribution;
+use crate::model::{
+    aggregator::AggregatorMetadata,
+    metadata::{Distribution, ProviderMetadata},
+};
+use crate::retrieve::RetrievedAdvisory;
 use crate::source::Source;
-use futures::{stream, Stream, StreamExt, TryFutureExt, TryStream, TryStreamExt};
-use std::fmt::Debug;
+use crate::validation::{
+    validate_offloaded, ValidatedAdvisory, ValidationError, ValidationProcessError,
+};
+use futures::{stream, Stream, StreamExt, TryFutureExt, TryStreamExt};
+use std::fmt::{Debug, Display};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use url::ParseError;
-use walker_common::progress::Progress;
+use walker_common::{
+    checkpoint::Checkpoint,
+    concurrency::AdaptiveConcurrency,
+    deadline::{Completion, Deadline},
+    progress::Progress,
+    run::{run_id, CorrelationId, WalkSummary},
+    validate::{
+        source::{KeySource, KeySourceError},
+        ValidationOptions,
+    },
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error<VE, SE>
@@ -23,14 +43,102 @@ where
     Url(#[from] ParseError),
     #[error("Visitor error: {0}")]
     Visitor(VE),
+    #[error("Failed to process {provenance}: {err}")]
+    Advisory {
+        provenance: Box<Provenance>,
+        err: VE,
+    },
+    #[error("Deadline exceeded: {0}")]
+    Deadline(Completion),
+}
+
+impl<VE, SE> Error<VE, SE>
+where
+    VE: std::fmt::Display + Debug,
+    SE: std::fmt::Display + Debug,
+{
+    fn advisory(provenance: Provenance, err: VE) -> Self {
+        Self::Advisory {
+            provenance: Box::new(provenance),
+            err,
+        }
+    }
+
+    /// The origin of the advisory which failed to process, if the error is specific to one
+    pub fn provenance(&self) -> Option<&Provenance> {
+        match self {
+            Self::Advisory { provenance, .. } => Some(provenance),
+            _ => None,
+        }
+    }
 }
 
 pub type DistributionFilter = Box<dyn Fn(&DistributionContext) -> bool>;
 
+/// Hooks, called by the [`Walker`] at points of its lifecycle.
+///
+/// All hooks default to doing nothing, so that only the relevant ones need to be implemented.
+pub trait WalkerHooks {
+    /// Called once the provider metadata was loaded, before walking any distribution.
+    fn on_start(&self, _context: &StartContext<'_>) {}
+
+    /// Called once the index of a distribution was loaded, before walking its advisories.
+    ///
+    /// The hook may change the index, e.g. by removing advisories, or clearing it to skip the
+    /// distribution.
+    fn on_distribution_start(&self, _context: DistributionStart<'_>) {}
+
+    /// Called once all advisories of a distribution have been processed.
+    fn on_distribution_end(&self, _context: &DistributionEnd<'_>) {}
+
+    /// Called once the walk finished, successfully or not.
+    fn on_end(&self, _context: &EndContext<'_>) {}
+}
+
+/// The context of [`WalkerHooks::on_start`]
+pub struct StartContext<'a> {
+    pub correlation_id: &'a CorrelationId,
+    pub metadata: &'a ProviderMetadata,
+    /// The distributions which will be walked, after applying the distribution filter
+    pub distributions: &'a [DistributionContext],
+}
+
+/// The context of [`WalkerHooks::on_distribution_start`]
+pub struct DistributionStart<'a> {
+    pub metadata: &'a ProviderMetadata,
+    pub distribution: &'a DistributionContext,
+    /// The advisories of the distribution, which will be walked
+    pub index: &'a mut Vec<DiscoveredAdvisory>,
+}
+
+/// The context of [`WalkerHooks::on_distribution_end`]
+pub struct DistributionEnd<'a> {
+    pub metadata: &'a ProviderMetadata,
+    pub distribution: &'a DistributionContext,
+    /// The number of advisories walked, not including those skipped by a checkpoint
+    pub advisories: usize,
+    /// The time since loading the index of the distribution started
+    pub duration: Duration,
+}
+
+/// The context of [`WalkerHooks::on_end`]
+pub struct EndContext<'a> {
+    pub correlation_id: &'a CorrelationId,
+    pub duration: Duration,
+    /// The error which failed the walk, if it failed
+    pub error: Option<&'a dyn Display>,
+}
+
 pub struct Walker<S: Source> {
     source: S,
     progress: Progress,
     distribution_filter: Option<DistributionFilter>,
+    deadline: Option<Instant>,
+    adaptive: Option<Arc<AdaptiveConcurrency>>,
+    checkpoint: Option<Arc<Checkpoint>>,
+    correlation_id: CorrelationId,
+    aggregator: Option<Arc<AggregatorMetadata>>,
+    hooks: Vec<Arc<dyn WalkerHooks>>,
 }
 
 impl<S: Source> Walker<S> {
@@ -39,6 +147,12 @@ impl<S: Source> Walker<S> {
             source,
             progress: Progress::default(),
             distribution_filter: None,
+            deadline: None,
+            adaptive: None,
+            checkpoint: None,
+            correlation_id: run_id().clone(),
+            aggregator: None,
+            hooks: vec![],
         }
     }
 
@@ -59,32 +173,139 @@ impl<S: Source> Walker<S> {
         self
     }
 
+    /// Set a deadline for walking.
+    ///
+    /// Once the deadline has passed, no more advisories will be processed. Advisories already
+    /// being processed will be completed. The walk then fails with [`Error::Deadline`], reporting
+    /// how much was completed.
+    pub fn with_deadline(mut self, deadline: impl Into<Option<Instant>>) -> Self {
+        self.deadline = deadline.into();
+        self
+    }
+
+    /// Adapt the number of concurrent tasks of [`Self::walk_parallel`] using a controller.
+    ///
+    /// The limit of [`Self::walk_parallel`] is still applied as an upper bound. The controller
+    /// should also be used by the fetcher of the source, so that it gets informed about the
+    /// outcome of requests.
+    pub fn with_adaptive_concurrency(
+        mut self,
+        adaptive: impl Into<Option<Arc<AdaptiveConcurrency>>>,
+    ) -> Self {
+        self.adaptive = adaptive.into();
+        self
+    }
+
+    /// Record the progress within each distribution in a checkpoint.
+    ///
+    /// Entries of a distribution which have been processed according to the checkpoint are
+    /// skipped, so that an interrupted walk of a large distribution can be resumed. Once the
+    /// walk completed, the checkpoint is cleared. This only applies to [`Self::walk`] and
+    /// [`Self::walk_parallel`].
+    pub fn with_checkpoint(mut self, checkpoint: impl Into<Option<Arc<Checkpoint>>>) -> Self {
+        self.checkpoint = checkpoint.into();
+        self
+    }
+
+    /// Set the correlation ID of the walk, reported in the [`WalkSummary`].
+    ///
+    /// This defaults to the ID of the run, see [`run_id`]. The fetcher of the source should use
+    /// the same ID, so that it gets sent with each request.
+    pub fn with_correlation_id(mut self, correlation_id: CorrelationId) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Attribute discovered advisories to the listing of the provider by an aggregator.
+    ///
+    /// Each [`DiscoveredAdvisory`] carries the aggregator, the listed provider, and the mirror it
+    /// was walked through, if any. If the provider is not listed by the aggregator, the
+    /// advisories are not attributed.
+    pub fn with_aggregator(
+        mut self,
+        aggregator: impl Into<Option<Arc<AggregatorMetadata>>>,
+    ) -> Self {
+        self.aggregator = aggregator.into();
+        self
+    }
+
+    /// Add hooks, which get called at points of the lifecycle of the walk.
+    ///
+    /// Hooks are called in the order they were added. This only applies to [`Self::walk`] and
+    /// [`Self::walk_parallel`].
+    pub fn with_hooks(mut self, hooks: impl WalkerHooks + 'static) -> Self {
+        self.hooks.push(Arc::new(hooks));
+        self
+    }
+
     fn collect_distributions(&self, distributions: Vec<Distribution>) -> Vec<DistributionContext> {
-        distributions
-            .into_iter()
-            .flat_map(|distribution| {
-                distribution
-                    .rolie
-                    .into_iter()
-                    .flat_map(|rolie| rolie.feeds)
-                    .map(|feed| DistributionContext::Feed(feed.url))
-                    .chain(
-                        distribution
-                            .directory_url
-                            .map(DistributionContext::Directory),
-                    )
-            })
-            .filter(|distribution| {
-                if let Some(filter) = &self.distribution_filter {
-                    filter(distribution)
-                } else {
-                    true
-                }
-            })
-            .collect()
+        collect_distributions(self.distribution_filter.as_ref(), distributions)
     }
 
-    pub async fn walk<V>(self, visitor: V) -> Result<(), Error<V::Error, S::Error>>
+    fn start(&self, metadata: &ProviderMetadata, distributions: &[DistributionContext]) {
+        let context = StartContext {
+            correlation_id: &self.correlation_id,
+            metadata,
+            distributions,
+        };
+        for hooks in &self.hooks {
+            hooks.on_start(&context);
+        }
+    }
+
+    fn start_distribution(
+        &self,
+        metadata: &ProviderMetadata,
+        distribution: &DistributionContext,
+        index: &mut Vec<DiscoveredAdvisory>,
+    ) {
+        for hooks in &self.hooks {
+            hooks.on_distribution_start(DistributionStart {
+                metadata,
+                distribution,
+                index,
+            });
+        }
+    }
+
+    fn end_distribution(
+        &self,
+        metadata: &ProviderMetadata,
+        distribution: &DistributionContext,
+        advisories: usize,
+        started: Instant,
+    ) {
+        let context = DistributionEnd {
+            metadata,
+            distribution,
+            advisories,
+            duration: started.elapsed(),
+        };
+        for hooks in &self.hooks {
+            hooks.on_distribution_end(&context);
+        }
+    }
+
+    pub async fn walk<V>(self, visitor: V) -> Result<WalkSummary, Error<V::Error, S::Error>>
+    where
+        V: DiscoveredVisitor,
+    {
+        let start = Instant::now();
+        let checkpoint = self.checkpoint.clone();
+        let correlation_id = self.correlation_id.clone();
+        let hooks = self.hooks.clone();
+        log::info!("Walking, correlation ID: {correlation_id}");
+
+        let result = self.walk_sequential(visitor).await;
+        finish_checkpoint(checkpoint.as_deref(), &result);
+        end(&hooks, &correlation_id, start.elapsed(), &result);
+        result.map(|()| WalkSummary {
+            correlation_id,
+            duration: start.elapsed(),
+        })
+    }
+
+    async fn walk_sequential<V>(self, visitor: V) -> Result<(), Error<V::Error, S::Error>>
     where
         V: DiscoveredVisitor,
     {
@@ -97,20 +318,35 @@ impl<S: Source> Walker<S> {
             .await
             .map_err(Error::Visitor)?;
 
-        let distributions = self.collect_distributions(metadata.distributions);
+        let deadline = self.deadline.map(Deadline::new);
+        let origin = origin(self.aggregator.as_deref(), &metadata);
+
+        let distributions = self.collect_distributions(metadata.distributions.clone());
         log::info!("processing {} distribution URLs", distributions.len());
+        self.start(&metadata, &distributions);
 
         for distribution in distributions {
+            if let Some(deadline) = deadline.as_ref().filter(|d| d.is_exceeded()) {
+                deadline.stop_discovery();
+                break;
+            }
+
             log::info!("Walking directory URL: {:?}", distribution);
-            let index = self
+            let started = Instant::now();
+            let key = distribution.url().to_string();
+            let mut index = self
                 .source
-                .load_index(distribution)
+                .load_index(distribution.clone())
                 .await
                 .map_err(Error::Source)?;
+            attribute_all(&mut index, origin.as_ref());
+            self.start_distribution(&metadata, &distribution, &mut index);
 
-            let progress = self.progress.start(index.len());
+            let skip = resume(self.checkpoint.as_deref(), &key, &index);
+            let advisories = index.len() - skip;
+            let progress = self.progress.start(advisories);
 
-            for advisory in index {
+            for (n, advisory) in index.into_iter().enumerate().skip(skip) {
                 log::debug!("  Discovered advisory: {advisory:?}");
                 progress.set_message(
                     advisory
@@ -122,21 +358,55 @@ impl<S: Source> Walker<S> {
                         .to_string()
                         .into(),
                 );
+                if !admit(deadline.as_ref(), &advisory) {
+                    progress.tick();
+                    continue;
+                }
+                let url = advisory.url.clone();
+                let provenance = Provenance::new(&metadata.canonical_url, &advisory);
                 visitor
                     .visit_advisory(&context, advisory)
                     .await
-                    .map_err(Error::Visitor)?;
+                    .map_err(|err| Error::advisory(provenance, err))?;
+                if let Some(checkpoint) = &self.checkpoint {
+                    checkpoint.record(&key, n, url.as_str());
+                }
                 progress.tick();
             }
+
+            self.end_distribution(&metadata, &distribution, advisories, started);
         }
 
-        Ok(())
+        finish(deadline)
     }
 
     pub async fn walk_parallel<V>(
         self,
         limit: usize,
         visitor: V,
+    ) -> Result<WalkSummary, Error<V::Error, S::Error>>
+    where
+        V: DiscoveredVisitor,
+    {
+        let start = Instant::now();
+        let checkpoint = self.checkpoint.clone();
+        let correlation_id = self.correlation_id.clone();
+        let hooks = self.hooks.clone();
+        log::info!("Walking, correlation ID: {correlation_id}");
+
+        let result = self.walk_concurrent(limit, visitor).await;
+        finish_checkpoint(checkpoint.as_deref(), &result);
+        end(&hooks, &correlation_id, start.elapsed(), &result);
+        result.map(|()| WalkSummary {
+            correlation_id,
+            duration: start.elapsed(),
+        })
+    }
+
+    async fn walk_concurrent<V>(
+        self,
+        limit: usize,
+        visitor: V,
     ) -> Result<(), Error<V::Error, S::Error>>
     where
         V: DiscoveredVisitor,
@@ -151,59 +421,420 @@ impl<S: Source> Walker<S> {
 
         let context = Arc::new(context);
         let visitor = Arc::new(visitor);
+        let deadline = self.deadline.map(Deadline::new);
+        let origin = origin(self.aggregator.as_deref(), &metadata);
 
-        let distributions = self.collect_distributions(metadata.distributions);
+        let distributions = self.collect_distributions(metadata.distributions.clone());
         log::info!("processing {} distribution URLs", distributions.len());
+        self.start(&metadata, &distributions);
 
-        let advisories: Vec<_> = collect_advisories::<V, S>(&self.source, distributions)
-            .try_collect()
-            .await?;
+        let mut advisories = vec![];
+        for distribution in distributions {
+            // loading the indexes (e.g. of a slow aggregator) counts against the deadline too
+            if let Some(deadline) = deadline.as_ref().filter(|d| d.is_exceeded()) {
+                deadline.stop_discovery();
+                break;
+            }
+
+            log::debug!("Walking: {}", distribution.url());
+            let started = Instant::now();
+            let key: Arc<str> = distribution.url().as_str().into();
+            let mut index = self
+                .source
+                .load_index(distribution.clone())
+                .await
+                .map_err(Error::Source)?;
+            attribute_all(&mut index, origin.as_ref());
+            self.start_distribution(&metadata, &distribution, &mut index);
+
+            let skip = resume(self.checkpoint.as_deref(), &key, &index);
+            let pending = Arc::new(PendingDistribution::new(
+                distribution,
+                index.len() - skip,
+                started,
+            ));
+            if pending.advisories == 0 {
+                pending.end(&self, &metadata);
+            }
+
+            advisories.extend(
+                index
+                    .into_iter()
+                    .enumerate()
+                    .skip(skip)
+                    .map(|(n, advisory)| (key.clone(), n, advisory, pending.clone())),
+            );
+        }
 
         let size = advisories.len();
         log::info!("Discovered {size} advisories");
 
         stream::iter(self.progress.wrap_iter(size, advisories.into_iter()))
             .map(Ok)
-            .try_for_each_concurrent(limit, |advisory| {
+            .try_for_each_concurrent(limit, |(key, n, advisory, pending)| {
                 log::debug!("Discovered advisory: {}", advisory.url);
+                let admitted = admit(deadline.as_ref(), &advisory);
+                let adaptive = self.adaptive.clone();
+                let checkpoint = self.checkpoint.clone();
                 let context = context.clone();
                 let visitor = visitor.clone();
+                let provenance = Provenance::new(&metadata.canonical_url, &advisory);
+                let walker = &self;
+                let metadata = &metadata;
 
                 async move {
+                    if !admitted {
+                        pending.complete(walker, metadata);
+                        return Ok(());
+                    }
+                    let _permit = match adaptive {
+                        Some(adaptive) => Some(adaptive.acquire().await),
+                        None => None,
+                    };
+                    let url = advisory.url.clone();
                     visitor
-                        .visit_advisory(&context, advisory.clone())
-                        .map_err(Error::Visitor)
-                        .await
+                        .visit_advisory(&context, advisory)
+                        .map_err(|err| Error::advisory(provenance, err))
+                        .await?;
+                    if let Some(checkpoint) = checkpoint {
+                        checkpoint.record(&key, n, url.as_str());
+                    }
+                    pending.complete(walker, metadata);
+                    Ok(())
                 }
             })
             .await?;
 
-        Ok(())
+        finish(deadline)
+    }
+
+    /// Discover advisories as a stream.
+    ///
+    /// Compared to [`Self::walk`], this doesn't report progress. Index files are only loaded
+    /// when the previous one got consumed.
+    pub fn discover_stream(self) -> impl Stream<Item = Result<DiscoveredAdvisory, S::Error>> {
+        let Self {
+            source,
+            distribution_filter,
+            aggregator,
+            ..
+        } = self;
+
+        stream::once(async move {
+            let metadata = source.load_metadata().await?;
+            let origin = origin(aggregator.as_deref(), &metadata);
+            let distributions =
+                collect_distributions(distribution_filter.as_ref(), metadata.distributions);
+            log::info!("processing {} distribution URLs", distributions.len());
+            Ok::<_, S::Error>(discover(source, distributions, origin))
+        })
+        .try_flatten()
+    }
+
+    /// Retrieve advisories as a stream.
+    ///
+    /// Up to `limit` advisories will be retrieved concurrently, keeping the order of discovery.
+    pub fn retrieve_stream(
+        self,
+        limit: usize,
+    ) -> impl Stream<Item = Result<RetrievedAdvisory, S::Error>> {
+        let source = self.source.clone();
+
+        self.discover_stream()
+            .map(move |discovered| {
+                let source = source.clone();
+                async move { source.load_advisory(discovered?).await }
+            })
+            .buffered(limit.max(1))
+    }
+
+    /// Retrieve and validate advisories as a stream.
+    ///
+    /// Up to `limit` advisories will be retrieved concurrently, keeping the order of discovery.
+    /// Advisories failing validation are reported as inner error, like to a [`ValidatedVisitor`].
+    ///
+    /// [`ValidatedVisitor`]: crate::validation::ValidatedVisitor
+    pub fn validate_stream(
+        self,
+        limit: usize,
+        options: impl Into<ValidationOptions>,
+    ) -> impl Stream<
+        Item = Result<
+            Result<ValidatedAdvisory, ValidationError>,
+            StreamError<<S as Source>::Error, <S as KeySource>::Error>,
+        >,
+    >
+    where
+        S: KeySource,
+    {
+        let options = Arc::new(options.into());
+        let Self {
+            source,
+            distribution_filter,
+            aggregator,
+            ..
+        } = self;
+
+        stream::once(async move {
+            let metadata = source.load_metadata().await.map_err(StreamError::Source)?;
+
+            let mut keys = Vec::with_capacity(metadata.public_openpgp_keys.len());
+            for key in &metadata.public_openpgp_keys {
+                keys.push(
+                    source
+                        .load_public_key(key.into())
+                        .await
+                        .map_err(StreamError::KeySource)?,
+                );
+            }
+            let keys = Arc::new(keys);
+            let origin = origin(aggregator.as_deref(), &metadata);
+
+            let distributions =
+                collect_distributions(distribution_filter.as_ref(), metadata.distributions);
+            log::info!("processing {} distribution URLs", distributions.len());
+
+            Ok::<_, StreamError<_, _>>(
+                discover(source.clone(), distributions, origin)
+                    .map(move |discovered| {
+                        let source = source.clone();
+                        let keys = keys.clone();
+                        let options = options.clone();
+                        async move {
+                            let retrieved = source
+                                .load_advisory(discovered.map_err(StreamError::Source)?)
+                                .await
+                                .map_err(StreamError::Source)?;
+
+                            match validate_offloaded(&options, &keys, retrieved).await {
+                                Ok(validated) => Ok(Ok(validated)),
+                                Err(ValidationProcessError::Proceed(err)) => Ok(Err(err)),
+                                Err(ValidationProcessError::Abort(err)) => {
+                                    Err(StreamError::Validation(err))
+                                }
+                            }
+                        }
+                    })
+                    .buffered(limit.max(1)),
+            )
+        })
+        .try_flatten()
     }
 }
 
-#[allow(clippy::needless_lifetimes)] // false positive
-fn collect_sources<'s, V: DiscoveredVisitor, S: Source>(
-    source: &'s S,
-    discover_contexts: Vec<DistributionContext>,
-) -> impl TryStream<Ok = impl Stream<Item = DiscoveredAdvisory>, Error = Error<V::Error, S::Error>> + 's
+/// An error of the stream based API
+#[derive(Debug, thiserror::Error)]
+pub enum StreamError<SE, KSE>
+where
+    SE: std::fmt::Display + Debug,
+    KSE: std::fmt::Display + Debug,
 {
-    stream::iter(discover_contexts).then(move |discover_context| async move {
-        log::debug!("Walking: {}", discover_context.url());
-        Ok(stream::iter(
-            source
-                .load_index(discover_context.clone())
-                .await
-                .map_err(Error::Source)?,
-        ))
-    })
+    #[error("Source error: {0}")]
+    Source(SE),
+    #[error("Key source error: {0}")]
+    KeySource(KeySourceError<KSE>),
+    #[error("Severe validation error: {0}")]
+    Validation(anyhow::Error),
 }
 
-fn collect_advisories<'s, V: DiscoveredVisitor + 's, S: Source>(
-    source: &'s S,
-    discover_contexts: Vec<DistributionContext>,
-) -> impl TryStream<Ok = DiscoveredAdvisory, Error = Error<V::Error, S::Error>> + 's {
-    collect_sources::<V, S>(source, discover_contexts)
-        .map_ok(|s| s.map(Ok))
+/// Discover all advisories of the distributions, loading one index after the other.
+fn discover<S: Source>(
+    source: S,
+    distributions: Vec<DistributionContext>,
+    origin: Option<Arc<AggregatorOrigin>>,
+) -> impl Stream<Item = Result<DiscoveredAdvisory, S::Error>> {
+    stream::iter(distributions)
+        .then(move |distribution| {
+            let source = source.clone();
+            let origin = origin.clone();
+            async move {
+                log::debug!("Walking: {}", distribution.url());
+                let index = source.load_index(distribution).await?;
+                Ok::<_, S::Error>(stream::iter(index).map(move |mut advisory| {
+                    attribute(&mut advisory, origin.as_ref());
+                    Ok(advisory)
+                }))
+            }
+        })
         .try_flatten()
 }
+
+/// Attribute all advisories of an index to the origin, see [`attribute`].
+fn attribute_all(index: &mut [DiscoveredAdvisory], origin: Option<&Arc<AggregatorOrigin>>) {
+    for advisory in index {
+        attribute(advisory, origin);
+    }
+}
+
+/// Attribute an advisory to the origin, if there is one. Otherwise, an attribution by the source
+/// (e.g. of a store) is kept.
+fn attribute(advisory: &mut DiscoveredAdvisory, origin: Option<&Arc<AggregatorOrigin>>) {
+    if let Some(origin) = origin {
+        advisory.aggregator = Some(origin.clone());
+    }
+}
+
+/// Look up the origin of the advisories of a provider, in the listing of the aggregator
+fn origin(
+    aggregator: Option<&AggregatorMetadata>,
+    metadata: &ProviderMetadata,
+) -> Option<Arc<AggregatorOrigin>> {
+    let aggregator = aggregator?;
+
+    match AggregatorOrigin::lookup(aggregator, metadata) {
+        Some(origin) => {
+            log::info!("Attributing advisories to: {origin}");
+            Some(Arc::new(origin))
+        }
+        None => {
+            log::warn!(
+                "Provider {} is not listed by aggregator {}",
+                metadata.canonical_url,
+                aggregator.canonical_url
+            );
+            None
+        }
+    }
+}
+
+fn collect_distributions(
+    distribution_filter: Option<&DistributionFilter>,
+    distributions: Vec<Distribution>,
+) -> Vec<DistributionContext> {
+    distributions
+        .into_iter()
+        .flat_map(|distribution| {
+            distribution
+                .rolie
+                .into_iter()
+                .flat_map(|rolie| rolie.feeds)
+                .map(DistributionContext::Feed)
+                .chain(
+                    distribution
+                        .directory_url
+                        .map(DistributionContext::Directory),
+                )
+        })
+        .filter(|distribution| {
+            if let Some(filter) = distribution_filter {
+                filter(distribution)
+            } else {
+                true
+            }
+        })
+        .collect()
+}
+
+/// Get the number of entries of an index to skip, as they have been processed according to the
+/// checkpoint
+fn resume(checkpoint: Option<&Checkpoint>, key: &str, index: &[DiscoveredAdvisory]) -> usize {
+    checkpoint.map_or(0, |checkpoint| {
+        checkpoint.resume(key, index.iter().map(|advisory| advisory.url.as_str()))
+    })
+}
+
+/// Check if the advisory may still be processed, before the deadline passed
+fn admit(deadline: Option<&Deadline>, advisory: &DiscoveredAdvisory) -> bool {
+    deadline.map_or(true, |deadline| deadline.admit(advisory.modified))
+}
+
+/// Finish a walk, failing if the deadline stopped it
+fn finish<VE, SE>(deadline: Option<Deadline>) -> Result<(), Error<VE, SE>>
+where
+    VE: std::fmt::Display + Debug,
+    SE: std::fmt::Display + Debug,
+{
+    match deadline.and_then(Deadline::into_completion) {
+        Some(completion) => Err(Error::Deadline(completion)),
+        None => Ok(()),
+    }
+}
+
+/// A distribution of a concurrent walk, with advisories still being processed
+struct PendingDistribution {
+    distribution: DistributionContext,
+    advisories: usize,
+    remaining: AtomicUsize,
+    started: Instant,
+}
+
+impl PendingDistribution {
+    fn new(distribution: DistributionContext, advisories: usize, started: Instant) -> Self {
+        Self {
+            distribution,
+            advisories,
+            remaining: AtomicUsize::new(advisories),
+            started,
+        }
+    }
+
+    /// Record an advisory as processed, ending the distribution with the last one.
+    fn complete<S: Source>(&self, walker: &Walker<S>, metadata: &ProviderMetadata) {
+        if self.remaining.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.end(walker, metadata);
+        }
+    }
+
+    fn end<S: Source>(&self, walker: &Walker<S>, metadata: &ProviderMetadata) {
+        walker.end_distribution(metadata, &self.distribution, self.advisories, self.started);
+    }
+}
+
+/// Call the hooks at the end of a walk
+fn end<VE, SE>(
+    hooks: &[Arc<dyn WalkerHooks>],
+    correlation_id: &CorrelationId,
+    duration: Duration,
+    result: &Result<(), Error<VE, SE>>,
+) where
+    VE: std::fmt::Display + Debug,
+    SE: std::fmt::Display + Debug,
+{
+    let context = EndContext {
+        correlation_id,
+        duration,
+        error: result.as_ref().err().map(|err| err as &dyn Display),
+    };
+    for hooks in hooks {
+        hooks.on_end(&context);
+    }
+}
+
+/// Clear the checkpoint if the walk completed, store it otherwise
+fn finish_checkpoint<T, E>(checkpoint: Option<&Checkpoint>, result: &Result<T, E>) {
+    if let Some(checkpoint) = checkpoint {
+        checkpoint.finish(result.is_ok());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        retrieve::RetrievingVisitor,
+        test_utils::{document, CollectingVisitor, MockProvider},
+    };
+
+    #[tokio::test]
+    async fn test_deadline_stops_discovery() -> anyhow::Result<()> {
+        let provider = MockProvider::new("https://example.com")?
+            .advisory("2024/a.json", document("A"))?
+            .advisory("2024/b.json", document("B"))?;
+
+        let collected = CollectingVisitor::new();
+        let result = Walker::new(provider.clone())
+            .with_deadline(Instant::now())
+            .walk_parallel(4, RetrievingVisitor::new(provider, collected.clone()))
+            .await;
+
+        // the deadline passed before any index was loaded
+        let Err(Error::Deadline(completion)) = result else {
+            panic!("walk must be stopped by the deadline");
+        };
+        assert_eq!(completion.processed, 0);
+        assert_eq!(completion.resume_since, None);
+        assert!(collected.urls().is_empty());
+
+        Ok(())
+    }
+}
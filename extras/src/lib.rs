@@ -2,6 +2,9 @@
 
 pub mod visitors;
 
+#[cfg(all(feature = "csaf-walker", feature = "sbom-walker"))]
+pub mod walker;
+
 #[cfg(feature = "sbom-walker")]
 pub use sbom_walker as sbom;
 
@@ -1,73 +1,209 @@
-use crate::visitors::SendVisitor;
-use reqwest::Url;
+use crate::visitors::{Compression, SendVisitor};
+use reqwest::{Method, Url};
 use std::path::PathBuf;
+use std::sync::Arc;
 use walker_common::sender::{
-    provider::OpenIdTokenProviderConfigArguments, HttpSender, HttpSenderOptions,
+    provider::{OpenIdTokenProviderConfigArguments, TokenProvider},
+    HttpSender, HttpSenderOptions,
+};
+#[cfg(feature = "csaf-walker")]
+use {
+    crate::visitors::{RoutingSendVisitor, SendRoute, SendRoutesConfig},
+    walker_common::sender::provider::{BearerTokenProvider, OpenIdTokenProviderConfig},
 };
 
 #[derive(Debug, clap::Parser)]
 #[command(next_help_heading = "Sending")]
 pub struct SendArguments {
     /// Target to send to
+    #[arg(env = "CSAF_WALKER_SENDER_TARGET")]
     pub target: Url,
 
     /// Sender connect timeout
-    #[arg(id = "sender-connect-timeout", long, default_value = "15s")]
+    #[arg(
+        id = "sender-connect-timeout",
+        long,
+        env = "CSAF_WALKER_SENDER_CONNECT_TIMEOUT",
+        default_value = "15s"
+    )]
     pub connect_timeout: humantime::Duration,
 
     /// Sender request timeout
-    #[arg(id = "sender-timeout", long, default_value = "5m")]
+    #[arg(
+        id = "sender-timeout",
+        long,
+        env = "CSAF_WALKER_SENDER_TIMEOUT",
+        default_value = "5m"
+    )]
     pub timeout: humantime::Duration,
 
     /// Additional root certificates
-    #[arg(id = "sender-tls-ca-certificate", long)]
+    #[arg(
+        id = "sender-tls-ca-certificate",
+        long,
+        env = "CSAF_WALKER_SENDER_TLS_CA_CERTIFICATE"
+    )]
     pub additional_root_certificates: Vec<PathBuf>,
 
     /// Allow using TLS in an insecure mode when contacting the target (DANGER!)
-    #[arg(id = "sender-tls-insecure", long)]
+    #[arg(
+        id = "sender-tls-insecure",
+        long,
+        env = "CSAF_WALKER_SENDER_TLS_INSECURE",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
     pub tls_insecure: bool,
 
     /// Number of retries in case of temporary failures
-    #[arg(id = "sender-retries", long, default_value = "0")]
+    #[arg(
+        id = "sender-retries",
+        long,
+        env = "CSAF_WALKER_SENDER_RETRIES",
+        default_value = "0"
+    )]
     pub retries: usize,
 
     /// Delay between retries
-    #[arg(id = "sender-retry-delay", long, default_value = "5s")]
+    #[arg(
+        id = "sender-retry-delay",
+        long,
+        env = "CSAF_WALKER_SENDER_RETRY_DELAY",
+        default_value = "5s"
+    )]
     pub retry_delay: humantime::Duration,
 
+    /// HTTP method used for uploading documents
+    #[arg(
+        id = "sender-method",
+        long,
+        env = "CSAF_WALKER_SENDER_METHOD",
+        value_enum,
+        default_value_t = SendMethod::Post
+    )]
+    pub method: SendMethod,
+
+    /// Don't send an `Idempotency-Key` header, derived from the digest of the document
+    #[arg(
+        id = "sender-no-idempotency-key",
+        long,
+        env = "CSAF_WALKER_SENDER_NO_IDEMPOTENCY_KEY",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub no_idempotency_key: bool,
+
+    /// Compress the uploaded documents, falling back to uncompressed uploads if the target rejects them
+    #[arg(
+        id = "sender-compression",
+        long,
+        env = "CSAF_WALKER_SENDER_COMPRESSION",
+        value_enum,
+        default_value_t = Compression::None
+    )]
+    pub compression: Compression,
+
+    /// Send CSAF documents to different targets, based on their publisher namespace or TLP label. A (YAML) file
+    /// with a list of routes, documents not matching any route are sent to the target. Not supported for SBOMs.
+    #[arg(id = "sender-routes", long, env = "CSAF_WALKER_SENDER_ROUTES")]
+    pub routes: Option<PathBuf>,
+
     #[command(flatten)]
     pub oidc: OpenIdTokenProviderConfigArguments,
 }
 
+/// The HTTP method used for uploading documents
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum SendMethod {
+    #[default]
+    Post,
+    Put,
+    Patch,
+}
+
+impl From<SendMethod> for Method {
+    fn from(value: SendMethod) -> Self {
+        match value {
+            SendMethod::Post => Method::POST,
+            SendMethod::Put => Method::PUT,
+            SendMethod::Patch => Method::PATCH,
+        }
+    }
+}
+
 impl SendArguments {
     pub async fn into_visitor(self) -> Result<SendVisitor, anyhow::Error> {
-        let SendArguments {
-            target,
-            connect_timeout,
-            timeout,
-            additional_root_certificates,
-            tls_insecure,
-            retries,
-            retry_delay,
-            oidc,
-        } = self;
-
-        let provider = oidc.into_provider().await?;
+        if self.routes.is_some() {
+            anyhow::bail!("Send routes are only supported for CSAF documents");
+        }
+
+        let provider = self.oidc.clone().into_provider().await?;
+        self.new_visitor(self.target.clone(), provider).await
+    }
+
+    /// Create a visitor sending documents to the first matching route of `--sender-routes`, and
+    /// all others to the target.
+    #[cfg(feature = "csaf-walker")]
+    pub async fn into_routing_visitor(self) -> Result<RoutingSendVisitor, anyhow::Error> {
+        let provider = self.oidc.clone().into_provider().await?;
+        let mut visitor = RoutingSendVisitor::new(
+            self.new_visitor(self.target.clone(), provider.clone())
+                .await?,
+        );
+
+        let Some(routes) = &self.routes else {
+            return Ok(visitor);
+        };
+
+        for route in SendRoutesConfig::load(routes)?.routes {
+            let provider: Arc<dyn TokenProvider> = match (route.bearer_token, route.oidc) {
+                (Some(_), Some(_)) => anyhow::bail!(
+                    "Route to {} must not use both a bearer token and OIDC",
+                    route.target
+                ),
+                (Some(token), None) => Arc::new(BearerTokenProvider { token }),
+                (None, Some(oidc)) => {
+                    OpenIdTokenProviderConfig::new_provider(Some(OpenIdTokenProviderConfig {
+                        client_id: oidc.client_id,
+                        client_secret: oidc.client_secret,
+                        issuer_url: oidc.issuer_url,
+                        refresh_before: self.oidc.refresh_before,
+                        tls_insecure: self.oidc.tls_insecure,
+                        tls_ca_certificates: self.oidc.tls_ca_certificates.clone(),
+                    }))
+                    .await?
+                }
+                (None, None) => provider.clone(),
+            };
+
+            visitor = visitor.add_route(
+                SendRoute::new(self.new_visitor(route.target, provider).await?)
+                    .namespaces(route.namespaces)
+                    .tlp_labels(route.tlp_labels),
+            );
+        }
+
+        Ok(visitor)
+    }
+
+    async fn new_visitor(
+        &self,
+        target: Url,
+        provider: Arc<dyn TokenProvider>,
+    ) -> Result<SendVisitor, anyhow::Error> {
         let sender = HttpSender::new(
             provider,
             HttpSenderOptions::default()
-                .connect_timeout(Some(connect_timeout.into()))
-                .timeout(Some(timeout.into()))
-                .tls_insecure(tls_insecure)
-                .additional_root_certificates(additional_root_certificates),
+                .connect_timeout(Some(self.connect_timeout.into()))
+                .timeout(Some(self.timeout.into()))
+                .tls_insecure(self.tls_insecure)
+                .additional_root_certificates(self.additional_root_certificates.clone()),
         )
         .await?;
 
-        Ok(SendVisitor {
-            url: target,
-            sender,
-            retries,
-            retry_delay: Some(retry_delay.into()),
-        })
+        Ok(SendVisitor::new(target, sender)
+            .method(self.method.into())
+            .idempotency_keys(!self.no_idempotency_key)
+            .compression(self.compression)
+            .retries(self.retries)
+            .retry_delay(self.retry_delay))
     }
 }
@@ -0,0 +1,58 @@
+use bytes::Bytes;
+
+/// Compression of uploaded request bodies
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Compression {
+    /// Send the document as it is
+    #[default]
+    None,
+    /// Compress using gzip
+    Gzip,
+    /// Compress using zstd
+    Zstd,
+}
+
+impl Compression {
+    /// The value of the `Content-Encoding` header, `None` if the body isn't compressed
+    pub fn encoding(&self) -> Option<&'static str> {
+        match self {
+            Self::None => None,
+            Self::Gzip => Some("gzip"),
+            Self::Zstd => Some("zstd"),
+        }
+    }
+
+    /// Compress the data, or fail if no encoder was enabled.
+    pub fn compress(&self, data: &[u8]) -> Result<Bytes, anyhow::Error> {
+        match self {
+            Self::None => Ok(Bytes::copy_from_slice(data)),
+            Self::Gzip => compress_gzip(data),
+            Self::Zstd => compress_zstd(data),
+        }
+    }
+}
+
+#[cfg(feature = "gzip")]
+fn compress_gzip(data: &[u8]) -> Result<Bytes, anyhow::Error> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+    encoder.write_all(data)?;
+    Ok(Bytes::from(encoder.finish()?))
+}
+
+#[cfg(not(feature = "gzip"))]
+fn compress_gzip(_data: &[u8]) -> Result<Bytes, anyhow::Error> {
+    anyhow::bail!("No gzip encoder enabled")
+}
+
+#[cfg(feature = "zstd")]
+fn compress_zstd(data: &[u8]) -> Result<Bytes, anyhow::Error> {
+    Ok(Bytes::from(zstd::encode_all(data, 0)?))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn compress_zstd(_data: &[u8]) -> Result<Bytes, anyhow::Error> {
+    anyhow::bail!("No zstd encoder enabled")
+}
@@ -4,6 +4,8 @@ use crate::csaf::{
     validation::{ValidatedAdvisory, ValidatedVisitor, ValidationContext, ValidationError},
 };
 use csaf_walker::discover::DiscoveredAdvisory;
+use reqwest::header::HeaderValue;
+use walker_common::validate::evidence::ValidationEvidence;
 
 #[derive(Debug, thiserror::Error)]
 pub enum SendRetrievedAdvisoryError {
@@ -26,7 +28,7 @@ impl RetrievedVisitor for SendVisitor {
         _context: &Self::Context,
         result: Result<RetrievedAdvisory, RetrievalError>,
     ) -> Result<(), Self::Error> {
-        self.send_csaf(result?).await?;
+        self.send_csaf(result?, None).await?;
         Ok(())
     }
 }
@@ -52,13 +54,22 @@ impl ValidatedVisitor for SendVisitor {
         _context: &Self::Context,
         result: Result<ValidatedAdvisory, ValidationError>,
     ) -> Result<(), Self::Error> {
-        self.send_csaf(result?.retrieved).await?;
+        let advisory = result?;
+        self.send_csaf(advisory.retrieved, Some(&advisory.evidence))
+            .await?;
         Ok(())
     }
 }
 
+/// The header carrying the validation evidence (as JSON) of a sent document
+pub const VALIDATION_EVIDENCE_HEADER: &str = "x-validation-evidence";
+
 impl SendVisitor {
-    async fn send_csaf(&self, advisory: RetrievedAdvisory) -> Result<(), SendError> {
+    pub(super) async fn send_csaf(
+        &self,
+        advisory: RetrievedAdvisory,
+        evidence: Option<&ValidationEvidence>,
+    ) -> Result<(), SendError> {
         log::debug!(
             "Sending: {} (modified: {:?})",
             advisory.url,
@@ -71,8 +82,18 @@ impl SendVisitor {
             ..
         } = advisory;
 
-        self.send(url.as_str(), data, |request| {
-            request.header(header::CONTENT_TYPE, "application/json")
+        let evidence = evidence.and_then(|evidence| {
+            serde_json::to_string(evidence)
+                .ok()
+                .and_then(|evidence| HeaderValue::from_str(&evidence).ok())
+        });
+
+        self.send(url.as_str(), data, true, |request| {
+            let request = request.header(header::CONTENT_TYPE, "application/json");
+            match &evidence {
+                Some(evidence) => request.header(VALIDATION_EVIDENCE_HEADER, evidence.clone()),
+                None => request,
+            }
         })
         .await
     }
@@ -1,8 +1,16 @@
 use bytes::Bytes;
 use reqwest::{header, Body, Method, StatusCode, Url};
+use sha2::{Digest, Sha256};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 use std::time::Duration;
 use walker_common::sender::{self, HttpSender};
 
+mod compression;
+pub use compression::*;
+
 #[cfg(feature = "sbom-walker")]
 mod sbom;
 #[cfg(feature = "sbom-walker")]
@@ -13,6 +21,11 @@ mod csaf;
 #[cfg(feature = "csaf-walker")]
 pub use csaf::*;
 
+#[cfg(feature = "csaf-walker")]
+mod route;
+#[cfg(feature = "csaf-walker")]
+pub use route::*;
+
 #[cfg(feature = "clap")]
 mod clap;
 #[cfg(feature = "clap")]
@@ -39,6 +52,18 @@ pub struct SendVisitor {
     /// The target endpoint
     pub url: Url,
 
+    /// The HTTP method used for uploading
+    pub method: Method,
+
+    /// Send an `Idempotency-Key` header, derived from the digest of the document
+    pub idempotency_keys: bool,
+
+    /// Compress the request body
+    pub compression: Compression,
+
+    /// Set once the server rejected a compressed body, falling back to uncompressed ones
+    compression_rejected: Arc<AtomicBool>,
+
     /// The HTTP client to use
     pub sender: HttpSender,
 
@@ -53,12 +78,31 @@ impl SendVisitor {
     pub fn new(url: impl Into<Url>, sender: HttpSender) -> Self {
         Self {
             url: url.into(),
+            method: Method::POST,
+            idempotency_keys: true,
+            compression: Compression::None,
+            compression_rejected: Default::default(),
             sender,
             retries: 0,
             retry_delay: None,
         }
     }
 
+    pub fn method(mut self, method: Method) -> Self {
+        self.method = method;
+        self
+    }
+
+    pub fn idempotency_keys(mut self, idempotency_keys: bool) -> Self {
+        self.idempotency_keys = idempotency_keys;
+        self
+    }
+
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
     pub fn retries(mut self, retries: usize) -> Self {
         self.retries = retries;
         self
@@ -73,6 +117,14 @@ impl SendVisitor {
 /// The default amount of time to wait before trying
 const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(5);
 
+/// The header carrying the idempotency key of an upload
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Derive the idempotency key from the SHA-256 digest of the document.
+fn idempotency_key(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
 pub enum SendOnceError {
     Temporary(SendError),
     Permanent(SendError),
@@ -84,20 +136,27 @@ impl SendVisitor {
         &self,
         name: &str,
         data: Bytes,
+        encoding: Option<&str>,
+        idempotency_key: Option<&str>,
         customizer: F,
     ) -> Result<(), SendOnceError>
     where
-        F: FnOnce(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
+        F: Fn(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
     {
-        let request = self
+        let response = self
             .sender
-            .request(Method::POST, self.url.clone())
-            .await
-            .map_err(|err| SendOnceError::Temporary(err.into()))?
-            .body(Body::from(data));
-        let request = customizer(request);
-        let response = request
-            .send()
+            .execute(self.method.clone(), self.url.clone(), |request| {
+                let request = request.body(Body::from(data.clone()));
+                let request = match idempotency_key {
+                    Some(key) => request.header(IDEMPOTENCY_KEY_HEADER, key),
+                    None => request,
+                };
+                let request = match encoding {
+                    Some(encoding) => request.header(header::CONTENT_ENCODING, encoding),
+                    None => request,
+                };
+                customizer(request)
+            })
             .await
             .map_err(|err| SendOnceError::Temporary(err.into()))?;
 
@@ -119,15 +178,69 @@ impl SendVisitor {
         }
     }
 
+    /// Compress the data, if requested and not rejected by the server before.
+    ///
+    /// Returns the compressed data and its encoding, or `None` if the data should be sent as it is.
+    fn encode(&self, name: &str, data: &[u8], compressible: bool) -> Option<(Bytes, &'static str)> {
+        let encoding = self.compression.encoding()?;
+        if !compressible || self.compression_rejected.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        match self.compression.compress(data) {
+            Ok(data) => Some((data, encoding)),
+            Err(err) => {
+                log::warn!("Failed to compress {name}, sending it uncompressed: {err}");
+                None
+            }
+        }
+    }
+
     /// Send request, retry in case of temporary errors
-    async fn send<F>(&self, name: &str, data: Bytes, customizer: F) -> Result<(), SendError>
+    ///
+    /// Unless the data is already compressed (not `compressible`), it gets compressed as
+    /// configured. If the server rejects the compressed body, it is sent again uncompressed.
+    async fn send<F>(
+        &self,
+        name: &str,
+        data: Bytes,
+        compressible: bool,
+        customizer: F,
+    ) -> Result<(), SendError>
     where
         F: Fn(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
     {
+        // the same key for all attempts, so that a retried upload can be recognized
+        let idempotency_key = self.idempotency_keys.then(|| idempotency_key(&data));
+        let mut encoded = self.encode(name, &data, compressible);
+
         let mut retries = self.retries;
         loop {
-            match self.send_once(name, data.clone(), &customizer).await {
+            let (body, encoding) = match &encoded {
+                Some((body, encoding)) => (body.clone(), Some(*encoding)),
+                None => (data.clone(), None),
+            };
+
+            match self
+                .send_once(
+                    name,
+                    body,
+                    encoding,
+                    idempotency_key.as_deref(),
+                    &customizer,
+                )
+                .await
+            {
                 Ok(()) => break Ok(()),
+                Err(SendOnceError::Permanent(SendError::Client(
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                ))) if encoding.is_some() => {
+                    log::info!("Server rejected the compressed body, falling back to uncompressed");
+                    self.compression_rejected.store(true, Ordering::Relaxed);
+                    encoded = None;
+                    // not a failure of the upload, try again right away
+                    continue;
+                }
                 Err(SendOnceError::Permanent(err)) => break Err(err),
                 Err(SendOnceError::Temporary(err)) if retries == 0 => break Err(err),
                 Err(SendOnceError::Temporary(_)) => {
@@ -0,0 +1,225 @@
+use super::*;
+use crate::csaf::{
+    retrieve::{RetrievalContext, RetrievalError, RetrievedAdvisory, RetrievedVisitor},
+    validation::{ValidatedAdvisory, ValidatedVisitor, ValidationContext, ValidationError},
+};
+use anyhow::Context;
+use csaf_walker::visitors::filter::normalize_tlp;
+use std::path::Path;
+use walker_common::validate::evidence::ValidationEvidence;
+
+/// A route, sending matching documents to a dedicated target.
+#[derive(Clone)]
+pub struct SendRoute {
+    /// Only match documents of these publisher namespaces (`document/publisher/namespace`), any
+    /// if empty
+    pub namespaces: Vec<String>,
+
+    /// Only match documents with one of these TLP labels, any if empty
+    pub tlp_labels: Vec<String>,
+
+    /// The visitor sending the matching documents
+    pub visitor: SendVisitor,
+}
+
+impl SendRoute {
+    pub fn new(visitor: SendVisitor) -> Self {
+        Self {
+            namespaces: vec![],
+            tlp_labels: vec![],
+            visitor,
+        }
+    }
+
+    pub fn namespaces<I>(mut self, namespaces: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        self.namespaces = Vec::from_iter(namespaces);
+        self
+    }
+
+    pub fn tlp_labels<I>(mut self, tlp_labels: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        self.tlp_labels = Vec::from_iter(tlp_labels);
+        self
+    }
+
+    fn matches(&self, namespace: Option<&str>, tlp_label: Option<&str>) -> bool {
+        let namespace_matches = self.namespaces.is_empty()
+            || namespace.is_some_and(|namespace| {
+                self.namespaces
+                    .iter()
+                    .any(|n| n.trim_end_matches('/') == namespace.trim_end_matches('/'))
+            });
+
+        let tlp_matches = self.tlp_labels.is_empty()
+            || tlp_label.is_some_and(|label| {
+                let label = normalize_tlp(label);
+                self.tlp_labels.iter().any(|l| normalize_tlp(l) == label)
+            });
+
+        namespace_matches && tlp_matches
+    }
+}
+
+/// Send documents to the target of the first matching route, or to the default target.
+#[derive(Clone)]
+pub struct RoutingSendVisitor {
+    /// The routes, in the order of evaluation
+    pub routes: Vec<SendRoute>,
+
+    /// The visitor for documents not matching any route, which get skipped if there is none
+    pub default: Option<SendVisitor>,
+}
+
+impl RoutingSendVisitor {
+    pub fn new(default: impl Into<Option<SendVisitor>>) -> Self {
+        Self {
+            routes: vec![],
+            default: default.into(),
+        }
+    }
+
+    pub fn add_route(mut self, route: SendRoute) -> Self {
+        self.routes.push(route);
+        self
+    }
+
+    /// Select the visitor for an advisory.
+    fn route(&self, advisory: &RetrievedAdvisory) -> Option<&SendVisitor> {
+        if self.routes.is_empty() {
+            return self.default.as_ref();
+        }
+
+        let namespace = publisher_namespace(&advisory.data);
+        let tlp_label = advisory.tlp_label();
+
+        self.routes
+            .iter()
+            .find(|route| route.matches(namespace.as_deref(), tlp_label.as_deref()))
+            .map(|route| &route.visitor)
+            .or(self.default.as_ref())
+    }
+
+    async fn send_routed(
+        &self,
+        advisory: RetrievedAdvisory,
+        evidence: Option<&ValidationEvidence>,
+    ) -> Result<(), SendError> {
+        match self.route(&advisory) {
+            Some(visitor) => visitor.send_csaf(advisory, evidence).await,
+            None => {
+                log::info!("No route for {}, skipping", advisory.url);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Get the publisher namespace of a CSAF document.
+fn publisher_namespace(data: &[u8]) -> Option<String> {
+    let document = serde_json::from_slice::<serde_json::Value>(data).ok()?;
+    document["document"]["publisher"]["namespace"]
+        .as_str()
+        .map(ToString::to_string)
+}
+
+impl RetrievedVisitor for RoutingSendVisitor {
+    type Error = SendRetrievedAdvisoryError;
+    type Context = ();
+
+    async fn visit_context(&self, _: &RetrievalContext<'_>) -> Result<Self::Context, Self::Error> {
+        Ok(())
+    }
+
+    async fn visit_advisory(
+        &self,
+        _context: &Self::Context,
+        result: Result<RetrievedAdvisory, RetrievalError>,
+    ) -> Result<(), Self::Error> {
+        self.send_routed(result?, None).await?;
+        Ok(())
+    }
+}
+
+impl ValidatedVisitor for RoutingSendVisitor {
+    type Error = SendValidatedAdvisoryError;
+    type Context = ();
+
+    async fn visit_context(&self, _: &ValidationContext<'_>) -> Result<Self::Context, Self::Error> {
+        Ok(())
+    }
+
+    async fn visit_advisory(
+        &self,
+        _context: &Self::Context,
+        result: Result<ValidatedAdvisory, ValidationError>,
+    ) -> Result<(), Self::Error> {
+        let advisory = result?;
+        self.send_routed(advisory.retrieved, Some(&advisory.evidence))
+            .await?;
+        Ok(())
+    }
+}
+
+/// The configuration of send routes, loaded from a (YAML) file.
+///
+/// ```yaml
+/// routes:
+///   - target: https://tenant-a.example.com/api/v1/vex
+///     namespaces: [https://www.redhat.com]
+///     tlpLabels: [WHITE, GREEN]
+///     oidc:
+///       clientId: tenant-a
+///       clientSecret: secret
+///       issuerUrl: https://sso.example.com/realms/tenant-a
+///   - target: https://tenant-b.example.com/api/v1/vex
+///     tlpLabels: [AMBER]
+///     bearerToken: token
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct SendRoutesConfig {
+    pub routes: Vec<SendRouteConfig>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SendRouteConfig {
+    /// The target to send matching documents to
+    pub target: Url,
+    /// Publisher namespaces to match, any if empty
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+    /// TLP labels to match, any if empty
+    #[serde(default)]
+    pub tlp_labels: Vec<String>,
+    /// A static bearer token for the target
+    #[serde(default)]
+    pub bearer_token: Option<String>,
+    /// OIDC client credentials for the target
+    #[serde(default)]
+    pub oidc: Option<SendRouteOidcConfig>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SendRouteOidcConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    pub issuer_url: String,
+}
+
+impl SendRoutesConfig {
+    /// Load the routes from a YAML file.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open send routes: {}", path.display()))?;
+        serde_yaml::from_reader(std::io::BufReader::new(file))
+            .with_context(|| format!("Failed to parse send routes: {}", path.display()))
+    }
+}
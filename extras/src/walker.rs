@@ -0,0 +1,275 @@
+//! Walking CSAF advisories and SBOMs of a provider with a single call
+//!
+//! ## Example
+//!
+//! ```rust
+//! use walker_common::fetcher::Fetcher;
+//! use walker_extras::walker::{ProviderWalker, ValidatedDocument};
+//!
+//! async fn walk() -> anyhow::Result<()> {
+//!   let fetcher = Fetcher::new(Default::default()).await?;
+//!
+//!   let report = ProviderWalker::new(fetcher, "redhat.com")
+//!     .sbom_source(reqwest::Url::parse("https://access.redhat.com/security/data/sbom/beta/")?)
+//!     .walk(|document: ValidatedDocument| async move {
+//!         log::info!("Found document: {}", document.url());
+//!         Ok::<_, anyhow::Error>(())
+//!     })
+//!     .await?;
+//!
+//!   log::info!("CSAF: {}, SBOM: {}", report.csaf.total, report.sbom.total);
+//!
+//!   Ok(())
+//! }
+//! ```
+
+use reqwest::Url;
+use std::{
+    fmt::{Debug, Display},
+    future::Future,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+use walker_common::{
+    fetcher::Fetcher, progress::Progress, utils::url::Urlify, validate::ValidationOptions,
+};
+
+/// A validated document, either a CSAF advisory or an SBOM
+#[derive(Debug)]
+pub enum ValidatedDocument {
+    Csaf(
+        Result<
+            csaf_walker::validation::ValidatedAdvisory,
+            csaf_walker::validation::ValidationError,
+        >,
+    ),
+    Sbom(Result<sbom_walker::validation::ValidatedSbom, sbom_walker::validation::ValidationError>),
+}
+
+impl ValidatedDocument {
+    /// The URL of the document
+    pub fn url(&self) -> &Url {
+        match self {
+            Self::Csaf(Ok(doc)) => doc.url(),
+            Self::Csaf(Err(err)) => err.url(),
+            Self::Sbom(Ok(doc)) => doc.url(),
+            Self::Sbom(Err(err)) => err.url(),
+        }
+    }
+
+    /// The error message, if the document failed to validate
+    pub fn error(&self) -> Option<String> {
+        match self {
+            Self::Csaf(Err(err)) => Some(err.to_string()),
+            Self::Sbom(Err(err)) => Some(err.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// A visitor for all documents of a provider
+pub trait DocumentVisitor {
+    type Error: Display + Debug + Send + Sync + 'static;
+
+    fn visit_document(
+        &self,
+        document: ValidatedDocument,
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+impl<F, E, Fut> DocumentVisitor for F
+where
+    F: Fn(ValidatedDocument) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+    E: Display + Debug + Send + Sync + 'static,
+{
+    type Error = E;
+
+    fn visit_document(
+        &self,
+        document: ValidatedDocument,
+    ) -> impl Future<Output = Result<(), Self::Error>> {
+        self(document)
+    }
+}
+
+/// The outcome of walking one type of artifact
+#[derive(Clone, Debug, Default)]
+pub struct ArtifactReport {
+    /// Number of processed documents
+    pub total: usize,
+    /// Documents which failed to validate, with their error message
+    pub errors: Vec<(Url, String)>,
+}
+
+/// The outcome of walking a provider
+#[derive(Clone, Debug, Default)]
+pub struct WalkReport {
+    pub csaf: ArtifactReport,
+    pub sbom: ArtifactReport,
+}
+
+impl ArtifactReport {
+    fn record(report: &Mutex<Self>, document: &ValidatedDocument) {
+        // a poisoned lock only means another visitor panicked, the counters are still fine
+        let mut report = report.lock().unwrap_or_else(|err| err.into_inner());
+        report.total += 1;
+        if let Some(err) = document.error() {
+            report.errors.push((document.url().clone(), err));
+        }
+    }
+}
+
+/// Walk CSAF documents and SBOMs of a provider, sharing fetcher, progress, and options.
+///
+/// CSAF documents are discovered from the provider domain (or metadata URL). As there is no
+/// standardized lookup for SBOMs, their source needs to be provided explicitly, if the provider
+/// publishes them.
+pub struct ProviderWalker {
+    fetcher: Fetcher,
+    progress: Progress,
+    csaf: Option<String>,
+    sbom: Option<Url>,
+    since: Option<SystemTime>,
+    validation: ValidationOptions,
+    workers: usize,
+}
+
+impl ProviderWalker {
+    /// Create a new walker for the provider, using the CSAF metadata lookup process.
+    pub fn new(fetcher: Fetcher, provider: impl Into<String>) -> Self {
+        Self {
+            fetcher,
+            progress: Progress::default(),
+            csaf: Some(provider.into()),
+            sbom: None,
+            since: None,
+            validation: ValidationOptions::default(),
+            workers: 1,
+        }
+    }
+
+    pub fn with_progress(mut self, progress: Progress) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Disable walking CSAF documents
+    pub fn no_csaf(mut self) -> Self {
+        self.csaf = None;
+        self
+    }
+
+    /// Set the base URL of the SBOM source, publishing a `changes.csv`
+    pub fn sbom_source(mut self, sbom: impl Into<Option<Url>>) -> Self {
+        self.sbom = sbom.into();
+        self
+    }
+
+    pub fn since(mut self, since: impl Into<Option<SystemTime>>) -> Self {
+        self.since = since.into();
+        self
+    }
+
+    pub fn validation(mut self, validation: impl Into<ValidationOptions>) -> Self {
+        self.validation = validation.into();
+        self
+    }
+
+    /// Number of parallel workers, `1` walks sequentially, `0` is unlimited.
+    pub fn workers(mut self, workers: usize) -> Self {
+        self.workers = workers;
+        self
+    }
+
+    /// Walk all documents, first the CSAF documents, then the SBOMs.
+    pub async fn walk<V>(self, visitor: V) -> anyhow::Result<WalkReport>
+    where
+        V: DocumentVisitor,
+    {
+        let visitor = Arc::new(visitor);
+        let csaf_report = Arc::new(Mutex::new(ArtifactReport::default()));
+        let sbom_report = Arc::new(Mutex::new(ArtifactReport::default()));
+
+        if let Some(provider) = self.csaf {
+            use csaf_walker::{
+                metadata::MetadataRetriever,
+                retrieve::RetrievingVisitor,
+                source::{HttpOptions, HttpSource},
+                validation::{ValidatedAdvisory, ValidationError, ValidationVisitor},
+                walker::Walker,
+            };
+
+            let source = HttpSource::new(
+                MetadataRetriever::new(provider),
+                self.fetcher.clone(),
+                HttpOptions::new().since(self.since),
+            );
+
+            let visitor = visitor.clone();
+            let report = csaf_report.clone();
+            let visitor = RetrievingVisitor::new(
+                source.clone(),
+                ValidationVisitor::new(
+                    move |advisory: Result<ValidatedAdvisory, ValidationError>| {
+                        let visitor = visitor.clone();
+                        let document = ValidatedDocument::Csaf(advisory);
+                        ArtifactReport::record(&report, &document);
+                        async move { visitor.visit_document(document).await }
+                    },
+                )
+                .with_options(self.validation.clone()),
+            );
+
+            let walker = Walker::new(source).with_progress(self.progress.clone());
+            match self.workers {
+                1 => walker.walk(visitor).await?,
+                n => walker.walk_parallel(n, visitor).await?,
+            }
+        }
+
+        if let Some(url) = self.sbom {
+            use sbom_walker::{
+                retrieve::RetrievingVisitor,
+                source::{HttpOptions, HttpSource},
+                validation::{ValidatedSbom, ValidationError, ValidationVisitor},
+                walker::Walker,
+            };
+
+            let source = HttpSource::new(
+                url,
+                self.fetcher.clone(),
+                HttpOptions::new().since(self.since),
+            );
+
+            let visitor = visitor.clone();
+            let report = sbom_report.clone();
+            let visitor = RetrievingVisitor::new(
+                source.clone(),
+                ValidationVisitor::new(move |sbom: Result<ValidatedSbom, ValidationError>| {
+                    let visitor = visitor.clone();
+                    let document = ValidatedDocument::Sbom(sbom);
+                    ArtifactReport::record(&report, &document);
+                    async move { visitor.visit_document(document).await }
+                })
+                .with_options(self.validation),
+            );
+
+            let walker = Walker::new(source).with_progress(self.progress);
+            match self.workers {
+                1 => walker.walk(visitor).await?,
+                n => walker.walk_parallel(n, visitor).await?,
+            }
+        }
+
+        let take = |report: Arc<Mutex<ArtifactReport>>| {
+            let report = report.lock().unwrap_or_else(|err| err.into_inner());
+            report.clone()
+        };
+
+        Ok(WalkReport {
+            csaf: take(csaf_report),
+            sbom: take(sbom_report),
+        })
+    }
+}
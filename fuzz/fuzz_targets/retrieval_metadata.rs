@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use walker_common::fuzzing::check_headers;
+
+fuzz_target!(|headers: Vec<(Vec<u8>, Vec<u8>)>| {
+    check_headers(&headers);
+});
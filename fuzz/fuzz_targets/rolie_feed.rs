@@ -0,0 +1,8 @@
+#![no_main]
+
+use csaf_walker::fuzzing::check_rolie_feed;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    check_rolie_feed(data);
+});
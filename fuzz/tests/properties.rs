@@ -0,0 +1,50 @@
+use csaf_walker::{fuzzing::*, rolie::RolieSource};
+use proptest::prelude::*;
+use walker_common::{changes::ChangeSource, fuzzing::*, retrieve::DigestFile};
+
+proptest! {
+    #[test]
+    fn digest_file_valid((content, expected) in digest_file()) {
+        prop_assert_eq!(DigestFile::parse(&content), Ok(expected));
+        check_digest_file(&content);
+    }
+
+    #[test]
+    fn digest_file_any(content in any::<String>()) {
+        check_digest_file(&content);
+    }
+
+    #[test]
+    fn changes_csv_valid((content, expected) in changes_csv()) {
+        let parsed = ChangeSource::parse(&content);
+        prop_assert!(parsed.is_ok());
+        prop_assert_eq!(parsed.map(|source| source.entries).unwrap_or_default(), expected);
+    }
+
+    #[test]
+    fn changes_csv_any(content in any::<String>()) {
+        check_changes_csv(&content);
+    }
+
+    #[test]
+    fn rolie_feed_valid(feed in rolie_feed()) {
+        let data = serde_json::to_vec(&feed)?;
+        prop_assert!(RolieSource::parse(&data).is_ok());
+        check_rolie_feed(&data);
+    }
+
+    #[test]
+    fn rolie_feed_any(data in prop::collection::vec(any::<u8>(), 0..256)) {
+        check_rolie_feed(&data);
+    }
+
+    #[test]
+    fn retrieval_metadata_stored(metadata in retrieval_metadata()) {
+        check_retrieval_metadata(&metadata);
+    }
+
+    #[test]
+    fn retrieval_metadata_headers(headers in headers()) {
+        check_headers(&headers);
+    }
+}
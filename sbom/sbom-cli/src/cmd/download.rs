@@ -7,7 +7,7 @@ use sbom_walker::{
     visitors::store::StoreVisitor,
 };
 use walker_common::{
-    cli::{client::ClientArguments, runner::RunnerArguments},
+    cli::{client::ClientArguments, lock::LockArguments, runner::RunnerArguments},
     progress::Progress,
     since::Since,
 };
@@ -29,10 +29,19 @@ pub struct Download {
 
     #[command(flatten)]
     store: StoreArguments,
+
+    #[command(flatten)]
+    lock: LockArguments,
 }
 
 impl Download {
     pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
+        let target = self.store.base()?;
+        let _locks = self
+            .lock
+            .lock(&target, self.skip.since_file.as_deref())
+            .await?;
+
         let store: StoreVisitor = self.store.try_into()?;
         let base = store.base.clone();
 
@@ -43,27 +52,40 @@ impl Download {
                 .since_file_offset
                 .map(|d| d.into())
                 .unwrap_or_default(),
+            &self.runner.clock(),
         )?;
 
-        walk_visitor(
+        let head = match self.skip.head_check {
+            true => Some(self.client.clone().new_fetcher().await?),
+            false => None,
+        };
+
+        let offload = self.runner.offload();
+        let budget = self.runner.memory_budget();
+
+        let completion = walk_visitor(
             progress,
             self.client,
-            DiscoverConfig::from(self.discover).with_since(since.since),
+            DiscoverConfig::from(self.discover)
+                .with_since(since.since)
+                .with_offload(offload),
             self.runner,
             move |source| async move {
                 let base = base.clone();
-                let visitor = { RetrievingVisitor::new(source.clone(), store) };
+                let visitor =
+                    { RetrievingVisitor::new(source.clone(), store).with_memory_budget(budget) };
 
                 Ok(SkipExistingVisitor {
                     visitor,
                     output: base,
                     since: since.since,
+                    head,
                 })
             },
         )
         .await?;
 
-        since.store()?;
+        since.store_completion(completion.as_ref())?;
 
         Ok(())
     }
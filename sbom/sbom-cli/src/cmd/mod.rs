@@ -3,6 +3,7 @@ use flexible_time::timestamp::StartTimestamp;
 use reqwest::Url;
 use sbom_walker::visitors::store::StoreVisitor;
 use std::path::PathBuf;
+use walker_common::encryption::{Decryption, Encryption};
 
 pub mod discover;
 pub mod download;
@@ -16,33 +17,76 @@ pub mod sync;
 pub struct DiscoverArguments {
     /// Source to scan from
     pub source: String,
-    #[arg(short = 'k', long = "key")]
+    #[arg(short = 'k', long = "key", env = "CSAF_WALKER_KEY")]
     /// URLs to keys which should be used for validation. The fragment part of a key can be used as the fingerprint.
     pub keys: Vec<Url>,
+
+    /// OpenPGP keyring with the secret key to decrypt documents of an encrypted `file:` source.
+    #[arg(long, env = "CSAF_WALKER_DECRYPTION_KEY", value_parser = parse_decryption)]
+    pub decryption_key: Option<Decryption>,
+
+    /// Scan the subdirectories of a `file:` source as well.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_RECURSIVE",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub recursive: bool,
+
+    /// A JSON file, listing the documents of a `file:` source with additional metadata, instead of scanning its directory.
+    #[arg(long, env = "CSAF_WALKER_MANIFEST")]
+    pub manifest: Option<PathBuf>,
+}
+
+fn parse_decryption(path: &str) -> anyhow::Result<Decryption> {
+    Decryption::from_files([path])
 }
 
 #[derive(Debug, clap::Parser)]
 #[command(next_help_heading = "Storage")]
 pub struct StoreArguments {
     /// Disable applying the modification timestamp to the downloaded file.
-    #[arg(long)]
+    #[arg(
+        long,
+        env = "CSAF_WALKER_NO_TIMESTAMPS",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
     pub no_timestamps: bool,
 
     /// Output path, defaults to the local directory.
-    #[arg(short, long)]
+    #[arg(short, long, env = "CSAF_WALKER_DATA")]
     pub data: Option<PathBuf>,
+
+    /// Encrypt stored documents for the recipients of the OpenPGP keyring
+    #[arg(long, env = "CSAF_WALKER_ENCRYPT_TO")]
+    pub encrypt_to: Vec<PathBuf>,
+}
+
+impl StoreArguments {
+    /// The output directory
+    pub fn base(&self) -> anyhow::Result<PathBuf> {
+        match &self.data {
+            Some(base) => Ok(base.clone()),
+            None => std::env::current_dir().context("Get the current working directory"),
+        }
+    }
 }
 
 impl TryFrom<StoreArguments> for StoreVisitor {
     type Error = anyhow::Error;
 
     fn try_from(value: StoreArguments) -> Result<Self, Self::Error> {
-        let base = match value.data {
-            Some(base) => base,
-            None => std::env::current_dir().context("Get the current working directory")?,
+        let base = value.base()?;
+
+        let encryption = if value.encrypt_to.is_empty() {
+            None
+        } else {
+            Some(Encryption::from_files(&value.encrypt_to)?)
         };
 
-        Ok(Self::new(base).no_timestamps(value.no_timestamps))
+        Ok(Self::new(base)
+            .no_timestamps(value.no_timestamps)
+            .encryption(encryption))
     }
 }
 
@@ -50,14 +94,23 @@ impl TryFrom<StoreArguments> for StoreVisitor {
 #[command(next_help_heading = "Skipping")]
 pub struct SkipArguments {
     /// Provide a timestamp since when files are considered changed.
-    #[arg(short, long)]
+    #[arg(short, long, env = "CSAF_WALKER_SINCE")]
     pub since: Option<StartTimestamp>,
 
     /// A file to read/store the last sync timestamp to at the end of a successful run.
-    #[arg(short = 'S', long)]
+    #[arg(short = 'S', long, env = "CSAF_WALKER_SINCE_FILE")]
     pub since_file: Option<PathBuf>,
 
     /// A delta to add to the value loaded from the since-state file.
-    #[arg(long)]
+    #[arg(long, env = "CSAF_WALKER_SINCE_FILE_OFFSET")]
     pub since_file_offset: Option<humantime::Duration>,
+
+    /// Issue a HEAD request for documents considered changed, skipping them if the ETag, or
+    /// Last-Modified and Content-Length, match the stored retrieval metadata.
+    #[arg(
+        long,
+        env = "CSAF_WALKER_HEAD_CHECK",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub head_check: bool,
 }
@@ -1,10 +1,23 @@
+mod partial;
+mod purl;
 mod spdx;
+mod spdx3;
 
 use crate::cmd::report::ReportSink;
-use sbom_walker::Sbom;
+use sbom_walker::{model::partial::PartialSpdx, Sbom};
 
 pub fn all(report: &dyn ReportSink, sbom: Sbom) {
-    if let Sbom::Spdx(sbom) = sbom {
-        spdx::all(report, sbom)
+    purl::all(report, &sbom);
+
+    match sbom {
+        Sbom::Spdx(sbom) => spdx::all(report, sbom),
+        Sbom::Spdx3(sbom) => spdx3::all(report, sbom),
+        Sbom::CycloneDx(_) => {}
     }
 }
+
+/// Run the checks which work with a partially parsed document.
+pub fn partial_spdx(report: &dyn ReportSink, spdx: &PartialSpdx) {
+    purl::partial_spdx(report, spdx);
+    partial::all(report, spdx);
+}
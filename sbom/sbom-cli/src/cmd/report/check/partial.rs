@@ -0,0 +1,65 @@
+use crate::cmd::report::ReportSink;
+use sbom_walker::model::partial::PartialSpdx;
+use std::collections::HashSet;
+
+pub fn all(report: &dyn ReportSink, spdx: &PartialSpdx) {
+    PartialChecks { report, spdx }.all();
+}
+
+/// Checks of a partially parsed SPDX document, which only has its packages and relationships.
+struct PartialChecks<'c> {
+    report: &'c dyn ReportSink,
+    spdx: &'c PartialSpdx,
+}
+
+impl PartialChecks<'_> {
+    pub fn all(&self) {
+        self.licenses();
+        self.rel_targets();
+    }
+
+    /// check if the declared licenses are valid SPDX license expressions
+    fn licenses(&self) {
+        for package in &self.spdx.packages {
+            if let Some(declared) = &package.license_declared {
+                if let Err(err) = spdx_expression::SpdxExpression::parse(declared) {
+                    self.report
+                        .error(format!("Faulty SPDX license expression: {err}"));
+                }
+            }
+        }
+    }
+
+    /// check if all relationships have valid targets
+    fn rel_targets(&self) {
+        let mut ids = self
+            .spdx
+            .packages
+            .iter()
+            .map(|p| p.spdx_id.as_str())
+            .collect::<HashSet<_>>();
+
+        ids.insert(&self.spdx.spdx_id);
+
+        // now see if all relationships have valid targets
+
+        for rel in &self.spdx.relationships {
+            if !ids.contains(rel.spdx_element_id.as_str()) {
+                self.report.error(format!(
+                    "Invalid reference '{left}' of relationship '{left}' -[{rel}]-> '{right}'",
+                    left = rel.spdx_element_id,
+                    rel = rel.relationship_type,
+                    right = rel.related_spdx_element
+                ));
+            }
+            if !ids.contains(rel.related_spdx_element.as_str()) {
+                self.report.error(format!(
+                    "Invalid reference '{right}' of relationship '{left}' -[{rel}]-> '{right}'",
+                    left = rel.spdx_element_id,
+                    rel = rel.relationship_type,
+                    right = rel.related_spdx_element
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::cmd::report::ReportSink;
+use packageurl::PackageUrl;
+use sbom_walker::{model::partial::PartialSpdx, Sbom};
+use std::{collections::HashMap, str::FromStr};
+
+/// A package, as declared by the SBOM
+struct Package {
+    /// Identifier of the package inside the SBOM
+    id: String,
+    name: String,
+    version: Option<String>,
+    purl: String,
+}
+
+pub fn all(report: &dyn ReportSink, sbom: &Sbom) {
+    PurlChecks {
+        report,
+        packages: packages(sbom),
+    }
+    .all();
+}
+
+pub fn partial_spdx(report: &dyn ReportSink, spdx: &PartialSpdx) {
+    let packages = spdx
+        .packages
+        .iter()
+        .flat_map(|package| {
+            package
+                .external_refs
+                .iter()
+                .filter(|r| r.reference_type == "purl")
+                .map(move |purl| Package {
+                    id: package.spdx_id.clone(),
+                    name: package.name.clone(),
+                    version: package.version_info.clone(),
+                    purl: purl.reference_locator.clone(),
+                })
+        })
+        .collect();
+
+    PurlChecks { report, packages }.all();
+}
+
+/// collect all packages which declare a purl
+fn packages(sbom: &Sbom) -> Vec<Package> {
+    let mut result = vec![];
+
+    match sbom {
+        Sbom::Spdx(spdx) => {
+            for package in &spdx.package_information {
+                for purl in package
+                    .external_reference
+                    .iter()
+                    .filter(|r| r.reference_type == "purl")
+                {
+                    result.push(Package {
+                        id: package.package_spdx_identifier.clone(),
+                        name: package.package_name.clone(),
+                        version: package.package_version.clone(),
+                        purl: purl.reference_locator.clone(),
+                    });
+                }
+            }
+        }
+        Sbom::Spdx3(spdx) => {
+            for package in spdx.elements_of_type("software_Package") {
+                if let Some(purl) = package.property_str("software_packageUrl") {
+                    result.push(Package {
+                        id: package.spdx_id.clone().unwrap_or_default(),
+                        name: package.name.clone().unwrap_or_default(),
+                        version: package
+                            .property_str("software_packageVersion")
+                            .map(ToString::to_string),
+                        purl: purl.to_string(),
+                    });
+                }
+            }
+        }
+        Sbom::CycloneDx(bom) => {
+            let mut components = vec![];
+            if let Some(component) = bom.metadata.as_ref().and_then(|m| m.component.as_ref()) {
+                components.push(component);
+            }
+            if let Some(c) = &bom.components {
+                components.extend(&c.0);
+            }
+
+            while let Some(component) = components.pop() {
+                if let Some(c) = &component.components {
+                    components.extend(&c.0);
+                }
+
+                if let Some(purl) = &component.purl {
+                    result.push(Package {
+                        id: component
+                            .bom_ref
+                            .clone()
+                            .unwrap_or_else(|| component.name.to_string()),
+                        name: component.name.to_string(),
+                        version: component.version.as_ref().map(ToString::to_string),
+                        purl: purl.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    result
+}
+
+struct PurlChecks<'c> {
+    report: &'c dyn ReportSink,
+    packages: Vec<Package>,
+}
+
+impl PurlChecks<'_> {
+    pub fn all(&self) {
+        self.valid();
+        self.unique();
+    }
+
+    /// check if purls parse, and match the declared name and version
+    fn valid(&self) {
+        for package in &self.packages {
+            let purl = match PackageUrl::from_str(&package.purl) {
+                Ok(purl) => purl,
+                Err(err) => {
+                    self.report.error(format!(
+                        "Invalid purl '{purl}' of package '{id}': {err}",
+                        purl = package.purl,
+                        id = package.id,
+                    ));
+                    continue;
+                }
+            };
+
+            // the declared name might contain the namespace (e.g. Maven's group ID)
+            if !package
+                .name
+                .to_lowercase()
+                .ends_with(&purl.name().to_lowercase())
+            {
+                self.report.error(format!(
+                    "Name of purl '{purl}' doesn't match name '{name}' of package '{id}'",
+                    purl = package.purl,
+                    name = package.name,
+                    id = package.id,
+                ));
+            }
+
+            if let (Some(version), Some(purl_version)) = (&package.version, purl.version()) {
+                if version != purl_version {
+                    self.report.error(format!(
+                        "Version of purl '{purl}' doesn't match version '{version}' of package '{id}'",
+                        purl = package.purl,
+                        id = package.id,
+                    ));
+                }
+            }
+        }
+    }
+
+    /// check if purls are unique
+    fn unique(&self) {
+        let mut seen = HashMap::<&str, &str>::new();
+
+        for package in &self.packages {
+            if let Some(first) = seen.insert(&package.purl, &package.id) {
+                if first != package.id {
+                    self.report.error(format!(
+                        "Duplicate purl '{purl}' of packages '{first}' and '{id}'",
+                        purl = package.purl,
+                        id = package.id,
+                    ));
+                }
+            }
+        }
+    }
+}
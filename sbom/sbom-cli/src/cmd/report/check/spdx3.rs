@@ -0,0 +1,74 @@
+use crate::cmd::report::ReportSink;
+use sbom_walker::model::spdx3::Spdx3;
+use std::collections::HashSet;
+
+pub fn all(report: &dyn ReportSink, spdx: Spdx3) {
+    Spdx3Checks {
+        report,
+        spdx: &spdx,
+    }
+    .all();
+}
+
+struct Spdx3Checks<'c> {
+    report: &'c dyn ReportSink,
+    spdx: &'c Spdx3,
+}
+
+impl Spdx3Checks<'_> {
+    pub fn all(&self) {
+        self.document();
+        self.rel_targets();
+    }
+
+    /// check if there is exactly one SPDX document element
+    fn document(&self) {
+        match self.spdx.elements_of_type("SpdxDocument").count() {
+            1 => {}
+            0 => self
+                .report
+                .error("Missing element of type 'SpdxDocument'".to_string()),
+            n => self.report.error(format!(
+                "Expected exactly one element of type 'SpdxDocument', found: {n}"
+            )),
+        }
+
+        if self.spdx.spec_version().is_none() {
+            self.report
+                .error("Missing 'specVersion' of 'CreationInfo'".to_string());
+        }
+    }
+
+    /// check if all relationships have valid targets
+    fn rel_targets(&self) {
+        let ids = self
+            .spdx
+            .graph
+            .iter()
+            .filter_map(|element| element.spdx_id.as_deref())
+            .collect::<HashSet<_>>();
+
+        // now see if all relationships have valid targets
+
+        for rel in self.spdx.relationships() {
+            if !ids.contains(rel.from) {
+                self.report.error(format!(
+                    "Invalid reference '{left}' of relationship '{left}' -[{rel}]-> {right:?}",
+                    left = rel.from,
+                    rel = rel.relationship_type,
+                    right = rel.to
+                ));
+            }
+            for to in &rel.to {
+                if !ids.contains(to) {
+                    self.report.error(format!(
+                        "Invalid reference '{to}' of relationship '{left}' -[{rel}]-> {right:?}",
+                        left = rel.from,
+                        rel = rel.relationship_type,
+                        right = rel.to
+                    ));
+                }
+            }
+        }
+    }
+}
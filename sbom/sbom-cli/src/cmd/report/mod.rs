@@ -5,8 +5,12 @@ use crate::{cmd::DiscoverArguments, common::walk_visitor};
 use parking_lot::Mutex;
 use reqwest::Url;
 use sbom_walker::{
+    discover::DiscoverConfig,
     discover::DiscoveredSbom,
-    model::sbom::ParseAnyError,
+    model::{
+        partial::{use_partial, PartialSpdx, DEFAULT_PARTIAL_THRESHOLD},
+        sbom::{Encoding, ParseAnyError},
+    },
     retrieve::{RetrievedSbom, RetrievingVisitor},
     validation::{ValidatedSbom, ValidationError, ValidationVisitor},
     Sbom,
@@ -14,6 +18,7 @@ use sbom_walker::{
 use serde_json::Value;
 use std::{
     collections::BTreeMap,
+    io::{BufWriter, Write},
     path::PathBuf,
     sync::{
         atomic::{AtomicUsize, Ordering},
@@ -22,9 +27,14 @@ use std::{
 };
 use tokio::task;
 use walker_common::{
-    cli::{client::ClientArguments, runner::RunnerArguments, validation::ValidationArguments},
+    cli::{
+        client::ClientArguments, gate::GateArguments, report::ReportStyleArguments,
+        runner::RunnerArguments, signing::SigningArguments, validation::ValidationArguments,
+    },
+    clock::Clock,
     compression::decompress,
     progress::Progress,
+    report::ReportOptions,
     utils::url::Urlify,
     validate::ValidationOptions,
 };
@@ -54,6 +64,16 @@ pub struct Report {
 
     #[command(flatten)]
     render: RenderOptions,
+
+    #[command(flatten)]
+    signing: SigningArguments,
+
+    #[command(flatten)]
+    gate: GateArguments,
+
+    /// Only parse the packages and relationships of SPDX documents of at least this size (in MiB), reducing the memory required for checking large documents. `0` disables this.
+    #[arg(long, env = "CSAF_WALKER_PARTIAL_THRESHOLD", default_value_t = DEFAULT_PARTIAL_THRESHOLD >> 20)]
+    partial_threshold: usize,
 }
 
 #[derive(clap::Args, Debug)]
@@ -69,6 +89,9 @@ pub struct RenderOptions {
     /// Override source URL
     #[arg(long)]
     source_url: Option<Url>,
+
+    #[command(flatten)]
+    style: ReportStyleArguments,
 }
 
 #[derive(Clone, Debug)]
@@ -89,18 +112,25 @@ impl ReportSink for (String, Arc<Mutex<BTreeMap<String, Vec<String>>>>) {
 
 impl Report {
     pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
-        let options: ValidationOptions = self.validation.into();
+        // the report only contains errors
+        self.gate.errors_only()?;
 
+        let offload = self.runner.offload();
+        let clock = self.runner.clock();
+        let options = ValidationOptions::try_from(self.validation)?.offload(offload.clone());
+
+        let partial_threshold = self.partial_threshold.saturating_mul(1024 * 1024);
         let total: Arc<AtomicUsize> = Default::default();
         let errors: Arc<Mutex<BTreeMap<String, Vec<String>>>> = Default::default();
 
         {
             let total = total.clone();
             let errors = errors.clone();
+            let budget = self.runner.memory_budget();
             walk_visitor(
                 progress,
                 self.client,
-                self.discover,
+                DiscoverConfig::from(self.discover).with_offload(offload),
                 self.runner,
                 |source| async move {
                     Ok(RetrievingVisitor::new(
@@ -116,7 +146,7 @@ impl Report {
                                     };
 
                                     task::spawn_blocking(move || {
-                                        Self::inspect(&(name, errors), sbom);
+                                        Self::inspect(&(name, errors), sbom, partial_threshold);
                                     })
                                     .await
                                     .expect("unable to spawn inspection");
@@ -126,31 +156,51 @@ impl Report {
                             },
                         )
                         .with_options(options),
-                    ))
+                    )
+                    .with_memory_budget(budget))
                 },
             )
             .await?;
         }
 
-        Self::render(
+        let output = Self::render(
             self.render,
             ReportResult {
                 errors: &errors.lock(),
                 total: total.load(Ordering::SeqCst),
             },
+            clock,
         )?;
 
+        self.signing.apply(&[output]).await?;
+
+        self.gate
+            .check(errors.lock().values().map(Vec::len).sum(), 0)?;
+
         Ok(())
     }
 
-    fn render(render: RenderOptions, report: ReportResult) -> anyhow::Result<()> {
-        let mut out = std::fs::File::create(&render.output)?;
-        render::render_to_html(&mut out, &report, &render)?;
+    fn render(
+        render: RenderOptions,
+        report: ReportResult,
+        clock: Clock,
+    ) -> anyhow::Result<PathBuf> {
+        let options = ReportOptions {
+            clock,
+            ..render.style.report_options()?
+        };
+        let mut out = BufWriter::new(std::fs::File::create(&render.output)?);
+        render::render_to_html(&mut out, &report, &render, &options)?;
+        out.flush()?;
 
-        Ok(())
+        Ok(render.output)
     }
 
-    fn inspect(report: &dyn ReportSink, sbom: Result<ValidatedSbom, ValidationError>) {
+    fn inspect(
+        report: &dyn ReportSink,
+        sbom: Result<ValidatedSbom, ValidationError>,
+        partial_threshold: usize,
+    ) {
         let sbom = match sbom {
             Ok(sbom) => sbom,
             Err(err) => {
@@ -159,6 +209,7 @@ impl Report {
             }
         };
 
+        let encoding = sbom.encoding();
         let ValidatedSbom {
             retrieved:
                 RetrievedSbom {
@@ -166,6 +217,7 @@ impl Report {
                     discovered: DiscoveredSbom { url, .. },
                     ..
                 },
+            ..
         } = sbom;
 
         let data = decompress(data, url.path());
@@ -178,12 +230,29 @@ impl Report {
             }
         };
 
+        if encoding.or_else(|| Encoding::sniff(&data)) == Some(Encoding::Xml) {
+            match Sbom::try_parse_any_xml(&data) {
+                Ok(sbom) => check::all(report, sbom),
+                Err(err) => report.error(format!("Failed to parse file: {err}")),
+            }
+            return;
+        }
+
+        if use_partial(data.len(), partial_threshold) {
+            match PartialSpdx::parse(&data) {
+                Ok(spdx) => {
+                    log::info!("Checked {url} partially, as it has {} bytes", data.len());
+                    check::partial_spdx(report, &spdx);
+                    return;
+                }
+                Err(err) => log::debug!("Unable to parse {url} partially: {err}"),
+            }
+        }
+
         let mut value = match serde_json::from_slice(&data) {
             Ok(value) => value,
             Err(err) => {
-                report.error(format!(
-                    "Failed to parse file as JSON: {err} (currently only JSON files are supported)"
-                ));
+                report.error(format!("Failed to parse file as JSON: {err}"));
                 return;
             }
         };
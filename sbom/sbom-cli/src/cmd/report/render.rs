@@ -3,20 +3,16 @@ use crate::cmd::report::RenderOptions;
 use reqwest::Url;
 use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
-use walker_common::report::Summary;
-use walker_common::{locale::Formatted, report};
+use walker_common::report::{ReportOptions, Summary};
+use walker_common::{locale::Formatted, report, run::run_id};
 
 pub fn render_to_html<W: std::io::Write>(
     out: &mut W,
     report: &ReportResult,
     render: &RenderOptions,
+    options: &ReportOptions,
 ) -> anyhow::Result<()> {
-    report::render(
-        out,
-        "SBOM Report",
-        HtmlReport(report, render),
-        &Default::default(),
-    )?;
+    report::render(out, "SBOM Report", HtmlReport(report, render), options)?;
 
     Ok(())
 }
@@ -105,6 +101,7 @@ impl HtmlReport<'_> {
         if let Some(source) = self.1.source_url.as_ref().or(self.1.base_url.as_ref()) {
             summary.push(("Source", source.to_string()));
         }
+        summary.push(("Correlation ID", run_id().to_string()));
 
         Summary(summary).fmt(f)
     }
@@ -5,9 +5,16 @@ use sbom_walker::{
     validation::{ValidatedSbom, ValidationError},
     Sbom,
 };
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 use tokio::task;
 use walker_common::{
-    cli::{client::ClientArguments, runner::RunnerArguments, validation::ValidationArguments},
+    cli::{
+        client::ClientArguments, gate::GateArguments, runner::RunnerArguments,
+        validation::ValidationArguments,
+    },
     compression::decompress,
     progress::Progress,
 };
@@ -26,52 +33,73 @@ pub struct Scan {
 
     #[command(flatten)]
     validation: ValidationArguments,
+
+    #[command(flatten)]
+    gate: GateArguments,
 }
 
 impl Scan {
     pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
-        walk_standard(
-            progress,
-            self.client,
-            self.runner,
-            self.discover,
-            self.validation,
-            |advisory: Result<ValidatedSbom, ValidationError>| async move {
-                match advisory {
-                    Ok(sbom) => {
-                        println!("Advisory: {}", sbom.url);
-                        log::debug!("  Metadata: {:?}", sbom.sha256);
-                        log::debug!("    SHA256: {:?}", sbom.sha256);
-                        log::debug!("    SHA512: {:?}", sbom.sha512);
-
-                        let ValidatedSbom {
-                            retrieved:
-                                RetrievedSbom {
-                                    data,
-                                    discovered: DiscoveredSbom { url, .. },
+        // scanning only finds errors
+        self.gate.errors_only()?;
+
+        let errors = Arc::new(AtomicUsize::default());
+
+        {
+            let errors = errors.clone();
+            walk_standard(
+                progress,
+                self.client,
+                self.runner,
+                self.discover,
+                self.validation,
+                move |advisory: Result<ValidatedSbom, ValidationError>| {
+                    let errors = errors.clone();
+                    async move {
+                        match advisory {
+                            Ok(sbom) => {
+                                println!("Advisory: {}", sbom.url);
+                                log::debug!("  Metadata: {:?}", sbom.sha256);
+                                log::debug!("    SHA256: {:?}", sbom.sha256);
+                                log::debug!("    SHA512: {:?}", sbom.sha512);
+
+                                let encoding = sbom.encoding();
+                                let ValidatedSbom {
+                                    retrieved:
+                                        RetrievedSbom {
+                                            data,
+                                            discovered: DiscoveredSbom { url, .. },
+                                            ..
+                                        },
                                     ..
-                                },
-                        } = sbom;
+                                } = sbom;
 
-                        let data =
-                            task::spawn_blocking(move || decompress(data, url.path())).await??;
+                                let data =
+                                    task::spawn_blocking(move || decompress(data, url.path()))
+                                        .await??;
 
-                        match Sbom::try_parse_any(&data) {
-                            Ok(sbom) => process_sbom(sbom),
+                                match Sbom::try_parse(&data, encoding) {
+                                    Ok(sbom) => process_sbom(sbom),
+                                    Err(err) => {
+                                        eprintln!("  Format error: {err}");
+                                        errors.fetch_add(1, Ordering::Release);
+                                    }
+                                }
+                            }
                             Err(err) => {
-                                eprintln!("  Format error: {err}");
+                                eprintln!("SBOM(ERR): {err}");
+                                errors.fetch_add(1, Ordering::Release);
                             }
                         }
+
+                        Ok::<_, anyhow::Error>(())
                     }
-                    Err(err) => {
-                        eprintln!("SBOM(ERR): {err}");
-                    }
-                }
+                },
+            )
+            .await?;
+        }
 
-                Ok::<_, anyhow::Error>(())
-            },
-        )
-        .await?;
+        self.gate.check(errors.load(Ordering::Acquire), 0)?;
 
         Ok(())
     }
@@ -89,5 +117,14 @@ fn process_sbom(sbom: Sbom) {
         Sbom::CycloneDx(_sbom) => {
             println!("  CycloneDX");
         }
+
+        Sbom::Spdx3(sbom) => {
+            println!(
+                "  SPDX 3: {}",
+                sbom.elements_of_type("SpdxDocument")
+                    .find_map(|doc| doc.name.as_deref())
+                    .unwrap_or_default()
+            );
+        }
     }
 }
@@ -42,7 +42,8 @@ pub struct Send {
 
 impl Send {
     pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
-        let options: ValidationOptions = self.validation.into();
+        let offload = self.runner.offload();
+        let options = ValidationOptions::try_from(self.validation)?.offload(offload.clone());
         let send: SendVisitor = self.send.into_visitor().await?;
 
         let since = Since::new(
@@ -52,14 +53,19 @@ impl Send {
                 .since_file_offset
                 .map(|d| d.into())
                 .unwrap_or_default(),
+            &self.runner.clock(),
         )?;
 
         log::debug!("Start walking");
 
-        walk_visitor(
+        let budget = self.runner.memory_budget();
+
+        let completion = walk_visitor(
             progress,
             self.client,
-            DiscoverConfig::from(self.discover).with_since(since.since),
+            DiscoverConfig::from(self.discover)
+                .with_since(since.since)
+                .with_offload(offload),
             self.runner,
             move |source| async move {
                 let visitor = {
@@ -70,6 +76,7 @@ impl Send {
                         })
                         .with_options(options)
                     })
+                    .with_memory_budget(budget)
                 };
 
                 Ok(visitor)
@@ -77,7 +84,7 @@ impl Send {
         )
         .await?;
 
-        since.store()?;
+        since.store_completion(completion.as_ref())?;
 
         Ok(())
     }
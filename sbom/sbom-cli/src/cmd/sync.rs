@@ -9,7 +9,10 @@ use sbom_walker::{
     visitors::{skip::SkipExistingVisitor, store::StoreVisitor},
 };
 use walker_common::{
-    cli::{client::ClientArguments, runner::RunnerArguments, validation::ValidationArguments},
+    cli::{
+        client::ClientArguments, lock::LockArguments, runner::RunnerArguments,
+        validation::ValidationArguments,
+    },
     progress::Progress,
     since::Since,
     validate::ValidationOptions,
@@ -35,11 +38,21 @@ pub struct Sync {
 
     #[command(flatten)]
     store: StoreArguments,
+
+    #[command(flatten)]
+    lock: LockArguments,
 }
 
 impl Sync {
     pub async fn run(self, progress: Progress) -> anyhow::Result<()> {
-        let options: ValidationOptions = self.validation.into();
+        let target = self.store.base()?;
+        let _locks = self
+            .lock
+            .lock(&target, self.skip.since_file.as_deref())
+            .await?;
+
+        let offload = self.runner.offload();
+        let options = ValidationOptions::try_from(self.validation)?.offload(offload.clone());
         let store: StoreVisitor = self.store.try_into()?;
         let base = store.base.clone();
 
@@ -50,12 +63,22 @@ impl Sync {
                 .since_file_offset
                 .map(|d| d.into())
                 .unwrap_or_default(),
+            &self.runner.clock(),
         )?;
 
-        walk_visitor(
+        let head = match self.skip.head_check {
+            true => Some(self.client.clone().new_fetcher().await?),
+            false => None,
+        };
+
+        let budget = self.runner.memory_budget();
+
+        let completion = walk_visitor(
             progress,
             self.client,
-            DiscoverConfig::from(self.discover).with_since(since.since),
+            DiscoverConfig::from(self.discover)
+                .with_since(since.since)
+                .with_offload(offload),
             self.runner,
             move |source| async move {
                 let base = base.clone();
@@ -64,18 +87,20 @@ impl Sync {
                         source.clone(),
                         ValidationVisitor::new(store).with_options(options),
                     )
+                    .with_memory_budget(budget)
                 };
 
                 Ok(SkipExistingVisitor {
                     visitor,
                     output: base,
                     since: since.since,
+                    head,
                 })
             },
         )
         .await?;
 
-        since.store()?;
+        since.store_completion(completion.as_ref())?;
 
         Ok(())
     }
@@ -6,11 +6,15 @@ use sbom_walker::{
     source::new_source,
     source::DispatchSource,
     validation::{ValidatedVisitor, ValidationVisitor},
-    walker::Walker,
+    walker::{Error, Walker},
 };
 use std::future::Future;
+use std::sync::Arc;
 use walker_common::{
     cli::{client::ClientArguments, runner::RunnerArguments, validation::ValidationArguments},
+    concurrency::AdaptiveConcurrency,
+    deadline::Completion,
+    fetcher::FetcherOptions,
     progress::Progress,
     validate::ValidationOptions,
 };
@@ -27,7 +31,10 @@ where
     V: ValidatedVisitor,
     V::Error: Send + Sync + 'static,
 {
-    let options: ValidationOptions = validation.into();
+    let offload = runner.offload();
+    let options = ValidationOptions::try_from(validation)?.offload(offload.clone());
+    let discover = DiscoverConfig::with_offload(discover.into(), offload);
+    let budget = runner.memory_budget();
 
     walk_visitor(
         progress,
@@ -38,10 +45,13 @@ where
             Ok(RetrievingVisitor::new(
                 source.clone(),
                 ValidationVisitor::new(visitor).with_options(options),
-            ))
+            )
+            .with_memory_budget(budget))
         },
     )
-    .await
+    .await?;
+
+    Ok(())
 }
 
 impl From<DiscoverArguments> for DiscoverConfig {
@@ -54,6 +64,10 @@ impl From<DiscoverArguments> for DiscoverConfig {
                 .into_iter()
                 .map(metadata::Key::from)
                 .collect::<Vec<_>>(),
+            decryption: value.decryption_key,
+            offload: None,
+            recursive: value.recursive,
+            manifest: value.manifest,
         }
     }
 }
@@ -64,41 +78,56 @@ pub async fn walk_visitor<F, Fut, V>(
     discover: impl Into<DiscoverConfig>,
     runner: RunnerArguments,
     f: F,
-) -> anyhow::Result<()>
+) -> anyhow::Result<Option<Completion>>
 where
     F: FnOnce(DispatchSource) -> Fut,
     Fut: Future<Output = anyhow::Result<V>>,
     V: DiscoveredVisitor,
     V::Error: Send + Sync + 'static,
 {
-    let source = new_source(discover, client).await?;
+    let adaptive = runner.adaptive();
+    let source = new_source(
+        discover,
+        FetcherOptions::from(client).adaptive(adaptive.clone()),
+    )
+    .await?;
 
-    walk_source(progress, source, runner, f).await
+    walk_source(progress, source, runner, adaptive, f).await
 }
 
 pub async fn walk_source<F, Fut, V>(
     progress: Progress,
     source: DispatchSource,
     runner: RunnerArguments,
+    adaptive: Option<Arc<AdaptiveConcurrency>>,
     f: F,
-) -> anyhow::Result<()>
+) -> anyhow::Result<Option<Completion>>
 where
     F: FnOnce(DispatchSource) -> Fut,
     Fut: Future<Output = anyhow::Result<V>>,
     V: DiscoveredVisitor,
     V::Error: Send + Sync + 'static,
 {
+    let deadline = runner.deadline();
+    let checkpoint = runner.checkpoint()?;
     let visitor = f(source.clone()).await?;
-    let walker = Walker::new(source).with_progress(progress);
+    let walker = Walker::new(source)
+        .with_progress(progress)
+        .with_deadline(deadline)
+        .with_adaptive_concurrency(adaptive)
+        .with_checkpoint(checkpoint);
 
-    match runner.workers {
-        1 => {
-            walker.walk(visitor).await?;
-        }
-        n => {
-            walker.walk_parallel(n, visitor).await?;
+    let result = match runner.workers {
+        1 => walker.walk(visitor).await,
+        n => walker.walk_parallel(n, visitor).await,
+    };
+
+    match result {
+        Ok(_) => Ok(None),
+        Err(Error::Deadline(completion)) => {
+            log::warn!("Deadline exceeded: {completion}");
+            Ok(Some(completion))
         }
+        Err(err) => Err(err.into()),
     }
-
-    Ok(())
 }
@@ -6,7 +6,11 @@ use crate::cmd::{
 };
 use clap::Parser;
 use std::process::ExitCode;
-use walker_common::{cli::log::Logging, progress::Progress, utils::measure::MeasureTime};
+use walker_common::{
+    cli::{log::Logging, parse::parse},
+    progress::Progress,
+    utils::measure::MeasureTime,
+};
 
 #[derive(Debug, Parser)]
 #[command(version, about = "SBOM Tool", author, long_about = None)]
@@ -59,7 +63,7 @@ impl Cli {
 
 #[tokio::main]
 async fn main() -> ExitCode {
-    if let Err(err) = Cli::parse().run().await {
+    if let Err(err) = parse::<Cli>().run().await {
         log::error!("Failed to execute: {err}");
         for (n, cause) in err.chain().enumerate().skip(1) {
             log::info!("  {n}: {cause}");
@@ -2,12 +2,14 @@
 
 use crate::model::metadata;
 use crate::model::metadata::SourceMetadata;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::future::Future;
 use std::ops::Deref;
+use std::path::PathBuf;
 use std::time::SystemTime;
 use url::Url;
-use walker_common::utils::url::Urlify;
+use walker_common::{encryption::Decryption, offload::Offload, utils::url::Urlify};
 
 /// Discovery configuration
 pub struct DiscoverConfig {
@@ -23,6 +25,18 @@ pub struct DiscoverConfig {
 
     /// Keys which can be used for validation
     pub keys: Vec<metadata::Key>,
+
+    /// Decrypt documents of a file source, which have been stored encrypted.
+    pub decryption: Option<Decryption>,
+
+    /// Calculate digests on the blocking thread pool.
+    pub offload: Option<Offload>,
+
+    /// Scan the subdirectories of a file source as well.
+    pub recursive: bool,
+
+    /// A manifest, listing the documents of a file source (see [`crate::source::Manifest`]).
+    pub manifest: Option<PathBuf>,
 }
 
 impl DiscoverConfig {
@@ -30,6 +44,26 @@ impl DiscoverConfig {
         self.since = since.into();
         self
     }
+
+    pub fn with_decryption(mut self, decryption: impl Into<Option<Decryption>>) -> Self {
+        self.decryption = decryption.into();
+        self
+    }
+
+    pub fn with_offload(mut self, offload: impl Into<Option<Offload>>) -> Self {
+        self.offload = offload.into();
+        self
+    }
+
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    pub fn with_manifest(mut self, manifest: impl Into<Option<PathBuf>>) -> Self {
+        self.manifest = manifest.into();
+        self
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -38,6 +72,8 @@ pub struct DiscoveredSbom {
     pub url: Url,
     /// The "last changed" date from the change information
     pub modified: SystemTime,
+    /// Additional information about the SBOM, like the product and version, if known
+    pub labels: BTreeMap<String, String>,
 }
 
 impl Urlify for DiscoveredSbom {
@@ -1,2 +1,5 @@
 pub mod metadata;
+pub mod partial;
+pub mod provider;
 pub mod sbom;
+pub mod spdx3;
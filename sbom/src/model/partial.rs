@@ -0,0 +1,128 @@
+//! Partial parsing of large SPDX documents
+//!
+//! Parsing an SPDX document into its full model (or into a JSON [`serde_json::Value`]) requires
+//! a multiple of its size in memory, which becomes a problem for documents of a gigabyte or more.
+//! Most checks only need the packages and relationships of a document. [`PartialSpdx`] only keeps
+//! those, skipping all other sections (like files and snippets) while parsing.
+
+use serde::Deserialize;
+use std::io::Read;
+
+/// The default size (in bytes) from which on documents should be parsed partially.
+pub const DEFAULT_PARTIAL_THRESHOLD: usize = 256 * 1024 * 1024;
+
+/// Check if a document of that size should be parsed partially.
+///
+/// A threshold of zero disables parsing partially.
+pub fn use_partial(size: usize, threshold: usize) -> bool {
+    threshold > 0 && size >= threshold
+}
+
+/// The packages and relationships of an SPDX 2.x JSON document
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialSpdx {
+    pub spdx_version: String,
+    #[serde(rename = "SPDXID")]
+    pub spdx_id: String,
+    #[serde(default)]
+    pub packages: Vec<PartialPackage>,
+    #[serde(default)]
+    pub relationships: Vec<PartialRelationship>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialPackage {
+    #[serde(rename = "SPDXID")]
+    pub spdx_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub version_info: Option<String>,
+    #[serde(default)]
+    pub license_declared: Option<String>,
+    #[serde(default)]
+    pub external_refs: Vec<PartialExternalRef>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialExternalRef {
+    pub reference_type: String,
+    pub reference_locator: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialRelationship {
+    pub spdx_element_id: String,
+    pub relationship_type: String,
+    pub related_spdx_element: String,
+}
+
+impl PartialSpdx {
+    /// Parse the relevant sections of a document.
+    pub fn parse(data: &[u8]) -> anyhow::Result<Self> {
+        Self::check(serde_json::from_slice(data)?)
+    }
+
+    /// Parse the relevant sections of a document, while reading it (e.g. from a decompressing
+    /// reader), without keeping the full document in memory.
+    pub fn from_reader(reader: impl Read) -> anyhow::Result<Self> {
+        Self::check(serde_json::from_reader(reader)?)
+    }
+
+    fn check(spdx: Self) -> anyhow::Result<Self> {
+        match spdx.spdx_version.as_str() {
+            "SPDX-2.2" | "SPDX-2.3" => Ok(spdx),
+            version => anyhow::bail!("Unsupported SPDX version: {version}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let spdx = PartialSpdx::parse(
+            serde_json::json!({
+                "spdxVersion": "SPDX-2.3",
+                "SPDXID": "SPDXRef-DOCUMENT",
+                "name": "example",
+                "packages": [{
+                    "SPDXID": "SPDXRef-a",
+                    "name": "a",
+                    "versionInfo": "1.0",
+                    "licenseDeclared": "Apache-2.0",
+                    "externalRefs": [{
+                        "referenceCategory": "PACKAGE-MANAGER",
+                        "referenceType": "purl",
+                        "referenceLocator": "pkg:cargo/a@1.0",
+                    }],
+                }],
+                "files": [{ "SPDXID": "SPDXRef-file", "fileName": "./a" }],
+                "relationships": [{
+                    "spdxElementId": "SPDXRef-DOCUMENT",
+                    "relationshipType": "DESCRIBES",
+                    "relatedSpdxElement": "SPDXRef-a",
+                }],
+            })
+            .to_string()
+            .as_bytes(),
+        )
+        .expect("document must parse");
+
+        assert_eq!(spdx.packages.len(), 1);
+        assert_eq!(spdx.packages[0].external_refs[0].reference_type, "purl");
+        assert_eq!(spdx.relationships[0].relationship_type, "DESCRIBES");
+
+        assert!(PartialSpdx::parse(br#"{"spdxVersion": "SPDX-3.0", "SPDXID": "x"}"#).is_err());
+        assert!(use_partial(
+            DEFAULT_PARTIAL_THRESHOLD,
+            DEFAULT_PARTIAL_THRESHOLD
+        ));
+        assert!(!use_partial(DEFAULT_PARTIAL_THRESHOLD, 0));
+    }
+}
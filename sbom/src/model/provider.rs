@@ -0,0 +1,102 @@
+//! Metadata of an SBOM provider
+//!
+//! Similar to the provider metadata of CSAF, a provider can publish a JSON document describing its
+//! collection of SBOMs: the keys used for signing them, and the directories they are distributed
+//! in. Each directory must provide a `changes.csv` file, the same as a directory used as a source
+//! directly.
+//!
+//! ```json
+//! {
+//!   "canonical_url": "https://example.com/sbom/provider-metadata.json",
+//!   "publisher": { "name": "Example Inc.", "namespace": "https://example.com" },
+//!   "last_updated": "2024-01-01T00:00:00Z",
+//!   "public_openpgp_keys": [
+//!     { "fingerprint": "0123456789ABCDEF", "url": "https://example.com/sbom/key.txt" }
+//!   ],
+//!   "distributions": [
+//!     { "directory_url": "https://example.com/sbom/product-a/" }
+//!   ]
+//! }
+//! ```
+
+use crate::model::metadata::Key;
+use time::OffsetDateTime;
+use url::Url;
+
+/// The metadata of an SBOM provider
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct ProviderMetadata {
+    /// The URL of the metadata document itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<Url>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<Publisher>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "time::serde::rfc3339::option"
+    )]
+    pub last_updated: Option<OffsetDateTime>,
+
+    /// Keys used for signing the SBOMs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub public_openpgp_keys: Vec<Key>,
+
+    /// Directories containing SBOMs, each with a `changes.csv` file
+    #[serde(default)]
+    pub distributions: Vec<Distribution>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct Publisher {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<Url>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct Distribution {
+    pub directory_url: Url,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let metadata: ProviderMetadata = serde_json::from_str(
+            r#"{
+  "publisher": { "name": "Example Inc." },
+  "last_updated": "2024-01-01T00:00:00Z",
+  "public_openpgp_keys": [
+    { "fingerprint": "0123456789ABCDEF", "url": "https://example.com/sbom/key.txt" }
+  ],
+  "distributions": [
+    { "directory_url": "https://example.com/sbom/product-a/" },
+    { "directory_url": "https://example.com/sbom/product-b/" }
+  ]
+}"#,
+        )
+        .expect("example value must parse");
+
+        assert_eq!(metadata.public_openpgp_keys.len(), 1);
+        assert_eq!(
+            metadata.public_openpgp_keys[0].fingerprint.as_deref(),
+            Some("0123456789ABCDEF")
+        );
+        assert_eq!(
+            metadata
+                .distributions
+                .iter()
+                .map(|d| d.directory_url.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "https://example.com/sbom/product-a/",
+                "https://example.com/sbom/product-b/"
+            ]
+        );
+    }
+}
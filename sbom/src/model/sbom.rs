@@ -1,9 +1,13 @@
 //! SBOM Model
 
+use super::spdx3::Spdx3;
 use anyhow::{anyhow, bail};
 use serde::Deserialize;
 use serde_json::Value;
-use std::fmt::{Debug, Display, Formatter};
+use std::{
+    fmt::{Debug, Display, Formatter},
+    str::FromStr,
+};
 
 pub enum Parser {
     CycloneDxJson,
@@ -16,11 +20,11 @@ pub enum Sbom {
     Spdx(spdx_rs::models::SPDX),
     #[cfg(feature = "cyclonedx-bom")]
     CycloneDx(cyclonedx_bom::prelude::Bom),
+    Spdx3(Spdx3),
 }
 
 impl Debug for Sbom {
-    fn fmt(&self, #[allow(unused)] f: &mut Formatter<'_>) -> std::fmt::Result {
-        #[cfg(any(feature = "spdx-rs", feature = "cyclonedx-bom"))]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             #[cfg(feature = "spdx-rs")]
             Self::Spdx(doc) => f.debug_tuple("Spdx").field(doc).finish()?,
@@ -29,6 +33,7 @@ impl Debug for Sbom {
                 .debug_tuple("CycloneDx")
                 .field(&"unable to display")
                 .finish()?,
+            Self::Spdx3(doc) => f.debug_tuple("Spdx3").field(doc).finish()?,
         }
 
         Ok(())
@@ -81,6 +86,7 @@ pub enum ParserKind {
     Cyclone13DxXml,
     Spdx23Json,
     Spdx23Tag,
+    Spdx3JsonLd,
 }
 
 impl Display for ParserKind {
@@ -90,6 +96,105 @@ impl Display for ParserKind {
             Self::Cyclone13DxXml => write!(f, "CycloneDX 1.3 XML"),
             Self::Spdx23Json => write!(f, "SPDX 2.3 JSON"),
             Self::Spdx23Tag => write!(f, "SPDX 2.3 tagged"),
+            Self::Spdx3JsonLd => write!(f, "SPDX 3 JSON-LD"),
+        }
+    }
+}
+
+/// CycloneDX specification versions known for parsing
+///
+/// Version 1.6 is detected, but can't be parsed yet, as `cyclonedx-bom` 0.6 only supports up to
+/// version 1.5.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CycloneDxVersion {
+    V1_2,
+    V1_3,
+    V1_4,
+    V1_5,
+    V1_6,
+}
+
+impl FromStr for CycloneDxVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "1.2" => Self::V1_2,
+            "1.3" => Self::V1_3,
+            "1.4" => Self::V1_4,
+            "1.5" => Self::V1_5,
+            "1.6" => Self::V1_6,
+            _ => bail!("Unsupported CycloneDX version: {s}"),
+        })
+    }
+}
+
+impl Display for CycloneDxVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::V1_2 => write!(f, "1.2"),
+            Self::V1_3 => write!(f, "1.3"),
+            Self::V1_4 => write!(f, "1.4"),
+            Self::V1_5 => write!(f, "1.5"),
+            Self::V1_6 => write!(f, "1.6"),
+        }
+    }
+}
+
+/// The serialization format of an SBOM document
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    Json,
+    Xml,
+    Tag,
+}
+
+impl Encoding {
+    /// Detect the encoding from a file name or URL path, ignoring compression suffixes
+    pub fn from_path(path: &str) -> Option<Self> {
+        let path = path.strip_suffix(".bz2").unwrap_or(path);
+
+        if path.ends_with(".json") {
+            Some(Self::Json)
+        } else if path.ends_with(".xml") {
+            Some(Self::Xml)
+        } else if path.ends_with(".spdx") {
+            Some(Self::Tag)
+        } else {
+            None
+        }
+    }
+
+    /// Detect the encoding from a content type, ignoring any parameters
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let media_type = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+
+        match media_type.as_str() {
+            "application/json" | "application/ld+json" | "application/spdx+json" => {
+                Some(Self::Json)
+            }
+            "application/xml" | "text/xml" => Some(Self::Xml),
+            "text/spdx" => Some(Self::Tag),
+            media_type if media_type.ends_with("+json") => Some(Self::Json),
+            media_type if media_type.ends_with("+xml") => Some(Self::Xml),
+            _ => None,
+        }
+    }
+
+    /// Detect the encoding by looking at the content
+    pub fn sniff(data: &[u8]) -> Option<Self> {
+        // skip a byte order mark
+        let data = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+
+        match data.iter().find(|b| !b.is_ascii_whitespace()) {
+            Some(b'{') => Some(Self::Json),
+            Some(b'<') => Some(Self::Xml),
+            _ => None,
         }
     }
 }
@@ -121,24 +226,38 @@ impl Sbom {
         Ok(version)
     }
 
+    /// test if the file is a SPDX 3 (JSON-LD) document
+    pub fn is_spdx3_json(json: &Value) -> anyhow::Result<()> {
+        let context = json
+            .get("@context")
+            .ok_or_else(|| anyhow!("Missing field '@context'"))?;
+
+        if !Spdx3::is_spdx3_context(context) {
+            bail!("Unknown SPDX 3 '@context' value: {context}");
+        }
+
+        Ok(())
+    }
+
     pub fn try_parse_any_json(json: Value) -> Result<Self, ParseAnyError> {
         let err = ParseAnyError::new();
 
         #[cfg(feature = "cyclonedx-bom")]
-        let err = match Self::is_cyclondx_json(&json) {
-            Ok("1.2" | "1.3" | "1.4") => {
-                return Self::try_cyclonedx_json(JsonPayload::Value(json)).map_err(|e| {
-                    // drop any previous error, as we know what format and version it is
-                    ParseAnyError::from((ParserKind::Cyclone13DxJson, e.into()))
-                });
+        let err = match Self::is_cyclondx_json(&json).map(CycloneDxVersion::from_str) {
+            Ok(Ok(version)) => {
+                return Self::try_cyclonedx_json_version(version, JsonPayload::Value(json))
+                    .map_err(|e| {
+                        // drop any previous error, as we know what format and version it is
+                        ParseAnyError::from((
+                            ParserKind::Cyclone13DxJson,
+                            e.context(format!("CycloneDX {version}")),
+                        ))
+                    });
             }
-            Ok(version) => {
+            Ok(Err(e)) => {
                 // We can stop here, and drop any previous error, as we know what the format is.
                 // But we disagree with the version.
-                return Err(ParseAnyError::from((
-                    ParserKind::Cyclone13DxJson,
-                    anyhow!("Unsupported CycloneDX version: {version}"),
-                )));
+                return Err(ParseAnyError::from((ParserKind::Cyclone13DxJson, e)));
             }
             // failed to detect as CycloneDX, record error and move on
             Err(e) => err.add(ParserKind::Cyclone13DxJson, e),
@@ -163,13 +282,49 @@ impl Sbom {
             Err(e) => err.add(ParserKind::Spdx23Json, e),
         };
 
-        // mark as unused for clippy
-        let _json = json;
+        let err = match Self::is_spdx3_json(&json) {
+            Ok(()) => {
+                return Self::try_spdx3_json(JsonPayload::Value(json)).map_err(|e| {
+                    // drop any previous error, as we know what format it is
+                    ParseAnyError::from((ParserKind::Spdx3JsonLd, e.into()))
+                });
+            }
+            Err(e) => err.add(ParserKind::Spdx3JsonLd, e),
+        };
+
         Err(err)
     }
 
     /// try parsing with all possible kinds that make sense.
     pub fn try_parse_any(data: &[u8]) -> Result<Self, ParseAnyError> {
+        Self::try_parse(data, None)
+    }
+
+    /// try parsing with the kinds that make sense for the encoding.
+    ///
+    /// If no encoding is provided (e.g. from the content type or file name), the content will be
+    /// inspected to detect XML documents. Otherwise all kinds will be tried.
+    pub fn try_parse(data: &[u8], encoding: Option<Encoding>) -> Result<Self, ParseAnyError> {
+        match encoding.or_else(|| Encoding::sniff(data)) {
+            Some(Encoding::Xml) => Self::try_parse_any_xml(data),
+            _ => Self::try_parse_any_unknown(data),
+        }
+    }
+
+    /// try parsing as XML document
+    pub fn try_parse_any_xml(#[allow(unused)] data: &[u8]) -> Result<Self, ParseAnyError> {
+        let err = ParseAnyError::new();
+
+        #[cfg(feature = "cyclonedx-bom")]
+        let err = match Self::try_cyclonedx_xml(data) {
+            Ok(doc) => return Ok(doc),
+            Err(e) => err.add(ParserKind::Cyclone13DxXml, e),
+        };
+
+        Err(err)
+    }
+
+    fn try_parse_any_unknown(data: &[u8]) -> Result<Self, ParseAnyError> {
         #[allow(unused)]
         if let Ok(json) = serde_json::from_slice(data) {
             // try to parse this as JSON, which eliminates e.g. the "tag" format, which seems to just parse anything
@@ -182,7 +337,7 @@ impl Sbom {
             #[cfg(feature = "cyclonedx-bom")]
             let err = match Self::try_cyclonedx_xml(data) {
                 Ok(doc) => return Ok(doc),
-                Err(e) => err.add(ParserKind::Cyclone13DxXml, e.into()),
+                Err(e) => err.add(ParserKind::Cyclone13DxXml, e),
             };
 
             #[cfg(feature = "spdx-rs")]
@@ -211,6 +366,10 @@ impl Sbom {
         Ok(Self::Spdx(spdx_rs::parsers::spdx_from_tag_value(data)?))
     }
 
+    pub fn try_spdx3_json(data: JsonPayload) -> Result<Self, serde_json::Error> {
+        Ok(Self::Spdx3(data.parse()?))
+    }
+
     #[cfg(feature = "cyclonedx-bom")]
     pub fn try_cyclonedx_json<'a>(
         data: impl Into<JsonPayload<'a>>,
@@ -223,11 +382,76 @@ impl Sbom {
         }
     }
 
+    /// Parse a CycloneDX JSON document, using the parser of the provided spec version.
+    ///
+    /// Unlike [`Self::try_cyclonedx_json`], this doesn't coerce the document into a different
+    /// version, but fails if the document doesn't match the version's schema.
     #[cfg(feature = "cyclonedx-bom")]
-    pub fn try_cyclonedx_xml(data: &[u8]) -> Result<Self, cyclonedx_bom::errors::XmlReadError> {
-        Ok(Self::CycloneDx(
-            cyclonedx_bom::prelude::Bom::parse_from_xml_v1_3(data)?,
-        ))
+    pub fn try_cyclonedx_json_version<'a>(
+        version: CycloneDxVersion,
+        data: impl Into<JsonPayload<'a>>,
+    ) -> anyhow::Result<Self> {
+        use cyclonedx_bom::prelude::Bom;
+
+        let bom = match (version, data.into()) {
+            (CycloneDxVersion::V1_6, _) => bail!("Unsupported spec version {version}"),
+            (CycloneDxVersion::V1_2 | CycloneDxVersion::V1_3, JsonPayload::Value(json)) => {
+                Bom::parse_json_value_v1_3(json)?
+            }
+            (CycloneDxVersion::V1_4, JsonPayload::Value(json)) => Bom::parse_json_value_v1_4(json)?,
+            (CycloneDxVersion::V1_5, JsonPayload::Value(json)) => Bom::parse_json_value_v1_5(json)?,
+            (CycloneDxVersion::V1_2 | CycloneDxVersion::V1_3, JsonPayload::Bytes(data)) => {
+                Bom::parse_from_json_v1_3(data)?
+            }
+            (CycloneDxVersion::V1_4, JsonPayload::Bytes(data)) => Bom::parse_from_json_v1_4(data)?,
+            (CycloneDxVersion::V1_5, JsonPayload::Bytes(data)) => Bom::parse_from_json_v1_5(data)?,
+        };
+
+        Ok(Self::CycloneDx(bom))
+    }
+
+    /// test if the document is a CycloneDX XML document, returning the spec version
+    ///
+    /// This only inspects the namespace declaration at the start of the document.
+    pub fn is_cyclonedx_xml(data: &[u8]) -> anyhow::Result<CycloneDxVersion> {
+        const NAMESPACE: &[u8] = b"http://cyclonedx.org/schema/bom/";
+
+        // the namespace is declared on the root element, no need to scan the full document
+        let head = &data[..data.len().min(4096)];
+
+        let start = head
+            .windows(NAMESPACE.len())
+            .position(|w| w == NAMESPACE)
+            .ok_or_else(|| anyhow!("Missing CycloneDX namespace"))?
+            + NAMESPACE.len();
+
+        let version = head[start..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit() || **b == b'.')
+            .map(|b| *b as char)
+            .collect::<String>();
+
+        version.parse()
+    }
+
+    /// Parse a CycloneDX XML document, using the parser of the spec version declared by its
+    /// namespace.
+    #[cfg(feature = "cyclonedx-bom")]
+    pub fn try_cyclonedx_xml(data: &[u8]) -> anyhow::Result<Self> {
+        use anyhow::Context;
+        use cyclonedx_bom::prelude::Bom;
+
+        let version = Self::is_cyclonedx_xml(data)?;
+
+        let bom = match version {
+            CycloneDxVersion::V1_6 => bail!("Unsupported spec version {version}"),
+            CycloneDxVersion::V1_2 | CycloneDxVersion::V1_3 => Bom::parse_from_xml_v1_3(data),
+            CycloneDxVersion::V1_4 => Bom::parse_from_xml_v1_4(data),
+            CycloneDxVersion::V1_5 => Bom::parse_from_xml_v1_5(data),
+        }
+        .with_context(|| format!("CycloneDX {version}"))?;
+
+        Ok(Self::CycloneDx(bom))
     }
 }
 
@@ -0,0 +1,102 @@
+//! SPDX 3.0 model
+//!
+//! SPDX 3 documents are serialized as JSON-LD, consisting of a context and a flat graph of
+//! elements. This model keeps the well-known properties typed, and all others as raw JSON.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Prefix of the JSON-LD context URLs of SPDX 3 documents
+pub const CONTEXT_PREFIX: &str = "https://spdx.org/rdf/3.";
+
+/// An SPDX 3 document, serialized as JSON-LD
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Spdx3 {
+    #[serde(rename = "@context")]
+    pub context: Value,
+    #[serde(rename = "@graph", default)]
+    pub graph: Vec<Element>,
+}
+
+/// An element of the SPDX 3 graph
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Element {
+    #[serde(rename = "type")]
+    pub r#type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spdx_id: Option<String>,
+    /// Blank node ID, used e.g. by `CreationInfo`
+    #[serde(rename = "@id", default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub properties: Map<String, Value>,
+}
+
+/// A view on an element of type `Relationship`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Relationship<'a> {
+    pub spdx_id: Option<&'a str>,
+    pub relationship_type: &'a str,
+    pub from: &'a str,
+    pub to: Vec<&'a str>,
+}
+
+impl Spdx3 {
+    /// test if the context is an SPDX 3 context
+    pub fn is_spdx3_context(context: &Value) -> bool {
+        match context {
+            Value::String(context) => context.starts_with(CONTEXT_PREFIX),
+            Value::Array(contexts) => contexts.iter().any(Self::is_spdx3_context),
+            _ => false,
+        }
+    }
+
+    /// The spec version, taken from the first `CreationInfo` element
+    pub fn spec_version(&self) -> Option<&str> {
+        self.elements_of_type("CreationInfo")
+            .find_map(|element| element.properties.get("specVersion"))
+            .and_then(Value::as_str)
+    }
+
+    /// All elements of the provided type
+    pub fn elements_of_type<'a>(&'a self, r#type: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.graph
+            .iter()
+            .filter(move |element| element.r#type == r#type)
+    }
+
+    /// All relationships of the graph
+    pub fn relationships(&self) -> impl Iterator<Item = Relationship<'_>> {
+        self.graph.iter().filter_map(Element::as_relationship)
+    }
+}
+
+impl Element {
+    /// Get a property as string
+    pub fn property_str(&self, name: &str) -> Option<&str> {
+        self.properties.get(name).and_then(Value::as_str)
+    }
+
+    /// Interpret the element as relationship, if it is one
+    pub fn as_relationship(&self) -> Option<Relationship<'_>> {
+        if self.r#type != "Relationship" {
+            return None;
+        }
+
+        let to = match self.properties.get("to") {
+            Some(Value::String(to)) => vec![to.as_str()],
+            Some(Value::Array(to)) => to.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+
+        Some(Relationship {
+            spdx_id: self.spdx_id.as_deref(),
+            relationship_type: self.property_str("relationshipType").unwrap_or_default(),
+            from: self.property_str("from")?,
+            to,
+        })
+    }
+}
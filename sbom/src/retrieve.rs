@@ -2,6 +2,7 @@
 
 use crate::{
     discover::{DiscoveredContext, DiscoveredSbom, DiscoveredVisitor},
+    model::sbom::Encoding,
     source::Source,
 };
 use bytes::Bytes;
@@ -10,9 +11,12 @@ use sha2::{Sha256, Sha512};
 use std::fmt::Debug;
 use std::future::Future;
 use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
 use url::Url;
 use walker_common::{
+    budget::MemoryBudget,
     retrieve::{RetrievalMetadata, RetrievedDigest},
+    stage,
     utils::{openpgp::PublicKey, url::Urlify},
     validate::source::{KeySource, KeySourceError},
 };
@@ -47,6 +51,18 @@ impl Urlify for RetrievedSbom {
     }
 }
 
+impl RetrievedSbom {
+    /// The encoding of the document, from the content type reported by the server, falling back
+    /// to the file name.
+    pub fn encoding(&self) -> Option<Encoding> {
+        self.metadata
+            .content_type
+            .as_deref()
+            .and_then(Encoding::from_content_type)
+            .or_else(|| Encoding::from_path(self.url.path()))
+    }
+}
+
 impl Deref for RetrievedSbom {
     type Target = DiscoveredSbom;
 
@@ -68,12 +84,18 @@ pub enum RetrievalError {
         code: StatusCode,
         discovered: DiscoveredSbom,
     },
+    #[error("Skipped retrieving: {reason}")]
+    Skipped {
+        reason: String,
+        discovered: DiscoveredSbom,
+    },
 }
 
 impl Urlify for RetrievalError {
     fn url(&self) -> &Url {
         match self {
             Self::InvalidResponse { discovered, .. } => &discovered.url,
+            Self::Skipped { discovered, .. } => &discovered.url,
         }
     }
 }
@@ -135,6 +157,7 @@ where
 pub struct RetrievingVisitor<V: RetrievedVisitor, S: Source + KeySource> {
     visitor: V,
     source: S,
+    budget: Option<Arc<MemoryBudget>>,
 }
 
 impl<V, S> RetrievingVisitor<V, S>
@@ -143,7 +166,18 @@ where
     S: Source + KeySource,
 {
     pub fn new(source: S, visitor: V) -> Self {
-        Self { visitor, source }
+        Self {
+            visitor,
+            source,
+            budget: None,
+        }
+    }
+
+    /// Wait for a shared memory budget before retrieving a document, and reserve its size until
+    /// it was processed by the next visitor.
+    pub fn with_memory_budget(mut self, budget: impl Into<Option<Arc<MemoryBudget>>>) -> Self {
+        self.budget = budget.into();
+        self
     }
 }
 
@@ -185,7 +219,10 @@ where
             );
         }
 
-        log::info!(
+        tracing::info!(
+            target: stage::RETRIEVE,
+            stage = "retrieve",
+            keys = keys.len(),
             "Loaded {} public key{}",
             keys.len(),
             (keys.len() != 1).then_some("s").unwrap_or_default()
@@ -213,14 +250,56 @@ where
         context: &Self::Context,
         discovered: DiscoveredSbom,
     ) -> Result<(), Self::Error> {
-        let sbom = self
-            .source
-            .load_sbom(discovered)
-            .await
-            .map_err(Error::Source)?;
+        let url = &discovered.url;
+
+        if let Some(budget) = &self.budget {
+            budget.ready().await;
+        }
+
+        let result = match self.source.load_sbom(discovered.clone()).await {
+            Ok(sbom) => {
+                tracing::debug!(
+                    target: stage::RETRIEVE,
+                    stage = "retrieve",
+                    url = %url,
+                    outcome = "retrieved",
+                    "Retrieved SBOM"
+                );
+                Ok(sbom)
+            }
+            Err(err) => match S::skip_reason(&err) {
+                Some(reason) => {
+                    tracing::info!(
+                        target: stage::RETRIEVE,
+                        stage = "retrieve",
+                        url = %url,
+                        outcome = "skipped",
+                        reason = %reason,
+                        "Skipped SBOM"
+                    );
+                    Err(RetrievalError::Skipped { reason, discovered })
+                }
+                None => {
+                    tracing::warn!(
+                        target: stage::RETRIEVE,
+                        stage = "retrieve",
+                        url = %url,
+                        outcome = "failed",
+                        error = %err,
+                        "Failed to retrieve SBOM"
+                    );
+                    return Err(Error::Source(err));
+                }
+            },
+        };
+
+        let _reservation = match (&self.budget, &result) {
+            (Some(budget), Ok(retrieved)) => Some(budget.reserve(retrieved.data.len())),
+            _ => None,
+        };
 
         self.visitor
-            .visit_sbom(context, Ok(sbom))
+            .visit_sbom(context, result)
             .await
             .map_err(Error::Visitor)?;
 
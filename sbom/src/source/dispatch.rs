@@ -3,6 +3,7 @@ use crate::model::metadata::SourceMetadata;
 use crate::retrieve::RetrievedSbom;
 use crate::source::{FileSource, HttpSource, Source};
 use walker_common::{
+    fetcher,
     utils::openpgp::PublicKey,
     validate::source::{Key, KeySource, KeySourceError, MapSourceError},
 };
@@ -35,6 +36,14 @@ impl From<FileSource> for DispatchSource {
 impl Source for DispatchSource {
     type Error = anyhow::Error;
 
+    fn skip_reason(error: &Self::Error) -> Option<String> {
+        error
+            .chain()
+            .filter_map(|err| err.downcast_ref::<fetcher::Error>())
+            .any(fetcher::Error::is_circuit_open)
+            .then(|| error.to_string())
+    }
+
     async fn load_metadata(&self) -> Result<SourceMetadata, Self::Error> {
         match self {
             Self::Http(source) => Ok(source.load_metadata().await?),
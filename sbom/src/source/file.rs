@@ -5,15 +5,19 @@ use crate::{
     source::Source,
     visitors::store::DIR_METADATA,
 };
-use anyhow::{anyhow, Context};
+use anyhow::{anyhow, bail, Context};
 use bytes::Bytes;
+use std::collections::BTreeMap;
 use std::fs;
-use std::io::ErrorKind;
-use std::path::{Path, PathBuf};
+use std::io::{BufReader, ErrorKind};
+use std::path::{Component, Path, PathBuf};
 use std::time::SystemTime;
 use time::OffsetDateTime;
 use url::Url;
+use walkdir::WalkDir;
 use walker_common::{
+    encryption::Decryption,
+    offload::Offload,
     retrieve::RetrievalMetadata,
     source::file::{read_sig_and_digests, to_path},
     utils::{self, openpgp::PublicKey},
@@ -24,6 +28,14 @@ use walker_common::{
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct FileOptions {
     pub since: Option<SystemTime>,
+    /// decrypt documents, stored encrypted by the store visitor
+    pub decryption: Option<Decryption>,
+    /// calculate digests on the blocking thread pool
+    pub offload: Option<Offload>,
+    /// scan subdirectories as well
+    pub recursive: bool,
+    /// list the documents using a manifest, instead of scanning the directory
+    pub manifest: Option<PathBuf>,
 }
 
 impl FileOptions {
@@ -35,6 +47,65 @@ impl FileOptions {
         self.since = since.into();
         self
     }
+
+    pub fn decryption(mut self, decryption: impl Into<Option<Decryption>>) -> Self {
+        self.decryption = decryption.into();
+        self
+    }
+
+    pub fn offload(mut self, offload: impl Into<Option<Offload>>) -> Self {
+        self.offload = offload.into();
+        self
+    }
+
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    pub fn manifest(mut self, manifest: impl Into<Option<PathBuf>>) -> Self {
+        self.manifest = manifest.into();
+        self
+    }
+}
+
+/// A manifest, listing the documents of a file source
+///
+/// This allows walking stores which are organized into subdirectories (e.g. by product and
+/// version), providing additional information for each document.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    #[serde(default)]
+    pub documents: Vec<ManifestEntry>,
+}
+
+/// A document listed in a [`Manifest`]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    /// The path of the document, relative to the base of the source
+    pub path: String,
+    /// The "last changed" date, defaults to the modification timestamp of the file
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "time::serde::rfc3339::option"
+    )]
+    pub modified: Option<OffsetDateTime>,
+    /// Additional information, like the product and version
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+}
+
+impl Manifest {
+    /// Load a manifest from a JSON file.
+    pub fn load_from(path: &Path) -> anyhow::Result<Self> {
+        let file = fs::File::open(path)
+            .with_context(|| format!("Failed to open manifest: {}", path.display()))?;
+        serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("Failed to parse manifest: {}", path.display()))
+    }
 }
 
 /// A file-based source, possibly created by the [`crate::visitors::store::StoreVisitor`].
@@ -56,6 +127,42 @@ impl FileSource {
         })
     }
 
+    /// Resolve the documents listed by the manifest.
+    fn load_manifest(&self, manifest: &Path) -> anyhow::Result<Vec<DiscoveredSbom>> {
+        let manifest = Manifest::load_from(manifest)?;
+
+        manifest
+            .documents
+            .into_iter()
+            .map(|entry| {
+                let relative = Path::new(&entry.path);
+                if !relative
+                    .components()
+                    .all(|c| matches!(c, Component::Normal(_)))
+                {
+                    bail!("Invalid path in manifest: {}", entry.path);
+                }
+
+                let path = self.base.join(relative);
+                let modified = match entry.modified {
+                    Some(modified) => modified.into(),
+                    None => path
+                        .metadata()
+                        .and_then(|md| md.modified())
+                        .with_context(|| format!("Failed to access file: {}", path.display()))?,
+                };
+                let url = Url::from_file_path(&path)
+                    .map_err(|()| anyhow!("Failed to convert to URL: {}", path.display()))?;
+
+                Ok(DiscoveredSbom {
+                    url,
+                    modified,
+                    labels: entry.labels,
+                })
+            })
+            .collect()
+    }
+
     async fn scan_keys(&self) -> Result<Vec<metadata::Key>, anyhow::Error> {
         let dir = self.base.join(DIR_METADATA).join("keys");
 
@@ -113,47 +220,30 @@ impl Source for FileSource {
     }
 
     async fn load_index(&self) -> Result<Vec<DiscoveredSbom>, Self::Error> {
-        const SKIP: &[&str] = &[".asc", ".sha256", ".sha512"];
-
         log::info!("Loading index - since: {:?}", self.options.since);
 
-        let mut entries = tokio::fs::read_dir(&self.base).await?;
-        let mut result = vec![];
-
-        'entry: while let Some(entry) = entries.next_entry().await? {
-            let path = entry.path();
-            if !path.is_file() {
-                continue;
-            }
-            let name = match path.file_name().and_then(|s| s.to_str()) {
-                Some(name) => name,
-                None => continue,
-            };
-
-            for ext in SKIP {
-                if name.ends_with(ext) {
-                    log::debug!("Skipping file: {}", name);
-                    continue 'entry;
-                }
+        let result = match &self.options.manifest {
+            Some(manifest) => self.load_manifest(manifest)?,
+            None => {
+                let base = self.base.clone();
+                let recursive = self.options.recursive;
+                tokio::task::spawn_blocking(move || scan(&base, recursive)).await??
             }
+        };
 
-            if let Some(since) = self.options.since {
-                let modified = path.metadata()?.modified()?;
-                if modified < since {
-                    log::debug!("Skipping file due to modification constraint: {modified:?}");
-                    continue;
+        Ok(result
+            .into_iter()
+            .filter(|sbom| match self.options.since {
+                Some(since) if sbom.modified < since => {
+                    log::debug!(
+                        "Skipping file due to modification constraint: {:?}",
+                        sbom.modified
+                    );
+                    false
                 }
-            }
-
-            let url = Url::from_file_path(&path)
-                .map_err(|()| anyhow!("Failed to convert to URL: {}", path.display()))?;
-
-            let modified = path.metadata()?.modified()?;
-
-            result.push(DiscoveredSbom { url, modified })
-        }
-
-        Ok(result)
+                _ => true,
+            })
+            .collect())
     }
 
     async fn load_sbom(&self, discovered: DiscoveredSbom) -> Result<RetrievedSbom, Self::Error> {
@@ -162,9 +252,16 @@ impl Source for FileSource {
             .to_file_path()
             .map_err(|()| anyhow!("Unable to convert URL into path: {}", discovered.url))?;
 
-        let data = Bytes::from(tokio::fs::read(&path).await?);
+        let data = tokio::fs::read(&path).await?;
+        let data = Bytes::from(match &self.options.decryption {
+            Some(decryption) => decryption
+                .decrypt(&data)
+                .with_context(|| format!("Failed to decrypt document: {}", path.display()))?,
+            None => data,
+        });
 
-        let (signature, sha256, sha512) = read_sig_and_digests(&path, &data).await?;
+        let (signature, sha256, sha512) =
+            read_sig_and_digests(&path, &data, self.options.offload.as_ref()).await?;
 
         let last_modification = path
             .metadata()
@@ -181,11 +278,57 @@ impl Source for FileSource {
             metadata: RetrievalMetadata {
                 last_modification,
                 etag: None,
+                ..Default::default()
             },
         })
     }
 }
 
+/// Scan a directory for documents, skipping sidecar files and the metadata of a store.
+fn scan(base: &Path, recursive: bool) -> anyhow::Result<Vec<DiscoveredSbom>> {
+    const SKIP: &[&str] = &[".asc", ".sig", ".sha256", ".sha512", ".metadata"];
+
+    let entries = WalkDir::new(base)
+        .min_depth(1)
+        .max_depth(if recursive { usize::MAX } else { 1 })
+        .follow_links(true)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            !(entry.depth() == 1 && entry.file_type().is_dir() && entry.file_name() == DIR_METADATA)
+        });
+
+    let mut result = vec![];
+
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Some(name) = entry.file_name().to_str() else {
+            continue;
+        };
+
+        if SKIP.iter().any(|ext| name.ends_with(ext)) {
+            log::debug!("Skipping file: {}", name);
+            continue;
+        }
+
+        let path = entry.path();
+        let url = Url::from_file_path(path)
+            .map_err(|()| anyhow!("Failed to convert to URL: {}", path.display()))?;
+        let modified = entry.metadata()?.modified()?;
+
+        result.push(DiscoveredSbom {
+            url,
+            modified,
+            labels: Default::default(),
+        })
+    }
+
+    Ok(result)
+}
+
 impl KeySource for FileSource {
     type Error = anyhow::Error;
 
@@ -200,3 +343,73 @@ impl KeySource for FileSource {
             .map_err(KeySourceError::OpenPgp)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn names(sboms: &[DiscoveredSbom]) -> Vec<String> {
+        sboms
+            .iter()
+            .filter_map(|sbom| {
+                sbom.url
+                    .path_segments()?
+                    .next_back()
+                    .map(ToString::to_string)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_load_index() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let base = dir.path().to_path_buf();
+
+        for path in [
+            "a.json",
+            "a.json.sha256",
+            "metadata/metadata.json",
+            "product/1.0/b.json",
+            "product/2.0/c.json",
+        ] {
+            let path = base.join(path);
+            fs::create_dir_all(path.parent().expect("must have a parent"))?;
+            fs::write(&path, b"{}")?;
+        }
+
+        let source = FileSource::new(&base, FileOptions::new())?;
+        assert_eq!(names(&source.load_index().await?), vec!["a.json"]);
+
+        let source = FileSource::new(&base, FileOptions::new().recursive(true))?;
+        assert_eq!(
+            names(&source.load_index().await?),
+            vec!["a.json", "b.json", "c.json"]
+        );
+
+        let manifest = base.join("manifest.json");
+        fs::write(
+            &manifest,
+            serde_json::json!({
+                "documents": [{
+                    "path": "product/2.0/c.json",
+                    "modified": "2024-01-01T00:00:00Z",
+                    "labels": { "product": "product", "version": "2.0" },
+                }],
+            })
+            .to_string(),
+        )?;
+
+        let source = FileSource::new(&base, FileOptions::new().manifest(manifest.clone()))?;
+        let index = source.load_index().await?;
+        assert_eq!(names(&index), vec!["c.json"]);
+        assert_eq!(
+            index[0].labels.get("version").map(String::as_str),
+            Some("2.0")
+        );
+
+        fs::write(&manifest, r#"{"documents": [{"path": "../outside.json"}]}"#)?;
+        assert!(source.load_index().await.is_err());
+
+        Ok(())
+    }
+}
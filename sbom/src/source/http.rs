@@ -1,21 +1,24 @@
 use crate::{
     discover::DiscoveredSbom,
-    model::{self, metadata::SourceMetadata},
+    model::{self, metadata::SourceMetadata, provider::ProviderMetadata},
     retrieve::RetrievedSbom,
     source::Source,
 };
-use bytes::{BufMut, Bytes, BytesMut};
+use bytes::Bytes;
 use digest::Digest;
 use futures::try_join;
 use reqwest::Response;
 use sha2::{Sha256, Sha512};
 use std::time::SystemTime;
-use time::{format_description::well_known::Rfc2822, OffsetDateTime};
 use url::{ParseError, Url};
 use walker_common::{
     changes::{self, ChangeEntry, ChangeSource},
-    fetcher::{self, DataProcessor, Fetcher},
-    retrieve::{RetrievalMetadata, RetrievedDigest, RetrievingDigest},
+    fetcher::{self, DataProcessor, Fetcher, Json},
+    offload::Offload,
+    retrieve::{
+        RequestTiming, RetrievalMetadata, RetrievedDigest, RetrievingBody, RetrievingDigest,
+    },
+    signature::normalize_signature,
     utils::openpgp::PublicKey,
     validate::source::{Key, KeySource, KeySourceError},
 };
@@ -25,6 +28,8 @@ use walker_common::{
 pub struct HttpOptions {
     pub since: Option<SystemTime>,
     pub keys: Vec<model::metadata::Key>,
+    /// Calculate digests on the blocking thread pool
+    pub offload: Option<Offload>,
 }
 
 impl HttpOptions {
@@ -57,6 +62,11 @@ impl HttpOptions {
         self.keys.push(key.into());
         self
     }
+
+    pub fn offload(mut self, offload: impl Into<Option<Offload>>) -> Self {
+        self.offload = offload.into();
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -74,6 +84,51 @@ impl HttpSource {
             options,
         }
     }
+
+    /// Load the provider metadata, if the URL points to a metadata document (instead of a
+    /// directory of SBOMs).
+    async fn provider_metadata(&self) -> Result<Option<ProviderMetadata>, HttpSourceError> {
+        if !self.url.path().ends_with(".json") {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            self.fetcher
+                .fetch::<Json<ProviderMetadata>>(self.url.clone())
+                .await?
+                .into_inner(),
+        ))
+    }
+
+    /// Load the SBOMs of a directory, using its `changes.csv` file.
+    async fn load_directory(&self, url: &Url) -> Result<Vec<DiscoveredSbom>, HttpSourceError> {
+        let base = match url.path().ends_with('/') {
+            true => url.clone(),
+            false => Url::parse(&format!("{url}/"))?,
+        };
+
+        let changes = ChangeSource::retrieve(&self.fetcher, &base).await?;
+
+        Ok(changes
+            .entries
+            .into_iter()
+            .map(|ChangeEntry { file, timestamp }| {
+                let modified = timestamp.into();
+                let url = base.join(&file)?;
+
+                Ok::<_, ParseError>(DiscoveredSbom {
+                    url,
+                    modified,
+                    labels: Default::default(),
+                })
+            })
+            // filter out advisories based in since, but only if we can be sure
+            .filter(|advisory| match (advisory, &self.options.since) {
+                (Ok(DiscoveredSbom { modified, .. }), Some(since)) => modified >= since,
+                _ => true,
+            })
+            .collect::<Result<_, _>>()?)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -99,35 +154,38 @@ impl From<changes::Error> for HttpSourceError {
 impl Source for HttpSource {
     type Error = HttpSourceError;
 
+    fn skip_reason(error: &Self::Error) -> Option<String> {
+        match error {
+            HttpSourceError::Fetcher(err) if err.is_circuit_open() => Some(err.to_string()),
+            _ => None,
+        }
+    }
+
     async fn load_metadata(&self) -> Result<SourceMetadata, Self::Error> {
-        Ok(SourceMetadata {
-            keys: self.options.keys.clone(),
-        })
+        let mut keys = self.options.keys.clone();
+        if let Some(metadata) = self.provider_metadata().await? {
+            keys.extend(metadata.public_openpgp_keys);
+        }
+
+        Ok(SourceMetadata { keys })
     }
 
     async fn load_index(&self) -> Result<Vec<DiscoveredSbom>, Self::Error> {
-        let base = match self.url.path().ends_with('/') {
-            true => self.url.clone(),
-            false => Url::parse(&format!("{}/", self.url))?,
+        let directories = match self.provider_metadata().await? {
+            Some(metadata) => metadata
+                .distributions
+                .into_iter()
+                .map(|distribution| distribution.directory_url)
+                .collect(),
+            None => vec![self.url.clone()],
         };
 
-        let changes = ChangeSource::retrieve(&self.fetcher, &base).await?;
-
-        Ok(changes
-            .entries
-            .into_iter()
-            .map(|ChangeEntry { file, timestamp }| {
-                let modified = timestamp.into();
-                let url = base.join(&file)?;
+        let mut result = vec![];
+        for directory in directories {
+            result.extend(self.load_directory(&directory).await?);
+        }
 
-                Ok::<_, ParseError>(DiscoveredSbom { url, modified })
-            })
-            // filter out advisories based in since, but only if we can be sure
-            .filter(|advisory| match (advisory, &self.options.since) {
-                (Ok(DiscoveredSbom { url: _, modified }), Some(since)) => modified >= since,
-                _ => true,
-            })
-            .collect::<Result<_, _>>()?)
+        Ok(result)
     }
 
     async fn load_sbom(&self, discovered: DiscoveredSbom) -> Result<RetrievedSbom, Self::Error> {
@@ -140,26 +198,31 @@ impl Source for HttpSource {
                 .fetch::<Option<String>>(format!("{url}.sha512", url = discovered.url)),
         )?;
 
+        let signature = match signature {
+            Some(signature) => Some(signature),
+            None => self
+                .fetcher
+                .fetch::<Option<Bytes>>(format!("{url}.sig", url = discovered.url))
+                .await?
+                .and_then(|signature| normalize_signature(&signature)),
+        };
+
         let sha256 = sha256
-            // take the first "word" from the line
-            .and_then(|expected| expected.split(' ').next().map(ToString::to_string))
-            .map(|expected| RetrievingDigest {
-                expected,
-                current: Sha256::new(),
-            });
+            .as_deref()
+            .map(RetrievingDigest::<Sha256>::from_digest_file);
         let sha512 = sha512
-            // take the first "word" from the line
-            .and_then(|expected| expected.split(' ').next().map(ToString::to_string))
-            .map(|expected| RetrievingDigest {
-                expected,
-                current: Sha512::new(),
-            });
+            .as_deref()
+            .map(RetrievingDigest::<Sha512>::from_digest_file);
 
         let advisory = self
             .fetcher
             .fetch_processed(
                 discovered.url.clone(),
-                FetchingRetrievedSbom { sha256, sha512 },
+                FetchingRetrievedSbom {
+                    sha256,
+                    sha512,
+                    offload: self.options.offload.clone(),
+                },
             )
             .await?;
 
@@ -194,6 +257,8 @@ impl FetchedRetrievedSbom {
 pub struct FetchingRetrievedSbom {
     pub sha256: Option<RetrievingDigest<Sha256>>,
     pub sha512: Option<RetrievingDigest<Sha512>>,
+    /// Calculate the digests on the blocking thread pool, once the body is complete
+    pub offload: Option<Offload>,
 }
 
 impl DataProcessor for FetchingRetrievedSbom {
@@ -201,41 +266,47 @@ impl DataProcessor for FetchingRetrievedSbom {
 
     async fn process(&self, response: Response) -> Result<Self::Type, reqwest::Error> {
         let mut response = response.error_for_status()?;
+        let mut metadata = RetrievalMetadata::from_response(&response);
 
-        let mut data = BytesMut::new();
+        let mut data = RetrievingBody::new();
         let mut sha256 = self.sha256.clone();
         let mut sha512 = self.sha512.clone();
 
         while let Some(chunk) = response.chunk().await? {
-            if let Some(d) = &mut sha256 {
-                d.update(&chunk);
-            }
-            if let Some(d) = &mut sha512 {
-                d.update(&chunk);
+            if self.offload.is_none() {
+                if let Some(d) = &mut sha256 {
+                    d.update(&chunk);
+                }
+                if let Some(d) = &mut sha512 {
+                    d.update(&chunk);
+                }
             }
-            data.put(chunk);
+            data.push(chunk);
         }
 
-        let etag = response
-            .headers()
-            .get(reqwest::header::ETAG)
-            .and_then(|s| s.to_str().ok())
-            .map(ToString::to_string);
-
-        let last_modification = response
-            .headers()
-            .get(reqwest::header::LAST_MODIFIED)
-            .and_then(|s| s.to_str().ok())
-            .and_then(|s| OffsetDateTime::parse(s, &Rfc2822).ok());
+        metadata.timings = RequestTiming::of(&response);
+
+        let data = data.freeze();
+        let (sha256, sha512) = match &self.offload {
+            Some(offload) => {
+                let data = data.clone();
+                offload
+                    .run(move || {
+                        (
+                            sha256.map(|d| d.finalize_with(&data)),
+                            sha512.map(|d| d.finalize_with(&data)),
+                        )
+                    })
+                    .await
+            }
+            None => (sha256.map(|d| d.into()), sha512.map(|d| d.into())),
+        };
 
         Ok(FetchedRetrievedSbom {
-            data: data.freeze(),
-            sha256: sha256.map(|d| d.into()),
-            sha512: sha512.map(|d| d.into()),
-            metadata: RetrievalMetadata {
-                last_modification,
-                etag,
-            },
+            data,
+            sha256,
+            sha512,
+            metadata,
         })
     }
 }
@@ -30,6 +30,13 @@ pub trait Source: Clone {
         &self,
         sbom: DiscoveredSbom,
     ) -> impl Future<Output = Result<RetrievedSbom, Self::Error>>;
+
+    /// Check if an error of loading a single SBOM only skips it, instead of failing the walk.
+    ///
+    /// Returns the reason for skipping, e.g. because the host was considered unavailable.
+    fn skip_reason(_error: &Self::Error) -> Option<String> {
+        None
+    }
 }
 
 pub async fn new_source(
@@ -45,7 +52,16 @@ pub async fn new_source(
                 Some("file") => {
                     let source = uri.path().as_str();
                     log::debug!("Creating file source: {source}");
-                    Ok(FileSource::new(source, FileOptions::new().since(discover.since))?.into())
+                    Ok(FileSource::new(
+                        source,
+                        FileOptions::new()
+                            .since(discover.since)
+                            .decryption(discover.decryption)
+                            .offload(discover.offload)
+                            .recursive(discover.recursive)
+                            .manifest(discover.manifest),
+                    )?
+                    .into())
                 }
                 Some(_scheme) => {
                     log::debug!("Creating HTTP source: {source}");
@@ -53,7 +69,10 @@ pub async fn new_source(
                     Ok(HttpSource::new(
                         Url::parse(&source)?,
                         fetcher,
-                        HttpOptions::new().since(discover.since).keys(discover.keys),
+                        HttpOptions::new()
+                            .since(discover.since)
+                            .keys(discover.keys)
+                            .offload(discover.offload),
                     )
                     .into())
                 }
@@ -83,6 +102,10 @@ mod test {
                 source: "file:/".to_string(),
                 since: None,
                 keys: vec![],
+                decryption: None,
+                offload: None,
+                recursive: false,
+                manifest: None,
             },
             FetcherOptions::default(),
         )
@@ -98,6 +121,10 @@ mod test {
                 source: "https://foo.bar/baz".to_string(),
                 since: None,
                 keys: vec![],
+                decryption: None,
+                offload: None,
+                recursive: false,
+                manifest: None,
             },
             FetcherOptions::default(),
         )
@@ -113,6 +140,10 @@ mod test {
                 source: "/var/files".to_string(),
                 since: None,
                 keys: vec![],
+                decryption: None,
+                offload: None,
+                recursive: false,
+                manifest: None,
             },
             FetcherOptions::default(),
         )
@@ -5,18 +5,61 @@ use digest::Digest;
 use std::fmt::{Debug, Display, Formatter};
 use std::future::Future;
 use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
 use url::Url;
 use walker_common::utils::url::Urlify;
 use walker_common::{
     retrieve::RetrievedDigest,
+    signature::Cleartext,
+    stage,
     utils::openpgp::PublicKey,
-    validate::{openpgp, ValidationOptions},
+    validate::{
+        evidence::ValidationFailure,
+        openpgp,
+        sidecar::{self, SidecarError},
+        tls::{self, TlsViolation},
+        ValidationMode, ValidationOptions,
+    },
 };
 
 #[derive(Clone, Debug)]
 pub struct ValidatedSbom {
     /// The discovered advisory
     pub retrieved: RetrievedSbom,
+    /// The final URL, in case the SBOM was served through a cross-origin redirect
+    pub cross_origin_redirect: Option<Url>,
+    /// The failed digest or signature checks, only populated in the [`ValidationMode::Collect`] mode
+    pub failures: Vec<ValidationFailure>,
+}
+
+impl ValidatedSbom {
+    fn new(retrieved: RetrievedSbom, failures: Vec<ValidationFailure>) -> Self {
+        let cross_origin_redirect = retrieved
+            .metadata
+            .cross_origin_redirect(&retrieved.url)
+            .cloned();
+
+        if let Some(url) = &cross_origin_redirect {
+            tracing::warn!(
+                target: stage::VALIDATE,
+                stage = "validate",
+                url = %retrieved.url,
+                redirect = %url,
+                "SBOM was served through a cross-origin redirect"
+            );
+        }
+
+        Self {
+            retrieved,
+            cross_origin_redirect,
+            failures,
+        }
+    }
+
+    /// Check if the SBOM passed all digest and signature checks.
+    pub fn is_trusted(&self) -> bool {
+        self.failures.is_empty()
+    }
 }
 
 impl Urlify for ValidatedSbom {
@@ -55,6 +98,45 @@ pub enum ValidationError {
         error: anyhow::Error,
         retrieved: RetrievedSbom,
     },
+    MalformedSidecar {
+        error: SidecarError,
+        retrieved: RetrievedSbom,
+    },
+    Tls {
+        error: TlsViolation,
+        retrieved: RetrievedSbom,
+    },
+}
+
+impl ValidationError {
+    /// Convert a failed integrity check into a failure, handing back the SBOM.
+    ///
+    /// Other errors are returned unchanged.
+    fn into_failure(self) -> Result<(ValidationFailure, RetrievedSbom), Self> {
+        match self {
+            Self::DigestMismatch {
+                expected,
+                actual,
+                retrieved,
+            } => Ok((
+                ValidationFailure::DigestMismatch { expected, actual },
+                retrieved,
+            )),
+            Self::Signature { error, retrieved } => Ok((
+                ValidationFailure::Signature {
+                    reason: error.to_string(),
+                },
+                retrieved,
+            )),
+            Self::MalformedSidecar { error, retrieved } => Ok((
+                ValidationFailure::MalformedSidecar {
+                    reason: error.to_string(),
+                },
+                retrieved,
+            )),
+            other => Err(other),
+        }
+    }
 }
 
 impl Urlify for ValidationError {
@@ -63,6 +145,8 @@ impl Urlify for ValidationError {
             Self::Retrieval(err) => err.url(),
             Self::DigestMismatch { retrieved, .. } => &retrieved.url,
             Self::Signature { retrieved, .. } => &retrieved.url,
+            Self::MalformedSidecar { retrieved, .. } => &retrieved.url,
+            Self::Tls { retrieved, .. } => &retrieved.url,
         }
     }
 }
@@ -83,6 +167,12 @@ impl Display for ValidationError {
             Self::Signature { error, retrieved } => {
                 write!(f, "Invalid signature: {error} ({})", retrieved.url)
             }
+            Self::MalformedSidecar { error, retrieved } => {
+                write!(f, "Malformed sidecar file: {error} ({})", retrieved.url)
+            }
+            Self::Tls { error, retrieved } => {
+                write!(f, "TLS requirements not met: {error} ({})", retrieved.url)
+            }
         }
     }
 }
@@ -184,6 +274,11 @@ where
         self
     }
 
+    pub fn with_mode(mut self, mode: ValidationMode) -> Self {
+        self.options.mode = mode;
+        self
+    }
+
     /// Perform the actual validation.
     ///
     /// Returning either a processing error, or a result which will will be forwarded to the visitor.
@@ -192,56 +287,159 @@ where
         context: &InnerValidationContext<V::Context>,
         retrieved: RetrievedSbom,
     ) -> Result<ValidatedSbom, ValidationProcessError> {
-        if let Err((expected, actual)) = Self::validate_digest(&retrieved.sha256) {
-            return Err(ValidationProcessError::Proceed(
+        match &self.options.offload {
+            Some(offload) => {
+                let options = self.options.clone();
+                let keys = context.keys.clone();
+                offload
+                    .run(move || validate(&options, &keys, retrieved))
+                    .await
+            }
+            None => validate(&self.options, &context.keys, retrieved),
+        }
+    }
+}
+
+/// Validate a retrieved SBOM, using the provided options and keys.
+fn validate(
+    options: &ValidationOptions,
+    keys: &[PublicKey],
+    mut retrieved: RetrievedSbom,
+) -> Result<ValidatedSbom, ValidationProcessError> {
+    if options.strict_tls {
+        if let Err(error) = tls::check(&retrieved.metadata) {
+            return Err(ValidationProcessError::Proceed(ValidationError::Tls {
+                error,
+                retrieved,
+            }));
+        }
+    }
+
+    let mut failures = vec![];
+
+    // when lenient, malformed digest files are ignored by the digest validation
+    let malformed = match options.lenient_digests {
+        true => None,
+        false => sidecar::check_digests(&retrieved.sha256, &retrieved.sha512).err(),
+    };
+
+    if let Some(error) = malformed {
+        retrieved = fail(
+            options,
+            &mut failures,
+            ValidationError::MalformedSidecar { error, retrieved },
+        )?;
+    } else {
+        if let Err((expected, actual)) = validate_digest(options, &retrieved.url, &retrieved.sha256)
+        {
+            retrieved = fail(
+                options,
+                &mut failures,
                 ValidationError::DigestMismatch {
                     expected,
                     actual,
                     retrieved,
                 },
-            ));
+            )?;
         }
-        if let Err((expected, actual)) = Self::validate_digest(&retrieved.sha512) {
-            return Err(ValidationProcessError::Proceed(
+        if let Err((expected, actual)) = validate_digest(options, &retrieved.url, &retrieved.sha512)
+        {
+            retrieved = fail(
+                options,
+                &mut failures,
                 ValidationError::DigestMismatch {
                     expected,
                     actual,
                     retrieved,
                 },
-            ));
+            )?;
         }
+    }
 
-        if let Some(signature) = &retrieved.signature {
-            match openpgp::validate_signature(
-                &self.options,
-                &context.keys,
-                signature,
-                &retrieved.data,
-            ) {
-                Ok(()) => Ok(ValidatedSbom { retrieved }),
-                Err(error) => Err(ValidationProcessError::Proceed(
-                    ValidationError::Signature { error, retrieved },
-                )),
-            }
-        } else {
-            Ok(ValidatedSbom { retrieved })
+    // a cleartext signed document carries its signature, digests refer to the published document
+    if retrieved.signature.is_none() {
+        if let Some(cleartext) = Cleartext::split(&retrieved.data) {
+            retrieved.data = cleartext.content;
+            retrieved.signature = Some(cleartext.signature);
         }
     }
 
-    /// ensure that the digest matches if we have one
-    fn validate_digest<D: Digest>(
-        digest: &Option<RetrievedDigest<D>>,
-    ) -> Result<(), (String, String)> {
-        if let Some(digest) = &digest {
-            digest.validate().map_err(|(s1, s2)| (s1.to_string(), s2))?;
+    if let Some(signature) = retrieved.signature.clone() {
+        if let Err(error) = sidecar::check_signature(&signature) {
+            retrieved = fail(
+                options,
+                &mut failures,
+                ValidationError::MalformedSidecar { error, retrieved },
+            )?;
+        } else if let Err(error) =
+            openpgp::validate_signature(options, keys, &signature, &retrieved.data)
+        {
+            retrieved = fail(
+                options,
+                &mut failures,
+                ValidationError::Signature { error, retrieved },
+            )?;
+        }
+    }
+
+    Ok(ValidatedSbom::new(retrieved, failures))
+}
+
+/// Fail the validation of the SBOM, or in the collect mode, record the failed integrity check
+/// and continue with the SBOM.
+fn fail(
+    options: &ValidationOptions,
+    failures: &mut Vec<ValidationFailure>,
+    error: ValidationError,
+) -> Result<RetrievedSbom, ValidationProcessError> {
+    match options.mode {
+        ValidationMode::Strict => Err(ValidationProcessError::Proceed(error)),
+        ValidationMode::Collect => {
+            let (failure, retrieved) = error
+                .into_failure()
+                .map_err(ValidationProcessError::Proceed)?;
+            tracing::warn!(
+                target: stage::VALIDATE,
+                stage = "validate",
+                url = %retrieved.url,
+                failure = %failure,
+                "Passing on SBOM, which failed an integrity check"
+            );
+            failures.push(failure);
+            Ok(retrieved)
         }
-        Ok(())
     }
 }
 
+/// ensure that the digest matches if we have one
+fn validate_digest<D: Digest>(
+    options: &ValidationOptions,
+    url: &Url,
+    digest: &Option<RetrievedDigest<D>>,
+) -> Result<(), (String, String)> {
+    if let Some(digest) = &digest {
+        if let Err((expected, actual)) = digest.validate() {
+            if options.lenient_digests {
+                if let Some(err) = digest.malformed() {
+                    tracing::warn!(
+                        target: stage::VALIDATE,
+                        stage = "validate",
+                        url = %url,
+                        error = %err,
+                        "Ignoring malformed digest file"
+                    );
+                    return Ok(());
+                }
+            }
+            return Err((expected.to_string(), actual));
+        }
+    }
+    Ok(())
+}
+
 pub struct InnerValidationContext<VC> {
     context: VC,
-    keys: Vec<PublicKey>,
+    keys: Arc<Vec<PublicKey>>,
 }
 
 impl<V> RetrievedVisitor for ValidationVisitor<V>
@@ -255,7 +453,7 @@ where
         &self,
         context: &RetrievalContext<'_>,
     ) -> Result<Self::Context, Self::Error> {
-        let keys = context.keys.clone();
+        let keys = Arc::new(context.keys.clone());
 
         let context = self
             .visitor
@@ -273,11 +471,31 @@ where
     ) -> Result<(), Self::Error> {
         match outcome {
             Ok(advisory) => {
+                let url = advisory.url.clone();
+
                 let result = match self.validate(context, advisory).await {
                     Ok(result) => Ok(result),
                     Err(ValidationProcessError::Proceed(err)) => Err(err),
                     Err(ValidationProcessError::Abort(err)) => return Err(Error::Validation(err)),
                 };
+
+                match &result {
+                    Ok(_) => tracing::debug!(
+                        target: stage::VALIDATE,
+                        stage = "validate",
+                        url = %url,
+                        outcome = "valid",
+                        "Validated SBOM"
+                    ),
+                    Err(err) => tracing::info!(
+                        target: stage::VALIDATE,
+                        stage = "validate",
+                        url = %url,
+                        outcome = "invalid",
+                        error = %err,
+                        "Failed to validate SBOM"
+                    ),
+                }
                 self.visitor
                     .visit_sbom(&context.context, result)
                     .await
@@ -1,10 +1,11 @@
 use crate::discover::{DiscoveredContext, DiscoveredSbom, DiscoveredVisitor};
 use crate::validation::{ValidatedSbom, ValidatedVisitor, ValidationContext, ValidationError};
 use std::fmt::{Debug, Display};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use tokio::fs;
-use walker_common::utils::url::Urlify;
+use url::Url;
+use walker_common::{fetcher::Fetcher, store::load_metadata, utils::url::Urlify};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error<VE: Display + Debug> {
@@ -24,6 +25,29 @@ pub struct SkipExistingVisitor<V: DiscoveredVisitor> {
     ///
     /// Overrides the "file modified" timestamp which is used by default.
     pub since: Option<SystemTime>,
+    /// Issue a `HEAD` request for documents considered changed, and skip them if the
+    /// response matches the stored retrieval metadata.
+    pub head: Option<Fetcher>,
+}
+
+impl<V: DiscoveredVisitor> SkipExistingVisitor<V> {
+    /// Check if the remote document is unchanged, compared to the stored retrieval metadata.
+    async fn is_unchanged(&self, path: &Path, url: &Url) -> bool {
+        let Some(fetcher) = &self.head else {
+            return false;
+        };
+        let Some(stored) = load_metadata(path).await else {
+            return false;
+        };
+
+        match fetcher.head(url.clone()).await {
+            Ok(current) => stored.is_unchanged(&current),
+            Err(err) => {
+                log::debug!("Failed to check {url}, retrieving: {err}");
+                false
+            }
+        }
+    }
 }
 
 impl<V: DiscoveredVisitor> DiscoveredVisitor for SkipExistingVisitor<V> {
@@ -69,6 +93,11 @@ impl<V: DiscoveredVisitor> DiscoveredVisitor for SkipExistingVisitor<V> {
                 // the file was modified after the change date, skip it
                 return Ok(());
             }
+
+            if self.is_unchanged(&path, &sbom.url).await {
+                log::debug!("Unchanged, according to HEAD request: {}", sbom.url);
+                return Ok(());
+            }
         }
 
         self.visitor
@@ -9,7 +9,9 @@ use std::io::{ErrorKind, Write};
 use std::path::{Path, PathBuf};
 use tokio::fs;
 use walker_common::{
-    store::{store_document, Document, StoreError},
+    encryption::Encryption,
+    stage,
+    store::{store_document, write_replace, Document, StoreError},
     utils::openpgp::PublicKey,
 };
 
@@ -27,6 +29,12 @@ pub struct StoreVisitor {
     /// whether to store additional metadata (like the etag) using extended attributes
     #[cfg(any(target_os = "linux", target_os = "macos"))]
     pub no_xattrs: bool,
+
+    /// encrypt documents for the recipients before storing them
+    pub encryption: Option<Encryption>,
+
+    /// space to keep free on the filesystem of the store
+    pub min_free_space: Option<u64>,
 }
 
 impl StoreVisitor {
@@ -36,6 +44,8 @@ impl StoreVisitor {
             no_timestamps: false,
             #[cfg(any(target_os = "linux", target_os = "macos"))]
             no_xattrs: false,
+            encryption: None,
+            min_free_space: None,
         }
     }
 
@@ -49,6 +59,20 @@ impl StoreVisitor {
         self.no_xattrs = no_xattrs;
         self
     }
+
+    pub fn encryption(mut self, encryption: impl Into<Option<Encryption>>) -> Self {
+        self.encryption = encryption.into();
+        self
+    }
+
+    /// Keep at least this amount of space (in bytes) free on the filesystem of the store.
+    ///
+    /// Storing a document fails if it would leave less space, instead of running out of space
+    /// while writing it.
+    pub fn min_free_space(mut self, min_free_space: impl Into<Option<u64>>) -> Self {
+        self.min_free_space = min_free_space.into();
+        self
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -127,17 +151,13 @@ impl StoreVisitor {
             .map_err(StoreError::Io)?;
 
         let file = metadir.join("metadata.json");
-        let mut out = std::fs::File::create(&file)
-            .with_context(|| {
-                format!(
-                    "Unable to open provider metadata file for writing: {}",
-                    file.display()
-                )
-            })
-            .map_err(StoreError::Io)?;
-        serde_json::to_writer_pretty(&mut out, metadata)
+        let data = serde_json::to_vec_pretty(metadata)
             .context("Failed serializing provider metadata")
             .map_err(StoreError::Io)?;
+        write_replace(&file, &data)
+            .await
+            .with_context(|| format!("Unable to write provider metadata file: {}", file.display()))
+            .map_err(StoreError::Io)?;
         Ok(())
     }
 
@@ -170,7 +190,7 @@ impl StoreVisitor {
 
         let data = Self::serialize_key(cert).map_err(StoreError::SerializeKey)?;
 
-        fs::write(&name, data)
+        write_replace(&name, &data)
             .await
             .with_context(|| format!("Failed to store key: {}", name.display()))
             .map_err(StoreError::Io)?;
@@ -184,10 +204,12 @@ impl StoreVisitor {
     }
 
     async fn store(&self, sbom: &RetrievedSbom) -> Result<(), StoreError> {
-        log::info!(
-            "Storing: {} (modified: {:?})",
-            sbom.url,
-            sbom.metadata.last_modification
+        tracing::info!(
+            target: stage::STORE,
+            stage = "store",
+            url = %sbom.url,
+            modified = ?sbom.metadata.last_modification,
+            "Storing SBOM"
         );
 
         let file = PathBuf::from(sbom.url.path())
@@ -195,8 +217,6 @@ impl StoreVisitor {
             .map(|file| self.base.join(file))
             .ok_or_else(|| StoreError::Filename(sbom.url.to_string()))?;
 
-        log::debug!("Writing {}", file.display());
-
         store_document(
             &file,
             Document {
@@ -209,6 +229,9 @@ impl StoreVisitor {
                 no_timestamps: self.no_timestamps,
                 #[cfg(any(target_os = "linux", target_os = "macos"))]
                 no_xattrs: self.no_xattrs,
+                encryption: self.encryption.as_ref(),
+                evidence: None,
+                min_free_space: self.min_free_space,
             },
         )
         .await?;
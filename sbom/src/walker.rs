@@ -1,12 +1,19 @@
 //! The actual walker
 
-use crate::discover::{DiscoveredContext, DiscoveredVisitor};
+use crate::discover::{DiscoveredContext, DiscoveredSbom, DiscoveredVisitor};
 use crate::source::Source;
 use futures::{stream, StreamExt, TryFutureExt, TryStreamExt};
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Instant;
 use url::ParseError;
-use walker_common::progress::Progress;
+use walker_common::{
+    checkpoint::Checkpoint,
+    concurrency::AdaptiveConcurrency,
+    deadline::{Completion, Deadline},
+    progress::Progress,
+    run::{run_id, CorrelationId, WalkSummary},
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error<VE, SE>
@@ -20,18 +27,31 @@ where
     Url(#[from] ParseError),
     #[error("Visitor error: {0}")]
     Visitor(VE),
+    #[error("Deadline exceeded: {0}")]
+    Deadline(Completion),
 }
 
 pub struct Walker<S: Source> {
     source: S,
     progress: Progress,
+    deadline: Option<Instant>,
+    adaptive: Option<Arc<AdaptiveConcurrency>>,
+    checkpoint: Option<Arc<Checkpoint>>,
+    correlation_id: CorrelationId,
 }
 
+/// The key of the index in the checkpoint
+const CHECKPOINT_KEY: &str = "index";
+
 impl<S: Source> Walker<S> {
     pub fn new(source: S) -> Self {
         Self {
             source,
             progress: Progress::default(),
+            deadline: None,
+            adaptive: None,
+            checkpoint: None,
+            correlation_id: run_id().clone(),
         }
     }
 
@@ -40,7 +60,65 @@ impl<S: Source> Walker<S> {
         self
     }
 
-    pub async fn walk<V>(self, visitor: V) -> Result<(), Error<V::Error, S::Error>>
+    /// Set a deadline for walking.
+    ///
+    /// Once the deadline has passed, no more SBOMs will be processed. SBOMs already being
+    /// processed will be completed. The walk then fails with [`Error::Deadline`], reporting how
+    /// much was completed.
+    pub fn with_deadline(mut self, deadline: impl Into<Option<Instant>>) -> Self {
+        self.deadline = deadline.into();
+        self
+    }
+
+    /// Adapt the number of concurrent tasks of [`Self::walk_parallel`] using a controller.
+    ///
+    /// The limit of [`Self::walk_parallel`] is still applied as an upper bound. The controller
+    /// should also be used by the fetcher of the source, so that it gets informed about the
+    /// outcome of requests.
+    pub fn with_adaptive_concurrency(
+        mut self,
+        adaptive: impl Into<Option<Arc<AdaptiveConcurrency>>>,
+    ) -> Self {
+        self.adaptive = adaptive.into();
+        self
+    }
+
+    /// Record the progress within the index in a checkpoint.
+    ///
+    /// Entries which have been processed according to the checkpoint are skipped, so that an
+    /// interrupted walk can be resumed. Once the walk completed, the checkpoint is cleared.
+    pub fn with_checkpoint(mut self, checkpoint: impl Into<Option<Arc<Checkpoint>>>) -> Self {
+        self.checkpoint = checkpoint.into();
+        self
+    }
+
+    /// Set the correlation ID of the walk, reported in the [`WalkSummary`].
+    ///
+    /// This defaults to the ID of the run, see [`run_id`]. The fetcher of the source should use
+    /// the same ID, so that it gets sent with each request.
+    pub fn with_correlation_id(mut self, correlation_id: CorrelationId) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    pub async fn walk<V>(self, visitor: V) -> Result<WalkSummary, Error<V::Error, S::Error>>
+    where
+        V: DiscoveredVisitor,
+    {
+        let start = Instant::now();
+        let checkpoint = self.checkpoint.clone();
+        let correlation_id = self.correlation_id.clone();
+        log::info!("Walking, correlation ID: {correlation_id}");
+
+        let result = self.walk_sequential(visitor).await;
+        finish_checkpoint(checkpoint.as_deref(), &result);
+        result.map(|()| WalkSummary {
+            correlation_id,
+            duration: start.elapsed(),
+        })
+    }
+
+    async fn walk_sequential<V>(self, visitor: V) -> Result<(), Error<V::Error, S::Error>>
     where
         V: DiscoveredVisitor,
     {
@@ -54,9 +132,11 @@ impl<S: Source> Walker<S> {
             .map_err(Error::Visitor)?;
 
         let index = self.source.load_index().await.map_err(Error::Source)?;
-        let progress = self.progress.start(index.len());
+        let skip = resume(self.checkpoint.as_deref(), &index);
+        let progress = self.progress.start(index.len() - skip);
+        let deadline = self.deadline.map(Deadline::new);
 
-        for sbom in index {
+        for (n, sbom) in index.into_iter().enumerate().skip(skip) {
             log::debug!("  Discovered SBOM: {sbom:?}");
             progress.set_message(
                 sbom.url
@@ -67,20 +147,49 @@ impl<S: Source> Walker<S> {
                     .to_string()
                     .into(),
             );
+            if !admit(deadline.as_ref(), &sbom) {
+                progress.tick();
+                continue;
+            }
+            let url = sbom.url.clone();
             visitor
                 .visit_sbom(&context, sbom)
                 .await
                 .map_err(Error::Visitor)?;
+            if let Some(checkpoint) = &self.checkpoint {
+                checkpoint.record(CHECKPOINT_KEY, n, url.as_str());
+            }
             progress.tick();
         }
 
-        Ok(())
+        finish(deadline)
     }
 
     pub async fn walk_parallel<V>(
         self,
         limit: usize,
         visitor: V,
+    ) -> Result<WalkSummary, Error<V::Error, S::Error>>
+    where
+        V: DiscoveredVisitor,
+    {
+        let start = Instant::now();
+        let checkpoint = self.checkpoint.clone();
+        let correlation_id = self.correlation_id.clone();
+        log::info!("Walking, correlation ID: {correlation_id}");
+
+        let result = self.walk_concurrent(limit, visitor).await;
+        finish_checkpoint(checkpoint.as_deref(), &result);
+        result.map(|()| WalkSummary {
+            correlation_id,
+            duration: start.elapsed(),
+        })
+    }
+
+    async fn walk_concurrent<V>(
+        self,
+        limit: usize,
+        visitor: V,
     ) -> Result<(), Error<V::Error, S::Error>>
     where
         V: DiscoveredVisitor,
@@ -97,23 +206,74 @@ impl<S: Source> Walker<S> {
 
         let visitor = Arc::new(visitor);
         let context = Arc::new(context);
+        let deadline = self.deadline.map(Deadline::new);
 
-        stream::iter(self.source.load_index().await.map_err(Error::Source)?)
+        let index = self.source.load_index().await.map_err(Error::Source)?;
+        let skip = resume(self.checkpoint.as_deref(), &index);
+
+        stream::iter(index.into_iter().enumerate().skip(skip))
             .map(Ok)
-            .try_for_each_concurrent(limit, |sbom| {
+            .try_for_each_concurrent(limit, |(n, sbom)| {
                 log::debug!("Discovered advisory: {}", sbom.url);
+                let admitted = admit(deadline.as_ref(), &sbom);
+                let adaptive = self.adaptive.clone();
+                let checkpoint = self.checkpoint.clone();
                 let visitor = visitor.clone();
                 let context = context.clone();
 
                 async move {
+                    if !admitted {
+                        return Ok(());
+                    }
+                    let _permit = match adaptive {
+                        Some(adaptive) => Some(adaptive.acquire().await),
+                        None => None,
+                    };
+                    let url = sbom.url.clone();
                     visitor
                         .visit_sbom(&context, sbom)
                         .map_err(Error::Visitor)
-                        .await
+                        .await?;
+                    if let Some(checkpoint) = checkpoint {
+                        checkpoint.record(CHECKPOINT_KEY, n, url.as_str());
+                    }
+                    Ok(())
                 }
             })
             .await?;
 
-        Ok(())
+        finish(deadline)
+    }
+}
+
+/// Get the number of entries of the index to skip, as they have been processed according to the
+/// checkpoint
+fn resume(checkpoint: Option<&Checkpoint>, index: &[DiscoveredSbom]) -> usize {
+    checkpoint.map_or(0, |checkpoint| {
+        checkpoint.resume(CHECKPOINT_KEY, index.iter().map(|sbom| sbom.url.as_str()))
+    })
+}
+
+/// Check if the SBOM may still be processed, before the deadline passed
+fn admit(deadline: Option<&Deadline>, sbom: &DiscoveredSbom) -> bool {
+    deadline.map_or(true, |deadline| deadline.admit(sbom.modified))
+}
+
+/// Finish a walk, failing if the deadline stopped it
+fn finish<VE, SE>(deadline: Option<Deadline>) -> Result<(), Error<VE, SE>>
+where
+    VE: std::fmt::Display + Debug,
+    SE: std::fmt::Display + Debug,
+{
+    match deadline.and_then(Deadline::into_completion) {
+        Some(completion) => Err(Error::Deadline(completion)),
+        None => Ok(()),
+    }
+}
+
+/// Clear the checkpoint if the walk completed, store it otherwise
+fn finish_checkpoint<T, E>(checkpoint: Option<&Checkpoint>, result: &Result<T, E>) {
+    if let Some(checkpoint) = checkpoint {
+        checkpoint.finish(result.is_ok());
     }
 }
@@ -1,7 +1,140 @@
-use sbom_walker::Sbom;
+use bytes::Bytes;
+use sbom_walker::{
+    discover::DiscoveredSbom,
+    model::sbom::{CycloneDxVersion, Encoding, JsonPayload},
+    retrieve::RetrievedSbom,
+    Sbom,
+};
+use std::time::SystemTime;
+use url::Url;
+use walker_common::retrieve::RetrievalMetadata;
 
 #[test]
 fn test_cyclonedx_v13_json() {
     let _ =
         Sbom::try_cyclonedx_json(include_bytes!("data/cyclonedx.v1_3.json")).expect("must parse");
 }
+
+#[test]
+fn test_cyclonedx_versions_json() {
+    for data in [
+        &include_bytes!("data/cyclonedx.v1_3.json")[..],
+        &include_bytes!("data/cyclonedx.v1_4.json")[..],
+        &include_bytes!("data/cyclonedx.v1_5.json")[..],
+    ] {
+        let sbom = Sbom::try_parse_any(data).expect("must parse");
+        assert!(matches!(sbom, Sbom::CycloneDx(_)));
+    }
+}
+
+#[test]
+fn test_cyclonedx_explicit_version() {
+    let _ = Sbom::try_cyclonedx_json_version(
+        CycloneDxVersion::V1_5,
+        include_bytes!("data/cyclonedx.v1_5.json"),
+    )
+    .expect("must parse");
+
+    // a parsed value uses the parser of the requested version too
+    let json = serde_json::from_slice(include_bytes!("data/cyclonedx.v1_4.json")).unwrap();
+    let _ = Sbom::try_cyclonedx_json_version(CycloneDxVersion::V1_4, JsonPayload::Value(json))
+        .expect("must parse");
+}
+
+#[test]
+fn test_cyclonedx_v16_unsupported() {
+    let data = br#"{"bomFormat": "CycloneDX", "specVersion": "1.6", "version": 1}"#;
+
+    let err = Sbom::try_parse_any(data).expect_err("must not parse");
+    assert_eq!(
+        format!("{:#}", err.0[0].1),
+        "CycloneDX 1.6: Unsupported spec version 1.6"
+    );
+}
+
+#[test]
+fn test_spdx3_json() {
+    let sbom = Sbom::try_parse_any(include_bytes!("data/spdx3.json")).expect("must parse");
+    let Sbom::Spdx3(sbom) = sbom else {
+        panic!("must be detected as SPDX 3, is: {sbom:?}");
+    };
+
+    assert_eq!(sbom.spec_version(), Some("3.0.1"));
+    assert_eq!(sbom.relationships().count(), 1);
+}
+
+#[test]
+fn test_cyclonedx_xml() {
+    let data = include_bytes!("data/cyclonedx.v1_4.xml");
+
+    assert_eq!(
+        Sbom::is_cyclonedx_xml(data).ok(),
+        Some(CycloneDxVersion::V1_4)
+    );
+
+    let sbom = Sbom::try_parse(data, Encoding::from_path("sbom.xml.bz2")).expect("must parse");
+    assert!(matches!(sbom, Sbom::CycloneDx(_)));
+
+    // without a hint, the content must be detected
+    let sbom = Sbom::try_parse_any(data).expect("must parse");
+    assert!(matches!(sbom, Sbom::CycloneDx(_)));
+}
+
+fn retrieved(url: &str, content_type: Option<&str>, data: &'static [u8]) -> RetrievedSbom {
+    RetrievedSbom {
+        discovered: DiscoveredSbom {
+            url: Url::parse(url).expect("example URL must parse"),
+            modified: SystemTime::UNIX_EPOCH,
+            labels: Default::default(),
+        },
+        data: Bytes::from_static(data),
+        signature: None,
+        sha256: None,
+        sha512: None,
+        metadata: RetrievalMetadata {
+            content_type: content_type.map(ToString::to_string),
+            ..Default::default()
+        },
+    }
+}
+
+#[test]
+fn test_encoding_from_content_type() {
+    let data = include_bytes!("data/cyclonedx.v1_4.xml");
+
+    // the content type takes precedence over the file name
+    let sbom = retrieved(
+        "https://example.com/sbom",
+        Some("application/vnd.cyclonedx+xml; charset=utf-8"),
+        data,
+    );
+    assert_eq!(sbom.encoding(), Some(Encoding::Xml));
+    let parsed = Sbom::try_parse(&sbom.data, sbom.encoding()).expect("must parse");
+    assert!(matches!(parsed, Sbom::CycloneDx(_)));
+
+    let sbom = retrieved(
+        "https://example.com/sbom.json",
+        Some("application/xml"),
+        data,
+    );
+    assert_eq!(sbom.encoding(), Some(Encoding::Xml));
+}
+
+#[test]
+fn test_encoding_fallback() {
+    let data = include_bytes!("data/cyclonedx.v1_4.xml");
+
+    // an unspecific content type falls back to the file name
+    let sbom = retrieved(
+        "https://example.com/sbom.xml.bz2",
+        Some("application/octet-stream"),
+        data,
+    );
+    assert_eq!(sbom.encoding(), Some(Encoding::Xml));
+
+    // neither gives a hint, the content gets sniffed
+    let sbom = retrieved("https://example.com/sbom", None, data);
+    assert_eq!(sbom.encoding(), None);
+    let parsed = Sbom::try_parse(&sbom.data, sbom.encoding()).expect("must parse");
+    assert!(matches!(parsed, Sbom::CycloneDx(_)));
+}